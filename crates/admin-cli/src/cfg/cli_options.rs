@@ -21,13 +21,13 @@ use crate::cfg::measurement;
 use crate::{
     bmc_machine, boot_override, component_manager, compute_allocation, credential, devenv, domain,
     dpa, dpu, dpu_remediation, expected_machines, expected_power_shelf, expected_rack,
-    expected_switch, extension_service, firmware, generate_shell_complete, host, ib_partition,
-    instance, instance_type, inventory, ip, ipxe_template, jump, machine, machine_interfaces,
-    machine_validation, managed_host, managed_switch, mlx, network_devices, network_security_group,
-    network_segment, nvl_logical_partition, nvl_partition, operating_system, os_image, ping,
-    power_shelf, rack, rack_firmware, redfish, resource_pool, rms, route_server, scout_stream, set,
-    site_explorer, sku, ssh, switch, tenant, tenant_keyset, tpm_ca, trim_table, version, vpc,
-    vpc_peering, vpc_prefix,
+    expected_switch, extension_service, firmware, generate_shell_complete, health_silence, host,
+    ib_partition, instance, instance_type, inventory, ip, ipxe_template, jump, machine,
+    machine_interfaces, machine_validation, managed_host, managed_switch, mlx, network_devices,
+    network_security_group, network_segment, nvl_logical_partition, nvl_partition,
+    operating_system, os_image, ping, power_shelf, prefix_list, rack, rack_firmware, redfish,
+    resource_pool, rms, route_server, scout_stream, set, site_explorer, sku, ssh, switch, tenant,
+    tenant_keyset, tpm_ca, trim_table, version, vpc, vpc_peering, vpc_prefix,
 };
 
 #[derive(Parser, Debug)]
@@ -278,6 +278,13 @@ pub enum CliCommand {
     )]
     NetworkSecurityGroup(network_security_group::Cmd),
 
+    #[clap(
+        about = "Prefix list management for network security group rules",
+        visible_alias = "pl",
+        subcommand
+    )]
+    PrefixList(prefix_list::Cmd),
+
     #[clap(about = "Manage machine SKUs", subcommand)]
     Sku(sku::Cmd),
 
@@ -326,6 +333,8 @@ pub enum CliCommand {
     Dpa(dpa::Cmd),
     #[clap(about = "Trim DB tables", subcommand)]
     TrimTable(trim_table::Cmd),
+    #[clap(about = "Time-bounded silences of health alerts", subcommand)]
+    HealthSilence(health_silence::Cmd),
     #[clap(about = "Dpu Remediation handling", subcommand)]
     DpuRemediation(dpu_remediation::Cmd),
     #[clap(
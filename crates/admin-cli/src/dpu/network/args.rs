@@ -15,6 +15,8 @@
  * limitations under the License.
  */
 
+use carbide_uuid::machine::MachineId;
+
 #[derive(clap::Subcommand, Debug)]
 #[clap(rename_all = "kebab-case")]
 pub enum Args {
@@ -22,4 +24,21 @@ pub enum Args {
     Status,
     #[clap(about = "Machine network configuration, used by VPC.")]
     Config(crate::machine::NetworkConfigQuery),
+    #[clap(
+        about = "Render the NVUE config of a DPU and diff it against the config it last applied."
+    )]
+    Render(RenderQuery),
+}
+
+#[derive(clap::Parser, Debug)]
+pub struct RenderQuery {
+    #[clap(long, required(true), help = "DPU machine id")]
+    pub machine_id: MachineId,
+    #[clap(
+        long,
+        help = "Render as if the instance on the DPU used this network security group"
+    )]
+    pub network_security_group_id: Option<String>,
+    #[clap(long, help = "Print the full rendered config instead of the diff")]
+    pub full: bool,
 }
@@ -18,10 +18,11 @@
 use std::collections::HashMap;
 
 use ::rpc::admin_cli::{CarbideCliError, CarbideCliResult, OutputFormat};
-use ::rpc::forge::ManagedHostNetworkConfigResponse;
+use ::rpc::forge::{ManagedHostNetworkConfigResponse, RenderDpuNetworkConfigRequest};
 use carbide_uuid::machine::MachineId;
 use prettytable::{Table, format, row};
 
+use super::args::RenderQuery;
 use crate::async_write;
 use crate::machine::network::Args as NetworkCommand;
 use crate::rpc::ApiClient;
@@ -270,3 +271,45 @@ pub async fn show_dpu_status(
     }
     Ok(())
 }
+
+pub async fn render_network_config(
+    api_client: &ApiClient,
+    output_file: &mut Box<dyn tokio::io::AsyncWrite + Unpin>,
+    query: RenderQuery,
+    output_format: OutputFormat,
+) -> CarbideCliResult<()> {
+    if !query.machine_id.machine_type().is_dpu() {
+        return Err(CarbideCliError::GenericError(
+            "Only DPU id is allowed.".to_string(),
+        ));
+    }
+    let rendered = api_client
+        .0
+        .render_dpu_network_config(RenderDpuNetworkConfigRequest {
+            dpu_machine_id: Some(query.machine_id),
+            network_config: None,
+            network_security_group_id: query.network_security_group_id,
+        })
+        .await?;
+    match output_format {
+        OutputFormat::Json => {
+            async_write!(output_file, "{}", serde_json::to_string(&rendered)?)?;
+        }
+        OutputFormat::Yaml => {
+            async_write!(output_file, "{}", serde_yaml::to_string(&rendered)?)?;
+        }
+        _ if query.full => {
+            async_write!(output_file, "{}", rendered.rendered_config)?;
+        }
+        _ if rendered.diff.is_empty() => {
+            async_write!(
+                output_file,
+                "Rendered config is the same as the config the DPU last applied.\n"
+            )?;
+        }
+        _ => {
+            async_write!(output_file, "{}", rendered.diff)?;
+        }
+    }
+    Ok(())
+}
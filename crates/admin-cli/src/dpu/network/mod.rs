@@ -29,6 +29,15 @@ impl Run for Args {
         let cmd = match self {
             Args::Status => crate::machine::network::Args::Status,
             Args::Config(q) => crate::machine::network::Args::Config(q),
+            Args::Render(q) => {
+                return cmd::render_network_config(
+                    &ctx.api_client,
+                    &mut ctx.output_file,
+                    q,
+                    ctx.config.format,
+                )
+                .await;
+            }
         };
         cmd::network(
             &ctx.api_client,
@@ -0,0 +1,91 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::time::Duration;
+
+use clap::{ArgGroup, Parser, ValueEnum};
+
+#[derive(Parser, Debug, Clone)]
+#[clap(group(
+    ArgGroup::new("matcher")
+        .required(true)
+        .multiple(true)
+        .args(&["probe_id", "classification", "target", "object_kind", "object_ids", "labels", "sku", "rack_id"])))]
+pub struct Args {
+    #[clap(long, help = "Why the alerts are silenced")]
+    pub reason: String,
+    #[clap(
+        long,
+        value_parser = duration_str::parse,
+        help = "How long the silence lasts, e.g. '4h', '2d'. At most 30 days"
+    )]
+    pub duration: Duration,
+    #[clap(long, help = "Only silence alerts raised by this probe")]
+    pub probe_id: Option<String>,
+    #[clap(long, help = "Only silence alerts with this classification")]
+    pub classification: Option<String>,
+    #[clap(long, help = "Only silence alerts for this probe target")]
+    pub target: Option<String>,
+    #[clap(long, help = "Only silence alerts of this kind of object")]
+    pub object_kind: Option<ObjectKind>,
+    #[clap(
+        long = "object-id",
+        help = "Only silence alerts of this object. Can be repeated"
+    )]
+    pub object_ids: Vec<String>,
+    #[clap(
+        long = "label",
+        value_parser = parse_label,
+        help = "Only silence alerts of objects carrying this label, as key=value. Can be repeated"
+    )]
+    pub labels: Vec<(String, String)>,
+    #[clap(long, help = "Only silence alerts of machines with this SKU")]
+    pub sku: Option<String>,
+    #[clap(long, help = "Only silence alerts of objects in this rack")]
+    pub rack_id: Option<String>,
+    #[clap(
+        long,
+        help = "The author of the silence, if not authenticated as a user"
+    )]
+    pub author: Option<String>,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectKind {
+    Machine,
+    Rack,
+    Switch,
+    PowerShelf,
+}
+
+impl From<ObjectKind> for ::rpc::forge::HealthSilenceObjectKind {
+    fn from(kind: ObjectKind) -> Self {
+        use ::rpc::forge::HealthSilenceObjectKind;
+        match kind {
+            ObjectKind::Machine => HealthSilenceObjectKind::Machine,
+            ObjectKind::Rack => HealthSilenceObjectKind::Rack,
+            ObjectKind::Switch => HealthSilenceObjectKind::Switch,
+            ObjectKind::PowerShelf => HealthSilenceObjectKind::PowerShelf,
+        }
+    }
+}
+
+fn parse_label(s: &str) -> Result<(String, String), String> {
+    s.split_once('=')
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .ok_or_else(|| format!("label '{s}' is not in key=value format"))
+}
@@ -0,0 +1,68 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::{CarbideCliResult, OutputFormat};
+use ::rpc::forge as rpc;
+
+use super::args::Args;
+use crate::async_writeln;
+use crate::rpc::ApiClient;
+
+pub async fn create(
+    args: Args,
+    output_format: OutputFormat,
+    output_file: &mut Box<dyn tokio::io::AsyncWrite + Unpin>,
+    api_client: &ApiClient,
+) -> CarbideCliResult<()> {
+    let request = rpc::CreateHealthSilenceRequest {
+        matcher: Some(rpc::HealthSilenceMatcher {
+            probe_id: args.probe_id,
+            classification: args.classification,
+            target: args.target,
+            object_kind: args
+                .object_kind
+                .map(rpc::HealthSilenceObjectKind::from)
+                .unwrap_or(rpc::HealthSilenceObjectKind::Any)
+                .into(),
+            object_ids: args.object_ids,
+            labels: args.labels.into_iter().collect(),
+            sku: args.sku,
+            rack_id: args.rack_id,
+        }),
+        reason: args.reason,
+        author: args.author,
+        starts_at: None,
+        expires_at: None,
+        duration: Some(args.duration.into()),
+    };
+    let silence = api_client.0.create_health_silence(request).await?;
+
+    match output_format {
+        OutputFormat::Json => {
+            async_writeln!(output_file, "{}", serde_json::to_string_pretty(&silence)?)?;
+        }
+        _ => {
+            async_writeln!(
+                output_file,
+                "Created health silence {}, expiring at {}",
+                silence.id,
+                silence.expires_at.unwrap_or_default()
+            )?;
+        }
+    }
+    Ok(())
+}
@@ -15,15 +15,17 @@
  * limitations under the License.
  */
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub enum TrimTableTarget {
-    MeasuredBoot,
-}
+pub mod args;
+pub mod cmd;
+
+use ::rpc::admin_cli::CarbideCliResult;
+pub use args::Args;
+
+use crate::cfg::run::Run;
+use crate::cfg::runtime::RuntimeContext;
 
-impl From<rpc::forge::TrimTableTarget> for TrimTableTarget {
-    fn from(target: rpc::forge::TrimTableTarget) -> Self {
-        match target {
-            rpc::forge::TrimTableTarget::MeasuredBoot => TrimTableTarget::MeasuredBoot,
-        }
+impl Run for Args {
+    async fn run(self, ctx: &mut RuntimeContext) -> CarbideCliResult<()> {
+        cmd::expire(self, &ctx.api_client).await
     }
 }
@@ -0,0 +1,114 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::{CarbideCliResult, OutputFormat};
+use ::rpc::forge as rpc;
+use prettytable::{Table, row};
+
+use super::args::Args;
+use crate::rpc::ApiClient;
+use crate::{async_write, async_writeln};
+
+pub async fn list(
+    args: Args,
+    output_format: OutputFormat,
+    output_file: &mut Box<dyn tokio::io::AsyncWrite + Unpin>,
+    api_client: &ApiClient,
+) -> CarbideCliResult<()> {
+    let silences = api_client
+        .0
+        .list_health_silences(rpc::ListHealthSilencesRequest {
+            include_ended: args.all,
+        })
+        .await?;
+
+    match output_format {
+        OutputFormat::Json => {
+            async_writeln!(output_file, "{}", serde_json::to_string_pretty(&silences)?)?;
+        }
+        _ => {
+            let table = convert_silences_to_nice_table(silences);
+            async_write!(output_file, "{}", table)?;
+        }
+    }
+    Ok(())
+}
+
+/// Renders the criteria of a matcher as `key=value` pairs
+pub(crate) fn fmt_matcher(matcher: Option<&rpc::HealthSilenceMatcher>) -> String {
+    let Some(matcher) = matcher else {
+        return String::new();
+    };
+    let mut criteria = Vec::new();
+    if let Some(probe_id) = &matcher.probe_id {
+        criteria.push(format!("probe_id={probe_id}"));
+    }
+    if let Some(classification) = &matcher.classification {
+        criteria.push(format!("classification={classification}"));
+    }
+    if let Some(target) = &matcher.target {
+        criteria.push(format!("target={target}"));
+    }
+    if matcher.object_kind() != rpc::HealthSilenceObjectKind::Any {
+        criteria.push(format!(
+            "object_kind={}",
+            matcher.object_kind().as_str_name()
+        ));
+    }
+    for id in &matcher.object_ids {
+        criteria.push(format!("object_id={id}"));
+    }
+    let mut labels: Vec<_> = matcher.labels.iter().collect();
+    labels.sort();
+    for (key, value) in labels {
+        criteria.push(format!("label:{key}={value}"));
+    }
+    if let Some(sku) = &matcher.sku {
+        criteria.push(format!("sku={sku}"));
+    }
+    if let Some(rack_id) = &matcher.rack_id {
+        criteria.push(format!("rack_id={rack_id}"));
+    }
+    criteria.join(", ")
+}
+
+fn convert_silences_to_nice_table(silences: rpc::HealthSilenceList) -> Box<Table> {
+    let mut table = Box::new(Table::new());
+
+    table.set_titles(row![
+        "Id", "State", "Matcher", "Author", "Reason", "Starts", "Expires", "Ended", "Ended By",
+    ]);
+
+    for silence in silences.silences {
+        table.add_row(row![
+            silence.id,
+            silence.state().as_str_name(),
+            fmt_matcher(silence.matcher.as_ref()),
+            silence.author,
+            silence.reason,
+            silence.starts_at.unwrap_or_default(),
+            silence.expires_at.unwrap_or_default(),
+            silence
+                .ended_at
+                .map(|ended_at| ended_at.to_string())
+                .unwrap_or_default(),
+            silence.ended_by.unwrap_or_default(),
+        ]);
+    }
+
+    table
+}
@@ -0,0 +1,158 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// The intent of the tests.rs file is to test the integrity of the
+// command, including things like basic structure parsing, enum
+// translations, and any external input validators that are
+// configured. Specific "categories" are:
+//
+// Command Structure - Baseline debug_assert() of the entire command.
+// Argument Parsing  - Ensure required/optional arg combinations parse correctly.
+
+use std::time::Duration;
+
+use clap::{CommandFactory, Parser};
+
+use super::*;
+
+// verify_cmd_structure runs a baseline clap debug_assert()
+// to do basic command configuration checking and validation,
+// ensuring things like unique argument definitions, group
+// configurations, argument references, etc. Things that would
+// otherwise be missed until runtime.
+#[test]
+fn verify_cmd_structure() {
+    Cmd::command().debug_assert();
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// Argument Parsing
+//
+// This section contains tests specific to argument parsing,
+// including testing required arguments, as well as optional
+// flag-specific checking.
+
+// parse_create ensures create parses a matcher, duration and reason.
+#[test]
+fn parse_create() {
+    let cmd = Cmd::try_parse_from([
+        "health-silence",
+        "create",
+        "--reason",
+        "PSU RMA",
+        "--duration",
+        "4h",
+        "--probe-id",
+        "BmcSensor",
+        "--object-kind",
+        "power-shelf",
+        "--label",
+        "pool=training",
+        "--object-id",
+        "ps-1",
+        "--object-id",
+        "ps-2",
+    ])
+    .expect("should parse create");
+
+    match cmd {
+        Cmd::Create(args) => {
+            assert_eq!(args.reason, "PSU RMA");
+            assert_eq!(args.duration, Duration::from_secs(4 * 60 * 60));
+            assert_eq!(args.probe_id.as_deref(), Some("BmcSensor"));
+            assert_eq!(args.object_kind, Some(create::args::ObjectKind::PowerShelf));
+            assert_eq!(
+                args.labels,
+                vec![("pool".to_string(), "training".to_string())]
+            );
+            assert_eq!(args.object_ids, vec!["ps-1", "ps-2"]);
+        }
+        _ => panic!("expected Create variant"),
+    }
+}
+
+// parse_create_requires_matcher ensures create fails without
+// any matcher criterion.
+#[test]
+fn parse_create_requires_matcher() {
+    let result = Cmd::try_parse_from([
+        "health-silence",
+        "create",
+        "--reason",
+        "maintenance",
+        "--duration",
+        "1h",
+    ]);
+    assert!(result.is_err(), "should fail without a matcher");
+}
+
+// parse_create_requires_duration ensures create fails without
+// an expiry.
+#[test]
+fn parse_create_requires_duration() {
+    let result = Cmd::try_parse_from([
+        "health-silence",
+        "create",
+        "--reason",
+        "maintenance",
+        "--sku",
+        "sku-a",
+    ]);
+    assert!(result.is_err(), "should fail without duration");
+}
+
+// parse_create_invalid_label ensures create rejects labels
+// which are not in key=value format.
+#[test]
+fn parse_create_invalid_label() {
+    let result = Cmd::try_parse_from([
+        "health-silence",
+        "create",
+        "--reason",
+        "maintenance",
+        "--duration",
+        "1h",
+        "--label",
+        "pool",
+    ]);
+    assert!(result.is_err(), "should fail with invalid label");
+}
+
+// parse_list_all ensures list parses --all.
+#[test]
+fn parse_list_all() {
+    let cmd = Cmd::try_parse_from(["health-silence", "list", "--all"]).expect("should parse list");
+
+    match cmd {
+        Cmd::List(args) => assert!(args.all),
+        _ => panic!("expected List variant"),
+    }
+}
+
+// parse_expire ensures expire parses the silence ID.
+#[test]
+fn parse_expire() {
+    let cmd = Cmd::try_parse_from(["health-silence", "expire", "42"]).expect("should parse expire");
+
+    match cmd {
+        Cmd::Expire(args) => {
+            assert_eq!(args.id, 42);
+            assert_eq!(args.ended_by, None);
+        }
+        _ => panic!("expected Expire variant"),
+    }
+}
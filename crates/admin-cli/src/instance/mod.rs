@@ -19,6 +19,7 @@ mod allocate;
 pub(crate) mod common;
 mod reboot;
 mod release;
+mod sanitization_report;
 mod show;
 mod update_ib_config;
 mod update_nvlink_config;
@@ -53,4 +54,6 @@ pub enum Cmd {
     UpdateIbConfig(update_ib_config::Args),
     #[clap(about = "Update instance NVLink configuration")]
     UpdateNvLinkConfig(update_nvlink_config::Args),
+    #[clap(about = "Show how the storage of a released instance was sanitized")]
+    SanitizationReport(sanitization_report::Args),
 }
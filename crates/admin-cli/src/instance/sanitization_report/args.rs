@@ -0,0 +1,47 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::forge::MediaSanitizationReportRequest;
+use carbide_uuid::instance::InstanceId;
+use carbide_uuid::machine::MachineId;
+use clap::{ArgGroup, Parser};
+
+#[derive(Parser, Debug)]
+#[clap(group(ArgGroup::new("target").required(true).args(&["instance", "machine"])))]
+pub struct Args {
+    #[clap(short, long, help = "The released instance to show the report of")]
+    pub instance: Option<InstanceId>,
+
+    #[clap(short, long, help = "Show the latest report of this machine")]
+    pub machine: Option<MachineId>,
+
+    #[clap(
+        long,
+        action,
+        help = "Only print the report signed with the tenant's signing key"
+    )]
+    pub signed: bool,
+}
+
+impl From<&Args> for MediaSanitizationReportRequest {
+    fn from(args: &Args) -> Self {
+        MediaSanitizationReportRequest {
+            instance_id: args.instance,
+            machine_id: args.machine,
+        }
+    }
+}
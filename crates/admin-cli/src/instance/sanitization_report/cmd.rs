@@ -0,0 +1,162 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::{CarbideCliError, CarbideCliResult, OutputFormat};
+use ::rpc::forge as forgerpc;
+use prettytable::{Table, row};
+
+use super::args::Args;
+use crate::rpc::ApiClient;
+
+pub async fn handle_sanitization_report(
+    args: Args,
+    output_format: OutputFormat,
+    api_client: &ApiClient,
+) -> CarbideCliResult<()> {
+    let report = api_client
+        .0
+        .get_media_sanitization_report(forgerpc::MediaSanitizationReportRequest::from(&args))
+        .await?;
+
+    if args.signed {
+        if report.signed_report.is_empty() {
+            return Err(CarbideCliError::GenericError(
+                "The report is not signed, the tenant has no machine identity signing key"
+                    .to_string(),
+            ));
+        }
+        println!("{}", report.signed_report);
+        return Ok(());
+    }
+
+    match output_format {
+        OutputFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&report).map_err(CarbideCliError::JsonError)?
+        ),
+        OutputFormat::Yaml => println!(
+            "{}",
+            serde_yaml::to_string(&report).map_err(CarbideCliError::YamlError)?
+        ),
+        OutputFormat::Csv => {
+            convert_records_to_table(&report.records)
+                .to_csv(std::io::stdout())
+                .map_err(CarbideCliError::CsvError)?
+                .flush()?;
+        }
+        _ => {
+            let mut summary = Table::new();
+            summary.add_row(row![
+                "Machine ID",
+                report
+                    .machine_id
+                    .map(|id| id.to_string())
+                    .unwrap_or_default()
+            ]);
+            summary.add_row(row![
+                "Instance ID",
+                report
+                    .instance_id
+                    .map(|id| id.to_string())
+                    .unwrap_or_default()
+            ]);
+            summary.add_row(row![
+                "Tenant Organization ID",
+                report.tenant_organization_id
+            ]);
+            summary.add_row(row![
+                "Released",
+                report
+                    .released_at
+                    .map(|t| t.to_string())
+                    .unwrap_or_default()
+            ]);
+            summary.add_row(row![
+                "Completed",
+                report
+                    .completed_at
+                    .map(|t| t.to_string())
+                    .unwrap_or_else(|| "Pending".to_string())
+            ]);
+            summary.add_row(row![
+                "Signed",
+                if report.signed_report.is_empty() {
+                    "No".to_string()
+                } else {
+                    format!("Yes (key {})", report.key_id)
+                }
+            ]);
+            summary.printstd();
+            convert_records_to_table(&report.records).printstd();
+        }
+    }
+
+    Ok(())
+}
+
+fn convert_records_to_table(records: &[forgerpc::MediaSanitizationRecord]) -> Table {
+    let mut table = Table::new();
+    table.set_titles(row![
+        "Device",
+        "Transport",
+        "Serial",
+        "Model",
+        "Method",
+        "NIST Category",
+        "RAID Arrays",
+        "Result",
+        "Verification",
+        "Started",
+        "Completed",
+    ]);
+
+    for record in records {
+        let result = match record.result() {
+            forgerpc::machine_cleanup_info::CleanupResult::Ok => "OK".to_string(),
+            forgerpc::machine_cleanup_info::CleanupResult::Error => {
+                format!("Error: {}", record.message)
+            }
+        };
+        let verification = match &record.verification {
+            Some(v) => format!(
+                "{} ({}/{} sectors unchanged)",
+                if v.passed { "Passed" } else { "Failed" },
+                v.sectors_unchanged,
+                v.sectors_sampled
+            ),
+            None => "Not performed".to_string(),
+        };
+        table.add_row(row![
+            record.device,
+            record.transport,
+            record.serial_number,
+            record.model,
+            record.method,
+            record.nist_category,
+            record.raid_arrays.join(", "),
+            result,
+            verification,
+            record.started_at.map(|t| t.to_string()).unwrap_or_default(),
+            record
+                .completed_at
+                .map(|t| t.to_string())
+                .unwrap_or_default(),
+        ]);
+    }
+
+    table
+}
@@ -234,3 +234,59 @@ fn parse_allocate_missing_required_fails() {
         "should fail without subnet/vpc_prefix and prefix-name"
     );
 }
+
+// parse_sanitization_report_instance ensures sanitization-report
+// parses with an instance ID.
+#[test]
+fn parse_sanitization_report_instance() {
+    let cmd = Cmd::try_parse_from([
+        "instance",
+        "sanitization-report",
+        "--instance",
+        TEST_INSTANCE_ID,
+        "--signed",
+    ])
+    .expect("should parse sanitization-report");
+
+    match cmd {
+        Cmd::SanitizationReport(args) => {
+            assert_eq!(
+                args.instance.map(|id| id.to_string()),
+                Some(TEST_INSTANCE_ID.to_string())
+            );
+            assert!(args.machine.is_none());
+            assert!(args.signed);
+        }
+        _ => panic!("expected SanitizationReport variant"),
+    }
+}
+
+// parse_sanitization_report_machine ensures sanitization-report
+// parses with a machine ID.
+#[test]
+fn parse_sanitization_report_machine() {
+    let cmd = Cmd::try_parse_from([
+        "instance",
+        "sanitization-report",
+        "--machine",
+        TEST_MACHINE_ID,
+    ])
+    .expect("should parse sanitization-report");
+
+    match cmd {
+        Cmd::SanitizationReport(args) => {
+            assert!(args.instance.is_none());
+            assert!(args.machine.is_some());
+            assert!(!args.signed);
+        }
+        _ => panic!("expected SanitizationReport variant"),
+    }
+}
+
+// parse_sanitization_report_missing_required_fails ensures
+// sanitization-report fails without an instance or machine.
+#[test]
+fn parse_sanitization_report_missing_required_fails() {
+    let result = Cmd::try_parse_from(["instance", "sanitization-report"]);
+    assert!(result.is_err(), "should fail without instance/machine");
+}
@@ -57,6 +57,7 @@ mod expected_switch;
 mod extension_service;
 mod firmware;
 mod generate_shell_complete;
+mod health_silence;
 mod health_utils;
 mod host;
 mod ib_partition;
@@ -83,6 +84,7 @@ mod operating_system;
 mod os_image;
 mod ping;
 mod power_shelf;
+mod prefix_list;
 mod rack;
 mod rack_firmware;
 mod redfish;
@@ -209,6 +211,7 @@ async fn main() -> color_eyre::Result<()> {
         CliCommand::Credential(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::ComponentManager(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::ComputeAllocation(cmd) => cmd.dispatch(ctx).await?,
+        CliCommand::PrefixList(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::DevEnv(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::Domain(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::Dpa(cmd) => cmd.dispatch(ctx).await?,
@@ -225,6 +228,7 @@ async fn main() -> color_eyre::Result<()> {
         CliCommand::IbPartition(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::Instance(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::InstanceType(cmd) => cmd.dispatch(ctx).await?,
+        CliCommand::HealthSilence(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::Inventory(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::Ip(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::Jump(cmd) => cmd.dispatch(ctx).await?,
@@ -0,0 +1,47 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use carbide_uuid::machine::MachineId;
+use chrono::{DateTime, Utc};
+use clap::Parser;
+use rpc::forge as forgerpc;
+
+#[derive(Parser, Debug)]
+pub struct Args {
+    #[clap(help = "ID of the host machine, or of one of its DPUs")]
+    pub machine: MachineId,
+    #[clap(
+        long,
+        help = "Time to reconstruct the managed host at, in RFC 3339 format (e.g. 2026-05-14T03:12:00Z)"
+    )]
+    pub timestamp: DateTime<Utc>,
+    #[clap(
+        long,
+        help = "Also reconstruct the managed host at this earlier time and show what changed since"
+    )]
+    pub diff_from: Option<DateTime<Utc>>,
+}
+
+impl From<Args> for forgerpc::GetManagedHostAtRequest {
+    fn from(args: Args) -> Self {
+        Self {
+            machine_id: Some(args.machine),
+            timestamp: Some(args.timestamp.into()),
+            diff_from: args.diff_from.map(Into::into),
+        }
+    }
+}
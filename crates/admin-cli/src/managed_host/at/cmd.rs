@@ -0,0 +1,168 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::{CarbideCliResult, OutputFormat};
+use ::rpc::forge::{GetManagedHostAtResponse, MachineStateAt, ManagedHostStateAt};
+use prettytable::{Table, format, row};
+
+use super::args::Args;
+use crate::async_write;
+use crate::rpc::ApiClient;
+
+pub async fn show_at(
+    api_client: &ApiClient,
+    output_file: &mut Box<dyn tokio::io::AsyncWrite + Unpin>,
+    args: Args,
+    output_format: OutputFormat,
+) -> CarbideCliResult<()> {
+    let response = api_client.0.get_managed_host_at(args).await?;
+    match output_format {
+        OutputFormat::Json => {
+            async_write!(output_file, "{}", serde_json::to_string_pretty(&response)?)?;
+        }
+        OutputFormat::Yaml => {
+            async_write!(output_file, "{}", serde_yaml::to_string(&response)?)?;
+        }
+        _ => {
+            async_write!(output_file, "{}", convert_to_table(&response))?;
+        }
+    }
+    Ok(())
+}
+
+fn convert_to_table(response: &GetManagedHostAtResponse) -> String {
+    let mut output = String::new();
+    if let Some(snapshot) = &response.snapshot {
+        output.push_str(&snapshot_table(snapshot).to_string());
+    }
+
+    if response.diff_from_snapshot.is_some() {
+        if response.changes.is_empty() {
+            output.push_str("\nNo changes\n");
+        } else {
+            let mut table = Table::new();
+            table.set_format(*format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+            table.set_titles(row!["Field", "Before", "After"]);
+            for change in response.changes.iter() {
+                table.add_row(row![
+                    change.field,
+                    change.before.as_deref().unwrap_or("-"),
+                    change.after.as_deref().unwrap_or("-"),
+                ]);
+            }
+            output.push_str("\nChanges:\n");
+            output.push_str(&table.to_string());
+        }
+    }
+    output
+}
+
+fn snapshot_table(snapshot: &ManagedHostStateAt) -> Table {
+    let mut table = Table::new();
+    table.set_format(*format::consts::FORMAT_NO_LINESEP);
+    table.add_row(row![
+        "Time",
+        snapshot
+            .time
+            .as_ref()
+            .map(|time| time.to_string())
+            .unwrap_or_default()
+    ]);
+    if let Some(host) = &snapshot.host {
+        add_machine_rows(&mut table, "Host", host);
+    }
+    for dpu in snapshot.dpus.iter() {
+        add_machine_rows(&mut table, "DPU", dpu);
+    }
+    match &snapshot.instance {
+        Some(instance) => {
+            table.add_row(row![
+                "Instance",
+                instance
+                    .instance_id
+                    .map(|id| id.to_string())
+                    .unwrap_or_default()
+            ]);
+            table.add_row(row!["  Config version", instance.config_version]);
+            table.add_row(row![
+                "  Network config version",
+                instance.network_config_version
+            ]);
+            if let Some(deleted) = &instance.deleted {
+                table.add_row(row!["  Deletion requested", deleted]);
+            }
+        }
+        None => {
+            table.add_row(row!["Instance", "None"]);
+        }
+    }
+    table
+}
+
+fn add_machine_rows(table: &mut Table, kind: &str, machine: &MachineStateAt) {
+    table.add_row(row![
+        kind,
+        machine
+            .machine_id
+            .map(|id| id.to_string())
+            .unwrap_or_default()
+    ]);
+    table.add_row(row![
+        "  State",
+        machine
+            .controller_state
+            .as_ref()
+            .map(|state| state.event.as_str())
+            .unwrap_or("Unknown")
+    ]);
+    let alerts = machine
+        .health
+        .as_ref()
+        .and_then(|health| health.health.as_ref())
+        .map(|health| {
+            health
+                .alerts
+                .iter()
+                .map(|alert| match &alert.target {
+                    Some(target) => format!("{} [{target}]", alert.id),
+                    None => alert.id.clone(),
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        });
+    table.add_row(row![
+        "  Health alerts",
+        alerts.unwrap_or_else(|| "Unknown".to_string())
+    ]);
+    table.add_row(row![
+        "  Network config version",
+        machine
+            .network_config_version
+            .as_ref()
+            .map(|record| record.version.as_str())
+            .unwrap_or("Unknown")
+    ]);
+    for interface in machine.interfaces.iter() {
+        table.add_row(row![
+            format!(
+                "  Interface {}",
+                interface.mac_address.as_deref().unwrap_or_default()
+            ),
+            interface.addresses.join(", ")
+        ]);
+    }
+}
@@ -15,6 +15,7 @@
  * limitations under the License.
  */
 
+mod at;
 mod debug_bundle;
 mod maintenance;
 mod power_options;
@@ -61,4 +62,8 @@ pub enum Cmd {
     SetPrimaryDpu(set_primary_dpu::Args),
     #[clap(about = "Download debug bundle with logs for a specific host")]
     DebugBundle(debug_bundle::Args),
+    #[clap(
+        about = "Reconstruct a managed host at a point in time from history, optionally diffing it against an earlier time"
+    )]
+    At(at::Args),
 }
@@ -289,6 +289,41 @@ fn parse_debug_bundle() {
     }
 }
 
+// parse_at ensures at parses with a timestamp and an
+// optional diff_from timestamp.
+#[test]
+fn parse_at() {
+    let cmd = Cmd::try_parse_from([
+        "managed-host",
+        "at",
+        TEST_MACHINE_ID,
+        "--timestamp",
+        "2026-05-14T03:12:00Z",
+        "--diff-from",
+        "2026-05-13T03:12:00Z",
+    ])
+    .expect("should parse at");
+
+    match cmd {
+        Cmd::At(args) => {
+            assert_eq!(args.timestamp.to_rfc3339(), "2026-05-14T03:12:00+00:00");
+            assert_eq!(
+                args.diff_from.map(|diff_from| diff_from.to_rfc3339()),
+                Some("2026-05-13T03:12:00+00:00".to_string())
+            );
+        }
+        _ => panic!("expected At variant"),
+    }
+}
+
+// parse_at_missing_timestamp_fails ensures at fails
+// without --timestamp.
+#[test]
+fn parse_at_missing_timestamp_fails() {
+    let result = Cmd::try_parse_from(["managed-host", "at", TEST_MACHINE_ID]);
+    assert!(result.is_err(), "should fail without --timestamp");
+}
+
 // parse_maintenance_on_missing_required_fails ensures
 // maintenance on fails without required args.
 #[test]
@@ -0,0 +1,49 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::forge::{self as forgerpc};
+use clap::Parser;
+
+use crate::network_security_group::common::{ConnectivityProtocolArg, parse_connectivity_endpoint};
+
+#[derive(Parser, Debug, Clone)]
+pub struct Args {
+    #[clap(
+        short = 's',
+        long,
+        value_parser = parse_connectivity_endpoint,
+        help = "Source: an IP address, an instance ID, or INSTANCE_ID/SEGMENT_ID for a specific interface"
+    )]
+    pub source: forgerpc::ConnectivityEndpoint,
+
+    #[clap(
+        short = 'd',
+        long,
+        value_parser = parse_connectivity_endpoint,
+        help = "Destination: an IP address, an instance ID, or INSTANCE_ID/SEGMENT_ID for a specific interface"
+    )]
+    pub destination: forgerpc::ConnectivityEndpoint,
+
+    #[clap(short = 'p', long, help = "Protocol of the traffic")]
+    pub protocol: ConnectivityProtocolArg,
+
+    #[clap(long, help = "Optional, source port of the traffic")]
+    pub src_port: Option<u16>,
+
+    #[clap(long, help = "Optional, destination port of the traffic")]
+    pub dst_port: Option<u16>,
+}
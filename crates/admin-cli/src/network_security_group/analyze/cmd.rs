@@ -0,0 +1,88 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::{CarbideCliError, CarbideCliResult, OutputFormat};
+use ::rpc::forge::{self as forgerpc};
+use prettytable::{Table, row};
+
+use super::args::Args;
+use crate::network_security_group::common::describe_connectivity_rule;
+use crate::rpc::ApiClient;
+
+/// Evaluates whether traffic from the source to the destination
+/// would be allowed, and shows the stage and rule responsible.
+pub async fn analyze(
+    args: Args,
+    output_format: OutputFormat,
+    api_client: &ApiClient,
+) -> CarbideCliResult<()> {
+    let flow = forgerpc::ConnectivityFlow {
+        protocol: forgerpc::NetworkSecurityGroupRuleProtocol::from(args.protocol).into(),
+        src_port: args.src_port.map(u32::from),
+        dst_port: args.dst_port.map(u32::from),
+    };
+    let analysis = api_client
+        .0
+        .analyze_connectivity(forgerpc::AnalyzeConnectivityRequest {
+            source: Some(args.source),
+            destination: Some(args.destination),
+            flow: Some(flow),
+        })
+        .await?;
+
+    if output_format == OutputFormat::Json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&analysis).map_err(CarbideCliError::JsonError)?
+        );
+        return Ok(());
+    }
+
+    let describe_endpoint = |endpoint: Option<&forgerpc::ResolvedConnectivityEndpoint>| {
+        endpoint
+            .map(|endpoint| match &endpoint.instance_id {
+                Some(instance_id) => format!("{} (instance {instance_id})", endpoint.ip_address),
+                None => endpoint.ip_address.clone(),
+            })
+            .unwrap_or_default()
+    };
+    println!(
+        "{} -> {}: {}",
+        describe_endpoint(analysis.source.as_ref()),
+        describe_endpoint(analysis.destination.as_ref()),
+        if analysis.allowed {
+            "ALLOWED"
+        } else {
+            "DENIED"
+        }
+    );
+
+    let mut table = Box::new(Table::new());
+    table.set_titles(row!["Stage", "Allowed", "Reason", "Rule", "NSG"]);
+    for step in &analysis.steps {
+        table.add_row(row![
+            step.stage().as_str_name(),
+            step.allowed,
+            step.reason,
+            describe_connectivity_rule(step),
+            step.network_security_group_id.clone().unwrap_or_default(),
+        ]);
+    }
+    table.printstd();
+
+    Ok(())
+}
@@ -15,10 +15,79 @@
  * limitations under the License.
  */
 
+use std::net::IpAddr;
+
 use ::rpc::admin_cli::{CarbideCliError, CarbideCliResult};
 use ::rpc::forge::{self as forgerpc};
+use carbide_uuid::instance::InstanceId;
+use carbide_uuid::network::NetworkSegmentId;
+use clap::ValueEnum;
 use prettytable::{Table, row};
 
+#[derive(Copy, Clone, Debug, PartialEq, ValueEnum)]
+#[clap(rename_all = "kebab_case")]
+pub enum ConnectivityProtocolArg {
+    Tcp,
+    Udp,
+    Icmp,
+    Icmp6,
+}
+
+impl From<ConnectivityProtocolArg> for forgerpc::NetworkSecurityGroupRuleProtocol {
+    fn from(protocol: ConnectivityProtocolArg) -> Self {
+        match protocol {
+            ConnectivityProtocolArg::Tcp => Self::NsgRuleProtoTcp,
+            ConnectivityProtocolArg::Udp => Self::NsgRuleProtoUdp,
+            ConnectivityProtocolArg::Icmp => Self::NsgRuleProtoIcmp,
+            ConnectivityProtocolArg::Icmp6 => Self::NsgRuleProtoIcmp6,
+        }
+    }
+}
+
+/// Parses a connectivity endpoint: an IP address, an instance ID
+/// (the first interface of the instance), or `INSTANCE_ID/SEGMENT_ID`
+/// for a specific interface.
+pub fn parse_connectivity_endpoint(s: &str) -> Result<forgerpc::ConnectivityEndpoint, String> {
+    use forgerpc::connectivity_endpoint::Endpoint;
+
+    let endpoint = if let Ok(address) = s.parse::<IpAddr>() {
+        Endpoint::IpAddress(address.to_string())
+    } else if let Some((instance_id, segment_id)) = s.split_once('/') {
+        Endpoint::Interface(forgerpc::ConnectivityInstanceInterface {
+            instance_id: Some(
+                instance_id
+                    .parse::<InstanceId>()
+                    .map_err(|e| format!("invalid instance ID '{instance_id}': {e}"))?,
+            ),
+            network_segment_id: Some(
+                segment_id
+                    .parse::<NetworkSegmentId>()
+                    .map_err(|e| format!("invalid network segment ID '{segment_id}': {e}"))?,
+            ),
+        })
+    } else {
+        Endpoint::InstanceId(s.parse::<InstanceId>().map_err(|e| {
+            format!("expected an IP address, INSTANCE_ID or INSTANCE_ID/SEGMENT_ID, got '{s}': {e}")
+        })?)
+    };
+    Ok(forgerpc::ConnectivityEndpoint {
+        endpoint: Some(endpoint),
+    })
+}
+
+/// Short description of the rule that decided a connectivity step, if any.
+pub fn describe_connectivity_rule(step: &forgerpc::ConnectivityStep) -> String {
+    let Some(rule) = step.rule.as_ref() else {
+        return String::new();
+    };
+    let id = rule.id.clone().unwrap_or_default();
+    if step.site_policy_override {
+        format!("{id} (site policy)")
+    } else {
+        id
+    }
+}
+
 /// Produces a table for printing a non-JSON representation of a
 /// network security group to standard out.
 ///
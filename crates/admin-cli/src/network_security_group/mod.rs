@@ -15,11 +15,13 @@
  * limitations under the License.
  */
 
+mod analyze;
 mod attach;
 mod common;
 mod create;
 mod delete;
 mod detach;
+mod reachability;
 mod show;
 mod show_attachments;
 mod update;
@@ -66,4 +68,16 @@ pub enum Cmd {
         visible_alias = "r"
     )]
     Detach(detach::Args),
+
+    #[clap(
+        about = "Analyze whether traffic between two endpoints is allowed by routing and network security groups",
+        visible_alias = "z"
+    )]
+    Analyze(analyze::Args),
+
+    #[clap(
+        about = "Show which instances of a VPC can reach each other",
+        visible_alias = "m"
+    )]
+    Reachability(reachability::Args),
 }
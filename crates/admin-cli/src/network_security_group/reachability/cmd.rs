@@ -0,0 +1,100 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::{CarbideCliError, CarbideCliResult, OutputFormat};
+use ::rpc::forge::{self as forgerpc};
+use prettytable::{Table, row};
+
+use super::args::Args;
+use crate::network_security_group::common::describe_connectivity_rule;
+use crate::rpc::ApiClient;
+
+/// Shows which instance addresses of a VPC can reach each other.
+pub async fn reachability(
+    args: Args,
+    output_format: OutputFormat,
+    api_client: &ApiClient,
+) -> CarbideCliResult<()> {
+    let flow = forgerpc::ConnectivityFlow {
+        protocol: forgerpc::NetworkSecurityGroupRuleProtocol::from(args.protocol).into(),
+        src_port: args.src_port.map(u32::from),
+        dst_port: args.dst_port.map(u32::from),
+    };
+    let matrix = api_client
+        .0
+        .get_vpc_reachability_matrix(forgerpc::GetVpcReachabilityMatrixRequest {
+            vpc_id: Some(args.vpc_id),
+            flow: Some(flow),
+            include_peered_vpcs: args.include_peered_vpcs,
+        })
+        .await?;
+
+    if output_format == OutputFormat::Json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&matrix).map_err(CarbideCliError::JsonError)?
+        );
+        return Ok(());
+    }
+
+    if matrix.entries.is_empty() {
+        println!(
+            "VPC {} has no pairs of instance addresses to analyze",
+            args.vpc_id
+        );
+        return Ok(());
+    }
+
+    let describe_endpoint = |index: u32| {
+        matrix
+            .endpoints
+            .get(index as usize)
+            .map(|endpoint| match &endpoint.instance_id {
+                Some(instance_id) => format!("{} ({instance_id})", endpoint.ip_address),
+                None => endpoint.ip_address.clone(),
+            })
+            .unwrap_or_default()
+    };
+
+    let mut table = Box::new(Table::new());
+    table.set_titles(row![
+        "Source",
+        "Destination",
+        "Allowed",
+        "Denied By",
+        "Rule"
+    ]);
+    for entry in &matrix.entries {
+        let (denied_by, rule) = match &entry.denied_by {
+            Some(step) => (
+                format!("{}: {}", step.stage().as_str_name(), step.reason),
+                describe_connectivity_rule(step),
+            ),
+            None => (String::new(), String::new()),
+        };
+        table.add_row(row![
+            describe_endpoint(entry.source),
+            describe_endpoint(entry.destination),
+            entry.allowed,
+            denied_by,
+            rule,
+        ]);
+    }
+    table.printstd();
+
+    Ok(())
+}
@@ -23,8 +23,10 @@
 // Command Structure - Baseline debug_assert() of the entire command.
 // Argument Parsing  - Ensure required/optional arg combinations parse correctly.
 
+use ::rpc::forge::{self as forgerpc};
 use clap::{CommandFactory, Parser};
 
+use super::common::ConnectivityProtocolArg;
 use super::*;
 
 // verify_cmd_structure runs a baseline clap debug_assert()
@@ -222,6 +224,86 @@ fn parse_detach() {
     }
 }
 
+// parse_analyze ensures analyze parses IP address and
+// instance interface endpoints.
+#[test]
+fn parse_analyze() {
+    let cmd = Cmd::try_parse_from([
+        "network-security-group",
+        "analyze",
+        "--source",
+        "10.0.0.5",
+        "--destination",
+        "67e55044-10b1-426f-9247-bb680e5fe0c8/5e3f7e5e-9fdd-11ef-9a16-7f2e5dc7a2e1",
+        "--protocol",
+        "tcp",
+        "--dst-port",
+        "443",
+    ])
+    .expect("should parse analyze");
+
+    match cmd {
+        Cmd::Analyze(args) => {
+            assert_eq!(
+                args.source.endpoint,
+                Some(forgerpc::connectivity_endpoint::Endpoint::IpAddress(
+                    "10.0.0.5".to_string()
+                ))
+            );
+            assert!(matches!(
+                args.destination.endpoint,
+                Some(forgerpc::connectivity_endpoint::Endpoint::Interface(_))
+            ));
+            assert_eq!(args.protocol, ConnectivityProtocolArg::Tcp);
+            assert!(args.src_port.is_none());
+            assert_eq!(args.dst_port, Some(443));
+        }
+        _ => panic!("expected Analyze variant"),
+    }
+}
+
+// parse_analyze_invalid_endpoint_fails ensures endpoints
+// that are neither addresses nor IDs are rejected.
+#[test]
+fn parse_analyze_invalid_endpoint_fails() {
+    let result = Cmd::try_parse_from([
+        "network-security-group",
+        "analyze",
+        "--source",
+        "not-an-endpoint",
+        "--destination",
+        "10.0.0.6",
+        "--protocol",
+        "udp",
+    ]);
+    assert!(result.is_err(), "should fail with an invalid --source");
+}
+
+// parse_reachability ensures reachability parses with
+// required arguments.
+#[test]
+fn parse_reachability() {
+    let cmd = Cmd::try_parse_from([
+        "network-security-group",
+        "reachability",
+        "--vpc-id",
+        "67e55044-10b1-426f-9247-bb680e5fe0c8",
+        "--protocol",
+        "icmp",
+        "--include-peered-vpcs",
+    ])
+    .expect("should parse reachability");
+
+    match cmd {
+        Cmd::Reachability(args) => {
+            assert_eq!(args.protocol, ConnectivityProtocolArg::Icmp);
+            assert!(args.include_peered_vpcs);
+            assert!(args.dst_port.is_none());
+        }
+        _ => panic!("expected Reachability variant"),
+    }
+}
+
 // parse_create_missing_required_fails ensures create
 // fails without tenant org ID.
 #[test]
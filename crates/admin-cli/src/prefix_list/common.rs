@@ -0,0 +1,102 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::CarbideCliResult;
+use ::rpc::forge as forgerpc;
+use prettytable::{Table, row};
+
+/// Produces a table for printing a non-JSON representation of a
+/// prefix list to standard out.
+pub fn convert_prefix_lists_to_table(
+    prefix_lists: Vec<forgerpc::PrefixList>,
+    verbose: bool,
+) -> CarbideCliResult<Box<Table>> {
+    let mut table = Box::new(Table::new());
+    let default_metadata = Default::default();
+
+    if verbose {
+        table.set_titles(row![
+            "Id",
+            "Tenant Organization ID",
+            "Name",
+            "Description",
+            "Version",
+            "Created",
+            "Created By",
+            "Updated By",
+            "Labels",
+            "Prefixes",
+        ]);
+    } else {
+        table.set_titles(row![
+            "Id",
+            "Tenant Organization ID",
+            "Name",
+            "Description",
+            "Version",
+            "Created",
+            "Prefixes",
+        ]);
+    }
+
+    for prefix_list in prefix_lists {
+        let metadata = prefix_list.metadata.as_ref().unwrap_or(&default_metadata);
+        let attributes = prefix_list.attributes.unwrap_or_default();
+        let id = prefix_list
+            .id
+            .as_ref()
+            .map(|prefix_list_id| prefix_list_id.to_string())
+            .unwrap_or_default();
+
+        let labels = metadata
+            .labels
+            .iter()
+            .map(|label| {
+                let key = &label.key;
+                let value = label.value.as_deref().unwrap_or_default();
+                format!("\"{key}:{value}\"")
+            })
+            .collect::<Vec<_>>();
+
+        if verbose {
+            table.add_row(row![
+                id,
+                prefix_list.tenant_organization_id,
+                metadata.name,
+                metadata.description,
+                prefix_list.version,
+                prefix_list.created_at.unwrap_or_default(),
+                prefix_list.created_by.unwrap_or_default(),
+                prefix_list.updated_by.unwrap_or_default(),
+                labels.join(", "),
+                attributes.prefixes.join("\n"),
+            ]);
+        } else {
+            table.add_row(row![
+                id,
+                prefix_list.tenant_organization_id,
+                metadata.name,
+                metadata.description,
+                prefix_list.version,
+                prefix_list.created_at.unwrap_or_default(),
+                attributes.prefixes.len(),
+            ]);
+        }
+    }
+
+    Ok(table)
+}
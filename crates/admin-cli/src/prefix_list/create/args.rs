@@ -0,0 +1,82 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::CarbideCliError;
+use ::rpc::forge::{self as forgerpc, CreatePrefixListRequest};
+use carbide_uuid::prefix_list::PrefixListId;
+use clap::Parser;
+
+#[derive(Parser, Debug, Clone)]
+pub struct Args {
+    #[clap(
+        short = 'i',
+        long,
+        help = "Optional, unique ID to use when creating the prefix list"
+    )]
+    pub id: Option<PrefixListId>,
+
+    #[clap(short = 't', long, help = "Tenant organization ID for the prefix list")]
+    pub tenant_organization_id: String,
+
+    #[clap(
+        short = 'p',
+        long,
+        value_delimiter = ',',
+        help = "Comma-separated list of IPv4 and IPv6 prefixes in the prefix list"
+    )]
+    pub prefixes: Vec<String>,
+
+    #[clap(short = 'n', long, help = "Name of the prefix list")]
+    pub name: String,
+
+    #[clap(short = 'd', long, help = "Description of the prefix list")]
+    pub description: Option<String>,
+
+    #[clap(
+        short = 'l',
+        long,
+        help = "JSON map of simple key:value pairs to be applied as labels to the prefix list"
+    )]
+    pub labels: Option<String>,
+}
+
+impl TryFrom<Args> for CreatePrefixListRequest {
+    type Error = CarbideCliError;
+
+    fn try_from(args: Args) -> Result<Self, Self::Error> {
+        let labels = if let Some(labels_json) = args.labels {
+            serde_json::from_str(&labels_json)?
+        } else {
+            vec![]
+        };
+
+        let metadata = forgerpc::Metadata {
+            name: args.name,
+            description: args.description.unwrap_or_default(),
+            labels,
+        };
+
+        Ok(CreatePrefixListRequest {
+            id: args.id,
+            tenant_organization_id: args.tenant_organization_id,
+            metadata: Some(metadata),
+            attributes: Some(forgerpc::PrefixListAttributes {
+                prefixes: args.prefixes,
+            }),
+        })
+    }
+}
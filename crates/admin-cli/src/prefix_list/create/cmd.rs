@@ -0,0 +1,56 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::{CarbideCliError, CarbideCliResult, OutputFormat};
+use ::rpc::forge::CreatePrefixListRequest;
+
+use super::args::Args;
+use crate::prefix_list::common::convert_prefix_lists_to_table;
+use crate::rpc::ApiClient;
+
+/// Create a prefix list.
+/// On successful creation, the details of the
+/// new prefix list will be displayed.
+pub async fn create(
+    args: Args,
+    output_format: OutputFormat,
+    api_client: &ApiClient,
+) -> CarbideCliResult<()> {
+    let req: CreatePrefixListRequest = args.try_into()?;
+    let prefix_list = api_client.0.create_prefix_list(req).await?;
+    let prefix_list = prefix_list.prefix_list.ok_or(CarbideCliError::Empty)?;
+
+    match output_format {
+        OutputFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&prefix_list).map_err(CarbideCliError::JsonError)?
+        ),
+        OutputFormat::Yaml => println!(
+            "{}",
+            serde_yaml::to_string(&prefix_list).map_err(CarbideCliError::YamlError)?
+        ),
+        OutputFormat::Csv => {
+            convert_prefix_lists_to_table(vec![prefix_list], true)?
+                .to_csv(std::io::stdout())
+                .map_err(CarbideCliError::CsvError)?
+                .flush()?;
+        }
+        _ => convert_prefix_lists_to_table(vec![prefix_list], true)?.printstd(),
+    }
+
+    Ok(())
+}
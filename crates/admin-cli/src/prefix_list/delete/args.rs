@@ -15,26 +15,24 @@
  * limitations under the License.
  */
 
-use sqlx::PgConnection;
+use ::rpc::forge::DeletePrefixListRequest;
+use carbide_uuid::prefix_list::PrefixListId;
+use clap::Parser;
 
-use crate::DatabaseError;
+#[derive(Parser, Debug, Clone)]
+pub struct Args {
+    #[clap(short = 'i', long, help = "Prefix list ID to delete")]
+    pub id: PrefixListId,
 
-pub async fn trim_table(
-    txn: &mut PgConnection,
-    target: model::trim_table::TrimTableTarget,
-    keep_entries: u32,
-) -> Result<i32, DatabaseError> {
-    // choose a target and call an appropriate stored procedure/function
-    match target {
-        model::trim_table::TrimTableTarget::MeasuredBoot => {
-            let query = "SELECT * FROM measured_boot_reports_keep_limit($1)";
+    #[clap(short = 't', long, help = "Tenant organization ID for the prefix list")]
+    pub tenant_organization_id: String,
+}
 
-            let val: (i32,) = sqlx::query_as(query)
-                .bind(keep_entries as i32)
-                .fetch_one(txn)
-                .await
-                .map_err(|e| DatabaseError::new(query, e))?;
-            Ok(val.0)
+impl From<Args> for DeletePrefixListRequest {
+    fn from(args: Args) -> Self {
+        DeletePrefixListRequest {
+            id: Some(args.id),
+            tenant_organization_id: args.tenant_organization_id,
         }
     }
 }
@@ -0,0 +1,95 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::{CarbideCliError, CarbideCliResult, OutputFormat};
+use ::rpc::forge::FindPrefixListsByIdsRequest;
+
+use super::args::Args;
+use crate::prefix_list::common::convert_prefix_lists_to_table;
+use crate::rpc::ApiClient;
+
+/// Show one or more prefix lists.
+/// If only a single prefix list is found, verbose output is used
+/// automatically.
+pub async fn show(
+    args: Args,
+    output_format: OutputFormat,
+    api_client: &ApiClient,
+    page_size: usize,
+    verbose: bool,
+) -> CarbideCliResult<()> {
+    let prefix_lists = if let Some(id) = args.id {
+        api_client
+            .0
+            .find_prefix_lists_by_ids(FindPrefixListsByIdsRequest {
+                prefix_list_ids: vec![id],
+                tenant_organization_id: args.tenant_organization_id,
+            })
+            .await?
+            .prefix_lists
+    } else {
+        let tenant_organization_id = args.tenant_organization_id.clone();
+        let all_ids = api_client
+            .0
+            .find_prefix_list_ids(args)
+            .await?
+            .prefix_list_ids;
+
+        let mut prefix_lists = Vec::with_capacity(all_ids.len());
+
+        for ids in all_ids.chunks(page_size) {
+            let chunk = api_client
+                .0
+                .find_prefix_lists_by_ids(FindPrefixListsByIdsRequest {
+                    prefix_list_ids: ids.to_vec(),
+                    tenant_organization_id: tenant_organization_id.clone(),
+                })
+                .await?
+                .prefix_lists;
+            prefix_lists.extend(chunk);
+        }
+
+        prefix_lists
+    };
+
+    match output_format {
+        OutputFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&prefix_lists).map_err(CarbideCliError::JsonError)?
+        ),
+        OutputFormat::Yaml => println!(
+            "{}",
+            serde_yaml::to_string(&prefix_lists).map_err(CarbideCliError::YamlError)?
+        ),
+        OutputFormat::Csv => {
+            let verbose = prefix_lists.len() == 1 || verbose;
+            convert_prefix_lists_to_table(prefix_lists, verbose)?
+                .to_csv(std::io::stdout())
+                .map_err(CarbideCliError::CsvError)?
+                .flush()?;
+        }
+        _ => {
+            if prefix_lists.len() == 1 {
+                convert_prefix_lists_to_table(prefix_lists, true)?.printstd();
+            } else {
+                convert_prefix_lists_to_table(prefix_lists, verbose)?.printstd();
+            }
+        }
+    }
+
+    Ok(())
+}
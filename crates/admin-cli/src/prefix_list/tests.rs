@@ -0,0 +1,116 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// The intent of the tests.rs file is to test the integrity of the
+// command, including things like basic structure parsing, enum
+// translations, and any external input validators that are
+// configured. Specific "categories" are:
+//
+// Command Structure - Baseline debug_assert() of the entire command.
+// Argument Parsing  - Ensure required/optional arg combinations parse correctly.
+
+use ::rpc::forge::CreatePrefixListRequest;
+use clap::{CommandFactory, Parser};
+
+use super::*;
+
+// verify_cmd_structure runs a baseline clap debug_assert()
+// to do basic command configuration checking and validation,
+// ensuring things like unique argument definitions, group
+// configurations, argument references, etc. Things that would
+// otherwise be missed until runtime.
+#[test]
+fn verify_cmd_structure() {
+    Cmd::command().debug_assert();
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// Argument Parsing
+//
+// This section contains tests specific to argument parsing,
+// including testing required arguments, as well as optional
+// flag-specific checking.
+
+// parse_create ensures create parses a comma-separated
+// list of prefixes into the request.
+#[test]
+fn parse_create() {
+    let cmd = Cmd::try_parse_from([
+        "prefix-list",
+        "create",
+        "--tenant-organization-id",
+        "tenant-123",
+        "--name",
+        "partners",
+        "--prefixes",
+        "192.0.2.0/24,2001:db8::/32",
+    ])
+    .expect("should parse create");
+
+    match cmd {
+        Cmd::Create(args) => {
+            let req = CreatePrefixListRequest::try_from(args).expect("should convert");
+            assert_eq!(req.tenant_organization_id, "tenant-123");
+            assert_eq!(req.metadata.unwrap().name, "partners");
+            assert_eq!(
+                req.attributes.unwrap().prefixes,
+                vec!["192.0.2.0/24".to_string(), "2001:db8::/32".to_string()]
+            );
+        }
+        _ => panic!("expected Create variant"),
+    }
+}
+
+// parse_update_without_prefixes ensures update leaves the
+// prefixes alone unless they're given.
+#[test]
+fn parse_update_without_prefixes() {
+    let cmd = Cmd::try_parse_from([
+        "prefix-list",
+        "update",
+        "--id",
+        "9ab5a1b8-4d1c-11f1-9c1e-1b2f0a6c3d4e",
+        "--tenant-organization-id",
+        "tenant-123",
+        "--version",
+        "V1-T1",
+    ])
+    .expect("should parse update");
+
+    match cmd {
+        Cmd::Update(args) => {
+            assert!(args.prefixes.is_none());
+            assert_eq!(args.version.as_deref(), Some("V1-T1"));
+        }
+        _ => panic!("expected Update variant"),
+    }
+}
+
+// parse_delete_invalid_id_fails ensures delete rejects
+// an ID that isn't a UUID.
+#[test]
+fn parse_delete_invalid_id_fails() {
+    let result = Cmd::try_parse_from([
+        "prefix-list",
+        "delete",
+        "--id",
+        "not-a-uuid",
+        "--tenant-organization-id",
+        "tenant-123",
+    ]);
+    assert!(result.is_err(), "should fail with invalid id");
+}
@@ -0,0 +1,56 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use carbide_uuid::prefix_list::PrefixListId;
+use clap::Parser;
+
+#[derive(Parser, Debug, Clone)]
+pub struct Args {
+    #[clap(short = 'i', long, help = "Prefix list ID to update")]
+    pub id: PrefixListId,
+
+    #[clap(short = 't', long, help = "Tenant organization ID for the prefix list")]
+    pub tenant_organization_id: String,
+
+    #[clap(short = 'n', long, help = "Name of the prefix list")]
+    pub name: Option<String>,
+
+    #[clap(short = 'd', long, help = "Description of the prefix list")]
+    pub description: Option<String>,
+
+    #[clap(
+        short = 'l',
+        long,
+        help = "JSON map of simple key:value pairs to be applied as labels to the prefix list - will COMPLETELY overwrite any existing labels"
+    )]
+    pub labels: Option<String>,
+
+    #[clap(
+        short = 'p',
+        long,
+        value_delimiter = ',',
+        help = "Optional, comma-separated list of IPv4 and IPv6 prefixes - will COMPLETELY overwrite the existing prefixes"
+    )]
+    pub prefixes: Option<Vec<String>>,
+
+    #[clap(
+        short = 'v',
+        long,
+        help = "Optional, version to use for comparison when performing the update, which will be rejected if the actual version of the record does not match the value of this parameter"
+    )]
+    pub version: Option<String>,
+}
@@ -0,0 +1,105 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::{CarbideCliError, CarbideCliResult, OutputFormat};
+use ::rpc::forge::{FindPrefixListsByIdsRequest, UpdatePrefixListRequest};
+
+use super::args::Args;
+use crate::prefix_list::common::convert_prefix_lists_to_table;
+use crate::rpc::ApiClient;
+
+/// Update a prefix list.
+/// On successful update, the details of the prefix list
+/// and the network security groups that reference it
+/// will be displayed.
+pub async fn update(
+    args: Args,
+    output_format: OutputFormat,
+    api_client: &ApiClient,
+) -> CarbideCliResult<()> {
+    let prefix_list = api_client
+        .0
+        .find_prefix_lists_by_ids(FindPrefixListsByIdsRequest {
+            prefix_list_ids: vec![args.id],
+            tenant_organization_id: Some(args.tenant_organization_id.clone()),
+        })
+        .await?
+        .prefix_lists
+        .into_iter()
+        .next()
+        .ok_or(CarbideCliError::Empty)?;
+
+    let mut metadata = prefix_list.metadata.unwrap_or_default();
+    let mut attributes = prefix_list.attributes.unwrap_or_default();
+
+    if let Some(description) = args.description {
+        metadata.description = description;
+    }
+
+    if let Some(name) = args.name {
+        metadata.name = name;
+    }
+
+    if let Some(labels_json) = args.labels {
+        metadata.labels = serde_json::from_str(&labels_json)?;
+    }
+
+    if let Some(prefixes) = args.prefixes {
+        attributes.prefixes = prefixes;
+    }
+
+    let updated = api_client
+        .0
+        .update_prefix_list(UpdatePrefixListRequest {
+            id: Some(args.id),
+            tenant_organization_id: args.tenant_organization_id,
+            metadata: Some(metadata),
+            if_version_match: args.version,
+            attributes: Some(attributes),
+        })
+        .await?;
+    let network_security_group_ids = updated.network_security_group_ids;
+    let updated = updated.prefix_list.ok_or(CarbideCliError::Empty)?;
+
+    match output_format {
+        OutputFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&updated).map_err(CarbideCliError::JsonError)?
+        ),
+        OutputFormat::Yaml => println!(
+            "{}",
+            serde_yaml::to_string(&updated).map_err(CarbideCliError::YamlError)?
+        ),
+        OutputFormat::Csv => {
+            convert_prefix_lists_to_table(vec![updated], true)?
+                .to_csv(std::io::stdout())
+                .map_err(CarbideCliError::CsvError)?
+                .flush()?;
+        }
+        _ => {
+            convert_prefix_lists_to_table(vec![updated], true)?.printstd();
+            if !network_security_group_ids.is_empty() {
+                println!(
+                    "Updated network security groups: {}",
+                    network_security_group_ids.join(", ")
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
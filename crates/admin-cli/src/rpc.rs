@@ -1808,6 +1808,7 @@ impl ApiClient {
                     }),
                     vpc_ids: vpc_ids.unwrap_or_default(),
                     instance_ids: instance_ids.unwrap_or_default(),
+                    prefix_list_ids: vec![],
                 },
             )
             .await?;
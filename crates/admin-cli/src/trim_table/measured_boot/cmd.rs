@@ -23,7 +23,9 @@ use crate::rpc::ApiClient;
 pub async fn trim_measured_boot(args: Args, api_client: &ApiClient) -> CarbideCliResult<()> {
     let request = ::rpc::forge::TrimTableRequest {
         target: ::rpc::forge::TrimTableTarget::MeasuredBoot.into(),
-        keep_entries: args.keep_entries,
+        keep_entries: Some(args.keep_entries),
+        max_age: None,
+        keep_last: 0,
     };
 
     let response = api_client.0.trim_table(request).await?;
@@ -16,6 +16,7 @@
  */
 
 mod measured_boot;
+mod table;
 
 #[cfg(test)]
 mod tests;
@@ -28,4 +29,6 @@ use crate::cfg::dispatch::Dispatch;
 #[clap(rename_all = "kebab_case")]
 pub enum Cmd {
     MeasuredBoot(measured_boot::Args),
+    #[clap(about = "Trim a table according to a one-off retention policy")]
+    Table(table::Args),
 }
@@ -0,0 +1,86 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use clap::{ArgGroup, Parser, ValueEnum};
+
+#[derive(Parser, Debug, Clone)]
+#[clap(group(
+    ArgGroup::new("trim_by")
+        .required(true)
+        .multiple(true)
+        .args(&["keep_entries", "max_age_days"])))]
+pub struct Args {
+    #[clap(help = "The table to trim")]
+    pub target: TableTarget,
+    #[clap(long, help = "Number of entries to keep per object")]
+    pub keep_entries: Option<u32>,
+    #[clap(long, help = "Trim entries older than this many days")]
+    pub max_age_days: Option<u32>,
+    #[clap(
+        long,
+        default_value_t = 0,
+        help = "Number of newest entries per object which are always kept"
+    )]
+    pub keep_last: u32,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableTarget {
+    MeasuredBoot,
+    MachineStateHistory,
+    NetworkSegmentStateHistory,
+    IbPartitionStateHistory,
+    DpaInterfaceStateHistory,
+    PowerShelfStateHistory,
+    SwitchStateHistory,
+    RackStateHistory,
+    MachineHealthHistory,
+    MachineValidationResults,
+    RedfishBmcActions,
+    SpdmAttestationHistory,
+    RackFirmwareApplyHistory,
+    InstanceConfigHistory,
+    MachineNetworkConfigHistory,
+    MachineInterfaceAddressHistory,
+}
+
+impl From<TableTarget> for ::rpc::forge::TrimTableTarget {
+    fn from(target: TableTarget) -> Self {
+        use ::rpc::forge::TrimTableTarget;
+        match target {
+            TableTarget::MeasuredBoot => TrimTableTarget::MeasuredBoot,
+            TableTarget::MachineStateHistory => TrimTableTarget::MachineStateHistory,
+            TableTarget::NetworkSegmentStateHistory => TrimTableTarget::NetworkSegmentStateHistory,
+            TableTarget::IbPartitionStateHistory => TrimTableTarget::IbPartitionStateHistory,
+            TableTarget::DpaInterfaceStateHistory => TrimTableTarget::DpaInterfaceStateHistory,
+            TableTarget::PowerShelfStateHistory => TrimTableTarget::PowerShelfStateHistory,
+            TableTarget::SwitchStateHistory => TrimTableTarget::SwitchStateHistory,
+            TableTarget::RackStateHistory => TrimTableTarget::RackStateHistory,
+            TableTarget::MachineHealthHistory => TrimTableTarget::MachineHealthHistory,
+            TableTarget::MachineValidationResults => TrimTableTarget::MachineValidationResults,
+            TableTarget::RedfishBmcActions => TrimTableTarget::RedfishBmcActions,
+            TableTarget::SpdmAttestationHistory => TrimTableTarget::SpdmAttestationHistory,
+            TableTarget::RackFirmwareApplyHistory => TrimTableTarget::RackFirmwareApplyHistory,
+            TableTarget::InstanceConfigHistory => TrimTableTarget::InstanceConfigHistory,
+            TableTarget::MachineNetworkConfigHistory => {
+                TrimTableTarget::MachineNetworkConfigHistory
+            }
+            TableTarget::MachineInterfaceAddressHistory => {
+                TrimTableTarget::MachineInterfaceAddressHistory
+            }
+        }
+    }
+}
@@ -0,0 +1,40 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use ::rpc::admin_cli::CarbideCliResult;
+
+use super::args::Args;
+use crate::rpc::ApiClient;
+
+pub async fn trim_table(args: Args, api_client: &ApiClient) -> CarbideCliResult<()> {
+    let target = ::rpc::forge::TrimTableTarget::from(args.target);
+    let request = ::rpc::forge::TrimTableRequest {
+        target: target.into(),
+        keep_entries: args.keep_entries,
+        max_age: args
+            .max_age_days
+            .map(|days| std::time::Duration::from_secs(u64::from(days) * 24 * 60 * 60).into()),
+        keep_last: args.keep_last,
+    };
+    let response = api_client.0.trim_table(request).await?;
+    println!(
+        "Trimmed {} entries from {} ({} archived)",
+        response.total_deleted,
+        target.as_str_name(),
+        response.total_archived
+    );
+    Ok(())
+}
@@ -54,6 +54,7 @@ fn parse_measured_boot() {
         Cmd::MeasuredBoot(args) => {
             assert_eq!(args.keep_entries, 100);
         }
+        _ => panic!("expected MeasuredBoot variant"),
     }
 }
 
@@ -67,6 +68,7 @@ fn parse_measured_boot_zero() {
         Cmd::MeasuredBoot(args) => {
             assert_eq!(args.keep_entries, 0);
         }
+        _ => panic!("expected MeasuredBoot variant"),
     }
 }
 
@@ -81,6 +83,7 @@ fn parse_measured_boot_large_value() {
         Cmd::MeasuredBoot(args) => {
             assert_eq!(args.keep_entries, 1000000);
         }
+        _ => panic!("expected MeasuredBoot variant"),
     }
 }
 
@@ -107,3 +110,45 @@ fn parse_measured_boot_negative_fails() {
     let result = Cmd::try_parse_from(["trim-table", "measured-boot", "--keep-entries", "-1"]);
     assert!(result.is_err(), "should fail with negative value");
 }
+
+// parse_table ensures table parses a target with
+// both trim criteria.
+#[test]
+fn parse_table() {
+    let cmd = Cmd::try_parse_from([
+        "trim-table",
+        "table",
+        "machine-state-history",
+        "--max-age-days",
+        "90",
+        "--keep-last",
+        "20",
+    ])
+    .expect("should parse table");
+
+    match cmd {
+        Cmd::Table(args) => {
+            assert_eq!(args.target, table::args::TableTarget::MachineStateHistory);
+            assert_eq!(args.keep_entries, None);
+            assert_eq!(args.max_age_days, Some(90));
+            assert_eq!(args.keep_last, 20);
+        }
+        _ => panic!("expected Table variant"),
+    }
+}
+
+// parse_table_requires_criteria ensures table fails
+// without --keep-entries or --max-age-days.
+#[test]
+fn parse_table_requires_criteria() {
+    let result = Cmd::try_parse_from(["trim-table", "table", "machine-health-history"]);
+    assert!(result.is_err(), "should fail without trim criteria");
+}
+
+// parse_table_invalid_target_fails ensures table
+// rejects unknown tables.
+#[test]
+fn parse_table_invalid_target_fails() {
+    let result = Cmd::try_parse_from(["trim-table", "table", "machines", "--keep-entries", "1"]);
+    assert!(result.is_err(), "should fail with unknown table");
+}
@@ -0,0 +1,354 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Commit-confirm for NVUE configs.
+//!
+//! A config that NVUE applies cleanly can still break BGP or the path to carbide-api, which would
+//! strand the DPU. With commit-confirm enabled, `update_nvue` keeps the previous config until the
+//! probes configured in `[commit-confirm]` pass. If they don't pass by the deadline the previous
+//! config is restored, and the rejected one is refused (and reported) until it changes or the
+//! retry period expires.
+
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+use ::rpc::forge as rpc;
+use ::rpc::forge_tls_client::{self, ForgeClientConfig};
+use carbide_host_support::agent_config::{CommitConfirmConfig, CommitConfirmProbe};
+use carbide_systemd::systemd;
+use chrono::{DateTime, Utc};
+use health_report::HealthReport;
+
+use crate::HBNDeviceNames;
+use crate::health::{self, HealthCheckParams, probe_ids};
+
+const API_PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Inputs of the BGP probes, taken from the network config being confirmed
+pub struct ProbeParams<'a> {
+    pub hbn_root: &'a Path,
+    pub host_routes: &'a [&'a str],
+    pub min_healthy_links: u32,
+    pub route_servers: &'a [String],
+    pub hbn_device_names: HBNDeviceNames,
+}
+
+pub struct CommitConfirm {
+    config: CommitConfirmConfig,
+    forge_api: String,
+    forge_client_config: Arc<ForgeClientConfig>,
+    rejected: Mutex<Option<RejectedConfig>>,
+}
+
+#[derive(Debug, Clone)]
+struct RejectedConfig {
+    /// The NVUE config that was rolled back
+    contents: String,
+    retry_at: SystemTime,
+    rollback: rpc::NetworkConfigRollback,
+}
+
+impl CommitConfirm {
+    pub fn new(
+        config: CommitConfirmConfig,
+        forge_api: String,
+        forge_client_config: Arc<ForgeClientConfig>,
+    ) -> Self {
+        Self {
+            config,
+            forge_api,
+            forge_client_config,
+            rejected: Mutex::new(None),
+        }
+    }
+
+    /// Fails if `contents` is a config that was rolled back and may not be retried yet.
+    pub fn check_not_rejected(&self, contents: &str) -> eyre::Result<()> {
+        self.check_not_rejected_at(contents, SystemTime::now())
+    }
+
+    fn check_not_rejected_at(&self, contents: &str, now: SystemTime) -> eyre::Result<()> {
+        let mut rejected = self.rejected.lock().unwrap();
+        let Some(current) = rejected.as_ref().filter(|r| r.contents == contents) else {
+            return Ok(());
+        };
+        if now >= current.retry_at {
+            tracing::info!(
+                config_version = current.rollback.rejected_config_version,
+                "Retrying network config that was previously rolled back"
+            );
+            *rejected = None;
+            return Ok(());
+        }
+        Err(eyre::eyre!(
+            "network config {} was rolled back after failing commit-confirm probes ({}), not retrying before {}",
+            current.rollback.rejected_config_version,
+            current.rollback.failed_probes.join("; "),
+            DateTime::<Utc>::from(current.retry_at).to_rfc3339(),
+        ))
+    }
+
+    /// The rollback to report in `DpuNetworkStatus`, while the rejected config is refused
+    pub fn rollback_status(&self) -> Option<rpc::NetworkConfigRollback> {
+        self.rejected
+            .lock()
+            .unwrap()
+            .as_ref()
+            .filter(|r| SystemTime::now() < r.retry_at)
+            .map(|r| r.rollback.clone())
+    }
+
+    /// A new config passed the probes: whatever was rejected before is no longer relevant.
+    pub fn confirmed(&self) {
+        *self.rejected.lock().unwrap() = None;
+    }
+
+    pub fn rolled_back(
+        &self,
+        contents: String,
+        config_version: String,
+        failed_probes: Vec<String>,
+        restored: bool,
+    ) {
+        let now = SystemTime::now();
+        let retry_at = now + Duration::from_secs(self.config.retry_after_secs);
+        *self.rejected.lock().unwrap() = Some(RejectedConfig {
+            contents,
+            retry_at,
+            rollback: rpc::NetworkConfigRollback {
+                rejected_config_version: config_version,
+                rolled_back_at: Some(now.into()),
+                failed_probes,
+                restored,
+                retry_after: Some(retry_at.into()),
+            },
+        });
+    }
+
+    /// Runs the probes until they all pass or the deadline expires.
+    /// Returns the failures of the last run if the deadline expired.
+    pub async fn verify(&self, params: ProbeParams<'_>) -> Result<(), Vec<String>> {
+        let deadline = Instant::now() + Duration::from_secs(self.config.deadline_secs);
+        let interval = Duration::from_secs(self.config.probe_interval_secs.max(1));
+        loop {
+            let failures = self.probe(&params).await;
+            if failures.is_empty() {
+                return Ok(());
+            }
+            if Instant::now() + interval >= deadline {
+                return Err(failures);
+            }
+            tracing::info!(
+                ?failures,
+                "Waiting for new network config to pass commit-confirm probes"
+            );
+            // The main loop is blocked on us, keep systemd from restarting the agent
+            if let Err(err) = systemd::notify_watchdog().await {
+                tracing::error!(error = format!("{err:#}"), "systemd::notify_watchdog");
+            }
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    async fn probe(&self, params: &ProbeParams<'_>) -> Vec<String> {
+        let probes = &self.config.probes;
+        let bgp_report = if probes.iter().any(|probe| {
+            matches!(
+                probe,
+                CommitConfirmProbe::BgpPeers | CommitConfirmProbe::RouteServers
+            )
+        }) {
+            Some(
+                health::bgp_health_check(HealthCheckParams {
+                    hbn_root: params.hbn_root,
+                    host_routes: params.host_routes,
+                    has_changed_configs: false,
+                    min_healthy_links: params.min_healthy_links,
+                    route_servers: params.route_servers,
+                    hbn_device_names: params.hbn_device_names.clone(),
+                    include_dhcp_server: false,
+                    run_restricted_mode_check: false,
+                })
+                .await,
+            )
+        } else {
+            None
+        };
+        let api_result = if probes.contains(&CommitConfirmProbe::CarbideApi) {
+            Some(self.probe_api().await)
+        } else {
+            None
+        };
+        probe_failures(probes, bgp_report.as_ref(), api_result)
+    }
+
+    async fn probe_api(&self) -> Result<(), String> {
+        let call = async {
+            let mut client = forge_tls_client::ForgeTlsClient::new(&self.forge_client_config)
+                .build(&self.forge_api)
+                .await
+                .map_err(|err| format!("{err:#}"))?;
+            client
+                .version(tonic::Request::new(rpc::VersionRequest {
+                    display_config: false,
+                }))
+                .await
+                .map_err(|err| err.to_string())?;
+            Ok(())
+        };
+        tokio::time::timeout(API_PROBE_TIMEOUT, call)
+            .await
+            .unwrap_or_else(|_| Err(format!("no response within {API_PROBE_TIMEOUT:?}")))
+    }
+}
+
+fn probe_failures(
+    probes: &[CommitConfirmProbe],
+    bgp_report: Option<&HealthReport>,
+    api_result: Option<Result<(), String>>,
+) -> Vec<String> {
+    let describe = |alert: &health_report::HealthProbeAlert| match &alert.target {
+        Some(target) => format!("{} ({target}): {}", alert.id, alert.message),
+        None => format!("{}: {}", alert.id, alert.message),
+    };
+    let mut failures = Vec::new();
+    for probe in probes {
+        match probe {
+            CommitConfirmProbe::BgpPeers => failures.extend(
+                bgp_report
+                    .iter()
+                    .flat_map(|hr| hr.alerts.iter())
+                    .filter(|alert| alert.id != *probe_ids::BgpPeeringRouteServer)
+                    .map(|alert| format!("bgp-peers: {}", describe(alert))),
+            ),
+            CommitConfirmProbe::RouteServers => failures.extend(
+                bgp_report
+                    .iter()
+                    .flat_map(|hr| hr.alerts.iter())
+                    .filter(|alert| alert.id == *probe_ids::BgpPeeringRouteServer)
+                    .map(|alert| format!("route-servers: {}", describe(alert))),
+            ),
+            CommitConfirmProbe::CarbideApi => {
+                if let Some(Err(err)) = &api_result {
+                    failures.push(format!("carbide-api: {err}"));
+                }
+            }
+        }
+    }
+    failures
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alert(id: &health_report::HealthProbeId, message: &str) -> health_report::HealthProbeAlert {
+        health_report::HealthProbeAlert {
+            id: id.clone(),
+            target: None,
+            in_alert_since: None,
+            message: message.to_string(),
+            tenant_message: None,
+            classifications: vec![],
+        }
+    }
+
+    fn commit_confirm() -> CommitConfirm {
+        CommitConfirm::new(
+            CommitConfirmConfig {
+                enabled: true,
+                ..Default::default()
+            },
+            "https://carbide-api.forge".to_string(),
+            Arc::new(ForgeClientConfig::new("/dev/null".to_string(), None)),
+        )
+    }
+
+    #[test]
+    fn test_probe_failures_by_probe() {
+        let mut hr = HealthReport::empty("forge-dpu-agent".to_string());
+        hr.alerts
+            .push(alert(&probe_ids::BgpPeeringTor, "session to p0 is Idle"));
+        hr.alerts.push(alert(
+            &probe_ids::BgpPeeringRouteServer,
+            "session to 10.0.0.1 is Active",
+        ));
+
+        let all = probe_failures(
+            &[
+                CommitConfirmProbe::BgpPeers,
+                CommitConfirmProbe::RouteServers,
+                CommitConfirmProbe::CarbideApi,
+            ],
+            Some(&hr),
+            Some(Err("connection refused".to_string())),
+        );
+        assert_eq!(
+            all,
+            vec![
+                "bgp-peers: BgpPeeringTor: session to p0 is Idle",
+                "route-servers: BgpPeeringRouteServer: session to 10.0.0.1 is Active",
+                "carbide-api: connection refused",
+            ]
+        );
+
+        let only_api = probe_failures(&[CommitConfirmProbe::CarbideApi], Some(&hr), Some(Ok(())));
+        assert!(only_api.is_empty());
+    }
+
+    #[test]
+    fn test_rejected_config_is_refused_until_retry() {
+        let commit_confirm = commit_confirm();
+        let now = SystemTime::now();
+        commit_confirm.rolled_back(
+            "bad config".to_string(),
+            "V2-T1".to_string(),
+            vec!["bgp-peers: BgpPeeringTor: down".to_string()],
+            true,
+        );
+
+        let rollback = commit_confirm.rollback_status().unwrap();
+        assert_eq!(rollback.rejected_config_version, "V2-T1");
+        assert!(rollback.restored);
+
+        let err = commit_confirm
+            .check_not_rejected_at("bad config", now)
+            .unwrap_err();
+        assert!(err.to_string().contains("V2-T1"));
+        // A different config is always tried
+        commit_confirm
+            .check_not_rejected_at("new config", now)
+            .unwrap();
+
+        // Once the retry period expires the rejected config is tried again
+        let later = now + Duration::from_secs(CommitConfirmConfig::default().retry_after_secs + 1);
+        commit_confirm
+            .check_not_rejected_at("bad config", later)
+            .unwrap();
+        assert!(commit_confirm.rollback_status().is_none());
+    }
+
+    #[test]
+    fn test_confirmed_clears_rollback() {
+        let commit_confirm = commit_confirm();
+        commit_confirm.rolled_back("bad".to_string(), "V2-T1".to_string(), vec![], false);
+        assert!(commit_confirm.rollback_status().is_some());
+        commit_confirm.confirmed();
+        assert!(commit_confirm.rollback_status().is_none());
+    }
+}
@@ -26,20 +26,18 @@ use std::time::Duration;
 use std::{fmt, fs, io};
 
 use ::rpc::InterfaceFunctionType;
-use ::rpc::forge::{
-    self as rpc, FlatInterfaceConfig, ManagedHostNetworkConfigResponse,
-    NetworkSecurityGroupRuleAction, NetworkSecurityGroupRuleProtocol,
-};
+use ::rpc::forge::{self as rpc, ManagedHostNetworkConfigResponse};
 use carbide_network::ip::prefix::Ipv4Net;
 use carbide_network::virtualization::{VpcVirtualizationType, build_dual_stack_list};
 use eyre::WrapErr;
 use mac_address::MacAddress;
 use nvue_client::{NvueClient, NvueConfig};
+use nvue_config::{NvueRenderContext, build_nvue_config};
 use serde::Deserialize;
 use tokio::process::Command as TokioCommand;
 use tokio::time::timeout;
 
-use crate::nvue::NetworkSecurityGroupRule;
+use crate::commit_confirm::{CommitConfirm, ProbeParams};
 use crate::{HBNDeviceNames, acl_rules, dhcp, hbn, nvue, traffic_intercept_bridging};
 
 /// None of the files we deal with should be bigger than this
@@ -145,8 +143,15 @@ struct PostAction {
 }
 
 pub enum NvueUpdateFlavor<'a> {
-    StartupFile { hbn_root: &'a Path, skip_post: bool },
-    RestApi { nvue_client: &'a NvueClient },
+    StartupFile {
+        hbn_root: &'a Path,
+        skip_post: bool,
+        /// When set, an applied config is only kept if it passes the commit-confirm probes
+        commit_confirm: Option<&'a CommitConfirm>,
+    },
+    RestApi {
+        nvue_client: &'a NvueClient,
+    },
 }
 
 /// Update the NVUE network config. Returns Ok(true) if the configuration changed, and
@@ -166,317 +171,22 @@ pub async fn update_nvue(
             .and_then(|build_value| hbn::parse_nvue_build_as_hbn_version(&build_value))?,
     };
 
-    let l_ip_str = match &nc.managed_host_config {
-        None => {
-            return Err(eyre::eyre!("Missing managed_host_config in response"));
-        }
-        Some(cfg) => {
-            if cfg.loopback_ip.is_empty() {
-                return Err(eyre::eyre!("Missing loopback IP"));
-            }
-            &cfg.loopback_ip
-        }
-    };
-    let loopback_ip = l_ip_str.parse().wrap_err_with(|| l_ip_str.clone())?;
-
-    let access_vlans = if nc.use_admin_network {
-        let admin_interface = nc
-            .admin_interface
-            .as_ref()
-            .ok_or_else(|| eyre::eyre!("Missing admin_interface"))?;
-        vec![nvue::VlanConfig {
-            vlan_id: admin_interface.vlan_id,
-            network: admin_interface.interface_prefix.clone(),
-            ip: admin_interface.ip.clone(),
-            ipv6_vlan_config: admin_interface.ipv6_interface_config.as_ref().map(|v6| {
-                nvue::Ipv6VlanConfig {
-                    network: v6.interface_prefix.clone(),
-                    ip: v6.ip.clone(),
-                }
-            }),
-        }]
-    } else {
-        let mut access_vlans = Vec::with_capacity(nc.tenant_interfaces.len());
-        for net in &nc.tenant_interfaces {
-            access_vlans.push(nvue::VlanConfig {
-                vlan_id: net.vlan_id,
-                network: net.interface_prefix.clone(),
-                ip: net.ip.clone(),
-                ipv6_vlan_config: net.ipv6_interface_config.as_ref().map(|v6| {
-                    nvue::Ipv6VlanConfig {
-                        network: v6.interface_prefix.clone(),
-                        ip: v6.ip.clone(),
-                    }
-                }),
-            });
-        }
-        access_vlans
-    };
-
-    let (has_stateful_nsg, network_security_groups) =
-        build_network_security_group_rules(&nc.tenant_interfaces)?;
-
-    // If we aren't on the admin network _or_ if we are the primary DPU
-    // then we should be enabled for tenancy (i.e. VRFs and related config)
-    let tenancy_enabled = !nc.use_admin_network || nc.is_primary_dpu;
-
-    let physical_name = hbn_device_names.reps[0].to_string();
-    let networks = if nc.use_admin_network {
-        if nc.is_primary_dpu {
-            let admin_interface = nc
-                .admin_interface
-                .as_ref()
-                .ok_or_else(|| eyre::eyre!("Missing admin_interface"))?;
-            vec![nvue::PortConfig {
-                interface_name: physical_name,
-                is_phy: true,
-                vlan: admin_interface.vlan_id as u16,
-                vni: if nc.network_virtualization_type() == ::rpc::forge::VpcVirtualizationType::Fnn
-                {
-                    Some(admin_interface.vni)
-                } else {
-                    None
-                },
-                l3_vni: if nc.network_virtualization_type()
-                    == ::rpc::forge::VpcVirtualizationType::Fnn
-                {
-                    Some(admin_interface.vpc_vni)
-                } else {
-                    None
-                },
-                gateway_cidr: admin_interface.gateway.clone(),
-                ipv6_port_config: admin_interface.ipv6_interface_config.as_ref().map(|v6| {
-                    nvue::Ipv6PortConfig {
-                        gateway_cidr: v6.interface_prefix.clone(),
-                        svi_ip: v6.svi_ip.clone(),
-                    }
-                }),
-                vpc_prefixes: admin_interface.vpc_prefixes.clone(),
-                vpc_peer_prefixes: admin_interface.vpc_peer_prefixes.clone(),
-                vpc_peer_vnis: admin_interface.vpc_peer_vnis.clone(),
-                svi_ip: admin_interface.svi_ip.clone(),
-                tenant_vrf_loopback_ip: admin_interface.tenant_vrf_loopback_ip.clone(),
-                network_security_group_id: None, // NSGs are not applied on the admin network.
-                is_l2_segment: if nc.network_virtualization_type()
-                    == ::rpc::forge::VpcVirtualizationType::Fnn
-                {
-                    admin_interface.is_l2_segment
-                } else {
-                    // Why false in legacy case? ¯\_(ツ)_/¯
-                    false
-                },
-            }]
-        } else {
-            vec![]
-        }
-    } else {
-        let mut ifs = Vec::with_capacity(nc.tenant_interfaces.len());
-        for net in &nc.tenant_interfaces {
-            let name = if net.function_type == rpc::InterfaceFunctionType::Physical as i32 {
-                physical_name.clone()
-            } else {
-                match net.virtual_function_id {
-                    Some(id) => hbn_device_names.build_virt(id),
-                    None => {
-                        eyre::bail!("Missing virtual function id");
-                    }
-                }
-            };
-
-            // For dual-stack FNN, the DPU-side IPv6 address is the network address
-            // of the /127 linknet (the ::0 end). The ::1 end is the host.
-            ifs.push(nvue::PortConfig {
-                interface_name: name,
-                is_phy: net.function_type == rpc::InterfaceFunctionType::Physical as i32,
-                vlan: net.vlan_id as u16,
-                vni: Some(net.vni), // TODO should this be nc.vni_device?
-                l3_vni: Some(net.vpc_vni),
-                gateway_cidr: net.gateway.clone(),
-                ipv6_port_config: net.ipv6_interface_config.as_ref().map(|v6| {
-                    nvue::Ipv6PortConfig {
-                        gateway_cidr: v6.interface_prefix.clone(),
-                        svi_ip: v6.svi_ip.clone(),
-                    }
-                }),
-                vpc_prefixes: net.vpc_prefixes.clone(),
-                vpc_peer_prefixes: net.vpc_peer_prefixes.clone(),
-                vpc_peer_vnis: net.vpc_peer_vnis.clone(),
-                svi_ip: net.svi_ip.clone(),
-                tenant_vrf_loopback_ip: net.tenant_vrf_loopback_ip.clone(),
-                network_security_group_id: net
-                    .network_security_group
-                    .as_ref()
-                    .map(|n| n.id.clone()),
-                is_l2_segment: net.is_l2_segment,
-            });
-        }
-        ifs
-    };
-
-    // We should explicitly guard against the absence of interfaces.
-    // A follow-up should probably do some work to split out tenant enabled vs. disabled DPUs more clearly.
-    if tenancy_enabled && networks.is_empty() {
-        return Err(eyre::eyre!(
-            "BUG: network config provided without interfaces"
-        ));
-    }
-
-    // Currently there's only one quarantine mode, BlockAllTraffic, so we block everything if it's set at all.
-    let is_quarantined = nc
-        .managed_host_config
-        .as_ref()
-        .is_some_and(|c| c.quarantine_state.is_some());
-
-    let network_security_policy_override_rules = if is_quarantined {
-        tracing::info!("managed host is quarantined! Disabling network access via nvue");
-
-        build_quarantined_network_security_group_rules()
-    } else {
-        nc.network_security_policy_overrides
-            .iter()
-            .map(|r| r.try_into())
-            .collect::<Result<Vec<NetworkSecurityGroupRule>, eyre::Error>>()?
-    };
-
-    let hostname = hostname().wrap_err("gethostname error")?;
     let is_dpu_os = matches!(update_flavor, NvueUpdateFlavor::StartupFile { .. });
-    let conf = nvue::NvueConfig {
-        is_fnn: false,
-        is_dpu_os,
-        fmds_gateway_vlan: if !is_dpu_os {
-            nc.tenant_interfaces
-                .iter()
-                .find(|i| i.function_type == rpc::InterfaceFunctionType::Physical as i32)
-                .map(|i| i.vlan_id as u16)
-        } else {
-            None
-        },
-        vpc_virtualization_type,
-        site_global_vpc_vni: nc.site_global_vpc_vni,
-        use_admin_network: nc.use_admin_network,
-        tenancy_enabled,
-        loopback_ip,
-        vf_intercept_bridge_port_name: nc.traffic_intercept_config.as_ref().and_then(|vc| {
-            vc.bridging
-                .as_ref()
-                .map(|b| b.vf_intercept_bridge_port.clone())
-        }),
-        vf_intercept_bridge_sf: nc.traffic_intercept_config.as_ref().and_then(|vc| {
-            vc.bridging
-                .as_ref()
-                .map(|b| b.vf_intercept_bridge_sf.clone())
-        }),
-        host_intercept_bridge_port_name: nc.traffic_intercept_config.as_ref().and_then(|vc| {
-            vc.bridging
-                .as_ref()
-                .map(|b| b.host_intercept_bridge_port.clone())
-        }),
-        secondary_overlay_vtep_ip: nc
-            .traffic_intercept_config
-            .as_ref()
-            .and_then(|vc| vc.additional_overlay_vtep_ip.clone()),
-        internal_bridge_routing_prefix: nc.traffic_intercept_config.as_ref().and_then(|vc| {
-            vc.bridging
-                .as_ref()
-                .map(|b| b.internal_bridge_routing_prefix.clone())
-        }),
-        traffic_intercept_public_prefixes: nc
-            .traffic_intercept_config
-            .as_ref()
-            .map(|vc| vc.public_prefixes.clone())
-            .unwrap_or_default(),
-        asn: nc.asn,
-        datacenter_asn: nc.datacenter_asn,
-        common_internal_route_target: nc.common_internal_route_target.map(|rt| {
-            nvue::RouteTargetConfig {
-                asn: rt.asn,
-                vni: rt.vni,
-            }
-        }),
-        additional_route_target_imports: nc
-            .additional_route_target_imports
-            .iter()
-            .map(|rt| nvue::RouteTargetConfig {
-                asn: rt.asn,
-                vni: rt.vni,
-            })
-            .collect(),
-        dpu_hostname: hostname.hostname,
-        dpu_search_domain: hostname.search_domain,
-        hbn_version: Some(hbn_version),
-        uplinks: hbn_device_names
-            .uplinks
-            .into_iter()
-            .map(String::from)
-            .collect(),
-        dhcp_servers: nc.dhcp_servers.clone(),
-        route_servers: nc.route_servers.clone(),
-        ct_port_configs: networks,
-        ct_vrf_name: format!("vpc_{}", nc.vpc_vni.unwrap_or_default()),
-        ct_access_vlans: access_vlans,
-        deny_prefixes: nc.deny_prefixes.clone(),
-        site_fabric_prefixes: nc.site_fabric_prefixes.clone(),
-        anycast_site_prefixes: nc.anycast_site_prefixes.clone(),
-        tenant_host_asn: nc.tenant_host_asn,
-        stateful_acls_enabled: nc.stateful_acls_enabled && has_stateful_nsg,
-
-        // For now, the isolation options boil down to a boolean,
-        // but the match will make sure we catch and adjust accordingly
-        // if that changes in the future.
-        use_vpc_isolation: match nc.vpc_isolation_behavior() {
-            rpc::VpcIsolationBehaviorType::VpcIsolationInvalid => {
-                return Err(eyre::eyre!("received invalid VPC-isolation config"));
-            }
-            rpc::VpcIsolationBehaviorType::VpcIsolationMutual => true,
-            //  There's no isolation.
-            rpc::VpcIsolationBehaviorType::VpcIsolationOpen => false,
-        },
-
-        network_security_policy_override_rules,
-        network_security_groups,
-        ct_l3_vni: nc.vpc_vni,
-        ct_vrf_loopback: "FNN".to_string(),
-        l3_domains: vec![],
-        ct_routing_profile: if nc.network_virtualization_type()
-            == ::rpc::forge::VpcVirtualizationType::Fnn
-            && nc.routing_profile.is_none()
-        {
-            return Err(eyre::eyre!(
-                "BUG: FNN config provided without routing-profile"
-            ));
-        } else {
-            nc.routing_profile.as_ref().map(|rp| nvue::RoutingProfile {
-                leak_default_route_from_underlay: rp.leak_default_route_from_underlay,
-                leak_tenant_host_routes_to_underlay: rp.leak_tenant_host_routes_to_underlay,
-                tenant_leak_communities_accepted: rp.tenant_leak_communities_accepted,
-                route_target_imports: rp
-                    .route_target_imports
-                    .iter()
-                    .map(|rt| nvue::RouteTargetConfig {
-                        asn: rt.asn,
-                        vni: rt.vni,
-                    })
-                    .collect(),
-                route_targets_on_exports: rp
-                    .route_targets_on_exports
-                    .iter()
-                    .map(|rt| nvue::RouteTargetConfig {
-                        asn: rt.asn,
-                        vni: rt.vni,
-                    })
-                    .collect(),
-            })
-        },
-        bgp_leaf_session_password: nc.bgp_leaf_session_password.clone(),
-    };
+    let context = local_render_context(hbn_version, is_dpu_os)?;
 
     // next_contents is a YAML-serialized NVUE config.
-    let next_contents = nvue::build(conf)?;
+    let next_contents = build_nvue_config(
+        vpc_virtualization_type,
+        nc,
+        hbn_device_names.clone(),
+        &context,
+    )?;
 
     match update_flavor {
         NvueUpdateFlavor::StartupFile {
             hbn_root,
             skip_post,
+            commit_confirm,
         } => {
             // Cleanup non-NVUE ACL files
             // We can remove this once az01 is upgraded
@@ -515,6 +225,11 @@ pub async fn update_nvue(
                 );
             }
 
+            // Don't re-apply a config that we just had to roll back
+            if let Some(commit_confirm) = commit_confirm {
+                commit_confirm.check_not_rejected(&next_contents)?;
+            }
+
             // Write the config we're going to apply
             let path = FPath(hbn_root.join(nvue::PATH));
             path.cleanup();
@@ -537,11 +252,62 @@ pub async fn update_nvue(
                 return Ok(false);
             };
 
-            if !skip_post {
-                // Apply only when NVUE reports semantic diff.
-                return nvue::apply(hbn_root, &path).await;
+            if skip_post {
+                return Ok(true);
+            }
+            // Apply only when NVUE reports semantic diff.
+            let Some(commit_confirm) = commit_confirm else {
+                return nvue::apply(hbn_root, &path, false).await;
+            };
+            if !nvue::apply(hbn_root, &path, true).await? {
+                path.del("BAK");
+                return Ok(false);
+            }
+            let tenant_peers = tenant_peers(nc);
+            let probe_params = ProbeParams {
+                hbn_root,
+                host_routes: &tenant_peers,
+                min_healthy_links: nc.min_dpu_functioning_links.unwrap_or(2),
+                route_servers: &nc.route_servers,
+                hbn_device_names,
+            };
+            match commit_confirm.verify(probe_params).await {
+                Ok(()) => {
+                    path.del("BAK");
+                    commit_confirm.confirmed();
+                    Ok(true)
+                }
+                Err(failed_probes) => {
+                    tracing::error!(
+                        config_version = nc.managed_host_config_version,
+                        ?failed_probes,
+                        "NVUE config failed commit-confirm, rolling back"
+                    );
+                    let restored = match nvue::rollback(hbn_root, &path).await {
+                        Ok(restored) => restored,
+                        Err(err) => {
+                            tracing::error!("NVUE rollback failed: {err:#}");
+                            false
+                        }
+                    };
+                    commit_confirm.rolled_back(
+                        next_contents,
+                        nc.managed_host_config_version.clone(),
+                        failed_probes.clone(),
+                        restored,
+                    );
+                    Err(eyre::eyre!(
+                        "NVUE config {} failed commit-confirm ({}), {}",
+                        nc.managed_host_config_version,
+                        failed_probes.join("; "),
+                        if restored {
+                            "previous config restored"
+                        } else {
+                            "previous config could not be restored"
+                        }
+                    ))
+                }
             }
-            Ok(true)
         }
         NvueUpdateFlavor::RestApi { nvue_client } => {
             let config = NvueConfig::from_yaml(&next_contents)
@@ -556,6 +322,36 @@ pub async fn update_nvue(
     }
 }
 
+/// The NVUE render context of the DPU we are running on
+fn local_render_context(hbn_version: String, is_dpu_os: bool) -> eyre::Result<NvueRenderContext> {
+    let hostname = hostname().wrap_err("gethostname error")?;
+    Ok(NvueRenderContext {
+        hbn_version,
+        dpu_hostname: hostname.hostname,
+        dpu_search_domain: hostname.search_domain,
+        is_dpu_os,
+    })
+}
+
+/// The NVUE startup file, i.e. the config NVUE applied last (or had restored by a rollback),
+/// with the inputs it was rendered with. None if there is none yet.
+pub async fn applied_nvue_config(hbn_root: &Path) -> eyre::Result<Option<rpc::AppliedNvueConfig>> {
+    let path = hbn_root.join(nvue::PATH);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let contents =
+        fs::read_to_string(&path).wrap_err_with(|| format!("read {}", path.display()))?;
+    let context = local_render_context(hbn::read_version().await?, true)?;
+    Ok(Some(rpc::AppliedNvueConfig {
+        hbn_version: context.hbn_version,
+        dpu_hostname: context.dpu_hostname,
+        dpu_search_domain: context.dpu_search_domain,
+        is_dpu_os: context.is_dpu_os,
+        contents,
+    }))
+}
+
 // Update internal bridge configuration for traffic-intercept routing and bridging.
 pub async fn update_traffic_intercept_bridging(
     nc: &rpc::ManagedHostNetworkConfigResponse,
@@ -629,86 +425,6 @@ pub async fn update_traffic_intercept_bridging(
     Ok(true)
 }
 
-fn build_network_security_group_rules(
-    interfaces: &[FlatInterfaceConfig],
-) -> eyre::Result<(bool, Vec<nvue::NetworkSecurityGroup>)> {
-    let mut network_security_groups = HashMap::<String, nvue::NetworkSecurityGroup>::new();
-    let mut has_stateful = false;
-    for iface in interfaces {
-        if let Some(ref nsg) = iface.network_security_group {
-            let rules = nsg
-                .rules
-                .iter()
-                .map(NetworkSecurityGroupRule::try_from)
-                .collect::<Result<Vec<NetworkSecurityGroupRule>, _>>()?;
-
-            has_stateful |= nsg.stateful_egress;
-
-            network_security_groups
-                .entry(nsg.id.clone())
-                .or_insert_with(|| nvue::NetworkSecurityGroup {
-                    id: nsg.id.clone(),
-                    rules,
-                    stateful_egress: nsg.stateful_egress,
-                });
-        }
-    }
-    Ok((
-        has_stateful,
-        network_security_groups.into_values().collect(),
-    ))
-}
-
-/// Build a set of security group rules that deny all traffic.
-///
-/// Builds rules for ipv6 and ipv4, both ingress and ingress, denying traffic to all address
-/// prefixes.
-fn build_quarantined_network_security_group_rules() -> Vec<NetworkSecurityGroupRule> {
-    let build_rule = |ingress, ipv6| {
-        let catchall_prefix = if ipv6 {
-            vec!["::/0".to_string()]
-        } else {
-            vec!["0.0.0.0/0".to_string()]
-        };
-
-        nvue::NetworkSecurityGroupRule {
-            id: format!(
-                "quarantine_{}_{}",
-                if ipv6 { "ipv6" } else { "ipv4" },
-                if ingress { "ingress" } else { "egress" }
-            ),
-            ingress,
-            ipv6,
-            priority: 0,
-            src_port_start: None,
-            src_port_end: None,
-            dst_port_start: None,
-            dst_port_end: None,
-            can_match_any_protocol: true,
-            can_be_stateful: false,
-            protocol: NetworkSecurityGroupRuleProtocol::to_string_from_enum_i32(
-                NetworkSecurityGroupRuleProtocol::NsgRuleProtoAny.into(),
-            )
-            .expect("BUG: cannot convert `any` protocol to string?")
-            .to_lowercase(),
-            action: NetworkSecurityGroupRuleAction::to_string_from_enum_i32(
-                NetworkSecurityGroupRuleAction::NsgRuleActionDeny.into(),
-            )
-            .expect("BUG: cannot convert deny action to string?")
-            .to_lowercase(),
-            src_prefixes: catchall_prefix.clone(),
-            dst_prefixes: catchall_prefix,
-        }
-    };
-
-    vec![
-        build_rule(false, false),
-        build_rule(false, true),
-        build_rule(true, false),
-        build_rule(true, true),
-    ]
-}
-
 async fn do_post(
     skip_post: bool,
     post_actions: Vec<PostAction>,
@@ -1754,6 +1470,7 @@ mod tests {
         let update_flavor = NvueUpdateFlavor::StartupFile {
             hbn_root,
             skip_post: true,
+            commit_confirm: None,
         };
 
         let has_changes = super::update_nvue(
@@ -1793,6 +1510,7 @@ mod tests {
         let update_flavor = NvueUpdateFlavor::StartupFile {
             hbn_root,
             skip_post: true,
+            commit_confirm: None,
         };
 
         let has_changes = super::update_nvue(
@@ -1843,6 +1561,7 @@ mod tests {
         let update_flavor = NvueUpdateFlavor::StartupFile {
             hbn_root,
             skip_post: true,
+            commit_confirm: None,
         };
 
         let has_changes = super::update_nvue(
@@ -1893,6 +1612,7 @@ mod tests {
         let update_flavor = NvueUpdateFlavor::StartupFile {
             hbn_root,
             skip_post: true,
+            commit_confirm: None,
         };
 
         let has_changes = super::update_nvue(
@@ -1932,6 +1652,7 @@ mod tests {
         let update_flavor = NvueUpdateFlavor::StartupFile {
             hbn_root,
             skip_post: true,
+            commit_confirm: None,
         };
 
         let has_changes = super::update_nvue(
@@ -1970,6 +1691,7 @@ mod tests {
         let update_flavor = NvueUpdateFlavor::StartupFile {
             hbn_root,
             skip_post: true,
+            commit_confirm: None,
         };
 
         assert!(
@@ -2010,6 +1732,7 @@ mod tests {
         let update_flavor = NvueUpdateFlavor::StartupFile {
             hbn_root,
             skip_post: true,
+            commit_confirm: None,
         };
 
         let has_changes = super::update_nvue(
@@ -2064,6 +1787,7 @@ mod tests {
         let update_flavor = NvueUpdateFlavor::StartupFile {
             hbn_root,
             skip_post: true,
+            commit_confirm: None,
         };
 
         let has_changes = super::update_nvue(
@@ -2121,6 +1845,7 @@ mod tests {
         let update_flavor = NvueUpdateFlavor::StartupFile {
             hbn_root,
             skip_post: true,
+            commit_confirm: None,
         };
 
         let has_changes = super::update_nvue(
@@ -2187,6 +1912,7 @@ mod tests {
         let update_flavor = NvueUpdateFlavor::StartupFile {
             hbn_root,
             skip_post: true,
+            commit_confirm: None,
         };
 
         let has_changes = super::update_nvue(
@@ -2243,6 +1969,7 @@ mod tests {
         let update_flavor = NvueUpdateFlavor::StartupFile {
             hbn_root,
             skip_post: true,
+            commit_confirm: None,
         };
 
         let has_changes = super::update_nvue(
@@ -189,6 +189,45 @@ pub async fn health_check(params: HealthCheckParams<'_>) -> health_report::Healt
     hr
 }
 
+/// Only the BGP checks of [`health_check`]. Used to confirm a newly applied network config.
+pub async fn bgp_health_check(params: HealthCheckParams<'_>) -> health_report::HealthReport {
+    let mut hr = health_report::HealthReport::empty("forge-dpu-agent".to_string());
+    let container_id = match hbn::get_hbn_container_id().await {
+        Ok(id) => id,
+        Err(err) => {
+            failed(
+                &mut hr,
+                probe_ids::ContainerExists.clone(),
+                None,
+                err.to_string(),
+            );
+            return hr;
+        }
+    };
+    let hbn_daemons_file = params.hbn_root.join(HBN_DAEMONS_FILE);
+    bgp::check_daemon_enabled(&mut hr, &hbn_daemons_file.to_string_lossy());
+    bgp::check_bgp_stats(
+        &mut hr,
+        &container_id,
+        params.host_routes,
+        params.min_healthy_links,
+        params.route_servers,
+        params.hbn_device_names,
+    )
+    .await;
+    hr
+}
+
+/// The agent rolled back a network config that failed commit-confirm and refuses it for now.
+pub fn network_config_rolled_back(hr: &mut health_report::HealthReport, message: String) {
+    hr.alerts.push(make_alert(
+        probe_ids::NetworkConfigRolledBack.clone(),
+        None,
+        message,
+        false,
+    ));
+}
+
 // HBN processes should be running
 async fn check_hbn_services_running(
     hr: &mut health_report::HealthReport,
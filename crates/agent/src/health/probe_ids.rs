@@ -36,4 +36,5 @@ lazy_static::lazy_static! {
     pub static ref DpuDiskUtilizationCheck: HealthProbeId = "DpuDiskUtilizationCheck".parse().unwrap();
     pub static ref DpuDiskUtilizationCritical: HealthProbeId = "DpuDiskUtilizationCritical".parse().unwrap();
     pub static ref NvueApiRunning: HealthProbeId = "NvueApiRunning".parse().unwrap();
+    pub static ref NetworkConfigRolledBack: HealthProbeId = "NetworkConfigRolledBack".parse().unwrap();
 }
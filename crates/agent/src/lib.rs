@@ -35,8 +35,8 @@ use eyre::WrapErr;
 use forge_tls::client_config::ClientCert;
 use mac_address::MacAddress;
 use network_monitor::{NetworkPingerType, Ping};
+pub use nvue_config::HBNDeviceNames;
 use tokio::fs;
-use version_compare::{Part, Version};
 
 use crate::duppet::{SummaryFormat, SyncOptions};
 use crate::health::HealthCheckParams;
@@ -47,6 +47,7 @@ pub mod dpu;
 mod acl_rules;
 pub mod agent_platform;
 mod command_line;
+mod commit_confirm;
 pub mod containerd;
 mod dhcp;
 mod dhcp_server_grpc_client;
@@ -428,59 +429,6 @@ struct Registration {
     factory_mac_address: MacAddress,
 }
 
-#[derive(Clone)]
-struct HBNDeviceNames {
-    uplinks: [&'static str; 2],
-    reps: [&'static str; 2],
-    virt_rep_begin: &'static str,
-    sfs: [&'static str; 2],
-    sf_id: &'static str,
-}
-
-impl HBNDeviceNames {
-    pub fn pre_23() -> HBNDeviceNames {
-        HBNDeviceNames {
-            uplinks: ["p0_sf", "p1_sf"],
-            reps: ["pf0hpf_sf", "pf1hpf_sf"],
-            virt_rep_begin: "pf0vf",
-            sfs: ["pf0dpu0_sf", "pf0dpu2_sf"],
-            sf_id: "_sf",
-        }
-    }
-
-    pub fn hbn_23() -> HBNDeviceNames {
-        HBNDeviceNames {
-            uplinks: ["p0_if", "p1_if"],
-            reps: ["pf0hpf_if", "pf1hpf_if"],
-            virt_rep_begin: "pf0vf",
-            sfs: ["pf0dpu1", "pf0dpu3"],
-            sf_id: "_if",
-        }
-    }
-    pub fn new(hbn_version: Version) -> Self {
-        let min_version: Version = Version::from_parts(
-            "2.3.0-doca2.8.0",
-            vec![
-                Part::Number(2),
-                Part::Number(3),
-                Part::Number(0),
-                Part::Text("doca"),
-                Part::Number(2),
-                Part::Number(8),
-                Part::Number(0),
-            ],
-        );
-        if hbn_version < min_version {
-            HBNDeviceNames::pre_23()
-        } else {
-            HBNDeviceNames::hbn_23()
-        }
-    }
-    pub fn build_virt(&self, virt_rep_id: u32) -> String {
-        format!("{}{}{}", self.virt_rep_begin, virt_rep_id, self.sf_id)
-    }
-}
-
 /// Discover hardware, register DPU with carbide-api, and return machine id.
 async fn register(
     agent: &AgentConfig,
@@ -44,6 +44,7 @@ use tracing::log::error;
 use version_compare::Version;
 
 use crate::command_line::HbnConfigMode;
+use crate::commit_confirm::CommitConfirm;
 use crate::dpu::DpuNetworkInterfaces;
 use crate::dpu::interface::Interface;
 use crate::dpu::route::{DpuRoutePlan, IpRoute, Route};
@@ -214,6 +215,14 @@ pub async fn setup_and_run(
         }
     };
 
+    let commit_confirm = agent_config.commit_confirm.enabled.then(|| {
+        CommitConfirm::new(
+            agent_config.commit_confirm.clone(),
+            forge_api_server.clone(),
+            Arc::clone(&forge_client_config),
+        )
+    });
+
     let build_version = carbide_version::v!(build_version).to_string();
 
     let periodic_config_fetcher = periodic_config_fetcher::PeriodicConfigFetcher::new(
@@ -362,7 +371,9 @@ pub async fn setup_and_run(
         network_monitor_handle,
         extension_service_manager,
         nvue_client,
+        commit_confirm,
         dhcp_interface_translation_mode,
+        reported_nvue_config: None,
     };
 
     main_loop.run().await
@@ -395,7 +406,10 @@ struct MainLoop {
     close_sender: watch::Sender<bool>,
     extension_service_manager: extension_services::ExtensionServiceManager,
     nvue_client: Option<nvue_client::NvueClient>,
+    commit_confirm: Option<CommitConfirm>,
     dhcp_interface_translation_mode: Option<InterfaceTranslationMode>,
+    /// The applied NVUE config carbide-api last accepted. It is only reported again once it changes.
+    reported_nvue_config: Option<rpc::AppliedNvueConfig>,
 }
 
 struct IterationResult {
@@ -520,6 +534,8 @@ impl MainLoop {
             last_dhcp_requests: vec![],
             dpu_extension_service_version: None,
             dpu_extension_services: vec![],
+            network_config_rollback: None,
+            applied_nvue_config: None,
         };
 
         // `read` does not block
@@ -670,6 +686,7 @@ impl MainLoop {
                                 None => NvueUpdateFlavor::StartupFile {
                                     hbn_root: &self.agent_config.hbn.root_dir,
                                     skip_post: self.agent_config.hbn.skip_reload,
+                                    commit_confirm: self.commit_confirm.as_ref(),
                                 },
                             };
                             ethernet_virtualization::update_nvue(
@@ -811,7 +828,7 @@ impl MainLoop {
                 current_instance_config_version = status_out.instance_config_version.clone();
                 current_instance_id = status_out.instance_id.as_ref().map(|id| id.to_string());
 
-                let health_report = match self.nvue_client.as_ref() {
+                let mut health_report = match self.nvue_client.as_ref() {
                     None => {
                         health::health_check(HealthCheckParams {
                             hbn_root: &self.agent_config.hbn.root_dir,
@@ -827,6 +844,38 @@ impl MainLoop {
                     }
                     Some(nvue_client) => health::nvue_api_health(nvue_client).await,
                 };
+                let mut applied_nvue_config = None;
+                if self.nvue_client.is_none() {
+                    applied_nvue_config = match ethernet_virtualization::applied_nvue_config(
+                        &self.agent_config.hbn.root_dir,
+                    )
+                    .await
+                    {
+                        Ok(applied) => applied,
+                        Err(err) => {
+                            tracing::warn!("Reading applied NVUE config: {err:#}");
+                            None
+                        }
+                    };
+                    if applied_nvue_config != self.reported_nvue_config {
+                        status_out.applied_nvue_config = applied_nvue_config.clone();
+                    }
+                }
+                if let Some(rollback) = self
+                    .commit_confirm
+                    .as_ref()
+                    .and_then(CommitConfirm::rollback_status)
+                {
+                    health::network_config_rolled_back(
+                        &mut health_report,
+                        format!(
+                            "Network config {} was rolled back after failing: {}",
+                            rollback.rejected_config_version,
+                            rollback.failed_probes.join("; ")
+                        ),
+                    );
+                    status_out.network_config_rollback = Some(rollback);
+                }
                 is_healthy = !health_report.successes.is_empty() && health_report.alerts.is_empty();
                 self.is_hbn_up = health::is_up(&health_report);
                 // subset of is_healthy
@@ -877,12 +926,16 @@ impl MainLoop {
                 current_extension_service_version =
                     status_out.dpu_extension_service_version.clone();
 
-                record_network_status(
+                let reports_nvue_config = status_out.applied_nvue_config.is_some();
+                let recorded = record_network_status(
                     status_out,
                     &self.forge_api_server,
                     &self.forge_client_config,
                 )
                 .await;
+                if recorded && reports_nvue_config {
+                    self.reported_nvue_config = applied_nvue_config;
+                }
                 self.seen_blank = false;
             }
             None => {
@@ -1100,11 +1153,12 @@ async fn plan_fmds_armos_routing(
         Ok(None)
     }
 }
+/// Reports the network status to carbide-api. Returns whether carbide-api accepted it.
 pub async fn record_network_status(
     status: rpc::DpuNetworkStatus,
     forge_api: &str,
     forge_client_config: &forge_tls_client::ForgeClientConfig,
-) {
+) -> bool {
     let mut client = match forge_tls_client::ForgeTlsClient::new(forge_client_config)
         .build(forge_api)
         .await
@@ -1116,7 +1170,7 @@ pub async fn record_network_status(
                 error = format!("{err:#}"),
                 "record_network_status: Could not connect to Forge API server. Will retry."
             );
-            return;
+            return false;
         }
     };
     let request = tonic::Request::new(status);
@@ -1125,7 +1179,9 @@ pub async fn record_network_status(
             error = format!("{err:#}"),
             "Error while executing the record_network_status gRPC call"
         );
+        return false;
     }
+    true
 }
 
 // Get the link type, carrier status, MTU, and whatever else for our uplinks
@@ -0,0 +1,9 @@
+-- The NVUE config each DPU agent last reported applying, so that carbide-api can diff
+-- rendered configs against it. Kept out of `machines` because the documents are large.
+CREATE TABLE dpu_applied_nvue_configs (
+    dpu_id VARCHAR NOT NULL PRIMARY KEY,
+    config JSONB NOT NULL,
+    reported_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT fk_dpu_applied_nvue_configs_mid FOREIGN KEY (dpu_id) REFERENCES machines(id) ON DELETE CASCADE
+);
@@ -0,0 +1,20 @@
+-- Tenant-owned named lists of prefixes that network security group rules
+-- can reference as their source or destination.
+CREATE TABLE prefix_lists (
+    id                       uuid NOT NULL,
+    tenant_organization_id   character varying(64) NOT NULL,
+    name                     character varying NOT NULL,
+    version                  character varying(64) NOT NULL DEFAULT 'V1-T0'::character varying,
+    labels                   jsonb NOT NULL DEFAULT '{}'::jsonb,
+    description              character varying(1024) NOT NULL DEFAULT '',
+
+    prefixes                 jsonb NOT NULL DEFAULT '[]'::jsonb,
+
+    created                  timestamp with time zone NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    deleted                  timestamp with time zone,
+    created_by               character varying(64),
+    updated_by               character varying(64)
+);
+ALTER TABLE ONLY prefix_lists ADD CONSTRAINT prefix_lists_pkey PRIMARY KEY (id);
+CREATE UNIQUE INDEX prefix_lists_unique_name ON prefix_lists (name, tenant_organization_id) WHERE (deleted) IS NULL;
+ALTER TABLE ONLY prefix_lists ADD CONSTRAINT prefix_lists_tenant_id_fkey FOREIGN KEY (tenant_organization_id) REFERENCES tenants(organization_id);
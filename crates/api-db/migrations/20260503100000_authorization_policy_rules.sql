@@ -0,0 +1,13 @@
+-- Casbin policy rules managed at runtime. carbide-api merges these with the
+-- policy file when `auth.casbin_policy_from_database` is set, and picks up
+-- changes without a restart.
+CREATE TABLE authorization_policy_rules (
+    id          BIGSERIAL PRIMARY KEY,
+    -- The Casbin policy type, e.g. `p` or `g`
+    ptype       VARCHAR(16) NOT NULL,
+    -- The rule's fields, in the order the policy model defines them
+    rule        TEXT[] NOT NULL,
+    created     TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_by  VARCHAR(64)
+);
+CREATE UNIQUE INDEX authorization_policy_rules_unique_rule ON authorization_policy_rules (ptype, rule);
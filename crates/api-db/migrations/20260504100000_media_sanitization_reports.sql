@@ -0,0 +1,16 @@
+-- Evidence of the storage sanitization scout performs during machine cleanup.
+-- A report is opened when an instance is released, and completed with one
+-- record per storage device once scout reports the cleanup. Reports reference
+-- machines and instances by ID only, so they outlive both.
+CREATE TABLE media_sanitization_reports (
+    id                      BIGSERIAL PRIMARY KEY,
+    machine_id              VARCHAR(64) NOT NULL,
+    instance_id             UUID,
+    tenant_organization_id  VARCHAR,
+    released_at             TIMESTAMPTZ,
+    completed_at            TIMESTAMPTZ,
+    records                 JSONB NOT NULL DEFAULT '[]'::jsonb,
+    created                 TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX media_sanitization_reports_machine_id ON media_sanitization_reports (machine_id, created);
+CREATE INDEX media_sanitization_reports_instance_id ON media_sanitization_reports (instance_id);
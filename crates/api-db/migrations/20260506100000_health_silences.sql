@@ -0,0 +1,16 @@
+-- Time-bounded silences for health alerts of all objects matching a matcher.
+-- Silences are never deleted: once expired or ended early, `ended_at` (and
+-- `ended_by` for early ends) is set, so the table doubles as their audit trail.
+CREATE TABLE health_silences (
+    id          BIGSERIAL PRIMARY KEY,
+    matcher     JSONB NOT NULL,
+    author      VARCHAR NOT NULL,
+    reason      VARCHAR NOT NULL,
+    starts_at   TIMESTAMPTZ NOT NULL,
+    expires_at  TIMESTAMPTZ NOT NULL,
+    created     TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    ended_at    TIMESTAMPTZ,
+    ended_by    VARCHAR,
+    CHECK (expires_at > starts_at)
+);
+CREATE INDEX health_silences_open ON health_silences (expires_at) WHERE ended_at IS NULL;
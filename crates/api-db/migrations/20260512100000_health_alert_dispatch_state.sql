@@ -0,0 +1,10 @@
+-- The aggregate health of each object as last dispatched to the alert
+-- receivers. Comparing against it lets the alert dispatcher detect alert
+-- start and clear transitions across restarts and carbide-api replicas.
+CREATE TABLE health_alert_dispatch_state (
+    object_kind VARCHAR NOT NULL,
+    object_id   VARCHAR NOT NULL,
+    report      JSONB NOT NULL,
+    updated     TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (object_kind, object_id)
+);
@@ -0,0 +1,11 @@
+-- Registry of the state processors which share the queued objects of a state controller
+-- when processor sharding is enabled. Processors refresh `last_heartbeat` periodically and
+-- are considered gone once it is older than the configured expiry, at which point their
+-- shards are reassigned to the remaining processors.
+CREATE TABLE state_controller_processors (
+    queue_table     VARCHAR NOT NULL,
+    processor_id    VARCHAR NOT NULL,
+    registered_at   TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_heartbeat  TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (queue_table, processor_id)
+);
@@ -0,0 +1,44 @@
+-- Adds priority classes and fairness keys to the state controller queues.
+-- `priority` is 0 for periodically enqueued objects, 1 for objects requeued after a state
+-- transition and 2 for explicitly requested state handling. Objects sharing a `fairness_key`
+-- (e.g. a tenant organization) are dequeued in turn with objects of other keys.
+-- `queued_at` is used to age objects into higher priority classes and to measure queue wait times.
+ALTER TABLE machine_state_controller_queued_objects
+    ADD COLUMN priority SMALLINT NOT NULL DEFAULT 0,
+    ADD COLUMN fairness_key VARCHAR NULL,
+    ADD COLUMN queued_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+
+ALTER TABLE network_segments_controller_queued_objects
+    ADD COLUMN priority SMALLINT NOT NULL DEFAULT 0,
+    ADD COLUMN fairness_key VARCHAR NULL,
+    ADD COLUMN queued_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+
+ALTER TABLE ib_partition_controller_queued_objects
+    ADD COLUMN priority SMALLINT NOT NULL DEFAULT 0,
+    ADD COLUMN fairness_key VARCHAR NULL,
+    ADD COLUMN queued_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+
+ALTER TABLE dpa_interfaces_controller_queued_objects
+    ADD COLUMN priority SMALLINT NOT NULL DEFAULT 0,
+    ADD COLUMN fairness_key VARCHAR NULL,
+    ADD COLUMN queued_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+
+ALTER TABLE power_shelf_controller_queued_objects
+    ADD COLUMN priority SMALLINT NOT NULL DEFAULT 0,
+    ADD COLUMN fairness_key VARCHAR NULL,
+    ADD COLUMN queued_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+
+ALTER TABLE switch_controller_queued_objects
+    ADD COLUMN priority SMALLINT NOT NULL DEFAULT 0,
+    ADD COLUMN fairness_key VARCHAR NULL,
+    ADD COLUMN queued_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+
+ALTER TABLE rack_controller_queued_objects
+    ADD COLUMN priority SMALLINT NOT NULL DEFAULT 0,
+    ADD COLUMN fairness_key VARCHAR NULL,
+    ADD COLUMN queued_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+
+ALTER TABLE attestation_controller_queued_objects
+    ADD COLUMN priority SMALLINT NOT NULL DEFAULT 0,
+    ADD COLUMN fairness_key VARCHAR NULL,
+    ADD COLUMN queued_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
@@ -0,0 +1,152 @@
+-- History tables which allow reconstructing a managed host at a point in time, together
+-- with machine_state_history and machine_health_history.
+-- The tables are maintained by triggers, so that every code path which changes the
+-- tracked columns is covered. They are seeded with the current values, which means that
+-- history is only available from the time of this migration onwards.
+
+-- Config versions of instances. A row with NULL versions records that the instance
+-- was removed from the database.
+CREATE TABLE instance_config_history (
+    id BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    machine_id VARCHAR(64) NOT NULL,
+    instance_id uuid NOT NULL,
+    config_version VARCHAR(64) NULL,
+    network_config_version VARCHAR(64) NULL,
+    deleted TIMESTAMPTZ NULL,
+    "timestamp" TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_instance_config_history_machine_id ON instance_config_history (machine_id, id);
+
+CREATE OR REPLACE FUNCTION instance_config_history_record()
+RETURNS TRIGGER AS
+$body$
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        INSERT INTO instance_config_history (machine_id, instance_id)
+        VALUES (OLD.machine_id, OLD.id);
+    ELSE
+        INSERT INTO instance_config_history (machine_id, instance_id, config_version, network_config_version, deleted)
+        VALUES (NEW.machine_id, NEW.id, NEW.config_version, NEW.network_config_version, NEW.deleted);
+    END IF;
+    RETURN NULL;
+END;
+$body$
+LANGUAGE plpgsql;
+
+CREATE TRIGGER t_instance_config_history_insert
+    AFTER INSERT OR DELETE ON instances
+    FOR EACH ROW EXECUTE PROCEDURE instance_config_history_record();
+
+CREATE TRIGGER t_instance_config_history_update
+    AFTER UPDATE OF config_version, network_config_version, deleted ON instances
+    FOR EACH ROW
+    WHEN (OLD.config_version IS DISTINCT FROM NEW.config_version
+        OR OLD.network_config_version IS DISTINCT FROM NEW.network_config_version
+        OR OLD.deleted IS DISTINCT FROM NEW.deleted)
+    EXECUTE PROCEDURE instance_config_history_record();
+
+INSERT INTO instance_config_history (machine_id, instance_id, config_version, network_config_version, deleted)
+    SELECT machine_id, id, config_version, network_config_version, deleted FROM instances;
+
+-- Network config versions of machines
+CREATE TABLE machine_network_config_history (
+    id BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    machine_id VARCHAR(64) NOT NULL,
+    network_config_version VARCHAR(64) NOT NULL,
+    "timestamp" TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_machine_network_config_history_machine_id ON machine_network_config_history (machine_id, id);
+
+CREATE OR REPLACE FUNCTION machine_network_config_history_record()
+RETURNS TRIGGER AS
+$body$
+BEGIN
+    INSERT INTO machine_network_config_history (machine_id, network_config_version)
+    VALUES (NEW.id, NEW.network_config_version);
+    RETURN NULL;
+END;
+$body$
+LANGUAGE plpgsql;
+
+CREATE TRIGGER t_machine_network_config_history_insert
+    AFTER INSERT ON machines
+    FOR EACH ROW EXECUTE PROCEDURE machine_network_config_history_record();
+
+CREATE TRIGGER t_machine_network_config_history_update
+    AFTER UPDATE OF network_config_version ON machines
+    FOR EACH ROW
+    WHEN (OLD.network_config_version IS DISTINCT FROM NEW.network_config_version)
+    EXECUTE PROCEDURE machine_network_config_history_record();
+
+INSERT INTO machine_network_config_history (machine_id, network_config_version)
+    SELECT id, network_config_version FROM machines;
+
+-- Addresses of machine interfaces. Every row records that an address got assigned to or
+-- released from an interface, together with the machine the interface belonged to and
+-- the DPU it was attached to.
+-- Moving an interface to another machine or DPU releases its addresses from the old
+-- machine and DPU and assigns them to the new ones.
+CREATE TABLE machine_interface_address_history (
+    id BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    interface_id uuid NOT NULL,
+    machine_id VARCHAR(64) NULL,
+    attached_dpu_machine_id VARCHAR(64) NULL,
+    mac_address macaddr NULL,
+    address inet NOT NULL,
+    assigned BOOLEAN NOT NULL,
+    "timestamp" TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_machine_interface_address_history_machine_id ON machine_interface_address_history (machine_id);
+CREATE INDEX idx_machine_interface_address_history_attached_dpu_machine_id ON machine_interface_address_history (attached_dpu_machine_id);
+CREATE INDEX idx_machine_interface_address_history_interface_id ON machine_interface_address_history (interface_id, address, id);
+
+CREATE OR REPLACE FUNCTION machine_interface_address_history_record()
+RETURNS TRIGGER AS
+$body$
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        INSERT INTO machine_interface_address_history (interface_id, machine_id, attached_dpu_machine_id, mac_address, address, assigned)
+        SELECT OLD.interface_id, mi.machine_id, mi.attached_dpu_machine_id, mi.mac_address, OLD.address, false
+        FROM (SELECT 1) AS dummy LEFT JOIN machine_interfaces mi ON mi.id = OLD.interface_id;
+    ELSE
+        INSERT INTO machine_interface_address_history (interface_id, machine_id, attached_dpu_machine_id, mac_address, address, assigned)
+        SELECT NEW.interface_id, mi.machine_id, mi.attached_dpu_machine_id, mi.mac_address, NEW.address, true
+        FROM (SELECT 1) AS dummy LEFT JOIN machine_interfaces mi ON mi.id = NEW.interface_id;
+    END IF;
+    RETURN NULL;
+END;
+$body$
+LANGUAGE plpgsql;
+
+CREATE TRIGGER t_machine_interface_address_history
+    AFTER INSERT OR DELETE ON machine_interface_addresses
+    FOR EACH ROW EXECUTE PROCEDURE machine_interface_address_history_record();
+
+CREATE OR REPLACE FUNCTION machine_interface_address_history_move()
+RETURNS TRIGGER AS
+$body$
+BEGIN
+    INSERT INTO machine_interface_address_history (interface_id, machine_id, attached_dpu_machine_id, mac_address, address, assigned)
+    SELECT OLD.id, OLD.machine_id, OLD.attached_dpu_machine_id, OLD.mac_address, a.address, false
+    FROM machine_interface_addresses a WHERE a.interface_id = OLD.id;
+    INSERT INTO machine_interface_address_history (interface_id, machine_id, attached_dpu_machine_id, mac_address, address, assigned)
+    SELECT NEW.id, NEW.machine_id, NEW.attached_dpu_machine_id, NEW.mac_address, a.address, true
+    FROM machine_interface_addresses a WHERE a.interface_id = NEW.id;
+    RETURN NULL;
+END;
+$body$
+LANGUAGE plpgsql;
+
+CREATE TRIGGER t_machine_interface_address_history_move
+    AFTER UPDATE OF machine_id, attached_dpu_machine_id ON machine_interfaces
+    FOR EACH ROW
+    WHEN (OLD.machine_id IS DISTINCT FROM NEW.machine_id
+        OR OLD.attached_dpu_machine_id IS DISTINCT FROM NEW.attached_dpu_machine_id)
+    EXECUTE PROCEDURE machine_interface_address_history_move();
+
+INSERT INTO machine_interface_address_history (interface_id, machine_id, attached_dpu_machine_id, mac_address, address, assigned)
+    SELECT a.interface_id, mi.machine_id, mi.attached_dpu_machine_id, mi.mac_address, a.address, true
+    FROM machine_interface_addresses a JOIN machine_interfaces mi ON mi.id = a.interface_id;
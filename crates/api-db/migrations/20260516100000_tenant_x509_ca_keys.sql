@@ -0,0 +1,12 @@
+-- Key of the per-org X.509-SVID root CA, kept apart from the JWT-SVID signing key in
+-- tenant_identity_config. Created on first use and replaced when the signing key is rotated.
+-- Private key is encrypted with the same key as the signing key.
+
+CREATE TABLE tenant_x509_ca_keys (
+    organization_id     VARCHAR(255) PRIMARY KEY REFERENCES tenant_identity_config(organization_id) ON DELETE CASCADE,
+    -- key_id of the signing key the CA key was created with
+    signing_key_id      VARCHAR(255) NOT NULL,
+    encrypted_ca_key    TEXT NOT NULL,
+    encryption_key_id   VARCHAR(255) NOT NULL,
+    created_at          TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
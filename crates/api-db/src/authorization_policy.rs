@@ -0,0 +1,67 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//! Casbin policy rules stored in the database, see `carbide-api`'s `auth` module.
+
+use sqlx::{FromRow, PgConnection};
+
+use crate::db_read::DbReader;
+use crate::{DatabaseError, DatabaseResult};
+
+#[derive(Debug, Clone, PartialEq, Eq, FromRow)]
+pub struct AuthorizationPolicyRule {
+    pub id: i64,
+    pub ptype: String,
+    pub rule: Vec<String>,
+}
+
+/// All stored rules, oldest first.
+pub async fn find_all(txn: impl DbReader<'_>) -> DatabaseResult<Vec<AuthorizationPolicyRule>> {
+    let query = "SELECT id, ptype, rule FROM authorization_policy_rules ORDER BY id";
+    sqlx::query_as(query)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+pub async fn insert(
+    txn: &mut PgConnection,
+    ptype: &str,
+    rule: &[String],
+    created_by: Option<&str>,
+) -> DatabaseResult<AuthorizationPolicyRule> {
+    let query = "INSERT INTO authorization_policy_rules (ptype, rule, created_by)
+        VALUES ($1, $2, $3)
+        RETURNING id, ptype, rule";
+    sqlx::query_as(query)
+        .bind(ptype)
+        .bind(rule)
+        .bind(created_by)
+        .fetch_one(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Returns whether a rule with this ID existed.
+pub async fn delete(txn: &mut PgConnection, id: i64) -> DatabaseResult<bool> {
+    let query = "DELETE FROM authorization_policy_rules WHERE id = $1";
+    let result = sqlx::query(query)
+        .bind(id)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(result.rows_affected() > 0)
+}
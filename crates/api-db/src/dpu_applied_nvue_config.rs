@@ -0,0 +1,53 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use carbide_uuid::machine::MachineId;
+use model::machine::network::AppliedNvueConfig;
+use sqlx::PgConnection;
+use sqlx::types::Json;
+
+use crate::DatabaseError;
+
+/// Stores the NVUE config a DPU reported applying. Rows are only written if the config changed.
+pub async fn upsert(
+    txn: &mut PgConnection,
+    dpu_id: &MachineId,
+    config: &AppliedNvueConfig,
+) -> Result<(), DatabaseError> {
+    let query = "INSERT INTO dpu_applied_nvue_configs (dpu_id, config) VALUES ($1, $2)
+        ON CONFLICT (dpu_id) DO UPDATE SET config = EXCLUDED.config, reported_at = NOW()
+        WHERE dpu_applied_nvue_configs.config IS DISTINCT FROM EXCLUDED.config";
+    sqlx::query(query)
+        .bind(dpu_id)
+        .bind(Json(config))
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(())
+}
+
+pub async fn find(
+    txn: &mut PgConnection,
+    dpu_id: &MachineId,
+) -> Result<Option<AppliedNvueConfig>, DatabaseError> {
+    let query = "SELECT config FROM dpu_applied_nvue_configs WHERE dpu_id = $1";
+    let row: Option<(Json<AppliedNvueConfig>,)> = sqlx::query_as(query)
+        .bind(dpu_id)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(row.map(|(Json(config),)| config))
+}
@@ -0,0 +1,60 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//! Queries backing the fleet lifecycle dashboard in the admin web UI.
+
+use carbide_uuid::machine::{MachineId, MachineType};
+use sqlx::FromRow;
+
+use crate::db_read::DbReader;
+use crate::{DatabaseError, DatabaseResult};
+
+/// A host's current controller state plus the attributes the dashboard groups by.
+#[derive(Debug, Clone, FromRow)]
+pub struct HostLifecycleAttributes {
+    pub machine_id: MachineId,
+    /// The raw `controller_state` JSON, parsed by the caller so that states
+    /// written by older versions don't fail the whole query.
+    pub controller_state: String,
+    pub hw_sku: Option<String>,
+    pub sys_vendor: Option<String>,
+}
+
+/// Load every host's current state, SKU, and vendor (from the latest
+/// discovered topology).
+pub async fn find_host_attributes(
+    txn: impl DbReader<'_>,
+) -> DatabaseResult<Vec<HostLifecycleAttributes>> {
+    let query = r#"
+        SELECT m.id AS machine_id,
+            m.controller_state::TEXT AS controller_state,
+            m.hw_sku,
+            mt.topology->'discovery_data'->'Info'->'dmi_data'->>'sys_vendor' AS sys_vendor
+        FROM machines m
+        LEFT JOIN LATERAL (
+            SELECT topology FROM machine_topologies
+            WHERE machine_id = m.id
+            ORDER BY created DESC
+            LIMIT 1
+        ) mt ON TRUE
+        WHERE starts_with(m.id, $1)
+    "#;
+    sqlx::query_as(query)
+        .bind(MachineType::Host.id_prefix())
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
@@ -0,0 +1,69 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Health reports as last dispatched by the alert dispatcher, see
+//! [`model::alert_dispatch`].
+
+use health_report::HealthReport;
+use model::alert_dispatch::{AlertObjectKind, DispatchedHealthReport};
+use sqlx::PgConnection;
+
+use crate::db_read::DbReader;
+use crate::{DatabaseError, DatabaseResult};
+
+pub async fn find_all(txn: impl DbReader<'_>) -> DatabaseResult<Vec<DispatchedHealthReport>> {
+    let query = "SELECT object_kind, object_id, report, updated FROM health_alert_dispatch_state";
+    sqlx::query_as(query)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+pub async fn upsert(
+    txn: &mut PgConnection,
+    object_kind: AlertObjectKind,
+    object_id: &str,
+    report: &HealthReport,
+) -> DatabaseResult<()> {
+    let query = "INSERT INTO health_alert_dispatch_state (object_kind, object_id, report)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (object_kind, object_id)
+        DO UPDATE SET report = EXCLUDED.report, updated = NOW()";
+    sqlx::query(query)
+        .bind(object_kind.as_str())
+        .bind(object_id)
+        .bind(sqlx::types::Json(report))
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(())
+}
+
+pub async fn delete(
+    txn: &mut PgConnection,
+    object_kind: AlertObjectKind,
+    object_id: &str,
+) -> DatabaseResult<()> {
+    let query = "DELETE FROM health_alert_dispatch_state WHERE object_kind = $1 AND object_id = $2";
+    sqlx::query(query)
+        .bind(object_kind.as_str())
+        .bind(object_id)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(())
+}
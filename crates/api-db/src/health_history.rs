@@ -121,6 +121,29 @@ pub async fn find_by_object_ids(
     Ok(histories)
 }
 
+/// Retrieve the health an object had at `at`, i.e. the last health recorded
+/// at or before that time
+pub async fn find_at(
+    txn: &mut PgConnection,
+    table_id: HealthHistoryTableId,
+    object_id: &impl std::fmt::Display,
+    at: DateTime<Utc>,
+) -> Result<Option<HealthHistoryRecord>, DatabaseError> {
+    let sql_table = table_id.sql_table();
+    let query = format!(
+        "SELECT object_id, health, time FROM {sql_table}
+        WHERE object_id = $1 AND time <= $2
+        ORDER BY id DESC LIMIT 1"
+    );
+    let record: Option<DbHealthHistoryRecord> = sqlx::query_as(&query)
+        .bind(object_id.to_string())
+        .bind(at)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(&query, e))?;
+    Ok(record.map(Into::into))
+}
+
 /// Store a new health history record for an object
 pub async fn persist(
     txn: &mut PgConnection,
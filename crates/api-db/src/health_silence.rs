@@ -0,0 +1,125 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Health silences, see [`model::health_silence`].
+
+use chrono::{DateTime, Utc};
+use model::health_silence::{HealthSilence, HealthSilenceMatcher};
+use sqlx::PgConnection;
+
+use crate::db_read::DbReader;
+use crate::{DatabaseError, DatabaseResult};
+
+const SILENCE_COLUMNS: &str =
+    "id, matcher, author, reason, starts_at, expires_at, created, ended_at, ended_by";
+
+pub async fn create(
+    txn: &mut PgConnection,
+    matcher: &HealthSilenceMatcher,
+    author: &str,
+    reason: &str,
+    starts_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+) -> DatabaseResult<HealthSilence> {
+    let query = format!(
+        "INSERT INTO health_silences (matcher, author, reason, starts_at, expires_at)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING {SILENCE_COLUMNS}"
+    );
+    sqlx::query_as(&query)
+        .bind(sqlx::types::Json(matcher))
+        .bind(author)
+        .bind(reason)
+        .bind(starts_at)
+        .bind(expires_at)
+        .fetch_one(txn)
+        .await
+        .map_err(|e| DatabaseError::query(&query, e))
+}
+
+pub async fn find_by_id(txn: impl DbReader<'_>, id: i64) -> DatabaseResult<Option<HealthSilence>> {
+    let query = format!("SELECT {SILENCE_COLUMNS} FROM health_silences WHERE id = $1");
+    sqlx::query_as(&query)
+        .bind(id)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(&query, e))
+}
+
+/// All silences which have not yet ended, including those which start in the
+/// future. With `include_ended`, expired and ended silences are returned too.
+pub async fn find_all(
+    txn: impl DbReader<'_>,
+    include_ended: bool,
+) -> DatabaseResult<Vec<HealthSilence>> {
+    let query = format!(
+        "SELECT {SILENCE_COLUMNS} FROM health_silences
+        WHERE $1 OR (ended_at IS NULL AND expires_at > NOW())
+        ORDER BY id"
+    );
+    sqlx::query_as(&query)
+        .bind(include_ended)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(&query, e))
+}
+
+/// The silences that currently apply
+pub async fn find_active(txn: impl DbReader<'_>) -> DatabaseResult<Vec<HealthSilence>> {
+    let query = format!(
+        "SELECT {SILENCE_COLUMNS} FROM health_silences
+        WHERE ended_at IS NULL AND starts_at <= NOW() AND expires_at > NOW()
+        ORDER BY id"
+    );
+    sqlx::query_as(&query)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(&query, e))
+}
+
+/// Ends a silence before it expires. Returns `None` if the silence does not
+/// exist or already ended.
+pub async fn end(
+    txn: &mut PgConnection,
+    id: i64,
+    ended_by: &str,
+) -> DatabaseResult<Option<HealthSilence>> {
+    let query = format!(
+        "UPDATE health_silences SET ended_at = NOW(), ended_by = $2
+        WHERE id = $1 AND ended_at IS NULL AND expires_at > NOW()
+        RETURNING {SILENCE_COLUMNS}"
+    );
+    sqlx::query_as(&query)
+        .bind(id)
+        .bind(ended_by)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(&query, e))
+}
+
+/// Records the expiry of all silences whose expiry time passed, and returns them
+pub async fn mark_expired(txn: &mut PgConnection) -> DatabaseResult<Vec<HealthSilence>> {
+    let query = format!(
+        "UPDATE health_silences SET ended_at = expires_at
+        WHERE ended_at IS NULL AND expires_at <= NOW()
+        RETURNING {SILENCE_COLUMNS}"
+    );
+    sqlx::query_as(&query)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(&query, e))
+}
@@ -20,6 +20,7 @@
 #![allow(unknown_lints)]
 
 pub mod attestation;
+pub mod authorization_policy;
 pub mod bmc_metadata;
 pub mod carbide_version;
 pub mod compute_allocation;
@@ -30,6 +31,7 @@ pub mod dhcp_record;
 pub mod dns;
 pub mod dpa_interface;
 pub mod dpu_agent_upgrade_policy;
+pub mod dpu_applied_nvue_config;
 pub mod dpu_machine_update;
 pub mod dpu_remediation;
 pub mod expected_machine;
@@ -39,8 +41,11 @@ pub mod expected_switch;
 pub mod explored_endpoints;
 pub mod explored_managed_host;
 pub mod extension_service;
+pub mod fleet_lifecycle;
+pub mod health_alert_dispatch;
 pub mod health_history;
 pub mod health_report;
+pub mod health_silence;
 pub mod host_machine_update;
 pub mod ib_partition;
 pub mod instance;
@@ -58,7 +63,9 @@ pub mod machine_validation_config;
 pub mod machine_validation_result;
 pub mod machine_validation_suites;
 pub mod managed_host;
+pub mod managed_host_history;
 pub mod measured_boot;
+pub mod media_sanitization;
 pub mod migrations;
 pub mod network_devices;
 pub mod network_prefix;
@@ -71,11 +78,13 @@ pub mod os_image;
 pub mod power_options;
 pub mod power_shelf;
 pub mod predicted_machine_interface;
+pub mod prefix_list;
 pub mod queries;
 pub mod rack;
 pub mod rack_firmware;
 pub mod redfish_actions;
 pub mod resource_pool;
+pub mod retention;
 pub mod route_servers;
 pub mod site_exploration_report;
 pub mod sku;
@@ -84,7 +93,6 @@ pub mod switch;
 pub mod tenant;
 pub mod tenant_identity_config;
 pub mod tenant_keyset;
-pub mod trim_table;
 pub mod vpc;
 pub mod vpc_dpu_loopback;
 pub mod vpc_peering;
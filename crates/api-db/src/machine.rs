@@ -1189,7 +1189,7 @@ pub async fn try_sync_stable_id_with_current_machine_id_for_host(
         };
     }
 
-    // Update the machine state and health history to account for the rename
+    // Update the machine state, health and managed host history to account for the rename
     crate::state_history::update_object_ids(
         txn,
         crate::state_history::StateHistoryTableId::Machine,
@@ -1204,6 +1204,8 @@ pub async fn try_sync_stable_id_with_current_machine_id_for_host(
         &stable_machine_id,
     )
     .await?;
+    crate::managed_host_history::update_machine_ids(txn, current_machine_id, stable_machine_id)
+        .await?;
 
     // Table machine_interfaces has a FK ON UPDATE CASCADE so machine_interfaces.machine_id will
     // also change.
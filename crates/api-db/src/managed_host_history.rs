@@ -0,0 +1,271 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//! Reconstructs managed hosts at a point in time from the history tables
+
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+
+use carbide_uuid::instance::InstanceId;
+use carbide_uuid::machine::{MachineId, MachineInterfaceId};
+use chrono::{DateTime, Utc};
+use config_version::ConfigVersion;
+use mac_address::MacAddress;
+use model::managed_host_history::{
+    ConfigVersionRecord, InstanceStateAt, InterfaceAddressesAt, MachineStateAt, ManagedHostStateAt,
+};
+use sqlx::{FromRow, PgConnection};
+
+use crate::DatabaseError;
+use crate::health_history::HealthHistoryTableId;
+use crate::state_history::StateHistoryTableId;
+
+#[derive(Debug, FromRow)]
+struct DbConfigVersionRecord {
+    network_config_version: ConfigVersion,
+    timestamp: DateTime<Utc>,
+}
+
+#[derive(Debug, FromRow)]
+struct DbInstanceConfigRecord {
+    instance_id: InstanceId,
+    config_version: Option<ConfigVersion>,
+    network_config_version: Option<ConfigVersion>,
+    deleted: Option<DateTime<Utc>>,
+    timestamp: DateTime<Utc>,
+}
+
+#[derive(Debug, FromRow)]
+struct DbInterfaceAddressRecord {
+    interface_id: MachineInterfaceId,
+    mac_address: Option<MacAddress>,
+    address: IpAddr,
+}
+
+/// Selects the last record at `$2` for every address on every interface that ever
+/// belonged to machine `$1`. It tells whether the address was assigned, and to which
+/// machine and DPU.
+const LAST_INTERFACE_ADDRESS_RECORDS: &str = "SELECT DISTINCT ON (interface_id, address)
+        interface_id, machine_id, attached_dpu_machine_id, mac_address, address, assigned
+    FROM machine_interface_address_history
+    WHERE timestamp <= $2 AND interface_id IN (
+        SELECT interface_id FROM machine_interface_address_history WHERE machine_id = $1
+    )
+    ORDER BY interface_id, address, id DESC";
+
+/// Reconstructs a managed host, the DPUs attached to it and its instance as they
+/// looked at `at`
+pub async fn load_at(
+    txn: &mut PgConnection,
+    host_machine_id: &MachineId,
+    at: DateTime<Utc>,
+) -> Result<ManagedHostStateAt, DatabaseError> {
+    let host = load_machine_at(txn, host_machine_id, at).await?;
+    let mut dpus = Vec::new();
+    for dpu_machine_id in find_attached_dpu_ids_at(txn, host_machine_id, at).await? {
+        dpus.push(load_machine_at(txn, &dpu_machine_id, at).await?);
+    }
+    let instance = find_instance_at(txn, host_machine_id, at).await?;
+
+    Ok(ManagedHostStateAt {
+        time: at,
+        host,
+        dpus,
+        instance,
+    })
+}
+
+async fn load_machine_at(
+    txn: &mut PgConnection,
+    machine_id: &MachineId,
+    at: DateTime<Utc>,
+) -> Result<MachineStateAt, DatabaseError> {
+    Ok(MachineStateAt {
+        machine_id: *machine_id,
+        controller_state: crate::state_history::find_at(
+            txn,
+            StateHistoryTableId::Machine,
+            machine_id,
+            at,
+        )
+        .await?,
+        health: crate::health_history::find_at(txn, HealthHistoryTableId::Machine, machine_id, at)
+            .await?,
+        network_config_version: find_network_config_version_at(txn, machine_id, at).await?,
+        interfaces: find_interface_addresses_at(txn, machine_id, at).await?,
+    })
+}
+
+/// Returns the host which a DPU was attached to at `at`. Only interfaces which had an
+/// address assigned at that time are taken into account.
+pub async fn find_host_of_dpu_at(
+    txn: &mut PgConnection,
+    dpu_machine_id: &MachineId,
+    at: DateTime<Utc>,
+) -> Result<Option<MachineId>, DatabaseError> {
+    let query = "SELECT machine_id FROM (
+            SELECT DISTINCT ON (interface_id, address) id, machine_id, attached_dpu_machine_id, assigned
+            FROM machine_interface_address_history
+            WHERE timestamp <= $2 AND interface_id IN (
+                SELECT interface_id FROM machine_interface_address_history
+                WHERE attached_dpu_machine_id = $1
+            )
+            ORDER BY interface_id, address, id DESC
+        ) last
+        WHERE assigned AND attached_dpu_machine_id = $1 AND machine_id <> $1
+        ORDER BY id DESC
+        LIMIT 1";
+    let id: Option<(MachineId,)> = sqlx::query_as(query)
+        .bind(dpu_machine_id.to_string())
+        .bind(at)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(id.map(|(id,)| id))
+}
+
+/// Returns the DPUs which were attached to the interfaces of a host at `at`. Only
+/// interfaces which had an address assigned at that time are taken into account.
+async fn find_attached_dpu_ids_at(
+    txn: &mut PgConnection,
+    host_machine_id: &MachineId,
+    at: DateTime<Utc>,
+) -> Result<Vec<MachineId>, DatabaseError> {
+    let query = format!(
+        "SELECT DISTINCT attached_dpu_machine_id FROM ({LAST_INTERFACE_ADDRESS_RECORDS}) last
+        WHERE assigned AND machine_id = $1 AND attached_dpu_machine_id IS NOT NULL
+        ORDER BY attached_dpu_machine_id"
+    );
+    let ids: Vec<(MachineId,)> = sqlx::query_as(&query)
+        .bind(host_machine_id.to_string())
+        .bind(at)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(&query, e))?;
+    Ok(ids.into_iter().map(|(id,)| id).collect())
+}
+
+/// Returns the network config version a machine had at `at`
+pub async fn find_network_config_version_at(
+    txn: &mut PgConnection,
+    machine_id: &MachineId,
+    at: DateTime<Utc>,
+) -> Result<Option<ConfigVersionRecord>, DatabaseError> {
+    let query = "SELECT network_config_version, timestamp FROM machine_network_config_history
+        WHERE machine_id = $1 AND timestamp <= $2
+        ORDER BY id DESC LIMIT 1";
+    let record: Option<DbConfigVersionRecord> = sqlx::query_as(query)
+        .bind(machine_id.to_string())
+        .bind(at)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(record.map(|record| ConfigVersionRecord {
+        version: record.network_config_version,
+        time: record.timestamp,
+    }))
+}
+
+/// Returns the instance a host had at `at`. Returns `None` if there was no instance
+/// on the host at that time.
+pub async fn find_instance_at(
+    txn: &mut PgConnection,
+    host_machine_id: &MachineId,
+    at: DateTime<Utc>,
+) -> Result<Option<InstanceStateAt>, DatabaseError> {
+    let query = "SELECT instance_id, config_version, network_config_version, deleted, timestamp
+        FROM instance_config_history
+        WHERE machine_id = $1 AND timestamp <= $2
+        ORDER BY id DESC LIMIT 1";
+    let record: Option<DbInstanceConfigRecord> = sqlx::query_as(query)
+        .bind(host_machine_id.to_string())
+        .bind(at)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+
+    // Versions are NULL on the record which is written when an instance gets removed
+    Ok(record.and_then(|record| {
+        Some(InstanceStateAt {
+            instance_id: record.instance_id,
+            config_version: record.config_version?,
+            network_config_version: record.network_config_version?,
+            deleted: record.deleted,
+            time: record.timestamp,
+        })
+    }))
+}
+
+/// Returns the addresses which were assigned to the interfaces of a machine at `at`,
+/// sorted by interface ID
+pub async fn find_interface_addresses_at(
+    txn: &mut PgConnection,
+    machine_id: &MachineId,
+    at: DateTime<Utc>,
+) -> Result<Vec<InterfaceAddressesAt>, DatabaseError> {
+    let query = format!(
+        "SELECT interface_id, mac_address, address FROM ({LAST_INTERFACE_ADDRESS_RECORDS}) last
+        WHERE assigned AND machine_id = $1
+        ORDER BY interface_id, address"
+    );
+    let records: Vec<DbInterfaceAddressRecord> = sqlx::query_as(&query)
+        .bind(machine_id.to_string())
+        .bind(at)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(&query, e))?;
+
+    let mut interfaces: BTreeMap<MachineInterfaceId, InterfaceAddressesAt> = BTreeMap::new();
+    for record in records {
+        let interface =
+            interfaces
+                .entry(record.interface_id)
+                .or_insert_with(|| InterfaceAddressesAt {
+                    interface_id: record.interface_id,
+                    mac_address: record.mac_address,
+                    addresses: Vec::new(),
+                });
+        interface.addresses.push(record.address);
+    }
+    Ok(interfaces.into_values().collect())
+}
+
+/// Renames all history entries using one machine ID into using another machine ID
+pub async fn update_machine_ids(
+    txn: &mut PgConnection,
+    old_machine_id: &MachineId,
+    new_machine_id: &MachineId,
+) -> Result<(), DatabaseError> {
+    for (table, column) in [
+        ("instance_config_history", "machine_id"),
+        ("machine_network_config_history", "machine_id"),
+        ("machine_interface_address_history", "machine_id"),
+        (
+            "machine_interface_address_history",
+            "attached_dpu_machine_id",
+        ),
+    ] {
+        let query = format!("UPDATE {table} SET {column}=$1 WHERE {column}=$2");
+        sqlx::query(&query)
+            .bind(new_machine_id.to_string())
+            .bind(old_machine_id.to_string())
+            .execute(&mut *txn)
+            .await
+            .map_err(|e| DatabaseError::query(&query, e))?;
+    }
+
+    Ok(())
+}
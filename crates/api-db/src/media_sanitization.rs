@@ -0,0 +1,132 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//! Media sanitization reports, see [`model::media_sanitization`].
+
+use carbide_uuid::instance::InstanceId;
+use carbide_uuid::machine::MachineId;
+use model::media_sanitization::{MediaSanitizationRecord, MediaSanitizationReport};
+use model::tenant::TenantOrganizationId;
+use sqlx::PgConnection;
+
+use crate::db_read::DbReader;
+use crate::{DatabaseError, DatabaseResult};
+
+const REPORT_COLUMNS: &str = "id, machine_id, instance_id, tenant_organization_id, released_at, \
+    completed_at, records";
+
+/// Opens the report for the release of an instance. It is completed by the
+/// next [`complete`] call for the machine.
+pub async fn open(
+    txn: &mut PgConnection,
+    machine_id: &MachineId,
+    instance_id: InstanceId,
+    tenant_organization_id: &TenantOrganizationId,
+) -> DatabaseResult<MediaSanitizationReport> {
+    let query = format!(
+        "INSERT INTO media_sanitization_reports
+            (machine_id, instance_id, tenant_organization_id, released_at)
+        VALUES ($1, $2, $3, NOW())
+        RETURNING {REPORT_COLUMNS}"
+    );
+    sqlx::query_as(&query)
+        .bind(machine_id)
+        .bind(instance_id)
+        .bind(tenant_organization_id.as_str())
+        .fetch_one(txn)
+        .await
+        .map_err(|e| DatabaseError::query(&query, e))
+}
+
+/// Stores the records of a cleanup reported by a machine in its latest open
+/// report. The report stays open until a cleanup `succeeded`, so that the
+/// records of a retry replace those of a failed attempt in the same report.
+/// If the cleanup did not follow an instance release, a report without an
+/// instance is created.
+pub async fn complete(
+    txn: &mut PgConnection,
+    machine_id: &MachineId,
+    records: &[MediaSanitizationRecord],
+    succeeded: bool,
+) -> DatabaseResult<MediaSanitizationReport> {
+    let records = sqlx::types::Json(records);
+    let query = format!(
+        "UPDATE media_sanitization_reports
+        SET completed_at = CASE WHEN $3 THEN NOW() END, records = $2
+        WHERE id = (
+            SELECT id FROM media_sanitization_reports
+            WHERE machine_id = $1 AND completed_at IS NULL
+            ORDER BY created DESC LIMIT 1
+        )
+        RETURNING {REPORT_COLUMNS}"
+    );
+    let report = sqlx::query_as(&query)
+        .bind(machine_id)
+        .bind(&records)
+        .bind(succeeded)
+        .fetch_optional(&mut *txn)
+        .await
+        .map_err(|e| DatabaseError::query(&query, e))?;
+    if let Some(report) = report {
+        return Ok(report);
+    }
+
+    let query = format!(
+        "INSERT INTO media_sanitization_reports (machine_id, completed_at, records)
+        VALUES ($1, NOW(), $2)
+        RETURNING {REPORT_COLUMNS}"
+    );
+    sqlx::query_as(&query)
+        .bind(machine_id)
+        .bind(&records)
+        .fetch_one(txn)
+        .await
+        .map_err(|e| DatabaseError::query(&query, e))
+}
+
+/// The report of the release of an instance.
+pub async fn find_by_instance_id(
+    txn: impl DbReader<'_>,
+    instance_id: InstanceId,
+) -> DatabaseResult<Option<MediaSanitizationReport>> {
+    let query = format!(
+        "SELECT {REPORT_COLUMNS} FROM media_sanitization_reports
+        WHERE instance_id = $1
+        ORDER BY created DESC LIMIT 1"
+    );
+    sqlx::query_as(&query)
+        .bind(instance_id)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(&query, e))
+}
+
+/// The latest report of a machine, whether completed or not.
+pub async fn find_latest_by_machine_id(
+    txn: impl DbReader<'_>,
+    machine_id: &MachineId,
+) -> DatabaseResult<Option<MediaSanitizationReport>> {
+    let query = format!(
+        "SELECT {REPORT_COLUMNS} FROM media_sanitization_reports
+        WHERE machine_id = $1
+        ORDER BY created DESC LIMIT 1"
+    );
+    sqlx::query_as(&query)
+        .bind(machine_id)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(&query, e))
+}
@@ -16,6 +16,7 @@
  */
 use carbide_uuid::instance::InstanceId;
 use carbide_uuid::network_security_group::NetworkSecurityGroupId;
+use carbide_uuid::prefix_list::PrefixListId;
 use carbide_uuid::vpc::VpcId;
 use config_version::ConfigVersion;
 use model::metadata::Metadata;
@@ -163,6 +164,43 @@ pub async fn find_by_ids(
         .map_err(|err| DatabaseError::query(builder.sql(), err))
 }
 
+/// Queries the DB for non-deleted NetworkSecurityGroup records with
+/// rules that reference any of the supplied prefix lists
+///
+/// * `txn`             - A reference to an active DB transaction
+/// * `prefix_list_ids` - A list of PrefixListId values to look for in the source or
+///   destination of NetworkSecurityGroup rules
+/// * `for_update`      - A boolean flag to acquire DB locks for synchronization
+pub async fn find_by_prefix_list_ids(
+    txn: &mut PgConnection,
+    prefix_list_ids: &[PrefixListId],
+    for_update: bool,
+) -> Result<Vec<NetworkSecurityGroup>, DatabaseError> {
+    let mut builder = sqlx::QueryBuilder::new(
+        "SELECT * from network_security_groups nsg WHERE deleted is NULL
+            AND EXISTS (
+                SELECT 1 FROM jsonb_array_elements(nsg.rules) r
+                WHERE r #>> '{src_net,PrefixList}' = ANY(",
+    );
+
+    let prefix_list_ids: Vec<String> = prefix_list_ids.iter().map(|id| id.to_string()).collect();
+    builder.push_bind(&prefix_list_ids);
+    builder.push(") OR r #>> '{dst_net,PrefixList}' = ANY(");
+    builder.push_bind(&prefix_list_ids);
+    builder.push(")) ");
+
+    if for_update {
+        builder.push(" ORDER BY id ");
+        builder.push(" FOR UPDATE ");
+    }
+
+    builder
+        .build_query_as()
+        .fetch_all(txn)
+        .await
+        .map_err(|err| DatabaseError::query(builder.sql(), err))
+}
+
 /// Queries the DB for objects that have attached NetworkSecurityGroups
 ///
 /// * `txn`                        - A reference to an active DB transaction
@@ -466,6 +504,49 @@ pub async fn update(
     }
 }
 
+/// Increments the version of a NetworkSecurityGroup record without changing
+/// anything else, so that a change to something it references, like a prefix list,
+/// is treated as a new version of the NetworkSecurityGroup for propagation.
+///
+/// * `txn`              - A reference to an active DB transaction
+/// * `id`               - A reference to the NetworkSecurityGroupId of the record to update
+/// * `expected_version` - The version the record is expected to have prior to the update.
+///   ***Callers are expected to hold a lock on the record.***
+/// * `updated_by`       - Optional String containing an ID to track the user who caused
+///   the update
+pub async fn increment_version(
+    txn: &mut PgConnection,
+    id: &NetworkSecurityGroupId,
+    expected_version: ConfigVersion,
+    updated_by: Option<&str>,
+) -> Result<NetworkSecurityGroup, DatabaseError> {
+    let query = "UPDATE network_security_groups
+            SET
+                version=$1::varchar,
+                updated_by=$2::varchar
+            WHERE
+                id=$3::varchar
+                AND version = $4::varchar
+                AND deleted IS NULL
+            RETURNING *";
+
+    match sqlx::query_as::<Postgres, NetworkSecurityGroup>(query)
+        .bind(expected_version.increment())
+        .bind(updated_by)
+        .bind(id)
+        .bind(expected_version)
+        .fetch_one(txn)
+        .await
+    {
+        Ok(network_security_group) => Ok(network_security_group),
+        Err(sqlx::Error::RowNotFound) => Err(DatabaseError::ConcurrentModificationError(
+            "NetworkSecurityGroup",
+            expected_version.to_string(),
+        )),
+        Err(e) => Err(DatabaseError::query(query, e)),
+    }
+}
+
 /// Soft deletes an instance type by updating the deleted column in the DB.
 /// If the record with that ID is already deleted, nothing changes and Ok(None)
 /// is returned.
@@ -0,0 +1,254 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use carbide_uuid::prefix_list::PrefixListId;
+use config_version::ConfigVersion;
+use ipnetwork::IpNetwork;
+use model::metadata::Metadata;
+use model::prefix_list::PrefixList;
+use model::tenant::TenantOrganizationId;
+use sqlx::{PgConnection, Postgres};
+
+use crate::DatabaseError;
+
+/// Creates a new PrefixList DB record.  It enforces a unique `name` by
+/// only creating if there is no active record found with the same name.
+///
+/// * `txn`                    - A reference to an active DB transaction
+/// * `id`                     - A reference to a PrefixListId to be set as
+///   the id for the new PrefixList
+/// * `tenant_organization_id` - A reference to a TenantOrganizationId containing the
+///   tenant org that owns this PrefixList
+/// * `created_by`             - Optional String containing an ID to track the user who
+///   created the PrefixList
+/// * `metadata`               - A reference to a Metadata struct containing extra
+///   details about the PrefixList
+/// * `prefixes`               - The prefixes in the list.
+pub async fn create(
+    txn: &mut PgConnection,
+    id: &PrefixListId,
+    tenant_organization_id: &TenantOrganizationId,
+    created_by: Option<&str>,
+    metadata: &Metadata,
+    prefixes: &[IpNetwork],
+) -> Result<PrefixList, DatabaseError> {
+    let query = "INSERT INTO prefix_lists
+                (id, tenant_organization_id, name, labels, description, prefixes, version, created_by)
+            SELECT $1, $2::varchar, $3::varchar, $4::jsonb, $5::varchar, $6::jsonb, $7::varchar, $8::varchar
+            WHERE NOT EXISTS
+                /* There should be a unique constraint on id.  The condition here is just defensive. */
+                (SELECT id FROM prefix_lists WHERE (id=$1 OR (name=$3::varchar AND tenant_organization_id=$2::varchar)) AND deleted IS NULL)
+            RETURNING *";
+
+    match sqlx::query_as::<Postgres, PrefixList>(query)
+        .bind(id)
+        .bind(tenant_organization_id.to_string())
+        .bind(&metadata.name)
+        .bind(sqlx::types::Json(&metadata.labels))
+        .bind(&metadata.description)
+        .bind(sqlx::types::Json(prefixes))
+        .bind(ConfigVersion::initial())
+        .bind(created_by)
+        .fetch_one(txn)
+        .await
+    {
+        Ok(prefix_list) => Ok(prefix_list),
+        // This error should only show up when we didn't
+        // create a record because the subquery found an existing name already.
+        Err(sqlx::Error::RowNotFound) => Err(DatabaseError::AlreadyFoundError {
+            kind: "PrefixList",
+            id: metadata.name.clone(),
+        }),
+        Err(e) => Err(DatabaseError::query(query, e)),
+    }
+}
+
+/// Returns a list of IDs for all non-deleted PrefixList records.
+///
+/// * `txn`                    - A reference to an active DB transaction
+/// * `name`                   - Optional String containing the name of a desired
+///   PrefixList
+/// * `tenant_organization_id` - Optional TenantOrganizationId containing the tenant
+///   org to match against PrefixList records.
+/// * `for_update`             - A boolean flag to acquire DB locks for
+///   synchronization
+pub async fn find_ids(
+    txn: &mut PgConnection,
+    name: Option<&str>,
+    tenant_organization_id: Option<&TenantOrganizationId>,
+    for_update: bool,
+) -> Result<Vec<PrefixListId>, DatabaseError> {
+    let mut builder = sqlx::QueryBuilder::new("SELECT id FROM prefix_lists WHERE deleted is NULL");
+
+    if name.is_some() {
+        builder.push(" AND name = ");
+        builder.push_bind(name);
+    }
+
+    if tenant_organization_id.is_some() {
+        builder.push(" AND tenant_organization_id = ");
+        builder.push_bind(tenant_organization_id.map(|t| t.to_string()));
+    }
+
+    if for_update {
+        builder.push(" ORDER BY id ");
+        builder.push(" FOR UPDATE ");
+    }
+
+    builder
+        .build_query_as()
+        .fetch_all(txn)
+        .await
+        .map_err(|err: sqlx::Error| DatabaseError::query(builder.sql(), err))
+}
+
+/// Queries the DB for non-deleted PrefixList records
+/// based on the supplied list of IDs
+///
+/// * `txn`                    - A reference to an active DB transaction
+/// * `prefix_list_ids`        - A list of PrefixListId values to use for
+///   querying the Db for active PrefixList records
+/// * `tenant_organization_id` - Optional reference to TenantOrganizationId containing the
+///   tenant org to match against PrefixList records.
+/// * `for_update`             - A boolean flag to acquire DB locks for synchronization
+pub async fn find_by_ids(
+    txn: &mut PgConnection,
+    prefix_list_ids: &[PrefixListId],
+    tenant_organization_id: Option<&TenantOrganizationId>,
+    for_update: bool,
+) -> Result<Vec<PrefixList>, DatabaseError> {
+    let mut builder = sqlx::QueryBuilder::new("SELECT * from prefix_lists WHERE deleted is NULL");
+
+    builder.push(" AND id = ANY(");
+    builder.push_bind(prefix_list_ids);
+    builder.push(") ");
+
+    if tenant_organization_id.is_some() {
+        builder.push(" AND tenant_organization_id = ");
+        builder.push_bind(tenant_organization_id.map(|t| t.to_string()));
+    }
+
+    if for_update {
+        builder.push(" ORDER BY id ");
+        builder.push(" FOR UPDATE ");
+    }
+
+    builder
+        .build_query_as()
+        .fetch_all(txn)
+        .await
+        .map_err(|err: sqlx::Error| DatabaseError::query(builder.sql(), err))
+}
+
+/// Updates a PrefixList record in the DB.
+///
+/// * `txn`                    - A reference to an active DB transaction
+/// * `id`                     - A reference to the PrefixListId of the PrefixList to update
+/// * `tenant_organization_id` - A reference to a TenantOrganizationId for the tenant org that owns
+///   this PrefixList.  The update will will be ignored if
+///   this ID does not match that of the requested record.
+///   ***Callers are expected to verify the relationship prior to calling this function.***
+/// * `metadata`               - A reference to a Metadata struct containing extra details about
+///   the PrefixList
+/// * `prefixes`               - The new prefixes of the list.
+/// * `expected_version`       - The version the record is expected to have prior to the update.
+///   This will be auto-incremented. If this version passed in does not match the reality of the
+///   record, the update will be rejected, but ***callers are expected to have verified this version
+///   matches the record in advance.***
+/// * `updated_by`             - Optional String containing an ID to track the user who updated the
+///   PrefixList
+pub async fn update(
+    txn: &mut PgConnection,
+    id: &PrefixListId,
+    tenant_organization_id: &TenantOrganizationId,
+    metadata: &Metadata,
+    prefixes: &[IpNetwork],
+    expected_version: ConfigVersion,
+    updated_by: Option<&str>,
+) -> Result<PrefixList, DatabaseError> {
+    let query = "UPDATE prefix_lists
+            SET
+                name=$1::varchar,
+                labels=$2::jsonb,
+                description=$3::varchar,
+                prefixes=$4::jsonb,
+                version=$5::varchar,
+                updated_by=$6::varchar
+            WHERE
+                /*
+                    All but the final `AND NOT EXISTS` are here to be defensive.
+                    The cases should have already been covered by a query in advance.
+                */
+                id=$7
+                AND version = $8::varchar
+                AND deleted IS NULL
+                AND tenant_organization_id = $9::varchar
+                AND NOT EXISTS
+                    (SELECT id FROM prefix_lists WHERE id!=$7 AND (name=$1::varchar AND tenant_organization_id=$9::varchar AND deleted IS NULL))
+            RETURNING *";
+
+    match sqlx::query_as::<Postgres, PrefixList>(query)
+        .bind(&metadata.name)
+        .bind(sqlx::types::Json(&metadata.labels))
+        .bind(&metadata.description)
+        .bind(sqlx::types::Json(prefixes))
+        .bind(expected_version.increment())
+        .bind(updated_by)
+        .bind(id)
+        .bind(expected_version)
+        .bind(tenant_organization_id.to_string())
+        .fetch_one(txn)
+        .await
+    {
+        Ok(prefix_list) => Ok(prefix_list),
+        // This error should only show up when we didn't
+        // update a record because the subquery found an existing name already.
+        // deleted and version should have already been checked and reported
+        // before calling update()
+        Err(sqlx::Error::RowNotFound) => Err(DatabaseError::AlreadyFoundError {
+            kind: "PrefixList",
+            id: metadata.name.clone(),
+        }),
+        Err(e) => Err(DatabaseError::query(query, e)),
+    }
+}
+
+/// Soft deletes a prefix list by updating the deleted column in the DB.
+/// If the record with that ID is already deleted, nothing changes and Ok(None)
+/// is returned.
+///
+/// This does ***NOT*** check whether any NetworkSecurityGroup rules reference the list.
+///
+/// * `txn`                    - A reference to an active DB transaction
+/// * `prefix_list_id`         - A PrefixListId for the PrefixList to be soft-deleted.
+/// * `tenant_organization_id` - A reference to a TenantOrganizationId of the tenant organization
+///   that owns the PrefixList.  The delete will be ignored if this ID does not match that of
+///   the requested record. ***Callers are expected to verify the relationship prior to calling this
+///   function.***
+pub async fn soft_delete(
+    txn: &mut PgConnection,
+    prefix_list_id: &PrefixListId,
+    tenant_organization_id: &TenantOrganizationId,
+) -> Result<Option<PrefixListId>, DatabaseError> {
+    let query = "UPDATE prefix_lists SET deleted=NOW() WHERE id=$1 AND tenant_organization_id=$2::varchar AND deleted is NULL RETURNING id";
+
+    sqlx::query_as(query)
+        .bind(prefix_list_id)
+        .bind(tenant_organization_id.to_string())
+        .fetch_optional(txn)
+        .await
+        .map_err(|err: sqlx::Error| DatabaseError::query(query, err))
+}
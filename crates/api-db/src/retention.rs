@@ -0,0 +1,350 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//! Trimming of history tables, see [`model::retention`].
+
+use chrono::{DateTime, Utc};
+use model::retention::{RetentionPolicy, RetentionTarget};
+use sqlx::PgConnection;
+
+use crate::{DatabaseError, DatabaseResult};
+
+/// Describes how the rows of a [`RetentionTarget`] are identified, grouped and ordered.
+struct TableSpec {
+    table: &'static str,
+    /// Column identifying a row. `ctid` for tables without a primary key, whose
+    /// rows are not updated once they can be trimmed.
+    key: &'static str,
+    /// Columns identifying the object a row belongs to, separated by commas.
+    /// `None` treats the whole table as a single object.
+    object: Option<&'static str>,
+    /// Column holding the age of a row
+    time: &'static str,
+    /// Column ordering the rows of an object, newest last
+    order: &'static str,
+    /// Only rows matching this condition are considered for trimming
+    eligible: Option<&'static str>,
+    /// Rows of other tables referencing a trimmed row via `(table, column)`.
+    /// These are deleted along with it and are not archived.
+    dependents: &'static [(&'static str, &'static str)],
+}
+
+const fn state_history(table: &'static str, object: &'static str) -> TableSpec {
+    TableSpec {
+        table,
+        key: "id",
+        object: Some(object),
+        time: "\"timestamp\"",
+        order: "id",
+        eligible: None,
+        dependents: &[],
+    }
+}
+
+fn table_spec(target: RetentionTarget) -> TableSpec {
+    match target {
+        // Replaces the `measured_boot_reports_keep_limit` SQL function. That is kept for one more
+        // release, since replicas of the previous version still call it during a rolling
+        // upgrade. Drop it with a migration in the next release.
+        RetentionTarget::MeasuredBoot => TableSpec {
+            table: "measurement_reports",
+            key: "report_id",
+            object: Some("machine_id"),
+            time: "ts",
+            order: "ts",
+            eligible: None,
+            dependents: &[
+                ("measurement_journal", "report_id"),
+                ("measurement_reports_values", "report_id"),
+            ],
+        },
+        RetentionTarget::MachineStateHistory => {
+            state_history("machine_state_history", "machine_id")
+        }
+        RetentionTarget::NetworkSegmentStateHistory => {
+            state_history("network_segment_state_history", "segment_id")
+        }
+        RetentionTarget::IbPartitionStateHistory => {
+            state_history("ib_partition_state_history", "partition_id")
+        }
+        RetentionTarget::DpaInterfaceStateHistory => {
+            state_history("dpa_interface_state_history", "interface_id")
+        }
+        RetentionTarget::PowerShelfStateHistory => {
+            state_history("power_shelf_state_history", "power_shelf_id")
+        }
+        RetentionTarget::SwitchStateHistory => state_history("switch_state_history", "switch_id"),
+        RetentionTarget::RackStateHistory => state_history("rack_state_history", "rack_id"),
+        RetentionTarget::MachineHealthHistory => TableSpec {
+            table: "machine_health_history",
+            key: "id",
+            object: Some("object_id"),
+            time: "time",
+            order: "id",
+            eligible: None,
+            dependents: &[],
+        },
+        RetentionTarget::MachineValidationResults => TableSpec {
+            table: "machine_validation_results",
+            key: "ctid",
+            object: Some("machine_validation_id"),
+            time: "end_time",
+            order: "end_time",
+            eligible: None,
+            dependents: &[],
+        },
+        RetentionTarget::RedfishBmcActions => TableSpec {
+            table: "redfish_bmc_actions",
+            key: "request_id",
+            object: None,
+            time: "applied_at",
+            order: "request_id",
+            eligible: Some("applied_at IS NOT NULL"),
+            dependents: &[],
+        },
+        RetentionTarget::SpdmAttestationHistory => TableSpec {
+            table: "spdm_machine_attestation_history",
+            key: "id",
+            object: Some("machine_id"),
+            time: "updated_at",
+            order: "id",
+            eligible: None,
+            dependents: &[],
+        },
+        RetentionTarget::RackFirmwareApplyHistory => TableSpec {
+            table: "rack_firmware_apply_history",
+            key: "id",
+            object: Some("rack_id"),
+            time: "applied_at",
+            order: "id",
+            eligible: None,
+            dependents: &[],
+        },
+        RetentionTarget::InstanceConfigHistory => {
+            state_history("instance_config_history", "machine_id")
+        }
+        RetentionTarget::MachineNetworkConfigHistory => {
+            state_history("machine_network_config_history", "machine_id")
+        }
+        RetentionTarget::MachineInterfaceAddressHistory => {
+            // Each address of an interface has its own history
+            state_history("machine_interface_address_history", "interface_id, address")
+        }
+    }
+}
+
+/// Temporary table holding the keys of the rows a trimming run deletes
+const DOOMED_TABLE: &str = "retention_doomed";
+
+/// Builds the statements creating [`DOOMED_TABLE`] with the key type of the
+/// table and filling it with the keys of all rows that fall outside of the
+/// policy. Binds of the second statement:
+/// `$1` keep_last, `$2` max_rows_per_object, `$3` cutoff time
+fn collect_queries(spec: &TableSpec) -> [String; 3] {
+    let partition = spec
+        .object
+        .map(|object| format!("PARTITION BY {object}"))
+        .unwrap_or_default();
+    let eligible = spec
+        .eligible
+        .map(|condition| format!("WHERE {condition}"))
+        .unwrap_or_default();
+
+    [
+        format!("DROP TABLE IF EXISTS pg_temp.{DOOMED_TABLE}"),
+        format!(
+            "CREATE TEMPORARY TABLE {DOOMED_TABLE} AS
+            SELECT {key} AS retention_key FROM {table} WITH NO DATA",
+            key = spec.key,
+            table = spec.table,
+        ),
+        format!(
+            "INSERT INTO {DOOMED_TABLE} (retention_key)
+            SELECT retention_key FROM (
+                SELECT {key} AS retention_key, {time} AS retention_time,
+                    ROW_NUMBER() OVER ({partition} ORDER BY {order} DESC) AS row_idx
+                FROM {table}
+                {eligible}
+            ) ranked
+            WHERE row_idx > $1 AND (row_idx > $2 OR retention_time < $3)",
+            key = spec.key,
+            time = spec.time,
+            order = spec.order,
+            table = spec.table,
+        ),
+    ]
+}
+
+/// Builds the statement deleting the next batch of rows collected in
+/// [`DOOMED_TABLE`]. Binds: `$1` batch size
+fn trim_query(spec: &TableSpec, returning_rows: bool) -> String {
+    let dependents: String = spec
+        .dependents
+        .iter()
+        .enumerate()
+        .map(|(i, (table, column))| {
+            format!(
+                "deleted_dependent_{i} AS (
+                    DELETE FROM {table} WHERE {column} IN (SELECT retention_key FROM doomed)
+                ),"
+            )
+        })
+        .collect();
+    let select = if returning_rows {
+        "SELECT to_jsonb(deleted) FROM deleted"
+    } else {
+        "SELECT count(*) FROM deleted"
+    };
+
+    format!(
+        "WITH doomed AS (
+            DELETE FROM {DOOMED_TABLE}
+            WHERE ctid IN (SELECT ctid FROM {DOOMED_TABLE} LIMIT $1)
+            RETURNING retention_key
+        ),
+        {dependents}
+        deleted AS (
+            DELETE FROM {table} WHERE {key} IN (SELECT retention_key FROM doomed)
+            RETURNING {table}.*
+        )
+        {select}",
+        key = spec.key,
+        table = spec.table,
+    )
+}
+
+fn cutoff(policy: &RetentionPolicy, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    policy.max_age.map(|max_age| {
+        now.checked_sub_signed(max_age)
+            .unwrap_or(DateTime::<Utc>::MIN_UTC)
+    })
+}
+
+/// Collects the keys of all rows of `target` which fall outside of `policy`
+/// into a temporary table of the connection, and returns how many there are.
+/// The rows are then deleted in batches by [`trim_batch`] or
+/// [`trim_batch_returning`] on the same connection, which do not need to rank
+/// the table again. Call [`drop_doomed`] when done.
+pub async fn collect_doomed(
+    conn: &mut PgConnection,
+    target: RetentionTarget,
+    policy: &RetentionPolicy,
+    now: DateTime<Utc>,
+) -> DatabaseResult<u64> {
+    let [drop_query, create_query, insert_query] = collect_queries(&table_spec(target));
+    for query in [&drop_query, &create_query] {
+        sqlx::query(query)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| DatabaseError::query(query, e))?;
+    }
+    if policy.is_noop() {
+        // Leave the temporary table empty
+        return Ok(0);
+    }
+    let collected = sqlx::query(&insert_query)
+        .bind(i64::from(policy.keep_last))
+        .bind(policy.max_rows_per_object.map(i64::from))
+        .bind(cutoff(policy, now))
+        .execute(conn)
+        .await
+        .map_err(|e| DatabaseError::query(&insert_query, e))?;
+    Ok(collected.rows_affected())
+}
+
+/// Drops the temporary table created by [`collect_doomed`]
+pub async fn drop_doomed(conn: &mut PgConnection) -> DatabaseResult<()> {
+    let query = format!("DROP TABLE IF EXISTS pg_temp.{DOOMED_TABLE}");
+    sqlx::query(&query)
+        .execute(conn)
+        .await
+        .map_err(|e| DatabaseError::query(&query, e))?;
+    Ok(())
+}
+
+/// Deletes the next `batch_size` rows of `target` collected by
+/// [`collect_doomed`] and returns how many were deleted. Rows which were
+/// deleted by someone else in the meantime are skipped.
+pub async fn trim_batch(
+    txn: &mut PgConnection,
+    target: RetentionTarget,
+    batch_size: u32,
+) -> DatabaseResult<u64> {
+    let query = trim_query(&table_spec(target), false);
+    let (deleted,): (i64,) = sqlx::query_as(&query)
+        .bind(i64::from(batch_size))
+        .fetch_one(txn)
+        .await
+        .map_err(|e| DatabaseError::query(&query, e))?;
+    Ok(deleted as u64)
+}
+
+/// Like [`trim_batch`], but returns the deleted rows as JSON objects so that
+/// they can be archived. Rows of dependent tables are not returned.
+pub async fn trim_batch_returning(
+    txn: &mut PgConnection,
+    target: RetentionTarget,
+    batch_size: u32,
+) -> DatabaseResult<Vec<serde_json::Value>> {
+    let query = trim_query(&table_spec(target), true);
+    let rows: Vec<(serde_json::Value,)> = sqlx::query_as(&query)
+        .bind(i64::from(batch_size))
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(&query, e))?;
+    Ok(rows.into_iter().map(|(row,)| row).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_spec_matches_target() {
+        for target in RetentionTarget::ALL {
+            assert_eq!(table_spec(target).table, target.table_name());
+        }
+    }
+
+    #[test]
+    fn test_collect_queries() {
+        let [_, create, insert] = collect_queries(&table_spec(RetentionTarget::MeasuredBoot));
+        assert!(create.contains("SELECT report_id AS retention_key FROM measurement_reports"));
+        assert!(insert.contains("PARTITION BY machine_id ORDER BY ts DESC"));
+
+        let [_, create, insert] = collect_queries(&table_spec(RetentionTarget::RedfishBmcActions));
+        assert!(create.contains("SELECT request_id AS retention_key"));
+        assert!(insert.contains("OVER ( ORDER BY request_id DESC)"));
+        assert!(insert.contains("WHERE applied_at IS NOT NULL"));
+
+        let [_, _, insert] =
+            collect_queries(&table_spec(RetentionTarget::MachineInterfaceAddressHistory));
+        assert!(insert.contains("PARTITION BY interface_id, address ORDER BY id DESC"));
+    }
+
+    #[test]
+    fn test_trim_query_dependents() {
+        let query = trim_query(&table_spec(RetentionTarget::MeasuredBoot), false);
+        assert!(query.contains("DELETE FROM measurement_journal WHERE report_id IN"));
+        assert!(query.contains("DELETE FROM measurement_reports_values WHERE report_id IN"));
+        assert!(query.contains("DELETE FROM measurement_reports WHERE report_id IN"));
+        assert!(query.ends_with("SELECT count(*) FROM deleted"));
+
+        let query = trim_query(&table_spec(RetentionTarget::RedfishBmcActions), true);
+        assert!(!query.contains("deleted_dependent"));
+        assert!(query.ends_with("SELECT to_jsonb(deleted) FROM deleted"));
+    }
+}
@@ -22,6 +22,7 @@ use serde::Serialize;
 use sqlx::postgres::PgRow;
 use sqlx::{Encode, FromRow, PgConnection, Postgres, Row, Type};
 
+use crate::db_read::DbReader;
 use crate::{DatabaseError, DatabaseResult};
 
 #[derive(Debug, Clone)]
@@ -145,6 +146,51 @@ pub async fn find_by_object_ids(
     Ok(histories)
 }
 
+/// Retrieve the state history of all objects in a table for the window starting at `since`.
+///
+/// Besides the records written at or after `since`, the most recent record before it is included
+/// for every object, so that callers know which state each object was in when the window started.
+/// Records are keyed by object ID and sorted oldest first, like [`find_by_object_ids`].
+pub async fn find_in_window(
+    txn: impl DbReader<'_>,
+    table_id: StateHistoryTableId,
+    since: DateTime<Utc>,
+) -> DatabaseResult<std::collections::HashMap<String, Vec<StateHistoryRecord>>> {
+    let table = table_id.sql_table();
+    let object_id_column = table_id.object_id_column();
+    // The records in the window, and the newest record before it for every object
+    let query = format!(
+        "SELECT object_id, state, state_version, timestamp FROM (
+            SELECT id, {object_id_column}::TEXT AS object_id, state::TEXT, state_version, timestamp
+            FROM {table}
+            WHERE timestamp >= $1
+            UNION ALL
+            SELECT * FROM (
+                SELECT DISTINCT ON ({object_id_column})
+                    id, {object_id_column}::TEXT AS object_id, state::TEXT, state_version, timestamp
+                FROM {table}
+                WHERE timestamp < $1
+                ORDER BY {object_id_column}, id DESC
+            ) before_window
+        ) window_records
+        ORDER BY id ASC"
+    );
+
+    let query_results: Vec<DbStateHistoryRecord> = sqlx::query_as(&query)
+        .bind(since)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(&query, e))?;
+
+    let mut histories = std::collections::HashMap::new();
+    for result in query_results {
+        let object_id = result.object_id.clone();
+        let records: &mut Vec<StateHistoryRecord> = histories.entry(object_id).or_default();
+        records.push(result.into());
+    }
+    Ok(histories)
+}
+
 /// Retrieve state history for a single object.
 pub async fn for_object(
     txn: &mut PgConnection,
@@ -163,6 +209,28 @@ pub async fn for_object(
         .map_err(|e| DatabaseError::query(&query, e))
 }
 
+/// Retrieve the state an object was in at `at`, i.e. the last state recorded
+/// at or before that time.
+pub async fn find_at(
+    txn: &mut PgConnection,
+    table_id: StateHistoryTableId,
+    object_id: &impl std::fmt::Display,
+    at: DateTime<Utc>,
+) -> DatabaseResult<Option<StateHistoryRecord>> {
+    let query = format!(
+        "SELECT state::TEXT, state_version, timestamp FROM {} WHERE {}::TEXT=$1 AND timestamp <= $2
+        ORDER BY id DESC LIMIT 1",
+        table_id.sql_table(),
+        table_id.object_id_column()
+    );
+    sqlx::query_as::<_, StateHistoryRecord>(&query)
+        .bind(object_id.to_string())
+        .bind(at)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(&query, e))
+}
+
 /// Store a state history record for an object.
 pub async fn persist<ID, S>(
     txn: &mut PgConnection,
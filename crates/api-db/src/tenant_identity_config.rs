@@ -20,8 +20,9 @@
 
 use carbide_uuid::machine::MachineId;
 use model::tenant::{
-    EncryptedTokenDelegationAuthConfig, IdentityConfig, SigningKeyMaterial, TenantIdentityConfig,
-    TenantOrganizationId, TokenDelegation, TokenDelegationAuthMethod,
+    EncryptedSigningPrivateKey, EncryptedTokenDelegationAuthConfig, EncryptionKeyId,
+    IdentityConfig, KeyId, SigningKeyMaterial, TenantIdentityConfig, TenantOrganizationId,
+    TenantX509CaKey, TokenDelegation, TokenDelegationAuthMethod,
 };
 use sqlx::PgConnection;
 use sqlx::types::Json;
@@ -244,6 +245,63 @@ pub async fn delete(org_id: &TenantOrganizationId, txn: &mut PgConnection) -> Da
     Ok(result.rows_affected() > 0)
 }
 
+/// Find the X.509 CA key of an org.
+pub async fn find_x509_ca_key(
+    org_id: &TenantOrganizationId,
+    txn: &mut PgConnection,
+) -> DatabaseResult<Option<TenantX509CaKey>> {
+    let query = "SELECT organization_id, signing_key_id::text AS signing_key_id, encrypted_ca_key, \
+        encryption_key_id::text AS encryption_key_id, created_at \
+        FROM tenant_x509_ca_keys WHERE organization_id = $1";
+    sqlx::query_as(query)
+        .bind(org_id.as_str())
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Store the X.509 CA key of an org, created for the signing key `signing_key_id`.
+/// An existing CA key is only replaced if it was created for another signing key, so that
+/// concurrent callers end up with the same CA key. Returns the stored CA key.
+pub async fn set_x509_ca_key(
+    org_id: &TenantOrganizationId,
+    signing_key_id: &KeyId,
+    encrypted_ca_key: &EncryptedSigningPrivateKey,
+    encryption_key_id: &EncryptionKeyId,
+    txn: &mut PgConnection,
+) -> DatabaseResult<TenantX509CaKey> {
+    let query = r#"
+        INSERT INTO tenant_x509_ca_keys (organization_id, signing_key_id, encrypted_ca_key, encryption_key_id)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (organization_id) DO UPDATE SET
+            signing_key_id = EXCLUDED.signing_key_id,
+            encrypted_ca_key = EXCLUDED.encrypted_ca_key,
+            encryption_key_id = EXCLUDED.encryption_key_id,
+            created_at = NOW()
+        WHERE tenant_x509_ca_keys.signing_key_id <> EXCLUDED.signing_key_id
+        RETURNING organization_id, signing_key_id::text AS signing_key_id, encrypted_ca_key,
+            encryption_key_id::text AS encryption_key_id, created_at
+    "#;
+    let stored: Option<TenantX509CaKey> = sqlx::query_as(query)
+        .bind(org_id.as_str())
+        .bind(signing_key_id)
+        .bind(encrypted_ca_key)
+        .bind(encryption_key_id)
+        .fetch_optional(&mut *txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    match stored {
+        Some(stored) => Ok(stored),
+        // Another caller already stored a CA key for this signing key
+        None => find_x509_ca_key(org_id, txn)
+            .await?
+            .ok_or_else(|| DatabaseError::NotFoundError {
+                kind: "TenantX509CaKey",
+                id: org_id.as_str().to_string(),
+            }),
+    }
+}
+
 /// Clear token delegation for an org.
 pub async fn delete_token_delegation(
     org_id: &TenantOrganizationId,
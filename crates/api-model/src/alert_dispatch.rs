@@ -0,0 +1,144 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! State kept by the alert dispatcher, which notifies external receivers
+//! (Alertmanager, webhooks, MQTT) when health alerts start and clear.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use health_report::HealthReport;
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+use sqlx::postgres::PgRow;
+
+/// The kind of object a dispatched health report belongs to
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertObjectKind {
+    Machine,
+    Rack,
+    Switch,
+    PowerShelf,
+}
+
+impl AlertObjectKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertObjectKind::Machine => "machine",
+            AlertObjectKind::Rack => "rack",
+            AlertObjectKind::Switch => "switch",
+            AlertObjectKind::PowerShelf => "power_shelf",
+        }
+    }
+}
+
+impl fmt::Display for AlertObjectKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AlertObjectKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "machine" => Ok(AlertObjectKind::Machine),
+            "rack" => Ok(AlertObjectKind::Rack),
+            "switch" => Ok(AlertObjectKind::Switch),
+            "power_shelf" => Ok(AlertObjectKind::PowerShelf),
+            _ => Err(format!("Unknown alert object kind: {s}")),
+        }
+    }
+}
+
+/// Health alerts carry no severity of their own. The alert dispatcher derives
+/// it from the classifications of an alert.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertSeverity {
+    #[default]
+    Warning,
+    Critical,
+}
+
+impl AlertSeverity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertSeverity::Warning => "warning",
+            AlertSeverity::Critical => "critical",
+        }
+    }
+}
+
+impl fmt::Display for AlertSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The aggregate health of an object as it was last dispatched. Only alerts
+/// which were eligible for notification are part of `report`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DispatchedHealthReport {
+    pub object_kind: AlertObjectKind,
+    pub object_id: String,
+    pub report: HealthReport,
+    pub updated: DateTime<Utc>,
+}
+
+impl<'r> sqlx::FromRow<'r, PgRow> for DispatchedHealthReport {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let object_kind: String = row.try_get("object_kind")?;
+        let report: sqlx::types::Json<HealthReport> = row.try_get("report")?;
+        Ok(DispatchedHealthReport {
+            object_kind: object_kind
+                .parse()
+                .map_err(|e: String| sqlx::Error::ColumnDecode {
+                    index: "object_kind".to_string(),
+                    source: e.into(),
+                })?,
+            object_id: row.try_get("object_id")?,
+            report: report.0,
+            updated: row.try_get("updated")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn object_kind_round_trip() {
+        for kind in [
+            AlertObjectKind::Machine,
+            AlertObjectKind::Rack,
+            AlertObjectKind::Switch,
+            AlertObjectKind::PowerShelf,
+        ] {
+            assert_eq!(kind.to_string().parse::<AlertObjectKind>(), Ok(kind));
+            assert_eq!(
+                serde_json::to_string(&kind).unwrap(),
+                format!("\"{}\"", kind.as_str())
+            );
+        }
+        assert!("host".parse::<AlertObjectKind>().is_err());
+    }
+}
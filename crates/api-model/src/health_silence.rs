@@ -0,0 +1,462 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Health silences suppress health alerts of all objects matching a selector
+//! for a limited amount of time. Unlike health report overrides they are not
+//! tied to a single object, and they always expire.
+
+use std::collections::{BTreeMap, HashMap};
+
+use ::rpc::errors::RpcDataConversionError;
+use ::rpc::forge as rpc;
+use chrono::{DateTime, TimeDelta, Utc};
+use health_report::{HealthAlertClassification, HealthProbeAlert, HealthProbeId, HealthReport};
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+use sqlx::postgres::PgRow;
+
+/// The longest time a health silence can be created for
+pub const MAX_HEALTH_SILENCE_DURATION: TimeDelta = TimeDelta::days(30);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthSilenceObjectKind {
+    Machine,
+    Rack,
+    Switch,
+    PowerShelf,
+}
+
+/// Selects the alerts a health silence applies to. Every criterion which is
+/// set needs to match.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HealthSilenceMatcher {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub probe_id: Option<HealthProbeId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub classification: Option<HealthAlertClassification>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub object_kind: Option<HealthSilenceObjectKind>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub object_ids: Vec<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sku: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rack_id: Option<String>,
+}
+
+/// The object whose health a silence is applied to
+#[derive(Clone, Debug)]
+pub struct HealthSilenceSubject<'a> {
+    pub kind: HealthSilenceObjectKind,
+    /// The IDs the object can be referred to by. For Managed Hosts, these are
+    /// the Host and all DPU IDs.
+    pub ids: Vec<String>,
+    pub labels: &'a HashMap<String, String>,
+    pub sku: Option<&'a str>,
+    pub rack_id: Option<String>,
+}
+
+impl HealthSilenceMatcher {
+    /// A matcher without any criteria would silence every alert of the site
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    pub fn matches_subject(&self, subject: &HealthSilenceSubject) -> bool {
+        self.object_kind.is_none_or(|kind| kind == subject.kind)
+            && (self.object_ids.is_empty()
+                || self.object_ids.iter().any(|id| subject.ids.contains(id)))
+            && self
+                .labels
+                .iter()
+                .all(|(key, value)| subject.labels.get(key) == Some(value))
+            && self
+                .sku
+                .as_ref()
+                .is_none_or(|sku| subject.sku == Some(sku.as_str()))
+            && self
+                .rack_id
+                .as_ref()
+                .is_none_or(|rack_id| subject.rack_id.as_ref() == Some(rack_id))
+    }
+
+    pub fn matches_alert(&self, alert: &HealthProbeAlert) -> bool {
+        self.probe_id.as_ref().is_none_or(|id| id == &alert.id)
+            && self
+                .classification
+                .as_ref()
+                .is_none_or(|classification| alert.classifications.contains(classification))
+            && self
+                .target
+                .as_ref()
+                .is_none_or(|target| alert.target.as_ref() == Some(target))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HealthSilenceState {
+    /// The silence starts in the future
+    Pending,
+    Active,
+    Expired,
+    /// The silence was ended before it expired
+    Ended,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HealthSilence {
+    pub id: i64,
+    pub matcher: HealthSilenceMatcher,
+    pub author: String,
+    pub reason: String,
+    pub starts_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub created: DateTime<Utc>,
+    /// Set once the silence expired or was ended
+    pub ended_at: Option<DateTime<Utc>>,
+    /// Who ended the silence, if it was ended before it expired
+    pub ended_by: Option<String>,
+}
+
+impl HealthSilence {
+    pub fn state(&self, now: DateTime<Utc>) -> HealthSilenceState {
+        if self.ended_at.is_some() && self.ended_by.is_some() {
+            HealthSilenceState::Ended
+        } else if self.ended_at.is_some() || self.expires_at <= now {
+            HealthSilenceState::Expired
+        } else if self.starts_at > now {
+            HealthSilenceState::Pending
+        } else {
+            HealthSilenceState::Active
+        }
+    }
+
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.state(now) == HealthSilenceState::Active
+    }
+}
+
+/// Applies all active silences which match `subject` to the alerts of `report`.
+///
+/// Silenced alerts stay part of the report, so that the condition remains
+/// visible. They lose their classifications, apart from
+/// `SuppressExternalAlerting`, and their message names the silence.
+pub fn apply_health_silences(
+    report: &mut HealthReport,
+    silences: &[HealthSilence],
+    subject: &HealthSilenceSubject,
+    now: DateTime<Utc>,
+) {
+    let silences: Vec<&HealthSilence> = silences
+        .iter()
+        .filter(|silence| silence.is_active(now) && silence.matcher.matches_subject(subject))
+        .collect();
+    if silences.is_empty() {
+        return;
+    }
+
+    for alert in report.alerts.iter_mut() {
+        let Some(silence) = silences
+            .iter()
+            .find(|silence| silence.matcher.matches_alert(alert))
+        else {
+            continue;
+        };
+        alert.classifications = vec![HealthAlertClassification::suppress_external_alerting()];
+        alert.message = format!(
+            "{} [silenced by health silence {} until {}: {}]",
+            alert.message,
+            silence.id,
+            silence.expires_at.to_rfc3339(),
+            silence.reason
+        );
+    }
+}
+
+impl<'r> sqlx::FromRow<'r, PgRow> for HealthSilence {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let matcher: sqlx::types::Json<HealthSilenceMatcher> = row.try_get("matcher")?;
+        Ok(HealthSilence {
+            id: row.try_get("id")?,
+            matcher: matcher.0,
+            author: row.try_get("author")?,
+            reason: row.try_get("reason")?,
+            starts_at: row.try_get("starts_at")?,
+            expires_at: row.try_get("expires_at")?,
+            created: row.try_get("created")?,
+            ended_at: row.try_get("ended_at")?,
+            ended_by: row.try_get("ended_by")?,
+        })
+    }
+}
+
+impl From<HealthSilenceObjectKind> for rpc::HealthSilenceObjectKind {
+    fn from(kind: HealthSilenceObjectKind) -> Self {
+        match kind {
+            HealthSilenceObjectKind::Machine => rpc::HealthSilenceObjectKind::Machine,
+            HealthSilenceObjectKind::Rack => rpc::HealthSilenceObjectKind::Rack,
+            HealthSilenceObjectKind::Switch => rpc::HealthSilenceObjectKind::Switch,
+            HealthSilenceObjectKind::PowerShelf => rpc::HealthSilenceObjectKind::PowerShelf,
+        }
+    }
+}
+
+impl From<HealthSilenceState> for rpc::HealthSilenceState {
+    fn from(state: HealthSilenceState) -> Self {
+        match state {
+            HealthSilenceState::Pending => rpc::HealthSilenceState::Pending,
+            HealthSilenceState::Active => rpc::HealthSilenceState::Active,
+            HealthSilenceState::Expired => rpc::HealthSilenceState::Expired,
+            HealthSilenceState::Ended => rpc::HealthSilenceState::Ended,
+        }
+    }
+}
+
+impl From<HealthSilenceMatcher> for rpc::HealthSilenceMatcher {
+    fn from(matcher: HealthSilenceMatcher) -> Self {
+        rpc::HealthSilenceMatcher {
+            probe_id: matcher.probe_id.map(|id| id.to_string()),
+            classification: matcher
+                .classification
+                .map(|classification| classification.to_string()),
+            target: matcher.target,
+            object_kind: matcher
+                .object_kind
+                .map(rpc::HealthSilenceObjectKind::from)
+                .unwrap_or(rpc::HealthSilenceObjectKind::Any) as i32,
+            object_ids: matcher.object_ids,
+            labels: matcher.labels.into_iter().collect(),
+            sku: matcher.sku,
+            rack_id: matcher.rack_id,
+        }
+    }
+}
+
+impl TryFrom<rpc::HealthSilenceMatcher> for HealthSilenceMatcher {
+    type Error = RpcDataConversionError;
+
+    fn try_from(matcher: rpc::HealthSilenceMatcher) -> Result<Self, Self::Error> {
+        let object_kind = match rpc::HealthSilenceObjectKind::try_from(matcher.object_kind)
+            .map_err(|_| {
+                RpcDataConversionError::InvalidArgument(format!(
+                    "invalid object kind {}",
+                    matcher.object_kind
+                ))
+            })? {
+            rpc::HealthSilenceObjectKind::Any => None,
+            rpc::HealthSilenceObjectKind::Machine => Some(HealthSilenceObjectKind::Machine),
+            rpc::HealthSilenceObjectKind::Rack => Some(HealthSilenceObjectKind::Rack),
+            rpc::HealthSilenceObjectKind::Switch => Some(HealthSilenceObjectKind::Switch),
+            rpc::HealthSilenceObjectKind::PowerShelf => Some(HealthSilenceObjectKind::PowerShelf),
+        };
+        let probe_id = matcher
+            .probe_id
+            .map(|id| id.parse::<HealthProbeId>())
+            .transpose()
+            .map_err(|e| RpcDataConversionError::InvalidArgument(format!("probe_id: {e}")))?;
+        let classification = matcher
+            .classification
+            .map(|classification| classification.parse::<HealthAlertClassification>())
+            .transpose()
+            .map_err(|e| RpcDataConversionError::InvalidArgument(format!("classification: {e}")))?;
+
+        Ok(HealthSilenceMatcher {
+            probe_id,
+            classification,
+            target: matcher.target,
+            object_kind,
+            object_ids: matcher.object_ids,
+            labels: matcher.labels.into_iter().collect(),
+            sku: matcher.sku,
+            rack_id: matcher.rack_id,
+        })
+    }
+}
+
+impl HealthSilence {
+    pub fn into_rpc(self, now: DateTime<Utc>) -> rpc::HealthSilence {
+        let state = rpc::HealthSilenceState::from(self.state(now));
+        rpc::HealthSilence {
+            id: self.id,
+            matcher: Some(self.matcher.into()),
+            author: self.author,
+            reason: self.reason,
+            starts_at: Some(self.starts_at.into()),
+            expires_at: Some(self.expires_at.into()),
+            created: Some(self.created.into()),
+            ended_at: self.ended_at.map(Into::into),
+            ended_by: self.ended_by,
+            state: state as i32,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alert(id: &str, target: Option<&str>) -> HealthProbeAlert {
+        HealthProbeAlert {
+            id: id.parse().unwrap(),
+            target: target.map(str::to_string),
+            in_alert_since: None,
+            message: "broken".to_string(),
+            tenant_message: None,
+            classifications: vec![HealthAlertClassification::prevent_allocations()],
+        }
+    }
+
+    fn silence(matcher: HealthSilenceMatcher, now: DateTime<Utc>) -> HealthSilence {
+        HealthSilence {
+            id: 7,
+            matcher,
+            author: "operator".to_string(),
+            reason: "vendor RMA".to_string(),
+            starts_at: now - TimeDelta::hours(1),
+            expires_at: now + TimeDelta::hours(1),
+            created: now - TimeDelta::hours(1),
+            ended_at: None,
+            ended_by: None,
+        }
+    }
+
+    #[test]
+    fn test_matcher_selects_subject_and_alert() {
+        let labels = HashMap::from([("pool".to_string(), "training".to_string())]);
+        let subject = HealthSilenceSubject {
+            kind: HealthSilenceObjectKind::Machine,
+            ids: vec!["host1".to_string(), "dpu1".to_string()],
+            labels: &labels,
+            sku: Some("sku-a"),
+            rack_id: Some("rack-1".to_string()),
+        };
+
+        let matcher = HealthSilenceMatcher {
+            probe_id: Some("BmcSensor".parse().unwrap()),
+            target: Some("PSU1".to_string()),
+            labels: BTreeMap::from([("pool".to_string(), "training".to_string())]),
+            sku: Some("sku-a".to_string()),
+            ..Default::default()
+        };
+        assert!(matcher.matches_subject(&subject));
+        assert!(matcher.matches_alert(&alert("BmcSensor", Some("PSU1"))));
+        assert!(!matcher.matches_alert(&alert("BmcSensor", Some("PSU2"))));
+        assert!(!matcher.matches_alert(&alert("HeartbeatTimeout", Some("PSU1"))));
+
+        let by_dpu_id = HealthSilenceMatcher {
+            object_ids: vec!["dpu1".to_string()],
+            ..Default::default()
+        };
+        assert!(by_dpu_id.matches_subject(&subject));
+
+        for matcher in [
+            HealthSilenceMatcher {
+                object_kind: Some(HealthSilenceObjectKind::Switch),
+                ..Default::default()
+            },
+            HealthSilenceMatcher {
+                rack_id: Some("rack-2".to_string()),
+                ..Default::default()
+            },
+            HealthSilenceMatcher {
+                labels: BTreeMap::from([("pool".to_string(), "inference".to_string())]),
+                ..Default::default()
+            },
+            HealthSilenceMatcher {
+                sku: Some("sku-b".to_string()),
+                ..Default::default()
+            },
+        ] {
+            assert!(!matcher.matches_subject(&subject), "{matcher:?}");
+        }
+    }
+
+    #[test]
+    fn test_apply_health_silences() {
+        let now = Utc::now();
+        let labels = HashMap::new();
+        let subject = HealthSilenceSubject {
+            kind: HealthSilenceObjectKind::Rack,
+            ids: vec!["rack-1".to_string()],
+            labels: &labels,
+            sku: None,
+            rack_id: Some("rack-1".to_string()),
+        };
+        let mut report = HealthReport::empty("test".to_string());
+        report.alerts = vec![alert("Leak", None), alert("PowerShelf", None)];
+
+        let matcher = HealthSilenceMatcher {
+            probe_id: Some("Leak".parse().unwrap()),
+            ..Default::default()
+        };
+        let mut expired = silence(matcher.clone(), now);
+        expired.expires_at = now - TimeDelta::minutes(1);
+        let mut pending = silence(matcher.clone(), now);
+        pending.starts_at = now + TimeDelta::minutes(1);
+        apply_health_silences(&mut report, &[expired, pending], &subject, now);
+        assert_eq!(
+            report.alerts[0].classifications,
+            vec![HealthAlertClassification::prevent_allocations()]
+        );
+
+        apply_health_silences(&mut report, &[silence(matcher, now)], &subject, now);
+        assert_eq!(
+            report.alerts[0].classifications,
+            vec![HealthAlertClassification::suppress_external_alerting()]
+        );
+        assert!(report.alerts[0].message.contains("health silence 7"));
+        assert_eq!(
+            report.alerts[1].classifications,
+            vec![HealthAlertClassification::prevent_allocations()]
+        );
+    }
+
+    #[test]
+    fn test_silence_state() {
+        let now = Utc::now();
+        let mut s = silence(HealthSilenceMatcher::default(), now);
+        assert_eq!(s.state(now), HealthSilenceState::Active);
+        s.ended_at = Some(now);
+        assert_eq!(s.state(now), HealthSilenceState::Expired);
+        s.ended_by = Some("operator".to_string());
+        assert_eq!(s.state(now), HealthSilenceState::Ended);
+    }
+
+    #[test]
+    fn test_matcher_serde_roundtrip() {
+        let matcher = HealthSilenceMatcher {
+            classification: Some(HealthAlertClassification::prevent_allocations()),
+            object_kind: Some(HealthSilenceObjectKind::PowerShelf),
+            rack_id: Some("rack-1".to_string()),
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&matcher).unwrap();
+        assert_eq!(
+            json,
+            r#"{"classification":"PreventAllocations","object_kind":"power_shelf","rack_id":"rack-1"}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<HealthSilenceMatcher>(&json).unwrap(),
+            matcher
+        );
+    }
+}
@@ -34,6 +34,7 @@ use mac_address::MacAddress;
 use serde::{Deserialize, Serialize};
 
 pub mod address_selection_strategy;
+pub mod alert_dispatch;
 pub mod allocation_type;
 pub mod attestation;
 pub mod bmc_info;
@@ -56,6 +57,7 @@ pub mod extension_service;
 pub mod firmware;
 pub mod hardware_info;
 pub mod health;
+pub mod health_silence;
 pub mod host_machine_update;
 pub mod ib;
 pub mod ib_partition;
@@ -67,6 +69,8 @@ pub mod machine_boot_override;
 pub mod machine_interface_address;
 pub mod machine_update_module;
 pub mod machine_validation;
+pub mod managed_host_history;
+pub mod media_sanitization;
 pub mod metadata;
 pub mod network_devices;
 pub mod network_prefix;
@@ -79,12 +83,14 @@ pub mod os;
 pub mod power_manager;
 pub mod power_shelf;
 pub mod predicted_machine_interface;
+pub mod prefix_list;
 pub mod pxe;
 pub mod rack;
 pub mod rack_firmware;
 pub mod rack_type;
 pub mod redfish;
 pub mod resource_pool;
+pub mod retention;
 pub mod route_server;
 pub mod site_explorer;
 pub mod sku;
@@ -92,7 +98,6 @@ pub mod state_history;
 pub mod storage;
 pub mod switch;
 pub mod tenant;
-pub mod trim_table;
 pub mod vpc;
 pub mod vpc_prefix;
 
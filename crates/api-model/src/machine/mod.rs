@@ -61,6 +61,9 @@ use crate::errors::{ModelError, ModelResult};
 use crate::expected_machine::ExpectedMachineData;
 use crate::firmware::FirmwareComponentType;
 use crate::hardware_info::{HardwareInfo, MachineNvLinkInfo};
+use crate::health_silence::{
+    HealthSilence, HealthSilenceObjectKind, HealthSilenceSubject, apply_health_silences,
+};
 use crate::instance::config::network::DeviceLocator;
 use crate::instance::snapshot::InstanceSnapshotPgJson;
 use crate::machine::capabilities::MachineCapabilitiesSet;
@@ -130,6 +133,9 @@ pub struct ManagedHostStateSnapshot {
     /// Health overrides inherited from the rack this host belongs to (if any).
     /// Populated at read time; not stored on the machines table.
     pub rack_health_overrides: Option<HealthReportSources>,
+    /// Active health silences which apply to machines. Populated at read time and
+    /// matched against the host when its aggregate health is derived.
+    pub health_silences: Vec<HealthSilence>,
 }
 
 impl<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> for ManagedHostStateSnapshot {
@@ -145,6 +151,8 @@ impl<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> for ManagedHostStateSnapshot {
             row.try_get("dpu_snapshots")?;
         let rack_health_overrides: sqlx::types::Json<Vec<Option<RackHealthOverrides>>> =
             row.try_get("rack_health_overrides")?;
+        let health_silences: sqlx::types::Json<Vec<HealthSilence>> =
+            row.try_get("health_silences")?;
         // We are setting dpa_interface_snapshots to an emtpy vector here.
         // This will be filled by load_object_state later.
         let dpa_interface_snapshots: Vec<DpaInterface> = Vec::new();
@@ -201,6 +209,7 @@ impl<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> for ManagedHostStateSnapshot {
             managed_state,
             instance,
             rack_health_overrides,
+            health_silences: health_silences.0,
             // This will need to be modified by callers, as its value depends on a
             // HardwareHealthReportsConfig being specified.
             aggregate_health: health_report::HealthReport::empty("".to_string()),
@@ -422,6 +431,7 @@ impl ManagedHostStateSnapshot {
         if let Some(mut over) = self.host_snapshot.health_reports.replace.clone() {
             over.source = source;
             over.observed_at = observed_at;
+            self.apply_health_silences(&mut over);
             self.aggregate_health = over;
             return;
         }
@@ -515,11 +525,32 @@ impl ManagedHostStateSnapshot {
             }
         }
 
+        self.apply_health_silences(&mut output);
+
         output.source = source;
         output.observed_at = observed_at;
         self.aggregate_health = output;
     }
 
+    /// Applies the silences matching this host to an aggregate health report
+    fn apply_health_silences(&self, report: &mut HealthReport) {
+        if self.health_silences.is_empty() {
+            return;
+        }
+        let host = &self.host_snapshot;
+        let subject = HealthSilenceSubject {
+            kind: HealthSilenceObjectKind::Machine,
+            ids: std::iter::once(&host.id)
+                .chain(self.dpu_snapshots.iter().map(|dpu| &dpu.id))
+                .map(|id| id.to_string())
+                .collect(),
+            labels: &host.metadata.labels,
+            sku: host.hw_sku.as_deref(),
+            rack_id: host.rack_id.as_ref().map(|id| id.to_string()),
+        };
+        apply_health_silences(report, &self.health_silences, &subject, chrono::Utc::now());
+    }
+
     /// Creates an RPC Machine representation for either the Host or one of the DPUs
     pub fn rpc_machine_state(
         &self,
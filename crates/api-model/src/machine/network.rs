@@ -44,6 +44,87 @@ pub struct MachineNetworkStatusObservation {
     pub agent_version_superseded_at: Option<DateTime<Utc>>,
     pub instance_network_observation: Option<InstanceNetworkStatusObservation>,
     pub extension_service_observation: Option<InstanceExtensionServiceStatusObservation>,
+    /// Set while the agent refuses a network config that failed its commit-confirm probes
+    pub network_config_rollback: Option<NetworkConfigRollback>,
+}
+
+/// A network config that a DPU agent applied, but rolled back because it failed the
+/// commit-confirm probes. Persisted to a Postgres JSON column.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetworkConfigRollback {
+    pub rejected_config_version: String,
+    pub rolled_back_at: DateTime<Utc>,
+    pub failed_probes: Vec<String>,
+    /// False if there was no previously applied config to restore
+    pub restored: bool,
+    pub retry_after: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<rpc::NetworkConfigRollback> for NetworkConfigRollback {
+    type Error = RpcDataConversionError;
+
+    fn try_from(rollback: rpc::NetworkConfigRollback) -> Result<Self, Self::Error> {
+        let to_date_time = |timestamp: ::rpc::Timestamp| {
+            DateTime::try_from(timestamp)
+                .map_err(|_| RpcDataConversionError::InvalidTimestamp(timestamp.to_string()))
+        };
+        Ok(Self {
+            rejected_config_version: rollback.rejected_config_version,
+            rolled_back_at: to_date_time(rollback.rolled_back_at.ok_or(
+                RpcDataConversionError::MissingArgument("network_config_rollback.rolled_back_at"),
+            )?)?,
+            failed_probes: rollback.failed_probes,
+            restored: rollback.restored,
+            retry_after: rollback.retry_after.map(to_date_time).transpose()?,
+        })
+    }
+}
+
+impl From<NetworkConfigRollback> for rpc::NetworkConfigRollback {
+    fn from(rollback: NetworkConfigRollback) -> Self {
+        Self {
+            rejected_config_version: rollback.rejected_config_version,
+            rolled_back_at: Some(rollback.rolled_back_at.into()),
+            failed_probes: rollback.failed_probes,
+            restored: rollback.restored,
+            retry_after: rollback.retry_after.map(Into::into),
+        }
+    }
+}
+
+/// The NVUE config a DPU agent reported applying, and the DPU-side inputs it was rendered with.
+/// Persisted to a Postgres JSON column.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AppliedNvueConfig {
+    pub hbn_version: String,
+    pub dpu_hostname: String,
+    pub dpu_search_domain: String,
+    pub is_dpu_os: bool,
+    pub contents: String,
+}
+
+impl From<rpc::AppliedNvueConfig> for AppliedNvueConfig {
+    fn from(config: rpc::AppliedNvueConfig) -> Self {
+        Self {
+            hbn_version: config.hbn_version,
+            dpu_hostname: config.dpu_hostname,
+            dpu_search_domain: config.dpu_search_domain,
+            is_dpu_os: config.is_dpu_os,
+            contents: config.contents,
+        }
+    }
+}
+
+impl From<AppliedNvueConfig> for rpc::AppliedNvueConfig {
+    fn from(config: AppliedNvueConfig) -> Self {
+        Self {
+            hbn_version: config.hbn_version,
+            dpu_hostname: config.dpu_hostname,
+            dpu_search_domain: config.dpu_search_domain,
+            is_dpu_os: config.is_dpu_os,
+            contents: config.contents,
+        }
+    }
 }
 
 impl MachineNetworkStatusObservation {
@@ -212,6 +293,10 @@ impl TryFrom<rpc::DpuNetworkStatus> for MachineNetworkStatusObservation {
             agent_version_superseded_at: None,
             instance_network_observation,
             extension_service_observation,
+            network_config_rollback: obs
+                .network_config_rollback
+                .map(NetworkConfigRollback::try_from)
+                .transpose()?,
         })
     }
 }
@@ -239,6 +324,8 @@ impl From<MachineNetworkStatusObservation> for rpc::DpuNetworkStatus {
             last_dhcp_requests: vec![],
             dpu_extension_service_version: None,
             dpu_extension_services: vec![],
+            network_config_rollback: m.network_config_rollback.map(Into::into),
+            applied_nvue_config: None,
         }
     }
 }
@@ -0,0 +1,376 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Reconstruction of a managed host as it looked at a point in time.
+//!
+//! Combines the state and health histories of a host and its DPUs with the
+//! histories of instance config versions, machine network config versions and
+//! interface addresses.
+
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+
+use carbide_uuid::instance::InstanceId;
+use carbide_uuid::machine::{MachineId, MachineInterfaceId};
+use chrono::{DateTime, Utc};
+use config_version::ConfigVersion;
+use mac_address::MacAddress;
+
+use crate::health::HealthHistoryRecord;
+use crate::state_history::StateHistoryRecord;
+
+/// A managed host as it looked at a point in time.
+///
+/// Fields are `None` (or empty) if no history had been recorded for them up to
+/// that time, e.g. because recording started later or the history was trimmed.
+#[derive(Debug, Clone)]
+pub struct ManagedHostStateAt {
+    pub time: DateTime<Utc>,
+    pub host: MachineStateAt,
+    /// The DPUs which were attached to the host's interfaces that had addresses assigned
+    pub dpus: Vec<MachineStateAt>,
+    /// The instance on the host. `None` if the host had no instance.
+    pub instance: Option<InstanceStateAt>,
+}
+
+/// A host or DPU as it looked at a point in time
+#[derive(Debug, Clone)]
+pub struct MachineStateAt {
+    pub machine_id: MachineId,
+    /// The last controller state the machine entered
+    pub controller_state: Option<StateHistoryRecord>,
+    /// The last health the machine reported
+    pub health: Option<HealthHistoryRecord>,
+    pub network_config_version: Option<ConfigVersionRecord>,
+    pub interfaces: Vec<InterfaceAddressesAt>,
+}
+
+/// A config version, and the time it was recorded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigVersionRecord {
+    pub version: ConfigVersion,
+    pub time: DateTime<Utc>,
+}
+
+/// The addresses which had been assigned to an interface of a machine
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InterfaceAddressesAt {
+    pub interface_id: MachineInterfaceId,
+    pub mac_address: Option<MacAddress>,
+    pub addresses: Vec<IpAddr>,
+}
+
+/// The instance on a host as it looked at a point in time
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstanceStateAt {
+    pub instance_id: InstanceId,
+    pub config_version: ConfigVersion,
+    pub network_config_version: ConfigVersion,
+    /// When deletion of the instance had been requested
+    pub deleted: Option<DateTime<Utc>>,
+    /// When the last change to the instance was recorded
+    pub time: DateTime<Utc>,
+}
+
+/// A field which differs between two [`ManagedHostStateAt`]s
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManagedHostFieldChange {
+    /// Path of the field, e.g. `host.controller_state` or `instance.config_version`
+    pub field: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+impl ManagedHostStateAt {
+    /// Lists the fields which changed from `self` to `later`, sorted by field
+    pub fn diff(&self, later: &ManagedHostStateAt) -> Vec<ManagedHostFieldChange> {
+        let mut before = self.fields();
+        let mut changes = Vec::new();
+        for (field, after) in later.fields() {
+            let before = before.remove(&field);
+            if before.as_ref() != Some(&after) {
+                changes.push(ManagedHostFieldChange {
+                    field,
+                    before,
+                    after: Some(after),
+                });
+            }
+        }
+        changes.extend(
+            before
+                .into_iter()
+                .map(|(field, before)| ManagedHostFieldChange {
+                    field,
+                    before: Some(before),
+                    after: None,
+                }),
+        );
+        changes.sort_by(|a, b| a.field.cmp(&b.field));
+        changes
+    }
+
+    /// The values of all comparable fields, keyed by their path
+    fn fields(&self) -> BTreeMap<String, String> {
+        let mut fields = BTreeMap::new();
+        self.host.add_fields("host", &mut fields);
+        for dpu in self.dpus.iter() {
+            dpu.add_fields(&format!("dpus.{}", dpu.machine_id), &mut fields);
+        }
+        if let Some(instance) = &self.instance {
+            fields.insert("instance.id".to_string(), instance.instance_id.to_string());
+            fields.insert(
+                "instance.config_version".to_string(),
+                instance.config_version.version_string(),
+            );
+            fields.insert(
+                "instance.network_config_version".to_string(),
+                instance.network_config_version.version_string(),
+            );
+            if let Some(deleted) = instance.deleted {
+                fields.insert("instance.deleted".to_string(), deleted.to_rfc3339());
+            }
+        }
+        fields
+    }
+}
+
+impl MachineStateAt {
+    fn add_fields(&self, prefix: &str, fields: &mut BTreeMap<String, String>) {
+        if let Some(controller_state) = &self.controller_state {
+            fields.insert(
+                format!("{prefix}.controller_state"),
+                controller_state.state.clone(),
+            );
+        }
+        if let Some(health) = &self.health {
+            let mut alerts: Vec<String> = health
+                .health
+                .alerts
+                .iter()
+                .map(|alert| match &alert.target {
+                    Some(target) => format!("{}[{target}]", alert.id),
+                    None => alert.id.to_string(),
+                })
+                .collect();
+            alerts.sort();
+            fields.insert(
+                format!("{prefix}.health.alerts"),
+                format!("[{}]", alerts.join(", ")),
+            );
+        }
+        if let Some(network_config_version) = &self.network_config_version {
+            fields.insert(
+                format!("{prefix}.network_config_version"),
+                network_config_version.version.version_string(),
+            );
+        }
+        for interface in self.interfaces.iter() {
+            let mut addresses: Vec<String> = interface
+                .addresses
+                .iter()
+                .map(|address| address.to_string())
+                .collect();
+            addresses.sort();
+            fields.insert(
+                format!("{prefix}.interfaces.{}.addresses", interface.interface_id),
+                addresses.join(", "),
+            );
+        }
+    }
+}
+
+impl From<ManagedHostStateAt> for rpc::forge::ManagedHostStateAt {
+    fn from(value: ManagedHostStateAt) -> Self {
+        rpc::forge::ManagedHostStateAt {
+            time: Some(value.time.into()),
+            host: Some(value.host.into()),
+            dpus: value.dpus.into_iter().map(Into::into).collect(),
+            instance: value.instance.map(Into::into),
+        }
+    }
+}
+
+impl From<MachineStateAt> for rpc::forge::MachineStateAt {
+    fn from(value: MachineStateAt) -> Self {
+        rpc::forge::MachineStateAt {
+            machine_id: Some(value.machine_id),
+            controller_state: value.controller_state.map(Into::into),
+            health: value.health.map(Into::into),
+            network_config_version: value.network_config_version.map(Into::into),
+            interfaces: value.interfaces.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<ConfigVersionRecord> for rpc::forge::ConfigVersionRecord {
+    fn from(value: ConfigVersionRecord) -> Self {
+        rpc::forge::ConfigVersionRecord {
+            version: value.version.version_string(),
+            time: Some(value.time.into()),
+        }
+    }
+}
+
+impl From<InterfaceAddressesAt> for rpc::forge::InterfaceAddressesAt {
+    fn from(value: InterfaceAddressesAt) -> Self {
+        rpc::forge::InterfaceAddressesAt {
+            interface_id: Some(value.interface_id),
+            mac_address: value.mac_address.map(|mac_address| mac_address.to_string()),
+            addresses: value
+                .addresses
+                .iter()
+                .map(|address| address.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl From<InstanceStateAt> for rpc::forge::InstanceStateAt {
+    fn from(value: InstanceStateAt) -> Self {
+        rpc::forge::InstanceStateAt {
+            instance_id: Some(value.instance_id),
+            config_version: value.config_version.version_string(),
+            network_config_version: value.network_config_version.version_string(),
+            deleted: value.deleted.map(Into::into),
+            time: Some(value.time.into()),
+        }
+    }
+}
+
+impl From<ManagedHostFieldChange> for rpc::forge::ManagedHostFieldChange {
+    fn from(value: ManagedHostFieldChange) -> Self {
+        rpc::forge::ManagedHostFieldChange {
+            field: value.field,
+            before: value.before,
+            after: value.after,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use health_report::{HealthProbeAlert, HealthReport};
+
+    use super::*;
+
+    fn machine(
+        machine_id: MachineId,
+        interface_id: MachineInterfaceId,
+        state: &str,
+        alert_targets: &[&str],
+        addresses: &[&str],
+    ) -> MachineStateAt {
+        let now = Utc::now();
+        let mut health = HealthReport::empty("test".to_string());
+        health.alerts = alert_targets
+            .iter()
+            .map(|target| {
+                HealthProbeAlert::heartbeat_timeout(target.to_string(), String::new(), false, false)
+            })
+            .collect();
+        MachineStateAt {
+            machine_id,
+            controller_state: Some(StateHistoryRecord {
+                state: state.to_string(),
+                state_version: ConfigVersion::initial(),
+                time: Some(now),
+            }),
+            health: Some(HealthHistoryRecord { health, time: now }),
+            network_config_version: None,
+            interfaces: vec![InterfaceAddressesAt {
+                interface_id,
+                mac_address: None,
+                addresses: addresses
+                    .iter()
+                    .map(|address| address.parse().unwrap())
+                    .collect(),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_diff_managed_host_states() {
+        let host_id: MachineId = "fm100ht038bg3qsho433vkg684heguv282qaggmrsh2ugn1qk096n2c6hcg"
+            .parse()
+            .unwrap();
+        let interface_id = MachineInterfaceId::new();
+        let instance = InstanceStateAt {
+            instance_id: InstanceId::new(),
+            config_version: ConfigVersion::initial(),
+            network_config_version: ConfigVersion::initial(),
+            deleted: None,
+            time: Utc::now(),
+        };
+
+        let before = ManagedHostStateAt {
+            time: Utc::now(),
+            host: machine(host_id, interface_id, "\"ready\"", &[], &["10.0.0.2"]),
+            dpus: Vec::new(),
+            instance: None,
+        };
+        let after = ManagedHostStateAt {
+            time: Utc::now(),
+            host: machine(
+                host_id,
+                interface_id,
+                "\"assigned\"",
+                &["forge-dpu-agent"],
+                &["10.0.0.3", "10.0.0.2"],
+            ),
+            dpus: Vec::new(),
+            instance: Some(InstanceStateAt {
+                config_version: instance.config_version.increment(),
+                ..instance.clone()
+            }),
+        };
+        assert!(before.diff(&before).is_empty());
+
+        let changes = before.diff(&after);
+        let fields: Vec<&str> = changes.iter().map(|change| change.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            vec![
+                "host.controller_state",
+                "host.health.alerts",
+                &format!("host.interfaces.{interface_id}.addresses"),
+                "instance.config_version",
+                "instance.id",
+                "instance.network_config_version",
+            ]
+        );
+        assert_eq!(changes[1].before.as_deref(), Some("[]"));
+        assert_eq!(
+            changes[1].after.as_deref(),
+            Some("[HeartbeatTimeout[forge-dpu-agent]]")
+        );
+        assert_eq!(changes[2].after.as_deref(), Some("10.0.0.2, 10.0.0.3"));
+        assert_eq!(changes[3].before, None);
+        assert_eq!(
+            changes[3].after,
+            Some(instance.config_version.increment().version_string())
+        );
+
+        // The instance is gone again
+        let changes = after.diff(&before);
+        assert!(
+            changes
+                .iter()
+                .filter(|change| change.field.starts_with("instance."))
+                .all(|change| change.after.is_none())
+        );
+    }
+}
@@ -0,0 +1,304 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Evidence of the storage sanitization scout performs when a machine is
+//! cleaned up, kept per instance release so tenants can be given a report
+//! of how the disks their data was stored on were wiped.
+
+use ::rpc::errors::RpcDataConversionError;
+use ::rpc::forge as rpc;
+use carbide_uuid::instance::InstanceId;
+use carbide_uuid::machine::MachineId;
+use chrono::prelude::*;
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+use sqlx::postgres::PgRow;
+
+/// The NIST SP 800-88 category of a sanitization method which purges data.
+pub const NIST_CATEGORY_PURGE: &str = "purge";
+/// The NIST SP 800-88 category of a sanitization method which clears data.
+pub const NIST_CATEGORY_CLEAR: &str = "clear";
+
+/// The sanitization of one storage device.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MediaSanitizationRecord {
+    pub device: String,
+    pub transport: String,
+    pub serial_number: String,
+    pub model: String,
+    pub capacity_bytes: u64,
+    pub method: String,
+    pub nist_category: String,
+    #[serde(default)]
+    pub raid_arrays: Vec<String>,
+    pub succeeded: bool,
+    #[serde(default)]
+    pub message: String,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub verification: Option<SanitizationVerification>,
+}
+
+impl MediaSanitizationRecord {
+    /// Whether every device of a cleanup was sanitized. A cleanup without
+    /// records sanitized nothing.
+    pub fn all_succeeded(records: &[MediaSanitizationRecord]) -> bool {
+        !records.is_empty() && records.iter().all(|r| r.succeeded)
+    }
+}
+
+/// The outcome of reading back sampled sectors after sanitization.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SanitizationVerification {
+    pub sectors_sampled: u32,
+    pub sectors_unchanged: u32,
+    pub passed: bool,
+}
+
+/// The sanitization of all storage devices of a machine, following an
+/// instance release or any other cleanup of the machine.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct MediaSanitizationReport {
+    pub id: i64,
+    pub machine_id: MachineId,
+    /// The released instance, if the cleanup followed an instance release
+    pub instance_id: Option<InstanceId>,
+    pub tenant_organization_id: Option<String>,
+    pub released_at: Option<DateTime<Utc>>,
+    /// Set once the machine reported its cleanup
+    pub completed_at: Option<DateTime<Utc>>,
+    pub records: Vec<MediaSanitizationRecord>,
+}
+
+/// The outcome of a [`MediaSanitizationReport`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MediaSanitizationStatus {
+    /// The machine has not reported a successful cleanup yet
+    Pending,
+    /// A device was not sanitized or failed verification, or no device was
+    /// sanitized at all
+    Failed,
+    /// Every device was sanitized, but not every device could be read back to
+    /// verify it
+    Unverified,
+    /// Every device was sanitized and passed verification
+    Succeeded,
+}
+
+impl MediaSanitizationReport {
+    pub fn status(&self) -> MediaSanitizationStatus {
+        if self.completed_at.is_none() {
+            return MediaSanitizationStatus::Pending;
+        }
+        let verification_failed = self.records.iter().any(|r| {
+            r.verification
+                .is_some_and(|verification| !verification.passed)
+        });
+        if !MediaSanitizationRecord::all_succeeded(&self.records) || verification_failed {
+            return MediaSanitizationStatus::Failed;
+        }
+        if self.records.iter().all(|r| r.verification.is_some()) {
+            MediaSanitizationStatus::Succeeded
+        } else {
+            MediaSanitizationStatus::Unverified
+        }
+    }
+
+    /// Whether every device was sanitized and passed verification.
+    pub fn succeeded(&self) -> bool {
+        self.status() == MediaSanitizationStatus::Succeeded
+    }
+}
+
+impl<'r> sqlx::FromRow<'r, PgRow> for MediaSanitizationReport {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let records: sqlx::types::Json<Vec<MediaSanitizationRecord>> = row.try_get("records")?;
+        Ok(MediaSanitizationReport {
+            id: row.try_get("id")?,
+            machine_id: row.try_get("machine_id")?,
+            instance_id: row.try_get("instance_id")?,
+            tenant_organization_id: row.try_get("tenant_organization_id")?,
+            released_at: row.try_get("released_at")?,
+            completed_at: row.try_get("completed_at")?,
+            records: records.0,
+        })
+    }
+}
+
+fn timestamp_from_rpc(
+    timestamp: Option<::rpc::Timestamp>,
+) -> Result<Option<DateTime<Utc>>, RpcDataConversionError> {
+    timestamp
+        .map(|ts| {
+            DateTime::<Utc>::try_from(ts)
+                .map_err(|e| RpcDataConversionError::InvalidTimestamp(e.to_string()))
+        })
+        .transpose()
+}
+
+impl TryFrom<rpc::MediaSanitizationRecord> for MediaSanitizationRecord {
+    type Error = RpcDataConversionError;
+
+    fn try_from(record: rpc::MediaSanitizationRecord) -> Result<Self, Self::Error> {
+        if record.device.is_empty() {
+            return Err(RpcDataConversionError::MissingArgument("device"));
+        }
+        Ok(MediaSanitizationRecord {
+            succeeded: record.result() == rpc::machine_cleanup_info::CleanupResult::Ok,
+            started_at: timestamp_from_rpc(record.started_at)?,
+            completed_at: timestamp_from_rpc(record.completed_at)?,
+            verification: record.verification.map(|v| SanitizationVerification {
+                sectors_sampled: v.sectors_sampled,
+                sectors_unchanged: v.sectors_unchanged,
+                passed: v.passed,
+            }),
+            device: record.device,
+            transport: record.transport,
+            serial_number: record.serial_number,
+            model: record.model,
+            capacity_bytes: record.capacity_bytes,
+            method: record.method,
+            nist_category: record.nist_category,
+            raid_arrays: record.raid_arrays,
+            message: record.message,
+        })
+    }
+}
+
+impl From<MediaSanitizationRecord> for rpc::MediaSanitizationRecord {
+    fn from(record: MediaSanitizationRecord) -> Self {
+        let result = if record.succeeded {
+            rpc::machine_cleanup_info::CleanupResult::Ok
+        } else {
+            rpc::machine_cleanup_info::CleanupResult::Error
+        };
+        rpc::MediaSanitizationRecord {
+            device: record.device,
+            transport: record.transport,
+            serial_number: record.serial_number,
+            model: record.model,
+            capacity_bytes: record.capacity_bytes,
+            method: record.method,
+            nist_category: record.nist_category,
+            raid_arrays: record.raid_arrays,
+            result: result as i32,
+            message: record.message,
+            started_at: record.started_at.map(Into::into),
+            completed_at: record.completed_at.map(Into::into),
+            verification: record
+                .verification
+                .map(|v| rpc::MediaSanitizationVerification {
+                    sectors_sampled: v.sectors_sampled,
+                    sectors_unchanged: v.sectors_unchanged,
+                    passed: v.passed,
+                }),
+        }
+    }
+}
+
+impl From<MediaSanitizationReport> for rpc::MediaSanitizationReport {
+    fn from(report: MediaSanitizationReport) -> Self {
+        rpc::MediaSanitizationReport {
+            machine_id: Some(report.machine_id),
+            instance_id: report.instance_id,
+            tenant_organization_id: report.tenant_organization_id.unwrap_or_default(),
+            released_at: report.released_at.map(Into::into),
+            completed_at: report.completed_at.map(Into::into),
+            records: report.records.into_iter().map(Into::into).collect(),
+            signed_report: String::new(),
+            key_id: String::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rpc_record() -> rpc::MediaSanitizationRecord {
+        rpc::MediaSanitizationRecord {
+            device: "/dev/sda".to_string(),
+            transport: "sata".to_string(),
+            serial_number: "S1".to_string(),
+            model: "Disk".to_string(),
+            capacity_bytes: 1 << 40,
+            method: "ata-security-erase-enhanced".to_string(),
+            nist_category: NIST_CATEGORY_PURGE.to_string(),
+            raid_arrays: vec!["/dev/md0".to_string()],
+            result: rpc::machine_cleanup_info::CleanupResult::Ok as i32,
+            message: String::new(),
+            started_at: Some(Utc.with_ymd_and_hms(2026, 5, 1, 10, 0, 0).unwrap().into()),
+            completed_at: Some(Utc.with_ymd_and_hms(2026, 5, 1, 11, 0, 0).unwrap().into()),
+            verification: Some(rpc::MediaSanitizationVerification {
+                sectors_sampled: 64,
+                sectors_unchanged: 0,
+                passed: true,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_record_round_trip() {
+        let record = MediaSanitizationRecord::try_from(rpc_record()).unwrap();
+        assert!(record.succeeded);
+        assert_eq!(record.raid_arrays, vec!["/dev/md0".to_string()]);
+        assert_eq!(rpc::MediaSanitizationRecord::from(record), rpc_record());
+    }
+
+    #[test]
+    fn test_report_succeeded() {
+        let record = MediaSanitizationRecord::try_from(rpc_record()).unwrap();
+        let mut report = MediaSanitizationReport {
+            id: 1,
+            machine_id: "fm100ht038bg3qsho433vkg684heguv282qaggmrsh2ugn1qk096n2c6hcg"
+                .parse()
+                .unwrap(),
+            instance_id: None,
+            tenant_organization_id: None,
+            released_at: None,
+            completed_at: None,
+            records: vec![record.clone()],
+        };
+        // Not reported yet
+        assert_eq!(report.status(), MediaSanitizationStatus::Pending);
+        assert!(!report.succeeded());
+
+        report.completed_at = Some(Utc::now());
+        assert_eq!(report.status(), MediaSanitizationStatus::Succeeded);
+        assert!(report.succeeded());
+
+        // A device that was never read back is not verified
+        report.records[0].verification = None;
+        assert_eq!(report.status(), MediaSanitizationStatus::Unverified);
+        assert!(!report.succeeded());
+
+        report.records.push(MediaSanitizationRecord {
+            verification: Some(SanitizationVerification {
+                sectors_sampled: 64,
+                sectors_unchanged: 3,
+                passed: false,
+            }),
+            ..record
+        });
+        assert_eq!(report.status(), MediaSanitizationStatus::Failed);
+
+        // A cleanup which sanitized nothing
+        report.records.clear();
+        assert_eq!(report.status(), MediaSanitizationStatus::Failed);
+    }
+}
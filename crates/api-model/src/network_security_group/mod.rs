@@ -21,6 +21,7 @@ use ::rpc::errors::RpcDataConversionError;
 use ::rpc::forge as rpc;
 use carbide_uuid::instance::InstanceId;
 use carbide_uuid::network_security_group::NetworkSecurityGroupId;
+use carbide_uuid::prefix_list::PrefixListId;
 use carbide_uuid::vpc::VpcId;
 use chrono::prelude::*;
 use config_version::ConfigVersion;
@@ -32,6 +33,7 @@ use uuid::Uuid;
 
 use super::tenant::TenantOrganizationId;
 use crate::metadata::Metadata;
+use crate::prefix_list::PrefixList;
 
 /// The maximum priority value allowed for security group rule.
 /// We could expose this in config and validate it in the API
@@ -373,6 +375,11 @@ impl TryFrom<rpc::NetworkSecurityGroupRuleAction> for NetworkSecurityGroupRuleAc
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub enum NetworkSecurityGroupRuleNet {
     Prefix(ipnetwork::IpNetwork),
+    // A prefix list owned by the same tenant.  Unlike VPCs,
+    // the tenant controls what goes into the list, and every
+    // change to a list re-validates the limits of the NSGs
+    // referencing it before it's accepted.
+    PrefixList(PrefixListId),
     // Not yet supported.  We hide this in the proto spec.
     // Implementation wouldn't be hard, but it would be hard
     // to manage operationally because this would allow users
@@ -395,6 +402,31 @@ pub enum NetworkSecurityGroupRuleNet {
     // VpcId(VpcId),
 }
 
+impl NetworkSecurityGroupRuleNet {
+    pub fn prefix_list_id(&self) -> Option<&PrefixListId> {
+        match self {
+            NetworkSecurityGroupRuleNet::Prefix(_) => None,
+            NetworkSecurityGroupRuleNet::PrefixList(id) => Some(id),
+        }
+    }
+
+    /// Returns the prefixes the net expands to in a rule of the given IP version,
+    /// or the ID of the referenced prefix list if it isn't in `prefix_lists`.
+    pub fn resolve(
+        &self,
+        ipv6: bool,
+        prefix_lists: &HashMap<PrefixListId, PrefixList>,
+    ) -> Result<Vec<ipnetwork::IpNetwork>, PrefixListId> {
+        match self {
+            NetworkSecurityGroupRuleNet::Prefix(p) => Ok(vec![*p]),
+            NetworkSecurityGroupRuleNet::PrefixList(id) => prefix_lists
+                .get(id)
+                .map(|l| l.prefixes_for(ipv6).copied().collect())
+                .ok_or(*id),
+        }
+    }
+}
+
 impl TryFrom<rpc::network_security_group_rule_attributes::SourceNet>
     for NetworkSecurityGroupRuleNet
 {
@@ -410,6 +442,9 @@ impl TryFrom<rpc::network_security_group_rule_attributes::SourceNet>
                         .map_err(|e| RpcDataConversionError::InvalidIpAddress(e.to_string()))?,
                 ))
             }
+            rpc::network_security_group_rule_attributes::SourceNet::SrcPrefixListId(id) => {
+                Ok(NetworkSecurityGroupRuleNet::PrefixList(id))
+            }
         }
     }
 }
@@ -429,6 +464,9 @@ impl TryFrom<rpc::network_security_group_rule_attributes::DestinationNet>
                         .map_err(|e| RpcDataConversionError::InvalidIpAddress(e.to_string()))?,
                 ))
             }
+            rpc::network_security_group_rule_attributes::DestinationNet::DstPrefixListId(id) => {
+                Ok(NetworkSecurityGroupRuleNet::PrefixList(id))
+            }
         }
     }
 }
@@ -443,6 +481,9 @@ impl TryFrom<NetworkSecurityGroupRuleNet>
             NetworkSecurityGroupRuleNet::Prefix(p) => Ok(
                 rpc::network_security_group_rule_attributes::SourceNet::SrcPrefix(p.to_string()),
             ),
+            NetworkSecurityGroupRuleNet::PrefixList(id) => {
+                Ok(rpc::network_security_group_rule_attributes::SourceNet::SrcPrefixListId(id))
+            }
         }
     }
 }
@@ -459,6 +500,9 @@ impl TryFrom<NetworkSecurityGroupRuleNet>
                     p.to_string(),
                 ),
             ),
+            NetworkSecurityGroupRuleNet::PrefixList(id) => Ok(
+                rpc::network_security_group_rule_attributes::DestinationNet::DstPrefixListId(id),
+            ),
         }
     }
 }
@@ -594,28 +638,39 @@ impl TryFrom<rpc::NetworkSecurityGroupRuleAttributes> for NetworkSecurityGroupRu
 
         // If prefix is used for src or dst, IP version must match rule ipv6 value.
         // This also implicitly ensures that src and dst are the same IP version.
-        match (&converted_rule.src_net, &converted_rule.dst_net) {
-            (NetworkSecurityGroupRuleNet::Prefix(s), NetworkSecurityGroupRuleNet::Prefix(d)) => {
-                if s.is_ipv6() != converted_rule.ipv6 {
-                    return Err(RpcDataConversionError::InvalidValue(
-                        "src_prefix".to_string(),
-                        "IP version of prefix does not match IP version of rule".to_string(),
-                    ));
-                }
-
-                if d.is_ipv6() != converted_rule.ipv6 {
-                    return Err(RpcDataConversionError::InvalidValue(
-                        "dst_prefix".to_string(),
-                        "IP version of prefix does not match IP version of rule".to_string(),
-                    ));
-                }
+        // Prefix lists can hold both versions, and only the prefixes matching
+        // the rule are used when the rule is expanded.
+        for (field, net) in [
+            ("src_prefix", &converted_rule.src_net),
+            ("dst_prefix", &converted_rule.dst_net),
+        ] {
+            if let NetworkSecurityGroupRuleNet::Prefix(p) = net
+                && p.is_ipv6() != converted_rule.ipv6
+            {
+                return Err(RpcDataConversionError::InvalidValue(
+                    field.to_string(),
+                    "IP version of prefix does not match IP version of rule".to_string(),
+                ));
             }
-        };
+        }
 
         Ok(converted_rule)
     }
 }
 
+/// Returns the IDs of all prefix lists referenced by a set of rules, without duplicates.
+pub fn referenced_prefix_list_ids(rules: &[NetworkSecurityGroupRule]) -> Vec<PrefixListId> {
+    let mut ids: Vec<PrefixListId> = rules
+        .iter()
+        .flat_map(|r| [r.src_net.prefix_list_id(), r.dst_net.prefix_list_id()])
+        .flatten()
+        .copied()
+        .collect();
+    ids.sort();
+    ids.dedup();
+    ids
+}
+
 impl TryFrom<NetworkSecurityGroupRule> for rpc::NetworkSecurityGroupRuleAttributes {
     type Error = RpcDataConversionError;
 
@@ -1310,6 +1365,74 @@ mod tests {
         NetworkSecurityGroupRule::try_from(req).unwrap_err();
     }
 
+    #[test]
+    fn test_rpc_rule_with_prefix_list_conversion() {
+        let prefix_list_id: PrefixListId = "9ab5a1b8-4d1c-11f1-9c1e-1b2f0a6c3d4e".parse().unwrap();
+
+        // A prefix list can be used with an IPv6 rule even though its IP version
+        // can't be checked until the rule is expanded.
+        let req = rpc::NetworkSecurityGroupRuleAttributes {
+            id: Some("anything".to_string()),
+            direction: rpc::NetworkSecurityGroupRuleDirection::NsgRuleDirectionIngress.into(),
+            ipv6: true,
+            src_port_start: None,
+            src_port_end: None,
+            dst_port_start: Some(443),
+            dst_port_end: Some(443),
+            protocol: rpc::NetworkSecurityGroupRuleProtocol::NsgRuleProtoTcp.into(),
+            action: rpc::NetworkSecurityGroupRuleAction::NsgRuleActionPermit.into(),
+            priority: 100,
+            source_net: Some(
+                rpc::network_security_group_rule_attributes::SourceNet::SrcPrefixListId(
+                    prefix_list_id,
+                ),
+            ),
+            destination_net: Some(
+                rpc::network_security_group_rule_attributes::DestinationNet::DstPrefix(
+                    "2001:db8::/32".to_string(),
+                ),
+            ),
+        };
+        let rule = NetworkSecurityGroupRule::try_from(req.clone()).unwrap();
+        assert_eq!(
+            rule.src_net,
+            NetworkSecurityGroupRuleNet::PrefixList(prefix_list_id)
+        );
+        assert_eq!(
+            referenced_prefix_list_ids(&[rule.clone(), rule.clone()]),
+            vec![prefix_list_id]
+        );
+        assert_eq!(
+            rpc::NetworkSecurityGroupRuleAttributes::try_from(rule.clone()).unwrap(),
+            req
+        );
+
+        // Only the prefixes of the rule's IP version are used.
+        let prefix_list = PrefixList {
+            id: prefix_list_id,
+            tenant_organization_id: "best_org".parse().unwrap(),
+            prefixes: vec![
+                "192.0.2.0/24".parse().unwrap(),
+                "2001:db8:1::/48".parse().unwrap(),
+            ],
+            version: ConfigVersion::initial(),
+            created: "2025-01-01 01:01:01 UTC".parse().unwrap(),
+            deleted: None,
+            metadata: Metadata::default(),
+            created_by: None,
+            updated_by: None,
+        };
+        assert_eq!(
+            rule.src_net.resolve(rule.ipv6, &HashMap::new()),
+            Err(prefix_list_id)
+        );
+        assert_eq!(
+            rule.src_net
+                .resolve(rule.ipv6, &crate::prefix_list::by_id(vec![prefix_list])),
+            Ok(vec!["2001:db8:1::/48".parse().unwrap()])
+        );
+    }
+
     #[test]
     fn test_model_nsg_attachments_to_rpc_conversion() {
         // Full
@@ -258,6 +258,11 @@ impl PowerShelf {
     pub fn is_marked_as_deleted(&self) -> bool {
         self.deleted.is_some()
     }
+
+    /// The health of the power shelf, derived from all of its health report sources
+    pub fn aggregate_health(&self) -> health_report::HealthReport {
+        derive_power_shelf_aggregate_health(&self.health_reports)
+    }
 }
 
 /// State of a PowerShelf as tracked by the controller
@@ -0,0 +1,226 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::{HashMap, HashSet};
+
+use ::rpc::errors::RpcDataConversionError;
+use ::rpc::forge as rpc;
+use carbide_uuid::prefix_list::PrefixListId;
+use chrono::prelude::*;
+use config_version::ConfigVersion;
+use ipnetwork::IpNetwork;
+use sqlx::Row;
+use sqlx::postgres::PgRow;
+
+use super::tenant::TenantOrganizationId;
+use crate::metadata::Metadata;
+
+/* ********************************** */
+/*              PrefixList            */
+/* ********************************** */
+
+/// PrefixList is a named, tenant-owned set of prefixes that
+/// network security group rules can reference as their source
+/// or destination.  References are expanded to the prefixes of
+/// the list when the rules are sent to the DPUs, so changes to
+/// a list apply to every network security group using it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PrefixList {
+    pub id: PrefixListId,
+    pub tenant_organization_id: TenantOrganizationId,
+    pub prefixes: Vec<IpNetwork>,
+    pub version: ConfigVersion,
+    pub created: DateTime<Utc>,
+    pub deleted: Option<DateTime<Utc>>,
+    pub metadata: Metadata,
+    pub created_by: Option<String>,
+    pub updated_by: Option<String>,
+}
+
+impl PrefixList {
+    /// The prefixes that apply to a rule of the given IP version.
+    /// A list can mix IPv4 and IPv6 prefixes, but a rule only ever
+    /// matches one IP version.
+    pub fn prefixes_for(&self, ipv6: bool) -> impl Iterator<Item = &IpNetwork> {
+        self.prefixes.iter().filter(move |p| p.is_ipv6() == ipv6)
+    }
+}
+
+/// Converts the prefixes of a request, rejecting invalid and duplicate prefixes.
+pub fn prefixes_from_rpc(
+    attributes: rpc::PrefixListAttributes,
+) -> Result<Vec<IpNetwork>, RpcDataConversionError> {
+    let mut seen = HashSet::new();
+    attributes
+        .prefixes
+        .iter()
+        .map(|p| {
+            let prefix = p
+                .parse::<IpNetwork>()
+                .map_err(|e| RpcDataConversionError::InvalidIpAddress(e.to_string()))?;
+            if !seen.insert(prefix) {
+                return Err(RpcDataConversionError::InvalidValue(
+                    "prefixes".to_string(),
+                    format!("duplicate prefix `{prefix}`"),
+                ));
+            }
+            Ok(prefix)
+        })
+        .collect()
+}
+
+/// Indexes prefix lists by ID for resolving rule references.
+pub fn by_id(prefix_lists: Vec<PrefixList>) -> HashMap<PrefixListId, PrefixList> {
+    prefix_lists.into_iter().map(|p| (p.id, p)).collect()
+}
+
+impl<'r> sqlx::FromRow<'r, PgRow> for PrefixList {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let labels: sqlx::types::Json<HashMap<String, String>> = row.try_get("labels")?;
+
+        let metadata = Metadata {
+            name: row.try_get("name")?,
+            description: row.try_get("description")?,
+            labels: labels.0,
+        };
+
+        let prefixes: sqlx::types::Json<Vec<IpNetwork>> = row.try_get("prefixes")?;
+        let tenant_organization_id: String = row.try_get("tenant_organization_id")?;
+
+        Ok(PrefixList {
+            id: row.try_get("id")?,
+            version: row.try_get("version")?,
+            tenant_organization_id: tenant_organization_id
+                .parse::<TenantOrganizationId>()
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
+            created: row.try_get("created")?,
+            deleted: row.try_get("deleted")?,
+            created_by: row.try_get("created_by")?,
+            updated_by: row.try_get("updated_by")?,
+            metadata,
+            prefixes: prefixes.0,
+        })
+    }
+}
+
+impl From<PrefixList> for rpc::PrefixList {
+    fn from(prefix_list: PrefixList) -> Self {
+        rpc::PrefixList {
+            id: Some(prefix_list.id),
+            tenant_organization_id: prefix_list.tenant_organization_id.to_string(),
+            version: prefix_list.version.to_string(),
+            attributes: Some(rpc::PrefixListAttributes {
+                prefixes: prefix_list.prefixes.iter().map(|p| p.to_string()).collect(),
+            }),
+            created_at: Some(prefix_list.created.to_string()),
+            created_by: prefix_list.created_by,
+            updated_by: prefix_list.updated_by,
+            metadata: Some(rpc::Metadata {
+                name: prefix_list.metadata.name,
+                description: prefix_list.metadata.description,
+                labels: prefix_list
+                    .metadata
+                    .labels
+                    .iter()
+                    .map(|(key, value)| rpc::Label {
+                        key: key.to_owned(),
+                        value: if value.is_empty() {
+                            None
+                        } else {
+                            Some(value.to_owned())
+                        },
+                    })
+                    .collect(),
+            }),
+        }
+    }
+}
+
+/* ********************************** */
+/*              Tests                 */
+/* ********************************** */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_model_prefix_list_to_rpc_conversion() {
+        let version = ConfigVersion::initial();
+
+        let req_type = rpc::PrefixList {
+            id: Some("9ab5a1b8-4d1c-11f1-9c1e-1b2f0a6c3d4e".parse().unwrap()),
+            tenant_organization_id: "best_org".to_string(),
+            version: version.to_string(),
+            metadata: Some(rpc::Metadata {
+                name: "office".to_string(),
+                description: "".to_string(),
+                labels: vec![],
+            }),
+            attributes: Some(rpc::PrefixListAttributes {
+                prefixes: vec!["192.0.2.0/24".to_string(), "2001:db8::/32".to_string()],
+            }),
+            created_at: Some("2025-01-01 01:01:01 UTC".to_string()),
+            created_by: Some("this_guy".to_string()),
+            updated_by: None,
+        };
+
+        let prefix_list = PrefixList {
+            id: "9ab5a1b8-4d1c-11f1-9c1e-1b2f0a6c3d4e".parse().unwrap(),
+            tenant_organization_id: "best_org".parse().unwrap(),
+            prefixes: vec![
+                "192.0.2.0/24".parse().unwrap(),
+                "2001:db8::/32".parse().unwrap(),
+            ],
+            version,
+            created: "2025-01-01 01:01:01 UTC".parse().unwrap(),
+            deleted: None,
+            metadata: Metadata {
+                name: "office".to_string(),
+                description: "".to_string(),
+                labels: HashMap::new(),
+            },
+            created_by: Some("this_guy".to_string()),
+            updated_by: None,
+        };
+
+        assert_eq!(
+            prefix_list.prefixes_for(true).collect::<Vec<_>>(),
+            vec![&"2001:db8::/32".parse::<IpNetwork>().unwrap()]
+        );
+        assert_eq!(req_type, rpc::PrefixList::from(prefix_list));
+    }
+
+    #[test]
+    fn test_prefixes_from_rpc() {
+        let prefixes = prefixes_from_rpc(rpc::PrefixListAttributes {
+            prefixes: vec!["192.0.2.0/24".to_string(), "2001:db8::/32".to_string()],
+        })
+        .unwrap();
+        assert_eq!(prefixes.len(), 2);
+
+        prefixes_from_rpc(rpc::PrefixListAttributes {
+            prefixes: vec!["192.0.2.0/33".to_string()],
+        })
+        .unwrap_err();
+
+        prefixes_from_rpc(rpc::PrefixListAttributes {
+            prefixes: vec!["192.0.2.0/24".to_string(), "192.0.2.0/24".to_string()],
+        })
+        .unwrap_err();
+    }
+}
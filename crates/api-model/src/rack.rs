@@ -416,6 +416,12 @@ impl<'r> FromRow<'r, PgRow> for Rack {
 /// ```text
 /// FirmwareUpgrade -> NVOSUpdate -> ConfigureNmxCluster -> Completed -> Validating(Pending)
 /// ```
+///
+/// ### Leak Response
+///
+/// Any state other than `Deleting` enters `LeakResponse` when the leak-response
+/// playbook is enabled and a leak is reported for the rack. See
+/// [`RackLeakResponseState`] for the sub-states.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(tag = "state", rename_all = "snake_case")]
 pub enum RackState {
@@ -451,6 +457,12 @@ pub enum RackState {
         maintenance_state: RackMaintenanceState,
     },
 
+    /// The automated leak-response playbook is isolating the compute trays of
+    /// the rack after a leak was reported.
+    LeakResponse {
+        leak_response_state: RackLeakResponseState,
+    },
+
     /// There is error in the Rack; Rack can not be used if it's in error.
     Error { cause: String },
 
@@ -561,6 +573,73 @@ impl Display for NvosUpdateState {
     }
 }
 
+/// Sub-states of the automated leak-response playbook.
+///
+/// Every step is its own sub-state so that the rack state history records
+/// when allocations were blocked, tenants were notified and trays were
+/// powered off.
+///
+/// ## Sub-state Flow
+///
+/// ```text
+/// Confirming -> BlockingAllocations -> NotifyingTenants -> WaitingForTenants
+///     -> ShuttingDown -> WaitForShutdown -> Isolated
+///                              |               ^
+///                              v               |
+///                          PoweringOff <-> WaitForPowerOff
+/// ```
+///
+/// A leak which clears while `Confirming` ends the playbook without touching
+/// the trays. Once confirmed, the playbook runs to `Isolated`, which the rack
+/// only leaves through an on-demand maintenance request after the leak cleared.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RackLeakResponseState {
+    /// Waiting for the confirmation window to pass while the leak is still
+    /// reported.
+    Confirming {
+        /// When the leak alert was first raised.
+        detected_at: DateTime<Utc>,
+        /// Whether the rack was in a state other than `Ready`. A leak which
+        /// clears during confirmation then restarts the rack from
+        /// `Discovering` instead of returning it to `Ready`.
+        interrupted: bool,
+    },
+    /// Applying a health override which prevents allocations to every
+    /// compute tray of the rack.
+    BlockingAllocations,
+    /// Adding a tenant message to the override of every tray with an instance.
+    NotifyingTenants,
+    /// Giving tenants time to react before their trays are shut down.
+    WaitingForTenants { notified_at: DateTime<Utc> },
+    /// Requesting a graceful shutdown of every powered on tray.
+    ShuttingDown,
+    /// Waiting for the trays to finish their graceful shutdown.
+    WaitForShutdown { requested_at: DateTime<Utc> },
+    /// Forcing off the trays which are still powered on.
+    PoweringOff,
+    /// Waiting for the forced power off to take effect.
+    WaitForPowerOff { requested_at: DateTime<Utc> },
+    /// All trays are powered off and are kept off until an operator returns
+    /// the rack to service.
+    Isolated,
+}
+
+impl Display for RackLeakResponseState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RackLeakResponseState::Confirming { .. } => write!(f, "Confirming"),
+            RackLeakResponseState::BlockingAllocations => write!(f, "BlockingAllocations"),
+            RackLeakResponseState::NotifyingTenants => write!(f, "NotifyingTenants"),
+            RackLeakResponseState::WaitingForTenants { .. } => write!(f, "WaitingForTenants"),
+            RackLeakResponseState::ShuttingDown => write!(f, "ShuttingDown"),
+            RackLeakResponseState::WaitForShutdown { .. } => write!(f, "WaitForShutdown"),
+            RackLeakResponseState::PoweringOff => write!(f, "PoweringOff"),
+            RackLeakResponseState::WaitForPowerOff { .. } => write!(f, "WaitForPowerOff"),
+            RackLeakResponseState::Isolated => write!(f, "Isolated"),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum RackPowerState {
     PoweringOn,
@@ -664,6 +743,9 @@ impl Display for RackState {
             RackState::Maintenance { maintenance_state } => {
                 write!(f, "Maintenance({})", maintenance_state)
             }
+            RackState::LeakResponse {
+                leak_response_state,
+            } => write!(f, "LeakResponse({})", leak_response_state),
             RackState::Error { cause } => write!(f, "Error({})", cause),
             RackState::Deleting => write!(f, "Deleting"),
         }
@@ -719,7 +801,9 @@ pub enum MaintenanceActivity {
     PowerSequence,
     /// Per-device power control, dispatched by the rack state controller to
     /// the listed devices on its next tick. Framed out here for the component
-    /// manager routing path; the rack state handler side is a follow-up.
+    /// manager routing path. The rack state handler applies power actions to
+    /// compute trays during the leak-response playbook; running it as an
+    /// on-demand maintenance activity is a follow-up.
     PowerControl {
         action: PowerAction,
     },
@@ -796,7 +880,7 @@ pub struct RackConfig {
 /// See `Rack::check_accepts_maintenance`.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum RackMaintenanceRejection {
-    /// The rack is not in `Ready` or `Error`. Carries the current state so
+    /// The rack is not in `Ready`, `Error` or `LeakResponse(Isolated)`. Carries the current state so
     /// callers can report exactly what state they saw.
     NotReadyOrError(RackState),
     /// A maintenance request is already pending on this rack.
@@ -808,8 +892,8 @@ impl Display for RackMaintenanceRejection {
         match self {
             RackMaintenanceRejection::NotReadyOrError(state) => write!(
                 f,
-                "rack is not in Ready or Error state (current: {state:?}); \
-                 maintenance can only be requested from those states",
+                "rack is not in Ready or Error state, or isolated after a leak \
+                 (current: {state:?}); maintenance can only be requested from those states",
             ),
             RackMaintenanceRejection::AlreadyPending => {
                 write!(f, "rack already has a pending maintenance request")
@@ -819,6 +903,11 @@ impl Display for RackMaintenanceRejection {
 }
 
 impl Rack {
+    /// The health of the rack, derived from all of its health report sources
+    pub fn aggregate_health(&self) -> health_report::HealthReport {
+        derive_rack_aggregate_health(&self.health_reports)
+    }
+
     /// Tells us if this rack will accept a new on-demand maintenance requests
     /// right now. Used by every caller that writes to `RackConfig::maintenance_requested`
     /// (e.g. the on-demand-maintenance gRPC handler + and the Component Manager
@@ -827,7 +916,11 @@ impl Rack {
     pub fn check_accepts_maintenance(&self) -> Result<(), RackMaintenanceRejection> {
         if !matches!(
             *self.controller_state,
-            RackState::Ready | RackState::Error { .. }
+            RackState::Ready
+                | RackState::Error { .. }
+                | RackState::LeakResponse {
+                    leak_response_state: RackLeakResponseState::Isolated,
+                }
         ) {
             return Err(RackMaintenanceRejection::NotReadyOrError(
                 self.controller_state.value.clone(),
@@ -857,6 +950,7 @@ pub fn state_sla(state: &RackState, state_version: &ConfigVersion) -> StateSla {
         RackState::Validating { .. } => StateSla::no_sla(),
         RackState::Ready => StateSla::no_sla(),
         RackState::Maintenance { .. } => StateSla::no_sla(),
+        RackState::LeakResponse { .. } => StateSla::no_sla(),
         RackState::Error { .. } => StateSla::no_sla(),
         RackState::Deleting => StateSla::no_sla(),
     }
@@ -1103,6 +1197,29 @@ mod tests {
         assert!(matches!(err, RackMaintenanceRejection::NotReadyOrError(_)));
     }
 
+    #[test]
+    fn accepts_maintenance_when_isolated_after_leak() {
+        let rack = test_rack(
+            RackState::LeakResponse {
+                leak_response_state: RackLeakResponseState::Isolated,
+            },
+            None,
+        );
+        assert!(rack.check_accepts_maintenance().is_ok());
+    }
+
+    #[test]
+    fn rejects_maintenance_during_leak_response() {
+        let rack = test_rack(
+            RackState::LeakResponse {
+                leak_response_state: RackLeakResponseState::ShuttingDown,
+            },
+            None,
+        );
+        let err = rack.check_accepts_maintenance().unwrap_err();
+        assert!(matches!(err, RackMaintenanceRejection::NotReadyOrError(_)));
+    }
+
     #[test]
     fn rejects_maintenance_when_already_pending() {
         let rack = test_rack(RackState::Ready, Some(MaintenanceScope::default()));
@@ -0,0 +1,146 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//! Retention of history tables that would otherwise grow without bound.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// A table (or group of tables) whose rows can be trimmed by the retention engine.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetentionTarget {
+    /// Measured boot reports, together with their values and journal entries
+    MeasuredBoot,
+    MachineStateHistory,
+    NetworkSegmentStateHistory,
+    IbPartitionStateHistory,
+    DpaInterfaceStateHistory,
+    PowerShelfStateHistory,
+    SwitchStateHistory,
+    RackStateHistory,
+    MachineHealthHistory,
+    MachineValidationResults,
+    /// Applied redfish actions. Pending actions are never trimmed.
+    RedfishBmcActions,
+    SpdmAttestationHistory,
+    RackFirmwareApplyHistory,
+    InstanceConfigHistory,
+    MachineNetworkConfigHistory,
+    MachineInterfaceAddressHistory,
+}
+
+impl RetentionTarget {
+    pub const ALL: [RetentionTarget; 16] = [
+        RetentionTarget::MeasuredBoot,
+        RetentionTarget::MachineStateHistory,
+        RetentionTarget::NetworkSegmentStateHistory,
+        RetentionTarget::IbPartitionStateHistory,
+        RetentionTarget::DpaInterfaceStateHistory,
+        RetentionTarget::PowerShelfStateHistory,
+        RetentionTarget::SwitchStateHistory,
+        RetentionTarget::RackStateHistory,
+        RetentionTarget::MachineHealthHistory,
+        RetentionTarget::MachineValidationResults,
+        RetentionTarget::RedfishBmcActions,
+        RetentionTarget::SpdmAttestationHistory,
+        RetentionTarget::RackFirmwareApplyHistory,
+        RetentionTarget::InstanceConfigHistory,
+        RetentionTarget::MachineNetworkConfigHistory,
+        RetentionTarget::MachineInterfaceAddressHistory,
+    ];
+
+    /// The table the trimmed rows are taken from
+    pub fn table_name(&self) -> &'static str {
+        match self {
+            RetentionTarget::MeasuredBoot => "measurement_reports",
+            RetentionTarget::MachineStateHistory => "machine_state_history",
+            RetentionTarget::NetworkSegmentStateHistory => "network_segment_state_history",
+            RetentionTarget::IbPartitionStateHistory => "ib_partition_state_history",
+            RetentionTarget::DpaInterfaceStateHistory => "dpa_interface_state_history",
+            RetentionTarget::PowerShelfStateHistory => "power_shelf_state_history",
+            RetentionTarget::SwitchStateHistory => "switch_state_history",
+            RetentionTarget::RackStateHistory => "rack_state_history",
+            RetentionTarget::MachineHealthHistory => "machine_health_history",
+            RetentionTarget::MachineValidationResults => "machine_validation_results",
+            RetentionTarget::RedfishBmcActions => "redfish_bmc_actions",
+            RetentionTarget::SpdmAttestationHistory => "spdm_machine_attestation_history",
+            RetentionTarget::RackFirmwareApplyHistory => "rack_firmware_apply_history",
+            RetentionTarget::InstanceConfigHistory => "instance_config_history",
+            RetentionTarget::MachineNetworkConfigHistory => "machine_network_config_history",
+            RetentionTarget::MachineInterfaceAddressHistory => "machine_interface_address_history",
+        }
+    }
+}
+
+impl fmt::Display for RetentionTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.table_name())
+    }
+}
+
+impl From<rpc::forge::TrimTableTarget> for RetentionTarget {
+    fn from(target: rpc::forge::TrimTableTarget) -> Self {
+        use rpc::forge::TrimTableTarget;
+        match target {
+            TrimTableTarget::MeasuredBoot => RetentionTarget::MeasuredBoot,
+            TrimTableTarget::MachineStateHistory => RetentionTarget::MachineStateHistory,
+            TrimTableTarget::NetworkSegmentStateHistory => {
+                RetentionTarget::NetworkSegmentStateHistory
+            }
+            TrimTableTarget::IbPartitionStateHistory => RetentionTarget::IbPartitionStateHistory,
+            TrimTableTarget::DpaInterfaceStateHistory => RetentionTarget::DpaInterfaceStateHistory,
+            TrimTableTarget::PowerShelfStateHistory => RetentionTarget::PowerShelfStateHistory,
+            TrimTableTarget::SwitchStateHistory => RetentionTarget::SwitchStateHistory,
+            TrimTableTarget::RackStateHistory => RetentionTarget::RackStateHistory,
+            TrimTableTarget::MachineHealthHistory => RetentionTarget::MachineHealthHistory,
+            TrimTableTarget::MachineValidationResults => RetentionTarget::MachineValidationResults,
+            TrimTableTarget::RedfishBmcActions => RetentionTarget::RedfishBmcActions,
+            TrimTableTarget::SpdmAttestationHistory => RetentionTarget::SpdmAttestationHistory,
+            TrimTableTarget::RackFirmwareApplyHistory => RetentionTarget::RackFirmwareApplyHistory,
+            TrimTableTarget::InstanceConfigHistory => RetentionTarget::InstanceConfigHistory,
+            TrimTableTarget::MachineNetworkConfigHistory => {
+                RetentionTarget::MachineNetworkConfigHistory
+            }
+            TrimTableTarget::MachineInterfaceAddressHistory => {
+                RetentionTarget::MachineInterfaceAddressHistory
+            }
+        }
+    }
+}
+
+/// Which rows of a [`RetentionTarget`] are trimmed.
+///
+/// Rows are grouped by the object they belong to (e.g. the machine of a state
+/// history entry). A row is trimmed if it exceeds `max_rows_per_object` or is
+/// older than `max_age`, unless it is one of the `keep_last` newest rows of its
+/// object.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    pub max_age: Option<chrono::Duration>,
+    pub max_rows_per_object: Option<u32>,
+    /// The newest rows of each object which are always retained. For state
+    /// histories these are the last state transitions.
+    pub keep_last: u32,
+}
+
+impl RetentionPolicy {
+    /// Whether the policy can trim any rows at all
+    pub fn is_noop(&self) -> bool {
+        self.max_age.is_none() && self.max_rows_per_object.is_none()
+    }
+}
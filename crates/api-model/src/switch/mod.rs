@@ -509,6 +509,11 @@ impl Switch {
     pub fn is_marked_as_deleted(&self) -> bool {
         self.deleted.is_some()
     }
+
+    /// The health of the switch, derived from all of its health report sources
+    pub fn aggregate_health(&self) -> health_report::HealthReport {
+        derive_switch_aggregate_health(&self.health_reports)
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -516,6 +516,19 @@ pub struct SigningKeyMaterial {
     pub signing_key_public: SigningPublicKeyPem,
 }
 
+/// Database row for tenant_x509_ca_keys table.
+/// Key of the tenant X.509-SVID root CA, kept apart from the JWT-SVID signing key.
+#[derive(Debug, sqlx::FromRow)]
+pub struct TenantX509CaKey {
+    pub organization_id: TenantOrganizationId,
+    /// `key_id` of the signing key the CA key was created with. The CA key is replaced when
+    /// the signing key is rotated.
+    pub signing_key_id: KeyId,
+    pub encrypted_ca_key: EncryptedSigningPrivateKey,
+    pub encryption_key_id: EncryptionKeyId,
+    pub created_at: DateTime<Utc>,
+}
+
 /// Settable fields for tenant identity config (SPIFFE JWT-SVID).
 /// Used as input to set identity configuration.
 #[derive(Debug, Clone)]
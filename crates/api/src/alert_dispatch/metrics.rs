@@ -0,0 +1,69 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//! Metrics for the alert dispatcher.
+
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::{Counter, Meter};
+
+use super::AlertStatus;
+
+pub struct AlertDispatchMetrics {
+    alerts_sent: Counter<u64>,
+    delivery_retries: Counter<u64>,
+    delivery_failures: Counter<u64>,
+}
+
+impl AlertDispatchMetrics {
+    pub fn new(meter: &Meter) -> Self {
+        Self {
+            alerts_sent: meter
+                .u64_counter("carbide_alert_dispatch_alerts_sent_count")
+                .with_description("The number of alerts delivered to alert receivers")
+                .build(),
+            delivery_retries: meter
+                .u64_counter("carbide_alert_dispatch_delivery_retries_count")
+                .with_description("The number of retried deliveries of alert notifications")
+                .build(),
+            delivery_failures: meter
+                .u64_counter("carbide_alert_dispatch_delivery_failures_count")
+                .with_description(
+                    "The number of alert notifications which could not be delivered after all retries",
+                )
+                .build(),
+        }
+    }
+
+    pub fn record_sent(&self, receiver: &str, status: AlertStatus, count: u64) {
+        self.alerts_sent.add(
+            count,
+            &[
+                KeyValue::new("receiver", receiver.to_string()),
+                KeyValue::new("status", status.as_str()),
+            ],
+        );
+    }
+
+    pub fn record_retry(&self, receiver: &str) {
+        self.delivery_retries
+            .add(1, &[KeyValue::new("receiver", receiver.to_string())]);
+    }
+
+    pub fn record_failure(&self, receiver: &str) {
+        self.delivery_failures
+            .add(1, &[KeyValue::new("receiver", receiver.to_string())]);
+    }
+}
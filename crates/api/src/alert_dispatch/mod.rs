@@ -0,0 +1,817 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//! Notifies external receivers when health alerts start and clear.
+//!
+//! The `AlertDispatcher` periodically derives the aggregate health of all
+//! machines, racks, switches and power shelves and compares it with the
+//! report it last dispatched for each object, which is kept in the database.
+//! Alerts which appeared since are sent as firing, alerts which disappeared
+//! as resolved. Alerts classified as `SuppressExternalAlerting`, which
+//! includes silenced alerts, are treated as if they were not raised.
+//!
+//! Alerts are routed to receivers by classification, object kind and
+//! severity, and grouped into one notification per receiver and group key.
+//! If a notification can not be delivered after all retries, the stored
+//! reports of the affected objects are left unchanged, so that the next run
+//! sends the transitions again. Receivers therefore need to tolerate
+//! duplicate notifications.
+
+pub(crate) mod metrics;
+pub(crate) mod receiver;
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::io;
+use std::sync::Arc;
+
+use carbide_utils::periodic_timer::PeriodicTimer;
+use chrono::{DateTime, Utc};
+use db::ObjectColumnFilter;
+use db::work_lock_manager::WorkLockManagerHandle;
+use health_report::{HealthAlertClassification, HealthProbeAlert, HealthReport};
+use model::alert_dispatch::{AlertObjectKind, AlertSeverity};
+use model::health_silence::{HealthSilenceObjectKind, HealthSilenceSubject, apply_health_silences};
+use model::machine::{HostHealthConfig, LoadSnapshotOptions};
+use serde::Serialize;
+use sqlx::PgPool;
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+
+use self::metrics::AlertDispatchMetrics;
+use self::receiver::{AlertDeliveryError, AlertReceiver};
+use crate::CarbideResult;
+use crate::cfg::file::{AlertDispatchConfig, AlertRouteConfig};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertStatus {
+    Firing,
+    Resolved,
+}
+
+impl AlertStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertStatus::Firing => "firing",
+            AlertStatus::Resolved => "resolved",
+        }
+    }
+}
+
+/// A health alert of a single object, as sent to receivers
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct DispatchedAlert {
+    pub status: AlertStatus,
+    pub object_kind: AlertObjectKind,
+    pub object_id: String,
+    pub probe_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    pub severity: AlertSeverity,
+    pub classifications: Vec<HealthAlertClassification>,
+    pub message: String,
+    pub starts_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ends_at: Option<DateTime<Utc>>,
+    /// `alertname` (the probe ID), `object_kind`, `object_id`, `severity`,
+    /// and, where known, `target` and `rack_id`
+    pub labels: BTreeMap<String, String>,
+    /// Whether the alert started or cleared in this run. Alerts which are
+    /// still firing are only sent to receivers which re-send firing alerts.
+    #[serde(skip)]
+    pub transition: bool,
+}
+
+/// The alerts sent to a receiver in one request
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct AlertNotification {
+    pub receiver: String,
+    /// `firing` if any of the alerts is firing
+    pub status: AlertStatus,
+    pub group_labels: BTreeMap<String, String>,
+    pub alerts: Vec<DispatchedAlert>,
+}
+
+impl AlertNotification {
+    /// A one line description, e.g. `[FIRING:2] alertname=BmcSensor`
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "[{}:{}]",
+            self.status.as_str().to_uppercase(),
+            self.alerts.len()
+        );
+        for (name, value) in self.group_labels.iter() {
+            summary.push_str(&format!(" {name}={value}"));
+        }
+        summary
+    }
+}
+
+/// The aggregate health of an object, as considered for notifications
+#[derive(Clone, Debug)]
+pub(crate) struct ObjectHealth {
+    pub kind: AlertObjectKind,
+    pub id: String,
+    pub labels: BTreeMap<String, String>,
+    pub report: HealthReport,
+}
+
+impl ObjectHealth {
+    fn new(
+        kind: AlertObjectKind,
+        id: String,
+        rack_id: Option<String>,
+        report: HealthReport,
+    ) -> Self {
+        let labels = rack_id
+            .map(|rack_id| BTreeMap::from([("rack_id".to_string(), rack_id)]))
+            .unwrap_or_default();
+        Self {
+            kind,
+            id,
+            labels,
+            report: notifiable_report(report),
+        }
+    }
+}
+
+/// Removes the alerts which are not notified about, as well as the parts of
+/// a report which change without any alert starting or clearing.
+pub(crate) fn notifiable_report(mut report: HealthReport) -> HealthReport {
+    let suppress = HealthAlertClassification::suppress_external_alerting();
+    report
+        .alerts
+        .retain(|alert| !alert.classifications.contains(&suppress));
+    report.successes.clear();
+    report.observed_at = None;
+    report.triggered_by = None;
+    report
+}
+
+fn severity(
+    alert: &HealthProbeAlert,
+    critical_classifications: &[HealthAlertClassification],
+) -> AlertSeverity {
+    if alert
+        .classifications
+        .iter()
+        .any(|c| critical_classifications.contains(c))
+    {
+        AlertSeverity::Critical
+    } else {
+        AlertSeverity::Warning
+    }
+}
+
+/// Derives the alerts of an object to notify about. `object.report` needs to
+/// have its `in_alert_since` timestamps updated from `previous`.
+pub(crate) fn derive_alerts(
+    object: &ObjectHealth,
+    previous: Option<&HealthReport>,
+    critical_classifications: &[HealthAlertClassification],
+    now: DateTime<Utc>,
+) -> Vec<DispatchedAlert> {
+    let was_raised = |report: Option<&HealthReport>, alert: &HealthProbeAlert| -> bool {
+        report.is_some_and(|report| {
+            report
+                .alerts
+                .iter()
+                .any(|other| other.id == alert.id && other.target == alert.target)
+        })
+    };
+    let to_dispatched = |alert: &HealthProbeAlert, status: AlertStatus, transition: bool| {
+        let severity = severity(alert, critical_classifications);
+        let mut labels = object.labels.clone();
+        labels.insert("alertname".to_string(), alert.id.to_string());
+        labels.insert("object_kind".to_string(), object.kind.to_string());
+        labels.insert("object_id".to_string(), object.id.clone());
+        labels.insert("severity".to_string(), severity.to_string());
+        if let Some(target) = &alert.target {
+            labels.insert("target".to_string(), target.clone());
+        }
+        DispatchedAlert {
+            status,
+            object_kind: object.kind,
+            object_id: object.id.clone(),
+            probe_id: alert.id.to_string(),
+            target: alert.target.clone(),
+            severity,
+            classifications: alert.classifications.clone(),
+            message: alert.message.clone(),
+            starts_at: alert.in_alert_since.unwrap_or(now),
+            ends_at: (status == AlertStatus::Resolved).then_some(now),
+            labels,
+            transition,
+        }
+    };
+
+    let firing = object
+        .report
+        .alerts
+        .iter()
+        .map(|alert| to_dispatched(alert, AlertStatus::Firing, !was_raised(previous, alert)));
+    let resolved = previous
+        .into_iter()
+        .flat_map(|previous| previous.alerts.iter())
+        .filter(|alert| !was_raised(Some(&object.report), *alert))
+        .map(|alert| to_dispatched(alert, AlertStatus::Resolved, true));
+    firing.chain(resolved).collect()
+}
+
+fn route_matches(route: &AlertRouteConfig, alert: &DispatchedAlert) -> bool {
+    (route.object_kinds.is_empty() || route.object_kinds.contains(&alert.object_kind))
+        && (route.classifications.is_empty()
+            || route
+                .classifications
+                .iter()
+                .any(|c| alert.classifications.contains(c)))
+        && alert.severity >= route.min_severity
+}
+
+/// Routes alerts to receivers and groups them into notifications. An alert
+/// is sent at most once to each receiver, even if several routes match.
+pub(crate) fn group_alerts(
+    config: &AlertDispatchConfig,
+    alerts: Vec<DispatchedAlert>,
+    resending_receivers: &BTreeSet<String>,
+) -> Vec<AlertNotification> {
+    let mut groups: BTreeMap<(String, BTreeMap<String, String>), Vec<DispatchedAlert>> =
+        BTreeMap::new();
+    for alert in alerts {
+        let receivers: BTreeSet<&String> = config
+            .routes
+            .iter()
+            .filter(|route| route_matches(route, &alert))
+            .map(|route| &route.receiver)
+            .filter(|receiver| alert.transition || resending_receivers.contains(*receiver))
+            .collect();
+        let group_labels: BTreeMap<String, String> = config
+            .group_by
+            .iter()
+            .map(|label| {
+                (
+                    label.clone(),
+                    alert.labels.get(label).cloned().unwrap_or_default(),
+                )
+            })
+            .collect();
+        for receiver in receivers {
+            groups
+                .entry((receiver.clone(), group_labels.clone()))
+                .or_default()
+                .push(alert.clone());
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|((receiver, group_labels), alerts)| AlertNotification {
+            receiver,
+            status: alerts
+                .iter()
+                .map(|alert| alert.status)
+                .min()
+                .unwrap_or(AlertStatus::Resolved),
+            group_labels,
+            alerts,
+        })
+        .collect()
+}
+
+/// The `AlertDispatcher` periodically sends alert transitions to the
+/// configured receivers. Only one carbide-api replica dispatches at a time.
+pub struct AlertDispatcher {
+    database_connection: PgPool,
+    config: AlertDispatchConfig,
+    host_health: HostHealthConfig,
+    receivers: BTreeMap<String, Arc<dyn AlertReceiver>>,
+    metrics: AlertDispatchMetrics,
+    work_lock_manager_handle: WorkLockManagerHandle,
+}
+
+impl AlertDispatcher {
+    const ITERATION_WORK_KEY: &'static str = "AlertDispatcher::run_single_iteration";
+
+    pub fn new(
+        database_connection: PgPool,
+        config: AlertDispatchConfig,
+        host_health: HostHealthConfig,
+        receivers: BTreeMap<String, Arc<dyn AlertReceiver>>,
+        meter: opentelemetry::metrics::Meter,
+        work_lock_manager_handle: WorkLockManagerHandle,
+    ) -> Self {
+        AlertDispatcher {
+            database_connection,
+            config,
+            host_health,
+            receivers,
+            metrics: AlertDispatchMetrics::new(&meter),
+            work_lock_manager_handle,
+        }
+    }
+
+    /// Start the AlertDispatcher. It stops once `cancel_token` is cancelled.
+    pub fn start(
+        self,
+        join_set: &mut JoinSet<()>,
+        cancel_token: CancellationToken,
+    ) -> io::Result<()> {
+        if !self.config.enabled {
+            return Ok(());
+        }
+        if self.config.routes.is_empty() {
+            tracing::info!("No alert dispatch routes configured. Alert dispatch disabled");
+            return Ok(());
+        }
+        join_set
+            .build_task()
+            .name("alert_dispatcher")
+            .spawn(async move { self.run(cancel_token).await })?;
+        Ok(())
+    }
+
+    async fn run(&self, cancel_token: CancellationToken) {
+        let timer = PeriodicTimer::new(self.config.run_interval);
+        loop {
+            let tick = timer.tick();
+            if let Err(e) = self.run_single_iteration().await {
+                tracing::warn!("AlertDispatcher error: {}", e);
+            }
+
+            tokio::select! {
+                _ = tick.sleep() => {},
+                _ = cancel_token.cancelled() => {
+                    tracing::info!("Alert dispatcher stop was requested");
+                    return;
+                }
+            }
+        }
+    }
+
+    pub async fn run_single_iteration(&self) -> CarbideResult<()> {
+        let _lock = match self
+            .work_lock_manager_handle
+            .try_acquire_lock(Self::ITERATION_WORK_KEY.into())
+            .await
+        {
+            Ok(lock) => lock,
+            Err(e) => {
+                tracing::warn!(
+                    "AlertDispatcher failed to acquire work lock: Another instance of carbide running? {e}"
+                );
+                return Ok(());
+            }
+        };
+
+        let now = Utc::now();
+        let objects = self.load_object_health().await?;
+        let mut previous: HashMap<(AlertObjectKind, String), HealthReport> =
+            db::health_alert_dispatch::find_all(&self.database_connection)
+                .await?
+                .into_iter()
+                .map(|dispatched| {
+                    (
+                        (dispatched.object_kind, dispatched.object_id),
+                        dispatched.report,
+                    )
+                })
+                .collect();
+
+        let mut alerts = Vec::new();
+        // The reports to store once the transitions have been delivered.
+        // `None` removes the stored report of an object without alerts.
+        let mut updates = Vec::new();
+        for mut object in objects {
+            let previous_report = previous.remove(&(object.kind, object.id.clone()));
+            // Alerts keep the start time they were first dispatched with. New
+            // alerts use the start time of the aggregate report, if it has one.
+            let mut in_alert_since = object.report.clone();
+            if let Some(previous_report) = &previous_report {
+                in_alert_since.merge(previous_report);
+            }
+            object.report.update_in_alert_since(Some(&in_alert_since));
+            alerts.extend(derive_alerts(
+                &object,
+                previous_report.as_ref(),
+                &self.config.critical_classifications,
+                now,
+            ));
+            if previous_report.as_ref() != Some(&object.report)
+                && (previous_report.is_some() || !object.report.alerts.is_empty())
+            {
+                let report = (!object.report.alerts.is_empty()).then_some(object.report);
+                updates.push((object.kind, object.id, report));
+            }
+        }
+        // Objects which no longer exist resolve all of their alerts
+        for ((kind, id), report) in previous {
+            let object =
+                ObjectHealth::new(kind, id, None, HealthReport::empty(report.source.clone()));
+            alerts.extend(derive_alerts(
+                &object,
+                Some(&report),
+                &self.config.critical_classifications,
+                now,
+            ));
+            updates.push((object.kind, object.id, None));
+        }
+
+        let resending_receivers: BTreeSet<String> = self
+            .receivers
+            .iter()
+            .filter(|(_, receiver)| receiver.resends_firing())
+            .map(|(name, _)| name.clone())
+            .collect();
+        let notifications = group_alerts(&self.config, alerts, &resending_receivers);
+        let results =
+            futures::future::join_all(notifications.iter().map(|n| self.deliver(n))).await;
+
+        let mut undelivered = HashSet::new();
+        for (notification, result) in notifications.iter().zip(results) {
+            match result {
+                Ok(()) => {
+                    for status in [AlertStatus::Firing, AlertStatus::Resolved] {
+                        let count = notification
+                            .alerts
+                            .iter()
+                            .filter(|alert| alert.status == status)
+                            .count();
+                        self.metrics
+                            .record_sent(&notification.receiver, status, count as u64);
+                    }
+                }
+                Err(e) => {
+                    self.metrics.record_failure(&notification.receiver);
+                    tracing::warn!(
+                        receiver = %notification.receiver,
+                        summary = %notification.summary(),
+                        "Failed to deliver alert notification: {e}"
+                    );
+                    undelivered.extend(
+                        notification
+                            .alerts
+                            .iter()
+                            .filter(|alert| alert.transition)
+                            .map(|alert| (alert.object_kind, alert.object_id.clone())),
+                    );
+                }
+            }
+        }
+
+        let mut txn = db::Transaction::begin(&self.database_connection).await?;
+        for (kind, id, report) in updates {
+            if undelivered.contains(&(kind, id.clone())) {
+                continue;
+            }
+            match report {
+                Some(report) => {
+                    db::health_alert_dispatch::upsert(&mut txn, kind, &id, &report).await?
+                }
+                None => db::health_alert_dispatch::delete(&mut txn, kind, &id).await?,
+            }
+        }
+        txn.commit().await?;
+
+        Ok(())
+    }
+
+    /// Sends a notification, retrying with exponential backoff
+    async fn deliver(&self, notification: &AlertNotification) -> Result<(), AlertDeliveryError> {
+        let retry = &self.config.retry;
+        let receiver = &self.receivers[&notification.receiver];
+        let mut backoff = retry.initial_backoff;
+        let mut attempt = 1;
+        loop {
+            let result = tokio::time::timeout(retry.request_timeout, receiver.send(notification))
+                .await
+                .unwrap_or(Err(AlertDeliveryError::Timeout));
+            match result {
+                Ok(()) => return Ok(()),
+                Err(e) if attempt >= retry.max_attempts => return Err(e),
+                Err(e) => {
+                    tracing::debug!(
+                        receiver = %notification.receiver,
+                        attempt,
+                        "Retrying alert notification delivery: {e}"
+                    );
+                    self.metrics.record_retry(&notification.receiver);
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(retry.max_backoff);
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    /// Loads the aggregate health of all objects, with health silences applied
+    async fn load_object_health(&self) -> CarbideResult<Vec<ObjectHealth>> {
+        let mut txn = db::Transaction::begin(&self.database_connection).await?;
+        let hosts = db::managed_host::load_all(
+            &mut txn,
+            LoadSnapshotOptions {
+                include_history: false,
+                include_instance_data: false,
+                host_health_config: self.host_health,
+            },
+        )
+        .await?;
+        let racks =
+            db::rack::find_by(&mut txn, ObjectColumnFilter::All::<db::rack::IdColumn>).await?;
+        let switches =
+            db::switch::find_by(&mut txn, ObjectColumnFilter::All::<db::switch::IdColumn>).await?;
+        let power_shelves = db::power_shelf::find_by(
+            &mut txn,
+            ObjectColumnFilter::All::<db::power_shelf::IdColumn>,
+        )
+        .await?;
+        let silences = db::health_silence::find_active(&mut txn).await?;
+        txn.commit().await?;
+
+        let now = Utc::now();
+        let apply_silences = |report: &mut HealthReport,
+                              kind: HealthSilenceObjectKind,
+                              id: &str,
+                              labels: &HashMap<String, String>,
+                              rack_id: Option<String>| {
+            let subject = HealthSilenceSubject {
+                kind,
+                ids: vec![id.to_string()],
+                labels,
+                sku: None,
+                rack_id,
+            };
+            apply_health_silences(report, &silences, &subject, now);
+        };
+
+        let mut objects = Vec::new();
+        // Silences are already applied to the aggregate health of hosts
+        for host in hosts {
+            let host_snapshot = &host.host_snapshot;
+            objects.push(ObjectHealth::new(
+                AlertObjectKind::Machine,
+                host_snapshot.id.to_string(),
+                host_snapshot.rack_id.as_ref().map(|id| id.to_string()),
+                host.aggregate_health,
+            ));
+        }
+        for rack in racks.iter().filter(|rack| rack.deleted.is_none()) {
+            let id = rack.id.to_string();
+            let mut report = rack.aggregate_health();
+            apply_silences(
+                &mut report,
+                HealthSilenceObjectKind::Rack,
+                &id,
+                &rack.metadata.labels,
+                Some(id.clone()),
+            );
+            objects.push(ObjectHealth::new(
+                AlertObjectKind::Rack,
+                id.clone(),
+                Some(id),
+                report,
+            ));
+        }
+        for switch in switches.iter().filter(|s| !s.is_marked_as_deleted()) {
+            let id = switch.id.to_string();
+            let rack_id = switch.rack_id.as_ref().map(|id| id.to_string());
+            let mut report = switch.aggregate_health();
+            apply_silences(
+                &mut report,
+                HealthSilenceObjectKind::Switch,
+                &id,
+                &switch.metadata.labels,
+                rack_id.clone(),
+            );
+            objects.push(ObjectHealth::new(
+                AlertObjectKind::Switch,
+                id,
+                rack_id,
+                report,
+            ));
+        }
+        for power_shelf in power_shelves.iter().filter(|p| !p.is_marked_as_deleted()) {
+            let id = power_shelf.id.to_string();
+            let rack_id = power_shelf.rack_id.as_ref().map(|id| id.to_string());
+            let mut report = power_shelf.aggregate_health();
+            apply_silences(
+                &mut report,
+                HealthSilenceObjectKind::PowerShelf,
+                &id,
+                &power_shelf.metadata.labels,
+                rack_id.clone(),
+            );
+            objects.push(ObjectHealth::new(
+                AlertObjectKind::PowerShelf,
+                id,
+                rack_id,
+                report,
+            ));
+        }
+
+        Ok(objects)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alert(id: &str, classification: HealthAlertClassification) -> HealthProbeAlert {
+        HealthProbeAlert {
+            id: id.parse().unwrap(),
+            target: None,
+            in_alert_since: Some("2026-05-01T00:00:00Z".parse().unwrap()),
+            message: format!("{id} failed"),
+            tenant_message: None,
+            classifications: vec![classification],
+        }
+    }
+
+    fn report(alerts: Vec<HealthProbeAlert>) -> HealthReport {
+        HealthReport {
+            alerts,
+            ..HealthReport::empty("test".to_string())
+        }
+    }
+
+    fn object(kind: AlertObjectKind, alerts: Vec<HealthProbeAlert>) -> ObjectHealth {
+        ObjectHealth::new(
+            kind,
+            "object-1".to_string(),
+            Some("rack-1".to_string()),
+            report(alerts),
+        )
+    }
+
+    fn critical() -> Vec<HealthAlertClassification> {
+        AlertDispatchConfig::default().critical_classifications
+    }
+
+    #[test]
+    fn derives_start_and_clear_transitions() {
+        let now = Utc::now();
+        let previous = report(vec![
+            alert("Ongoing", HealthAlertClassification::hardware()),
+            alert("Cleared", HealthAlertClassification::hardware()),
+        ]);
+        let current = object(
+            AlertObjectKind::Machine,
+            vec![
+                alert("Ongoing", HealthAlertClassification::hardware()),
+                alert("Started", HealthAlertClassification::sensor_critical()),
+                alert(
+                    "Silenced",
+                    HealthAlertClassification::suppress_external_alerting(),
+                ),
+            ],
+        );
+
+        let alerts = derive_alerts(&current, Some(&previous), &critical(), now);
+        let summary: Vec<_> = alerts
+            .iter()
+            .map(|a| (a.probe_id.as_str(), a.status, a.transition, a.severity))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "Ongoing",
+                    AlertStatus::Firing,
+                    false,
+                    AlertSeverity::Warning
+                ),
+                (
+                    "Started",
+                    AlertStatus::Firing,
+                    true,
+                    AlertSeverity::Critical
+                ),
+                (
+                    "Cleared",
+                    AlertStatus::Resolved,
+                    true,
+                    AlertSeverity::Warning
+                ),
+            ]
+        );
+        assert_eq!(alerts[2].ends_at, Some(now));
+        assert_eq!(alerts[1].labels["alertname"], "Started");
+        assert_eq!(alerts[1].labels["object_kind"], "machine");
+        assert_eq!(alerts[1].labels["rack_id"], "rack-1");
+        assert_eq!(alerts[1].labels["severity"], "critical");
+    }
+
+    #[test]
+    fn routes_and_groups_alerts() {
+        let config = AlertDispatchConfig {
+            group_by: vec!["object_kind".to_string()],
+            routes: vec![
+                AlertRouteConfig {
+                    receiver: "oncall".to_string(),
+                    classifications: vec![],
+                    object_kinds: vec![],
+                    min_severity: AlertSeverity::Critical,
+                },
+                AlertRouteConfig {
+                    receiver: "hardware".to_string(),
+                    classifications: vec![HealthAlertClassification::hardware()],
+                    object_kinds: vec![AlertObjectKind::Rack, AlertObjectKind::Switch],
+                    min_severity: AlertSeverity::Warning,
+                },
+                // Duplicates the first route, which must not duplicate alerts
+                AlertRouteConfig {
+                    receiver: "oncall".to_string(),
+                    classifications: vec![HealthAlertClassification::sensor_critical()],
+                    object_kinds: vec![],
+                    min_severity: AlertSeverity::Warning,
+                },
+            ],
+            ..Default::default()
+        };
+        let now = Utc::now();
+        let mut alerts = Vec::new();
+        for kind in [AlertObjectKind::Rack, AlertObjectKind::Machine] {
+            alerts.extend(derive_alerts(
+                &object(
+                    kind,
+                    vec![
+                        alert("Leak", HealthAlertClassification::sensor_critical()),
+                        alert("Fan", HealthAlertClassification::hardware()),
+                    ],
+                ),
+                None,
+                &critical(),
+                now,
+            ));
+        }
+
+        let notifications = group_alerts(&config, alerts, &BTreeSet::new());
+        let summary: Vec<_> = notifications
+            .iter()
+            .map(|n| {
+                (
+                    n.receiver.as_str(),
+                    n.group_labels["object_kind"].as_str(),
+                    n.alerts
+                        .iter()
+                        .map(|a| a.probe_id.as_str())
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("hardware", "rack", vec!["Fan"]),
+                ("oncall", "machine", vec!["Leak"]),
+                ("oncall", "rack", vec!["Leak"]),
+            ]
+        );
+        assert_eq!(notifications[0].summary(), "[FIRING:1] object_kind=rack");
+    }
+
+    #[test]
+    fn only_resends_firing_alerts_to_resending_receivers() {
+        let config = AlertDispatchConfig {
+            routes: ["alertmanager", "webhook"]
+                .into_iter()
+                .map(|receiver| AlertRouteConfig {
+                    receiver: receiver.to_string(),
+                    classifications: vec![],
+                    object_kinds: vec![],
+                    min_severity: AlertSeverity::Warning,
+                })
+                .collect(),
+            ..Default::default()
+        };
+        let fan = alert("Fan", HealthAlertClassification::hardware());
+        let alerts = derive_alerts(
+            &object(AlertObjectKind::Switch, vec![fan.clone()]),
+            Some(&report(vec![fan])),
+            &critical(),
+            Utc::now(),
+        );
+
+        let notifications = group_alerts(
+            &config,
+            alerts,
+            &BTreeSet::from(["alertmanager".to_string()]),
+        );
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].receiver, "alertmanager");
+        assert!(notifications[0].group_labels.is_empty());
+    }
+}
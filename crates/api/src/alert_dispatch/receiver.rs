@@ -0,0 +1,526 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//! Receivers of alert notifications.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use mqttea::MqtteaClientError;
+use reqwest::header::{CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue};
+use serde_json::{Value, json};
+
+use super::{AlertNotification, AlertStatus};
+use crate::cfg::file::{AlertDispatchConfig, AlertReceiverConfig};
+use crate::mqtt_state_change_hook::hook::MqttPublisher;
+
+#[derive(Debug, thiserror::Error)]
+pub enum AlertDeliveryError {
+    #[error("HTTP request failed: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("Receiver responded with status {status}: {body}")]
+    Status {
+        status: reqwest::StatusCode,
+        body: String,
+    },
+    #[error("MQTT publish failed: {0}")]
+    Mqtt(#[from] MqtteaClientError),
+    #[error("Failed to serialize notification: {0}")]
+    Serialization(#[from] serde_json::Error),
+    #[error("Delivery timed out")]
+    Timeout,
+}
+
+/// A destination for alert notifications
+#[async_trait::async_trait]
+pub trait AlertReceiver: Send + Sync + 'static {
+    /// Whether alerts which are still firing are sent on every run, rather
+    /// than only when they start.
+    fn resends_firing(&self) -> bool {
+        false
+    }
+
+    async fn send(&self, notification: &AlertNotification) -> Result<(), AlertDeliveryError>;
+}
+
+/// Posts alerts to the v2 API of an Alertmanager
+pub struct AlertmanagerReceiver {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl AlertmanagerReceiver {
+    pub fn new(client: reqwest::Client, base_url: &str) -> Self {
+        Self {
+            client,
+            url: format!("{}/api/v2/alerts", base_url.trim_end_matches('/')),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl AlertReceiver for AlertmanagerReceiver {
+    fn resends_firing(&self) -> bool {
+        true
+    }
+
+    async fn send(&self, notification: &AlertNotification) -> Result<(), AlertDeliveryError> {
+        let body = serde_json::to_vec(&alertmanager_alerts(notification))?;
+        post_json(&self.client, &self.url, HeaderMap::new(), body).await
+    }
+}
+
+/// Converts a notification into the `PostableAlerts` of the Alertmanager v2 API.
+/// Firing alerts carry no `endsAt`, so Alertmanager resolves them on its own
+/// if they are not re-sent within its `resolve_timeout`.
+pub(crate) fn alertmanager_alerts(notification: &AlertNotification) -> Value {
+    Value::Array(
+        notification
+            .alerts
+            .iter()
+            .map(|alert| {
+                let mut postable = json!({
+                    "labels": alert.labels,
+                    "annotations": {
+                        "summary": alert.message,
+                        "classifications": alert
+                            .classifications
+                            .iter()
+                            .map(|c| c.as_str())
+                            .collect::<Vec<_>>()
+                            .join(","),
+                    },
+                    "startsAt": alert.starts_at.to_rfc3339(),
+                });
+                if let Some(ends_at) = alert.ends_at {
+                    postable["endsAt"] = Value::String(ends_at.to_rfc3339());
+                }
+                postable
+            })
+            .collect(),
+    )
+}
+
+/// Posts a JSON document, optionally rendered from a template, per notification
+pub struct WebhookReceiver {
+    client: reqwest::Client,
+    url: String,
+    body_template: Option<Value>,
+    headers: HeaderMap,
+}
+
+impl WebhookReceiver {
+    pub fn new(
+        client: reqwest::Client,
+        url: &str,
+        body_template: Option<&str>,
+        headers: &BTreeMap<String, String>,
+    ) -> eyre::Result<Self> {
+        let body_template = body_template
+            .map(serde_json::from_str::<Value>)
+            .transpose()
+            .map_err(|e| eyre::eyre!("Webhook body_template is not valid JSON: {e}"))?;
+        let headers = headers
+            .iter()
+            .map(|(name, value)| -> eyre::Result<(HeaderName, HeaderValue)> {
+                Ok((
+                    HeaderName::try_from(name.as_str())?,
+                    HeaderValue::try_from(value.as_str())?,
+                ))
+            })
+            .collect::<eyre::Result<HeaderMap>>()
+            .map_err(|e| eyre::eyre!("Invalid webhook header: {e}"))?;
+        Ok(Self {
+            client,
+            url: url.to_string(),
+            body_template,
+            headers,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl AlertReceiver for WebhookReceiver {
+    async fn send(&self, notification: &AlertNotification) -> Result<(), AlertDeliveryError> {
+        let body = match &self.body_template {
+            Some(template) => render_template(template, &template_context(notification)?),
+            None => serde_json::to_value(notification)?,
+        };
+        post_json(
+            &self.client,
+            &self.url,
+            self.headers.clone(),
+            serde_json::to_vec(&body)?,
+        )
+        .await
+    }
+}
+
+/// The values available to webhook templates
+pub(crate) fn template_context(
+    notification: &AlertNotification,
+) -> Result<Value, serde_json::Error> {
+    let firing_count = notification
+        .alerts
+        .iter()
+        .filter(|alert| alert.status == AlertStatus::Firing)
+        .count();
+    Ok(json!({
+        "receiver": notification.receiver,
+        "status": notification.status,
+        "summary": notification.summary(),
+        "group_labels": notification.group_labels,
+        "alerts": serde_json::to_value(&notification.alerts)?,
+        "firing_count": firing_count,
+        "resolved_count": notification.alerts.len() - firing_count,
+    }))
+}
+
+/// Renders a JSON template by replacing `{{name}}` placeholders in its strings.
+/// A string which consists of a single placeholder is replaced by the value
+/// itself, so that e.g. `"{{alerts}}"` becomes an array. Placeholders within
+/// longer strings are replaced by the value's text. Nested values can be
+/// referred to with dots, e.g. `{{group_labels.alertname}}`. Unknown
+/// placeholders render as `null` or as empty text.
+pub(crate) fn render_template(template: &Value, context: &Value) -> Value {
+    match template {
+        Value::String(text) => render_string(text, context),
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| render_template(item, context))
+                .collect(),
+        ),
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(key, value)| (key.clone(), render_template(value, context)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+fn render_string(text: &str, context: &Value) -> Value {
+    if let Some(name) = text
+        .strip_prefix("{{")
+        .and_then(|rest| rest.strip_suffix("}}"))
+        && !name.contains("{{")
+        && !name.contains("}}")
+    {
+        return lookup(context, name.trim()).cloned().unwrap_or(Value::Null);
+    }
+
+    let mut rendered = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        rendered.push_str(&rest[..start]);
+        match lookup(context, rest[start + 2..start + 2 + len].trim()) {
+            Some(Value::String(value)) => rendered.push_str(value),
+            Some(Value::Null) | None => {}
+            Some(value) => rendered.push_str(&value.to_string()),
+        }
+        rest = &rest[start + 2 + len + 2..];
+    }
+    rendered.push_str(rest);
+    Value::String(rendered)
+}
+
+fn lookup<'a>(context: &'a Value, name: &str) -> Option<&'a Value> {
+    name.split('.')
+        .try_fold(context, |value, key| value.get(key))
+}
+
+/// Publishes every alert of a notification to `{topic_prefix}/{object_kind}/{object_id}`
+pub struct MqttReceiver<P: MqttPublisher> {
+    publisher: P,
+    topic_prefix: String,
+}
+
+impl<P: MqttPublisher> MqttReceiver<P> {
+    pub fn new(publisher: P, topic_prefix: &str) -> Self {
+        Self {
+            publisher,
+            topic_prefix: topic_prefix.trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<P: MqttPublisher> AlertReceiver for MqttReceiver<P> {
+    async fn send(&self, notification: &AlertNotification) -> Result<(), AlertDeliveryError> {
+        for alert in notification.alerts.iter() {
+            let topic = format!(
+                "{}/{}/{}",
+                self.topic_prefix, alert.object_kind, alert.object_id
+            );
+            self.publisher
+                .publish(&topic, serde_json::to_vec(alert)?)
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+async fn post_json(
+    client: &reqwest::Client,
+    url: &str,
+    headers: HeaderMap,
+    body: Vec<u8>,
+) -> Result<(), AlertDeliveryError> {
+    let response = client
+        .post(url)
+        .headers(headers)
+        .header(CONTENT_TYPE, "application/json")
+        .body(body)
+        .send()
+        .await?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(AlertDeliveryError::Status { status, body });
+    }
+    Ok(())
+}
+
+/// Creates the configured receivers, connecting to MQTT brokers where needed,
+/// and checks that all routes refer to one of them.
+pub async fn build_receivers(
+    config: &AlertDispatchConfig,
+    credential_reader: Arc<dyn forge_secrets::credentials::CredentialReader>,
+) -> eyre::Result<BTreeMap<String, Arc<dyn AlertReceiver>>> {
+    if let Some(route) = config
+        .routes
+        .iter()
+        .find(|route| !config.receivers.contains_key(&route.receiver))
+    {
+        return Err(eyre::eyre!(
+            "Alert dispatch route refers to unknown receiver {}",
+            route.receiver
+        ));
+    }
+
+    let http_client = reqwest::Client::builder()
+        .timeout(config.retry.request_timeout)
+        .build()
+        .map_err(|e| eyre::eyre!("Failed to build alert dispatch HTTP client: {e}"))?;
+
+    let mut receivers: BTreeMap<String, Arc<dyn AlertReceiver>> = BTreeMap::new();
+    for (name, receiver_config) in config.receivers.iter() {
+        let receiver: Arc<dyn AlertReceiver> = match receiver_config {
+            AlertReceiverConfig::Alertmanager { url } => {
+                Arc::new(AlertmanagerReceiver::new(http_client.clone(), url))
+            }
+            AlertReceiverConfig::Webhook {
+                url,
+                body_template,
+                headers,
+            } => Arc::new(
+                WebhookReceiver::new(http_client.clone(), url, body_template.as_deref(), headers)
+                    .map_err(|e| e.wrap_err(format!("Alert receiver {name}")))?,
+            ),
+            AlertReceiverConfig::Mqtt {
+                mqtt_endpoint,
+                mqtt_broker_port,
+                topic_prefix,
+                auth,
+            } => {
+                let options = {
+                    let defaults =
+                        mqttea::client::ClientOptions::default().with_qos(mqttea::QoS::AtLeastOnce);
+                    if let Some(provider) = crate::auth::mqtt_auth::build_credentials_provider(
+                        auth,
+                        forge_secrets::credentials::CredentialKey::MqttAuth {
+                            credential_type:
+                                forge_secrets::credentials::MqttCredentialType::AlertDispatch,
+                        },
+                        credential_reader.clone(),
+                    )
+                    .await?
+                    {
+                        defaults.with_credentials_provider(provider)
+                    } else {
+                        defaults
+                    }
+                };
+
+                let client = mqttea::MqtteaClient::new(
+                    mqtt_endpoint,
+                    *mqtt_broker_port,
+                    &format!("carbide-alert-dispatch-{name}"),
+                    Some(options),
+                )
+                .await
+                .map_err(|e| {
+                    eyre::eyre!("Failed to create alert receiver {name} MQTT client: {e}")
+                })?;
+                client.connect().await.map_err(|e| {
+                    eyre::eyre!("Failed to connect alert receiver {name} MQTT client: {e}")
+                })?;
+
+                Arc::new(MqttReceiver::new(client, topic_prefix))
+            }
+        };
+        receivers.insert(name.clone(), receiver);
+    }
+
+    Ok(receivers)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use health_report::HealthAlertClassification;
+    use model::alert_dispatch::{AlertObjectKind, AlertSeverity};
+
+    use super::*;
+    use crate::alert_dispatch::DispatchedAlert;
+
+    fn notification() -> AlertNotification {
+        let starts_at: chrono::DateTime<Utc> = "2026-05-01T00:00:00Z".parse().unwrap();
+        let alert = |probe_id: &str, status: AlertStatus| DispatchedAlert {
+            status,
+            object_kind: AlertObjectKind::Rack,
+            object_id: "rack-1".to_string(),
+            probe_id: probe_id.to_string(),
+            target: None,
+            severity: AlertSeverity::Critical,
+            classifications: vec![
+                HealthAlertClassification::prevent_allocations(),
+                HealthAlertClassification::sensor_critical(),
+            ],
+            message: format!("{probe_id} detected"),
+            starts_at,
+            ends_at: (status == AlertStatus::Resolved).then(Utc::now),
+            labels: BTreeMap::from([("alertname".to_string(), probe_id.to_string())]),
+            transition: true,
+        };
+        AlertNotification {
+            receiver: "chat".to_string(),
+            status: AlertStatus::Firing,
+            group_labels: BTreeMap::from([("object_kind".to_string(), "rack".to_string())]),
+            alerts: vec![
+                alert("Leak", AlertStatus::Firing),
+                alert("Fan", AlertStatus::Resolved),
+            ],
+        }
+    }
+
+    #[test]
+    fn renders_templates() {
+        let template = json!({
+            "text": "{{summary}} via {{ receiver }}: {{firing_count}} firing, {{unknown}}done",
+            "kind": "{{group_labels.object_kind}}",
+            "alerts": "{{alerts}}",
+            "missing": "{{unknown}}",
+            "nested": [{"status": "{{status}}"}, 1, true],
+        });
+        let notification = notification();
+        let rendered = render_template(&template, &template_context(&notification).unwrap());
+
+        assert_eq!(
+            rendered["text"],
+            "[FIRING:2] object_kind=rack via chat: 1 firing, done"
+        );
+        assert_eq!(rendered["kind"], "rack");
+        assert_eq!(rendered["alerts"].as_array().unwrap().len(), 2);
+        assert_eq!(rendered["alerts"][1]["probe_id"], "Fan");
+        assert_eq!(rendered["alerts"][1]["status"], "resolved");
+        assert_eq!(rendered["missing"], Value::Null);
+        assert_eq!(rendered["nested"], json!([{"status": "firing"}, 1, true]));
+    }
+
+    #[test]
+    fn rejects_invalid_templates() {
+        let client = reqwest::Client::new();
+        assert!(
+            WebhookReceiver::new(
+                client.clone(),
+                "http://hook",
+                Some("{\"text\": "),
+                &BTreeMap::new()
+            )
+            .is_err()
+        );
+        assert!(
+            WebhookReceiver::new(
+                client,
+                "http://hook",
+                None,
+                &BTreeMap::from([("bad header".to_string(), "value".to_string())])
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn converts_to_alertmanager_alerts() {
+        let notification = notification();
+        let alerts = alertmanager_alerts(&notification);
+
+        assert_eq!(
+            alerts[0],
+            json!({
+                "labels": {"alertname": "Leak"},
+                "annotations": {
+                    "summary": "Leak detected",
+                    "classifications": "PreventAllocations,SensorCritical",
+                },
+                "startsAt": "2026-05-01T00:00:00+00:00",
+            })
+        );
+        assert_eq!(
+            alerts[1]["endsAt"],
+            notification.alerts[1].ends_at.unwrap().to_rfc3339()
+        );
+    }
+
+    #[tokio::test]
+    async fn publishes_alerts_per_object() {
+        #[derive(Default)]
+        struct Publisher(std::sync::Mutex<Vec<(String, Value)>>);
+
+        #[async_trait::async_trait]
+        impl MqttPublisher for Publisher {
+            async fn publish(
+                &self,
+                topic: &str,
+                payload: Vec<u8>,
+            ) -> Result<(), MqtteaClientError> {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push((topic.to_string(), serde_json::from_slice(&payload).unwrap()));
+                Ok(())
+            }
+        }
+
+        let publisher = Arc::new(Publisher::default());
+        MqttReceiver::new(publisher.clone(), "nico/v1/alert/")
+            .send(&notification())
+            .await
+            .unwrap();
+
+        let published = publisher.0.lock().unwrap();
+        assert_eq!(published.len(), 2);
+        assert_eq!(published[0].0, "nico/v1/alert/rack/rack-1");
+        assert_eq!(published[0].1["probe_id"], "Leak");
+        assert_eq!(published[1].1["status"], "resolved");
+    }
+}
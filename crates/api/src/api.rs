@@ -499,6 +499,13 @@ impl Forge for Api {
         crate::handlers::dpu::record_dpu_network_status(self, request).await
     }
 
+    async fn render_dpu_network_config(
+        &self,
+        request: Request<rpc::RenderDpuNetworkConfigRequest>,
+    ) -> Result<Response<rpc::RenderDpuNetworkConfigResponse>, Status> {
+        crate::handlers::dpu::render_dpu_network_config(self, request).await
+    }
+
     async fn list_machine_health_reports(
         &self,
         request: Request<MachineId>,
@@ -608,6 +615,27 @@ impl Forge for Api {
         crate::handlers::power_shelf::remove_power_shelf_health_report(self, request).await
     }
 
+    async fn create_health_silence(
+        &self,
+        request: Request<rpc::CreateHealthSilenceRequest>,
+    ) -> Result<Response<rpc::HealthSilence>, Status> {
+        crate::handlers::health_silence::create_health_silence(self, request).await
+    }
+
+    async fn list_health_silences(
+        &self,
+        request: Request<rpc::ListHealthSilencesRequest>,
+    ) -> Result<Response<rpc::HealthSilenceList>, Status> {
+        crate::handlers::health_silence::list_health_silences(self, request).await
+    }
+
+    async fn expire_health_silence(
+        &self,
+        request: Request<rpc::ExpireHealthSilenceRequest>,
+    ) -> Result<Response<rpc::HealthSilence>, Status> {
+        crate::handlers::health_silence::expire_health_silence(self, request).await
+    }
+
     async fn get_all_domain_metadata(
         &self,
         request: Request<DomainMetadataRequest>,
@@ -759,6 +787,13 @@ impl Forge for Api {
         crate::handlers::machine_scout::cleanup_machine_completed(self, request).await
     }
 
+    async fn get_media_sanitization_report(
+        &self,
+        request: Request<rpc::MediaSanitizationReportRequest>,
+    ) -> Result<Response<rpc::MediaSanitizationReport>, Status> {
+        crate::handlers::media_sanitization::get_media_sanitization_report(self, request).await
+    }
+
     // Invoked by forge-scout whenever a certain Machine can not be properly acted on
     async fn report_forge_scout_error(
         &self,
@@ -838,6 +873,13 @@ impl Forge for Api {
         crate::handlers::machine::find_machine_health_histories(self, request).await
     }
 
+    async fn get_managed_host_at(
+        &self,
+        request: Request<rpc::GetManagedHostAtRequest>,
+    ) -> std::result::Result<Response<rpc::GetManagedHostAtResponse>, Status> {
+        crate::handlers::machine::get_managed_host_at(self, request).await
+    }
+
     async fn assign_static_address(
         &self,
         request: Request<rpc::AssignStaticAddressRequest>,
@@ -2475,6 +2517,56 @@ impl Forge for Api {
     ) -> Result<Response<rpc::GetNetworkSecurityGroupAttachmentsResponse>, Status> {
         crate::handlers::network_security_group::get_attachments(self, request).await
     }
+
+    async fn analyze_connectivity(
+        &self,
+        request: Request<rpc::AnalyzeConnectivityRequest>,
+    ) -> Result<Response<rpc::AnalyzeConnectivityResponse>, Status> {
+        crate::handlers::connectivity::analyze(self, request).await
+    }
+
+    async fn get_vpc_reachability_matrix(
+        &self,
+        request: Request<rpc::GetVpcReachabilityMatrixRequest>,
+    ) -> Result<Response<rpc::GetVpcReachabilityMatrixResponse>, Status> {
+        crate::handlers::connectivity::reachability_matrix(self, request).await
+    }
+
+    async fn create_prefix_list(
+        &self,
+        request: Request<rpc::CreatePrefixListRequest>,
+    ) -> Result<Response<rpc::CreatePrefixListResponse>, Status> {
+        crate::handlers::prefix_list::create(self, request).await
+    }
+
+    async fn find_prefix_list_ids(
+        &self,
+        request: Request<rpc::FindPrefixListIdsRequest>,
+    ) -> Result<Response<rpc::FindPrefixListIdsResponse>, Status> {
+        crate::handlers::prefix_list::find_ids(self, request).await
+    }
+
+    async fn find_prefix_lists_by_ids(
+        &self,
+        request: Request<rpc::FindPrefixListsByIdsRequest>,
+    ) -> Result<Response<rpc::FindPrefixListsByIdsResponse>, Status> {
+        crate::handlers::prefix_list::find_by_ids(self, request).await
+    }
+
+    async fn update_prefix_list(
+        &self,
+        request: Request<rpc::UpdatePrefixListRequest>,
+    ) -> Result<Response<rpc::UpdatePrefixListResponse>, Status> {
+        crate::handlers::prefix_list::update(self, request).await
+    }
+
+    async fn delete_prefix_list(
+        &self,
+        request: Request<rpc::DeletePrefixListRequest>,
+    ) -> Result<Response<rpc::DeletePrefixListResponse>, Status> {
+        crate::handlers::prefix_list::delete(self, request).await
+    }
+
     async fn create_compute_allocation(
         &self,
         request: tonic::Request<rpc::CreateComputeAllocationRequest>,
@@ -3010,6 +3102,20 @@ impl Forge for Api {
         crate::handlers::machine_identity::get_open_id_configuration(self, request).await
     }
 
+    async fn get_machine_identity_trust_bundle(
+        &self,
+        request: Request<rpc::MachineIdentityTrustBundleRequest>,
+    ) -> Result<Response<rpc::MachineIdentityTrustBundle>, Status> {
+        crate::handlers::machine_identity::get_machine_identity_trust_bundle(self, request).await
+    }
+
+    async fn sign_machine_x509_intermediate(
+        &self,
+        request: Request<rpc::MachineX509IntermediateRequest>,
+    ) -> Result<Response<rpc::MachineX509IntermediateResponse>, Status> {
+        crate::handlers::machine_identity::sign_machine_x509_intermediate(self, request).await
+    }
+
     async fn modify_dpf_state(
         &self,
         request: Request<rpc::ModifyDpfStateRequest>,
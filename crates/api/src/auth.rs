@@ -14,19 +14,24 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
-use std::path::Path;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use carbide_authn::middleware::{ExternalUserInfo, Principal};
+use opentelemetry::metrics::Meter;
 
 use crate::CarbideError;
 
 mod casbin_engine;
+mod decision_log;
 pub mod internal_rbac_rules;
 pub mod middleware;
 pub mod mqtt_auth;
+pub mod policy_source;
 mod test_certs;
 
+pub use casbin_engine::PolicyReloader;
+
 pub type AuthContext = carbide_authn::middleware::AuthContext<Authorization>;
 
 // An Authorization is sort of like a ticket that says we're allowed to do the
@@ -77,6 +82,87 @@ pub enum Predicate {
     // relative to the Forge service that contains it (i.e. without any slash
     // delimiters).
     ForgeCall(String),
+
+    // A call to a Forge-owned gRPC method acting on a specific resource. This is
+    // checked by the method's handler once it has loaded the resource.
+    ForgeCallOn(String, ResourceAttributes),
+}
+
+impl Predicate {
+    pub fn method(&self) -> &str {
+        match self {
+            Predicate::ForgeCall(method) | Predicate::ForgeCallOn(method, _) => method,
+        }
+    }
+
+    // The attributes that policy conditions are evaluated against.
+    pub fn resource(&self) -> ResourceAttributes {
+        match self {
+            Predicate::ForgeCall(method) => ResourceAttributes {
+                deferred: RESOURCE_AUTHORIZED_METHODS.contains(&method.as_str()),
+                ..Default::default()
+            },
+            Predicate::ForgeCallOn(_, resource) => resource.clone(),
+        }
+    }
+}
+
+/// Forge methods whose handlers authorize again via [`ResourceAuthorizer`] once they know which
+/// object the call acts on. Policy conditions on any other method are evaluated against empty
+/// attributes.
+pub const RESOURCE_AUTHORIZED_METHODS: &[&str] = &["ReleaseInstance", "UpdateMachineMetadata"];
+
+/// Attributes of the object a call acts on, which policy rules can put conditions on, e.g.
+/// `r.res.tenant_organization_id == "acme"` or `r.res.labels["team"] == "ml"`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, serde::Serialize)]
+pub struct ResourceAttributes {
+    /// The kind of object, e.g. `instance` or `machine`
+    pub kind: String,
+    pub id: String,
+    /// Empty for objects not owned by a tenant
+    pub tenant_organization_id: String,
+    pub labels: BTreeMap<String, String>,
+    pub site: String,
+    /// Set for the method level check of a call in [`RESOURCE_AUTHORIZED_METHODS`], where the
+    /// object is not known yet. Conditions pass, and are evaluated by the handler's check.
+    pub deferred: bool,
+}
+
+/// Authorizes a call against the object it acts on, from inside the call's handler.
+///
+/// Create it from the request before consuming it, then call [`ResourceAuthorizer::authorize`]
+/// once the object is loaded. Calls are always allowed if the API runs without a Casbin policy.
+pub struct ResourceAuthorizer {
+    authorizer: Option<Arc<CasbinAuthorizer>>,
+    principals: Vec<Principal>,
+    method: &'static str,
+}
+
+impl ResourceAuthorizer {
+    pub fn from_request<T>(request: &tonic::Request<T>, method: &'static str) -> Self {
+        let extensions = request.extensions();
+        Self {
+            authorizer: extensions.get::<Arc<CasbinAuthorizer>>().cloned(),
+            principals: extensions
+                .get::<AuthContext>()
+                .map(|auth_context| auth_context.principals.clone())
+                .unwrap_or_default(),
+            method,
+        }
+    }
+
+    pub fn authorize(&self, resource: ResourceAttributes) -> Result<(), tonic::Status> {
+        let Some(authorizer) = self.authorizer.as_ref() else {
+            return Ok(());
+        };
+        authorizer
+            .authorize(
+                &self.principals.as_slice(),
+                Predicate::ForgeCallOn(self.method.to_string(), resource),
+            )
+            .map(|_| ())
+            .map_err(tonic::Status::from)
+    }
 }
 
 pub trait PrincipalExtractor {
@@ -143,21 +229,28 @@ impl CasbinAuthorizer {
         self.policy_engine = permissive_engine;
     }
 
+    /// Builds an authorizer enforcing the rules from `policy_sources`. The returned
+    /// [`PolicyReloader`] applies changes to those rules while the authorizer is in use.
     pub async fn build_casbin(
-        policy_path: &Path,
+        policy_sources: Vec<Box<dyn policy_source::PolicySource>>,
         permissive_mode: bool,
-    ) -> Result<Self, CasbinAuthorizerError> {
+        meter: &Meter,
+    ) -> Result<(Self, PolicyReloader), CasbinAuthorizerError> {
         use casbin_engine::{CasbinEngine, ModelType};
-        let engine = CasbinEngine::new(ModelType::Rbac, policy_path)
-            .await
-            .map_err(|e| CasbinAuthorizerError::InitializationError(e.to_string()))?;
-        let engine_object: Arc<PolicyEngineObject> = Arc::new(engine);
+        let engine = Arc::new(
+            CasbinEngine::new(ModelType::Abac, policy_sources)
+                .await
+                .map_err(|e| CasbinAuthorizerError::InitializationError(e.to_string()))?,
+        );
+        let reloader = PolicyReloader::new(engine.clone());
+        let engine_object: Arc<PolicyEngineObject> =
+            Arc::new(decision_log::DecisionLoggingWrapper::new(engine, meter));
         let mut authorizer = Self::new(engine_object);
         // TODO: config this out in release mode?
         if permissive_mode {
             authorizer.enable_permissive();
         }
-        Ok(authorizer)
+        Ok((authorizer, reloader))
     }
 }
 
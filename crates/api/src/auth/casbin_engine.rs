@@ -15,36 +15,77 @@
  * limitations under the License.
  */
 use std::error;
-use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
 
+use arc_swap::ArcSwap;
 use carbide_authn::middleware::Principal;
-use casbin::{CoreApi, DefaultModel, Enforcer, FileAdapter};
+use casbin::{CoreApi, DefaultModel, Enforcer, MemoryAdapter, MgmtApi};
+use tokio::sync::Mutex;
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
 
+use crate::auth::policy_source::{PolicyRule, PolicySource, PolicySourceError};
 use crate::auth::{Authorization, AuthorizationError, PolicyEngine, Predicate};
 
+#[derive(Clone, Copy, Debug)]
 pub enum ModelType {
     // Basic ACL with three arguments (subject, action, object)
     _BasicAcl,
 
     // A custom model that does RBAC on (subject, action) with glob matching
     // on the action.
-    Rbac,
+    _Rbac,
+
+    // The Rbac model, plus a condition per policy rule that is evaluated
+    // against the attributes of the resource being acted on. Rules without a
+    // condition always match.
+    Abac,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum PolicyReloadError {
+    #[error(transparent)]
+    Source(#[from] PolicySourceError),
+    #[error("Could not build the policy enforcer: {0}")]
+    Enforcer(#[from] casbin::Error),
 }
 
 pub struct CasbinEngine {
-    inner: Enforcer,
+    model_type: ModelType,
+    sources: Vec<Box<dyn PolicySource>>,
+    inner: ArcSwap<Enforcer>,
+    // The rules `inner` was built from, so that unchanged policies are not rebuilt.
+    loaded_rules: Mutex<Vec<PolicyRule>>,
 }
 
 impl CasbinEngine {
     pub async fn new(
         model_type: ModelType,
-        policy_path: &Path,
+        sources: Vec<Box<dyn PolicySource>>,
     ) -> Result<Self, Box<dyn error::Error>> {
-        let model = build_model(model_type).await;
-        let policy_path = PathBuf::from(policy_path);
-        let adapter = FileAdapter::new(policy_path);
-        let enforcer = Enforcer::new(model, adapter).await?;
-        Ok(CasbinEngine { inner: enforcer })
+        let rules = load_rules(model_type, &sources).await?;
+        let enforcer = build_enforcer(model_type, &rules).await?;
+        Ok(CasbinEngine {
+            model_type,
+            sources,
+            inner: ArcSwap::from_pointee(enforcer),
+            loaded_rules: Mutex::new(rules),
+        })
+    }
+
+    /// Loads the rules from all sources again, and starts enforcing them if they changed.
+    /// Returns whether they did. On errors, the current rules stay in effect.
+    pub async fn reload(&self) -> Result<bool, PolicyReloadError> {
+        let mut loaded_rules = self.loaded_rules.lock().await;
+        let rules = load_rules(self.model_type, &self.sources).await?;
+        if rules == *loaded_rules {
+            return Ok(false);
+        }
+        let enforcer = build_enforcer(self.model_type, &rules).await?;
+        self.inner.store(Arc::new(enforcer));
+        *loaded_rules = rules;
+        Ok(true)
     }
 }
 
@@ -54,11 +95,13 @@ impl PolicyEngine for CasbinEngine {
         principals: &[Principal],
         predicate: Predicate,
     ) -> Result<Authorization, AuthorizationError> {
-        let enforcer = &self.inner;
+        let enforcer = self.inner.load();
 
         // We move the predicate into the Authorization later, so let's record a
         // printable version of it up front for our logging needs.
         let dbg_predicate = format!("{:?}", &predicate);
+        let forge_call = format!("forge/{}", predicate.method());
+        let resource = predicate.resource();
 
         let auth_result = principals
             .iter()
@@ -67,10 +110,12 @@ impl PolicyEngine for CasbinEngine {
                 // Casbin is pretty stringly-typed under the hood. Be careful
                 // that what we're passing in here matches the order that the
                 // model and policy use.
-                let enforce_result = match &predicate {
-                    Predicate::ForgeCall(method) => {
-                        let forge_call = format!("forge/{method}");
-                        enforcer.enforce((cas_subject, forge_call))
+                let enforce_result = match self.model_type {
+                    ModelType::Abac => {
+                        enforcer.enforce((cas_subject, forge_call.clone(), resource.clone()))
+                    }
+                    ModelType::_BasicAcl | ModelType::_Rbac => {
+                        enforcer.enforce((cas_subject, forge_call.clone()))
                     }
                 };
                 match enforce_result {
@@ -99,13 +144,103 @@ impl PolicyEngine for CasbinEngine {
     }
 }
 
+/// Applies changes to the policy sources of a running authorizer.
+pub struct PolicyReloader {
+    engine: Arc<CasbinEngine>,
+}
+
+impl PolicyReloader {
+    pub(super) fn new(engine: Arc<CasbinEngine>) -> Self {
+        Self { engine }
+    }
+
+    /// Checks the policy sources for changes every `period`, until `cancel_token` is cancelled.
+    pub fn start(
+        self,
+        join_set: &mut JoinSet<()>,
+        period: Duration,
+        cancel_token: CancellationToken,
+    ) {
+        join_set
+            .build_task()
+            .name("authorization_policy_reload")
+            .spawn(async move {
+                loop {
+                    tokio::select! {
+                        _ = tokio::time::sleep(period) => {}
+                        _ = cancel_token.cancelled() => {
+                            break;
+                        }
+                    }
+
+                    match self.engine.reload().await {
+                        Ok(true) => tracing::info!("Reloaded the authorization policy"),
+                        Ok(false) => {}
+                        Err(err) => {
+                            tracing::error!(%err, "Failed reloading the authorization policy")
+                        }
+                    }
+                }
+            })
+            // Safety: spawn only fails if outside the tokio runtime.
+            .expect("Could not spawn authorization_policy_reload task");
+    }
+}
+
+// Loads and merges the rules of all sources, dropping duplicates.
+async fn load_rules(
+    model_type: ModelType,
+    sources: &[Box<dyn PolicySource>],
+) -> Result<Vec<PolicyRule>, PolicySourceError> {
+    let mut rules: Vec<PolicyRule> = Vec::new();
+    for source in sources {
+        for mut rule in source.load().await? {
+            // Plain RBAC rules are valid under the Abac model, as rules without a condition.
+            if matches!(model_type, ModelType::Abac) && rule.ptype == "p" && rule.fields.len() == 2
+            {
+                rule.fields.push("true".to_string());
+            }
+            if !rules.contains(&rule) {
+                rules.push(rule);
+            }
+        }
+    }
+    Ok(rules)
+}
+
+async fn build_enforcer(
+    model_type: ModelType,
+    rules: &[PolicyRule],
+) -> Result<Enforcer, casbin::Error> {
+    let model = build_model(model_type).await;
+    let mut enforcer = Enforcer::new(model, MemoryAdapter::default()).await?;
+
+    let mut ptypes: Vec<&str> = rules.iter().map(|rule| rule.ptype.as_str()).collect();
+    ptypes.sort_unstable();
+    ptypes.dedup();
+    for ptype in ptypes {
+        let fields = rules
+            .iter()
+            .filter(|rule| rule.ptype == ptype)
+            .map(|rule| rule.fields.clone())
+            .collect();
+        if ptype.starts_with('g') {
+            enforcer.add_named_grouping_policies(ptype, fields).await?;
+        } else {
+            enforcer.add_named_policies(ptype, fields).await?;
+        }
+    }
+    Ok(enforcer)
+}
+
 async fn build_model(model_type: ModelType) -> DefaultModel {
     // TODO: Is it possible to build this using the inscrutable .add_def()
     // method of DefaultModel? That seems to be what from_str() is implemented
     // on top of.
     let policy_config = match model_type {
         ModelType::_BasicAcl => MODEL_CONFIG_ACL,
-        ModelType::Rbac => MODEL_CONFIG_RBAC,
+        ModelType::_Rbac => MODEL_CONFIG_RBAC,
+        ModelType::Abac => MODEL_CONFIG_ABAC,
     };
     DefaultModel::from_str(policy_config)
         .await
@@ -143,3 +278,174 @@ e = some(where (p.eft == allow))
 [matchers]
 m = g(r.sub, p.sub) && globMatch(r.act, p.act)
 "#;
+
+// The condition (third policy field) is a Rhai expression over the request, e.g.
+// `r.res.tenant_organization_id == "acme"`, see `ResourceAttributes`. Conditions are only
+// evaluated against a known object (`r.res.kind` is set). Otherwise the attributes are empty,
+// and a negated condition like `r.res.tenant_organization_id != "acme"` would match.
+const MODEL_CONFIG_ABAC: &str = r#"
+[request_definition]
+r = sub, act, res
+
+[policy_definition]
+p = sub, act, cond
+
+[role_definition]
+g = _, _
+
+[policy_effect]
+e = some(where (p.eft == allow))
+
+[matchers]
+m = g(r.sub, p.sub) && globMatch(r.act, p.act) && (p.cond == "true" || r.res.deferred || (r.res.kind != "" && eval(p.cond)))
+"#;
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use carbide_authn::middleware::ExternalUserInfo;
+
+    use super::*;
+    use crate::auth::ResourceAttributes;
+    use crate::auth::policy_source::parse_policy;
+
+    #[derive(Clone, Default)]
+    struct TestPolicySource(Arc<std::sync::Mutex<String>>);
+
+    impl TestPolicySource {
+        fn set(&self, policy: &str) {
+            *self.0.lock().unwrap() = policy.to_string();
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl PolicySource for TestPolicySource {
+        fn name(&self) -> String {
+            "test".to_string()
+        }
+
+        async fn load(&self) -> Result<Vec<PolicyRule>, PolicySourceError> {
+            let policy = self.0.lock().unwrap().clone();
+            parse_policy(&policy).map_err(|e| PolicySourceError::Parse(PathBuf::from("test"), e))
+        }
+    }
+
+    fn user(group: &str) -> Principal {
+        Principal::ExternalUser(ExternalUserInfo::new(None, group.to_string(), None))
+    }
+
+    fn instance_of(tenant_organization_id: &str) -> ResourceAttributes {
+        ResourceAttributes {
+            kind: "instance".to_string(),
+            tenant_organization_id: tenant_organization_id.to_string(),
+            ..Default::default()
+        }
+    }
+
+    const POLICY: &str = r#"
+g, external-role/acme-admins, acme-admin
+p, anonymous, forge/Version
+p, acme-admin, forge/ReleaseInstance, "r.res.tenant_organization_id == ""acme"""
+p, acme-admin, forge/FindInstanceIds, "r.res.tenant_organization_id == ""acme"""
+p, acme-admin, forge/*, "r.res.tenant_organization_id != ""other"""
+"#;
+
+    #[tokio::test]
+    async fn test_abac_conditions() {
+        let source = TestPolicySource::default();
+        source.set(POLICY);
+        let engine = CasbinEngine::new(ModelType::Abac, vec![Box::new(source)])
+            .await
+            .unwrap();
+        let acme = [user("acme-admins")];
+
+        // Rules without a condition behave like the Rbac model.
+        assert!(
+            engine
+                .authorize(
+                    &[Principal::Anonymous],
+                    Predicate::ForgeCall("Version".into())
+                )
+                .is_ok()
+        );
+        assert!(
+            engine
+                .authorize(
+                    &[Principal::Anonymous],
+                    Predicate::ForgeCall("Other".into())
+                )
+                .is_err()
+        );
+
+        // ReleaseInstance checks the condition in its handler, once the instance is known.
+        assert!(
+            engine
+                .authorize(&acme, Predicate::ForgeCall("ReleaseInstance".into()))
+                .is_ok()
+        );
+        assert!(
+            engine
+                .authorize(
+                    &acme,
+                    Predicate::ForgeCallOn("ReleaseInstance".into(), instance_of("acme"))
+                )
+                .is_ok()
+        );
+        assert!(
+            engine
+                .authorize(
+                    &acme,
+                    Predicate::ForgeCallOn("ReleaseInstance".into(), instance_of("other"))
+                )
+                .is_err()
+        );
+
+        // Other methods have no resource attributes, so conditions on them never match.
+        assert!(
+            engine
+                .authorize(&acme, Predicate::ForgeCall("FindInstanceIds".into()))
+                .is_err()
+        );
+        // Not even negated ones, which would be true for empty attributes.
+        assert!(
+            engine
+                .authorize(&acme, Predicate::ForgeCall("FindMachineIds".into()))
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reload() {
+        let source = TestPolicySource::default();
+        source.set("p, anonymous, forge/Version");
+        let engine = CasbinEngine::new(ModelType::Abac, vec![Box::new(source.clone())])
+            .await
+            .unwrap();
+        let predicate = || Predicate::ForgeCall("FindMachineIds".into());
+
+        assert!(
+            engine
+                .authorize(&[Principal::Anonymous], predicate())
+                .is_err()
+        );
+        assert!(!engine.reload().await.unwrap());
+
+        source.set("p, anonymous, forge/Version\np, anonymous, forge/Find*");
+        assert!(engine.reload().await.unwrap());
+        assert!(
+            engine
+                .authorize(&[Principal::Anonymous], predicate())
+                .is_ok()
+        );
+
+        // A broken policy keeps the previous one in effect.
+        source.set("p, anonymous, \"forge/Version");
+        assert!(engine.reload().await.is_err());
+        assert!(
+            engine
+                .authorize(&[Principal::Anonymous], predicate())
+                .is_ok()
+        );
+    }
+}
@@ -0,0 +1,78 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//! The decision log: one structured event for every call the policy engine denies, with
+//! everything the decision was based on, so that denials can be audited and policy changes
+//! debugged. Events use the `authz_decision` target so they can be routed separately.
+
+use std::sync::Arc;
+
+use carbide_authn::middleware::Principal;
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::{Counter, Meter};
+
+use crate::auth::{Authorization, AuthorizationError, PolicyEngine, PolicyEngineObject, Predicate};
+
+pub(super) struct DecisionLoggingWrapper {
+    inner: Arc<PolicyEngineObject>,
+    denied_counter: Counter<u64>,
+}
+
+impl DecisionLoggingWrapper {
+    pub(super) fn new(inner: Arc<PolicyEngineObject>, meter: &Meter) -> Self {
+        let denied_counter = meter
+            .u64_counter("carbide-api.authz.denied")
+            .with_description("The amount of calls denied by the authorization policy")
+            .build();
+        Self {
+            inner,
+            denied_counter,
+        }
+    }
+}
+
+impl PolicyEngine for DecisionLoggingWrapper {
+    fn authorize(
+        &self,
+        principals: &[Principal],
+        predicate: Predicate,
+    ) -> Result<Authorization, AuthorizationError> {
+        let result = self.inner.authorize(principals, predicate.clone());
+        if let Err(e) = &result {
+            let subjects: Vec<String> = principals.iter().map(Principal::as_identifier).collect();
+            let resource = predicate.resource();
+            tracing::info!(
+                target: "authz_decision",
+                decision = "deny",
+                method = predicate.method(),
+                ?subjects,
+                resource_kind = %resource.kind,
+                resource_id = %resource.id,
+                tenant_organization_id = %resource.tenant_organization_id,
+                labels = ?resource.labels,
+                site = %resource.site,
+                deferred = resource.deferred,
+                error = %e,
+                "Authorization denied"
+            );
+            self.denied_counter.add(
+                1,
+                &[KeyValue::new("method", predicate.method().to_string())],
+            );
+        }
+        result
+    }
+}
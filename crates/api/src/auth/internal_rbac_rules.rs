@@ -161,6 +161,9 @@ impl InternalRBACRules {
         x.perm("ListPowerShelfHealthReports", vec![ForgeAdminCLI, Health]);
         x.perm("InsertPowerShelfHealthReport", vec![ForgeAdminCLI, Health]);
         x.perm("RemovePowerShelfHealthReport", vec![ForgeAdminCLI, Health]);
+        x.perm("CreateHealthSilence", vec![ForgeAdminCLI, Health]);
+        x.perm("ListHealthSilences", vec![ForgeAdminCLI, Health]);
+        x.perm("ExpireHealthSilence", vec![ForgeAdminCLI, Health]);
         // Deprecated aliases for the machine health report RPCs. Mirror the
         // permissions of their canonical equivalents above. Drop once we're
         // confident no clients are still calling the old names.
@@ -188,6 +191,7 @@ impl InternalRBACRules {
         x.perm("RenewMachineCertificate", vec![Agent]);
         x.perm("DiscoveryCompleted", vec![Machineatron, Scout]);
         x.perm("CleanupMachineCompleted", vec![Machineatron, Scout]);
+        x.perm("GetMediaSanitizationReport", vec![ForgeAdminCLI, SiteAgent]);
         x.perm("ReportForgeScoutError", vec![Scout]);
         x.perm("ReportScoutFirmwareUpgradeStatus", vec![Scout]);
         x.perm("DiscoverDhcp", vec![Dhcp, Machineatron]);
@@ -226,6 +230,7 @@ impl InternalRBACRules {
         x.perm("FindMachineIdsByBmcIps", vec![ForgeAdminCLI, Rla]);
         x.perm("FindMachineHealthHistories", vec![ForgeAdminCLI, SiteAgent]);
         x.perm("FindMachineStateHistories", vec![ForgeAdminCLI, SiteAgent]);
+        x.perm("GetManagedHostAt", vec![ForgeAdminCLI]);
         x.perm("IdentifyUuid", vec![ForgeAdminCLI]);
         x.perm("IdentifyMac", vec![ForgeAdminCLI]);
         x.perm("IdentifySerial", vec![ForgeAdminCLI, Machineatron, Rla]);
@@ -246,6 +251,7 @@ impl InternalRBACRules {
         x.perm("ValidateTenantPublicKey", vec![SiteAgent, Ssh, SshRs]);
         x.perm("GetBmcCredentials", vec![Health]);
         x.perm("GetAllManagedHostNetworkStatus", vec![ForgeAdminCLI]);
+        x.perm("RenderDpuNetworkConfig", vec![ForgeAdminCLI]);
         x.perm(
             "GetSiteExplorationReport",
             vec![ForgeAdminCLI, Machineatron],
@@ -316,6 +322,8 @@ impl InternalRBACRules {
         );
         x.perm("AttestQuote", vec![Anonymous]);
         x.perm("SignMachineIdentity", vec![Agent]);
+        x.perm("GetMachineIdentityTrustBundle", vec![Agent]);
+        x.perm("SignMachineX509Intermediate", vec![Agent]);
         x.perm(
             "GetTenantIdentityConfiguration",
             vec![ForgeAdminCLI, SiteAgent],
@@ -557,6 +565,13 @@ impl InternalRBACRules {
             "GetNetworkSecurityGroupAttachments",
             vec![ForgeAdminCLI, SiteAgent],
         );
+        x.perm("AnalyzeConnectivity", vec![ForgeAdminCLI, SiteAgent]);
+        x.perm("GetVpcReachabilityMatrix", vec![ForgeAdminCLI, SiteAgent]);
+        x.perm("CreatePrefixList", vec![ForgeAdminCLI, SiteAgent]);
+        x.perm("FindPrefixListIds", vec![ForgeAdminCLI, SiteAgent]);
+        x.perm("FindPrefixListsByIds", vec![ForgeAdminCLI, SiteAgent]);
+        x.perm("UpdatePrefixList", vec![ForgeAdminCLI, SiteAgent]);
+        x.perm("DeletePrefixList", vec![ForgeAdminCLI, SiteAgent]);
         x.perm(
             "GetDesiredFirmwareVersions",
             vec![ForgeAdminCLI, Machineatron, Rla],
@@ -86,6 +86,9 @@ where
                                 );
                             }
                             req_auth_context.authorization = Some(authorization);
+                            // Handlers of RESOURCE_AUTHORIZED_METHODS check again
+                            // once they know which object the call acts on.
+                            request.extensions_mut().insert(authorizer);
                             true
                         }
                         Err(e) => {
@@ -0,0 +1,204 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//! Where Casbin policy rules come from. The engine merges the rules of all its sources, and
+//! reloads them periodically so that policy changes apply without a restart.
+
+use std::path::PathBuf;
+
+use sqlx::PgPool;
+
+/// One line of a Casbin policy, e.g. `p, carbide-dhcp, forge/DiscoverDhcp`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PolicyRule {
+    /// The policy type, e.g. `p` or `g`
+    pub ptype: String,
+    pub fields: Vec<String>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum PolicySourceError {
+    #[error("Could not read policy file {0}: {1}")]
+    Io(PathBuf, std::io::Error),
+    #[error("Could not parse policy file {0}: {1}")]
+    Parse(PathBuf, String),
+    #[error("Could not load policy rules from the database: {0}")]
+    Database(#[from] db::DatabaseError),
+}
+
+#[async_trait::async_trait]
+pub trait PolicySource: Send + Sync {
+    /// A description of the source for logging
+    fn name(&self) -> String;
+
+    async fn load(&self) -> Result<Vec<PolicyRule>, PolicySourceError>;
+}
+
+/// A policy file in Casbin's CSV format.
+pub struct FilePolicySource {
+    path: PathBuf,
+}
+
+impl FilePolicySource {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+#[async_trait::async_trait]
+impl PolicySource for FilePolicySource {
+    fn name(&self) -> String {
+        format!("file {}", self.path.display())
+    }
+
+    async fn load(&self) -> Result<Vec<PolicyRule>, PolicySourceError> {
+        let contents = tokio::fs::read_to_string(&self.path)
+            .await
+            .map_err(|e| PolicySourceError::Io(self.path.clone(), e))?;
+        parse_policy(&contents).map_err(|e| PolicySourceError::Parse(self.path.clone(), e))
+    }
+}
+
+/// Rules from the `authorization_policy_rules` table.
+pub struct DatabasePolicySource {
+    pool: PgPool,
+}
+
+impl DatabasePolicySource {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl PolicySource for DatabasePolicySource {
+    fn name(&self) -> String {
+        "database".to_string()
+    }
+
+    async fn load(&self) -> Result<Vec<PolicyRule>, PolicySourceError> {
+        Ok(db::authorization_policy::find_all(&self.pool)
+            .await?
+            .into_iter()
+            .map(|rule| PolicyRule {
+                ptype: rule.ptype,
+                fields: rule.rule,
+            })
+            .collect())
+    }
+}
+
+/// Parses a policy in Casbin's CSV format: one rule per line, fields separated by commas, `#`
+/// starts a comment line. Fields may be double-quoted to contain commas, with `""` for a quote.
+pub(crate) fn parse_policy(contents: &str) -> Result<Vec<PolicyRule>, String> {
+    let mut rules = Vec::new();
+    for (line_number, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut fields = split_fields(line)
+            .map_err(|e| format!("line {}: {e}", line_number + 1))?
+            .into_iter();
+        let Some(ptype) = fields.next().filter(|ptype| !ptype.is_empty()) else {
+            continue;
+        };
+        rules.push(PolicyRule {
+            ptype,
+            fields: fields.collect(),
+        });
+    }
+    Ok(rules)
+}
+
+fn split_fields(line: &str) -> Result<Vec<String>, &'static str> {
+    let mut fields = Vec::new();
+    let mut chars = line.chars().peekable();
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        let mut field = String::new();
+        if chars.next_if_eq(&'"').is_some() {
+            loop {
+                match chars.next() {
+                    Some('"') if chars.next_if_eq(&'"').is_some() => field.push('"'),
+                    Some('"') => break,
+                    Some(c) => field.push(c),
+                    None => return Err("unterminated quoted field"),
+                }
+            }
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            if !matches!(chars.peek(), None | Some(',')) {
+                return Err("unexpected characters after quoted field");
+            }
+        } else {
+            while let Some(c) = chars.next_if(|c| *c != ',') {
+                field.push(c);
+            }
+            field.truncate(field.trim_end().len());
+        }
+        fields.push(field);
+        if chars.next().is_none() {
+            return Ok(fields);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_policy() {
+        let policy = r#"
+# A comment
+g, spiffe-service-id/carbide-dhcp, carbide-dhcp
+
+p, carbide-dhcp, forge/DiscoverDhcp
+p, external-role/acme-admins, forge/ReleaseInstance, "r.res.tenant_organization_id == ""acme"""
+"#;
+        let rules = parse_policy(policy).unwrap();
+        assert_eq!(
+            rules,
+            vec![
+                PolicyRule {
+                    ptype: "g".to_string(),
+                    fields: vec![
+                        "spiffe-service-id/carbide-dhcp".to_string(),
+                        "carbide-dhcp".to_string()
+                    ],
+                },
+                PolicyRule {
+                    ptype: "p".to_string(),
+                    fields: vec!["carbide-dhcp".to_string(), "forge/DiscoverDhcp".to_string()],
+                },
+                PolicyRule {
+                    ptype: "p".to_string(),
+                    fields: vec![
+                        "external-role/acme-admins".to_string(),
+                        "forge/ReleaseInstance".to_string(),
+                        r#"r.res.tenant_organization_id == "acme""#.to_string()
+                    ],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_policy_errors() {
+        assert!(parse_policy("p, anonymous, \"forge/Version").is_err());
+        assert!(parse_policy("p, anonymous, \"forge/Version\" x").is_err());
+    }
+}
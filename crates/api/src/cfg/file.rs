@@ -15,13 +15,14 @@
  * limitations under the License.
  */
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 use std::net::{Ipv4Addr, SocketAddr};
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 
+use base64::Engine;
 use bmc_vendor::BMCVendor;
 use carbide_authn::config::{AllowedCertCriteria, TrustConfig};
 use carbide_firmware::FirmwareConfig;
@@ -30,11 +31,15 @@ use carbide_nvlink_manager::config::NvLinkConfig;
 use carbide_preingestion_manager::PreingestionManagerConfig;
 use carbide_site_explorer::config::SiteExplorerConfig;
 use carbide_utils::config::{
-    as_duration, as_std_duration, deserialize_arc_atomic_bool, serialize_arc_atomic_bool,
+    as_duration, as_option_std_duration, as_std_duration, deserialize_arc_atomic_bool,
+    serialize_arc_atomic_bool,
 };
 use chrono::Duration;
-use duration_str::{deserialize_duration, deserialize_duration_chrono};
+use duration_str::{
+    deserialize_duration, deserialize_duration_chrono, deserialize_option_duration,
+};
 use figment::Figment;
+use health_report::HealthAlertClassification;
 use ipnetwork::{IpNetwork, Ipv4Network};
 use itertools::Itertools;
 use libmlx::firmware::config::FirmwareFlasherProfile;
@@ -42,6 +47,7 @@ use libmlx::profile::profile::MlxConfigProfile;
 use libmlx::profile::serialization::{
     deserialize_option_profile_map, serialize_option_profile_map,
 };
+use model::alert_dispatch::{AlertObjectKind, AlertSeverity};
 use model::firmware::{
     AgentUpgradePolicyChoice, Firmware, FirmwareComponent, FirmwareComponentType, FirmwareEntry,
 };
@@ -49,11 +55,12 @@ use model::machine::HostHealthConfig;
 use model::network_security_group::NetworkSecurityGroupRule;
 use model::network_segment::NetworkDefinition;
 use model::resource_pool::define::ResourcePoolDef;
+use model::retention::{RetentionPolicy, RetentionTarget};
 use model::tenant::identity_config::SigningAlgorithm;
 use regex::Regex;
 use serde::{Deserialize, Deserializer, Serialize};
 
-use crate::state_controller::config::IterationConfig;
+use crate::state_controller::config::{IterationConfig, ShardingConfig};
 
 static BF2_NIC: &str = "24.47.2682";
 static BF2_BMC: &str = "BF-25.10-20";
@@ -636,6 +643,39 @@ pub struct CarbideConfig {
     /// hidden when the list is empty.
     #[serde(default)]
     pub web_ui_sidebar_tools: Vec<ToolLink>,
+
+    /// Serial console page in the admin web UI, backed by ssh-console's
+    /// WebSocket endpoint. The page and its links are hidden when unset.
+    #[serde(default)]
+    pub web_ui_serial_console: Option<WebUiSerialConsoleConfig>,
+
+    /// Trimming of history tables (state history, health history,
+    /// measured boot reports, ...) which otherwise grow without bound.
+    #[serde(default)]
+    pub retention: RetentionConfig,
+
+    /// Notification of external receivers (Alertmanager, webhooks, MQTT)
+    /// when health alerts start and clear.
+    #[serde(default)]
+    pub alert_dispatch: AlertDispatchConfig,
+}
+
+/// Where the admin web UI's serial console page connects to.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct WebUiSerialConsoleConfig {
+    /// WebSocket URL of ssh-console's console endpoint, e.g.
+    /// "wss://carbide.example.com:8081/console". The machine ID is
+    /// appended as the last path segment.
+    pub websocket_url: String,
+    /// Script and stylesheet URLs for xterm.js 5.x, e.g. a copy hosted
+    /// next to the site or a CDN.
+    pub xterm_js_url: String,
+    pub xterm_css_url: String,
+    /// Subresource Integrity hashes of the script and stylesheet, e.g.
+    /// "sha384-<base64 digest>". Browsers refuse to load them if the
+    /// served content does not match.
+    pub xterm_js_integrity: String,
+    pub xterm_css_integrity: String,
 }
 
 /// One external tool link rendered in the admin web UI's "Tools"
@@ -811,6 +851,10 @@ pub struct MachineIdentityConfig {
     /// Same pattern syntax as [`Self::trust_domain_allowlist`].
     #[serde(default)]
     pub token_endpoint_domain_allowlist: Vec<String>,
+    /// Lifetime in seconds of the per-machine X.509-SVID intermediate CA signed by
+    /// SignMachineX509Intermediate. The DPU rotates it at half-life.
+    #[serde(default = "machine_identity_default_x509_intermediate_ttl_sec")]
+    pub x509_intermediate_ttl_sec: u32,
 }
 
 fn machine_identity_default_enabled() -> bool {
@@ -825,6 +869,9 @@ fn machine_identity_default_token_ttl_min_sec() -> u32 {
 fn machine_identity_default_token_ttl_max_sec() -> u32 {
     86400
 }
+fn machine_identity_default_x509_intermediate_ttl_sec() -> u32 {
+    86400
+}
 
 impl Default for MachineIdentityConfig {
     fn default() -> Self {
@@ -837,6 +884,7 @@ impl Default for MachineIdentityConfig {
             current_encryption_key_id: None,
             trust_domain_allowlist: Vec::new(),
             token_endpoint_domain_allowlist: Vec::new(),
+            x509_intermediate_ttl_sec: machine_identity_default_x509_intermediate_ttl_sec(),
         }
     }
 }
@@ -1067,6 +1115,35 @@ impl CarbideConfig {
         Ok(())
     }
 
+    /// Rejects a `web_ui_serial_console.websocket_url` that is unparsable
+    /// or doesn't use the `ws` / `wss` scheme.
+    pub fn validate_web_ui_serial_console(&self) -> eyre::Result<()> {
+        let Some(serial_console) = &self.web_ui_serial_console else {
+            return Ok(());
+        };
+        let url = &serial_console.websocket_url;
+        let parsed = url::Url::parse(url).map_err(|e| {
+            eyre::eyre!("web_ui_serial_console: invalid websocket_url {url:?}: {e}")
+        })?;
+        if !matches!(parsed.scheme(), "ws" | "wss") {
+            return Err(eyre::eyre!(
+                "web_ui_serial_console: websocket_url {url:?} must use ws or wss scheme"
+            ));
+        }
+        for (name, integrity) in [
+            ("xterm_js_integrity", &serial_console.xterm_js_integrity),
+            ("xterm_css_integrity", &serial_console.xterm_css_integrity),
+        ] {
+            if !is_valid_subresource_integrity(integrity) {
+                return Err(eyre::eyre!(
+                    "web_ui_serial_console: {name} {integrity:?} must be a sha256, sha384 or sha512 \
+                     Subresource Integrity hash, e.g. \"sha384-<base64 digest>\""
+                ));
+            }
+        }
+        Ok(())
+    }
+
     /// validate_supernic_firmware_profiles checks that each profile's inner
     /// part_number and psid match the HashMap keys they are nested under.
     /// Logs a warning for any mismatches (the inner values are authoritative
@@ -1389,6 +1466,112 @@ pub struct RackStateControllerConfig {
     /// Common state controller configs
     #[serde(default = "StateControllerConfig::default")]
     pub controller: StateControllerConfig,
+
+    /// Automated response to leaks reported for a rack
+    #[serde(default)]
+    pub leak_response: LeakResponseConfig,
+}
+
+/// Configuration of the automated leak-response playbook of the rack state
+/// controller.
+///
+/// Once a leak reported for a rack outlasts the confirmation window, the
+/// playbook blocks allocations to the compute trays of the rack, notifies the
+/// tenants of their instances, gracefully shuts the trays down and finally
+/// forces them off.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct LeakResponseConfig {
+    /// Whether a reported leak starts the playbook
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Rack health probe IDs which report a leak
+    #[serde(default = "LeakResponseConfig::default_leak_probe_ids")]
+    pub leak_probe_ids: Vec<String>,
+
+    /// How long a leak needs to be reported before the playbook acts on it
+    #[serde(
+        default = "LeakResponseConfig::default_confirmation_window",
+        deserialize_with = "deserialize_duration_chrono",
+        serialize_with = "as_duration"
+    )]
+    pub confirmation_window: Duration,
+
+    /// How long tenants are given between being notified and their trays
+    /// being shut down
+    #[serde(
+        default = "LeakResponseConfig::default_tenant_notice_period",
+        deserialize_with = "deserialize_duration_chrono",
+        serialize_with = "as_duration"
+    )]
+    pub tenant_notice_period: Duration,
+
+    /// How long trays are given to shut down gracefully before they are
+    /// forced off
+    #[serde(
+        default = "LeakResponseConfig::default_graceful_shutdown_timeout",
+        deserialize_with = "deserialize_duration_chrono",
+        serialize_with = "as_duration"
+    )]
+    pub graceful_shutdown_timeout: Duration,
+
+    /// How long to wait for a forced power off before it is requested again
+    #[serde(
+        default = "LeakResponseConfig::default_power_off_timeout",
+        deserialize_with = "deserialize_duration_chrono",
+        serialize_with = "as_duration"
+    )]
+    pub power_off_timeout: Duration,
+
+    /// Message relayed to the tenants of instances on the affected trays
+    #[serde(default = "LeakResponseConfig::default_tenant_message")]
+    pub tenant_message: String,
+}
+
+impl LeakResponseConfig {
+    pub fn default_leak_probe_ids() -> Vec<String> {
+        vec![
+            "BmsLeakDetectRack".to_string(),
+            "BmsLeakDetectRackTray".to_string(),
+            "BmcLeakDetection".to_string(),
+        ]
+    }
+
+    pub fn default_confirmation_window() -> Duration {
+        Duration::minutes(1)
+    }
+
+    pub fn default_tenant_notice_period() -> Duration {
+        Duration::minutes(5)
+    }
+
+    pub fn default_graceful_shutdown_timeout() -> Duration {
+        Duration::minutes(5)
+    }
+
+    pub fn default_power_off_timeout() -> Duration {
+        Duration::minutes(2)
+    }
+
+    pub fn default_tenant_message() -> String {
+        "A liquid leak was detected in the rack hosting this instance. \
+         The instance will be shut down to protect the hardware."
+            .to_string()
+    }
+}
+
+impl Default for LeakResponseConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            leak_probe_ids: Self::default_leak_probe_ids(),
+            confirmation_window: Self::default_confirmation_window(),
+            tenant_notice_period: Self::default_tenant_notice_period(),
+            graceful_shutdown_timeout: Self::default_graceful_shutdown_timeout(),
+            power_off_timeout: Self::default_power_off_timeout(),
+            tenant_message: Self::default_tenant_message(),
+        }
+    }
 }
 
 /// SwitchStateController related config
@@ -1484,6 +1667,63 @@ pub struct StateControllerConfig {
         serialize_with = "as_std_duration"
     )]
     pub metric_hold_time: std::time::Duration,
+
+    /// How long a queued object waits before it is dequeued alongside objects of the
+    /// next higher priority class. Prevents periodically re-evaluated objects from
+    /// starving while explicitly requested work keeps arriving.
+    #[serde(
+        default = "StateControllerConfig::priority_aging_default",
+        deserialize_with = "deserialize_duration",
+        serialize_with = "as_std_duration"
+    )]
+    pub priority_aging: std::time::Duration,
+
+    /// Partitions the queued objects between the state processors of all carbide-api
+    /// replicas by consistent hashing of their object IDs. If not set, every replica
+    /// processes whichever objects are queued.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sharding: Option<StateControllerShardingConfig>,
+}
+
+/// Configures how queued objects are sharded between state processors
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct StateControllerShardingConfig {
+    /// The amount of shards that objects are partitioned into. Must be the same on all
+    /// replicas. More shards spread objects more evenly between replicas.
+    #[serde(default = "StateControllerShardingConfig::num_shards_default")]
+    pub num_shards: u32,
+
+    /// How often a processor announces that it is alive and checks whether shards
+    /// need to be rebalanced
+    #[serde(
+        default = "StateControllerShardingConfig::heartbeat_interval_default",
+        deserialize_with = "deserialize_duration",
+        serialize_with = "as_std_duration"
+    )]
+    pub heartbeat_interval: std::time::Duration,
+
+    /// After which time without heartbeat a processor is considered gone, and its shards
+    /// are reassigned to the remaining processors
+    #[serde(
+        default = "StateControllerShardingConfig::processor_expiry_default",
+        deserialize_with = "deserialize_duration",
+        serialize_with = "as_std_duration"
+    )]
+    pub processor_expiry: std::time::Duration,
+}
+
+impl StateControllerShardingConfig {
+    pub const fn num_shards_default() -> u32 {
+        256
+    }
+
+    pub const fn heartbeat_interval_default() -> std::time::Duration {
+        std::time::Duration::from_secs(10)
+    }
+
+    pub const fn processor_expiry_default() -> std::time::Duration {
+        std::time::Duration::from_secs(60)
+    }
 }
 
 impl StateControllerConfig {
@@ -1514,6 +1754,10 @@ impl StateControllerConfig {
     pub const fn max_concurrency_default() -> usize {
         10
     }
+
+    pub const fn priority_aging_default() -> std::time::Duration {
+        std::time::Duration::from_secs(60)
+    }
 }
 
 impl Default for StateControllerConfig {
@@ -1526,6 +1770,8 @@ impl Default for StateControllerConfig {
             max_concurrency: Self::max_concurrency_default(),
             metric_emission_interval: Self::metric_emission_interval(),
             metric_hold_time: Self::metric_hold_time(),
+            priority_aging: Self::priority_aging_default(),
+            sharding: None,
         }
     }
 }
@@ -1540,6 +1786,12 @@ impl From<&StateControllerConfig> for IterationConfig {
             processor_log_interval: config.processor_log_interval,
             metric_emission_interval: config.metric_emission_interval,
             metric_hold_time: config.metric_hold_time,
+            priority_aging: config.priority_aging,
+            sharding: config.sharding.as_ref().map(|sharding| ShardingConfig {
+                num_shards: sharding.num_shards,
+                heartbeat_interval: sharding.heartbeat_interval,
+                processor_expiry: sharding.processor_expiry,
+            }),
         }
     }
 }
@@ -1623,6 +1875,19 @@ pub struct AuthConfig {
     /// The Casbin policy file (in CSV format).
     pub casbin_policy_file: Option<PathBuf>,
 
+    /// Also load Casbin policy rules from the `authorization_policy_rules` table.
+    #[serde(default)]
+    pub casbin_policy_from_database: bool,
+
+    /// How often the policy file and database rules are checked for changes,
+    /// which are applied without a restart.
+    #[serde(
+        default = "AuthConfig::policy_reload_interval_default",
+        deserialize_with = "deserialize_duration",
+        serialize_with = "as_std_duration"
+    )]
+    pub policy_reload_interval: std::time::Duration,
+
     /// Additional nico-admin-cli certs allowed.  This does not include actually allowing the cert to connect, just that certs that can be verified which match these criteria can do GRPC requests.
     pub cli_certs: Option<AllowedCertCriteria>,
 
@@ -1630,6 +1895,12 @@ pub struct AuthConfig {
     pub trust: Option<TrustConfig>,
 }
 
+impl AuthConfig {
+    pub const fn policy_reload_interval_default() -> std::time::Duration {
+        std::time::Duration::from_secs(30)
+    }
+}
+
 fn default_listen() -> SocketAddr {
     "[::]:1079".parse().unwrap()
 }
@@ -2123,6 +2394,23 @@ fn default_tenant_routing_profile() -> String {
     "EXTERNAL".to_string()
 }
 
+/// Whether `integrity` is a single Subresource Integrity hash: "sha256-", "sha384-" or
+/// "sha512-" followed by the base64 digest of matching length.
+fn is_valid_subresource_integrity(integrity: &str) -> bool {
+    let Some((algorithm, digest)) = integrity.split_once('-') else {
+        return false;
+    };
+    let digest_len = match algorithm {
+        "sha256" => 32,
+        "sha384" => 48,
+        "sha512" => 64,
+        _ => return false,
+    };
+    base64::engine::general_purpose::STANDARD
+        .decode(digest)
+        .is_ok_and(|digest| digest.len() == digest_len)
+}
+
 /// Configuration for the measured boot metrics collector,
 /// which exports TPM-based boot measurement data as
 /// Prometheus metrics.
@@ -2158,6 +2446,287 @@ impl MeasuredBootMetricsCollectorConfig {
     }
 }
 
+/// Configuration for the retention job, which periodically trims
+/// history tables according to per-table policies.
+///
+/// ```toml
+/// [retention]
+/// enabled = true
+/// archive_dir = "/var/lib/carbide/retention"
+///
+/// [retention.policies.machine_state_history]
+/// max_age = "90d"
+/// keep_last = 20
+/// ```
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct RetentionConfig {
+    /// Enables the periodic retention job. Only one carbide-api
+    /// replica trims at a time. `TrimTable` calls are served
+    /// regardless of this setting.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Interval at which the policies are applied.
+    /// Default is 1 hour.
+    #[serde(
+        default = "RetentionConfig::default_run_interval",
+        deserialize_with = "deserialize_duration",
+        serialize_with = "as_std_duration"
+    )]
+    pub run_interval: std::time::Duration,
+    /// Number of rows deleted per transaction.
+    #[serde(default = "RetentionConfig::default_batch_size")]
+    pub batch_size: u32,
+    /// If set, trimmed rows are written to gzip compressed JSONL
+    /// files below this directory before they are deleted.
+    #[serde(default)]
+    pub archive_dir: Option<PathBuf>,
+    /// Policy per table. Tables without a policy are not trimmed.
+    #[serde(default)]
+    pub policies: BTreeMap<RetentionTarget, RetentionPolicyConfig>,
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            run_interval: Self::default_run_interval(),
+            batch_size: Self::default_batch_size(),
+            archive_dir: None,
+            policies: BTreeMap::new(),
+        }
+    }
+}
+
+impl RetentionConfig {
+    const fn default_run_interval() -> std::time::Duration {
+        std::time::Duration::from_secs(3600)
+    }
+
+    const fn default_batch_size() -> u32 {
+        1000
+    }
+}
+
+/// Which rows of a table are trimmed, see [`RetentionPolicy`].
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct RetentionPolicyConfig {
+    /// Trim rows older than this.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_option_duration",
+        serialize_with = "as_option_std_duration",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_age: Option<std::time::Duration>,
+    /// Keep at most this many rows per object (machine, segment, ...).
+    #[serde(default)]
+    pub max_rows_per_object: Option<u32>,
+    /// The newest rows of each object which are never trimmed,
+    /// even when they are older than `max_age`.
+    #[serde(default)]
+    pub keep_last: u32,
+}
+
+impl From<&RetentionPolicyConfig> for RetentionPolicy {
+    fn from(config: &RetentionPolicyConfig) -> Self {
+        RetentionPolicy {
+            max_age: config
+                .max_age
+                .map(|max_age| Duration::from_std(max_age).unwrap_or(Duration::MAX)),
+            max_rows_per_object: config.max_rows_per_object,
+            keep_last: config.keep_last,
+        }
+    }
+}
+
+/// Configuration for the alert dispatcher, which notifies external receivers
+/// when health alerts of machines, racks, switches and power shelves start
+/// and clear.
+///
+/// ```toml
+/// [alert_dispatch]
+/// enabled = true
+/// group_by = ["object_kind", "alertname"]
+///
+/// [alert_dispatch.receivers.oncall]
+/// type = "alertmanager"
+/// url = "http://alertmanager.monitoring:9093"
+///
+/// [[alert_dispatch.routes]]
+/// receiver = "oncall"
+/// min_severity = "critical"
+/// ```
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct AlertDispatchConfig {
+    /// Enables the alert dispatcher. Only one carbide-api replica
+    /// dispatches at a time.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Interval at which health reports are checked for alert transitions.
+    /// Default is 30 seconds.
+    #[serde(
+        default = "AlertDispatchConfig::default_run_interval",
+        deserialize_with = "deserialize_duration",
+        serialize_with = "as_std_duration"
+    )]
+    pub run_interval: std::time::Duration,
+    /// Alert labels whose values form the group key. Alerts of a run which
+    /// are routed to the same receiver and share a group key are sent in a
+    /// single notification. Without labels, all alerts of a run are sent
+    /// together.
+    #[serde(default)]
+    pub group_by: Vec<String>,
+    /// Alerts with any of these classifications have `critical` severity,
+    /// all other alerts have `warning` severity.
+    #[serde(default = "AlertDispatchConfig::default_critical_classifications")]
+    pub critical_classifications: Vec<HealthAlertClassification>,
+    #[serde(default)]
+    pub retry: AlertDispatchRetryConfig,
+    /// Receivers by name
+    #[serde(default)]
+    pub receivers: BTreeMap<String, AlertReceiverConfig>,
+    /// Alerts are sent to the receivers of all matching routes.
+    #[serde(default)]
+    pub routes: Vec<AlertRouteConfig>,
+}
+
+impl Default for AlertDispatchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            run_interval: Self::default_run_interval(),
+            group_by: Vec::new(),
+            critical_classifications: Self::default_critical_classifications(),
+            retry: AlertDispatchRetryConfig::default(),
+            receivers: BTreeMap::new(),
+            routes: Vec::new(),
+        }
+    }
+}
+
+impl AlertDispatchConfig {
+    const fn default_run_interval() -> std::time::Duration {
+        std::time::Duration::from_secs(30)
+    }
+
+    fn default_critical_classifications() -> Vec<HealthAlertClassification> {
+        vec![
+            HealthAlertClassification::prevent_allocations(),
+            HealthAlertClassification::prevent_host_state_changes(),
+            HealthAlertClassification::sensor_critical(),
+        ]
+    }
+}
+
+/// How failed deliveries to a receiver are retried. Notifications which
+/// could not be delivered after `max_attempts` are attempted again on the
+/// next run.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct AlertDispatchRetryConfig {
+    #[serde(default = "AlertDispatchRetryConfig::default_max_attempts")]
+    pub max_attempts: u32,
+    /// Delay before the first retry. It doubles with every further retry.
+    #[serde(
+        default = "AlertDispatchRetryConfig::default_initial_backoff",
+        deserialize_with = "deserialize_duration",
+        serialize_with = "as_std_duration"
+    )]
+    pub initial_backoff: std::time::Duration,
+    #[serde(
+        default = "AlertDispatchRetryConfig::default_max_backoff",
+        deserialize_with = "deserialize_duration",
+        serialize_with = "as_std_duration"
+    )]
+    pub max_backoff: std::time::Duration,
+    /// Timeout of a single delivery attempt
+    #[serde(
+        default = "AlertDispatchRetryConfig::default_request_timeout",
+        deserialize_with = "deserialize_duration",
+        serialize_with = "as_std_duration"
+    )]
+    pub request_timeout: std::time::Duration,
+}
+
+impl Default for AlertDispatchRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: Self::default_max_attempts(),
+            initial_backoff: Self::default_initial_backoff(),
+            max_backoff: Self::default_max_backoff(),
+            request_timeout: Self::default_request_timeout(),
+        }
+    }
+}
+
+impl AlertDispatchRetryConfig {
+    const fn default_max_attempts() -> u32 {
+        4
+    }
+
+    const fn default_initial_backoff() -> std::time::Duration {
+        std::time::Duration::from_secs(1)
+    }
+
+    const fn default_max_backoff() -> std::time::Duration {
+        std::time::Duration::from_secs(10)
+    }
+
+    const fn default_request_timeout() -> std::time::Duration {
+        std::time::Duration::from_secs(10)
+    }
+}
+
+/// A destination for alert notifications
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AlertReceiverConfig {
+    /// Posts alerts to the v2 API (`/api/v2/alerts`) of an Alertmanager.
+    /// Firing alerts are re-sent on every run, so that Alertmanager does not
+    /// resolve them on its own.
+    Alertmanager { url: String },
+    /// Posts a JSON document per notification. Without `body_template`,
+    /// the notification itself is posted. See the alert dispatcher
+    /// documentation for the template placeholders.
+    Webhook {
+        url: String,
+        #[serde(default)]
+        body_template: Option<String>,
+        #[serde(default)]
+        headers: BTreeMap<String, String>,
+    },
+    /// Publishes every alert to `{topic_prefix}/{object_kind}/{object_id}`.
+    Mqtt {
+        #[serde(default = "default_mqtt_endpoint")]
+        mqtt_endpoint: String,
+        #[serde(default = "default_mqtt_broker_port")]
+        mqtt_broker_port: u16,
+        #[serde(default = "AlertReceiverConfig::default_topic_prefix")]
+        topic_prefix: String,
+        #[serde(default)]
+        auth: MqttAuthConfig,
+    },
+}
+
+impl AlertReceiverConfig {
+    fn default_topic_prefix() -> String {
+        "nico/v1/alert".to_string()
+    }
+}
+
+/// Selects the alerts sent to a receiver. Every criterion which is set
+/// needs to match.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct AlertRouteConfig {
+    pub receiver: String,
+    /// The alert needs to have at least one of these classifications
+    #[serde(default)]
+    pub classifications: Vec<HealthAlertClassification>,
+    #[serde(default)]
+    pub object_kinds: Vec<AlertObjectKind>,
+    #[serde(default)]
+    pub min_severity: AlertSeverity,
+}
+
 /// Controls which machine validation tests are active.
 #[derive(Default, Clone, Copy, Debug, Deserialize, Serialize)]
 pub enum MachineValidationTestSelectionMode {
@@ -2452,8 +3021,8 @@ impl MqttOAuth2Config {
     }
 }
 
-/// MQTT authentication configuration shared by DPA and
-/// DSX event bus.
+/// MQTT authentication configuration shared by DPA, the
+/// DSX event bus and the alert dispatcher.
 #[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
 pub struct MqttAuthConfig {
     /// Authentication mechanism to use for MQTT
@@ -2539,6 +3108,12 @@ pub struct DsxExchangeEventBusConfig {
     #[serde(default = "DsxExchangeEventBusConfig::default_queue_capacity")]
     pub queue_capacity: usize,
 
+    /// Directory for the durable MQTT outbox. When set, publishes are
+    /// persisted to disk and replayed in order once the broker is
+    /// reachable again, instead of being dropped while it is down.
+    #[serde(default)]
+    pub outbox_directory: Option<std::path::PathBuf>,
+
     #[serde(default)]
     pub auth: MqttAuthConfig,
 }
@@ -2728,6 +3303,8 @@ mod tests {
                 processor_log_interval: std::time::Duration::from_secs(60),
                 metric_emission_interval: std::time::Duration::from_secs(60),
                 metric_hold_time: std::time::Duration::from_secs(5 * 60),
+                priority_aging: std::time::Duration::from_secs(60),
+                sharding: None,
             },
             dpu_wait_time: Duration::minutes(20),
             power_down_wait: Duration::seconds(10),
@@ -2770,6 +3347,8 @@ mod tests {
                         processor_log_interval: std::time::Duration::from_secs(60),
                         metric_emission_interval: std::time::Duration::from_secs(60),
                         metric_hold_time: std::time::Duration::from_secs(5 * 60),
+                        priority_aging: std::time::Duration::from_secs(60),
+                        sharding: None,
                     }
                 },
                 dpu_wait_time: Duration::minutes(20),
@@ -2820,6 +3399,8 @@ mod tests {
                         processor_log_interval: std::time::Duration::from_secs(60),
                         metric_emission_interval: std::time::Duration::from_secs(60),
                         metric_hold_time: std::time::Duration::from_secs(5 * 60),
+                        priority_aging: std::time::Duration::from_secs(60),
+                        sharding: None,
                     }
                 },
                 network_segment_drain_time: Duration::minutes(21),
@@ -2841,7 +3422,7 @@ mod tests {
         let config_str = serde_json::to_string(&input).unwrap();
         assert_eq!(
             config_str,
-            r#"{"iteration_time":"30s","max_object_handling_time":"180s","max_concurrency":10,"processor_dispatch_interval":"2s","processor_log_interval":"60s","metric_emission_interval":"60s","metric_hold_time":"300s"}"#
+            r#"{"iteration_time":"30s","max_object_handling_time":"180s","max_concurrency":10,"processor_dispatch_interval":"2s","processor_log_interval":"60s","metric_emission_interval":"60s","metric_hold_time":"300s","priority_aging":"60s"}"#
         );
         let config: StateControllerConfig = serde_json::from_str(&config_str).unwrap();
         assert_eq!(config, input);
@@ -2895,6 +3476,46 @@ mod tests {
         assert!(config.validate_web_ui_sidebar_tools().is_err());
     }
 
+    #[test]
+    fn validate_web_ui_serial_console_requires_websocket_scheme() {
+        let mut config: CarbideConfig = Figment::new()
+            .merge(Toml::file(format!("{TEST_DATA_DIR}/min_config.toml")))
+            .extract()
+            .unwrap();
+        assert!(config.web_ui_serial_console.is_none());
+        assert!(config.validate_web_ui_serial_console().is_ok());
+
+        config.web_ui_serial_console = Some(WebUiSerialConsoleConfig {
+            websocket_url: "https://carbide.example.com:8081/console".to_string(),
+            xterm_js_url: "https://cdn.example.com/xterm.min.js".to_string(),
+            xterm_css_url: "https://cdn.example.com/xterm.min.css".to_string(),
+            xterm_js_integrity: format!("sha384-{}", "A".repeat(64)),
+            xterm_css_integrity: format!("sha256-{}=", "A".repeat(43)),
+        });
+        assert!(config.validate_web_ui_serial_console().is_err());
+
+        config.web_ui_serial_console.as_mut().unwrap().websocket_url =
+            "wss://carbide.example.com:8081/console".to_string();
+        assert!(config.validate_web_ui_serial_console().is_ok());
+
+        for integrity in [
+            "",
+            "sha1-AAAAAAAAAAAAAAAAAAAAAAAAAAA=",
+            "sha384-not base64",
+            "sha512-AAAA",
+        ] {
+            config
+                .web_ui_serial_console
+                .as_mut()
+                .unwrap()
+                .xterm_js_integrity = integrity.to_string();
+            assert!(
+                config.validate_web_ui_serial_console().is_err(),
+                "{integrity:?} should be rejected"
+            );
+        }
+    }
+
     #[test]
     fn serialize_configured_state_controller_config() {
         let input = StateControllerConfig {
@@ -2905,11 +3526,13 @@ mod tests {
             processor_log_interval: std::time::Duration::from_secs(60),
             metric_emission_interval: std::time::Duration::from_secs(60),
             metric_hold_time: std::time::Duration::from_secs(5 * 60),
+            priority_aging: std::time::Duration::from_secs(60),
+            sharding: None,
         };
         let config_str = serde_json::to_string(&input).unwrap();
         assert_eq!(
             config_str,
-            r#"{"iteration_time":"11s","max_object_handling_time":"22s","max_concurrency":33,"processor_dispatch_interval":"2s","processor_log_interval":"60s","metric_emission_interval":"60s","metric_hold_time":"300s"}"#
+            r#"{"iteration_time":"11s","max_object_handling_time":"22s","max_concurrency":33,"processor_dispatch_interval":"2s","processor_log_interval":"60s","metric_emission_interval":"60s","metric_hold_time":"300s","priority_aging":"60s"}"#
         );
         let config: StateControllerConfig = serde_json::from_str(&config_str).unwrap();
         assert_eq!(config, input);
@@ -2981,6 +3604,12 @@ mod tests {
                 run_interval: MeasuredBootMetricsCollectorConfig::default_run_interval(),
             }
         });
+        assert_eq!(config.retention, RetentionConfig::default());
+        assert_eq!(config.alert_dispatch, AlertDispatchConfig::default());
+        assert_eq!(
+            config.rack_state_controller.leak_response,
+            LeakResponseConfig::default()
+        );
         // And make sure lack of [mlx-config-profiles] doesn't blow up
         // for sites not configured with any.
         assert!(config.mlxconfig_profiles.is_none());
@@ -3083,6 +3712,8 @@ mod tests {
                     processor_log_interval: std::time::Duration::from_secs(60),
                     metric_emission_interval: std::time::Duration::from_secs(60),
                     metric_hold_time: std::time::Duration::from_secs(5 * 60),
+                    priority_aging: std::time::Duration::from_secs(60),
+                    sharding: None,
                 },
                 dpu_wait_time: Duration::minutes(7),
                 power_down_wait: Duration::seconds(17),
@@ -3104,6 +3735,8 @@ mod tests {
                     processor_log_interval: std::time::Duration::from_secs(60),
                     metric_emission_interval: std::time::Duration::from_secs(60),
                     metric_hold_time: std::time::Duration::from_secs(5 * 60),
+                    priority_aging: std::time::Duration::from_secs(60),
+                    sharding: None,
                 },
             }
         );
@@ -3118,6 +3751,8 @@ mod tests {
                     processor_log_interval: std::time::Duration::from_secs(60),
                     metric_emission_interval: std::time::Duration::from_secs(60),
                     metric_hold_time: std::time::Duration::from_secs(5 * 60),
+                    priority_aging: std::time::Duration::from_secs(60),
+                    sharding: None,
                 },
             }
         );
@@ -3170,6 +3805,11 @@ mod tests {
                 .as_os_str(),
             "/path/to/policy"
         );
+        assert!(config.auth.as_ref().unwrap().casbin_policy_from_database);
+        assert_eq!(
+            config.auth.as_ref().unwrap().policy_reload_interval,
+            std::time::Duration::from_secs(45)
+        );
         let pools = config.pools.as_ref().unwrap();
         assert_eq!(
             pools.get("lo-ip").unwrap(),
@@ -3267,6 +3907,8 @@ mod tests {
                     processor_log_interval: std::time::Duration::from_secs(60),
                     metric_emission_interval: std::time::Duration::from_secs(60),
                     metric_hold_time: std::time::Duration::from_secs(5 * 60),
+                    priority_aging: std::time::Duration::from_secs(60),
+                    sharding: None,
                 },
                 dpu_wait_time: Duration::minutes(3),
                 power_down_wait: Duration::seconds(13),
@@ -3288,6 +3930,8 @@ mod tests {
                     processor_log_interval: std::time::Duration::from_secs(60),
                     metric_emission_interval: std::time::Duration::from_secs(60),
                     metric_hold_time: std::time::Duration::from_secs(5 * 60),
+                    priority_aging: std::time::Duration::from_secs(60),
+                    sharding: None,
                 },
             }
         );
@@ -3302,6 +3946,8 @@ mod tests {
                     processor_log_interval: std::time::Duration::from_secs(60),
                     metric_emission_interval: std::time::Duration::from_secs(60),
                     metric_hold_time: std::time::Duration::from_secs(5 * 60),
+                    priority_aging: std::time::Duration::from_secs(60),
+                    sharding: None,
                 },
             }
         );
@@ -3576,6 +4222,8 @@ mod tests {
                     processor_log_interval: std::time::Duration::from_secs(60),
                     metric_emission_interval: std::time::Duration::from_secs(60),
                     metric_hold_time: std::time::Duration::from_secs(5 * 60),
+                    priority_aging: std::time::Duration::from_secs(60),
+                    sharding: None,
                 },
                 dpu_wait_time: Duration::minutes(7),
                 power_down_wait: Duration::seconds(17),
@@ -3597,6 +4245,8 @@ mod tests {
                     processor_log_interval: std::time::Duration::from_secs(60),
                     metric_emission_interval: std::time::Duration::from_secs(60),
                     metric_hold_time: std::time::Duration::from_secs(5 * 60),
+                    priority_aging: std::time::Duration::from_secs(60),
+                    sharding: None,
                 },
             }
         );
@@ -3611,6 +4261,8 @@ mod tests {
                     processor_log_interval: std::time::Duration::from_secs(60),
                     metric_emission_interval: std::time::Duration::from_secs(60),
                     metric_hold_time: std::time::Duration::from_secs(5 * 60),
+                    priority_aging: std::time::Duration::from_secs(60),
+                    sharding: None,
                 },
             }
         );
@@ -3741,6 +4393,186 @@ mqtt_endpoint = "mqtt.forge"
         );
     }
 
+    #[test]
+    fn deserialize_retention_config() {
+        let toml = r#"
+enabled = true
+archive_dir = "/var/lib/carbide/retention"
+
+[policies.machine_state_history]
+max_age = "90d"
+keep_last = 20
+
+[policies.measured_boot]
+max_rows_per_object = 250
+        "#;
+
+        let retention: RetentionConfig =
+            Figment::new().merge(Toml::string(toml)).extract().unwrap();
+
+        assert!(retention.enabled);
+        assert_eq!(
+            retention.run_interval,
+            RetentionConfig::default_run_interval()
+        );
+        assert_eq!(retention.batch_size, RetentionConfig::default_batch_size());
+        assert_eq!(
+            retention.archive_dir,
+            Some(PathBuf::from("/var/lib/carbide/retention"))
+        );
+        assert_eq!(retention.policies.len(), 2);
+        assert_eq!(
+            RetentionPolicy::from(&retention.policies[&RetentionTarget::MachineStateHistory]),
+            RetentionPolicy {
+                max_age: Some(Duration::days(90)),
+                max_rows_per_object: None,
+                keep_last: 20,
+            }
+        );
+        assert_eq!(
+            RetentionPolicy::from(&retention.policies[&RetentionTarget::MeasuredBoot]),
+            RetentionPolicy {
+                max_age: None,
+                max_rows_per_object: Some(250),
+                keep_last: 0,
+            }
+        );
+
+        // Round trips through serialization
+        let serialized = serde_json::to_string(&retention).unwrap();
+        let deserialized: RetentionConfig = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, retention);
+    }
+
+    #[test]
+    fn deserialize_leak_response_config() {
+        let toml = r#"
+[rack_state_controller.leak_response]
+enabled = true
+leak_probe_ids = ["BmsLeakDetectRack"]
+confirmation_window = "30s"
+graceful_shutdown_timeout = "10m"
+        "#;
+
+        let config: RackStateControllerConfig = Figment::new()
+            .merge(Toml::string(toml))
+            .extract_inner("rack_state_controller")
+            .unwrap();
+
+        let leak_response = config.leak_response;
+        assert!(leak_response.enabled);
+        assert_eq!(leak_response.leak_probe_ids, vec!["BmsLeakDetectRack"]);
+        assert_eq!(leak_response.confirmation_window, Duration::seconds(30));
+        assert_eq!(
+            leak_response.graceful_shutdown_timeout,
+            Duration::minutes(10)
+        );
+        assert_eq!(
+            leak_response.tenant_notice_period,
+            LeakResponseConfig::default_tenant_notice_period()
+        );
+        assert_eq!(
+            leak_response.power_off_timeout,
+            LeakResponseConfig::default_power_off_timeout()
+        );
+        assert_eq!(
+            leak_response.tenant_message,
+            LeakResponseConfig::default_tenant_message()
+        );
+        assert_eq!(config.controller, StateControllerConfig::default());
+    }
+
+    #[test]
+    fn deserialize_alert_dispatch_config() {
+        let toml = r#"
+enabled = true
+group_by = ["object_kind", "alertname"]
+
+[retry]
+max_attempts = 2
+
+[receivers.oncall]
+type = "alertmanager"
+url = "http://alertmanager:9093"
+
+[receivers.chat]
+type = "webhook"
+url = "https://chat.example.com/hook"
+body_template = '{"text": "{{summary}}"}'
+
+[receivers.bus]
+type = "mqtt"
+
+[[routes]]
+receiver = "oncall"
+min_severity = "critical"
+
+[[routes]]
+receiver = "chat"
+classifications = ["Hardware"]
+object_kinds = ["rack", "power_shelf"]
+        "#;
+
+        let config: AlertDispatchConfig =
+            Figment::new().merge(Toml::string(toml)).extract().unwrap();
+
+        assert!(config.enabled);
+        assert_eq!(
+            config.run_interval,
+            AlertDispatchConfig::default_run_interval()
+        );
+        assert_eq!(config.group_by, vec!["object_kind", "alertname"]);
+        assert_eq!(
+            config.critical_classifications,
+            AlertDispatchConfig::default_critical_classifications()
+        );
+        assert_eq!(config.retry.max_attempts, 2);
+        assert_eq!(
+            config.retry.initial_backoff,
+            AlertDispatchRetryConfig::default_initial_backoff()
+        );
+        assert_eq!(
+            config.receivers["oncall"],
+            AlertReceiverConfig::Alertmanager {
+                url: "http://alertmanager:9093".to_string()
+            }
+        );
+        assert_eq!(
+            config.receivers["chat"],
+            AlertReceiverConfig::Webhook {
+                url: "https://chat.example.com/hook".to_string(),
+                body_template: Some(r#"{"text": "{{summary}}"}"#.to_string()),
+                headers: BTreeMap::new(),
+            }
+        );
+        assert_eq!(
+            config.receivers["bus"],
+            AlertReceiverConfig::Mqtt {
+                mqtt_endpoint: default_mqtt_endpoint(),
+                mqtt_broker_port: default_mqtt_broker_port(),
+                topic_prefix: "nico/v1/alert".to_string(),
+                auth: MqttAuthConfig::default(),
+            }
+        );
+        assert_eq!(config.routes.len(), 2);
+        assert_eq!(config.routes[0].min_severity, AlertSeverity::Critical);
+        assert!(config.routes[0].classifications.is_empty());
+        assert_eq!(
+            config.routes[1].classifications,
+            vec![HealthAlertClassification::hardware()]
+        );
+        assert_eq!(
+            config.routes[1].object_kinds,
+            vec![AlertObjectKind::Rack, AlertObjectKind::PowerShelf]
+        );
+        assert_eq!(config.routes[1].min_severity, AlertSeverity::Warning);
+
+        // Round trips through serialization
+        let serialized = serde_json::to_string(&config).unwrap();
+        let deserialized: AlertDispatchConfig = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, config);
+    }
+
     #[test]
     fn deserialize_dpu_config() {
         let toml = r#"
@@ -0,0 +1,1045 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! What-if connectivity analysis between instance interfaces and addresses.
+//!
+//! This mirrors what the DPUs of both ends enforce: VPC peering decides whether traffic is
+//! routed between VPCs, and the site policy overrides and network security groups are ACLs
+//! where the lowest priority matching rule wins. Once an NSG applies, unmatched traffic is
+//! denied. Replies are evaluated as well, because with `stateful_egress` the DPU lets replies
+//! to permitted egress traffic back in even if no ingress rule permits them.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+use ::rpc::forge as rpc;
+use carbide_network::virtualization::VpcVirtualizationType;
+use carbide_uuid::instance::InstanceId;
+use carbide_uuid::network::NetworkSegmentId;
+use carbide_uuid::network_security_group::NetworkSecurityGroupId;
+use carbide_uuid::prefix_list::PrefixListId;
+use carbide_uuid::vpc::VpcId;
+use db::{self, ObjectColumnFilter, network_security_group, network_segment, vpc};
+use model::instance::snapshot::InstanceSnapshot;
+use model::instance_address::InstanceAddress;
+use model::network_security_group::{
+    NetworkSecurityGroup, NetworkSecurityGroupRule, NetworkSecurityGroupRuleAction,
+    NetworkSecurityGroupRuleDirection, NetworkSecurityGroupRuleNet,
+    NetworkSecurityGroupRuleProtocol, NetworkSecurityGroupSource, referenced_prefix_list_ids,
+};
+use model::network_segment::NetworkSegmentSearchConfig;
+use model::prefix_list::PrefixList;
+use model::tenant::TenantOrganizationId;
+use sqlx::PgConnection;
+
+use crate::CarbideError;
+use crate::cfg::file::VpcPeeringPolicy;
+
+/// An address that traffic is analyzed from or to
+#[derive(Debug, Clone)]
+pub struct Endpoint {
+    pub address: IpAddr,
+    /// Unset for addresses that don't belong to an instance
+    pub interface: Option<EndpointInterface>,
+}
+
+/// The instance interface an address belongs to, and what applies to it
+#[derive(Debug, Clone)]
+pub struct EndpointInterface {
+    pub instance_id: InstanceId,
+    pub segment_id: NetworkSegmentId,
+    pub vpc: Option<EndpointVpc>,
+    pub network_security_group: Option<(NetworkSecurityGroupSource, NetworkSecurityGroup)>,
+    /// The prefix lists referenced by the rules of `network_security_group`
+    pub prefix_lists: HashMap<PrefixListId, PrefixList>,
+    /// Quarantined hosts have all of their traffic denied by the DPU
+    pub quarantined: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct EndpointVpc {
+    pub id: VpcId,
+    pub network_virtualization_type: VpcVirtualizationType,
+    pub peer_ids: Vec<VpcId>,
+}
+
+/// The traffic to analyze
+#[derive(Debug, Clone)]
+pub struct Flow {
+    pub protocol: NetworkSecurityGroupRuleProtocol,
+    pub src_port: Option<u32>,
+    pub dst_port: Option<u32>,
+}
+
+impl Flow {
+    /// Fails if the protocol can't be used with the IP version of `address`
+    pub fn check_ip_version(&self, address: IpAddr) -> Result<(), CarbideError> {
+        match self.protocol {
+            NetworkSecurityGroupRuleProtocol::Icmp if address.is_ipv6() => {
+                Err(CarbideError::InvalidArgument(
+                    "ICMP cannot be used with IPv6 addresses".to_string(),
+                ))
+            }
+            NetworkSecurityGroupRuleProtocol::Icmp6 if address.is_ipv4() => {
+                Err(CarbideError::InvalidArgument(
+                    "ICMP6 cannot be used with IPv4 addresses".to_string(),
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl TryFrom<rpc::ConnectivityFlow> for Flow {
+    type Error = CarbideError;
+
+    fn try_from(flow: rpc::ConnectivityFlow) -> Result<Self, Self::Error> {
+        let protocol = NetworkSecurityGroupRuleProtocol::try_from(flow.protocol())?;
+        let has_ports = flow.src_port.is_some() || flow.dst_port.is_some();
+        match protocol {
+            NetworkSecurityGroupRuleProtocol::Any => {
+                return Err(CarbideError::InvalidArgument(
+                    "flow protocol must be one of TCP, UDP, ICMP or ICMP6".to_string(),
+                ));
+            }
+            NetworkSecurityGroupRuleProtocol::Icmp | NetworkSecurityGroupRuleProtocol::Icmp6
+                if has_ports =>
+            {
+                return Err(CarbideError::InvalidArgument(format!(
+                    "ports cannot be specified with {protocol} flows"
+                )));
+            }
+            _ => {}
+        }
+        if let Some(port) = [flow.src_port, flow.dst_port]
+            .into_iter()
+            .flatten()
+            .find(|port| *port > u32::from(u16::MAX))
+        {
+            return Err(CarbideError::InvalidArgument(format!(
+                "port {port} is out of range"
+            )));
+        }
+        Ok(Flow {
+            protocol,
+            src_port: flow.src_port,
+            dst_port: flow.dst_port,
+        })
+    }
+}
+
+/// Site-wide settings that the DPUs enforce in addition to the NSGs
+#[derive(Debug, Clone)]
+pub struct SitePolicy<'a> {
+    pub vpc_peering_policy: Option<VpcPeeringPolicy>,
+    pub stateful_acls_enabled: bool,
+    pub policy_overrides: &'a [NetworkSecurityGroupRule],
+}
+
+struct Packet<'a> {
+    src: IpAddr,
+    dst: IpAddr,
+    protocol: &'a NetworkSecurityGroupRuleProtocol,
+    src_port: Option<u32>,
+    dst_port: Option<u32>,
+}
+
+/// Evaluates every stage that applies to `flow` from `src` to `dst` and its replies.
+/// The traffic is allowed if all returned steps are.
+pub fn analyze(
+    src: &Endpoint,
+    dst: &Endpoint,
+    flow: &Flow,
+    policy: &SitePolicy<'_>,
+) -> Vec<rpc::ConnectivityStep> {
+    let request = Packet {
+        src: src.address,
+        dst: dst.address,
+        protocol: &flow.protocol,
+        src_port: flow.src_port,
+        dst_port: flow.dst_port,
+    };
+    let reply = Packet {
+        src: dst.address,
+        dst: src.address,
+        protocol: &flow.protocol,
+        src_port: flow.dst_port,
+        dst_port: flow.src_port,
+    };
+
+    let mut steps = vec![routing_step(src, dst, policy)];
+    if let Some(interface) = &src.interface {
+        steps.push(acl_step(
+            rpc::ConnectivityStage::SourceEgress,
+            interface,
+            NetworkSecurityGroupRuleDirection::Egress,
+            &request,
+            policy,
+        ));
+    }
+    if let Some(interface) = &dst.interface {
+        steps.push(acl_step(
+            rpc::ConnectivityStage::DestinationIngress,
+            interface,
+            NetworkSecurityGroupRuleDirection::Ingress,
+            &request,
+            policy,
+        ));
+        steps.push(acl_step(
+            rpc::ConnectivityStage::DestinationEgressReply,
+            interface,
+            NetworkSecurityGroupRuleDirection::Egress,
+            &reply,
+            policy,
+        ));
+    }
+    if let Some(interface) = &src.interface {
+        steps.push(acl_step(
+            rpc::ConnectivityStage::SourceIngressReply,
+            interface,
+            NetworkSecurityGroupRuleDirection::Ingress,
+            &reply,
+            policy,
+        ));
+    }
+    steps
+}
+
+fn routing_step(src: &Endpoint, dst: &Endpoint, policy: &SitePolicy<'_>) -> rpc::ConnectivityStep {
+    let vpc_of = |endpoint: &Endpoint| endpoint.interface.as_ref().and_then(|i| i.vpc.clone());
+    let (allowed, reason) = match (vpc_of(src), vpc_of(dst)) {
+        (Some(src_vpc), Some(dst_vpc)) if src_vpc.id == dst_vpc.id => {
+            (true, format!("both endpoints are in VPC {}", src_vpc.id))
+        }
+        (Some(src_vpc), Some(dst_vpc)) => peering(&src_vpc, &dst_vpc, policy),
+        (None, _) => (true, outside_of_vpcs(src.address)),
+        (_, None) => (true, outside_of_vpcs(dst.address)),
+    };
+    rpc::ConnectivityStep {
+        stage: rpc::ConnectivityStage::Routing.into(),
+        allowed,
+        reason,
+        rule: None,
+        site_policy_override: false,
+        network_security_group_id: None,
+    }
+}
+
+fn outside_of_vpcs(address: IpAddr) -> String {
+    format!("{address} is not in a VPC, routing outside of VPCs is not analyzed")
+}
+
+fn peering(src: &EndpointVpc, dst: &EndpointVpc, policy: &SitePolicy<'_>) -> (bool, String) {
+    if !src.peer_ids.contains(&dst.id) {
+        return (
+            false,
+            format!(
+                "there is no VPC peering between VPCs {} and {}",
+                src.id, dst.id
+            ),
+        );
+    }
+    match policy.vpc_peering_policy {
+        None | Some(VpcPeeringPolicy::None) => (
+            false,
+            format!(
+                "VPCs {} and {} are peered, but VPC peering is disabled on this site",
+                src.id, dst.id
+            ),
+        ),
+        Some(VpcPeeringPolicy::Exclusive)
+            if src.network_virtualization_type != dst.network_virtualization_type =>
+        {
+            (
+                false,
+                format!(
+                    "VPCs {} ({}) and {} ({}) are peered, but this site only allows peering VPCs of the same network virtualization type",
+                    src.id,
+                    src.network_virtualization_type,
+                    dst.id,
+                    dst.network_virtualization_type
+                ),
+            )
+        }
+        Some(_) => (true, format!("VPCs {} and {} are peered", src.id, dst.id)),
+    }
+}
+
+fn acl_step(
+    stage: rpc::ConnectivityStage,
+    interface: &EndpointInterface,
+    direction: NetworkSecurityGroupRuleDirection,
+    packet: &Packet<'_>,
+    policy: &SitePolicy<'_>,
+) -> rpc::ConnectivityStep {
+    let mut step = rpc::ConnectivityStep {
+        stage: stage.into(),
+        allowed: false,
+        reason: String::new(),
+        rule: None,
+        site_policy_override: false,
+        network_security_group_id: interface
+            .network_security_group
+            .as_ref()
+            .map(|(_, nsg)| nsg.id.to_string()),
+    };
+
+    if interface.quarantined {
+        step.site_policy_override = true;
+        step.reason = format!(
+            "the host of instance {} is quarantined",
+            interface.instance_id
+        );
+        return step;
+    }
+
+    // The override ACL comes first on the DPU. Traffic it permits still has to pass the NSG.
+    if let Some(rule) = first_match(policy.policy_overrides, &HashMap::new(), &direction, packet)
+        && rule.action == NetworkSecurityGroupRuleAction::Deny
+    {
+        step.site_policy_override = true;
+        step.reason = format!(
+            "denied by site policy override rule {}",
+            describe_rule(rule)
+        );
+        step.rule = rule.clone().try_into().ok();
+        return step;
+    }
+
+    let Some((source, nsg)) = &interface.network_security_group else {
+        step.allowed = true;
+        step.reason = format!(
+            "no network security group applies to instance {}",
+            interface.instance_id
+        );
+        return step;
+    };
+    let attached_to = match source {
+        NetworkSecurityGroupSource::Vpc => "its VPC",
+        _ => "the instance",
+    };
+
+    let is_reply = matches!(
+        stage,
+        rpc::ConnectivityStage::SourceIngressReply | rpc::ConnectivityStage::DestinationEgressReply
+    );
+    if let Some(rule) = first_match(&nsg.rules, &interface.prefix_lists, &direction, packet) {
+        step.allowed = rule.action == NetworkSecurityGroupRuleAction::Permit;
+        step.reason = format!(
+            "{} by rule {} of network security group {} attached to {attached_to}",
+            if step.allowed { "permitted" } else { "denied" },
+            describe_rule(rule),
+            nsg.id,
+        );
+        step.rule = rule.clone().try_into().ok();
+    } else if is_reply
+        && direction == NetworkSecurityGroupRuleDirection::Ingress
+        && policy.stateful_acls_enabled
+        && nsg.stateful_egress
+        && is_tracked_by_stateful_acls(packet)
+    {
+        step.allowed = true;
+        step.reason = format!(
+            "reply to traffic permitted by the stateful egress of network security group {} attached to {attached_to}",
+            nsg.id
+        );
+    } else {
+        step.reason = format!(
+            "no rule of network security group {} attached to {attached_to} matches, unmatched traffic is denied",
+            nsg.id
+        );
+    }
+    step
+}
+
+/// The DPU only permits established IPv4 TCP, UDP and ICMP connections ahead of the default deny
+fn is_tracked_by_stateful_acls(packet: &Packet<'_>) -> bool {
+    packet.src.is_ipv4()
+        && matches!(
+            packet.protocol,
+            NetworkSecurityGroupRuleProtocol::Tcp
+                | NetworkSecurityGroupRuleProtocol::Udp
+                | NetworkSecurityGroupRuleProtocol::Icmp
+        )
+}
+
+/// The rule the DPU would apply. Like forge-dpu-agent, ties in priority go to the earlier rule.
+fn first_match<'a>(
+    rules: &'a [NetworkSecurityGroupRule],
+    prefix_lists: &HashMap<PrefixListId, PrefixList>,
+    direction: &NetworkSecurityGroupRuleDirection,
+    packet: &Packet<'_>,
+) -> Option<&'a NetworkSecurityGroupRule> {
+    rules
+        .iter()
+        .filter(|rule| rule_matches(rule, prefix_lists, direction, packet))
+        .min_by_key(|rule| rule.priority)
+}
+
+fn rule_matches(
+    rule: &NetworkSecurityGroupRule,
+    prefix_lists: &HashMap<PrefixListId, PrefixList>,
+    direction: &NetworkSecurityGroupRuleDirection,
+    packet: &Packet<'_>,
+) -> bool {
+    // A prefix list is expanded to its prefixes for the IP version of the rule,
+    // just like the rules sent to the DPU.
+    let net_contains = |net: &NetworkSecurityGroupRuleNet, address: IpAddr| {
+        net.resolve(rule.ipv6, prefix_lists)
+            .is_ok_and(|prefixes| prefixes.iter().any(|prefix| prefix.contains(address)))
+    };
+    let port_matches = |start: Option<u32>, end: Option<u32>, port: Option<u32>| match (start, end)
+    {
+        (Some(start), Some(end)) => port.is_some_and(|port| (start..=end).contains(&port)),
+        _ => true,
+    };
+    rule.direction == *direction
+        && rule.ipv6 == packet.src.is_ipv6()
+        && net_contains(&rule.src_net, packet.src)
+        && net_contains(&rule.dst_net, packet.dst)
+        && (rule.protocol == NetworkSecurityGroupRuleProtocol::Any
+            || rule.protocol == *packet.protocol)
+        && port_matches(rule.src_port_start, rule.src_port_end, packet.src_port)
+        && port_matches(rule.dst_port_start, rule.dst_port_end, packet.dst_port)
+}
+
+fn describe_rule(rule: &NetworkSecurityGroupRule) -> String {
+    format!(
+        "{} (priority {})",
+        rule.id.as_deref().unwrap_or("<unnamed>"),
+        rule.priority
+    )
+}
+
+impl From<&Endpoint> for rpc::ResolvedConnectivityEndpoint {
+    fn from(endpoint: &Endpoint) -> Self {
+        let interface = endpoint.interface.as_ref();
+        let nsg = interface.and_then(|i| i.network_security_group.as_ref());
+        rpc::ResolvedConnectivityEndpoint {
+            ip_address: endpoint.address.to_string(),
+            instance_id: interface.map(|i| i.instance_id),
+            network_segment_id: interface.map(|i| i.segment_id),
+            vpc_id: interface.and_then(|i| i.vpc.as_ref()).map(|vpc| vpc.id),
+            network_security_group_id: nsg.map(|(_, nsg)| nsg.id.to_string()),
+            network_security_group_source: rpc::NetworkSecurityGroupSource::from(
+                nsg.map(|(source, _)| source.clone())
+                    .unwrap_or(NetworkSecurityGroupSource::None),
+            )
+            .into(),
+        }
+    }
+}
+
+/// Resolves an endpoint of a request to its addresses, IPv4 first.
+/// An instance interface can have both an IPv4 and an IPv6 address.
+pub async fn resolve_endpoint(
+    txn: &mut PgConnection,
+    endpoint: rpc::ConnectivityEndpoint,
+) -> Result<Vec<Endpoint>, CarbideError> {
+    use rpc::connectivity_endpoint::Endpoint as RequestedEndpoint;
+
+    let (instance, segment_id) = match endpoint
+        .endpoint
+        .ok_or(CarbideError::MissingArgument("endpoint"))?
+    {
+        RequestedEndpoint::InstanceId(instance_id) => {
+            let instance = find_instance(txn, instance_id).await?;
+            let segment_id = instance
+                .config
+                .network
+                .interfaces
+                .first()
+                .and_then(|iface| iface.network_segment_id)
+                .ok_or_else(|| {
+                    CarbideError::FailedPrecondition(format!(
+                        "instance {instance_id} has no network interfaces"
+                    ))
+                })?;
+            (instance, segment_id)
+        }
+        RequestedEndpoint::Interface(interface) => {
+            let instance_id = interface
+                .instance_id
+                .ok_or(CarbideError::MissingArgument("interface.instance_id"))?;
+            let segment_id = interface
+                .network_segment_id
+                .ok_or(CarbideError::MissingArgument(
+                    "interface.network_segment_id",
+                ))?;
+            (find_instance(txn, instance_id).await?, segment_id)
+        }
+        RequestedEndpoint::IpAddress(address) => {
+            let address: IpAddr = address.parse()?;
+            return match db::instance_address::find_by_address(&mut *txn, address).await? {
+                Some(instance_address) => Ok(vec![
+                    instance_address_endpoint(txn, instance_address).await?,
+                ]),
+                None => Ok(vec![Endpoint {
+                    address,
+                    interface: None,
+                }]),
+            };
+        }
+    };
+
+    let iface = instance
+        .config
+        .network
+        .interfaces
+        .iter()
+        .find(|iface| iface.network_segment_id == Some(segment_id))
+        .ok_or_else(|| CarbideError::NotFoundError {
+            kind: "instance interface",
+            id: format!("{}/{segment_id}", instance.id),
+        })?;
+    let mut addresses: Vec<IpAddr> = iface.ip_addrs.values().copied().collect();
+    if addresses.is_empty() {
+        return Err(CarbideError::FailedPrecondition(format!(
+            "the interface of instance {} on segment {segment_id} has no addresses",
+            instance.id
+        )));
+    }
+    addresses.sort_by_key(|address| (address.is_ipv6(), *address));
+
+    let interface = resolve_interface(txn, &instance, segment_id).await?;
+    Ok(addresses
+        .into_iter()
+        .map(|address| Endpoint {
+            address,
+            interface: Some(interface.clone()),
+        })
+        .collect())
+}
+
+/// The addresses of all instances in a VPC
+pub async fn vpc_instance_addresses(
+    txn: &mut PgConnection,
+    vpc_id: VpcId,
+) -> Result<Vec<InstanceAddress>, CarbideError> {
+    let segments = db::network_segment::find_by(
+        &mut *txn,
+        ObjectColumnFilter::One(network_segment::VpcColumn, &vpc_id),
+        NetworkSegmentSearchConfig::default(),
+    )
+    .await?;
+    let mut addresses = Vec::new();
+    for segment in segments {
+        addresses.extend(db::instance_address::find_by_segment_id(&mut *txn, &segment.id).await?);
+    }
+    Ok(addresses)
+}
+
+pub async fn instance_address_endpoint(
+    txn: &mut PgConnection,
+    instance_address: InstanceAddress,
+) -> Result<Endpoint, CarbideError> {
+    let instance = find_instance(txn, instance_address.instance_id).await?;
+    Ok(Endpoint {
+        address: instance_address.address,
+        interface: Some(resolve_interface(txn, &instance, instance_address.segment_id).await?),
+    })
+}
+
+async fn find_instance(
+    txn: &mut PgConnection,
+    instance_id: InstanceId,
+) -> Result<InstanceSnapshot, CarbideError> {
+    db::instance::find_by_id(&mut *txn, instance_id)
+        .await?
+        .ok_or_else(|| CarbideError::NotFoundError {
+            kind: "instance",
+            id: instance_id.to_string(),
+        })
+}
+
+async fn resolve_interface(
+    txn: &mut PgConnection,
+    instance: &InstanceSnapshot,
+    segment_id: NetworkSegmentId,
+) -> Result<EndpointInterface, CarbideError> {
+    let segment = db::network_segment::find_by(
+        &mut *txn,
+        ObjectColumnFilter::One(network_segment::IdColumn, &segment_id),
+        NetworkSegmentSearchConfig::default(),
+    )
+    .await?
+    .pop()
+    .ok_or_else(|| CarbideError::NotFoundError {
+        kind: "network_segment",
+        id: segment_id.to_string(),
+    })?;
+
+    let vpc = match segment.vpc_id {
+        Some(vpc_id) => Some(
+            db::vpc::find_by(&mut *txn, ObjectColumnFilter::One(vpc::IdColumn, &vpc_id))
+                .await?
+                .pop()
+                .ok_or(CarbideError::FindOneReturnedNoResultsError(vpc_id.into()))?,
+        ),
+        None => None,
+    };
+
+    // Like the network config sent to the DPU, the NSG of the instance takes precedence over
+    // the one of its VPC.
+    let network_security_group = match (&instance.config.network_security_group_id, &vpc) {
+        (Some(nsg_id), _) => Some((
+            NetworkSecurityGroupSource::Instance,
+            find_network_security_group(
+                txn,
+                nsg_id,
+                &instance.config.tenant.tenant_organization_id,
+            )
+            .await?,
+        )),
+        (None, Some(vpc)) => match &vpc.network_security_group_id {
+            Some(nsg_id) => {
+                let tenant_organization_id =
+                    vpc.tenant_organization_id
+                        .parse()
+                        .map_err(|_| CarbideError::Internal {
+                            message: "invalid tenant org in VPC data".to_string(),
+                        })?;
+                Some((
+                    NetworkSecurityGroupSource::Vpc,
+                    find_network_security_group(txn, nsg_id, &tenant_organization_id).await?,
+                ))
+            }
+            None => None,
+        },
+        (None, None) => None,
+    };
+
+    let prefix_lists = match &network_security_group {
+        Some((_, nsg)) => {
+            let prefix_list_ids = referenced_prefix_list_ids(&nsg.rules);
+            model::prefix_list::by_id(
+                db::prefix_list::find_by_ids(
+                    &mut *txn,
+                    &prefix_list_ids,
+                    Some(&nsg.tenant_organization_id),
+                    false,
+                )
+                .await?,
+            )
+        }
+        None => HashMap::new(),
+    };
+
+    let vpc = match vpc {
+        Some(vpc) => Some(EndpointVpc {
+            id: vpc.id,
+            network_virtualization_type: vpc.network_virtualization_type,
+            peer_ids: db::vpc_peering::get_vpc_peer_ids(txn, vpc.id).await?,
+        }),
+        None => None,
+    };
+
+    Ok(EndpointInterface {
+        instance_id: instance.id,
+        segment_id,
+        vpc,
+        network_security_group,
+        prefix_lists,
+        quarantined: db::machine::get_quarantine_state(&mut *txn, &instance.machine_id)
+            .await?
+            .is_some(),
+    })
+}
+
+async fn find_network_security_group(
+    txn: &mut PgConnection,
+    network_security_group_id: &NetworkSecurityGroupId,
+    tenant_organization_id: &TenantOrganizationId,
+) -> Result<NetworkSecurityGroup, CarbideError> {
+    network_security_group::find_by_ids(
+        txn,
+        std::slice::from_ref(network_security_group_id),
+        Some(tenant_organization_id),
+        false,
+    )
+    .await?
+    .pop()
+    .ok_or_else(|| CarbideError::NotFoundError {
+        kind: "NetworkSecurityGroup",
+        id: network_security_group_id.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(
+        id: &str,
+        direction: NetworkSecurityGroupRuleDirection,
+        protocol: NetworkSecurityGroupRuleProtocol,
+        dst_ports: Option<(u32, u32)>,
+        action: NetworkSecurityGroupRuleAction,
+        priority: u32,
+    ) -> NetworkSecurityGroupRule {
+        NetworkSecurityGroupRule {
+            id: Some(id.to_string()),
+            src_net: NetworkSecurityGroupRuleNet::Prefix("0.0.0.0/0".parse().unwrap()),
+            dst_net: NetworkSecurityGroupRuleNet::Prefix("0.0.0.0/0".parse().unwrap()),
+            direction,
+            ipv6: false,
+            src_port_start: None,
+            src_port_end: None,
+            dst_port_start: dst_ports.map(|(start, _)| start),
+            dst_port_end: dst_ports.map(|(_, end)| end),
+            protocol,
+            action,
+            priority,
+        }
+    }
+
+    fn nsg(
+        id: &str,
+        stateful_egress: bool,
+        rules: Vec<NetworkSecurityGroupRule>,
+    ) -> NetworkSecurityGroup {
+        NetworkSecurityGroup {
+            id: id.parse().unwrap(),
+            tenant_organization_id: "Tenant1".parse().unwrap(),
+            stateful_egress,
+            rules,
+            version: config_version::ConfigVersion::initial(),
+            created: chrono::Utc::now(),
+            deleted: None,
+            metadata: Default::default(),
+            created_by: None,
+            updated_by: None,
+        }
+    }
+
+    fn endpoint(
+        address: &str,
+        vpc: &EndpointVpc,
+        network_security_group: Option<NetworkSecurityGroup>,
+    ) -> Endpoint {
+        Endpoint {
+            address: address.parse().unwrap(),
+            interface: Some(EndpointInterface {
+                instance_id: uuid::Uuid::new_v4().into(),
+                segment_id: uuid::Uuid::new_v4().into(),
+                vpc: Some(vpc.clone()),
+                network_security_group: network_security_group
+                    .map(|nsg| (NetworkSecurityGroupSource::Instance, nsg)),
+                prefix_lists: HashMap::new(),
+                quarantined: false,
+            }),
+        }
+    }
+
+    fn vpc(peer_ids: Vec<VpcId>) -> EndpointVpc {
+        EndpointVpc {
+            id: uuid::Uuid::new_v4().into(),
+            network_virtualization_type: VpcVirtualizationType::EthernetVirtualizer,
+            peer_ids,
+        }
+    }
+
+    fn tcp(dst_port: u32) -> Flow {
+        Flow {
+            protocol: NetworkSecurityGroupRuleProtocol::Tcp,
+            src_port: Some(40000),
+            dst_port: Some(dst_port),
+        }
+    }
+
+    const POLICY: SitePolicy<'static> = SitePolicy {
+        vpc_peering_policy: Some(VpcPeeringPolicy::Exclusive),
+        stateful_acls_enabled: true,
+        policy_overrides: &[],
+    };
+
+    fn step(
+        steps: &[rpc::ConnectivityStep],
+        stage: rpc::ConnectivityStage,
+    ) -> &rpc::ConnectivityStep {
+        steps.iter().find(|s| s.stage() == stage).unwrap()
+    }
+
+    #[test]
+    fn test_same_vpc_without_nsgs_is_allowed() {
+        let vpc = vpc(vec![]);
+        let steps = analyze(
+            &endpoint("10.0.0.1", &vpc, None),
+            &endpoint("10.0.0.2", &vpc, None),
+            &tcp(22),
+            &POLICY,
+        );
+        assert_eq!(steps.len(), 5);
+        assert!(steps.iter().all(|s| s.allowed), "{steps:?}");
+    }
+
+    #[test]
+    fn test_unpeered_vpcs_are_not_routed() {
+        let steps = analyze(
+            &endpoint("10.0.0.1", &vpc(vec![]), None),
+            &endpoint("10.1.0.1", &vpc(vec![]), None),
+            &tcp(22),
+            &POLICY,
+        );
+        let routing = step(&steps, rpc::ConnectivityStage::Routing);
+        assert!(!routing.allowed);
+        assert!(
+            routing.reason.contains("no VPC peering"),
+            "{}",
+            routing.reason
+        );
+    }
+
+    #[test]
+    fn test_peering_requires_site_policy() {
+        let dst_vpc = vpc(vec![]);
+        let src_vpc = vpc(vec![dst_vpc.id]);
+        let src = endpoint("10.0.0.1", &src_vpc, None);
+        let dst = endpoint("10.1.0.1", &dst_vpc, None);
+
+        let steps = analyze(&src, &dst, &tcp(22), &POLICY);
+        assert!(step(&steps, rpc::ConnectivityStage::Routing).allowed);
+
+        let disabled = SitePolicy {
+            vpc_peering_policy: None,
+            ..POLICY
+        };
+        let steps = analyze(&src, &dst, &tcp(22), &disabled);
+        let routing = step(&steps, rpc::ConnectivityStage::Routing);
+        assert!(!routing.allowed);
+        assert!(routing.reason.contains("disabled"), "{}", routing.reason);
+    }
+
+    #[test]
+    fn test_lowest_priority_rule_decides() {
+        let vpc = vpc(vec![]);
+        let dst_nsg = nsg(
+            "dst-nsg",
+            false,
+            vec![
+                rule(
+                    "allow-ssh",
+                    NetworkSecurityGroupRuleDirection::Ingress,
+                    NetworkSecurityGroupRuleProtocol::Tcp,
+                    Some((22, 22)),
+                    NetworkSecurityGroupRuleAction::Permit,
+                    20,
+                ),
+                rule(
+                    "deny-tcp",
+                    NetworkSecurityGroupRuleDirection::Ingress,
+                    NetworkSecurityGroupRuleProtocol::Tcp,
+                    None,
+                    NetworkSecurityGroupRuleAction::Deny,
+                    10,
+                ),
+            ],
+        );
+        let steps = analyze(
+            &endpoint("10.0.0.1", &vpc, None),
+            &endpoint("10.0.0.2", &vpc, Some(dst_nsg)),
+            &tcp(22),
+            &POLICY,
+        );
+        let ingress = step(&steps, rpc::ConnectivityStage::DestinationIngress);
+        assert!(!ingress.allowed);
+        assert_eq!(
+            ingress.rule.as_ref().unwrap().id.as_deref(),
+            Some("deny-tcp")
+        );
+        assert_eq!(
+            ingress.network_security_group_id.as_deref(),
+            Some("dst-nsg")
+        );
+    }
+
+    #[test]
+    fn test_prefix_list_references_are_expanded() {
+        let vpc = vpc(vec![]);
+        let prefix_list = PrefixList {
+            id: uuid::Uuid::new_v4().into(),
+            tenant_organization_id: "Tenant1".parse().unwrap(),
+            prefixes: vec![
+                "10.0.0.0/30".parse().unwrap(),
+                "2001:db8::/32".parse().unwrap(),
+            ],
+            version: config_version::ConfigVersion::initial(),
+            created: chrono::Utc::now(),
+            deleted: None,
+            metadata: Default::default(),
+            created_by: None,
+            updated_by: None,
+        };
+        let dst_nsg = nsg(
+            "dst-nsg",
+            false,
+            vec![NetworkSecurityGroupRule {
+                src_net: NetworkSecurityGroupRuleNet::PrefixList(prefix_list.id),
+                ..rule(
+                    "allow-listed",
+                    NetworkSecurityGroupRuleDirection::Ingress,
+                    NetworkSecurityGroupRuleProtocol::Tcp,
+                    None,
+                    NetworkSecurityGroupRuleAction::Permit,
+                    10,
+                )
+            }],
+        );
+        let mut dst = endpoint("10.0.0.9", &vpc, Some(dst_nsg));
+        dst.interface.as_mut().unwrap().prefix_lists = model::prefix_list::by_id(vec![prefix_list]);
+
+        let steps = analyze(&endpoint("10.0.0.1", &vpc, None), &dst, &tcp(22), &POLICY);
+        let ingress = step(&steps, rpc::ConnectivityStage::DestinationIngress);
+        assert!(ingress.allowed, "{}", ingress.reason);
+
+        let steps = analyze(&endpoint("10.0.0.5", &vpc, None), &dst, &tcp(22), &POLICY);
+        let ingress = step(&steps, rpc::ConnectivityStage::DestinationIngress);
+        assert!(!ingress.allowed, "{}", ingress.reason);
+    }
+
+    #[test]
+    fn test_unmatched_traffic_is_denied_once_an_nsg_applies() {
+        let vpc = vpc(vec![]);
+        let dst_nsg = nsg(
+            "dst-nsg",
+            false,
+            vec![rule(
+                "allow-https",
+                NetworkSecurityGroupRuleDirection::Ingress,
+                NetworkSecurityGroupRuleProtocol::Tcp,
+                Some((443, 443)),
+                NetworkSecurityGroupRuleAction::Permit,
+                10,
+            )],
+        );
+        let steps = analyze(
+            &endpoint("10.0.0.1", &vpc, None),
+            &endpoint("10.0.0.2", &vpc, Some(dst_nsg)),
+            &tcp(22),
+            &POLICY,
+        );
+        let ingress = step(&steps, rpc::ConnectivityStage::DestinationIngress);
+        assert!(!ingress.allowed);
+        assert!(ingress.rule.is_none());
+        assert!(ingress.reason.contains("unmatched"), "{}", ingress.reason);
+    }
+
+    #[test]
+    fn test_stateful_egress_permits_replies() {
+        let vpc = vpc(vec![]);
+        let allow_egress = rule(
+            "allow-egress",
+            NetworkSecurityGroupRuleDirection::Egress,
+            NetworkSecurityGroupRuleProtocol::Any,
+            None,
+            NetworkSecurityGroupRuleAction::Permit,
+            10,
+        );
+        let src = endpoint(
+            "10.0.0.1",
+            &vpc,
+            Some(nsg("src-nsg", true, vec![allow_egress.clone()])),
+        );
+        let dst = endpoint("10.0.0.2", &vpc, None);
+
+        let steps = analyze(&src, &dst, &tcp(22), &POLICY);
+        let reply = step(&steps, rpc::ConnectivityStage::SourceIngressReply);
+        assert!(reply.allowed);
+        assert!(reply.reason.contains("stateful"), "{}", reply.reason);
+
+        // Without stateful egress the replies need an ingress rule
+        let src = endpoint(
+            "10.0.0.1",
+            &vpc,
+            Some(nsg("src-nsg", false, vec![allow_egress])),
+        );
+        let steps = analyze(&src, &dst, &tcp(22), &POLICY);
+        assert!(step(&steps, rpc::ConnectivityStage::SourceEgress).allowed);
+        assert!(!step(&steps, rpc::ConnectivityStage::SourceIngressReply).allowed);
+    }
+
+    #[test]
+    fn test_site_policy_override_denies_before_nsg() {
+        let vpc = vpc(vec![]);
+        let overrides = [rule(
+            "block-smtp",
+            NetworkSecurityGroupRuleDirection::Egress,
+            NetworkSecurityGroupRuleProtocol::Tcp,
+            Some((25, 25)),
+            NetworkSecurityGroupRuleAction::Deny,
+            1,
+        )];
+        let policy = SitePolicy {
+            policy_overrides: &overrides,
+            ..POLICY
+        };
+        let steps = analyze(
+            &endpoint("10.0.0.1", &vpc, None),
+            &endpoint("10.0.0.2", &vpc, None),
+            &tcp(25),
+            &policy,
+        );
+        let egress = step(&steps, rpc::ConnectivityStage::SourceEgress);
+        assert!(!egress.allowed);
+        assert!(egress.site_policy_override);
+        assert_eq!(
+            egress.rule.as_ref().unwrap().id.as_deref(),
+            Some("block-smtp")
+        );
+    }
+
+    #[test]
+    fn test_flow_validation() {
+        let flow = |protocol: rpc::NetworkSecurityGroupRuleProtocol, dst_port| {
+            Flow::try_from(rpc::ConnectivityFlow {
+                protocol: protocol.into(),
+                src_port: None,
+                dst_port,
+            })
+        };
+        assert!(
+            flow(
+                rpc::NetworkSecurityGroupRuleProtocol::NsgRuleProtoTcp,
+                Some(22)
+            )
+            .is_ok()
+        );
+        assert!(flow(rpc::NetworkSecurityGroupRuleProtocol::NsgRuleProtoAny, None).is_err());
+        assert!(
+            flow(
+                rpc::NetworkSecurityGroupRuleProtocol::NsgRuleProtoIcmp,
+                Some(22)
+            )
+            .is_err()
+        );
+        assert!(
+            flow(
+                rpc::NetworkSecurityGroupRuleProtocol::NsgRuleProtoUdp,
+                Some(70000)
+            )
+            .is_err()
+        );
+
+        let icmp = flow(
+            rpc::NetworkSecurityGroupRuleProtocol::NsgRuleProtoIcmp,
+            None,
+        )
+        .unwrap();
+        assert!(icmp.check_ip_version("10.0.0.1".parse().unwrap()).is_ok());
+        assert!(icmp.check_ip_version("fd00::1".parse().unwrap()).is_err());
+    }
+}
@@ -14,12 +14,14 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use std::collections::HashMap;
 use std::net::IpAddr;
 
 use ::rpc::forge as rpc;
 use carbide_network::virtualization::{VpcVirtualizationType, get_svi_ip};
 use carbide_uuid::instance::InstanceId;
 use carbide_uuid::machine::{MachineId, MachineInterfaceId};
+use carbide_uuid::prefix_list::PrefixListId;
 use db::vpc::{self};
 use db::vpc_peering::get_prefixes_by_vpcs;
 use db::{self, ObjectColumnFilter, network_security_group};
@@ -28,8 +30,10 @@ use model::instance::config::network::{InstanceInterfaceConfig, InterfaceFunctio
 use model::network_prefix::NetworkPrefix;
 use model::network_security_group::{
     NetworkSecurityGroup, NetworkSecurityGroupRule, NetworkSecurityGroupRuleNet,
+    referenced_prefix_list_ids,
 };
 use model::network_segment::NetworkSegment;
+use model::prefix_list::PrefixList;
 use model::resource_pool::common::CommonPools;
 use sqlx::PgConnection;
 
@@ -533,6 +537,23 @@ pub async fn tenant_network(
         _ => None,
     };
 
+    // Rules referencing prefix lists are expanded to the current
+    // prefixes of the lists.
+    let prefix_lists = match network_security_group_details.as_ref() {
+        Some((_, nsg)) if !referenced_prefix_list_ids(&nsg.rules).is_empty() => {
+            model::prefix_list::by_id(
+                db::prefix_list::find_by_ids(
+                    txn,
+                    &referenced_prefix_list_ids(&nsg.rules),
+                    Some(&nsg.tenant_organization_id),
+                    false,
+                )
+                .await?,
+            )
+        }
+        _ => HashMap::new(),
+    };
+
     Ok(rpc::FlatInterfaceConfig {
         function_type: rpc_ft.into(),
         virtual_function_id: match iface.function_id {
@@ -570,7 +591,7 @@ pub async fn tenant_network(
                             rules:
                                 nsg.rules
                                     .into_iter()
-                                    .map(resolve_security_group_rule)
+                                    .map(|r| resolve_security_group_rule(r, &prefix_lists))
                                     .collect::<Result<
                                         Vec<rpc::ResolvedNetworkSecurityGroupRule>,
                                         CarbideError,
@@ -598,21 +619,23 @@ pub async fn tenant_network(
 
 pub fn resolve_security_group_rule(
     rule: NetworkSecurityGroupRule,
+    prefix_lists: &HashMap<PrefixListId, PrefixList>,
 ) -> Result<rpc::ResolvedNetworkSecurityGroupRule, CarbideError> {
+    let resolve = |net: &NetworkSecurityGroupRuleNet| -> Result<Vec<String>, CarbideError> {
+        net.resolve(rule.ipv6, prefix_lists)
+            .map(|prefixes| prefixes.iter().map(|p| p.to_string()).collect())
+            .map_err(|id| CarbideError::NotFoundError {
+                kind: "PrefixList",
+                id: id.to_string(),
+            })
+    };
+
     Ok(rpc::ResolvedNetworkSecurityGroupRule {
-        // When we decide to allow object references,
-        // they would be resolved to their actual prefix
-        // lists and stored here.
-        src_prefixes: match rule.src_net {
-            NetworkSecurityGroupRuleNet::Prefix(ref p) => {
-                vec![p.to_string()]
-            }
-        },
-        dst_prefixes: match rule.dst_net {
-            NetworkSecurityGroupRuleNet::Prefix(ref p) => {
-                vec![p.to_string()]
-            }
-        },
+        // References to prefix lists are resolved to
+        // the prefixes of the list that match the IP
+        // version of the rule.
+        src_prefixes: resolve(&rule.src_net)?,
+        dst_prefixes: resolve(&rule.dst_net)?,
         rule: Some(rule.try_into()?),
     })
 }
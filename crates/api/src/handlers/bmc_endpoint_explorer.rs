@@ -521,7 +521,7 @@ async fn ipmitool_reset_bmc(
     Ok(Response::new(()))
 }
 
-async fn redfish_power_control(
+pub(crate) async fn redfish_power_control(
     api: &Api,
     request: rpc::BmcEndpointRequest,
     action: libredfish::SystemPowerControl,
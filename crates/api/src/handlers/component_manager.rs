@@ -35,9 +35,6 @@ use tonic::{Code, Request, Response, Status};
 
 use crate::api::{Api, log_request_data};
 
-const MACHINE_POWER_OVERRIDE_SOURCE: &str = "component_power_control";
-const MACHINE_POWER_OVERRIDE_MESSAGE: &str = "Compute-Tray component power control in progress";
-
 fn require_component_manager(api: &Api) -> Result<&ComponentManager, Status> {
     api.component_manager
         .as_ref()
@@ -623,139 +620,24 @@ async fn compute_power_control(
     bmc_endpoint: BmcEndpointRequest,
     action: PowerAction,
 ) -> Result<(), Status> {
-    let override_inserted = power_control_health_override(api, machine_id, true).await;
-
-    let result = machine_power_control(api, machine_id, bmc_endpoint, action).await;
-
-    if override_inserted {
-        power_control_health_override(api, machine_id, false).await;
-    }
-
-    result
-}
-
-/// Best-effort insert or removal of the health report override used to
-/// suppress external alerting during compute power control.
-/// Returns `true` when the operation succeeded.
-async fn power_control_health_override(
-    api: &Api,
-    machine_id: carbide_uuid::machine::MachineId,
-    insert: bool,
-) -> bool {
-    let result = if insert {
-        let req = rpc::InsertMachineHealthReportRequest {
-            machine_id: Some(machine_id),
-            health_report_entry: Some(rpc::HealthReportEntry {
-                report: Some(::rpc::health::HealthReport {
-                    source: MACHINE_POWER_OVERRIDE_SOURCE.to_string(),
-                    triggered_by: None,
-                    observed_at: None,
-                    successes: vec![],
-                    alerts: vec![::rpc::health::HealthProbeAlert {
-                        id: health_report::HealthProbeId::internal_maintenance().to_string(),
-                        target: None,
-                        in_alert_since: None,
-                        message: MACHINE_POWER_OVERRIDE_MESSAGE.to_string(),
-                        tenant_message: None,
-                        classifications: vec![
-                            health_report::HealthAlertClassification::suppress_external_alerting()
-                                .to_string(),
-                        ],
-                    }],
-                }),
-                mode: rpc::HealthReportApplyMode::Replace as i32,
-            }),
-        };
-        crate::handlers::health::insert_machine_health_report(api, Request::new(req))
-            .await
-            .map(|_| ())
-    } else {
-        let req = rpc::RemoveMachineHealthReportRequest {
-            machine_id: Some(machine_id),
-            source: MACHINE_POWER_OVERRIDE_SOURCE.to_string(),
-        };
-        crate::handlers::health::remove_machine_health_report(api, Request::new(req))
+    if machine_id.machine_type().is_dpu() {
+        return Err(Status::invalid_argument("Only host id is expected"));
+    }
+    crate::power_control::machine_power_control(
+        &api.database_connection,
+        machine_id,
+        action,
+        |redfish_action| async move {
+            crate::handlers::bmc_endpoint_explorer::redfish_power_control(
+                api,
+                bmc_endpoint,
+                redfish_action,
+            )
             .await
             .map(|_| ())
-    };
-
-    if let Err(e) = &result {
-        let action = if insert { "insert" } else { "remove" };
-        tracing::warn!(
-            %machine_id,
-            error = %e,
-            "failed to {action} health report override for power control"
-        );
-    }
-
-    result.is_ok()
-}
-
-fn desired_power_state(action: PowerAction) -> rpc::PowerState {
-    match action {
-        PowerAction::On
-        | PowerAction::ForceRestart
-        | PowerAction::GracefulRestart
-        | PowerAction::AcPowercycle => rpc::PowerState::On,
-        PowerAction::GracefulShutdown | PowerAction::ForceOff => rpc::PowerState::Off,
-    }
-}
-
-fn redfish_power_action(
-    action: PowerAction,
-) -> rpc::admin_power_control_request::SystemPowerControl {
-    use rpc::admin_power_control_request::SystemPowerControl;
-    match action {
-        PowerAction::On => SystemPowerControl::On,
-        PowerAction::ForceRestart => SystemPowerControl::ForceRestart,
-        PowerAction::GracefulRestart => SystemPowerControl::GracefulRestart,
-        PowerAction::AcPowercycle => SystemPowerControl::AcPowercycle,
-        PowerAction::GracefulShutdown => SystemPowerControl::GracefulShutdown,
-        PowerAction::ForceOff => SystemPowerControl::ForceOff,
-    }
-}
-
-/*
-machine_power_control facilitates power control against compute trays:
-    1. Configures the desired power state for the machine in the power-manager
-    2. Synchronously sends the redfish command to the compute tray's BMC to perform the action specified
-On success, the power manager will have the desired power state set for the machine and the redfish command will have been successfully sent to the compute BMC
-In the case of partial failure, the power manager may have the desired power state updated for the machine but the redfish command may have failed. We will leave reconvergence in this case to the power manager.
-*/
-async fn machine_power_control(
-    api: &Api,
-    machine_id: carbide_uuid::machine::MachineId,
-    bmc_endpoint: BmcEndpointRequest,
-    action: PowerAction,
-) -> Result<(), Status> {
-    let desired_power_state = desired_power_state(action) as i32;
-    let redfish_action = redfish_power_action(action);
-
-    let power_req = rpc::PowerOptionUpdateRequest {
-        machine_id: Some(machine_id),
-        power_state: desired_power_state,
-    };
-    match crate::handlers::power_options::update_power_option(api, Request::new(power_req)).await {
-        Ok(_) => {}
-        Err(e) if e.code() == Code::InvalidArgument && e.message().contains("already set as") => {
-            tracing::debug!(
-                %machine_id,
-                desired_power_state,
-                "power option already in desired state, skipping"
-            );
-        }
-        Err(e) => return Err(e),
-    }
-
-    let admin_req = rpc::AdminPowerControlRequest {
-        bmc_endpoint_request: Some(bmc_endpoint),
-        machine_id: Some(machine_id.to_string()),
-        action: redfish_action as i32,
-    };
-    crate::handlers::bmc_endpoint_explorer::admin_power_control(api, Request::new(admin_req))
-        .await?;
-
-    Ok(())
+        },
+    )
+    .await
 }
 
 /// Best-effort: flag BMC/PMC endpoints for re-exploration so the site
@@ -1864,69 +1746,4 @@ mod tests {
         );
         assert!(r.error.contains("could not resolve endpoint"));
     }
-
-    #[test]
-    fn desired_power_state_on_variants() {
-        use super::desired_power_state;
-        assert_eq!(
-            desired_power_state(PowerAction::On),
-            self::rpc::PowerState::On
-        );
-        assert_eq!(
-            desired_power_state(PowerAction::ForceRestart),
-            self::rpc::PowerState::On
-        );
-        assert_eq!(
-            desired_power_state(PowerAction::GracefulRestart),
-            self::rpc::PowerState::On
-        );
-        assert_eq!(
-            desired_power_state(PowerAction::AcPowercycle),
-            self::rpc::PowerState::On
-        );
-    }
-
-    #[test]
-    fn desired_power_state_off_variants() {
-        use super::desired_power_state;
-        assert_eq!(
-            desired_power_state(PowerAction::GracefulShutdown),
-            self::rpc::PowerState::Off
-        );
-        assert_eq!(
-            desired_power_state(PowerAction::ForceOff),
-            self::rpc::PowerState::Off
-        );
-    }
-
-    #[test]
-    fn redfish_power_action_mapping() {
-        use self::rpc::admin_power_control_request::SystemPowerControl;
-        use super::redfish_power_action;
-
-        assert_eq!(
-            redfish_power_action(PowerAction::On),
-            SystemPowerControl::On
-        );
-        assert_eq!(
-            redfish_power_action(PowerAction::ForceRestart),
-            SystemPowerControl::ForceRestart
-        );
-        assert_eq!(
-            redfish_power_action(PowerAction::GracefulRestart),
-            SystemPowerControl::GracefulRestart
-        );
-        assert_eq!(
-            redfish_power_action(PowerAction::AcPowercycle),
-            SystemPowerControl::AcPowercycle
-        );
-        assert_eq!(
-            redfish_power_action(PowerAction::GracefulShutdown),
-            SystemPowerControl::GracefulShutdown
-        );
-        assert_eq!(
-            redfish_power_action(PowerAction::ForceOff),
-            SystemPowerControl::ForceOff
-        );
-    }
 }
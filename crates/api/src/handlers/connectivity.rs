@@ -0,0 +1,167 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::forge as rpc;
+use db::{ObjectColumnFilter, vpc};
+use tonic::{Request, Response, Status};
+
+use crate::CarbideError;
+use crate::api::{Api, log_request_data};
+use crate::connectivity::{self, Flow, SitePolicy};
+
+/// Every ordered pair of endpoints is analyzed, so keep the matrix to a size that can be
+/// computed within one request.
+const MAX_REACHABILITY_MATRIX_ENDPOINTS: usize = 128;
+
+fn site_policy(api: &Api) -> SitePolicy<'_> {
+    SitePolicy {
+        // The same policy that decides which peer prefixes are sent to the DPUs
+        vpc_peering_policy: api
+            .runtime_config
+            .vpc_peering_policy_on_existing
+            .or(api.runtime_config.vpc_peering_policy),
+        stateful_acls_enabled: api
+            .runtime_config
+            .network_security_group
+            .stateful_acls_enabled,
+        policy_overrides: &api.runtime_config.network_security_group.policy_overrides,
+    }
+}
+
+pub(crate) async fn analyze(
+    api: &Api,
+    request: Request<rpc::AnalyzeConnectivityRequest>,
+) -> Result<Response<rpc::AnalyzeConnectivityResponse>, Status> {
+    log_request_data(&request);
+
+    let request = request.into_inner();
+    let flow = Flow::try_from(request.flow.ok_or(CarbideError::MissingArgument("flow"))?)?;
+
+    let mut txn = api.txn_begin().await?;
+    let sources = connectivity::resolve_endpoint(
+        &mut txn,
+        request
+            .source
+            .ok_or(CarbideError::MissingArgument("source"))?,
+    )
+    .await?;
+    let destinations = connectivity::resolve_endpoint(
+        &mut txn,
+        request
+            .destination
+            .ok_or(CarbideError::MissingArgument("destination"))?,
+    )
+    .await?;
+    txn.commit().await?;
+
+    // Addresses are sorted IPv4 first, so dual-stack interfaces are analyzed over IPv4
+    let (source, destination) = sources
+        .iter()
+        .flat_map(|source| destinations.iter().map(move |dst| (source, dst)))
+        .find(|(source, dst)| source.address.is_ipv6() == dst.address.is_ipv6())
+        .ok_or_else(|| {
+            CarbideError::InvalidArgument(
+                "source and destination have no addresses of the same IP version".to_string(),
+            )
+        })?;
+    flow.check_ip_version(source.address)?;
+
+    let steps = connectivity::analyze(source, destination, &flow, &site_policy(api));
+
+    Ok(Response::new(rpc::AnalyzeConnectivityResponse {
+        allowed: steps.iter().all(|step| step.allowed),
+        source: Some(source.into()),
+        destination: Some(destination.into()),
+        steps,
+    }))
+}
+
+pub(crate) async fn reachability_matrix(
+    api: &Api,
+    request: Request<rpc::GetVpcReachabilityMatrixRequest>,
+) -> Result<Response<rpc::GetVpcReachabilityMatrixResponse>, Status> {
+    log_request_data(&request);
+
+    let request = request.into_inner();
+    let vpc_id = request
+        .vpc_id
+        .ok_or(CarbideError::MissingArgument("vpc_id"))?;
+    let flow = Flow::try_from(request.flow.ok_or(CarbideError::MissingArgument("flow"))?)?;
+
+    let mut txn = api.txn_begin().await?;
+    if db::vpc::find_by(&mut txn, ObjectColumnFilter::One(vpc::IdColumn, &vpc_id))
+        .await?
+        .is_empty()
+    {
+        return Err(CarbideError::NotFoundError {
+            kind: "vpc",
+            id: vpc_id.to_string(),
+        }
+        .into());
+    }
+
+    let mut vpc_ids = vec![vpc_id];
+    if request.include_peered_vpcs {
+        vpc_ids.extend(db::vpc_peering::get_vpc_peer_ids(&mut txn, vpc_id).await?);
+    }
+    let mut instance_addresses = Vec::new();
+    for vpc_id in vpc_ids {
+        instance_addresses.extend(connectivity::vpc_instance_addresses(&mut txn, vpc_id).await?);
+    }
+    if instance_addresses.len() > MAX_REACHABILITY_MATRIX_ENDPOINTS {
+        return Err(CarbideError::InvalidArgument(format!(
+            "the matrix would have {} instance addresses, more than the maximum of {MAX_REACHABILITY_MATRIX_ENDPOINTS}; use AnalyzeConnectivity for individual pairs",
+            instance_addresses.len()
+        ))
+        .into());
+    }
+
+    let mut endpoints = Vec::with_capacity(instance_addresses.len());
+    for instance_address in instance_addresses {
+        endpoints.push(connectivity::instance_address_endpoint(&mut txn, instance_address).await?);
+    }
+    txn.commit().await?;
+
+    let policy = site_policy(api);
+    let mut entries = Vec::new();
+    for (source_index, source) in endpoints.iter().enumerate() {
+        if flow.check_ip_version(source.address).is_err() {
+            continue;
+        }
+        for (destination_index, destination) in endpoints.iter().enumerate() {
+            if source_index == destination_index
+                || source.address.is_ipv6() != destination.address.is_ipv6()
+            {
+                continue;
+            }
+            let denied_by = connectivity::analyze(source, destination, &flow, &policy)
+                .into_iter()
+                .find(|step| !step.allowed);
+            entries.push(rpc::ReachabilityMatrixEntry {
+                source: source_index as u32,
+                destination: destination_index as u32,
+                allowed: denied_by.is_none(),
+                denied_by,
+            });
+        }
+    }
+
+    Ok(Response::new(rpc::GetVpcReachabilityMatrixResponse {
+        endpoints: endpoints.iter().map(Into::into).collect(),
+        entries,
+    }))
+}
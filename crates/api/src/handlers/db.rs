@@ -16,9 +16,13 @@
  */
 
 use ::rpc::forge as rpc;
+use chrono::Utc;
+use model::retention::{RetentionPolicy, RetentionTarget};
 use tonic::{Request, Response, Status};
 
 use crate::api::{Api, log_request_data};
+use crate::retention::RetentionManager;
+use crate::{CarbideError, retention};
 
 pub(crate) async fn trim_table(
     api: &Api,
@@ -26,15 +30,53 @@ pub(crate) async fn trim_table(
 ) -> Result<Response<rpc::TrimTableResponse>, Status> {
     log_request_data(&request);
 
-    let mut txn = api.txn_begin().await?;
+    let request = request.into_inner();
+    let target: RetentionTarget = request.target().into();
+    let max_age = request
+        .max_age
+        .map(chrono::TimeDelta::try_from)
+        .transpose()
+        .map_err(|e| CarbideError::InvalidArgument(format!("max_age: {e}")))?;
+    let policy = RetentionPolicy {
+        // Clients which predate `max_age` never set it, and send keep_entries=0 as an
+        // absent field. Keep their meaning of "keep 0 entries per object".
+        max_rows_per_object: match (request.keep_entries, max_age) {
+            (None, None) => Some(0),
+            (keep_entries, _) => keep_entries,
+        },
+        max_age,
+        keep_last: request.keep_last,
+    };
 
-    let target: model::trim_table::TrimTableTarget = request.get_ref().target().into();
-    let total_deleted =
-        db::trim_table::trim_table(&mut txn, target, request.get_ref().keep_entries).await?;
+    // Take the same lock as the periodic trim, so both never run at once
+    let _lock = api
+        .work_lock_manager_handle
+        .try_acquire_lock(RetentionManager::ITERATION_WORK_KEY.into())
+        .await
+        .map_err(|e| {
+            Status::unavailable(format!("Failed to acquire the retention work lock: {e}"))
+        })?;
 
-    txn.commit().await?;
+    let retention_config = &api.runtime_config.retention;
+    let outcome = retention::trim(
+        &api.database_connection,
+        target,
+        &policy,
+        Utc::now(),
+        retention_config.batch_size,
+        retention_config.archive_dir.as_deref(),
+    )
+    .await?;
+
+    tracing::info!(
+        table = %target,
+        trimmed = outcome.trimmed,
+        archived = outcome.archived,
+        "Trimmed table"
+    );
 
     Ok(Response::new(rpc::TrimTableResponse {
-        total_deleted: total_deleted.to_string(),
+        total_deleted: outcome.trimmed.to_string(),
+        total_archived: outcome.archived,
     }))
 }
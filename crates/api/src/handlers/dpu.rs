@@ -24,6 +24,9 @@ use ::rpc::{common as rpc_common, forge as rpc};
 use carbide_network::virtualization::VpcVirtualizationType;
 use carbide_utils::models::arch::CpuArchitecture;
 use carbide_uuid::machine::MachineId;
+use carbide_uuid::network_security_group::{
+    NetworkSecurityGroupId, NetworkSecurityGroupIdParseError,
+};
 use db::{
     DatabaseError, ObjectColumnFilter, dpu_agent_upgrade_policy, network_security_group,
     network_segment,
@@ -35,7 +38,7 @@ use model::extension_service::{ExtensionService, ExtensionServiceVersionInfo};
 use model::hardware_info::MachineInventory;
 use model::instance::config::extension_services::InstanceExtensionServiceConfig;
 use model::machine::machine_search_config::MachineSearchConfig;
-use model::machine::network::MachineNetworkStatusObservation;
+use model::machine::network::{AppliedNvueConfig, MachineNetworkStatusObservation};
 use model::machine::upgrade_policy::{AgentUpgradePolicy, BuildVersion};
 use model::machine::{InstanceState, LoadSnapshotOptions, ManagedHostState};
 use model::machine_update_module::HOST_UPDATE_HEALTH_PROBE_ID;
@@ -66,9 +69,11 @@ fn build_consolidated_network_config(
     }
 }
 
+/// `network_security_group_override` renders the config as if the instance used that NSG.
 pub(crate) async fn get_managed_host_network_config_inner(
     api: &Api,
     dpu_machine_id: MachineId,
+    network_security_group_override: Option<NetworkSecurityGroupId>,
 ) -> Result<rpc::ManagedHostNetworkConfigResponse, tonic::Status> {
     let mut txn = api.txn_begin().await?;
 
@@ -301,7 +306,9 @@ pub(crate) async fn get_managed_host_network_config_inner(
                 && let Some((tenant_id, Some(nsg_id))) = snapshot.instance.as_ref().map(|i| {
                 (
                     &i.config.tenant.tenant_organization_id,
-                    i.config.network_security_group_id.as_ref(),
+                    network_security_group_override
+                        .as_ref()
+                        .or(i.config.network_security_group_id.as_ref()),
                 )
             }) {
                 // Make our DB query for the IDs to get our NetworkSecurityGroup
@@ -634,7 +641,9 @@ pub(crate) async fn get_managed_host_network_config_inner(
             .network_security_group
             .policy_overrides
             .iter()
-            .map(|r| ethernet_virtualization::resolve_security_group_rule(r.clone()))
+            .map(|r| {
+                ethernet_virtualization::resolve_security_group_rule(r.clone(), &HashMap::new())
+            })
             .collect::<Result<Vec<rpc::ResolvedNetworkSecurityGroupRule>, CarbideError>>()?,
         stateful_acls_enabled: api
             .runtime_config
@@ -756,11 +765,104 @@ pub(crate) async fn get_managed_host_network_config(
     let request = request.into_inner();
     let dpu_machine_id = convert_and_log_machine_id(request.dpu_machine_id.as_ref())?;
 
-    let resp = get_managed_host_network_config_inner(api, dpu_machine_id).await?;
+    let resp = get_managed_host_network_config_inner(api, dpu_machine_id, None).await?;
 
     Ok(Response::new(resp))
 }
 
+/// Renders the NVUE config of a DPU the way forge-dpu-agent does, without pushing it.
+/// For use by forge-admin-cli
+pub(crate) async fn render_dpu_network_config(
+    api: &Api,
+    request: Request<rpc::RenderDpuNetworkConfigRequest>,
+) -> Result<Response<rpc::RenderDpuNetworkConfigResponse>, Status> {
+    log_request_data(&request);
+
+    let request = request.into_inner();
+    let dpu_machine_id = convert_and_log_machine_id(request.dpu_machine_id.as_ref())?;
+
+    let network_config = match request.network_config {
+        Some(network_config) => network_config,
+        None => {
+            let network_security_group_override = request
+                .network_security_group_id
+                .map(|id| id.parse())
+                .transpose()
+                .map_err(|e: NetworkSecurityGroupIdParseError| {
+                    CarbideError::InvalidArgument(format!(
+                        "Invalid network_security_group_id: {}",
+                        e.value()
+                    ))
+                })?;
+            get_managed_host_network_config_inner(
+                api,
+                dpu_machine_id,
+                network_security_group_override,
+            )
+            .await?
+        }
+    };
+
+    // The HBN version and hostname of the DPU are inputs of the NVUE config that only the
+    // agent knows. Take them from the config it last applied.
+    let mut txn = api.txn_begin().await?;
+    let applied_config = db::dpu_applied_nvue_config::find(&mut txn, &dpu_machine_id)
+        .await?
+        .ok_or_else(|| {
+            CarbideError::FailedPrecondition(format!(
+                "DPU {dpu_machine_id} has not reported an applied NVUE config"
+            ))
+        })?;
+    txn.commit().await?;
+
+    let rendered_config = render_nvue_config(&network_config, &applied_config).map_err(|e| {
+        CarbideError::FailedPrecondition(format!("Rendering NVUE config failed: {e:#}"))
+    })?;
+    let diff = nvue_config_diff(&applied_config.contents, &rendered_config);
+
+    Ok(Response::new(rpc::RenderDpuNetworkConfigResponse {
+        network_config: Some(network_config),
+        rendered_config,
+        applied_config: Some(applied_config.into()),
+        diff,
+    }))
+}
+
+fn render_nvue_config(
+    network_config: &rpc::ManagedHostNetworkConfigResponse,
+    applied_config: &AppliedNvueConfig,
+) -> eyre::Result<String> {
+    // Unset means ETV, as in forge-dpu-agent
+    let virtualization_type = network_config
+        .network_virtualization_type
+        .map(rpc::VpcVirtualizationType::try_from)
+        .transpose()?
+        .map(VpcVirtualizationType::from)
+        .unwrap_or(VpcVirtualizationType::EthernetVirtualizer);
+    nvue_config::render_nvue_config(
+        virtualization_type,
+        network_config,
+        &nvue_config::NvueRenderContext {
+            hbn_version: applied_config.hbn_version.clone(),
+            dpu_hostname: applied_config.dpu_hostname.clone(),
+            dpu_search_domain: applied_config.dpu_search_domain.clone(),
+            is_dpu_os: applied_config.is_dpu_os,
+        },
+    )
+}
+
+/// Unified diff from the applied to the rendered NVUE config, empty if they are the same
+fn nvue_config_diff(applied: &str, rendered: &str) -> String {
+    if applied == rendered {
+        return String::new();
+    }
+    similar::TextDiff::from_lines(applied, rendered)
+        .unified_diff()
+        .context_radius(3)
+        .header("applied", "rendered")
+        .to_string()
+}
+
 pub(crate) async fn update_agent_reported_inventory(
     api: &Api,
     request: Request<rpc::DpuAgentInventoryReport>,
@@ -897,6 +999,11 @@ pub(crate) async fn record_dpu_network_status(
         .await?;
     }
 
+    if let Some(applied_nvue_config) = request.applied_nvue_config.clone() {
+        db::dpu_applied_nvue_config::upsert(&mut txn, &dpu_machine_id, &applied_nvue_config.into())
+            .await?;
+    }
+
     txn.commit().await?;
 
     // Check if we need to flag this forge-dpu-agent for upgrade or mark an upgrade completed
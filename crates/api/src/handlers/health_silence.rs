@@ -0,0 +1,223 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashMap;
+
+use ::rpc::forge as rpc;
+use chrono::{DateTime, TimeDelta, Utc};
+use model::health_silence::{
+    HealthSilence, HealthSilenceMatcher, HealthSilenceObjectKind, HealthSilenceSubject,
+    MAX_HEALTH_SILENCE_DURATION, apply_health_silences,
+};
+use tonic::{Request, Response, Status};
+
+use crate::CarbideError;
+use crate::api::{Api, log_request_data};
+use crate::auth::AuthContext;
+
+fn external_user_name<T>(request: &Request<T>) -> Option<String> {
+    request
+        .extensions()
+        .get::<AuthContext>()
+        .and_then(|ctx| ctx.get_external_user_name())
+        .map(String::from)
+}
+
+fn timestamp_from_rpc(
+    timestamp: ::rpc::Timestamp,
+    field: &str,
+) -> Result<DateTime<Utc>, CarbideError> {
+    DateTime::<Utc>::try_from(timestamp)
+        .map_err(|e| CarbideError::InvalidArgument(format!("{field}: {e}")))
+}
+
+pub(crate) async fn create_health_silence(
+    api: &Api,
+    request: Request<rpc::CreateHealthSilenceRequest>,
+) -> Result<Response<rpc::HealthSilence>, Status> {
+    log_request_data(&request);
+    let user_name = external_user_name(&request);
+    let request = request.into_inner();
+
+    let author = user_name
+        .or(request.author)
+        .filter(|author| !author.is_empty())
+        .ok_or(CarbideError::MissingArgument("author"))?;
+    if request.reason.trim().is_empty() {
+        return Err(CarbideError::MissingArgument("reason").into());
+    }
+    let matcher = request
+        .matcher
+        .ok_or(CarbideError::MissingArgument("matcher"))
+        .and_then(|matcher| HealthSilenceMatcher::try_from(matcher).map_err(CarbideError::from))?;
+    if matcher.is_empty() {
+        return Err(CarbideError::InvalidArgument(
+            "the matcher needs at least one criterion".to_string(),
+        )
+        .into());
+    }
+
+    let now = Utc::now();
+    let starts_at = request
+        .starts_at
+        .map(|ts| timestamp_from_rpc(ts, "starts_at"))
+        .transpose()?
+        .unwrap_or(now);
+    let expires_at = match (request.expires_at, request.duration) {
+        (Some(expires_at), None) => timestamp_from_rpc(expires_at, "expires_at")?,
+        (None, Some(duration)) => {
+            starts_at
+                + TimeDelta::try_from(duration)
+                    .map_err(|e| CarbideError::InvalidArgument(format!("duration: {e}")))?
+        }
+        (Some(_), Some(_)) => {
+            return Err(CarbideError::InvalidArgument(
+                "only one of expires_at and duration can be set".to_string(),
+            )
+            .into());
+        }
+        (None, None) => return Err(CarbideError::MissingArgument("expires_at or duration").into()),
+    };
+    if expires_at <= starts_at.max(now) {
+        return Err(CarbideError::InvalidArgument(
+            "the silence needs to expire in the future and after it starts".to_string(),
+        )
+        .into());
+    }
+    if expires_at - starts_at > MAX_HEALTH_SILENCE_DURATION {
+        return Err(CarbideError::InvalidArgument(format!(
+            "silences can last at most {} days",
+            MAX_HEALTH_SILENCE_DURATION.num_days()
+        ))
+        .into());
+    }
+
+    let mut txn = api.txn_begin().await?;
+    let silence = db::health_silence::create(
+        &mut txn,
+        &matcher,
+        &author,
+        &request.reason,
+        starts_at,
+        expires_at,
+    )
+    .await?;
+    txn.commit().await?;
+
+    tracing::info!(
+        silence_id = silence.id,
+        author = %silence.author,
+        reason = %silence.reason,
+        expires_at = %silence.expires_at,
+        "Created health silence"
+    );
+
+    Ok(Response::new(silence.into_rpc(now)))
+}
+
+pub(crate) async fn list_health_silences(
+    api: &Api,
+    request: Request<rpc::ListHealthSilencesRequest>,
+) -> Result<Response<rpc::HealthSilenceList>, Status> {
+    log_request_data(&request);
+    let request = request.into_inner();
+
+    let mut reader = api.db_reader();
+    let silences = db::health_silence::find_all(reader.as_mut(), request.include_ended).await?;
+
+    let now = Utc::now();
+    Ok(Response::new(rpc::HealthSilenceList {
+        silences: silences
+            .into_iter()
+            .map(|silence| silence.into_rpc(now))
+            .collect(),
+    }))
+}
+
+pub(crate) async fn expire_health_silence(
+    api: &Api,
+    request: Request<rpc::ExpireHealthSilenceRequest>,
+) -> Result<Response<rpc::HealthSilence>, Status> {
+    log_request_data(&request);
+    let user_name = external_user_name(&request);
+    let request = request.into_inner();
+
+    let ended_by = user_name
+        .or(request.ended_by)
+        .filter(|ended_by| !ended_by.is_empty())
+        .ok_or(CarbideError::MissingArgument("ended_by"))?;
+
+    let mut txn = api.txn_begin().await?;
+    let Some(silence) = db::health_silence::end(&mut txn, request.id, &ended_by).await? else {
+        let exists = db::health_silence::find_by_id(&mut txn, request.id)
+            .await?
+            .is_some();
+        return Err(if exists {
+            CarbideError::InvalidArgument(format!("health silence {} already ended", request.id))
+        } else {
+            CarbideError::NotFoundError {
+                kind: "health_silence",
+                id: request.id.to_string(),
+            }
+        }
+        .into());
+    };
+    txn.commit().await?;
+
+    tracing::info!(
+        silence_id = silence.id,
+        ended_by = %ended_by,
+        "Ended health silence"
+    );
+
+    Ok(Response::new(silence.into_rpc(Utc::now())))
+}
+
+/// Applies active health silences to the aggregate health of a Rack, Switch
+/// or Power Shelf, which is only derived when converting them to RPC types
+pub(crate) fn apply_to_rpc_health(
+    health: &mut Option<::rpc::health::HealthReport>,
+    silences: &[HealthSilence],
+    kind: HealthSilenceObjectKind,
+    id: String,
+    labels: &HashMap<String, String>,
+    rack_id: Option<String>,
+) {
+    if silences.is_empty() {
+        return;
+    }
+    let Some(rpc_health) = health.take() else {
+        return;
+    };
+    let mut report = match health_report::HealthReport::try_from(rpc_health.clone()) {
+        Ok(report) => report,
+        Err(e) => {
+            tracing::warn!(%id, "Can not apply health silences to malformed health report: {e}");
+            *health = Some(rpc_health);
+            return;
+        }
+    };
+    let subject = HealthSilenceSubject {
+        kind,
+        ids: vec![id],
+        labels,
+        sku: None,
+        rack_id,
+    };
+    apply_health_silences(&mut report, silences, &subject, Utc::now());
+    *health = Some(report.into());
+}
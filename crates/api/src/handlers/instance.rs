@@ -48,6 +48,7 @@ use serde_json::json;
 use tonic::{Request, Response, Status};
 
 use crate::api::{Api, log_machine_id, log_request_data, log_tenant_organization_id};
+use crate::auth::{ResourceAttributes, ResourceAuthorizer};
 use crate::handlers::utils::convert_and_log_machine_id;
 use crate::instance::{
     InstanceAllocationRequest, allocate_ib_port_guid, allocate_instance, allocate_network,
@@ -109,12 +110,34 @@ pub(crate) async fn allocate(
     log_machine_id(&request.machine_id);
     log_tenant_organization_id(request.config.tenant.tenant_organization_id.as_str());
 
+    let allocation = (
+        request.machine_id,
+        request.config.tenant.tenant_organization_id.to_string(),
+    );
+
     // Row-locking on Machine records happens in allocate_instance
     let mh_snapshot = allocate_instance(api, request, api.runtime_config.host_health).await?;
 
+    enqueue_allocated_hosts(api, &[allocation]).await;
+
     Ok(Response::new(snapshot_to_instance(mh_snapshot)?))
 }
 
+/// Wakes up the machine state handler for hosts which just got an instance allocated.
+/// The hosts are enqueued on behalf of the tenant, so that provisioning starts ahead of
+/// hosts which are only periodically re-evaluated, and without starving other tenants.
+async fn enqueue_allocated_hosts(api: &Api, allocations: &[(MachineId, String)]) {
+    for (machine_id, tenant_organization_id) in allocations {
+        if let Err(err) = api
+            .machine_state_handler_enqueuer
+            .enqueue_object_for_tenant(machine_id, tenant_organization_id)
+            .await
+        {
+            tracing::warn!(%err, %machine_id, "Failed to wake up state handler for machine");
+        }
+    }
+}
+
 pub(crate) async fn batch_allocate(
     api: &Api,
     request: Request<rpc::BatchInstanceAllocationRequest>,
@@ -150,6 +173,15 @@ pub(crate) async fn batch_allocate(
         log_machine_id(&request.machine_id);
         log_tenant_organization_id(request.config.tenant.tenant_organization_id.as_str());
     }
+    let allocations: Vec<(MachineId, String)> = requests
+        .iter()
+        .map(|request| {
+            (
+                request.machine_id,
+                request.config.tenant.tenant_organization_id.to_string(),
+            )
+        })
+        .collect();
 
     // Call batch allocation logic
     let snapshots =
@@ -159,6 +191,8 @@ pub(crate) async fn batch_allocate(
                 tracing::error!(error = %e, "Batch instance allocation failed");
             })?;
 
+    enqueue_allocated_hosts(api, &allocations).await;
+
     // Convert all snapshots to Instance responses
     let instances = snapshots
         .into_iter()
@@ -629,6 +663,7 @@ pub(crate) async fn release(
     request: Request<rpc::InstanceReleaseRequest>,
 ) -> Result<Response<rpc::InstanceReleaseResult>, Status> {
     log_request_data(&request);
+    let resource_authorizer = ResourceAuthorizer::from_request(&request, "ReleaseInstance");
     let delete_instance = DeleteInstance::try_from(request.into_inner())?;
 
     let mut txn = api.txn_begin().await?;
@@ -643,6 +678,20 @@ pub(crate) async fn release(
     log_machine_id(&instance.machine_id);
     log_tenant_organization_id(instance.config.tenant.tenant_organization_id.as_str());
 
+    resource_authorizer.authorize(ResourceAttributes {
+        kind: "instance".to_string(),
+        id: instance.id.to_string(),
+        tenant_organization_id: instance
+            .config
+            .tenant
+            .tenant_organization_id
+            .as_str()
+            .to_string(),
+        labels: instance.metadata.labels.clone().into_iter().collect(),
+        site: api.runtime_config.sitename.clone().unwrap_or_default(),
+        ..Default::default()
+    })?;
+
     // Instance Release called from the Repair tenant.
     if delete_instance.is_repair_tenant == Some(true) {
         tracing::info!(
@@ -34,7 +34,7 @@ use tonic::{Request, Response, Status};
 
 use crate::CarbideError;
 use crate::api::{Api, log_machine_id, log_request_data};
-use crate::auth::AuthContext;
+use crate::auth::{AuthContext, ResourceAttributes, ResourceAuthorizer};
 use crate::handlers::utils::convert_and_log_machine_id;
 
 pub(crate) async fn find_machine_ids(
@@ -228,6 +228,63 @@ pub(crate) async fn find_machine_health_histories(
     Ok(Response::new(response))
 }
 
+pub(crate) async fn get_managed_host_at(
+    api: &Api,
+    request: Request<rpc::GetManagedHostAtRequest>,
+) -> Result<Response<rpc::GetManagedHostAtResponse>, Status> {
+    log_request_data(&request);
+    let request = request.into_inner();
+
+    let machine_id = convert_and_log_machine_id(request.machine_id.as_ref())?;
+    let timestamp = request
+        .timestamp
+        .map(chrono::DateTime::<chrono::Utc>::try_from)
+        .transpose()
+        .map_err(|_| CarbideError::InvalidArgument("Invalid timestamp".to_string()))?
+        .ok_or_else(|| CarbideError::from(RpcDataConversionError::MissingArgument("timestamp")))?;
+    let diff_from = request
+        .diff_from
+        .map(chrono::DateTime::<chrono::Utc>::try_from)
+        .transpose()
+        .map_err(|_| CarbideError::InvalidArgument("Invalid diff_from timestamp".to_string()))?;
+
+    let mut txn = api.txn_begin().await?;
+
+    // History is recorded per machine, so it can still be looked up for hosts which
+    // have been deleted. DPUs are resolved to the host they were attached to at `timestamp`.
+    let host_machine_id = if machine_id.machine_type().is_dpu() {
+        db::managed_host_history::find_host_of_dpu_at(&mut txn, &machine_id, timestamp)
+            .await?
+            .ok_or(CarbideError::NotFoundError {
+                kind: "machine",
+                id: machine_id.to_string(),
+            })?
+    } else {
+        machine_id
+    };
+
+    let snapshot = db::managed_host_history::load_at(&mut txn, &host_machine_id, timestamp).await?;
+    let diff_from_snapshot = match diff_from {
+        Some(diff_from) => {
+            Some(db::managed_host_history::load_at(&mut txn, &host_machine_id, diff_from).await?)
+        }
+        None => None,
+    };
+
+    txn.commit().await?;
+
+    let changes = diff_from_snapshot
+        .as_ref()
+        .map(|diff_from_snapshot| diff_from_snapshot.diff(&snapshot))
+        .unwrap_or_default();
+
+    Ok(Response::new(rpc::GetManagedHostAtResponse {
+        snapshot: Some(snapshot.into()),
+        diff_from_snapshot: diff_from_snapshot.map(Into::into),
+        changes: changes.into_iter().map(Into::into).collect(),
+    }))
+}
+
 pub(crate) async fn machine_set_auto_update(
     api: &Api,
     request: Request<rpc::MachineSetAutoUpdateRequest>,
@@ -266,6 +323,7 @@ pub(crate) async fn update_machine_metadata(
     request: Request<rpc::MachineMetadataUpdateRequest>,
 ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
     log_request_data(&request);
+    let resource_authorizer = ResourceAuthorizer::from_request(&request, "UpdateMachineMetadata");
     let request = request.into_inner();
     let machine_id = convert_and_log_machine_id(request.machine_id.as_ref())?;
 
@@ -291,6 +349,16 @@ pub(crate) async fn update_machine_metadata(
         )
         .await?;
 
+    // Checked against the labels the machine has now, so a caller can't
+    // relabel a machine into a scope it is allowed to edit.
+    resource_authorizer.authorize(ResourceAttributes {
+        kind: "machine".to_string(),
+        id: machine_id.to_string(),
+        labels: machine.metadata.labels.clone().into_iter().collect(),
+        site: api.runtime_config.sitename.clone().unwrap_or_default(),
+        ..Default::default()
+    })?;
+
     let expected_version: config_version::ConfigVersion = match request.if_version_match {
         Some(version) => version.parse().map_err(CarbideError::from)?,
         None => machine.version,
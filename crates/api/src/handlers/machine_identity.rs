@@ -15,10 +15,12 @@
  * limitations under the License.
  */
 
-//! gRPC handlers for machine identity: JWT-SVID signing, JWKS, and OpenID discovery.
+//! gRPC handlers for machine identity: JWT-SVID signing, X.509-SVID intermediates, trust bundles,
+//! JWKS, and OpenID discovery.
 //! PEM/JWK encoding helpers live in `crate::machine_identity`; persisted config in `tenant_identity_config`.
 
 use std::convert::TryFrom;
+use std::time::Duration;
 
 use ::rpc::forge::{
     self as rpc, Jwks, JwksKind, JwksRequest, MachineIdentityResponse, OpenIdConfigRequest,
@@ -28,7 +30,10 @@ use carbide_uuid::machine::MachineId;
 use chrono::Utc;
 use db::{WithTransaction, tenant_identity_config};
 use forge_secrets::key_encryption;
-use model::tenant::{InvalidTenantOrg, TenantIdentityConfig, TenantOrganizationId};
+use model::tenant::{
+    EncryptedSigningPrivateKey, InvalidNonEmptyStr, InvalidTenantOrg, TenantIdentityConfig,
+    TenantOrganizationId, TenantX509CaKey,
+};
 use serde_json::json;
 use tonic::{Request, Response, Status};
 
@@ -36,9 +41,10 @@ use crate::CarbideError;
 use crate::api::{Api, log_request_data};
 use crate::auth::AuthContext;
 use crate::machine_identity::{
-    Es256Signer, SignOptions, Signer, decrypt_token_delegation_encrypted_blob,
-    machine_identity_encryption_secret, token_delegation_credentials, token_exchange_http_client,
-    token_exchange_request,
+    Es256Signer, SignOptions, Signer, TenantX509Authority, X509AuthorityError,
+    decrypt_token_delegation_encrypted_blob, machine_identity_encryption_secret,
+    token_delegation_credentials, token_exchange_http_client, token_exchange_request,
+    trust_domain_id,
 };
 
 /// Shared gate for APIs that require site `[machine_identity].enabled` (identity admin + discovery).
@@ -105,34 +111,12 @@ fn validate_audiences_in_allowlist(
     Ok(())
 }
 
-/// Handles the SignMachineIdentity gRPC call: validates the request, extracts
-/// machine identity from the client certificate, and returns a JWT(-SVID)–shaped OAuth token
-/// response.
-///
-/// The machine ID is taken from the client's mTLS certificate SPIFFE ID. The tenant organization
-/// is resolved from the instance row for that machine; per-org identity config supplies issuer,
-/// subject prefix, audiences, TTL, and signing key material.
-///
-/// When per-org **token delegation** is configured (`token_endpoint` + `subject_token_audience` +
-/// `auth_method`), Carbide first signs a subject JWT (`aud` = exchange service,
-/// `request_meta_data.aud` = caller-requested workload audiences) with the same `exp` / `iat` delta
-/// as `token_ttl_sec`, then performs an RFC 8693 token exchange `POST` to the tenant
-/// `token_endpoint` and returns that response (**`expires_in_sec` is taken from the tenant STS JSON
-/// `expires_in` field, not from `token_ttl_sec`**). Otherwise the handler returns a directly signed
-/// JWT using the org `token_ttl_sec` as `expires_in_sec`.
-pub(crate) async fn sign_machine_identity(
+/// Resolves the calling machine from its mTLS SPIFFE ID and loads the enabled identity config of
+/// the tenant whose instance runs on it.
+async fn identity_for_calling_machine<T>(
     api: &Api,
-    request: Request<rpc::MachineIdentityRequest>,
-) -> Result<Response<MachineIdentityResponse>, Status> {
-    log_request_data(&request);
-
-    if !api.runtime_config.machine_identity.enabled {
-        return Err(CarbideError::UnavailableError(
-            "Machine identity is disabled in site config".into(),
-        )
-        .into());
-    }
-
+    request: &Request<T>,
+) -> Result<(MachineId, TenantIdentityConfig), Status> {
     let auth_context = request
         .extensions()
         .get::<AuthContext>()
@@ -148,8 +132,6 @@ pub(crate) async fn sign_machine_identity(
         .parse()
         .map_err(|e| CarbideError::InvalidArgument(format!("Invalid machine ID format: {e}")))?;
 
-    let req = request.get_ref();
-
     let identity_row = api
         .database_connection
         .with_txn(|txn| {
@@ -159,14 +141,14 @@ pub(crate) async fn sign_machine_identity(
         })
         .await??;
 
-    let allowed: &[String] = identity_row.allowed_audiences.0.as_slice();
-    let audiences: Vec<String> = if req.audience.is_empty() {
-        vec![identity_row.default_audience.clone()]
-    } else {
-        req.audience.clone()
-    };
-    validate_audiences_in_allowlist(&audiences, allowed)?;
+    Ok((machine_id, identity_row))
+}
 
+/// Decrypts the tenant signing private key (PKCS#8 PEM).
+pub(crate) async fn decrypt_signing_key(
+    api: &Api,
+    identity_row: &TenantIdentityConfig,
+) -> Result<Vec<u8>, Status> {
     let aes = machine_identity_encryption_secret(
         api.credential_manager.as_ref(),
         &identity_row.encryption_key_id,
@@ -181,6 +163,50 @@ pub(crate) async fn sign_machine_identity(
             );
             CarbideError::internal("stored signing key could not be decrypted".to_string())
         })?;
+    Ok(private_pem)
+}
+
+/// Handles the SignMachineIdentity gRPC call: validates the request, extracts
+/// machine identity from the client certificate, and returns a JWT(-SVID)–shaped OAuth token
+/// response.
+///
+/// The machine ID is taken from the client's mTLS certificate SPIFFE ID. The tenant organization
+/// is resolved from the instance row for that machine; per-org identity config supplies issuer,
+/// subject prefix, audiences, TTL, and signing key material.
+///
+/// When per-org **token delegation** is configured (`token_endpoint` + `subject_token_audience` +
+/// `auth_method`), Carbide first signs a subject JWT (`aud` = exchange service,
+/// `request_meta_data.aud` = caller-requested workload audiences) with the same `exp` / `iat` delta
+/// as `token_ttl_sec`, then performs an RFC 8693 token exchange `POST` to the tenant
+/// `token_endpoint` and returns that response (**`expires_in_sec` is taken from the tenant STS JSON
+/// `expires_in` field, not from `token_ttl_sec`**). Otherwise the handler returns a directly signed
+/// JWT using the org `token_ttl_sec` as `expires_in_sec`.
+pub(crate) async fn sign_machine_identity(
+    api: &Api,
+    request: Request<rpc::MachineIdentityRequest>,
+) -> Result<Response<MachineIdentityResponse>, Status> {
+    log_request_data(&request);
+
+    if !api.runtime_config.machine_identity.enabled {
+        return Err(CarbideError::UnavailableError(
+            "Machine identity is disabled in site config".into(),
+        )
+        .into());
+    }
+
+    let (machine_id, identity_row) = identity_for_calling_machine(api, &request).await?;
+
+    let req = request.get_ref();
+
+    let allowed: &[String] = identity_row.allowed_audiences.0.as_slice();
+    let audiences: Vec<String> = if req.audience.is_empty() {
+        vec![identity_row.default_audience.clone()]
+    } else {
+        req.audience.clone()
+    };
+    validate_audiences_in_allowlist(&audiences, allowed)?;
+
+    let private_pem = decrypt_signing_key(api, &identity_row).await?;
 
     let signer = Es256Signer::new(&private_pem, &identity_row.key_id)
         .map_err(|e| CarbideError::InvalidArgument(e.to_string()))?;
@@ -279,6 +305,167 @@ pub(crate) async fn sign_machine_identity(
     Ok(Response::new(response))
 }
 
+/// Rebuilds the tenant X.509 root CA from its dedicated CA key. The CA key is created on first use
+/// and replaced when the tenant signing key is rotated, so rotation also rotates the X.509 root.
+async fn tenant_x509_authority(
+    api: &Api,
+    identity_row: &TenantIdentityConfig,
+) -> Result<TenantX509Authority, Status> {
+    let trust_domain = identity_row
+        .issuer
+        .trust_domain()
+        .map_err(|e| CarbideError::internal(format!("stored issuer has no trust domain: {e}")))?;
+
+    let org_id = identity_row.organization_id.clone();
+    let ca_key = api
+        .database_connection
+        .with_txn(|txn| {
+            Box::pin(async move { tenant_identity_config::find_x509_ca_key(&org_id, txn).await })
+        })
+        .await??;
+    let ca_key = match ca_key {
+        Some(ca_key) if ca_key.signing_key_id == identity_row.key_id => ca_key,
+        _ => create_x509_ca_key(api, identity_row).await?,
+    };
+
+    let aes = machine_identity_encryption_secret(
+        api.credential_manager.as_ref(),
+        &ca_key.encryption_key_id,
+    )
+    .await?;
+    let private_pem =
+        key_encryption::decrypt(ca_key.encrypted_ca_key.as_str(), &aes).map_err(|e| {
+            tracing::error!(
+                error = %e,
+                org_id = %identity_row.organization_id.as_str(),
+                "tenant X.509 CA key decrypt failed"
+            );
+            CarbideError::internal("stored X.509 CA key could not be decrypted".to_string())
+        })?;
+    let private_pem = std::str::from_utf8(&private_pem)
+        .map_err(|_| CarbideError::internal("stored X.509 CA key is not valid PEM".to_string()))?;
+    TenantX509Authority::new(private_pem, &trust_domain, ca_key.created_at)
+        .map_err(|e| CarbideError::internal(e.to_string()).into())
+}
+
+/// Generates and stores a new X.509 CA key for the current signing key of the tenant.
+/// Returns the stored CA key, which is the one of a concurrent caller if that one won.
+async fn create_x509_ca_key(
+    api: &Api,
+    identity_row: &TenantIdentityConfig,
+) -> Result<TenantX509CaKey, Status> {
+    let aes = machine_identity_encryption_secret(
+        api.credential_manager.as_ref(),
+        &identity_row.encryption_key_id,
+    )
+    .await?;
+    let (private_pem, _public_pem) = key_encryption::generate_es256_key_pair()
+        .map_err(|e| CarbideError::internal(e.to_string()))?;
+    let encrypted_ca_key: EncryptedSigningPrivateKey =
+        key_encryption::encrypt(&private_pem, &aes, identity_row.encryption_key_id.as_str())
+            .map_err(|e| CarbideError::internal(e.to_string()))?
+            .try_into()
+            .map_err(|e: InvalidNonEmptyStr| CarbideError::internal(e.to_string()))?;
+
+    let org_id = identity_row.organization_id.clone();
+    let signing_key_id = identity_row.key_id.clone();
+    let encryption_key_id = identity_row.encryption_key_id.clone();
+    let ca_key = api
+        .database_connection
+        .with_txn(|txn| {
+            Box::pin(async move {
+                tenant_identity_config::set_x509_ca_key(
+                    &org_id,
+                    &signing_key_id,
+                    &encrypted_ca_key,
+                    &encryption_key_id,
+                    txn,
+                )
+                .await
+            })
+        })
+        .await??;
+    Ok(ca_key)
+}
+
+/// Returns the calling machine's SPIFFE ID with the JWT and X.509 trust bundle of its tenant.
+/// Backs `FetchJWTBundles` / `FetchX509Bundles` of the DPU SPIFFE Workload API.
+pub(crate) async fn get_machine_identity_trust_bundle(
+    api: &Api,
+    request: Request<rpc::MachineIdentityTrustBundleRequest>,
+) -> Result<Response<rpc::MachineIdentityTrustBundle>, Status> {
+    log_request_data(&request);
+    require_machine_identity_site_enabled(api)?;
+
+    let (machine_id, identity_row) = identity_for_calling_machine(api, &request).await?;
+
+    let jwk = crate::machine_identity::public_pem_to_jwk_value(
+        identity_row.signing_key_public.as_ref(),
+        identity_row.key_id.as_ref(),
+        identity_row.algorithm.as_jwt_alg_str(),
+        crate::machine_identity::JwkPublicKeyUse::SpiffeJwtSvid,
+    )
+    .map_err(|e| CarbideError::InvalidArgument(e.to_string()))?;
+    let jwks = crate::machine_identity::jwks_document_string(&jwk)
+        .map_err(|e| CarbideError::InvalidArgument(e.to_string()))?;
+
+    let authority = tenant_x509_authority(api, &identity_row).await?;
+
+    Ok(Response::new(rpc::MachineIdentityTrustBundle {
+        spiffe_id: jwt_sub_claim(&identity_row.subject_prefix, &machine_id),
+        trust_domain_id: trust_domain_id(authority.trust_domain()),
+        jwks,
+        x509_authorities: vec![authority.root_der()],
+    }))
+}
+
+/// Signs a per-machine X.509 intermediate CA over the public key of the request CSR. The DPU keeps
+/// the intermediate key and issues short-lived X.509-SVIDs for `spiffe_id` from it.
+pub(crate) async fn sign_machine_x509_intermediate(
+    api: &Api,
+    request: Request<rpc::MachineX509IntermediateRequest>,
+) -> Result<Response<rpc::MachineX509IntermediateResponse>, Status> {
+    log_request_data(&request);
+
+    if !api.runtime_config.machine_identity.enabled {
+        return Err(CarbideError::UnavailableError(
+            "Machine identity is disabled in site config".into(),
+        )
+        .into());
+    }
+
+    let (machine_id, identity_row) = identity_for_calling_machine(api, &request).await?;
+    let req = request.into_inner();
+    if req.csr.is_empty() {
+        return Err(CarbideError::InvalidArgument("csr is required".to_string()).into());
+    }
+
+    let authority = tenant_x509_authority(api, &identity_row).await?;
+    let spiffe_id = jwt_sub_claim(&identity_row.subject_prefix, &machine_id);
+    let ttl = Duration::from_secs(u64::from(
+        api.runtime_config
+            .machine_identity
+            .x509_intermediate_ttl_sec,
+    ));
+
+    let signed = authority
+        .sign_intermediate(&req.csr, &spiffe_id, ttl, Utc::now())
+        .map_err(|e| match e {
+            X509AuthorityError::InvalidCsr(_) | X509AuthorityError::InvalidSpiffeId(_) => {
+                CarbideError::InvalidArgument(e.to_string())
+            }
+            _ => CarbideError::internal(e.to_string()),
+        })?;
+
+    Ok(Response::new(rpc::MachineX509IntermediateResponse {
+        certificate: signed.certificate,
+        x509_authorities: vec![authority.root_der()],
+        spiffe_id,
+        trust_domain_id: trust_domain_id(authority.trust_domain()),
+        expires_in_sec: u32::try_from(signed.expires_in.as_secs()).unwrap_or(u32::MAX),
+    }))
+}
+
 /// Public JWKS for JWT verification (intended for unauthenticated callers via REST gateway).
 pub(crate) async fn get_jwks(
     api: &Api,
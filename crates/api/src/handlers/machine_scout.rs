@@ -23,6 +23,7 @@ use model::machine::{
     ValidationState, get_action_for_dpu_state,
 };
 use model::machine_validation::{MachineValidationState, MachineValidationStatus};
+use model::media_sanitization::MediaSanitizationRecord;
 use tonic::{Request, Response, Status};
 
 use crate::CarbideError;
@@ -39,16 +40,40 @@ pub(crate) async fn cleanup_machine_completed(
 ) -> Result<Response<rpc::MachineCleanupResult>, Status> {
     log_request_data(&request);
 
-    let cleanup_info = request.into_inner();
+    let mut cleanup_info = request.into_inner();
     tracing::info!(?cleanup_info, "cleanup_machine_completed");
 
     let machine_id = convert_and_log_machine_id(cleanup_info.machine_id.as_ref())?;
+    let sanitization_records = std::mem::take(&mut cleanup_info.media_sanitization)
+        .into_iter()
+        .map(MediaSanitizationRecord::try_from)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(CarbideError::from)?;
 
     // Load machine from DB
     let (machine, mut txn) = api
         .load_machine(&machine_id, MachineSearchConfig::default())
         .await?;
 
+    if machine_id.machine_type().is_host() {
+        // A report stays open until the cleanup is retried successfully
+        let sanitized = MediaSanitizationRecord::all_succeeded(&sanitization_records);
+        let report = db::media_sanitization::complete(
+            &mut txn,
+            &machine_id,
+            &sanitization_records,
+            sanitized,
+        )
+        .await?;
+        tracing::info!(
+            machine_id = %machine_id,
+            instance_id = ?report.instance_id,
+            devices = report.records.len(),
+            status = ?report.status(),
+            "Stored media sanitization report"
+        );
+    }
+
     // Check if cleanup failed
     if let Some(ref nvme_result) = cleanup_info.nvme
         && rpc::machine_cleanup_info::CleanupResult::Error as i32 == nvme_result.result
@@ -0,0 +1,205 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::errors::RpcDataConversionError;
+use ::rpc::forge as rpc;
+use chrono::Utc;
+use db::{media_sanitization, tenant_identity_config};
+use model::media_sanitization::MediaSanitizationReport;
+use model::tenant::TenantOrganizationId;
+use serde_json::json;
+use sqlx::PgConnection;
+use tonic::{Request, Response, Status};
+
+use crate::CarbideError;
+use crate::api::{Api, log_machine_id, log_request_data};
+use crate::handlers::machine_identity::decrypt_signing_key;
+use crate::machine_identity::{Es256Signer, SignError, SignOptions, Signer};
+
+/// JWT header `typ` of signed reports. Identity tokens use `JWT`, so a report
+/// can't be passed off as one.
+pub(crate) const MEDIA_SANITIZATION_REPORT_JWT_TYP: &str = "media-sanitization-report+jwt";
+/// `aud` claim of signed reports
+pub(crate) const MEDIA_SANITIZATION_REPORT_AUDIENCE: &str = "carbide-media-sanitization-report";
+
+pub(crate) async fn get_media_sanitization_report(
+    api: &Api,
+    request: Request<rpc::MediaSanitizationReportRequest>,
+) -> Result<Response<rpc::MediaSanitizationReport>, Status> {
+    log_request_data(&request);
+    let request = request.into_inner();
+
+    let mut txn = api.txn_begin().await?;
+
+    let report = match (request.instance_id, request.machine_id) {
+        (Some(instance_id), _) => media_sanitization::find_by_instance_id(&mut txn, instance_id)
+            .await?
+            .ok_or_else(|| CarbideError::NotFoundError {
+                kind: "media_sanitization_report",
+                id: instance_id.to_string(),
+            })?,
+        (None, Some(machine_id)) => {
+            log_machine_id(&machine_id);
+            media_sanitization::find_latest_by_machine_id(&mut txn, &machine_id)
+                .await?
+                .ok_or_else(|| CarbideError::NotFoundError {
+                    kind: "media_sanitization_report",
+                    id: machine_id.to_string(),
+                })?
+        }
+        (None, None) => {
+            return Err(CarbideError::from(RpcDataConversionError::MissingArgument(
+                "instance_id or machine_id",
+            ))
+            .into());
+        }
+    };
+
+    let signature = sign_report(api, &mut txn, &report).await?;
+
+    txn.commit().await?;
+
+    let mut response = rpc::MediaSanitizationReport::from(report);
+    if let Some((signed_report, key_id)) = signature {
+        response.signed_report = signed_report;
+        response.key_id = key_id;
+    }
+    Ok(Response::new(response))
+}
+
+/// Signs the report with the machine identity signing key of the tenant whose
+/// instance was released, so the tenant can verify it with their JWKS.
+/// Returns the JWT and the ID of the key, or `None` if the tenant has no
+/// enabled machine identity configuration.
+async fn sign_report(
+    api: &Api,
+    txn: &mut PgConnection,
+    report: &MediaSanitizationReport,
+) -> Result<Option<(String, String)>, Status> {
+    if !api.runtime_config.machine_identity.enabled {
+        return Ok(None);
+    }
+    let Some(tenant_organization_id) = report.tenant_organization_id.as_deref() else {
+        return Ok(None);
+    };
+    let org_id: TenantOrganizationId = tenant_organization_id
+        .parse()
+        .map_err(|e| CarbideError::internal(format!("invalid tenant organization: {e}")))?;
+    let Some(identity_row) = tenant_identity_config::find(&org_id, txn)
+        .await?
+        .filter(|row| row.enabled)
+    else {
+        return Ok(None);
+    };
+
+    let private_pem = decrypt_signing_key(api, &identity_row).await?;
+    let signer = Es256Signer::new(&private_pem, &identity_row.key_id)
+        .map_err(|e| CarbideError::internal(e.to_string()))?;
+
+    let signed_report = sign_report_claims(&signer, identity_row.issuer.as_str(), report)
+        .map_err(|e| CarbideError::internal(e.to_string()))?;
+
+    Ok(Some((signed_report, signer.key_id().to_string())))
+}
+
+fn sign_report_claims(
+    signer: &impl Signer,
+    issuer: &str,
+    report: &MediaSanitizationReport,
+) -> Result<String, SignError> {
+    let subject = match report.instance_id {
+        Some(instance_id) => format!("instance/{instance_id}"),
+        None => format!("machine/{}", report.machine_id),
+    };
+    let claims = json!({
+        "iss": issuer,
+        "sub": subject,
+        "aud": MEDIA_SANITIZATION_REPORT_AUDIENCE,
+        "iat": Utc::now().timestamp(),
+        "media_sanitization_report": report,
+        "status": report.status(),
+        "succeeded": report.succeeded(),
+    });
+    let opts = SignOptions {
+        typ: Some(MEDIA_SANITIZATION_REPORT_JWT_TYP.to_string()),
+    };
+    signer.sign(&claims, &opts)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use carbide_uuid::machine::MachineId;
+    use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+    use serde_json::{Map, Value};
+
+    use super::*;
+
+    fn signed_report() -> (String, DecodingKey) {
+        let key_pair = rcgen::KeyPair::generate().expect("generate test key");
+        let signer =
+            Es256Signer::new(key_pair.serialize_pem().as_bytes(), "kid-1").expect("create signer");
+        let report = MediaSanitizationReport {
+            id: 1,
+            machine_id: MachineId::from_str(
+                "fm100htjsaledfasinabqqer70e2ua5ksqj4kfjii0v0a90vulps48c1h7g",
+            )
+            .unwrap(),
+            instance_id: None,
+            tenant_organization_id: Some("tenant-org".to_string()),
+            released_at: None,
+            completed_at: None,
+            records: vec![],
+        };
+        let token = sign_report_claims(&signer, "https://issuer.example", &report).expect("sign");
+        let decoding_key =
+            DecodingKey::from_ec_pem(key_pair.public_key_pem().as_bytes()).expect("decoding key");
+        (token, decoding_key)
+    }
+
+    #[test]
+    fn signed_report_verifies_with_its_typ_and_audience() {
+        let (token, decoding_key) = signed_report();
+        let header = jsonwebtoken::decode_header(&token).unwrap();
+        assert_eq!(
+            header.typ.as_deref(),
+            Some(MEDIA_SANITIZATION_REPORT_JWT_TYP)
+        );
+
+        let mut validation = Validation::new(Algorithm::ES256);
+        validation.set_audience(&[MEDIA_SANITIZATION_REPORT_AUDIENCE]);
+        validation.set_required_spec_claims(&["sub", "aud"]);
+        let claims = jsonwebtoken::decode::<Map<String, Value>>(&token, &decoding_key, &validation)
+            .unwrap()
+            .claims;
+        assert_eq!(claims["status"], "pending");
+    }
+
+    #[test]
+    fn signed_report_fails_identity_token_validation() {
+        let (token, decoding_key) = signed_report();
+
+        // Identity tokens are validated for the tenant's audience and must expire
+        let mut validation = Validation::new(Algorithm::ES256);
+        validation.set_audience(&["tenant-service"]);
+        validation.set_required_spec_claims(&["sub", "aud", "exp"]);
+        assert!(
+            jsonwebtoken::decode::<Map<String, Value>>(&token, &decoding_key, &validation).is_err()
+        );
+    }
+}
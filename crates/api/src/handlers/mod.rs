@@ -22,6 +22,7 @@ pub mod bmc_metadata;
 pub mod boot_override;
 pub mod component_manager;
 pub mod compute_allocation;
+pub mod connectivity;
 pub mod credential;
 pub mod db;
 pub mod dns;
@@ -38,6 +39,7 @@ pub mod extension_service;
 pub mod finder;
 pub mod firmware;
 pub mod health;
+pub mod health_silence;
 pub mod host_reprovisioning;
 pub mod ib_fabric;
 pub mod ib_partition;
@@ -55,6 +57,7 @@ pub mod machine_scout;
 pub mod machine_validation;
 pub mod managed_host;
 pub mod measured_boot;
+pub mod media_sanitization;
 pub mod mlx_admin;
 pub mod network_devices;
 pub mod network_security_group;
@@ -63,6 +66,7 @@ pub mod nvl_partition;
 pub mod operating_system;
 pub mod power_options;
 pub mod power_shelf;
+pub mod prefix_list;
 pub mod pxe;
 pub mod rack;
 pub mod rack_firmware;
@@ -15,18 +15,23 @@
  * limitations under the License.
  */
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use ::rpc::errors::RpcDataConversionError;
 use ::rpc::forge as rpc;
 use carbide_uuid::instance::InstanceId;
 use carbide_uuid::network_security_group::NetworkSecurityGroupId;
+use carbide_uuid::prefix_list::PrefixListId;
 use carbide_uuid::vpc::VpcId;
 use config_version::ConfigVersion;
 use db::network_security_group;
 use model::metadata::Metadata;
-use model::network_security_group::{NetworkSecurityGroupRule, NetworkSecurityGroupRuleNet};
+use model::network_security_group::{
+    NetworkSecurityGroupRule, NetworkSecurityGroupRuleNet, referenced_prefix_list_ids,
+};
+use model::prefix_list::PrefixList;
 use model::tenant::{InvalidTenantOrg, TenantOrganizationId};
+use sqlx::PgConnection;
 use tonic::{Request, Response, Status};
 use uuid::Uuid;
 
@@ -77,13 +82,6 @@ pub(crate) async fn create(
         )
     };
 
-    let max_nsg_size = api
-        .runtime_config
-        .network_security_group
-        .max_network_security_group_size as usize;
-
-    validate_expanded_rule_set(&rules, max_nsg_size)?;
-
     // Log tenant organization ID
     log_tenant_organization_id(&req.tenant_organization_id);
 
@@ -98,6 +96,18 @@ pub(crate) async fn create(
     // Start a new transaction for a db write.
     let mut txn = api.txn_begin().await?;
 
+    // Rules can reference prefix lists, so we can only check the size
+    // of the expanded rule set once we've looked them up.
+    let prefix_lists =
+        find_referenced_prefix_lists(&mut txn, &rules, &tenant_organization_id).await?;
+
+    let max_nsg_size = api
+        .runtime_config
+        .network_security_group
+        .max_network_security_group_size as usize;
+
+    validate_expanded_rule_set(&rules, &prefix_lists, max_nsg_size)?;
+
     // Write a new NetworkSecurityGroup to the DB and get back
     // our new NetworkSecurityGroup.
     let network_security_group = network_security_group::create(
@@ -288,13 +298,6 @@ pub(crate) async fn update(
         )
     };
 
-    let max_nsg_size = api
-        .runtime_config
-        .network_security_group
-        .max_network_security_group_size as usize;
-
-    validate_expanded_rule_set(&rules, max_nsg_size)?;
-
     // Log tenant organization ID from request
     log_tenant_organization_id(&req.tenant_organization_id);
 
@@ -367,6 +370,16 @@ pub(crate) async fn update(
         }
     };
 
+    let prefix_lists =
+        find_referenced_prefix_lists(&mut txn, &rules, &tenant_organization_id).await?;
+
+    let max_nsg_size = api
+        .runtime_config
+        .network_security_group
+        .max_network_security_group_size as usize;
+
+    validate_expanded_rule_set(&rules, &prefix_lists, max_nsg_size)?;
+
     // Update record in the DB and get back
     // our new NetworkSecurityGroup state.
     let network_security_group = network_security_group::update(
@@ -499,28 +512,28 @@ pub(crate) async fn get_propagation_status(
     let req = request.into_inner();
 
     let max_find_by_ids = api.runtime_config.max_find_by_ids as usize;
-    if req.vpc_ids.len() + req.instance_ids.len() > max_find_by_ids {
+    if req.vpc_ids.len() + req.instance_ids.len() + req.prefix_list_ids.len() > max_find_by_ids {
         return Err(CarbideError::InvalidArgument(format!(
             "no more than {max_find_by_ids} IDs combined can be submitted"
         ))
         .into());
     }
 
-    if req.vpc_ids.is_empty() && req.instance_ids.is_empty() {
+    if req.vpc_ids.is_empty() && req.instance_ids.is_empty() && req.prefix_list_ids.is_empty() {
         return Err(CarbideError::InvalidArgument(
-            "at least one VPC ID or Instance ID must be provided".to_string(),
+            "at least one VPC ID, Instance ID, or PrefixList ID must be provided".to_string(),
         )
         .into());
     }
 
-    let vpc_ids = req
+    let mut vpc_ids = req
         .vpc_ids
         .iter()
         .map(|v| v.parse::<VpcId>())
         .collect::<Result<Vec<VpcId>, _>>()
         .map_err(|e| CarbideError::from(RpcDataConversionError::InvalidVpcId(e.to_string())))?;
 
-    let instance_ids = req
+    let mut instance_ids = req
         .instance_ids
         .iter()
         .map(|i| i.parse::<InstanceId>())
@@ -529,24 +542,56 @@ pub(crate) async fn get_propagation_status(
             CarbideError::from(RpcDataConversionError::InvalidInstanceId(e.to_string()))
         })?;
 
+    let mut network_security_group_ids = req
+        .network_security_group_ids
+        .map(|nl| {
+            nl.ids
+                .iter()
+                .map(|v| v.parse::<NetworkSecurityGroupId>())
+                .collect::<Result<Vec<NetworkSecurityGroupId>, _>>()
+        })
+        .transpose()
+        .map_err(|e| {
+            CarbideError::from(RpcDataConversionError::InvalidInstanceId(e.to_string()))
+        })?;
+
     // Prepare our txn to associate machines with the NetworkSecurityGroup
     let mut txn = api.txn_begin().await?;
 
+    // A change to a prefix list is propagated as a new version of every
+    // NetworkSecurityGroup that references it, so the status of a prefix list
+    // is the status of those NetworkSecurityGroups wherever they're attached.
+    if !req.prefix_list_ids.is_empty() {
+        let referencing_ids: Vec<NetworkSecurityGroupId> =
+            network_security_group::find_by_prefix_list_ids(&mut txn, &req.prefix_list_ids, false)
+                .await?
+                .into_iter()
+                .map(|nsg| nsg.id)
+                .filter(|id| {
+                    network_security_group_ids
+                        .as_ref()
+                        .is_none_or(|ids| ids.contains(id))
+                })
+                .collect();
+
+        for attachments in network_security_group::find_objects_with_attachments(
+            &mut txn,
+            Some(&referencing_ids),
+            None,
+        )
+        .await?
+        {
+            vpc_ids.extend(attachments.vpc_ids);
+            instance_ids.extend(attachments.instance_ids);
+        }
+
+        network_security_group_ids = Some(referencing_ids);
+    }
+
     // Query the DB for propagation status.
     let (vpcs, instances) = network_security_group::get_propagation_status(
         &mut txn,
-        req.network_security_group_ids
-            .map(|nl| {
-                nl.ids
-                    .iter()
-                    .map(|v| v.parse::<NetworkSecurityGroupId>())
-                    .collect::<Result<Vec<NetworkSecurityGroupId>, _>>()
-            })
-            .transpose()
-            .map_err(|e| {
-                CarbideError::from(RpcDataConversionError::InvalidInstanceId(e.to_string()))
-            })?
-            .as_deref(),
+        network_security_group_ids.as_deref(),
         None,
         Some(&vpc_ids),
         Some(&instance_ids),
@@ -621,8 +666,43 @@ pub(crate) async fn get_attachments(
     Ok(Response::new(rpc_out))
 }
 
-fn validate_expanded_rule_set(
+/// Looks up the prefix lists referenced by a set of rules, failing if any of
+/// them doesn't exist or isn't owned by the tenant that owns the rules.
+/// The lists are locked so they can't change until the rules are written.
+pub(crate) async fn find_referenced_prefix_lists(
+    txn: &mut PgConnection,
     rules: &[NetworkSecurityGroupRule],
+    tenant_organization_id: &TenantOrganizationId,
+) -> Result<HashMap<PrefixListId, PrefixList>, CarbideError> {
+    let prefix_list_ids = referenced_prefix_list_ids(rules);
+
+    if prefix_list_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let prefix_lists = model::prefix_list::by_id(
+        db::prefix_list::find_by_ids(txn, &prefix_list_ids, Some(tenant_organization_id), true)
+            .await?,
+    );
+
+    if let Some(missing) = prefix_list_ids
+        .iter()
+        .find(|id| !prefix_lists.contains_key(id))
+    {
+        return Err(CarbideError::NotFoundError {
+            kind: "PrefixList",
+            id: format!("{missing} for tenant org `{tenant_organization_id}`"),
+        });
+    }
+
+    Ok(prefix_lists)
+}
+
+/// Checks that a set of rules doesn't exceed `limit` once port ranges are
+/// expanded and referenced prefix lists are replaced by their prefixes.
+pub(crate) fn validate_expanded_rule_set(
+    rules: &[NetworkSecurityGroupRule],
+    prefix_lists: &HashMap<PrefixListId, PrefixList>,
     limit: usize,
 ) -> Result<(), CarbideError> {
     let mut total_rules = 0u32;
@@ -643,33 +723,41 @@ fn validate_expanded_rule_set(
             )));
         }
 
-        match (&rule.src_net, &rule.dst_net) {
-            (NetworkSecurityGroupRuleNet::Prefix(_), NetworkSecurityGroupRuleNet::Prefix(_)) => {
-                // Negative ranges are caught when we convert from rpc to internal struct.
-                // so we can keep this simple.
-                let rule_count = (rule.src_port_end.unwrap_or_default()
-                    - rule.src_port_start.unwrap_or_default()
-                    + 1)
-                .saturating_mul(
-                    rule.dst_port_end.unwrap_or_default() - rule.dst_port_start.unwrap_or_default()
-                        + 1,
-                );
-
-                total_rules = match total_rules.overflowing_add(rule_count) {
-                    (_, true) => {
-                        return Err(CarbideError::InvalidArgument(format!(
-                            "expanded rule set contains more than {limit} maximum number of rules"
-                        )));
-                    }
-                    (v, false) => v,
-                };
-
-                if total_rules as usize > limit {
-                    return Err(CarbideError::InvalidArgument(format!(
-                        "expanded rule set contains more than {limit} maximum number of rules"
-                    )));
-                }
+        let net_count = |net: &NetworkSecurityGroupRuleNet| {
+            net.resolve(rule.ipv6, prefix_lists)
+                .map(|prefixes| prefixes.len() as u32)
+                .map_err(|id| {
+                    CarbideError::InvalidArgument(format!(
+                        "rule `{}` references unknown prefix list `{id}`",
+                        rule.id.clone().unwrap_or_default()
+                    ))
+                })
+        };
+
+        // Negative ranges are caught when we convert from rpc to internal struct.
+        // so we can keep this simple.
+        let rule_count = (rule.src_port_end.unwrap_or_default()
+            - rule.src_port_start.unwrap_or_default()
+            + 1)
+        .saturating_mul(
+            rule.dst_port_end.unwrap_or_default() - rule.dst_port_start.unwrap_or_default() + 1,
+        )
+        .saturating_mul(net_count(&rule.src_net)?)
+        .saturating_mul(net_count(&rule.dst_net)?);
+
+        total_rules = match total_rules.overflowing_add(rule_count) {
+            (_, true) => {
+                return Err(CarbideError::InvalidArgument(format!(
+                    "expanded rule set contains more than {limit} maximum number of rules"
+                )));
             }
+            (v, false) => v,
+        };
+
+        if total_rules as usize > limit {
+            return Err(CarbideError::InvalidArgument(format!(
+                "expanded rule set contains more than {limit} maximum number of rules"
+            )));
         }
     }
 
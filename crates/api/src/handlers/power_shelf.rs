@@ -19,12 +19,14 @@ use ::rpc::errors::RpcDataConversionError;
 use ::rpc::forge::{self as rpc, HealthReportEntry};
 use db::{ObjectColumnFilter, power_shelf as db_power_shelf};
 use health_report::HealthReportApplyMode;
+use model::health_silence::HealthSilenceObjectKind;
 use model::metadata::Metadata;
 use tonic::{Request, Response, Status};
 
 use crate::CarbideError;
 use crate::api::{Api, log_request_data};
 use crate::auth::AuthContext;
+use crate::handlers::health_silence;
 
 pub async fn find_power_shelf(
     api: &Api,
@@ -128,6 +130,7 @@ pub async fn find_by_ids(
         ObjectColumnFilter::List(db_power_shelf::IdColumn, &power_shelf_ids),
     )
     .await?;
+    let health_silences = db::health_silence::find_active(&mut txn).await?;
 
     let bmc_info_map: std::collections::HashMap<_, _> = {
         let rows = db_power_shelf::find_bmc_info_by_power_shelf_ids(&mut txn, &power_shelf_ids)
@@ -159,9 +162,21 @@ pub async fn find_by_ids(
         .map(|ps| {
             let id = ps.id;
             let bmc_info = bmc_info_map.get(&id).cloned();
+            let labels = ps.metadata.labels.clone();
+            let rack_id = ps.rack_id.as_ref().map(|rack_id| rack_id.to_string());
 
             rpc::PowerShelf::try_from(ps).map(|mut rpc_ps| {
                 rpc_ps.bmc_info = bmc_info;
+                if let Some(status) = rpc_ps.status.as_mut() {
+                    health_silence::apply_to_rpc_health(
+                        &mut status.health,
+                        &health_silences,
+                        HealthSilenceObjectKind::PowerShelf,
+                        id.to_string(),
+                        &labels,
+                        rack_id,
+                    );
+                }
                 rpc_ps
             })
         })
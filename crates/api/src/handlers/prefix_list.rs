@@ -0,0 +1,440 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::errors::RpcDataConversionError;
+use ::rpc::forge as rpc;
+use carbide_uuid::prefix_list::PrefixListId;
+use config_version::ConfigVersion;
+use db::{network_security_group, prefix_list};
+use ipnetwork::IpNetwork;
+use model::metadata::Metadata;
+use model::prefix_list::{PrefixList, prefixes_from_rpc};
+use model::tenant::{InvalidTenantOrg, TenantOrganizationId};
+use tonic::{Request, Response, Status};
+use uuid::Uuid;
+
+use crate::CarbideError;
+use crate::api::{Api, log_request_data, log_tenant_organization_id};
+use crate::handlers::network_security_group::{
+    find_referenced_prefix_lists, validate_expanded_rule_set,
+};
+
+/// Converts the prefixes of a request.  A list can never be larger
+/// than the largest rule set it could be used in.
+fn prefixes_from_request(
+    api: &Api,
+    attributes: Option<rpc::PrefixListAttributes>,
+) -> Result<Vec<IpNetwork>, CarbideError> {
+    let prefixes = prefixes_from_rpc(attributes.unwrap_or_default())?;
+
+    let max_nsg_size = api
+        .runtime_config
+        .network_security_group
+        .max_network_security_group_size as usize;
+
+    if prefixes.len() > max_nsg_size {
+        return Err(CarbideError::from(RpcDataConversionError::InvalidValue(
+            "prefixes".to_string(),
+            format!("prefix list contains more than {max_nsg_size} maximum number of prefixes"),
+        )));
+    }
+
+    Ok(prefixes)
+}
+
+pub(crate) async fn create(
+    api: &Api,
+    request: Request<rpc::CreatePrefixListRequest>,
+) -> Result<Response<rpc::CreatePrefixListResponse>, Status> {
+    log_request_data(&request);
+
+    let req = request.into_inner();
+
+    // Get the ID from the request
+    let id = match req.id {
+        None => PrefixListId::from(Uuid::new_v4()),
+        Some(i) => i,
+    };
+
+    // Prepare the metadata
+    let metadata = match req.metadata {
+        Some(m) => Metadata::try_from(m).map_err(CarbideError::from)?,
+        _ => {
+            return Err(
+                CarbideError::from(RpcDataConversionError::MissingArgument("metadata")).into(),
+            );
+        }
+    };
+
+    metadata.validate(true).map_err(CarbideError::from)?;
+
+    let prefixes = prefixes_from_request(api, req.attributes)?;
+
+    // Log tenant organization ID
+    log_tenant_organization_id(&req.tenant_organization_id);
+
+    // Parse tenant organization ID
+    let tenant_organization_id =
+        req.tenant_organization_id
+            .parse()
+            .map_err(|e: InvalidTenantOrg| {
+                CarbideError::from(RpcDataConversionError::InvalidTenantOrg(e.to_string()))
+            })?;
+
+    // Start a new transaction for a db write.
+    let mut txn = api.txn_begin().await?;
+
+    let prefix_list = prefix_list::create(
+        &mut txn,
+        &id,
+        &tenant_organization_id,
+        None,
+        &metadata,
+        &prefixes,
+    )
+    .await?;
+
+    // Prepare the response to send back
+    let rpc_out = rpc::CreatePrefixListResponse {
+        prefix_list: Some(prefix_list.into()),
+    };
+
+    //  Commit our txn if nothing has gone wrong so far.
+    txn.commit().await?;
+
+    // Send our response back.
+    Ok(Response::new(rpc_out))
+}
+
+pub(crate) async fn find_ids(
+    api: &Api,
+    request: Request<rpc::FindPrefixListIdsRequest>,
+) -> Result<Response<rpc::FindPrefixListIdsResponse>, Status> {
+    log_request_data(&request);
+
+    let req = request.into_inner();
+
+    // Log tenant organization ID if present
+    if let Some(ref tenant_org_id) = req.tenant_organization_id {
+        log_tenant_organization_id(tenant_org_id);
+    }
+
+    let tenant_organization_id = req
+        .tenant_organization_id
+        .map(|t| t.parse::<TenantOrganizationId>())
+        .transpose()
+        .map_err(|e: InvalidTenantOrg| {
+            CarbideError::from(RpcDataConversionError::InvalidTenantOrg(e.to_string()))
+        })?;
+
+    let mut txn = api.txn_begin().await?;
+
+    let prefix_list_ids = prefix_list::find_ids(
+        &mut txn,
+        req.name.as_deref(),
+        tenant_organization_id.as_ref(),
+        false,
+    )
+    .await?;
+
+    txn.commit().await?;
+
+    Ok(Response::new(rpc::FindPrefixListIdsResponse {
+        prefix_list_ids,
+    }))
+}
+
+pub(crate) async fn find_by_ids(
+    api: &Api,
+    request: Request<rpc::FindPrefixListsByIdsRequest>,
+) -> Result<Response<rpc::FindPrefixListsByIdsResponse>, Status> {
+    log_request_data(&request);
+
+    let req = request.into_inner();
+
+    let max_find_by_ids = api.runtime_config.max_find_by_ids as usize;
+    if req.prefix_list_ids.len() > max_find_by_ids {
+        return Err(CarbideError::InvalidArgument(format!(
+            "no more than {max_find_by_ids} IDs can be submitted"
+        ))
+        .into());
+    }
+
+    if req.prefix_list_ids.is_empty() {
+        return Err(
+            CarbideError::InvalidArgument("at least one ID must be provided".to_string()).into(),
+        );
+    }
+
+    // Log tenant organization ID if present
+    if let Some(ref tenant_org_id) = req.tenant_organization_id {
+        log_tenant_organization_id(tenant_org_id);
+    }
+
+    let tenant_organization_id = req
+        .tenant_organization_id
+        .map(|t| t.parse::<TenantOrganizationId>())
+        .transpose()
+        .map_err(|e: InvalidTenantOrg| {
+            CarbideError::from(RpcDataConversionError::InvalidTenantOrg(e.to_string()))
+        })?;
+
+    let mut txn = api.txn_begin().await?;
+
+    let prefix_lists = prefix_list::find_by_ids(
+        &mut txn,
+        &req.prefix_list_ids,
+        tenant_organization_id.as_ref(),
+        false,
+    )
+    .await?;
+
+    let rpc_out = rpc::FindPrefixListsByIdsResponse {
+        prefix_lists: prefix_lists.into_iter().map(|p| p.into()).collect(),
+    };
+
+    txn.commit().await?;
+
+    Ok(Response::new(rpc_out))
+}
+
+pub(crate) async fn update(
+    api: &Api,
+    request: Request<rpc::UpdatePrefixListRequest>,
+) -> Result<Response<rpc::UpdatePrefixListResponse>, Status> {
+    log_request_data(&request);
+
+    let req = request.into_inner();
+
+    let id = req
+        .id
+        .ok_or(CarbideError::from(RpcDataConversionError::MissingArgument(
+            "id",
+        )))?;
+
+    // Prepare the metadata
+    let metadata = match req.metadata {
+        Some(m) => Metadata::try_from(m).map_err(CarbideError::from)?,
+        _ => {
+            return Err(
+                CarbideError::from(RpcDataConversionError::MissingArgument("metadata")).into(),
+            );
+        }
+    };
+
+    metadata.validate(true).map_err(CarbideError::from)?;
+
+    let prefixes = prefixes_from_request(api, req.attributes)?;
+
+    // Log tenant organization ID from request
+    log_tenant_organization_id(&req.tenant_organization_id);
+
+    // Parse tenant organization ID
+    let tenant_organization_id =
+        req.tenant_organization_id
+            .parse()
+            .map_err(|e: InvalidTenantOrg| {
+                CarbideError::from(RpcDataConversionError::InvalidTenantOrg(e.to_string()))
+            })?;
+
+    // Start a new transaction for a db write.
+    let mut txn = api.txn_begin().await?;
+
+    let Some(current_prefix_list) = prefix_list::find_by_ids(
+        &mut txn,
+        std::slice::from_ref(&id),
+        Some(&tenant_organization_id),
+        true,
+    )
+    .await?
+    .pop() else {
+        return Err(CarbideError::NotFoundError {
+            kind: "PrefixList",
+            id: format!(
+                "{id} for tenant org `{}`",
+                req.tenant_organization_id.clone(),
+            ),
+        }
+        .into());
+    };
+
+    // Prepare the version match if present.
+    if let Some(if_version_match) = req.if_version_match {
+        let target_version = if_version_match
+            .parse::<ConfigVersion>()
+            .map_err(CarbideError::from)?;
+
+        if current_prefix_list.version != target_version {
+            return Err(CarbideError::ConcurrentModificationError(
+                "PrefixList",
+                target_version.to_string(),
+            )
+            .into());
+        }
+    };
+
+    // Every NetworkSecurityGroup that references the list has to stay
+    // within limits with the new prefixes, so lock them all and check
+    // their rule sets as they would be expanded after the update.
+    let referencing_network_security_groups =
+        network_security_group::find_by_prefix_list_ids(&mut txn, std::slice::from_ref(&id), true)
+            .await?;
+
+    let max_nsg_size = api
+        .runtime_config
+        .network_security_group
+        .max_network_security_group_size as usize;
+
+    let updated_prefix_list = PrefixList {
+        prefixes: prefixes.clone(),
+        ..current_prefix_list.clone()
+    };
+
+    for nsg in &referencing_network_security_groups {
+        let mut prefix_lists =
+            find_referenced_prefix_lists(&mut txn, &nsg.rules, &nsg.tenant_organization_id).await?;
+        prefix_lists.insert(id, updated_prefix_list.clone());
+
+        validate_expanded_rule_set(&nsg.rules, &prefix_lists, max_nsg_size).map_err(|e| {
+            CarbideError::FailedPrecondition(format!(
+                "PrefixList {id} is referenced by NetworkSecurityGroup {}, which would become invalid: {e}",
+                nsg.id
+            ))
+        })?;
+    }
+
+    // Update record in the DB and get back
+    // our new PrefixList state.
+    let prefix_list = prefix_list::update(
+        &mut txn,
+        &id,
+        &tenant_organization_id,
+        &metadata,
+        &prefixes,
+        current_prefix_list.version,
+        None,
+    )
+    .await?;
+
+    // Only a change to the prefixes changes what the DPUs need to apply.
+    // A new version for each referencing NetworkSecurityGroup is what
+    // sends the change to the DPUs and lets propagation status follow it.
+    let mut network_security_group_ids = Vec::new();
+    if prefix_list.prefixes != current_prefix_list.prefixes {
+        for nsg in referencing_network_security_groups {
+            network_security_group::increment_version(&mut txn, &nsg.id, nsg.version, None).await?;
+            network_security_group_ids.push(nsg.id.to_string());
+        }
+    }
+
+    // Prepare the response to send back
+    let rpc_out = rpc::UpdatePrefixListResponse {
+        prefix_list: Some(prefix_list.into()),
+        network_security_group_ids,
+    };
+
+    // Commit our txn if nothing has gone wrong so far.
+    txn.commit().await?;
+
+    // Send our response back.
+    Ok(Response::new(rpc_out))
+}
+
+pub(crate) async fn delete(
+    api: &Api,
+    request: Request<rpc::DeletePrefixListRequest>,
+) -> Result<Response<rpc::DeletePrefixListResponse>, Status> {
+    log_request_data(&request);
+
+    let req = request.into_inner();
+
+    let id = req
+        .id
+        .ok_or(CarbideError::from(RpcDataConversionError::MissingArgument(
+            "id",
+        )))?;
+
+    // Log tenant organization ID from request
+    log_tenant_organization_id(&req.tenant_organization_id);
+
+    // Parse tenant organization ID
+    let tenant_organization_id =
+        req.tenant_organization_id
+            .parse()
+            .map_err(|e: InvalidTenantOrg| {
+                CarbideError::from(RpcDataConversionError::InvalidTenantOrg(e.to_string()))
+            })?;
+
+    // Prepare our txn to delete from the DB
+    let mut txn = api.txn_begin().await?;
+
+    // Lock the record so a NetworkSecurityGroup can't start referencing
+    // it while we check for references.
+    let prefix_list = prefix_list::find_by_ids(
+        &mut txn,
+        std::slice::from_ref(&id),
+        // We'll check tenant ownership separately from the query here so we don't hide a
+        // 404 due to a mismatched tenant.
+        None,
+        true,
+    )
+    .await?
+    .pop();
+
+    let Some(prefix_list) = prefix_list else {
+        return Err(CarbideError::NotFoundError {
+            kind: "PrefixList",
+            id: id.to_string(),
+        }
+        .into());
+    };
+
+    if prefix_list.tenant_organization_id != tenant_organization_id {
+        return Err(CarbideError::InvalidArgument(format!(
+            "PrefixList `{id}` is not owned by Tenant `{tenant_organization_id}`"
+        ))
+        .into());
+    }
+
+    // A referenced list must not be deleted.
+    let referencing_network_security_groups =
+        network_security_group::find_by_prefix_list_ids(&mut txn, std::slice::from_ref(&id), false)
+            .await?;
+
+    if !referencing_network_security_groups.is_empty() {
+        return Err(CarbideError::FailedPrecondition(format!(
+            "PrefixList {id} is referenced by NetworkSecurityGroups {}",
+            referencing_network_security_groups
+                .iter()
+                .map(|nsg| nsg.id.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))
+        .into());
+    }
+
+    let _id = prefix_list::soft_delete(&mut txn, &id, &tenant_organization_id).await?;
+
+    // Prepare the response message
+    let rpc_out = rpc::DeletePrefixListResponse {};
+
+    // Commit if nothing has gone wrong up to now
+    txn.commit().await?;
+
+    // Send our response back
+    Ok(Response::new(rpc_out))
+}
@@ -30,14 +30,16 @@ use db::{
 };
 use futures_util::FutureExt;
 use health_report::HealthReportApplyMode;
+use model::health_silence::HealthSilenceObjectKind;
 use model::machine::machine_search_config::MachineSearchConfig;
 use model::metadata::Metadata;
-use model::rack::{MaintenanceActivity, MaintenanceScope, RackState};
+use model::rack::{MaintenanceActivity, MaintenanceScope, RackLeakResponseState, RackState};
 use tonic::{Request, Response, Status};
 
 use crate::CarbideError;
 use crate::api::{Api, log_request_data};
 use crate::auth::AuthContext;
+use crate::handlers::health_silence;
 
 pub async fn get_rack(
     api: &Api,
@@ -144,6 +146,7 @@ pub async fn find_by_ids(
         ObjectColumnFilter::List(db::rack::IdColumn, &rack_ids),
     )
     .await?;
+    let health_silences = db::health_silence::find_active(&mut txn).await?;
 
     let mut result = Vec::with_capacity(racks.len());
     for rack in racks {
@@ -178,7 +181,19 @@ pub async fn find_by_ids(
             db_expected_power_shelf::find_all_by_rack_id(&mut txn, &rack.id).await?;
         let expected_nvlink_switches =
             db_expected_switch::find_all_by_rack_id(&mut txn, &rack.id).await?;
+        let rack_id = rack.id.to_string();
+        let labels = rack.metadata.labels.clone();
         let mut rpc_rack: rpc::Rack = rack.into();
+        if let Some(status) = rpc_rack.status.as_mut() {
+            health_silence::apply_to_rpc_health(
+                &mut status.health,
+                &health_silences,
+                HealthSilenceObjectKind::Rack,
+                rack_id.clone(),
+                &labels,
+                Some(rack_id),
+            );
+        }
         rpc_rack.compute_trays = machine_ids;
         rpc_rack.switches = switch_ids;
         rpc_rack.power_shelves = power_shelf_ids;
@@ -557,10 +572,14 @@ pub(crate) async fn on_demand_rack_maintenance(
 
     if !matches!(
         *rack.controller_state,
-        RackState::Ready | RackState::Error { .. }
+        RackState::Ready
+            | RackState::Error { .. }
+            | RackState::LeakResponse {
+                leak_response_state: RackLeakResponseState::Isolated,
+            }
     ) {
         return Err(CarbideError::InvalidArgument(format!(
-            "Rack {} is not in Ready or Error state (current: {:?}). Maintenance can only be requested when the rack is Ready or in Error.",
+            "Rack {} is not in Ready or Error state (current: {:?}). Maintenance can only be requested when the rack is Ready, in Error or isolated after a leak.",
             rack_id, *rack.controller_state
         ))
         .into());
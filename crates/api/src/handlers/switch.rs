@@ -19,12 +19,14 @@ use ::rpc::errors::RpcDataConversionError;
 use ::rpc::forge::{self as rpc, HealthReportEntry};
 use db::{ObjectColumnFilter, switch as db_switch};
 use health_report::HealthReportApplyMode;
+use model::health_silence::HealthSilenceObjectKind;
 use model::metadata::Metadata;
 use tonic::{Request, Response, Status};
 
 use crate::CarbideError;
 use crate::api::{Api, log_request_data};
 use crate::auth::AuthContext;
+use crate::handlers::health_silence;
 
 pub async fn find_switch(
     api: &Api,
@@ -156,6 +158,7 @@ pub async fn find_by_ids(
         ObjectColumnFilter::List(db_switch::IdColumn, &switch_ids),
     )
     .await?;
+    let health_silences = db::health_silence::find_active(&mut txn).await?;
 
     let bmc_info_map: std::collections::HashMap<_, _> = {
         let rows = db_switch::find_bmc_info_by_switch_ids(&mut txn, &switch_ids)
@@ -187,9 +190,21 @@ pub async fn find_by_ids(
         .map(|s| {
             let id = s.id;
             let bmc_info = bmc_info_map.get(&id).cloned();
+            let labels = s.metadata.labels.clone();
+            let rack_id = s.rack_id.as_ref().map(|rack_id| rack_id.to_string());
 
             rpc::Switch::try_from(s).map(|mut rpc_switch| {
                 rpc_switch.bmc_info = bmc_info;
+                if let Some(status) = rpc_switch.status.as_mut() {
+                    health_silence::apply_to_rpc_health(
+                        &mut status.health,
+                        &health_silences,
+                        HealthSilenceObjectKind::Switch,
+                        id.to_string(),
+                        &labels,
+                        rack_id,
+                    );
+                }
                 rpc_switch
             })
         })
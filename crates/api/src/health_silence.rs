@@ -0,0 +1,117 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Records the expiry of health silences.
+//!
+//! Silences stop applying as soon as their expiry time passes, since they are
+//! only loaded while active. The `HealthSilenceExpiry` additionally sets
+//! `ended_at` on expired silences, so that the table holds a complete record of
+//! when each silence applied.
+
+use std::io;
+use std::time::Duration;
+
+use carbide_utils::periodic_timer::PeriodicTimer;
+use db::work_lock_manager::WorkLockManagerHandle;
+use sqlx::PgPool;
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+
+use crate::CarbideResult;
+
+pub struct HealthSilenceExpiry {
+    database_connection: PgPool,
+    work_lock_manager_handle: WorkLockManagerHandle,
+}
+
+impl HealthSilenceExpiry {
+    const ITERATION_WORK_KEY: &'static str = "HealthSilenceExpiry::run_single_iteration";
+    const RUN_INTERVAL: Duration = Duration::from_secs(60);
+
+    pub fn new(
+        database_connection: PgPool,
+        work_lock_manager_handle: WorkLockManagerHandle,
+    ) -> Self {
+        HealthSilenceExpiry {
+            database_connection,
+            work_lock_manager_handle,
+        }
+    }
+
+    /// Start the HealthSilenceExpiry. It stops once `cancel_token` is cancelled.
+    pub fn start(
+        self,
+        join_set: &mut JoinSet<()>,
+        cancel_token: CancellationToken,
+    ) -> io::Result<()> {
+        join_set
+            .build_task()
+            .name("health_silence_expiry")
+            .spawn(async move { self.run(cancel_token).await })?;
+        Ok(())
+    }
+
+    async fn run(&self, cancel_token: CancellationToken) {
+        let timer = PeriodicTimer::new(Self::RUN_INTERVAL);
+        loop {
+            let tick = timer.tick();
+            if let Err(e) = self.run_single_iteration().await {
+                tracing::warn!("HealthSilenceExpiry error: {}", e);
+            }
+
+            tokio::select! {
+                _ = tick.sleep() => {},
+                _ = cancel_token.cancelled() => {
+                    tracing::info!("Health silence expiry stop was requested");
+                    return;
+                }
+            }
+        }
+    }
+
+    pub async fn run_single_iteration(&self) -> CarbideResult<()> {
+        let _lock = match self
+            .work_lock_manager_handle
+            .try_acquire_lock(Self::ITERATION_WORK_KEY.into())
+            .await
+        {
+            Ok(lock) => lock,
+            Err(e) => {
+                tracing::warn!(
+                    "HealthSilenceExpiry failed to acquire work lock: Another instance of carbide running? {e}"
+                );
+                return Ok(());
+            }
+        };
+
+        let mut txn = db::Transaction::begin(&self.database_connection).await?;
+        let expired = db::health_silence::mark_expired(&mut txn).await?;
+        txn.commit().await?;
+
+        for silence in expired {
+            tracing::info!(
+                silence_id = silence.id,
+                author = %silence.author,
+                reason = %silence.reason,
+                expires_at = %silence.expires_at,
+                "Health silence expired"
+            );
+        }
+
+        Ok(())
+    }
+}
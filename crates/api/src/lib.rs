@@ -32,11 +32,13 @@
 // working dead-code detection: If modules here are public, rust will not find dead code for
 // anything marked `pub` within the module.
 
+mod alert_dispatch;
 mod api;
 mod attestation;
 mod auth;
 mod cfg;
 mod compat;
+mod connectivity;
 mod credentials;
 mod db_init;
 mod dhcp;
@@ -47,6 +49,7 @@ mod dynamic_settings;
 mod errors;
 mod ethernet_virtualization;
 mod handlers;
+mod health_silence;
 mod instance;
 mod ipxe;
 mod listener;
@@ -57,8 +60,10 @@ mod machine_validation;
 mod measured_boot;
 mod mqtt_state_change_hook;
 mod network_segment;
+mod power_control;
 mod rack;
 mod redfish;
+mod retention;
 mod run;
 mod scout_stream;
 mod setup;
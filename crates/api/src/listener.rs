@@ -41,6 +41,7 @@ use tower_http::auth::AsyncRequireAuthorizationLayer;
 use crate::api::Api;
 use crate::auth;
 use crate::auth::Authorization;
+use crate::auth::policy_source::{DatabasePolicySource, FilePolicySource, PolicySource};
 use crate::cfg::file::AuthConfig;
 use crate::errors::CarbideError;
 use crate::logging::api_logs::LogLayer;
@@ -219,18 +220,32 @@ pub async fn start(
     let cert_description_layer: CertDescriptionMiddleware<Authorization> =
         CertDescriptionMiddleware::new(extra_cli_certs, spiffe_context);
     let casbin_layer = if let Some(auth_config) = auth_config {
+        let mut policy_sources: Vec<Box<dyn PolicySource>> = Vec::new();
         if let Some(casbin_policy_file) = &auth_config.casbin_policy_file {
-            let casbin_authorizer = Arc::new(
-                auth::CasbinAuthorizer::build_casbin(
-                    casbin_policy_file,
-                    auth_config.permissive_mode,
-                )
-                .await?,
+            policy_sources.push(Box::new(FilePolicySource::new(casbin_policy_file.clone())));
+        }
+        if auth_config.casbin_policy_from_database {
+            policy_sources.push(Box::new(DatabasePolicySource::new(
+                api_service.database_connection.clone(),
+            )));
+        }
+
+        if policy_sources.is_empty() {
+            None
+        } else {
+            let (casbin_authorizer, policy_reloader) = auth::CasbinAuthorizer::build_casbin(
+                policy_sources,
+                auth_config.permissive_mode,
+                &meter,
+            )
+            .await?;
+            policy_reloader.start(
+                join_set,
+                auth_config.policy_reload_interval,
+                cancel_token.clone(),
             );
-            let middleware = auth::middleware::CasbinHandler::new(casbin_authorizer);
+            let middleware = auth::middleware::CasbinHandler::new(Arc::new(casbin_authorizer));
             Some(AsyncRequireAuthorizationLayer::new(middleware))
-        } else {
-            None
         }
     } else {
         None
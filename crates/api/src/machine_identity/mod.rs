@@ -20,10 +20,12 @@
 //! This module handles signing JWT-SVID tokens for machine identity verification.
 //! [`crypto`] holds AES envelope helpers for `tenant_identity_config` ciphertext.
 //! [`token_exchange`] implements RFC 8693 HTTP calls to a tenant token endpoint.
+//! [`x509`] derives the per-tenant X.509-SVID root CA and signs machine intermediates.
 #![allow(dead_code)] // Signer, Es256Signer, SignOptions, crypto, token_exchange: tests + handler
 
 mod crypto;
 mod token_exchange;
+mod x509;
 
 use std::collections::BTreeMap;
 use std::fmt;
@@ -40,6 +42,7 @@ use p256::elliptic_curve::sec1::ToEncodedPoint;
 use p256::pkcs8::DecodePublicKey;
 use serde_json::Value;
 pub(crate) use token_exchange::{token_exchange_http_client, token_exchange_request};
+pub(crate) use x509::{TenantX509Authority, X509AuthorityError, trust_domain_id};
 
 /// Error type for JWT-SVID signing.
 #[derive(Debug, thiserror::Error)]
@@ -52,7 +55,10 @@ pub enum SignError {
 
 /// Options for signing (e.g. future overrides for expiry, audience).
 #[derive(Debug, Default, Clone)]
-pub struct SignOptions {}
+pub struct SignOptions {
+    /// JWT header `typ`. Defaults to `JWT`, which identity tokens use.
+    pub typ: Option<String>,
+}
 
 /// Abstraction for signing JWT-SVID tokens. Key loading and metadata (e.g. from DB)
 /// stay outside: the caller builds a signer and passes it here.
@@ -86,7 +92,7 @@ impl Es256Signer {
 }
 
 impl Signer for Es256Signer {
-    fn sign(&self, payload: &Value, _opts: &SignOptions) -> Result<String, SignError> {
+    fn sign(&self, payload: &Value, opts: &SignOptions) -> Result<String, SignError> {
         let claims = payload
             .as_object()
             .ok_or_else(|| SignError::InvalidPayload("payload must be a JSON object".to_string()))?
@@ -96,6 +102,9 @@ impl Signer for Es256Signer {
 
         let mut header = Header::new(jsonwebtoken::Algorithm::ES256);
         header.kid = Some(self.key_id.clone());
+        if let Some(typ) = &opts.typ {
+            header.typ = Some(typ.clone());
+        }
         let token = encode(&header, &claims, &self.encoding_key)?;
         Ok(token)
     }
@@ -0,0 +1,352 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Per-tenant X.509 authority for X.509-SVIDs.
+//!
+//! The root CA is derived from a dedicated tenant CA key, which is kept apart from the key signing
+//! JWT-SVIDs. Every field of the root is a function of the CA key and its creation time; only the
+//! ECDSA signature differs between derivations, and relying parties verify chains by subject and
+//! public key. The root signs one CA intermediate per machine, whose key never leaves the DPU; the
+//! DPU then issues the short-lived leaf SVIDs itself. Intermediates are name constrained to the
+//! trust domain, so a compromised DPU cannot issue SVIDs for other trust domains.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rcgen::string::Ia5String;
+use rcgen::{
+    BasicConstraints, Certificate, CertificateParams, CertificateSigningRequestParams,
+    CustomExtension, DistinguishedName, DnType, IsCa, KeyPair, KeyUsagePurpose, SanType,
+    SerialNumber,
+};
+use sha2::{Digest, Sha256};
+use time::OffsetDateTime;
+
+/// Validity of the derived tenant root CA, counted from the identity config creation time.
+const ROOT_VALIDITY: Duration = Duration::from_secs(10 * 365 * 24 * 60 * 60);
+
+/// Tolerated clock skew between carbide-api and the DPU when back-dating `notBefore`.
+const NOT_BEFORE_SKEW: Duration = Duration::from_secs(5 * 60);
+
+/// OID of the X.509 name constraints extension.
+const NAME_CONSTRAINTS_OID: &[u64] = &[2, 5, 29, 30];
+
+/// Failure building the tenant root or signing an intermediate.
+#[derive(Debug, thiserror::Error)]
+pub enum X509AuthorityError {
+    #[error("invalid signing key: {0}")]
+    InvalidKey(rcgen::Error),
+    #[error("invalid certificate signing request: {0}")]
+    InvalidCsr(rcgen::Error),
+    #[error("invalid SPIFFE ID {0:?}")]
+    InvalidSpiffeId(String),
+    #[error("tenant root CA has expired")]
+    RootExpired,
+    #[error("certificate generation failed: {0}")]
+    Generate(rcgen::Error),
+}
+
+/// Intermediate CA certificate signed for one machine.
+#[derive(Debug)]
+pub struct SignedIntermediate {
+    /// DER encoded intermediate certificate.
+    pub certificate: Vec<u8>,
+    /// Time until the intermediate expires.
+    pub expires_in: Duration,
+}
+
+/// SPIFFE ID of a trust domain (`spiffe://<trust-domain>`).
+pub fn trust_domain_id(trust_domain: &str) -> String {
+    format!("spiffe://{trust_domain}")
+}
+
+/// Tenant root CA rebuilt from the tenant CA key.
+pub struct TenantX509Authority {
+    key_pair: KeyPair,
+    root: Certificate,
+    not_after: OffsetDateTime,
+    trust_domain: String,
+}
+
+impl TenantX509Authority {
+    /// Derives the root CA for a trust domain from the tenant CA key (PKCS#8 PEM) created at
+    /// `created_at`.
+    ///
+    /// The public key seeds the serial number, so rotating the CA key also rotates the root.
+    pub fn new(
+        private_key_pem: &str,
+        trust_domain: &str,
+        created_at: DateTime<Utc>,
+    ) -> Result<Self, X509AuthorityError> {
+        let key_pair =
+            KeyPair::from_pem(private_key_pem).map_err(X509AuthorityError::InvalidKey)?;
+
+        let not_before = OffsetDateTime::from_unix_timestamp(created_at.timestamp())
+            .unwrap_or(OffsetDateTime::UNIX_EPOCH);
+        let not_after = not_before + ROOT_VALIDITY;
+
+        let mut params = CertificateParams::default();
+        params.distinguished_name = distinguished_name(&format!("{trust_domain} root CA"));
+        params.subject_alt_names = vec![spiffe_san(&trust_domain_id(trust_domain))?];
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        params.key_usages = vec![
+            KeyUsagePurpose::KeyCertSign,
+            KeyUsagePurpose::CrlSign,
+            KeyUsagePurpose::DigitalSignature,
+        ];
+        params.serial_number = Some(serial_from(&key_pair.public_key_der()));
+        params.not_before = not_before;
+        params.not_after = not_after;
+
+        let root = params
+            .self_signed(&key_pair)
+            .map_err(X509AuthorityError::Generate)?;
+
+        Ok(Self {
+            key_pair,
+            root,
+            not_after,
+            trust_domain: trust_domain.to_string(),
+        })
+    }
+
+    /// Trust domain the root was derived for.
+    pub fn trust_domain(&self) -> &str {
+        &self.trust_domain
+    }
+
+    /// DER encoded root certificate, i.e. the X.509 bundle of the trust domain.
+    pub fn root_der(&self) -> Vec<u8> {
+        self.root.der().to_vec()
+    }
+
+    /// Signs a CA intermediate for `spiffe_id` over the public key of a DER encoded PKCS#10 CSR.
+    ///
+    /// Only the public key is taken from the CSR: subject, SAN, and constraints are set here so
+    /// the intermediate can issue leaf SVIDs (path length 0) of the trust domain but nothing else.
+    /// The lifetime is `ttl`, capped at the root expiry.
+    pub fn sign_intermediate(
+        &self,
+        csr_der: &[u8],
+        spiffe_id: &str,
+        ttl: Duration,
+        now: DateTime<Utc>,
+    ) -> Result<SignedIntermediate, X509AuthorityError> {
+        let mut csr = CertificateSigningRequestParams::from_der(&csr_der.to_vec().into())
+            .map_err(X509AuthorityError::InvalidCsr)?;
+
+        let now = OffsetDateTime::from_unix_timestamp(now.timestamp())
+            .unwrap_or(OffsetDateTime::UNIX_EPOCH);
+        if now >= self.not_after {
+            return Err(X509AuthorityError::RootExpired);
+        }
+        let not_after = (now + ttl).min(self.not_after);
+
+        let mut params = CertificateParams::default();
+        params.distinguished_name =
+            distinguished_name(&format!("{} intermediate CA", self.trust_domain));
+        params.subject_alt_names = vec![spiffe_san(spiffe_id)?];
+        params.is_ca = IsCa::Ca(BasicConstraints::Constrained(0));
+        params.key_usages = vec![
+            KeyUsagePurpose::KeyCertSign,
+            KeyUsagePurpose::CrlSign,
+            KeyUsagePurpose::DigitalSignature,
+        ];
+        params.custom_extensions = vec![trust_domain_name_constraints(&self.trust_domain)?];
+        params.use_authority_key_identifier_extension = true;
+        params.serial_number = Some(serial_from(&rand::random::<[u8; 32]>()));
+        params.not_before = now - NOT_BEFORE_SKEW;
+        params.not_after = not_after;
+        csr.params = params;
+
+        let certificate = csr
+            .signed_by(&self.root, &self.key_pair)
+            .map_err(X509AuthorityError::Generate)?;
+
+        Ok(SignedIntermediate {
+            certificate: certificate.der().to_vec(),
+            expires_in: Duration::try_from(not_after - now).unwrap_or_default(),
+        })
+    }
+}
+
+fn distinguished_name(common_name: &str) -> DistinguishedName {
+    let mut dn = DistinguishedName::new();
+    dn.push(DnType::OrganizationName, "Carbide");
+    dn.push(DnType::CommonName, common_name);
+    dn
+}
+
+fn spiffe_san(spiffe_id: &str) -> Result<SanType, X509AuthorityError> {
+    Ia5String::try_from(spiffe_id)
+        .map(SanType::URI)
+        .map_err(|_| X509AuthorityError::InvalidSpiffeId(spiffe_id.to_string()))
+}
+
+/// Critical name constraints extension which only permits URIs in `trust_domain`.
+///
+/// RFC 5280 matches URI constraints against the host of the URI, so the permitted subtree is the
+/// bare trust domain, which covers every `spiffe://<trust-domain>/...` ID. rcgen has no URI
+/// subtrees, so the extension is DER encoded here.
+fn trust_domain_name_constraints(
+    trust_domain: &str,
+) -> Result<CustomExtension, X509AuthorityError> {
+    if !trust_domain.is_ascii() {
+        return Err(X509AuthorityError::InvalidSpiffeId(trust_domain_id(
+            trust_domain,
+        )));
+    }
+    // NameConstraints ::= SEQUENCE { permittedSubtrees [0] GeneralSubtrees }
+    // GeneralSubtree ::= SEQUENCE { base GeneralName }, base is uniformResourceIdentifier [6]
+    let uri = der_tlv(0x86, trust_domain.as_bytes());
+    let subtree = der_tlv(0x30, &uri);
+    let permitted_subtrees = der_tlv(0xa0, &subtree);
+    let mut extension =
+        CustomExtension::from_oid_content(NAME_CONSTRAINTS_OID, der_tlv(0x30, &permitted_subtrees));
+    extension.set_criticality(true);
+    Ok(extension)
+}
+
+/// DER encodes a value with the given tag.
+fn der_tlv(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut encoded = vec![tag];
+    match u8::try_from(content.len()) {
+        Ok(len) if len < 0x80 => encoded.push(len),
+        _ => {
+            let len = content.len().to_be_bytes();
+            let len = &len[len.iter().take_while(|b| **b == 0).count()..];
+            encoded.push(0x80 | len.len() as u8);
+            encoded.extend_from_slice(len);
+        }
+    }
+    encoded.extend_from_slice(content);
+    encoded
+}
+
+/// Positive 128-bit serial derived from `seed`.
+fn serial_from(seed: &[u8]) -> SerialNumber {
+    let mut serial = Sha256::digest(seed)[..16].to_vec();
+    serial[0] &= 0x7f;
+    SerialNumber::from(serial)
+}
+
+#[cfg(test)]
+mod tests {
+    use rcgen::PKCS_ECDSA_P256_SHA256;
+    use x509_parser::prelude::{FromDer, GeneralName, ParsedExtension, X509Certificate};
+
+    use super::*;
+
+    fn authority() -> TenantX509Authority {
+        let key = KeyPair::generate_for(&PKCS_ECDSA_P256_SHA256).unwrap();
+        TenantX509Authority::new(&key.serialize_pem(), "td.example", Utc::now()).unwrap()
+    }
+
+    fn uri_sans(cert: &X509Certificate<'_>) -> Vec<String> {
+        cert.extensions()
+            .iter()
+            .filter_map(|ext| match ext.parsed_extension() {
+                ParsedExtension::SubjectAlternativeName(san) => Some(san),
+                _ => None,
+            })
+            .flat_map(|san| san.general_names.iter())
+            .filter_map(|name| match name {
+                GeneralName::URI(uri) => Some(uri.to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn root_carries_trust_domain_id() {
+        let authority = authority();
+        let der = authority.root_der();
+        let (_, root) = X509Certificate::from_der(&der).unwrap();
+        assert!(root.is_ca());
+        assert_eq!(uri_sans(&root), vec!["spiffe://td.example".to_string()]);
+    }
+
+    #[test]
+    fn intermediate_is_signed_by_root_and_constrained() {
+        let authority = authority();
+        let intermediate_key = KeyPair::generate_for(&PKCS_ECDSA_P256_SHA256).unwrap();
+        let csr = CertificateParams::default()
+            .serialize_request(&intermediate_key)
+            .unwrap();
+
+        let signed = authority
+            .sign_intermediate(
+                csr.der(),
+                "spiffe://td.example/org/machine",
+                Duration::from_secs(3600),
+                Utc::now(),
+            )
+            .unwrap();
+        assert_eq!(signed.expires_in, Duration::from_secs(3600));
+
+        let root_der = authority.root_der();
+        let (_, root) = X509Certificate::from_der(&root_der).unwrap();
+        let (_, cert) = X509Certificate::from_der(&signed.certificate).unwrap();
+        cert.verify_signature(Some(root.public_key())).unwrap();
+        assert_eq!(cert.public_key().raw, intermediate_key.public_key_der());
+        assert_eq!(
+            uri_sans(&cert),
+            vec!["spiffe://td.example/org/machine".to_string()]
+        );
+        let constraints = cert.basic_constraints().unwrap().unwrap().value;
+        assert!(constraints.ca);
+        assert_eq!(constraints.path_len_constraint, Some(0));
+
+        let name_constraints = cert.name_constraints().unwrap().unwrap();
+        assert!(name_constraints.critical);
+        let permitted: Vec<String> = name_constraints
+            .value
+            .permitted_subtrees
+            .iter()
+            .flatten()
+            .filter_map(|subtree| match &subtree.base {
+                GeneralName::URI(uri) => Some(uri.to_string()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(permitted, vec!["td.example".to_string()]);
+        assert!(name_constraints.value.excluded_subtrees.is_none());
+    }
+
+    #[test]
+    fn der_lengths() {
+        assert_eq!(der_tlv(0x04, &[1, 2]), vec![0x04, 0x02, 1, 2]);
+        let long = der_tlv(0x04, &[0; 200]);
+        assert_eq!(&long[..3], &[0x04, 0x81, 200]);
+        assert_eq!(long.len(), 203);
+        let longer = der_tlv(0x04, &[0; 300]);
+        assert_eq!(&longer[..4], &[0x04, 0x82, 0x01, 0x2c]);
+    }
+
+    #[test]
+    fn intermediate_rejects_garbage_csr() {
+        let err = authority()
+            .sign_intermediate(
+                b"not a csr",
+                "spiffe://td.example/org/machine",
+                Duration::from_secs(3600),
+                Utc::now(),
+            )
+            .unwrap_err();
+        assert!(matches!(err, X509AuthorityError::InvalidCsr(_)));
+    }
+}
@@ -656,7 +656,9 @@ mod tests {
         // now trim the table and verify that it has been trimmed down to 500
         let request = tonic::Request::new(rpc::forge::TrimTableRequest {
             target: rpc::forge::TrimTableTarget::MeasuredBoot as i32,
-            keep_entries: 250,
+            keep_entries: Some(250),
+            max_age: None,
+            keep_last: 0,
         });
 
         let response = env.api.trim_table(request).await?;
@@ -0,0 +1,273 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Power control of compute trays, shared by the component manager and the
+//! rack state controller.
+//!
+//! While the action is sent to the tray's BMC, a health report override
+//! suppresses external alerting for the host. The desired power state of the
+//! power manager is updated before the action is sent, so that the machine state
+//! controller does not revert it.
+
+use std::fmt::Display;
+
+use carbide_uuid::machine::MachineId;
+use db::DatabaseError;
+use health_report::{
+    HealthAlertClassification, HealthProbeAlert, HealthProbeId, HealthReport, HealthReportApplyMode,
+};
+use libredfish::SystemPowerControl;
+use model::component_manager::PowerAction;
+use model::machine::machine_search_config::MachineSearchConfig;
+use model::power_manager::PowerState;
+use sqlx::{PgConnection, PgPool};
+
+const MACHINE_POWER_OVERRIDE_SOURCE: &str = "component_power_control";
+const MACHINE_POWER_OVERRIDE_MESSAGE: &str = "Compute-Tray component power control in progress";
+
+/// Returns the power state the power manager should keep a host in after `action`.
+pub(crate) fn desired_power_state(action: PowerAction) -> PowerState {
+    match action {
+        PowerAction::On
+        | PowerAction::ForceRestart
+        | PowerAction::GracefulRestart
+        | PowerAction::AcPowercycle => PowerState::On,
+        PowerAction::GracefulShutdown | PowerAction::ForceOff => PowerState::Off,
+    }
+}
+
+pub(crate) fn redfish_power_action(action: PowerAction) -> SystemPowerControl {
+    match action {
+        PowerAction::On => SystemPowerControl::On,
+        PowerAction::ForceRestart => SystemPowerControl::ForceRestart,
+        PowerAction::GracefulRestart => SystemPowerControl::GracefulRestart,
+        PowerAction::AcPowercycle => SystemPowerControl::ACPowercycle,
+        PowerAction::GracefulShutdown => SystemPowerControl::GracefulShutdown,
+        PowerAction::ForceOff => SystemPowerControl::ForceOff,
+    }
+}
+
+/// Updates the desired power state the power manager keeps for a host.
+/// Hosts without power options are left alone.
+pub(crate) async fn set_desired_power_state(
+    txn: &mut PgConnection,
+    machine_id: &MachineId,
+    desired: PowerState,
+) -> Result<(), DatabaseError> {
+    let Some(options) = db::power_options::get_by_ids(&[*machine_id], &mut *txn)
+        .await?
+        .pop()
+    else {
+        return Ok(());
+    };
+    if options.desired_power_state == desired {
+        tracing::debug!(
+            %machine_id,
+            ?desired,
+            "power option already in desired state, skipping"
+        );
+        return Ok(());
+    }
+    db::power_options::update_desired_state(
+        machine_id,
+        desired,
+        &options.desired_power_state_version,
+        txn,
+    )
+    .await?;
+    Ok(())
+}
+
+/*
+machine_power_control facilitates power control against compute trays:
+    1. Suppresses external alerting for the host while the action is in progress
+    2. Configures the desired power state for the machine in the power-manager
+    3. Sends the redfish command to the compute tray's BMC via `send_power_action`
+On success, the power manager will have the desired power state set for the machine and the redfish command will have been successfully sent to the compute BMC
+In the case of partial failure, the power manager may have the desired power state updated for the machine but the redfish command may have failed. We will leave reconvergence in this case to the power manager.
+*/
+pub(crate) async fn machine_power_control<E, F, Fut>(
+    db_pool: &PgPool,
+    machine_id: MachineId,
+    action: PowerAction,
+    send_power_action: F,
+) -> Result<(), E>
+where
+    E: From<DatabaseError>,
+    F: FnOnce(SystemPowerControl) -> Fut,
+    Fut: Future<Output = Result<(), E>>,
+{
+    let override_inserted = power_control_health_override(db_pool, machine_id, true).await;
+
+    let result = async {
+        let mut txn = db::Transaction::begin(db_pool).await?;
+        set_desired_power_state(&mut txn, &machine_id, desired_power_state(action)).await?;
+        txn.commit().await?;
+
+        send_power_action(redfish_power_action(action)).await
+    }
+    .await;
+
+    if override_inserted {
+        power_control_health_override(db_pool, machine_id, false).await;
+    }
+
+    result
+}
+
+/// Best-effort insert or removal of the health report override used to
+/// suppress external alerting during compute power control.
+/// Returns `true` when the operation succeeded.
+async fn power_control_health_override(
+    db_pool: &PgPool,
+    machine_id: MachineId,
+    insert: bool,
+) -> bool {
+    let result = async {
+        let mut txn = db::Transaction::begin(db_pool).await?;
+        if insert {
+            db::machine::insert_health_report(
+                &mut txn,
+                &machine_id,
+                HealthReportApplyMode::Replace,
+                &power_control_report(),
+                false,
+            )
+            .await?;
+        } else {
+            remove_power_control_report(&mut txn, &machine_id).await?;
+        }
+        txn.commit().await
+    }
+    .await;
+
+    if let Err(e) = &result {
+        log_override_error(machine_id, insert, e);
+    }
+
+    result.is_ok()
+}
+
+fn log_override_error(machine_id: MachineId, insert: bool, error: &impl Display) {
+    let action = if insert { "insert" } else { "remove" };
+    tracing::warn!(
+        %machine_id,
+        %error,
+        "failed to {action} health report override for power control"
+    );
+}
+
+fn power_control_report() -> HealthReport {
+    HealthReport {
+        source: MACHINE_POWER_OVERRIDE_SOURCE.to_string(),
+        triggered_by: None,
+        observed_at: Some(chrono::Utc::now()),
+        successes: vec![],
+        alerts: vec![HealthProbeAlert {
+            id: HealthProbeId::internal_maintenance(),
+            target: None,
+            in_alert_since: Some(chrono::Utc::now()),
+            message: MACHINE_POWER_OVERRIDE_MESSAGE.to_string(),
+            tenant_message: None,
+            classifications: vec![HealthAlertClassification::suppress_external_alerting()],
+        }],
+    }
+}
+
+/// Removes the power control override, unless another replace override took its place.
+async fn remove_power_control_report(
+    txn: &mut PgConnection,
+    machine_id: &MachineId,
+) -> Result<(), DatabaseError> {
+    let machine =
+        db::machine::find_one(&mut *txn, machine_id, MachineSearchConfig::default()).await?;
+    let is_power_control_report = machine.is_some_and(|machine| {
+        machine
+            .health_reports
+            .replace
+            .is_some_and(|report| report.source == MACHINE_POWER_OVERRIDE_SOURCE)
+    });
+    if is_power_control_report {
+        db::machine::remove_health_report(
+            txn,
+            machine_id,
+            HealthReportApplyMode::Replace,
+            MACHINE_POWER_OVERRIDE_SOURCE,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn desired_power_state_on_variants() {
+        assert_eq!(desired_power_state(PowerAction::On), PowerState::On);
+        assert_eq!(
+            desired_power_state(PowerAction::ForceRestart),
+            PowerState::On
+        );
+        assert_eq!(
+            desired_power_state(PowerAction::GracefulRestart),
+            PowerState::On
+        );
+        assert_eq!(
+            desired_power_state(PowerAction::AcPowercycle),
+            PowerState::On
+        );
+    }
+
+    #[test]
+    fn desired_power_state_off_variants() {
+        assert_eq!(
+            desired_power_state(PowerAction::GracefulShutdown),
+            PowerState::Off
+        );
+        assert_eq!(desired_power_state(PowerAction::ForceOff), PowerState::Off);
+    }
+
+    #[test]
+    fn redfish_power_action_mapping() {
+        assert_eq!(
+            redfish_power_action(PowerAction::On),
+            SystemPowerControl::On
+        );
+        assert_eq!(
+            redfish_power_action(PowerAction::ForceRestart),
+            SystemPowerControl::ForceRestart
+        );
+        assert_eq!(
+            redfish_power_action(PowerAction::GracefulRestart),
+            SystemPowerControl::GracefulRestart
+        );
+        assert_eq!(
+            redfish_power_action(PowerAction::AcPowercycle),
+            SystemPowerControl::ACPowercycle
+        );
+        assert_eq!(
+            redfish_power_action(PowerAction::GracefulShutdown),
+            SystemPowerControl::GracefulShutdown
+        );
+        assert_eq!(
+            redfish_power_action(PowerAction::ForceOff),
+            SystemPowerControl::ForceOff
+        );
+    }
+}
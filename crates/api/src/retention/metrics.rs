@@ -0,0 +1,57 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//! Metrics for the retention job.
+
+use model::retention::RetentionTarget;
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::{Counter, Meter};
+
+pub struct RetentionMetrics {
+    rows_trimmed: Counter<u64>,
+    rows_archived: Counter<u64>,
+    failures: Counter<u64>,
+}
+
+impl RetentionMetrics {
+    pub fn new(meter: &Meter) -> Self {
+        Self {
+            rows_trimmed: meter
+                .u64_counter("carbide_retention_rows_trimmed_count")
+                .with_description("The number of rows deleted by retention policies")
+                .build(),
+            rows_archived: meter
+                .u64_counter("carbide_retention_rows_archived_count")
+                .with_description("The number of trimmed rows written to archive files")
+                .build(),
+            failures: meter
+                .u64_counter("carbide_retention_failures_count")
+                .with_description("The number of times a retention policy could not be applied")
+                .build(),
+        }
+    }
+
+    pub fn record_trimmed(&self, target: RetentionTarget, trimmed: u64, archived: u64) {
+        let attrs = [KeyValue::new("table", target.table_name())];
+        self.rows_trimmed.add(trimmed, &attrs);
+        self.rows_archived.add(archived, &attrs);
+    }
+
+    pub fn record_failure(&self, target: RetentionTarget) {
+        self.failures
+            .add(1, &[KeyValue::new("table", target.table_name())]);
+    }
+}
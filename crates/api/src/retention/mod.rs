@@ -0,0 +1,315 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//! Periodic trimming of history tables according to the policies in [`RetentionConfig`].
+//!
+//! Each policy is applied by first collecting the keys of all rows outside of
+//! it, and then deleting those in batches of `batch_size` rows, every batch in
+//! its own transaction. If an archive directory is configured, the rows of a batch
+//! are written to a gzip compressed JSONL file before the batch is committed.
+//! A batch whose commit fails after its file was written is trimmed again by
+//! the next run, so rows can show up in more than one archive file.
+
+pub(crate) mod metrics;
+
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use carbide_utils::periodic_timer::PeriodicTimer;
+use chrono::{DateTime, Utc};
+use db::DatabaseError;
+use db::work_lock_manager::WorkLockManagerHandle;
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use model::retention::{RetentionPolicy, RetentionTarget};
+use sqlx::{PgConnection, PgPool};
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+
+use self::metrics::RetentionMetrics;
+use crate::cfg::file::RetentionConfig;
+use crate::{CarbideError, CarbideResult};
+
+/// The `RetentionManager` periodically applies the configured retention
+/// policies. Only one carbide-api replica trims at a time.
+pub struct RetentionManager {
+    database_connection: PgPool,
+    config: RetentionConfig,
+    metrics: RetentionMetrics,
+    work_lock_manager_handle: WorkLockManagerHandle,
+}
+
+impl RetentionManager {
+    pub(crate) const ITERATION_WORK_KEY: &'static str = "RetentionManager::run_single_iteration";
+
+    pub fn new(
+        database_connection: PgPool,
+        config: RetentionConfig,
+        meter: opentelemetry::metrics::Meter,
+        work_lock_manager_handle: WorkLockManagerHandle,
+    ) -> Self {
+        RetentionManager {
+            database_connection,
+            config,
+            metrics: RetentionMetrics::new(&meter),
+            work_lock_manager_handle,
+        }
+    }
+
+    /// Start the RetentionManager. It stops once `cancel_token` is cancelled.
+    pub fn start(
+        self,
+        join_set: &mut JoinSet<()>,
+        cancel_token: CancellationToken,
+    ) -> io::Result<()> {
+        if !self.config.enabled {
+            return Ok(());
+        }
+        if self.config.policies.is_empty() {
+            tracing::info!("No retention policies configured. Retention disabled");
+            return Ok(());
+        }
+        join_set
+            .build_task()
+            .name("retention_manager")
+            .spawn(async move { self.run(cancel_token).await })?;
+        Ok(())
+    }
+
+    async fn run(&self, cancel_token: CancellationToken) {
+        let timer = PeriodicTimer::new(self.config.run_interval);
+        loop {
+            let tick = timer.tick();
+            if let Err(e) = self.run_single_iteration().await {
+                tracing::warn!("RetentionManager error: {}", e);
+            }
+
+            tokio::select! {
+                _ = tick.sleep() => {},
+                _ = cancel_token.cancelled() => {
+                    tracing::info!("Retention manager stop was requested");
+                    return;
+                }
+            }
+        }
+    }
+
+    pub async fn run_single_iteration(&self) -> CarbideResult<()> {
+        let _lock = match self
+            .work_lock_manager_handle
+            .try_acquire_lock(Self::ITERATION_WORK_KEY.into())
+            .await
+        {
+            Ok(lock) => lock,
+            Err(e) => {
+                tracing::warn!(
+                    "RetentionManager failed to acquire work lock: Another instance of carbide running? {e}"
+                );
+                return Ok(());
+            }
+        };
+
+        let now = Utc::now();
+        for (target, policy) in self.config.policies.iter() {
+            let policy = RetentionPolicy::from(policy);
+            match trim(
+                &self.database_connection,
+                *target,
+                &policy,
+                now,
+                self.config.batch_size,
+                self.config.archive_dir.as_deref(),
+            )
+            .await
+            {
+                Ok(outcome) => {
+                    self.metrics
+                        .record_trimmed(*target, outcome.trimmed, outcome.archived);
+                    if outcome.trimmed > 0 {
+                        tracing::info!(
+                            table = %target,
+                            trimmed = outcome.trimmed,
+                            archived = outcome.archived,
+                            "Applied retention policy"
+                        );
+                    }
+                }
+                Err(e) => {
+                    self.metrics.record_failure(*target);
+                    tracing::warn!(table = %target, "Failed to apply retention policy: {e}");
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct TrimOutcome {
+    pub trimmed: u64,
+    pub archived: u64,
+}
+
+/// Trims all rows of `target` which fall outside of `policy`, archiving them
+/// to `archive_dir` first if one is given.
+pub(crate) async fn trim(
+    pool: &PgPool,
+    target: RetentionTarget,
+    policy: &RetentionPolicy,
+    now: DateTime<Utc>,
+    batch_size: u32,
+    archive_dir: Option<&Path>,
+) -> CarbideResult<TrimOutcome> {
+    if policy.is_noop() {
+        return Ok(TrimOutcome::default());
+    }
+    let batch_size = batch_size.max(1);
+
+    // The rows to trim are collected once into a temporary table, which only
+    // exists on this connection
+    let mut conn = pool.acquire().await.map_err(DatabaseError::acquire)?;
+    let doomed = db::retention::collect_doomed(&mut conn, target, policy, now).await?;
+    let result = trim_doomed(&mut conn, target, doomed, now, batch_size, archive_dir).await;
+    db::retention::drop_doomed(&mut conn).await?;
+    result
+}
+
+/// Deletes the `doomed` rows collected by [`db::retention::collect_doomed`] in
+/// batches of `batch_size`, one transaction per batch.
+async fn trim_doomed(
+    conn: &mut PgConnection,
+    target: RetentionTarget,
+    doomed: u64,
+    now: DateTime<Utc>,
+    batch_size: u32,
+    archive_dir: Option<&Path>,
+) -> CarbideResult<TrimOutcome> {
+    let mut outcome = TrimOutcome::default();
+    let batches = doomed.div_ceil(u64::from(batch_size));
+
+    for batch in 0..batches {
+        let mut txn = db::Transaction::begin_inner(conn).await?;
+        let trimmed = match archive_dir {
+            Some(archive_dir) => {
+                let rows =
+                    db::retention::trim_batch_returning(&mut txn, target, batch_size).await?;
+                if !rows.is_empty() {
+                    let path = archive_path(archive_dir, target, now, batch);
+                    let archived = rows.len() as u64;
+                    tokio::task::spawn_blocking(move || write_archive(&path, &rows))
+                        .await
+                        .map_err(|e| CarbideError::internal(e.to_string()))?
+                        .map_err(|e| {
+                            CarbideError::internal(format!(
+                                "Failed to archive trimmed rows of {target}: {e}"
+                            ))
+                        })?;
+                    outcome.archived += archived;
+                }
+                rows.len() as u64
+            }
+            None => db::retention::trim_batch(&mut txn, target, batch_size).await?,
+        };
+        txn.commit().await?;
+
+        outcome.trimmed += trimmed;
+    }
+
+    Ok(outcome)
+}
+
+/// `<archive_dir>/<table>/<table>-<run start>-<batch>.jsonl.gz`
+fn archive_path(
+    archive_dir: &Path,
+    target: RetentionTarget,
+    now: DateTime<Utc>,
+    batch: u64,
+) -> PathBuf {
+    let table = target.table_name();
+    archive_dir.join(table).join(format!(
+        "{table}-{}-{batch:06}.jsonl.gz",
+        now.format("%Y%m%dT%H%M%S%.6fZ")
+    ))
+}
+
+/// Writes one JSON object per line. The file only appears under its final
+/// name once it has been completely written and synced.
+fn write_archive(path: &Path, rows: &[serde_json::Value]) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let partial_path = path.with_extension("gz.partial");
+    let file = std::fs::File::create(&partial_path)?;
+    let mut encoder = GzEncoder::new(io::BufWriter::new(file), Compression::default());
+    for row in rows {
+        serde_json::to_writer(&mut encoder, row)?;
+        encoder.write_all(b"\n")?;
+    }
+    let file = encoder.finish()?.into_inner().map_err(|e| e.into_error())?;
+    file.sync_all()?;
+    std::fs::rename(&partial_path, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use flate2::read::GzDecoder;
+
+    use super::*;
+
+    #[test]
+    fn test_archive_path() {
+        let now = DateTime::parse_from_rfc3339("2026-05-05T10:11:12.123456Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            archive_path(
+                Path::new("/var/lib/carbide/retention"),
+                RetentionTarget::MachineStateHistory,
+                now,
+                3
+            ),
+            PathBuf::from(
+                "/var/lib/carbide/retention/machine_state_history/\
+                 machine_state_history-20260505T101112.123456Z-000003.jsonl.gz"
+            )
+        );
+    }
+
+    #[test]
+    fn test_write_archive() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history").join("history-1.jsonl.gz");
+        let rows = vec![
+            serde_json::json!({"id": 1, "state": {"state": "ready"}}),
+            serde_json::json!({"id": 2, "state": {"state": "assigned"}}),
+        ];
+        write_archive(&path, &rows).unwrap();
+        assert!(!path.with_extension("gz.partial").exists());
+
+        let mut content = String::new();
+        GzDecoder::new(std::fs::File::open(&path).unwrap())
+            .read_to_string(&mut content)
+            .unwrap();
+        let lines: Vec<serde_json::Value> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines, rows);
+    }
+}
@@ -59,6 +59,7 @@ use tokio::task::JoinSet;
 use tokio_util::sync::CancellationToken;
 use tracing_log::AsLog as _;
 
+use crate::alert_dispatch::AlertDispatcher;
 use crate::api::Api;
 use crate::api::metrics::ApiMetricsEmitter;
 use crate::cfg::file::{CarbideConfig, InitialObjectsConfig, ListenMode};
@@ -66,6 +67,7 @@ use crate::dpa::handler::{DpaInfo, start_dpa_handler};
 use crate::dynamic_settings::DynamicSettings;
 use crate::errors::CarbideError;
 use crate::handlers::machine_validation::apply_config_on_startup;
+use crate::health_silence::HealthSilenceExpiry;
 use crate::listener::ApiListenMode;
 use crate::logging::log_limiter::LogLimiter;
 use crate::logging::service_health_metrics::{
@@ -75,6 +77,7 @@ use crate::machine_update_manager::MachineUpdateManager;
 use crate::measured_boot::metrics_collector::MeasuredBootMetricsCollector;
 use crate::mqtt_state_change_hook::hook::MqttStateChangeHook;
 use crate::rack::bms_client::BmsDsxExchangeHandle;
+use crate::retention::RetentionManager;
 use crate::scout_stream::ConnectionRegistry;
 use crate::state_controller::common_services::CommonStateHandlerServices;
 use crate::state_controller::controller::{Enqueuer, StateController};
@@ -281,6 +284,9 @@ pub fn parse_carbide_config(
     // admin-UI sidebar and per-machine "Logs" deep link can read it.
     crate::web::init_tools(config.web_ui_sidebar_tools.clone());
 
+    // Validate the admin-UI serial console's WebSocket URL.
+    config.validate_web_ui_serial_console()?;
+
     // Validate that the firmware profile config keys match their inner
     // part_number and psid values. Mismatches are logged as warnings.
     config.validate_supernic_firmware_profiles();
@@ -827,8 +833,11 @@ pub async fn initialize_and_start_controllers(
             && config.enabled
         {
             let options = {
-                let defaults =
+                let mut defaults =
                     mqttea::client::ClientOptions::default().with_qos(mqttea::QoS::AtMostOnce);
+                if let Some(ref outbox_directory) = config.outbox_directory {
+                    defaults = defaults.with_outbox(mqttea::OutboxOptions::new(outbox_directory));
+                }
 
                 if let Some(provider) = crate::auth::mqtt_auth::build_credentials_provider(
                     &config.auth,
@@ -1160,6 +1169,34 @@ pub async fn initialize_and_start_controllers(
     )
     .start(join_set, cancel_token.clone())?;
 
+    RetentionManager::new(
+        db_pool.clone(),
+        carbide_config.retention.clone(),
+        meter.clone(),
+        work_lock_manager_handle.clone(),
+    )
+    .start(join_set, cancel_token.clone())?;
+
+    HealthSilenceExpiry::new(db_pool.clone(), work_lock_manager_handle.clone())
+        .start(join_set, cancel_token.clone())?;
+
+    if carbide_config.alert_dispatch.enabled {
+        let receivers = crate::alert_dispatch::receiver::build_receivers(
+            &carbide_config.alert_dispatch,
+            api_service.credential_manager.clone(),
+        )
+        .await?;
+        AlertDispatcher::new(
+            db_pool.clone(),
+            carbide_config.alert_dispatch.clone(),
+            carbide_config.host_health,
+            receivers,
+            meter.clone(),
+            work_lock_manager_handle.clone(),
+        )
+        .start(join_set, cancel_token.clone())?;
+    }
+
     // we need to create ek_cert_status entries for all existing machines
     attestation::backfill_ek_cert_status_for_existing_machines(db_pool).await?;
 
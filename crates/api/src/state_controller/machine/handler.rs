@@ -6052,6 +6052,17 @@ impl StateHandler for InstanceStateHandler {
                         .await
                         .map_err(|err| StateHandlerError::GenericError(err.into()))?;
 
+                    // The sanitization scout performs during the following cleanup is
+                    // reported against this release.
+                    db::media_sanitization::open(
+                        &mut txn,
+                        host_machine_id,
+                        instance.id,
+                        &instance.config.tenant.tenant_organization_id,
+                    )
+                    .await
+                    .map_err(|err| StateHandlerError::GenericError(err.into()))?;
+
                     release_network_segments_with_vpc_prefix(
                         &instance.config.network.interfaces,
                         &mut txn,
@@ -25,6 +25,7 @@ use crate::state_controller::rack::created::handle_created;
 use crate::state_controller::rack::deleting::handle_deleting;
 use crate::state_controller::rack::discovering::handle_discovering;
 use crate::state_controller::rack::error_state::handle_error;
+use crate::state_controller::rack::leak_response::{handle_leak_response, leak_response_trigger};
 use crate::state_controller::rack::maintenance::handle_maintenance;
 use crate::state_controller::rack::ready::handle_ready;
 use crate::state_controller::rack::validating::handle_validating;
@@ -61,6 +62,9 @@ impl RackStateHandler {
                 handle_validating(id, state, validating_state, ctx).await
             }
             RackState::Ready => handle_ready(id, state, &config, ctx).await,
+            RackState::LeakResponse {
+                leak_response_state,
+            } => handle_leak_response(id, state, leak_response_state, ctx).await,
             RackState::Error { cause } => handle_error(id, state, &config, cause, ctx).await,
             RackState::Deleting => handle_deleting().await,
         }
@@ -92,6 +96,20 @@ impl StateHandler for RackStateHandler {
             return Ok(StateHandlerOutcome::transition(RackState::Deleting));
         }
 
+        if let Some(next_state) = leak_response_trigger(
+            state,
+            controller_state,
+            &ctx.services.site_config.rack_state_controller.leak_response,
+        ) {
+            tracing::warn!(
+                "Rack {} has a leak reported, transitioning from {} to {}",
+                id,
+                controller_state,
+                next_state
+            );
+            return Ok(StateHandlerOutcome::transition(next_state));
+        }
+
         self.attempt_state_transition(id, state, controller_state, ctx)
             .await
     }
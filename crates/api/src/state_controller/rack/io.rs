@@ -24,7 +24,8 @@ use db::{DatabaseError, ObjectColumnFilter, rack as db_rack};
 use model::StateSla;
 use model::controller_outcome::PersistentStateHandlerOutcome;
 use model::rack::{
-    Rack, RackMaintenanceState, RackSearchFilter, RackState, RackValidationState, state_sla,
+    Rack, RackLeakResponseState, RackMaintenanceState, RackSearchFilter, RackState,
+    RackValidationState, state_sla,
 };
 use sqlx::PgConnection;
 
@@ -147,6 +148,27 @@ impl StateControllerIO for RackStateControllerIO {
                 RackMaintenanceState::PowerSequence { .. } => ("maintenance", "power_sequence"),
                 RackMaintenanceState::Completed => ("maintenance", "completed"),
             },
+            RackState::LeakResponse {
+                leak_response_state,
+            } => match leak_response_state {
+                RackLeakResponseState::Confirming { .. } => ("leak_response", "confirming"),
+                RackLeakResponseState::BlockingAllocations => {
+                    ("leak_response", "blocking_allocations")
+                }
+                RackLeakResponseState::NotifyingTenants => ("leak_response", "notifying_tenants"),
+                RackLeakResponseState::WaitingForTenants { .. } => {
+                    ("leak_response", "waiting_for_tenants")
+                }
+                RackLeakResponseState::ShuttingDown => ("leak_response", "shutting_down"),
+                RackLeakResponseState::WaitForShutdown { .. } => {
+                    ("leak_response", "wait_for_shutdown")
+                }
+                RackLeakResponseState::PoweringOff => ("leak_response", "powering_off"),
+                RackLeakResponseState::WaitForPowerOff { .. } => {
+                    ("leak_response", "wait_for_power_off")
+                }
+                RackLeakResponseState::Isolated => ("leak_response", "isolated"),
+            },
             RackState::Error { .. } => ("error", ""),
             RackState::Deleting => ("deleting", ""),
         }
@@ -0,0 +1,392 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Handler for RackState::LeakResponse, the automated leak-response playbook.
+
+use carbide_uuid::rack::RackId;
+use chrono::{DateTime, Utc};
+use db::{instance as db_instance, machine as db_machine, rack as db_rack};
+use health_report::{
+    HealthAlertClassification, HealthProbeAlert, HealthReport, HealthReportApplyMode,
+};
+use libredfish::PowerState;
+use model::component_manager::PowerAction;
+use model::machine::Machine;
+use model::power_manager::PowerState as DesiredPowerState;
+use model::rack::{Rack, RackLeakResponseState, RackState};
+use sqlx::PgConnection;
+
+use crate::cfg::file::LeakResponseConfig;
+use crate::power_control::set_desired_power_state;
+use crate::state_controller::rack::context::RackStateHandlerContextObjects;
+use crate::state_controller::rack::maintenance::first_maintenance_state;
+use crate::state_controller::rack::power_control::{tray_power_control, tray_power_state};
+use crate::state_controller::state_handler::{
+    StateHandlerContext, StateHandlerError, StateHandlerOutcome,
+};
+
+/// Source of the health override the playbook places on the compute trays.
+pub(crate) const LEAK_RESPONSE_OVERRIDE_SOURCE: &str = "rack-leak-response";
+const LEAK_RESPONSE_ALERT_ID: &str = "RackLeakResponse";
+
+/// Returns the leak alert in the aggregate health of the rack, if any.
+pub(crate) fn active_leak_alert<'a>(
+    health: &'a HealthReport,
+    config: &LeakResponseConfig,
+) -> Option<&'a HealthProbeAlert> {
+    health.alerts.iter().find(|alert| {
+        config
+            .leak_probe_ids
+            .iter()
+            .any(|id| id == alert.id.as_str())
+    })
+}
+
+/// Returns the state a rack enters when the playbook starts for a leak
+/// reported for it, or `None` if the playbook does not start.
+pub(crate) fn leak_response_trigger(
+    rack: &Rack,
+    controller_state: &RackState,
+    config: &LeakResponseConfig,
+) -> Option<RackState> {
+    if !config.enabled
+        || matches!(
+            controller_state,
+            RackState::LeakResponse { .. } | RackState::Deleting
+        )
+    {
+        return None;
+    }
+    let health = rack.aggregate_health();
+    let alert = active_leak_alert(&health, config)?;
+    Some(RackState::LeakResponse {
+        leak_response_state: RackLeakResponseState::Confirming {
+            detected_at: alert.in_alert_since.unwrap_or_else(Utc::now),
+            interrupted: !matches!(controller_state, RackState::Ready),
+        },
+    })
+}
+
+fn has_elapsed(since: &DateTime<Utc>, duration: chrono::Duration) -> bool {
+    Utc::now().signed_duration_since(*since) >= duration
+}
+
+fn leak_response_report(rack_id: &RackId, tenant_message: Option<String>) -> HealthReport {
+    HealthReport {
+        source: LEAK_RESPONSE_OVERRIDE_SOURCE.to_string(),
+        triggered_by: None,
+        observed_at: Some(Utc::now()),
+        successes: vec![],
+        alerts: vec![HealthProbeAlert {
+            id: LEAK_RESPONSE_ALERT_ID
+                .parse()
+                .expect("non-empty strings are always valid probe ids"),
+            target: Some(rack_id.to_string()),
+            in_alert_since: None,
+            message: format!("Leak response in progress for rack {rack_id}"),
+            tenant_message,
+            classifications: vec![HealthAlertClassification::prevent_allocations()],
+        }],
+    }
+}
+
+/// Loads the compute trays of the rack.
+async fn compute_trays(
+    rack: &Rack,
+    txn: &mut PgConnection,
+) -> Result<Vec<Machine>, StateHandlerError> {
+    Ok(super::get_machines_from_rack(rack, txn)
+        .await?
+        .into_iter()
+        .filter(|machine| machine.id.machine_type().is_host())
+        .collect())
+}
+
+/// Returns the trays which are not confirmed to be powered off.
+async fn powered_trays(
+    trays: Vec<Machine>,
+    ctx: &mut StateHandlerContext<'_, RackStateHandlerContextObjects>,
+) -> Vec<Machine> {
+    let mut powered = Vec::new();
+    for tray in trays {
+        match tray_power_state(&tray, ctx).await {
+            Ok(PowerState::Off) => {}
+            Ok(_) => powered.push(tray),
+            Err(e) => {
+                tracing::warn!(machine_id = %tray.id, error = %e, "Failed to read tray power state");
+                powered.push(tray);
+            }
+        }
+    }
+    powered
+}
+
+/// Applies a power action to every tray. Failures are logged; trays which do
+/// not power off are handled by the following wait state.
+async fn power_trays(
+    rack_id: &RackId,
+    trays: &[Machine],
+    action: PowerAction,
+    ctx: &mut StateHandlerContext<'_, RackStateHandlerContextObjects>,
+) {
+    for tray in trays {
+        if let Err(e) = tray_power_control(tray, action, ctx).await {
+            tracing::warn!(
+                rack_id = %rack_id,
+                machine_id = %tray.id,
+                ?action,
+                error = %e,
+                "Leak response failed to send power action to tray"
+            );
+        }
+    }
+}
+
+pub async fn handle_leak_response(
+    id: &RackId,
+    state: &mut Rack,
+    leak_response_state: &RackLeakResponseState,
+    ctx: &mut StateHandlerContext<'_, RackStateHandlerContextObjects>,
+) -> Result<StateHandlerOutcome<RackState>, StateHandlerError> {
+    let config = ctx
+        .services
+        .site_config
+        .rack_state_controller
+        .leak_response
+        .clone();
+    let leak_response = |leak_response_state: RackLeakResponseState| RackState::LeakResponse {
+        leak_response_state,
+    };
+
+    match leak_response_state {
+        RackLeakResponseState::Confirming {
+            detected_at,
+            interrupted,
+        } => {
+            if active_leak_alert(&state.aggregate_health(), &config).is_none() {
+                let next_state = if *interrupted {
+                    RackState::Discovering
+                } else {
+                    RackState::Ready
+                };
+                tracing::info!(
+                    "Rack {} leak cleared during confirmation, transitioning to {}",
+                    id,
+                    next_state
+                );
+                return Ok(StateHandlerOutcome::transition(next_state));
+            }
+            if !has_elapsed(detected_at, config.confirmation_window) {
+                return Ok(StateHandlerOutcome::wait(format!(
+                    "confirming leak reported at {detected_at}"
+                )));
+            }
+
+            tracing::warn!(
+                "Rack {} leak confirmed, starting leak response playbook",
+                id
+            );
+            // A maintenance request made before the leak must not release the
+            // rack once it is isolated.
+            let mut txn = ctx.services.db_pool.begin().await?;
+            if state.config.maintenance_requested.take().is_some() {
+                db_rack::update(txn.as_mut(), id, &state.config).await?;
+            }
+            Ok(StateHandlerOutcome::transition(leak_response(
+                RackLeakResponseState::BlockingAllocations,
+            ))
+            .with_txn(txn))
+        }
+        RackLeakResponseState::BlockingAllocations => {
+            let mut txn = ctx.services.db_pool.begin().await?;
+            let trays = compute_trays(state, &mut txn).await?;
+            let report = leak_response_report(id, None);
+            for tray in &trays {
+                db_machine::insert_health_report(
+                    &mut txn,
+                    &tray.id,
+                    HealthReportApplyMode::Merge,
+                    &report,
+                    false,
+                )
+                .await?;
+            }
+            tracing::info!(
+                "Rack {} leak response blocked allocations to {} trays",
+                id,
+                trays.len()
+            );
+            Ok(StateHandlerOutcome::transition(leak_response(
+                RackLeakResponseState::NotifyingTenants,
+            ))
+            .with_txn(txn))
+        }
+        RackLeakResponseState::NotifyingTenants => {
+            let mut txn = ctx.services.db_pool.begin().await?;
+            let trays = compute_trays(state, &mut txn).await?;
+            let tray_ids: Vec<_> = trays.iter().map(|tray| &tray.id).collect();
+            let instances = db_instance::find_by_machine_ids(&mut txn, &tray_ids).await?;
+            let report = leak_response_report(id, Some(config.tenant_message.clone()));
+            for instance in &instances {
+                db_machine::insert_health_report(
+                    &mut txn,
+                    &instance.machine_id,
+                    HealthReportApplyMode::Merge,
+                    &report,
+                    false,
+                )
+                .await?;
+            }
+            tracing::info!(
+                "Rack {} leak response notified the tenants of {} instances",
+                id,
+                instances.len()
+            );
+            Ok(StateHandlerOutcome::transition(leak_response(
+                RackLeakResponseState::WaitingForTenants {
+                    notified_at: Utc::now(),
+                },
+            ))
+            .with_txn(txn))
+        }
+        RackLeakResponseState::WaitingForTenants { notified_at } => {
+            if !has_elapsed(notified_at, config.tenant_notice_period) {
+                return Ok(StateHandlerOutcome::wait(format!(
+                    "tenants notified at {notified_at}, waiting before shutting down trays"
+                )));
+            }
+            Ok(StateHandlerOutcome::transition(leak_response(
+                RackLeakResponseState::ShuttingDown,
+            )))
+        }
+        RackLeakResponseState::ShuttingDown => {
+            let mut txn = ctx.services.db_pool.begin().await?;
+            let trays = compute_trays(state, &mut txn).await?;
+            txn.commit().await?;
+            let trays = powered_trays(trays, ctx).await;
+            tracing::info!(
+                "Rack {} leak response shutting down {} trays",
+                id,
+                trays.len()
+            );
+            power_trays(id, &trays, PowerAction::GracefulShutdown, ctx).await;
+            Ok(StateHandlerOutcome::transition(leak_response(
+                RackLeakResponseState::WaitForShutdown {
+                    requested_at: Utc::now(),
+                },
+            )))
+        }
+        RackLeakResponseState::WaitForShutdown { requested_at } => {
+            let mut txn = ctx.services.db_pool.begin().await?;
+            let trays = compute_trays(state, &mut txn).await?;
+            txn.commit().await?;
+            let powered = powered_trays(trays, ctx).await;
+            if powered.is_empty() {
+                return Ok(StateHandlerOutcome::transition(leak_response(
+                    RackLeakResponseState::Isolated,
+                )));
+            }
+            if !has_elapsed(requested_at, config.graceful_shutdown_timeout) {
+                return Ok(StateHandlerOutcome::wait(format!(
+                    "waiting for {} trays to shut down",
+                    powered.len()
+                )));
+            }
+            tracing::warn!(
+                "Rack {} leak response: {} trays did not shut down gracefully, forcing them off",
+                id,
+                powered.len()
+            );
+            Ok(StateHandlerOutcome::transition(leak_response(
+                RackLeakResponseState::PoweringOff,
+            )))
+        }
+        RackLeakResponseState::PoweringOff => {
+            let mut txn = ctx.services.db_pool.begin().await?;
+            let trays = compute_trays(state, &mut txn).await?;
+            txn.commit().await?;
+            let trays = powered_trays(trays, ctx).await;
+            power_trays(id, &trays, PowerAction::ForceOff, ctx).await;
+            Ok(StateHandlerOutcome::transition(leak_response(
+                RackLeakResponseState::WaitForPowerOff {
+                    requested_at: Utc::now(),
+                },
+            )))
+        }
+        RackLeakResponseState::WaitForPowerOff { requested_at } => {
+            let mut txn = ctx.services.db_pool.begin().await?;
+            let trays = compute_trays(state, &mut txn).await?;
+            txn.commit().await?;
+            let powered = powered_trays(trays, ctx).await;
+            if powered.is_empty() {
+                return Ok(StateHandlerOutcome::transition(leak_response(
+                    RackLeakResponseState::Isolated,
+                )));
+            }
+            if !has_elapsed(requested_at, config.power_off_timeout) {
+                return Ok(StateHandlerOutcome::wait(format!(
+                    "waiting for {} trays to power off",
+                    powered.len()
+                )));
+            }
+            tracing::warn!(
+                "Rack {} leak response: {} trays are still powered, forcing them off again",
+                id,
+                powered.len()
+            );
+            Ok(StateHandlerOutcome::transition(leak_response(
+                RackLeakResponseState::PoweringOff,
+            )))
+        }
+        RackLeakResponseState::Isolated => {
+            if active_leak_alert(&state.aggregate_health(), &config).is_some() {
+                return Ok(StateHandlerOutcome::wait(
+                    "rack isolated, leak is still reported".into(),
+                ));
+            }
+            let Some(scope) = &state.config.maintenance_requested else {
+                return Ok(StateHandlerOutcome::wait(
+                    "rack isolated, leak cleared; waiting for maintenance to return it to service"
+                        .into(),
+                ));
+            };
+
+            tracing::info!(
+                "Rack {} maintenance requested after leak, releasing trays and transitioning to Maintenance",
+                id
+            );
+            let next_state = first_maintenance_state(scope);
+            let mut txn = ctx.services.db_pool.begin().await?;
+            for tray in compute_trays(state, &mut txn).await? {
+                db_machine::remove_health_report(
+                    &mut txn,
+                    &tray.id,
+                    HealthReportApplyMode::Merge,
+                    LEAK_RESPONSE_OVERRIDE_SOURCE,
+                )
+                .await?;
+                set_desired_power_state(&mut txn, &tray.id, DesiredPowerState::On).await?;
+            }
+            // Leave maintenance_requested set; the maintenance handler reads the
+            // scope and clears it on Completed.
+            Ok(StateHandlerOutcome::transition(RackState::Maintenance {
+                maintenance_state: next_state,
+            })
+            .with_txn(txn))
+        }
+    }
+}
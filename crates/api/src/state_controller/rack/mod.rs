@@ -36,7 +36,9 @@ pub mod error_state;
 pub mod fabric_manager;
 pub mod handler;
 pub mod io;
+pub mod leak_response;
 pub mod maintenance;
+pub mod power_control;
 pub mod ready;
 pub mod validating;
 
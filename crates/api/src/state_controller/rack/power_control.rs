@@ -0,0 +1,72 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Power control of compute trays by the rack state controller.
+//!
+//! This is the rack state handler side of `MaintenanceActivity::PowerControl`
+//! for compute trays. Actions go through the same path as the component
+//! manager's compute power control, see [`crate::power_control`].
+
+use libredfish::PowerState;
+use model::component_manager::PowerAction;
+use model::machine::Machine;
+
+use crate::power_control::machine_power_control;
+use crate::state_controller::rack::context::RackStateHandlerContextObjects;
+use crate::state_controller::state_handler::{StateHandlerContext, StateHandlerError};
+
+/// Applies a power action to a compute tray.
+pub(crate) async fn tray_power_control(
+    machine: &Machine,
+    action: PowerAction,
+    ctx: &mut StateHandlerContext<'_, RackStateHandlerContextObjects>,
+) -> Result<(), StateHandlerError> {
+    let services = &ctx.services;
+    machine_power_control(
+        &services.db_pool,
+        machine.id,
+        action,
+        |redfish_action| async move {
+            let redfish_client = services.create_redfish_client_from_machine(machine).await?;
+            redfish_client.power(redfish_action).await.map_err(|error| {
+                StateHandlerError::RedfishError {
+                    operation: "power control",
+                    error,
+                }
+            })
+        },
+    )
+    .await
+}
+
+/// Returns the power state the BMC reports for a compute tray.
+pub(crate) async fn tray_power_state(
+    machine: &Machine,
+    ctx: &mut StateHandlerContext<'_, RackStateHandlerContextObjects>,
+) -> Result<PowerState, StateHandlerError> {
+    let redfish_client = ctx
+        .services
+        .create_redfish_client_from_machine(machine)
+        .await?;
+    redfish_client
+        .get_power_state()
+        .await
+        .map_err(|error| StateHandlerError::RedfishError {
+            operation: "get power state",
+            error,
+        })
+}
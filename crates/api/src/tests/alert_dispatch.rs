@@ -0,0 +1,224 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use health_report::{HealthAlertClassification, HealthProbeAlert, HealthReport};
+use model::alert_dispatch::{AlertObjectKind, AlertSeverity};
+use rpc::forge::forge_server::Forge;
+use rpc::forge::{self as rpc_forge};
+use tonic::Request;
+
+use crate::alert_dispatch::receiver::{AlertDeliveryError, AlertReceiver};
+use crate::alert_dispatch::{AlertDispatcher, AlertNotification, AlertStatus};
+use crate::cfg::file::{AlertDispatchConfig, AlertDispatchRetryConfig, AlertRouteConfig};
+use crate::tests::common::api_fixtures::site_explorer::TestRackDbBuilder;
+use crate::tests::common::api_fixtures::{
+    TestEnv, TestEnvOverrides, create_test_env_with_overrides, get_config,
+};
+
+/// Records notifications instead of sending them
+#[derive(Default)]
+struct RecordingReceiver {
+    notifications: Mutex<Vec<AlertNotification>>,
+    fail: AtomicBool,
+}
+
+impl RecordingReceiver {
+    fn take(&self) -> Vec<AlertNotification> {
+        std::mem::take(&mut self.notifications.lock().unwrap())
+    }
+}
+
+#[async_trait::async_trait]
+impl AlertReceiver for RecordingReceiver {
+    async fn send(&self, notification: &AlertNotification) -> Result<(), AlertDeliveryError> {
+        if self.fail.load(Ordering::SeqCst) {
+            return Err(AlertDeliveryError::Timeout);
+        }
+        self.notifications
+            .lock()
+            .unwrap()
+            .push(notification.clone());
+        Ok(())
+    }
+}
+
+fn leak_alert_report() -> HealthReport {
+    HealthReport {
+        source: "dsx-exchange-consumer".to_string(),
+        triggered_by: None,
+        observed_at: Some(chrono::Utc::now()),
+        successes: vec![],
+        alerts: vec![HealthProbeAlert {
+            id: "BmsLeakDetectRack".parse().unwrap(),
+            target: None,
+            in_alert_since: Some(chrono::Utc::now()),
+            message: "Leak detected".to_string(),
+            tenant_message: None,
+            classifications: vec![HealthAlertClassification::prevent_allocations()],
+        }],
+    }
+}
+
+fn dispatcher(env: &TestEnv, receiver: Arc<RecordingReceiver>) -> AlertDispatcher {
+    AlertDispatcher::new(
+        env.pool.clone(),
+        AlertDispatchConfig {
+            enabled: true,
+            group_by: vec!["alertname".to_string()],
+            retry: AlertDispatchRetryConfig {
+                max_attempts: 2,
+                initial_backoff: std::time::Duration::from_millis(1),
+                ..Default::default()
+            },
+            routes: vec![AlertRouteConfig {
+                receiver: "oncall".to_string(),
+                classifications: vec![],
+                object_kinds: vec![AlertObjectKind::Rack],
+                min_severity: AlertSeverity::Critical,
+            }],
+            ..Default::default()
+        },
+        env.config.host_health,
+        BTreeMap::from([("oncall".to_string(), receiver as Arc<dyn AlertReceiver>)]),
+        env.test_meter.meter(),
+        env.api.work_lock_manager_handle.clone(),
+    )
+}
+
+#[crate::sqlx_test]
+async fn test_alert_dispatch_sends_transitions(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env =
+        create_test_env_with_overrides(pool.clone(), TestEnvOverrides::with_config(get_config()))
+            .await;
+
+    let mut txn = pool.acquire().await?;
+    let rack_id = TestRackDbBuilder::new().persist(&mut txn).await?;
+    drop(txn);
+
+    let receiver = Arc::new(RecordingReceiver::default());
+    let dispatcher = dispatcher(&env, receiver.clone());
+
+    // Nothing is alerting yet
+    dispatcher.run_single_iteration().await?;
+    assert!(receiver.take().is_empty());
+
+    env.api
+        .insert_rack_health_report(Request::new(rpc_forge::InsertRackHealthReportRequest {
+            rack_id: Some(rack_id.clone()),
+            health_report_entry: Some(rpc_forge::HealthReportEntry {
+                report: Some(leak_alert_report().into()),
+                mode: rpc_forge::HealthReportApplyMode::Merge as i32,
+            }),
+        }))
+        .await?;
+
+    // A failed delivery is sent again by the next run
+    receiver.fail.store(true, Ordering::SeqCst);
+    dispatcher.run_single_iteration().await?;
+    assert!(receiver.take().is_empty());
+    receiver.fail.store(false, Ordering::SeqCst);
+
+    dispatcher.run_single_iteration().await?;
+    let notifications = receiver.take();
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0].status, AlertStatus::Firing);
+    assert_eq!(
+        notifications[0].group_labels,
+        BTreeMap::from([("alertname".to_string(), "BmsLeakDetectRack".to_string())])
+    );
+    let alert = &notifications[0].alerts[0];
+    assert_eq!(alert.object_kind, AlertObjectKind::Rack);
+    assert_eq!(alert.object_id, rack_id.to_string());
+    assert_eq!(alert.severity, AlertSeverity::Critical);
+    assert_eq!(alert.labels["rack_id"], rack_id.to_string());
+    assert_eq!(alert.ends_at, None);
+    let raised_at = alert.starts_at;
+
+    // The alert is still firing, which is not sent again
+    dispatcher.run_single_iteration().await?;
+    assert!(receiver.take().is_empty());
+
+    env.api
+        .remove_rack_health_report(Request::new(rpc_forge::RemoveRackHealthReportRequest {
+            rack_id: Some(rack_id.clone()),
+            source: "dsx-exchange-consumer".to_string(),
+        }))
+        .await?;
+
+    dispatcher.run_single_iteration().await?;
+    let notifications = receiver.take();
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0].status, AlertStatus::Resolved);
+    let alert = &notifications[0].alerts[0];
+    assert_eq!(alert.starts_at, raised_at);
+    assert!(alert.ends_at.is_some());
+
+    assert!(db::health_alert_dispatch::find_all(&pool).await?.is_empty());
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_alert_dispatch_skips_silenced_alerts(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env =
+        create_test_env_with_overrides(pool.clone(), TestEnvOverrides::with_config(get_config()))
+            .await;
+
+    let mut txn = pool.acquire().await?;
+    let rack_id = TestRackDbBuilder::new().persist(&mut txn).await?;
+    drop(txn);
+
+    env.api
+        .insert_rack_health_report(Request::new(rpc_forge::InsertRackHealthReportRequest {
+            rack_id: Some(rack_id.clone()),
+            health_report_entry: Some(rpc_forge::HealthReportEntry {
+                report: Some(leak_alert_report().into()),
+                mode: rpc_forge::HealthReportApplyMode::Merge as i32,
+            }),
+        }))
+        .await?;
+    env.api
+        .create_health_silence(Request::new(rpc_forge::CreateHealthSilenceRequest {
+            matcher: Some(rpc_forge::HealthSilenceMatcher {
+                probe_id: Some("BmsLeakDetectRack".to_string()),
+                rack_id: Some(rack_id.to_string()),
+                ..Default::default()
+            }),
+            reason: "leak sensor replacement".to_string(),
+            author: Some("operator".to_string()),
+            starts_at: None,
+            expires_at: None,
+            duration: Some(std::time::Duration::from_secs(60 * 60).into()),
+        }))
+        .await?;
+
+    let receiver = Arc::new(RecordingReceiver::default());
+    dispatcher(&env, receiver.clone())
+        .run_single_iteration()
+        .await?;
+    assert!(receiver.take().is_empty());
+
+    Ok(())
+}
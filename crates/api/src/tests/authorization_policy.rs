@@ -0,0 +1,102 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::time::Duration;
+
+use carbide_authn::middleware::Principal;
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+
+use crate::auth::policy_source::DatabasePolicySource;
+use crate::auth::{CasbinAuthorizer, Predicate, ResourceAttributes};
+
+fn rule(fields: &[&str]) -> Vec<String> {
+    fields.iter().map(|f| f.to_string()).collect()
+}
+
+fn instance_of(tenant_organization_id: &str) -> ResourceAttributes {
+    ResourceAttributes {
+        kind: "instance".to_string(),
+        tenant_organization_id: tenant_organization_id.to_string(),
+        ..Default::default()
+    }
+}
+
+#[crate::sqlx_test]
+async fn test_database_policy_source(db_pool: sqlx::PgPool) -> eyre::Result<()> {
+    let mut txn = db_pool.begin().await?;
+    let version_rule = db::authorization_policy::insert(
+        &mut txn,
+        "p",
+        &rule(&["anonymous", "forge/Version"]),
+        Some("test"),
+    )
+    .await?;
+    db::authorization_policy::insert(
+        &mut txn,
+        "p",
+        &rule(&[
+            "anonymous",
+            "forge/ReleaseInstance",
+            r#"r.res.tenant_organization_id == "acme""#,
+        ]),
+        None,
+    )
+    .await?;
+    txn.commit().await?;
+
+    let (authorizer, reloader) = CasbinAuthorizer::build_casbin(
+        vec![Box::new(DatabasePolicySource::new(db_pool.clone()))],
+        false,
+        &opentelemetry::global::meter("test"),
+    )
+    .await?;
+    let mut join_set = JoinSet::new();
+    let cancel_token = CancellationToken::new();
+    reloader.start(
+        &mut join_set,
+        Duration::from_millis(100),
+        cancel_token.clone(),
+    );
+
+    let anonymous = [Principal::Anonymous];
+    let authorize = |predicate: Predicate| {
+        authorizer
+            .authorize(&anonymous.as_slice(), predicate)
+            .is_ok()
+    };
+
+    assert!(authorize(Predicate::ForgeCall("Version".into())));
+    assert!(authorize(Predicate::ForgeCallOn(
+        "ReleaseInstance".into(),
+        instance_of("acme")
+    )));
+    assert!(!authorize(Predicate::ForgeCallOn(
+        "ReleaseInstance".into(),
+        instance_of("other")
+    )));
+
+    // Deleting a rule takes effect with the next reload.
+    let mut txn = db_pool.begin().await?;
+    assert!(db::authorization_policy::delete(&mut txn, version_rule.id).await?);
+    txn.commit().await?;
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert!(!authorize(Predicate::ForgeCall("Version".into())));
+
+    cancel_token.cancel();
+    join_set.join_all().await;
+    Ok(())
+}
@@ -1057,6 +1057,9 @@ pub fn get_config() -> CarbideConfig {
     CarbideConfig {
         default_tenant_routing_profile_type: "EXTERNAL".to_string(),
         web_ui_sidebar_tools: vec![],
+        web_ui_serial_console: None,
+        retention: Default::default(),
+        alert_dispatch: Default::default(),
         bgp_leaf_session_password: None,
         rack_validation_config: crate::cfg::file::RackValidationConfig {
             enabled: true,
@@ -1147,6 +1150,7 @@ pub fn get_config() -> CarbideConfig {
         },
         rack_state_controller: RackStateControllerConfig {
             controller: StateControllerConfig::default(),
+            leak_response: Default::default(),
         },
         switch_state_controller: SwitchStateControllerConfig {
             controller: StateControllerConfig::default(),
@@ -2254,6 +2258,8 @@ pub async fn network_configured_with_health_and_ext_services(
             .instance
             .map(|instance| instance.dpu_extension_service_version),
         dpu_extension_services,
+        network_config_rollback: None,
+        applied_nvue_config: None,
     };
     tracing::trace!(
         "network_configured machine={} instance_network={} instance={}",
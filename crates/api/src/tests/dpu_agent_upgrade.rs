@@ -97,6 +97,8 @@ async fn test_upgrade_check(db_pool: sqlx::PgPool) -> Result<(), eyre::Report> {
             last_dhcp_requests: vec![],
             dpu_extension_service_version: Some("V1-T1".to_string()),
             dpu_extension_services: vec![],
+            network_config_rollback: None,
+            applied_nvue_config: None,
         }))
         .await
         .unwrap();
@@ -279,6 +281,8 @@ impl TestManagedHost {
                 last_dhcp_requests: vec![],
                 dpu_extension_service_version: Some("V1-T1".to_string()),
                 dpu_extension_services: vec![],
+                network_config_rollback: None,
+                applied_nvue_config: None,
             }))
             .await
             .unwrap();
@@ -0,0 +1,358 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use carbide_uuid::rack::RackId;
+use health_report::{HealthAlertClassification, HealthProbeAlert, HealthReport};
+use model::expected_machine::ExpectedMachineData;
+use model::machine::LoadSnapshotOptions;
+use rpc::forge::forge_server::Forge;
+use rpc::forge::{self as rpc_forge};
+use tonic::Request;
+
+use crate::health_silence::HealthSilenceExpiry;
+use crate::tests::common::api_fixtures::managed_host::ManagedHostConfig;
+use crate::tests::common::api_fixtures::site_explorer::TestRackDbBuilder;
+use crate::tests::common::api_fixtures::{
+    TestEnvOverrides, create_managed_host_with_config, create_test_env_with_overrides, get_config,
+};
+
+fn leak_alert_report() -> HealthReport {
+    HealthReport {
+        source: "dsx-exchange-consumer".to_string(),
+        triggered_by: None,
+        observed_at: Some(chrono::Utc::now()),
+        successes: vec![],
+        alerts: vec![HealthProbeAlert {
+            id: "BmsLeakDetectRack".parse().unwrap(),
+            target: None,
+            in_alert_since: Some(chrono::Utc::now()),
+            message: "Leak detected".to_string(),
+            tenant_message: None,
+            classifications: vec![HealthAlertClassification::prevent_allocations()],
+        }],
+    }
+}
+
+fn leak_silence_request(
+    object_kind: rpc_forge::HealthSilenceObjectKind,
+    rack_id: &RackId,
+) -> rpc_forge::CreateHealthSilenceRequest {
+    rpc_forge::CreateHealthSilenceRequest {
+        matcher: Some(rpc_forge::HealthSilenceMatcher {
+            probe_id: Some("BmsLeakDetectRack".to_string()),
+            object_kind: object_kind as i32,
+            rack_id: Some(rack_id.to_string()),
+            ..Default::default()
+        }),
+        reason: "leak sensor replacement".to_string(),
+        author: Some("operator".to_string()),
+        starts_at: None,
+        expires_at: None,
+        duration: Some(std::time::Duration::from_secs(4 * 60 * 60).into()),
+    }
+}
+
+fn leak_alert_classifications(health: &HealthReport) -> Vec<HealthAlertClassification> {
+    health
+        .alerts
+        .iter()
+        .find(|alert| alert.id.as_str() == "BmsLeakDetectRack")
+        .expect("leak alert should stay visible")
+        .classifications
+        .clone()
+}
+
+async fn rack_health(
+    env: &crate::tests::common::api_fixtures::TestEnv,
+    rack_id: &RackId,
+) -> HealthReport {
+    let racks = env
+        .api
+        .find_racks_by_ids(Request::new(rpc_forge::RacksByIdsRequest {
+            rack_ids: vec![rack_id.clone()],
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+        .racks;
+    racks[0]
+        .status
+        .as_ref()
+        .unwrap()
+        .health
+        .clone()
+        .unwrap()
+        .try_into()
+        .unwrap()
+}
+
+#[crate::sqlx_test]
+async fn test_health_silence_applies_to_matching_hosts(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env =
+        create_test_env_with_overrides(pool.clone(), TestEnvOverrides::with_config(get_config()))
+            .await;
+
+    let rack_id = RackId::new(uuid::Uuid::new_v4().to_string());
+    let mut txn = pool.acquire().await?;
+    TestRackDbBuilder::new()
+        .with_rack_id(rack_id.clone())
+        .persist(&mut txn)
+        .await?;
+    drop(txn);
+
+    let mh = create_managed_host_with_config(
+        &env,
+        ManagedHostConfig::with_expected_machine_data(ExpectedMachineData {
+            rack_id: Some(rack_id.clone()),
+            ..Default::default()
+        }),
+    )
+    .await;
+
+    env.api
+        .insert_rack_health_report(Request::new(rpc_forge::InsertRackHealthReportRequest {
+            rack_id: Some(rack_id.clone()),
+            health_report_entry: Some(rpc_forge::HealthReportEntry {
+                report: Some(leak_alert_report().into()),
+                mode: rpc_forge::HealthReportApplyMode::Merge as i32,
+            }),
+        }))
+        .await?;
+
+    let silence = env
+        .api
+        .create_health_silence(Request::new(leak_silence_request(
+            rpc_forge::HealthSilenceObjectKind::Machine,
+            &rack_id,
+        )))
+        .await?
+        .into_inner();
+    assert_eq!(silence.state(), rpc_forge::HealthSilenceState::Active);
+    assert_eq!(silence.author, "operator");
+
+    let snapshot = db::managed_host::load_snapshot(
+        &mut env.db_reader(),
+        &mh.id,
+        LoadSnapshotOptions::default(),
+    )
+    .await?
+    .unwrap();
+    assert_eq!(
+        leak_alert_classifications(&snapshot.aggregate_health),
+        vec![HealthAlertClassification::suppress_external_alerting()]
+    );
+
+    // The silence only selects machines, so the rack itself is still unhealthy
+    assert_eq!(
+        leak_alert_classifications(&rack_health(&env, &rack_id).await),
+        vec![HealthAlertClassification::prevent_allocations()]
+    );
+
+    let ended = env
+        .api
+        .expire_health_silence(Request::new(rpc_forge::ExpireHealthSilenceRequest {
+            id: silence.id,
+            ended_by: Some("operator".to_string()),
+        }))
+        .await?
+        .into_inner();
+    assert_eq!(ended.state(), rpc_forge::HealthSilenceState::Ended);
+    assert_eq!(ended.ended_by.as_deref(), Some("operator"));
+
+    let snapshot = db::managed_host::load_snapshot(
+        &mut env.db_reader(),
+        &mh.id,
+        LoadSnapshotOptions::default(),
+    )
+    .await?
+    .unwrap();
+    assert_eq!(
+        leak_alert_classifications(&snapshot.aggregate_health),
+        vec![HealthAlertClassification::prevent_allocations()]
+    );
+
+    let open = env
+        .api
+        .list_health_silences(Request::new(rpc_forge::ListHealthSilencesRequest {
+            include_ended: false,
+        }))
+        .await?
+        .into_inner();
+    assert!(open.silences.is_empty());
+    let all = env
+        .api
+        .list_health_silences(Request::new(rpc_forge::ListHealthSilencesRequest {
+            include_ended: true,
+        }))
+        .await?
+        .into_inner();
+    assert_eq!(all.silences.len(), 1);
+    assert_eq!(all.silences[0].reason, "leak sensor replacement");
+
+    let err = env
+        .api
+        .expire_health_silence(Request::new(rpc_forge::ExpireHealthSilenceRequest {
+            id: silence.id,
+            ended_by: Some("operator".to_string()),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_health_silence_applies_to_racks(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env =
+        create_test_env_with_overrides(pool.clone(), TestEnvOverrides::with_config(get_config()))
+            .await;
+
+    let mut txn = pool.acquire().await?;
+    let rack_id = TestRackDbBuilder::new().persist(&mut txn).await?;
+    drop(txn);
+
+    env.api
+        .insert_rack_health_report(Request::new(rpc_forge::InsertRackHealthReportRequest {
+            rack_id: Some(rack_id.clone()),
+            health_report_entry: Some(rpc_forge::HealthReportEntry {
+                report: Some(leak_alert_report().into()),
+                mode: rpc_forge::HealthReportApplyMode::Merge as i32,
+            }),
+        }))
+        .await?;
+
+    env.api
+        .create_health_silence(Request::new(leak_silence_request(
+            rpc_forge::HealthSilenceObjectKind::Rack,
+            &rack_id,
+        )))
+        .await?;
+
+    let health = rack_health(&env, &rack_id).await;
+    assert_eq!(
+        leak_alert_classifications(&health),
+        vec![HealthAlertClassification::suppress_external_alerting()]
+    );
+    assert!(health.alerts[0].message.contains("leak sensor replacement"));
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_create_health_silence_validation(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env =
+        create_test_env_with_overrides(pool.clone(), TestEnvOverrides::with_config(get_config()))
+            .await;
+    let rack_id = RackId::new(uuid::Uuid::new_v4().to_string());
+    let valid = leak_silence_request(rpc_forge::HealthSilenceObjectKind::Any, &rack_id);
+
+    let invalid_requests = [
+        rpc_forge::CreateHealthSilenceRequest {
+            matcher: Some(Default::default()),
+            ..valid.clone()
+        },
+        rpc_forge::CreateHealthSilenceRequest {
+            duration: None,
+            ..valid.clone()
+        },
+        rpc_forge::CreateHealthSilenceRequest {
+            duration: Some(std::time::Duration::from_secs(31 * 24 * 60 * 60).into()),
+            ..valid.clone()
+        },
+        rpc_forge::CreateHealthSilenceRequest {
+            expires_at: Some((chrono::Utc::now() - chrono::TimeDelta::hours(1)).into()),
+            duration: None,
+            ..valid.clone()
+        },
+        rpc_forge::CreateHealthSilenceRequest {
+            reason: String::new(),
+            ..valid.clone()
+        },
+        rpc_forge::CreateHealthSilenceRequest {
+            author: None,
+            ..valid.clone()
+        },
+    ];
+    for request in invalid_requests {
+        let err = env
+            .api
+            .create_health_silence(Request::new(request.clone()))
+            .await
+            .expect_err(&format!("{request:?} should be rejected"));
+        assert_eq!(err.code(), tonic::Code::InvalidArgument, "{request:?}");
+    }
+
+    let err = env
+        .api
+        .expire_health_silence(Request::new(rpc_forge::ExpireHealthSilenceRequest {
+            id: 12345,
+            ended_by: Some("operator".to_string()),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::NotFound);
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_health_silence_expiry_is_recorded(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env =
+        create_test_env_with_overrides(pool.clone(), TestEnvOverrides::with_config(get_config()))
+            .await;
+
+    let now = chrono::Utc::now();
+    let mut txn = pool.begin().await?;
+    let silence = db::health_silence::create(
+        &mut txn,
+        &model::health_silence::HealthSilenceMatcher {
+            sku: Some("sku-a".to_string()),
+            ..Default::default()
+        },
+        "operator",
+        "firmware rollout",
+        now - chrono::TimeDelta::hours(2),
+        now - chrono::TimeDelta::hours(1),
+    )
+    .await?;
+    txn.commit().await?;
+
+    HealthSilenceExpiry::new(pool.clone(), env.api.work_lock_manager_handle.clone())
+        .run_single_iteration()
+        .await?;
+
+    let silence = db::health_silence::find_by_id(&pool, silence.id)
+        .await?
+        .unwrap();
+    assert_eq!(silence.ended_at, Some(silence.expires_at));
+    assert_eq!(silence.ended_by, None);
+    assert_eq!(
+        silence.state(chrono::Utc::now()),
+        model::health_silence::HealthSilenceState::Expired
+    );
+
+    Ok(())
+}
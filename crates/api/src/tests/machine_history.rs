@@ -277,6 +277,145 @@ async fn test_old_machine_state_history(
     Ok(())
 }
 
+/// Check that a managed host can be reconstructed at a point in time and diffed
+/// against an earlier point in time
+#[crate::sqlx_test]
+async fn test_get_managed_host_at(pool: sqlx::PgPool) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let (host_machine_id, dpu_machine_id) = create_managed_host(&env).await.into();
+    let before = chrono::Utc::now();
+
+    let mut txn = env.pool.begin().await?;
+    let query = "INSERT INTO machine_state_history (machine_id, state, state_version) VALUES ($1, $2::jsonb, $3)";
+    sqlx::query(query)
+        .bind(host_machine_id.to_string())
+        .bind(r#"{"state": "timetravel"}"#)
+        .bind(ConfigVersion::initial())
+        .execute(&mut *txn)
+        .await?;
+    txn.commit().await?;
+    let after = chrono::Utc::now();
+
+    // Looking up a DPU reconstructs its host
+    let response = env
+        .api
+        .get_managed_host_at(tonic::Request::new(rpc::forge::GetManagedHostAtRequest {
+            machine_id: Some(dpu_machine_id),
+            timestamp: Some(after.into()),
+            diff_from: Some(before.into()),
+        }))
+        .await?
+        .into_inner();
+
+    let snapshot = response.snapshot.unwrap();
+    let host = snapshot.host.unwrap();
+    assert_eq!(host.machine_id, Some(host_machine_id));
+    assert_eq!(
+        host.controller_state.unwrap().event,
+        r#"{"state": "timetravel"}"#
+    );
+    assert!(host.network_config_version.is_some());
+    assert_eq!(snapshot.dpus.len(), 1);
+    assert_eq!(snapshot.dpus[0].machine_id, Some(dpu_machine_id));
+    assert!(snapshot.instance.is_none());
+
+    let diff_from_snapshot = response.diff_from_snapshot.unwrap();
+    assert_ne!(
+        diff_from_snapshot
+            .host
+            .unwrap()
+            .controller_state
+            .unwrap()
+            .event,
+        r#"{"state": "timetravel"}"#
+    );
+    let change = response
+        .changes
+        .iter()
+        .find(|change| change.field == "host.controller_state")
+        .unwrap();
+    assert_eq!(change.after.as_deref(), Some(r#"{"state": "timetravel"}"#));
+
+    // DPUs are looked up as of the requested time, so detaching the DPU doesn't change the past
+    let query =
+        "UPDATE machine_interfaces SET attached_dpu_machine_id = NULL WHERE machine_id = $1";
+    sqlx::query(query)
+        .bind(host_machine_id)
+        .execute(&env.pool)
+        .await?;
+    for (timestamp, expected_dpus) in [(after, 1), (chrono::Utc::now(), 0)] {
+        let snapshot = env
+            .api
+            .get_managed_host_at(tonic::Request::new(rpc::forge::GetManagedHostAtRequest {
+                machine_id: Some(host_machine_id),
+                timestamp: Some(timestamp.into()),
+                diff_from: None,
+            }))
+            .await?
+            .into_inner()
+            .snapshot
+            .unwrap();
+        assert_eq!(snapshot.dpus.len(), expected_dpus);
+    }
+
+    // A DPU resolves to the host it was attached to at the requested time
+    let snapshot = env
+        .api
+        .get_managed_host_at(tonic::Request::new(rpc::forge::GetManagedHostAtRequest {
+            machine_id: Some(dpu_machine_id),
+            timestamp: Some(after.into()),
+            diff_from: None,
+        }))
+        .await?
+        .into_inner()
+        .snapshot
+        .unwrap();
+    assert_eq!(snapshot.host.unwrap().machine_id, Some(host_machine_id));
+    let err = env
+        .api
+        .get_managed_host_at(tonic::Request::new(rpc::forge::GetManagedHostAtRequest {
+            machine_id: Some(dpu_machine_id),
+            timestamp: Some(chrono::Utc::now().into()),
+            diff_from: None,
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::NotFound);
+
+    // Nothing was recorded before the host was created
+    let response = env
+        .api
+        .get_managed_host_at(tonic::Request::new(rpc::forge::GetManagedHostAtRequest {
+            machine_id: Some(host_machine_id),
+            timestamp: Some((before - chrono::Duration::days(1)).into()),
+            diff_from: None,
+        }))
+        .await?
+        .into_inner();
+    let snapshot = response.snapshot.unwrap();
+    assert!(snapshot.dpus.is_empty());
+    let host = snapshot.host.unwrap();
+    assert!(host.controller_state.is_none());
+    assert!(host.health.is_none());
+    assert!(host.network_config_version.is_none());
+    assert!(host.interfaces.is_empty());
+    assert!(response.diff_from_snapshot.is_none());
+    assert!(response.changes.is_empty());
+
+    let err = env
+        .api
+        .get_managed_host_at(tonic::Request::new(rpc::forge::GetManagedHostAtRequest {
+            machine_id: Some(host_machine_id),
+            timestamp: None,
+            diff_from: None,
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+    Ok(())
+}
+
 fn json_history(history: &[StateHistoryRecord]) -> serde_json::Result<Vec<serde_json::Value>> {
     // // Check that version numbers are always incrementing by 1
     if !history.is_empty() {
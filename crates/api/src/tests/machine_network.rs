@@ -18,10 +18,12 @@ use std::ops::DerefMut;
 use std::time::SystemTime;
 
 use ::rpc::forge::{
-    CreateDpuExtensionServiceRequest, DpuExtensionServiceType, DpuNetworkStatus,
+    AppliedNvueConfig, CreateDpuExtensionServiceRequest, DpuExtensionServiceType, DpuNetworkStatus,
     InstanceDpuExtensionServiceConfig, InstanceDpuExtensionServicesConfig,
-    ManagedHostNetworkConfigRequest, ManagedHostNetworkStatusRequest,
+    ManagedHostNetworkConfigRequest, ManagedHostNetworkStatusRequest, NetworkConfigRollback,
+    RenderDpuNetworkConfigRequest, RenderDpuNetworkConfigResponse,
 };
+use carbide_uuid::machine::MachineId;
 use common::api_fixtures::{self, create_managed_host, dpu, network_configured_with_health};
 use forge_secrets::credentials::{BgpCredentialType, CredentialKey, Credentials};
 use model::machine::network::ManagedHostQuarantineMode;
@@ -456,6 +458,8 @@ async fn test_dpu_health_is_required(pool: sqlx::PgPool) {
             last_dhcp_requests: vec![],
             dpu_extension_service_version: Some("V1-T1".to_string()),
             dpu_extension_services: vec![],
+            network_config_rollback: None,
+            applied_nvue_config: None,
         }))
         .await
         .expect_err("Should fail");
@@ -791,3 +795,141 @@ async fn test_quarantine_state_crud(pool: sqlx::PgPool) -> Result<(), Box<dyn st
 
     Ok(())
 }
+
+async fn record_applied_nvue_config(
+    env: &api_fixtures::TestEnv,
+    dpu_machine_id: &MachineId,
+    applied_nvue_config: AppliedNvueConfig,
+) {
+    env.api
+        .record_dpu_network_status(tonic::Request::new(DpuNetworkStatus {
+            dpu_machine_id: Some(*dpu_machine_id),
+            dpu_health: Some(rpc::health::HealthReport {
+                source: "forge-dpu-agent".to_string(),
+                triggered_by: None,
+                observed_at: None,
+                successes: vec![],
+                alerts: vec![],
+            }),
+            applied_nvue_config: Some(applied_nvue_config),
+            ..Default::default()
+        }))
+        .await
+        .unwrap();
+}
+
+async fn render_dpu_network_config(
+    env: &api_fixtures::TestEnv,
+    dpu_machine_id: &MachineId,
+) -> Result<RenderDpuNetworkConfigResponse, tonic::Status> {
+    env.api
+        .render_dpu_network_config(tonic::Request::new(RenderDpuNetworkConfigRequest {
+            dpu_machine_id: Some(*dpu_machine_id),
+            network_config: None,
+            network_security_group_id: None,
+        }))
+        .await
+        .map(|response| response.into_inner())
+}
+
+#[crate::sqlx_test]
+async fn test_render_dpu_network_config(pool: sqlx::PgPool) {
+    let env = api_fixtures::create_test_env(pool).await;
+    let mh = create_managed_host(&env).await;
+    let dpu_machine_id = mh.dpu().id;
+
+    // The HBN version and hostname of the DPU are unknown until it reports an applied config
+    let err = render_dpu_network_config(&env, &dpu_machine_id)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+
+    let mut applied = AppliedNvueConfig {
+        hbn_version: "2.4.0-doca2.9.0".to_string(),
+        dpu_hostname: "dpu-1".to_string(),
+        dpu_search_domain: "example.com".to_string(),
+        is_dpu_os: true,
+        contents: "previous: config\n".to_string(),
+    };
+    record_applied_nvue_config(&env, &dpu_machine_id, applied.clone()).await;
+
+    let rendered = render_dpu_network_config(&env, &dpu_machine_id)
+        .await
+        .unwrap();
+    let network_config = rendered.network_config.as_ref().unwrap();
+    assert!(network_config.use_admin_network);
+    assert!(rendered.rendered_config.contains("dpu-1"));
+    assert!(rendered.diff.starts_with("--- applied\n+++ rendered\n"));
+    assert!(rendered.diff.contains("\n-previous: config\n"));
+    assert_eq!(rendered.applied_config.unwrap(), applied);
+
+    // Once the DPU applied the rendered config, there is no difference
+    applied.contents = rendered.rendered_config.clone();
+    record_applied_nvue_config(&env, &dpu_machine_id, applied).await;
+    let rerendered = render_dpu_network_config(&env, &dpu_machine_id)
+        .await
+        .unwrap();
+    assert_eq!(rerendered.rendered_config, rendered.rendered_config);
+    assert!(rerendered.diff.is_empty());
+}
+
+#[crate::sqlx_test]
+async fn test_record_network_config_rollback(pool: sqlx::PgPool) {
+    let env = api_fixtures::create_test_env(pool).await;
+    let (_host_machine_id, dpu_machine_id) = create_managed_host(&env).await.into();
+
+    let rollback = NetworkConfigRollback {
+        rejected_config_version: "V2-T1".to_string(),
+        rolled_back_at: Some(chrono::Utc::now().into()),
+        failed_probes: vec!["BgpPeeringTor: not established".to_string()],
+        restored: true,
+        retry_after: Some((chrono::Utc::now() + chrono::Duration::minutes(10)).into()),
+    };
+    env.api
+        .record_dpu_network_status(tonic::Request::new(DpuNetworkStatus {
+            dpu_machine_id: Some(dpu_machine_id),
+            dpu_health: Some(rpc::health::HealthReport {
+                source: "forge-dpu-agent".to_string(),
+                triggered_by: None,
+                observed_at: None,
+                successes: vec![],
+                alerts: vec![],
+            }),
+            network_config_rollback: Some(rollback.clone()),
+            ..Default::default()
+        }))
+        .await
+        .unwrap();
+
+    let response = env
+        .api
+        .get_all_managed_host_network_status(tonic::Request::new(
+            ManagedHostNetworkStatusRequest {},
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+    let status = response
+        .all
+        .into_iter()
+        .find(|status| status.dpu_machine_id == Some(dpu_machine_id))
+        .unwrap();
+    assert_eq!(status.network_config_rollback, Some(rollback));
+
+    // The rollback is cleared once the agent stops reporting it
+    network_configured_with_health(&env, &dpu_machine_id, None).await;
+    let response = env
+        .api
+        .get_all_managed_host_network_status(tonic::Request::new(
+            ManagedHostNetworkStatusRequest {},
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+    let status = response
+        .all
+        .into_iter()
+        .find(|status| status.dpu_machine_id == Some(dpu_machine_id))
+        .unwrap();
+    assert_eq!(status.network_config_rollback, None);
+}
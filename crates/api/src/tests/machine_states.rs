@@ -568,6 +568,7 @@ async fn test_nvme_clean_failed_state_host(pool: sqlx::PgPool) {
         ram: None,
         mem_overwrite: None,
         ib: None,
+        media_sanitization: vec![],
         result: 0,
     });
 
@@ -612,6 +613,7 @@ async fn test_nvme_clean_failed_state_host(pool: sqlx::PgPool) {
         ram: None,
         mem_overwrite: None,
         ib: None,
+        media_sanitization: vec![],
         result: 0,
     });
     env.api
@@ -644,6 +646,7 @@ async fn test_nvme_clean_failed_state_host(pool: sqlx::PgPool) {
         ram: None,
         mem_overwrite: None,
         ib: None,
+        media_sanitization: vec![],
         result: 0,
     });
     env.api
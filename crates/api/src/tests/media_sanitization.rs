@@ -0,0 +1,220 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use chrono::Utc;
+use common::api_fixtures::{create_managed_host, create_test_env};
+use rpc::forge::forge_server::Forge;
+
+use crate::tests::common;
+
+fn nvme_record() -> rpc::forge::MediaSanitizationRecord {
+    let now = Utc::now();
+    rpc::forge::MediaSanitizationRecord {
+        device: "/dev/nvme0".to_string(),
+        transport: "nvme".to_string(),
+        serial_number: "S4EVNF0M123456".to_string(),
+        model: "SAMSUNG MZ1L2960HCJR".to_string(),
+        capacity_bytes: 3_840_755_982_336,
+        method: "nvme-format-crypto-erase".to_string(),
+        nist_category: "purge".to_string(),
+        raid_arrays: vec!["md0".to_string()],
+        result: rpc::machine_cleanup_info::CleanupResult::Ok as _,
+        message: String::new(),
+        started_at: Some(now.into()),
+        completed_at: Some(now.into()),
+        verification: Some(rpc::forge::MediaSanitizationVerification {
+            sectors_sampled: 64,
+            sectors_unchanged: 0,
+            passed: true,
+        }),
+    }
+}
+
+fn cleanup_info(
+    machine_id: carbide_uuid::machine::MachineId,
+    records: Vec<rpc::forge::MediaSanitizationRecord>,
+) -> tonic::Request<rpc::MachineCleanupInfo> {
+    tonic::Request::new(rpc::MachineCleanupInfo {
+        machine_id: Some(machine_id),
+        nvme: None,
+        ram: None,
+        mem_overwrite: None,
+        ib: None,
+        media_sanitization: records,
+        result: rpc::machine_cleanup_info::CleanupResult::Ok as _,
+    })
+}
+
+#[crate::sqlx_test]
+async fn test_media_sanitization_report_after_instance_release(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    let segment_id = env.create_vpc_and_tenant_segment().await;
+    let mh = create_managed_host(&env).await;
+
+    let tinstance = mh
+        .instance_builer(&env)
+        .single_interface_network_config(segment_id)
+        .build()
+        .await;
+    let instance_id = tinstance.id;
+    mh.delete_instance(&env, instance_id).await;
+
+    // The report is opened when the instance is released, before scout reports back
+    let report = env
+        .api
+        .get_media_sanitization_report(tonic::Request::new(
+            rpc::forge::MediaSanitizationReportRequest {
+                instance_id: Some(instance_id),
+                machine_id: None,
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(report.machine_id, Some(mh.id));
+    assert_eq!(report.instance_id, Some(instance_id));
+    assert!(report.released_at.is_some());
+    assert!(report.completed_at.is_none());
+    assert!(report.records.is_empty());
+
+    env.api
+        .cleanup_machine_completed(cleanup_info(mh.id, vec![nvme_record()]))
+        .await
+        .unwrap();
+
+    let report = env
+        .api
+        .get_media_sanitization_report(tonic::Request::new(
+            rpc::forge::MediaSanitizationReportRequest {
+                instance_id: Some(instance_id),
+                machine_id: None,
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(report.completed_at.is_some());
+    assert_eq!(report.records.len(), 1);
+    let record = &report.records[0];
+    assert_eq!(record.serial_number, "S4EVNF0M123456");
+    assert_eq!(record.method, "nvme-format-crypto-erase");
+    assert_eq!(record.raid_arrays, vec!["md0".to_string()]);
+    assert!(record.verification.as_ref().unwrap().passed);
+    // The test tenant has no machine identity signing key
+    assert!(report.signed_report.is_empty());
+}
+
+#[crate::sqlx_test]
+async fn test_media_sanitization_report_after_failed_cleanup(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    let segment_id = env.create_vpc_and_tenant_segment().await;
+    let mh = create_managed_host(&env).await;
+
+    let tinstance = mh
+        .instance_builer(&env)
+        .single_interface_network_config(segment_id)
+        .build()
+        .await;
+    let instance_id = tinstance.id;
+    mh.delete_instance(&env, instance_id).await;
+
+    let get_report = || {
+        env.api.get_media_sanitization_report(tonic::Request::new(
+            rpc::forge::MediaSanitizationReportRequest {
+                instance_id: Some(instance_id),
+                machine_id: None,
+            },
+        ))
+    };
+
+    // A failed cleanup leaves the report open
+    let mut failed_record = nvme_record();
+    failed_record.result = rpc::machine_cleanup_info::CleanupResult::Error as _;
+    failed_record.message = "sanitize command failed".to_string();
+    env.api
+        .cleanup_machine_completed(cleanup_info(mh.id, vec![failed_record]))
+        .await
+        .unwrap();
+
+    let report = get_report().await.unwrap().into_inner();
+    assert!(report.completed_at.is_none());
+    assert_eq!(report.records.len(), 1);
+    assert_eq!(
+        report.records[0].result,
+        rpc::machine_cleanup_info::CleanupResult::Error as i32
+    );
+
+    // The successful retry completes the same report
+    env.api
+        .cleanup_machine_completed(cleanup_info(mh.id, vec![nvme_record()]))
+        .await
+        .unwrap();
+
+    let report = get_report().await.unwrap().into_inner();
+    assert_eq!(report.instance_id, Some(instance_id));
+    assert!(report.completed_at.is_some());
+    assert_eq!(report.records.len(), 1);
+    assert_eq!(
+        report.records[0].result,
+        rpc::machine_cleanup_info::CleanupResult::Ok as i32
+    );
+}
+
+#[crate::sqlx_test]
+async fn test_media_sanitization_report_without_release(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    let mh = create_managed_host(&env).await;
+
+    let err = env
+        .api
+        .get_media_sanitization_report(tonic::Request::new(
+            rpc::forge::MediaSanitizationReportRequest {
+                instance_id: None,
+                machine_id: Some(mh.id),
+            },
+        ))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::NotFound);
+
+    env.api
+        .cleanup_machine_completed(cleanup_info(mh.id, vec![nvme_record()]))
+        .await
+        .unwrap();
+
+    let report = env
+        .api
+        .get_media_sanitization_report(tonic::Request::new(
+            rpc::forge::MediaSanitizationReportRequest {
+                instance_id: None,
+                machine_id: Some(mh.id),
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(report.instance_id, None);
+    assert_eq!(report.records.len(), 1);
+
+    let err = env
+        .api
+        .get_media_sanitization_report(tonic::Request::new(
+            rpc::forge::MediaSanitizationReportRequest::default(),
+        ))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+}
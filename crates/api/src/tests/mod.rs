@@ -15,6 +15,8 @@
  * limitations under the License.
  */
 
+mod alert_dispatch;
+mod authorization_policy;
 pub(crate) mod common;
 mod compute_allocation;
 mod connected_device;
@@ -40,6 +42,7 @@ mod explored_endpoint_find;
 mod explored_managed_host_find;
 mod extension_service;
 mod finder;
+mod health_silence;
 mod host_bmc_firmware_test;
 mod ib_fabric_find;
 mod ib_fabric_monitor;
@@ -80,6 +83,7 @@ mod machine_validation;
 mod maintenance;
 #[cfg(feature = "linux-build")]
 mod measured_boot;
+mod media_sanitization;
 mod mqtt_state_change_hook;
 mod network_device;
 mod network_security_group;
@@ -94,6 +98,7 @@ mod power_shelf_find;
 mod power_shelf_health;
 mod power_shelf_metadata;
 mod power_shelf_state_controller;
+mod prefix_list;
 mod prevent_duplicate_mac_addresses;
 mod rack_find;
 mod rack_firmware;
@@ -102,6 +107,7 @@ mod rack_metadata;
 mod rack_state_controller;
 mod redfish_actions;
 mod resource_pool;
+mod retention;
 mod route_servers;
 mod service_health_metrics;
 mod set_primary_dpu;
@@ -37,7 +37,7 @@ use crate::tests::common::api_fixtures::instance::{
 };
 use crate::tests::common::api_fixtures::managed_host::ManagedHostConfig;
 use crate::tests::common::api_fixtures::{
-    create_test_env, populate_network_security_groups, site_explorer,
+    create_managed_host, create_test_env, populate_network_security_groups, site_explorer,
 };
 use crate::tests::common::rpc_builder::VpcCreationRequest;
 
@@ -87,6 +87,8 @@ async fn update_network_status_observation(
             last_dhcp_requests: vec![],
             dpu_extension_service_version: Some("V1-T1".to_string()),
             dpu_extension_services: vec![],
+            network_config_rollback: None,
+            applied_nvue_config: None,
         }))
         .await
         .unwrap();
@@ -1122,6 +1124,7 @@ async fn test_network_security_group_propagation_impl(
                 network_security_group_ids: None,
                 vpc_ids: vec![],
                 instance_ids: vec![],
+                prefix_list_ids: vec![],
             },
         ))
         .await
@@ -1142,6 +1145,7 @@ async fn test_network_security_group_propagation_impl(
                 network_security_group_ids: None,
                 vpc_ids: vec![vpc_id.to_string()],
                 instance_ids: vec![instance_id.to_string()],
+                prefix_list_ids: vec![],
             },
         ))
         .await
@@ -1239,6 +1243,7 @@ async fn test_network_security_group_propagation_impl(
                 network_security_group_ids: None,
                 vpc_ids: vec![vpc_id.to_string()],
                 instance_ids: vec![instance_id.to_string()],
+                prefix_list_ids: vec![],
             },
         ))
         .await
@@ -1301,6 +1306,7 @@ async fn test_network_security_group_propagation_impl(
                 network_security_group_ids: None,
                 vpc_ids: vec![vpc_id.to_string()],
                 instance_ids: vec![instance_id.to_string()],
+                prefix_list_ids: vec![],
             },
         ))
         .await
@@ -1365,6 +1371,7 @@ async fn test_network_security_group_propagation_impl(
                 network_security_group_ids: None,
                 vpc_ids: vec![vpc_id.to_string()],
                 instance_ids: vec![],
+                prefix_list_ids: vec![],
             },
         ))
         .await
@@ -1410,6 +1417,7 @@ async fn test_network_security_group_propagation_impl(
                 network_security_group_ids: None,
                 vpc_ids: vec![vpc_id.to_string()],
                 instance_ids: vec![],
+                prefix_list_ids: vec![],
             },
         ))
         .await
@@ -1487,6 +1495,7 @@ async fn test_network_security_group_propagation_impl(
                 network_security_group_ids: None,
                 vpc_ids: vec![vpc_id.to_string()],
                 instance_ids: vec![],
+                prefix_list_ids: vec![],
             },
         ))
         .await
@@ -1539,6 +1548,7 @@ async fn test_network_security_group_propagation_impl(
                 network_security_group_ids: None,
                 vpc_ids: vec![vpc_id.to_string()],
                 instance_ids: vec![],
+                prefix_list_ids: vec![],
             },
         ))
         .await
@@ -1621,6 +1631,7 @@ async fn test_network_security_group_propagation_impl(
                 network_security_group_ids: None,
                 vpc_ids: vec![vpc_id.to_string()],
                 instance_ids: vec![],
+                prefix_list_ids: vec![],
             },
         ))
         .await
@@ -1668,6 +1679,7 @@ async fn test_network_security_group_propagation_impl(
                 network_security_group_ids: None,
                 vpc_ids: vec![vpc_id.to_string()],
                 instance_ids: vec![],
+                prefix_list_ids: vec![],
             },
         ))
         .await
@@ -1715,6 +1727,7 @@ async fn test_network_security_group_propagation_impl(
                 network_security_group_ids: None,
                 vpc_ids: vec![vpc_id.to_string()],
                 instance_ids: vec![],
+                prefix_list_ids: vec![],
             },
         ))
         .await
@@ -1886,3 +1899,143 @@ async fn test_network_security_group_get_attachments(
 
     Ok(())
 }
+
+#[crate::sqlx_test]
+async fn test_analyze_connectivity(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+
+    populate_network_security_groups(env.api.clone()).await;
+
+    // Our known fixture network security group, which denies ingress
+    // with source and destination ports between 80 and 32768.
+    let network_security_group_id = "fd3ab096-d811-11ef-8fe9-7be4b2483448";
+
+    let segment_id = env
+        .create_vpc_and_tenant_segment_with_vpc_details(
+            VpcCreationRequest::builder("Tenant1", "Tenant1")
+                .network_security_group_id(network_security_group_id)
+                .rpc(),
+        )
+        .await;
+
+    let mh1 = create_managed_host(&env).await;
+    let instance1 = mh1
+        .instance_builer(&env)
+        .single_interface_network_config(segment_id)
+        .build()
+        .await;
+    let mh2 = create_managed_host(&env).await;
+    let instance2 = mh2
+        .instance_builer(&env)
+        .single_interface_network_config(segment_id)
+        .build()
+        .await;
+
+    let flow = |src_port| rpc::forge::ConnectivityFlow {
+        protocol: rpc::forge::NetworkSecurityGroupRuleProtocol::NsgRuleProtoTcp.into(),
+        src_port,
+        dst_port: Some(8080),
+    };
+    let instance_endpoint = |instance_id| rpc::forge::ConnectivityEndpoint {
+        endpoint: Some(rpc::forge::connectivity_endpoint::Endpoint::InstanceId(
+            instance_id,
+        )),
+    };
+
+    let analysis = env
+        .api
+        .analyze_connectivity(tonic::Request::new(
+            rpc::forge::AnalyzeConnectivityRequest {
+                source: Some(instance_endpoint(instance1.id)),
+                destination: Some(instance_endpoint(instance2.id)),
+                flow: Some(flow(Some(1000))),
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert!(!analysis.allowed);
+    let destination = analysis.destination.unwrap();
+    assert_eq!(destination.instance_id, Some(instance2.id));
+    assert_eq!(
+        destination.network_security_group_source(),
+        rpc::forge::NetworkSecurityGroupSource::NsgSourceVpc
+    );
+
+    let step = |steps: &[rpc::forge::ConnectivityStep], stage| {
+        steps
+            .iter()
+            .find(|step| step.stage() == stage)
+            .cloned()
+            .unwrap()
+    };
+    assert!(step(&analysis.steps, rpc::forge::ConnectivityStage::Routing).allowed);
+    let ingress = step(
+        &analysis.steps,
+        rpc::forge::ConnectivityStage::DestinationIngress,
+    );
+    assert!(!ingress.allowed);
+    assert_eq!(
+        ingress.rule.unwrap().id.as_deref(),
+        Some(network_security_group_id)
+    );
+
+    // The fixture rule only matches a known source port, so without one
+    // the ingress is denied because no rule matches.
+    let analysis = env
+        .api
+        .analyze_connectivity(tonic::Request::new(
+            rpc::forge::AnalyzeConnectivityRequest {
+                source: Some(instance_endpoint(instance1.id)),
+                destination: Some(instance_endpoint(instance2.id)),
+                flow: Some(flow(None)),
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+    let ingress = step(
+        &analysis.steps,
+        rpc::forge::ConnectivityStage::DestinationIngress,
+    );
+    assert!(!ingress.allowed);
+    assert!(ingress.rule.is_none());
+
+    // The protocol of the flow has to be specific
+    let err = env
+        .api
+        .analyze_connectivity(tonic::Request::new(
+            rpc::forge::AnalyzeConnectivityRequest {
+                source: Some(instance_endpoint(instance1.id)),
+                destination: Some(instance_endpoint(instance2.id)),
+                flow: Some(rpc::forge::ConnectivityFlow {
+                    protocol: rpc::forge::NetworkSecurityGroupRuleProtocol::NsgRuleProtoAny.into(),
+                    src_port: None,
+                    dst_port: None,
+                }),
+            },
+        ))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::InvalidArgument);
+
+    let matrix = env
+        .api
+        .get_vpc_reachability_matrix(tonic::Request::new(
+            rpc::forge::GetVpcReachabilityMatrixRequest {
+                vpc_id: destination.vpc_id,
+                flow: Some(flow(Some(1000))),
+                include_peered_vpcs: false,
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(matrix.endpoints.len(), 2);
+    assert_eq!(matrix.entries.len(), 2);
+    for entry in matrix.entries {
+        assert!(!entry.allowed);
+        assert!(entry.denied_by.is_some());
+    }
+}
@@ -0,0 +1,333 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use rpc::forge::forge_server::Forge;
+use tonic::Code;
+
+use super::common::api_fixtures::TestEnv;
+use crate::cfg::file::default_max_network_security_group_size;
+use crate::tests::common::api_fixtures::create_test_env;
+
+const TENANT_ORG: &str = "Tenant1";
+
+async fn create_tenant(env: &TestEnv) {
+    env.api
+        .create_tenant(tonic::Request::new(rpc::forge::CreateTenantRequest {
+            organization_id: TENANT_ORG.to_string(),
+            routing_profile_type: None,
+            metadata: Some(rpc::forge::Metadata {
+                name: TENANT_ORG.to_string(),
+                description: "".to_string(),
+                labels: vec![],
+            }),
+        }))
+        .await
+        .unwrap();
+}
+
+fn metadata(name: &str) -> Option<rpc::forge::Metadata> {
+    Some(rpc::forge::Metadata {
+        name: name.to_string(),
+        description: "".to_string(),
+        labels: vec![],
+    })
+}
+
+fn prefixes(count: u8) -> Option<rpc::forge::PrefixListAttributes> {
+    Some(rpc::forge::PrefixListAttributes {
+        prefixes: (0..count).map(|i| format!("192.0.2.{i}/32")).collect(),
+    })
+}
+
+/// An ingress rule from the prefix list to `dst_port_count` ports.
+fn rule_from_prefix_list(
+    prefix_list_id: carbide_uuid::prefix_list::PrefixListId,
+    dst_port_count: u32,
+) -> rpc::forge::NetworkSecurityGroupRuleAttributes {
+    rpc::forge::NetworkSecurityGroupRuleAttributes {
+        id: Some("from-prefix-list".to_string()),
+        direction: rpc::forge::NetworkSecurityGroupRuleDirection::NsgRuleDirectionIngress.into(),
+        ipv6: false,
+        src_port_start: None,
+        src_port_end: None,
+        dst_port_start: Some(1000),
+        dst_port_end: Some(1000 + dst_port_count - 1),
+        protocol: rpc::forge::NetworkSecurityGroupRuleProtocol::NsgRuleProtoTcp.into(),
+        action: rpc::forge::NetworkSecurityGroupRuleAction::NsgRuleActionPermit.into(),
+        priority: 100,
+        source_net: Some(
+            rpc::forge::network_security_group_rule_attributes::SourceNet::SrcPrefixListId(
+                prefix_list_id,
+            ),
+        ),
+        destination_net: Some(
+            rpc::forge::network_security_group_rule_attributes::DestinationNet::DstPrefix(
+                "0.0.0.0/0".to_string(),
+            ),
+        ),
+    }
+}
+
+#[crate::sqlx_test]
+async fn test_prefix_list_lifecycle(pool: sqlx::PgPool) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    create_tenant(&env).await;
+
+    let max_nsg_size = default_max_network_security_group_size();
+
+    // A list can't be larger than the largest rule set it could be used in.
+    let err = env
+        .api
+        .create_prefix_list(tonic::Request::new(rpc::forge::CreatePrefixListRequest {
+            id: None,
+            tenant_organization_id: TENANT_ORG.to_string(),
+            metadata: metadata("too_big"),
+            attributes: Some(rpc::forge::PrefixListAttributes {
+                prefixes: (0..=max_nsg_size)
+                    .map(|i| format!("10.{}.{}.0/24", i / 256, i % 256))
+                    .collect(),
+            }),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::InvalidArgument);
+
+    // Duplicates are rejected.
+    let err = env
+        .api
+        .create_prefix_list(tonic::Request::new(rpc::forge::CreatePrefixListRequest {
+            id: None,
+            tenant_organization_id: TENANT_ORG.to_string(),
+            metadata: metadata("duplicates"),
+            attributes: Some(rpc::forge::PrefixListAttributes {
+                prefixes: vec!["192.0.2.0/24".to_string(), "192.0.2.0/24".to_string()],
+            }),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::InvalidArgument);
+
+    let prefix_list = env
+        .api
+        .create_prefix_list(tonic::Request::new(rpc::forge::CreatePrefixListRequest {
+            id: None,
+            tenant_organization_id: TENANT_ORG.to_string(),
+            metadata: metadata("partners"),
+            attributes: prefixes(1),
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+        .prefix_list
+        .unwrap();
+    let prefix_list_id = prefix_list.id.unwrap();
+    let initial_version = prefix_list.version.clone();
+
+    let found = env
+        .api
+        .find_prefix_list_ids(tonic::Request::new(rpc::forge::FindPrefixListIdsRequest {
+            name: Some("partners".to_string()),
+            tenant_organization_id: Some(TENANT_ORG.to_string()),
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+        .prefix_list_ids;
+    assert_eq!(found, vec![prefix_list_id]);
+
+    let found = env
+        .api
+        .find_prefix_lists_by_ids(tonic::Request::new(
+            rpc::forge::FindPrefixListsByIdsRequest {
+                prefix_list_ids: vec![prefix_list_id],
+                tenant_organization_id: None,
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner()
+        .prefix_lists;
+    assert_eq!(found, vec![prefix_list.clone()]);
+
+    // An NSG can't reference a list that doesn't exist.
+    let err = env
+        .api
+        .create_network_security_group(tonic::Request::new(
+            rpc::forge::CreateNetworkSecurityGroupRequest {
+                id: None,
+                tenant_organization_id: TENANT_ORG.to_string(),
+                metadata: metadata("missing_list"),
+                network_security_group_attributes: Some(
+                    rpc::forge::NetworkSecurityGroupAttributes {
+                        stateful_egress: false,
+                        rules: vec![rule_from_prefix_list(uuid::Uuid::new_v4().into(), 1)],
+                    },
+                ),
+            },
+        ))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::NotFound);
+
+    // Half the limit of ports, so the rule set is exactly at the limit
+    // once the list has two prefixes.
+    let nsg = env
+        .api
+        .create_network_security_group(tonic::Request::new(
+            rpc::forge::CreateNetworkSecurityGroupRequest {
+                id: None,
+                tenant_organization_id: TENANT_ORG.to_string(),
+                metadata: metadata("uses_list"),
+                network_security_group_attributes: Some(
+                    rpc::forge::NetworkSecurityGroupAttributes {
+                        stateful_egress: false,
+                        rules: vec![rule_from_prefix_list(prefix_list_id, max_nsg_size / 2)],
+                    },
+                ),
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner()
+        .network_security_group
+        .unwrap();
+
+    // Growing the list to two prefixes keeps the NSG within limits,
+    // and the NSG gets a new version so the change is propagated.
+    let updated = env
+        .api
+        .update_prefix_list(tonic::Request::new(rpc::forge::UpdatePrefixListRequest {
+            id: Some(prefix_list_id),
+            tenant_organization_id: TENANT_ORG.to_string(),
+            metadata: metadata("partners"),
+            if_version_match: Some(initial_version.clone()),
+            attributes: prefixes(2),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(updated.network_security_group_ids, vec![nsg.id.clone()]);
+    let prefix_list = updated.prefix_list.unwrap();
+    assert_eq!(prefix_list.attributes.unwrap().prefixes.len(), 2);
+
+    let updated_nsg = env
+        .api
+        .find_network_security_groups_by_ids(tonic::Request::new(
+            rpc::forge::FindNetworkSecurityGroupsByIdsRequest {
+                network_security_group_ids: vec![nsg.id.clone()],
+                tenant_organization_id: None,
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner()
+        .network_security_groups
+        .pop()
+        .unwrap();
+    assert_ne!(updated_nsg.version, nsg.version);
+    assert_eq!(updated_nsg.attributes, nsg.attributes);
+
+    // A third prefix would push the NSG past its limits.
+    let err = env
+        .api
+        .update_prefix_list(tonic::Request::new(rpc::forge::UpdatePrefixListRequest {
+            id: Some(prefix_list_id),
+            tenant_organization_id: TENANT_ORG.to_string(),
+            metadata: metadata("partners"),
+            if_version_match: None,
+            attributes: prefixes(3),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::FailedPrecondition);
+    assert!(err.message().contains(&nsg.id), "{}", err.message());
+
+    // A stale version is rejected.
+    let err = env
+        .api
+        .update_prefix_list(tonic::Request::new(rpc::forge::UpdatePrefixListRequest {
+            id: Some(prefix_list_id),
+            tenant_organization_id: TENANT_ORG.to_string(),
+            metadata: metadata("partners"),
+            if_version_match: Some(initial_version),
+            attributes: prefixes(1),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::FailedPrecondition);
+
+    // Propagation status can be requested by prefix list alone.  The NSG
+    // isn't attached to anything, so there's nothing to report.
+    let status = env
+        .api
+        .get_network_security_group_propagation_status(tonic::Request::new(
+            rpc::forge::GetNetworkSecurityGroupPropagationStatusRequest {
+                network_security_group_ids: None,
+                vpc_ids: vec![],
+                instance_ids: vec![],
+                prefix_list_ids: vec![prefix_list_id],
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(status.vpcs.is_empty());
+    assert!(status.instances.is_empty());
+
+    // A list can't be deleted while an NSG references it.
+    let err = env
+        .api
+        .delete_prefix_list(tonic::Request::new(rpc::forge::DeletePrefixListRequest {
+            id: Some(prefix_list_id),
+            tenant_organization_id: TENANT_ORG.to_string(),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::FailedPrecondition);
+
+    env.api
+        .delete_network_security_group(tonic::Request::new(
+            rpc::forge::DeleteNetworkSecurityGroupRequest {
+                id: nsg.id.clone(),
+                tenant_organization_id: TENANT_ORG.to_string(),
+            },
+        ))
+        .await
+        .unwrap();
+
+    env.api
+        .delete_prefix_list(tonic::Request::new(rpc::forge::DeletePrefixListRequest {
+            id: Some(prefix_list_id),
+            tenant_organization_id: TENANT_ORG.to_string(),
+        }))
+        .await
+        .unwrap();
+
+    let found = env
+        .api
+        .find_prefix_list_ids(tonic::Request::new(rpc::forge::FindPrefixListIdsRequest {
+            name: None,
+            tenant_organization_id: Some(TENANT_ORG.to_string()),
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+        .prefix_list_ids;
+    assert!(found.is_empty());
+
+    Ok(())
+}
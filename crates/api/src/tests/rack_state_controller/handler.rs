@@ -210,7 +210,7 @@ async fn insert_default_rack_firmware(
     txn.commit().await.unwrap();
 }
 
-async fn create_single_compute_rack(
+pub(crate) async fn create_single_compute_rack(
     env: &TestEnv,
     pool: &sqlx::PgPool,
 ) -> Result<(RackId, model::machine::ManagedHostStateSnapshot), Box<dyn std::error::Error>> {
@@ -2427,7 +2427,7 @@ async fn test_validation_failed_transitions_to_error(
     Ok(())
 }
 
-async fn get_db_rack<DB>(conn: &mut DB, rack_id: &RackId) -> Rack
+pub(crate) async fn get_db_rack<DB>(conn: &mut DB, rack_id: &RackId) -> Rack
 where
     for<'db> &'db mut DB: DbReader<'db>,
 {
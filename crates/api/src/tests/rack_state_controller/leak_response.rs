@@ -0,0 +1,444 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use carbide_redfish::libredfish::test_support::RedfishSimAction;
+use carbide_uuid::rack::{RackId, RackProfileId};
+use db::rack as db_rack;
+use health_report::{
+    HealthAlertClassification, HealthProbeAlert, HealthReport, HealthReportApplyMode,
+};
+use libredfish::SystemPowerControl;
+use model::machine::machine_search_config::MachineSearchConfig;
+use model::power_manager::PowerState as DesiredPowerState;
+use model::rack::{MaintenanceScope, RackConfig, RackLeakResponseState, RackState};
+
+use super::handler::{
+    config_with_rack_profiles, create_single_compute_rack, get_db_rack, new_rack_id,
+};
+use crate::state_controller::db_write_batch::DbWriteBatch;
+use crate::state_controller::rack::context::RackStateHandlerContextObjects;
+use crate::state_controller::rack::handler::RackStateHandler;
+use crate::state_controller::rack::leak_response::LEAK_RESPONSE_OVERRIDE_SOURCE;
+use crate::state_controller::state_handler::{
+    StateHandler, StateHandlerContext, StateHandlerOutcome,
+};
+use crate::tests::common::api_fixtures::{
+    TestEnv, TestEnvOverrides, create_test_env_with_overrides,
+};
+
+const LEAK_SOURCE: &str = "dsx-exchange-consumer";
+
+async fn leak_response_env(pool: &sqlx::PgPool) -> TestEnv {
+    let mut config = config_with_rack_profiles();
+    config.rack_state_controller.leak_response.enabled = true;
+    create_test_env_with_overrides(pool.clone(), TestEnvOverrides::with_config(config)).await
+}
+
+async fn report_leak(
+    pool: &sqlx::PgPool,
+    rack_id: &RackId,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let report = HealthReport {
+        source: LEAK_SOURCE.to_string(),
+        triggered_by: None,
+        observed_at: Some(chrono::Utc::now()),
+        successes: vec![],
+        alerts: vec![HealthProbeAlert {
+            id: "BmsLeakDetectRack".parse().unwrap(),
+            target: Some(rack_id.to_string()),
+            in_alert_since: Some(chrono::Utc::now()),
+            message: "Leak detected".to_string(),
+            tenant_message: None,
+            classifications: vec![
+                HealthAlertClassification::prevent_allocations(),
+                HealthAlertClassification::sensor_critical(),
+            ],
+        }],
+    };
+    let mut txn = pool.begin().await?;
+    db_rack::insert_health_report(&mut txn, rack_id, HealthReportApplyMode::Merge, &report).await?;
+    txn.commit().await?;
+    Ok(())
+}
+
+async fn clear_leak(
+    pool: &sqlx::PgPool,
+    rack_id: &RackId,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut txn = pool.begin().await?;
+    db_rack::remove_health_report(&mut txn, rack_id, HealthReportApplyMode::Merge, LEAK_SOURCE)
+        .await?;
+    txn.commit().await?;
+    Ok(())
+}
+
+async fn handle(
+    env: &TestEnv,
+    rack_id: &RackId,
+    controller_state: RackState,
+) -> Result<StateHandlerOutcome<RackState>, Box<dyn std::error::Error>> {
+    let mut rack = get_db_rack(env.db_reader().as_mut(), rack_id).await;
+    let handler = RackStateHandler::default();
+    let mut services = env.state_handler_services();
+    let mut metrics = ();
+    let mut db_writes = DbWriteBatch::default();
+    let mut ctx = StateHandlerContext::<RackStateHandlerContextObjects> {
+        services: &mut services,
+        metrics: &mut metrics,
+        pending_db_writes: &mut db_writes,
+    };
+    let mut outcome = handler
+        .handle_object_state(rack_id, &mut rack, &controller_state, &mut ctx)
+        .await?;
+    if let Some(txn) = outcome.take_transaction() {
+        txn.commit().await?;
+    }
+    Ok(outcome)
+}
+
+fn next_state(outcome: StateHandlerOutcome<RackState>) -> RackState {
+    match outcome {
+        StateHandlerOutcome::Transition { next_state, .. } => next_state,
+        other => panic!(
+            "Expected Transition, got {:?}",
+            std::mem::discriminant(&other)
+        ),
+    }
+}
+
+fn leak_response(leak_response_state: RackLeakResponseState) -> RackState {
+    RackState::LeakResponse {
+        leak_response_state,
+    }
+}
+
+/// A leak reported for a Ready rack starts the playbook in Confirming.
+#[crate::sqlx_test]
+async fn test_leak_on_ready_rack_enters_confirming(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = leak_response_env(&pool).await;
+
+    let rack_id = new_rack_id();
+    let mut txn = pool.acquire().await?;
+    db_rack::create(
+        &mut txn,
+        &rack_id,
+        Some(&RackProfileId::new("Empty")),
+        &RackConfig::default(),
+        None,
+    )
+    .await?;
+    drop(txn);
+
+    let outcome = handle(&env, &rack_id, RackState::Ready).await?;
+    assert!(
+        !matches!(outcome, StateHandlerOutcome::Transition { .. }),
+        "Ready rack without a leak should not transition"
+    );
+
+    report_leak(&pool, &rack_id).await?;
+    let next = next_state(handle(&env, &rack_id, RackState::Ready).await?);
+    assert!(
+        matches!(
+            next,
+            RackState::LeakResponse {
+                leak_response_state: RackLeakResponseState::Confirming {
+                    interrupted: false,
+                    ..
+                },
+            }
+        ),
+        "Leak should start the playbook, got {next:?}"
+    );
+
+    Ok(())
+}
+
+/// A leak which clears during the confirmation window ends the playbook
+/// without touching the trays.
+#[crate::sqlx_test]
+async fn test_leak_cleared_during_confirmation(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = leak_response_env(&pool).await;
+
+    let rack_id = new_rack_id();
+    let mut txn = pool.acquire().await?;
+    db_rack::create(
+        &mut txn,
+        &rack_id,
+        Some(&RackProfileId::new("Empty")),
+        &RackConfig::default(),
+        None,
+    )
+    .await?;
+    drop(txn);
+
+    report_leak(&pool, &rack_id).await?;
+    let confirming = |interrupted| {
+        leak_response(RackLeakResponseState::Confirming {
+            detected_at: chrono::Utc::now(),
+            interrupted,
+        })
+    };
+    let outcome = handle(&env, &rack_id, confirming(false)).await?;
+    assert!(
+        matches!(outcome, StateHandlerOutcome::Wait { .. }),
+        "Leak within the confirmation window should wait"
+    );
+
+    clear_leak(&pool, &rack_id).await?;
+    assert_eq!(
+        next_state(handle(&env, &rack_id, confirming(false)).await?),
+        RackState::Ready
+    );
+    assert_eq!(
+        next_state(handle(&env, &rack_id, confirming(true)).await?),
+        RackState::Discovering
+    );
+
+    Ok(())
+}
+
+/// A confirmed leak drops pending maintenance and blocks allocations to the
+/// compute trays of the rack.
+#[crate::sqlx_test]
+async fn test_confirmed_leak_blocks_allocations(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = leak_response_env(&pool).await;
+    let (rack_id, host) = create_single_compute_rack(&env, &pool).await?;
+    let host_id = host.host_snapshot.id;
+
+    let mut txn = pool.begin().await?;
+    let config = RackConfig {
+        maintenance_requested: Some(MaintenanceScope::default()),
+        ..Default::default()
+    };
+    db_rack::update(&mut txn, &rack_id, &config).await?;
+    txn.commit().await?;
+    report_leak(&pool, &rack_id).await?;
+
+    let confirming = leak_response(RackLeakResponseState::Confirming {
+        detected_at: chrono::Utc::now() - chrono::Duration::minutes(10),
+        interrupted: false,
+    });
+    assert_eq!(
+        next_state(handle(&env, &rack_id, confirming).await?),
+        leak_response(RackLeakResponseState::BlockingAllocations)
+    );
+    let rack = get_db_rack(env.db_reader().as_mut(), &rack_id).await;
+    assert!(rack.config.maintenance_requested.is_none());
+
+    assert_eq!(
+        next_state(
+            handle(
+                &env,
+                &rack_id,
+                leak_response(RackLeakResponseState::BlockingAllocations)
+            )
+            .await?
+        ),
+        leak_response(RackLeakResponseState::NotifyingTenants)
+    );
+
+    let machine = db::machine::find_one(
+        env.db_reader().as_mut(),
+        &host_id,
+        MachineSearchConfig::default(),
+    )
+    .await?
+    .unwrap();
+    let report = machine
+        .health_reports
+        .merges
+        .get(LEAK_RESPONSE_OVERRIDE_SOURCE)
+        .expect("leak response override on the tray");
+    assert!(
+        report.alerts[0]
+            .classifications
+            .contains(&HealthAlertClassification::prevent_allocations())
+    );
+    assert_eq!(report.alerts[0].tenant_message, None);
+
+    Ok(())
+}
+
+/// An isolated rack stays isolated while the leak is reported and returns to
+/// service through an on-demand maintenance request once it cleared.
+#[crate::sqlx_test]
+async fn test_isolated_rack_returns_to_maintenance_after_leak_clears(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = leak_response_env(&pool).await;
+    let (rack_id, host) = create_single_compute_rack(&env, &pool).await?;
+    let host_id = host.host_snapshot.id;
+
+    report_leak(&pool, &rack_id).await?;
+    handle(
+        &env,
+        &rack_id,
+        leak_response(RackLeakResponseState::BlockingAllocations),
+    )
+    .await?;
+
+    let isolated = leak_response(RackLeakResponseState::Isolated);
+    let mut txn = pool.begin().await?;
+    let config = RackConfig {
+        maintenance_requested: Some(MaintenanceScope::default()),
+        ..Default::default()
+    };
+    db_rack::update(&mut txn, &rack_id, &config).await?;
+    txn.commit().await?;
+
+    let outcome = handle(&env, &rack_id, isolated.clone()).await?;
+    assert!(
+        matches!(outcome, StateHandlerOutcome::Wait { .. }),
+        "Isolated rack should wait while the leak is reported"
+    );
+
+    clear_leak(&pool, &rack_id).await?;
+    let next = next_state(handle(&env, &rack_id, isolated).await?);
+    assert!(
+        matches!(next, RackState::Maintenance { .. }),
+        "Maintenance request should release the rack, got {next:?}"
+    );
+
+    let machine = db::machine::find_one(
+        env.db_reader().as_mut(),
+        &host_id,
+        MachineSearchConfig::default(),
+    )
+    .await?
+    .unwrap();
+    assert!(
+        !machine
+            .health_reports
+            .merges
+            .contains_key(LEAK_RESPONSE_OVERRIDE_SOURCE)
+    );
+
+    Ok(())
+}
+
+/// Trays are shut down gracefully through the compute power control path, and
+/// forced off if they do not shut down in time.
+#[crate::sqlx_test]
+async fn test_trays_are_shut_down_and_forced_off(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = leak_response_env(&pool).await;
+    let (rack_id, host) = create_single_compute_rack(&env, &pool).await?;
+    let host_id = host.host_snapshot.id;
+    let redfish_client = env
+        .state_handler_services()
+        .create_redfish_client_from_machine(&host.host_snapshot)
+        .await?;
+    redfish_client.power(SystemPowerControl::On).await?;
+
+    let timepoint = env.redfish_sim.timepoint();
+    let next = next_state(
+        handle(
+            &env,
+            &rack_id,
+            leak_response(RackLeakResponseState::ShuttingDown),
+        )
+        .await?,
+    );
+    assert!(
+        matches!(
+            next,
+            RackState::LeakResponse {
+                leak_response_state: RackLeakResponseState::WaitForShutdown { .. },
+            }
+        ),
+        "Shutting down trays should wait for them, got {next:?}"
+    );
+    assert_eq!(
+        env.redfish_sim.actions_since(&timepoint).all_hosts(),
+        vec![RedfishSimAction::Power(
+            SystemPowerControl::GracefulShutdown
+        )]
+    );
+
+    let mut txn = pool.begin().await?;
+    let power_options = db::power_options::get_by_ids(&[host_id], &mut txn).await?;
+    assert_eq!(power_options[0].desired_power_state, DesiredPowerState::Off);
+    let machine = db::machine::find_one(&mut *txn, &host_id, MachineSearchConfig::default())
+        .await?
+        .unwrap();
+    assert!(
+        machine.health_reports.replace.is_none(),
+        "Power control override should be removed once the action was sent"
+    );
+    txn.commit().await?;
+
+    // The tray does not shut down in time
+    redfish_client.power(SystemPowerControl::On).await?;
+    let wait_for_shutdown =
+        |requested_at| leak_response(RackLeakResponseState::WaitForShutdown { requested_at });
+    let outcome = handle(&env, &rack_id, wait_for_shutdown(chrono::Utc::now())).await?;
+    assert!(
+        matches!(outcome, StateHandlerOutcome::Wait { .. }),
+        "Powered tray should be waited for until the graceful shutdown timeout"
+    );
+    let requested_at = chrono::Utc::now() - chrono::Duration::hours(1);
+    assert_eq!(
+        next_state(handle(&env, &rack_id, wait_for_shutdown(requested_at)).await?),
+        leak_response(RackLeakResponseState::PoweringOff)
+    );
+
+    let timepoint = env.redfish_sim.timepoint();
+    let next = next_state(
+        handle(
+            &env,
+            &rack_id,
+            leak_response(RackLeakResponseState::PoweringOff),
+        )
+        .await?,
+    );
+    assert!(
+        matches!(
+            next,
+            RackState::LeakResponse {
+                leak_response_state: RackLeakResponseState::WaitForPowerOff { .. },
+            }
+        ),
+        "Powering off trays should wait for them, got {next:?}"
+    );
+    assert_eq!(
+        env.redfish_sim.actions_since(&timepoint).all_hosts(),
+        vec![RedfishSimAction::Power(SystemPowerControl::ForceOff)]
+    );
+
+    assert_eq!(
+        next_state(
+            handle(
+                &env,
+                &rack_id,
+                leak_response(RackLeakResponseState::WaitForPowerOff {
+                    requested_at: chrono::Utc::now(),
+                }),
+            )
+            .await?
+        ),
+        leak_response(RackLeakResponseState::Isolated)
+    );
+
+    Ok(())
+}
@@ -48,6 +48,7 @@ use crate::tests::common::api_fixtures::{
 
 mod fixtures;
 mod handler;
+mod leak_response;
 use fixtures::rack::set_rack_controller_state;
 
 use crate::state_controller::rack::handler::RackStateHandler;
@@ -0,0 +1,269 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::net::IpAddr;
+
+use carbide_uuid::instance::InstanceId;
+use carbide_uuid::machine::MachineId;
+use common::api_fixtures::{create_managed_host, create_test_env};
+use flate2::read::GzDecoder;
+use model::allocation_type::AllocationType;
+use model::retention::RetentionTarget;
+use rpc::forge::forge_server::Forge;
+
+use crate::cfg::file::{RetentionConfig, RetentionPolicyConfig};
+use crate::retention::RetentionManager;
+use crate::tests::common;
+
+async fn state_history_len(pool: &sqlx::PgPool, machine_id: &MachineId) -> i64 {
+    let (count,): (i64,) =
+        sqlx::query_as("SELECT count(*) FROM machine_state_history WHERE machine_id = $1")
+            .bind(machine_id.to_string())
+            .fetch_one(pool)
+            .await
+            .unwrap();
+    count
+}
+
+#[crate::sqlx_test]
+async fn test_trim_table_max_rows_per_object(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    let mh = create_managed_host(&env).await;
+    let dpu_id = mh.dpu_ids[0];
+
+    let host_history = state_history_len(&env.pool, &mh.id).await;
+    let dpu_history = state_history_len(&env.pool, &dpu_id).await;
+    assert!(host_history > 3);
+    assert!(dpu_history > 3);
+
+    let response = env
+        .api
+        .trim_table(tonic::Request::new(rpc::forge::TrimTableRequest {
+            target: rpc::forge::TrimTableTarget::MachineStateHistory as i32,
+            keep_entries: Some(3),
+            max_age: None,
+            keep_last: 0,
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(
+        response.total_deleted,
+        (host_history + dpu_history - 6).to_string()
+    );
+    assert_eq!(response.total_archived, 0);
+    assert_eq!(state_history_len(&env.pool, &mh.id).await, 3);
+    assert_eq!(state_history_len(&env.pool, &dpu_id).await, 3);
+
+    // The newest entry is still the current state
+    let (latest_state,): (serde_json::Value,) = sqlx::query_as(
+        "SELECT state FROM machine_state_history WHERE machine_id = $1 ORDER BY id DESC LIMIT 1",
+    )
+    .bind(mh.id.to_string())
+    .fetch_one(&env.pool)
+    .await
+    .unwrap();
+    assert_eq!(latest_state["state"], "ready");
+}
+
+#[crate::sqlx_test]
+async fn test_trim_table_max_age_keeps_last(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    let mh = create_managed_host(&env).await;
+
+    // Every entry is older than a zero max_age, but the newest 2 are kept
+    env.api
+        .trim_table(tonic::Request::new(rpc::forge::TrimTableRequest {
+            target: rpc::forge::TrimTableTarget::MachineStateHistory as i32,
+            keep_entries: None,
+            max_age: Some(std::time::Duration::ZERO.into()),
+            keep_last: 2,
+        }))
+        .await
+        .unwrap();
+    assert_eq!(state_history_len(&env.pool, &mh.id).await, 2);
+
+    // Without keep_entries and max_age, old clients asked to keep 0 entries
+    env.api
+        .trim_table(tonic::Request::new(rpc::forge::TrimTableRequest {
+            target: rpc::forge::TrimTableTarget::MachineStateHistory as i32,
+            keep_entries: None,
+            max_age: None,
+            keep_last: 0,
+        }))
+        .await
+        .unwrap();
+    assert_eq!(state_history_len(&env.pool, &mh.id).await, 0);
+}
+
+#[crate::sqlx_test]
+async fn test_retention_manager_archives_trimmed_rows(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    let mh = create_managed_host(&env).await;
+    let archive_dir = tempfile::tempdir().unwrap();
+
+    let mut history_rows: i64 = 0;
+    for machine_id in std::iter::once(mh.id).chain(mh.dpu_ids.iter().copied()) {
+        history_rows += state_history_len(&env.pool, &machine_id).await;
+    }
+
+    let manager = RetentionManager::new(
+        env.pool.clone(),
+        RetentionConfig {
+            enabled: true,
+            // Forces multiple batches and archive files
+            batch_size: 2,
+            archive_dir: Some(archive_dir.path().to_path_buf()),
+            policies: BTreeMap::from([(
+                RetentionTarget::MachineStateHistory,
+                RetentionPolicyConfig {
+                    max_rows_per_object: Some(1),
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        },
+        env.test_meter.meter(),
+        env.api.work_lock_manager_handle.clone(),
+    );
+    manager.run_single_iteration().await.unwrap();
+
+    let machine_count = 1 + mh.dpu_ids.len() as i64;
+    assert_eq!(state_history_len(&env.pool, &mh.id).await, 1);
+
+    let table_dir = archive_dir.path().join("machine_state_history");
+    let mut archived = Vec::new();
+    for entry in std::fs::read_dir(&table_dir).unwrap() {
+        let path = entry.unwrap().path();
+        assert!(path.to_string_lossy().ends_with(".jsonl.gz"));
+        let mut content = String::new();
+        GzDecoder::new(std::fs::File::open(&path).unwrap())
+            .read_to_string(&mut content)
+            .unwrap();
+        for line in content.lines() {
+            archived.push(serde_json::from_str::<serde_json::Value>(line).unwrap());
+        }
+    }
+    assert_eq!(archived.len() as i64, history_rows - machine_count);
+    assert!(archived.iter().all(|row| row["machine_id"].is_string()
+        && row["state"].is_object()
+        && row["id"].is_number()));
+
+    assert!(
+        !env.test_meter
+            .formatted_metrics("carbide_retention_rows_archived")
+            .is_empty()
+    );
+}
+
+/// The history triggers record instance allocations and address assignments, and trimming
+/// keeps the newest record of every address of an interface
+#[crate::sqlx_test]
+async fn test_trim_machine_interface_address_history(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    let segment_id = env.create_vpc_and_tenant_segment().await;
+    let mh = create_managed_host(&env).await;
+
+    let (tinstance, _) = mh
+        .instance_builer(&env)
+        .single_interface_network_config(segment_id)
+        .build_and_return()
+        .await;
+    tinstance.delete().await;
+
+    // The instance is recorded with its versions, and without them once it got removed
+    let instance_history: Vec<(InstanceId, Option<String>)> = sqlx::query_as(
+        "SELECT instance_id, config_version FROM instance_config_history WHERE machine_id = $1 ORDER BY id",
+    )
+    .bind(mh.id.to_string())
+    .fetch_all(&env.pool)
+    .await
+    .unwrap();
+    assert!(instance_history.len() >= 2);
+    assert!(instance_history.iter().all(|(id, _)| *id == tinstance.id));
+    assert!(instance_history.first().unwrap().1.is_some());
+    assert!(instance_history.last().unwrap().1.is_none());
+
+    // Assign an additional address to the host interface and release it again
+    let address: IpAddr = "fd00::1234".parse().unwrap();
+    let mut txn = env.pool.begin().await.unwrap();
+    let interface_id = db::machine_interface::find_by_machine_ids(&mut txn, &[mh.id])
+        .await
+        .unwrap()[&mh.id][0]
+        .id;
+    db::machine_interface_address::insert(&mut txn, interface_id, address, AllocationType::Static)
+        .await
+        .unwrap();
+    txn.commit().await.unwrap();
+    let mut txn = env.pool.begin().await.unwrap();
+    assert!(
+        db::machine_interface_address::delete_by_address(&mut txn, address, AllocationType::Static)
+            .await
+            .unwrap()
+    );
+    txn.commit().await.unwrap();
+
+    let address_history_query = "SELECT assigned, machine_id FROM machine_interface_address_history
+        WHERE interface_id = $1 AND address = $2 ORDER BY id";
+    let address_history: Vec<(bool, Option<String>)> = sqlx::query_as(address_history_query)
+        .bind(interface_id)
+        .bind(address)
+        .fetch_all(&env.pool)
+        .await
+        .unwrap();
+    assert_eq!(
+        address_history,
+        vec![
+            (true, Some(mh.id.to_string())),
+            (false, Some(mh.id.to_string()))
+        ]
+    );
+
+    env.api
+        .trim_table(tonic::Request::new(rpc::forge::TrimTableRequest {
+            target: rpc::forge::TrimTableTarget::MachineInterfaceAddressHistory as i32,
+            keep_entries: Some(1),
+            max_age: None,
+            keep_last: 0,
+        }))
+        .await
+        .unwrap();
+
+    // Only the release of the additional address is left of its history, while the
+    // address the host got through DHCP is still known to be assigned
+    let address_history: Vec<(bool, Option<String>)> = sqlx::query_as(address_history_query)
+        .bind(interface_id)
+        .bind(address)
+        .fetch_all(&env.pool)
+        .await
+        .unwrap();
+    assert_eq!(address_history, vec![(false, Some(mh.id.to_string()))]);
+
+    let mut txn = env.pool.begin().await.unwrap();
+    let interfaces =
+        db::managed_host_history::find_interface_addresses_at(&mut txn, &mh.id, chrono::Utc::now())
+            .await
+            .unwrap();
+    let interface = interfaces
+        .iter()
+        .find(|interface| interface.interface_id == interface_id)
+        .unwrap();
+    assert!(!interface.addresses.is_empty());
+    assert!(!interface.addresses.contains(&address));
+}
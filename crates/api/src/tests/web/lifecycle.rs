@@ -0,0 +1,71 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use axum::body::Body;
+use http_body_util::BodyExt;
+use hyper::http::StatusCode;
+use tower::ServiceExt;
+
+use crate::tests::common::api_fixtures::{create_managed_host, create_test_env};
+use crate::tests::web::{make_test_app, web_request_builder};
+
+async fn get_body(app: axum::Router, uri: &str) -> (StatusCode, String) {
+    let response = app
+        .oneshot(web_request_builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body_bytes = response
+        .into_body()
+        .collect()
+        .await
+        .expect("Empty response body?")
+        .to_bytes();
+    (status, String::from_utf8_lossy(&body_bytes).into_owned())
+}
+
+#[crate::sqlx_test]
+async fn test_lifecycle_dashboard(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    let (host_machine_id, _dpu_machine_id) = create_managed_host(&env).await.into();
+    let app = make_test_app(&env);
+
+    let (status, body) = get_body(app.clone(), "/admin/lifecycle?hours=6").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("Fleet Lifecycle Dashboard"));
+    assert!(body.contains("<td>ready</td>"));
+    // Ingestion went through several states on the way to Ready within the window.
+    assert!(body.contains("Time in State"));
+    assert!(!body.contains(&host_machine_id.to_string()));
+
+    let (status, csv) = get_body(
+        app.clone(),
+        "/admin/lifecycle.csv?report=distribution&hours=6",
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some("state,substate,count"));
+    assert_eq!(lines.next(), Some("ready,,1"));
+
+    let (status, csv) = get_body(app.clone(), "/admin/lifecycle.csv?report=transitions").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(csv.starts_with("state,substate,transitions,failures,failure_percent"));
+    assert!(csv.lines().count() > 1);
+
+    let (status, _) = get_body(app, "/admin/lifecycle.csv?report=bogus").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
@@ -22,7 +22,10 @@ use hyper::http::request::Builder;
 use crate::tests::common;
 use crate::web::routes;
 mod health;
+mod lifecycle;
 mod managed_host;
+mod rack_elevation;
+mod serial_console;
 mod vpc;
 
 fn make_test_app(env: &TestEnv) -> Router {
@@ -0,0 +1,94 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use axum::body::Body;
+use carbide_uuid::rack::RackId;
+use carbide_uuid::switch::SwitchId;
+use db::switch as db_switch;
+use http_body_util::BodyExt;
+use hyper::http::StatusCode;
+use model::switch::{NewSwitch, SwitchConfig};
+use tower::ServiceExt;
+
+use crate::tests::common::api_fixtures::create_test_env;
+use crate::tests::common::api_fixtures::site_explorer::TestRackDbBuilder;
+use crate::tests::web::{make_test_app, web_request_builder};
+
+async fn get_body(app: axum::Router, uri: &str) -> (StatusCode, String) {
+    let response = app
+        .oneshot(web_request_builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body_bytes = response
+        .into_body()
+        .collect()
+        .await
+        .expect("Empty response body?")
+        .to_bytes();
+    (status, String::from_utf8_lossy(&body_bytes).into_owned())
+}
+
+#[crate::sqlx_test]
+async fn test_rack_elevation(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+
+    let rack_id: RackId = "Rack1".parse().unwrap();
+    let switch_id = SwitchId::from(uuid::Uuid::new_v4());
+    let mut txn = env.pool.begin().await.unwrap();
+    TestRackDbBuilder::new()
+        .with_rack_id(rack_id.clone())
+        .persist(&mut txn)
+        .await
+        .unwrap();
+    db_switch::create(
+        &mut txn,
+        &NewSwitch {
+            id: switch_id,
+            config: SwitchConfig {
+                name: "Switch1".to_string(),
+                enable_nmxc: false,
+                fabric_manager_config: None,
+            },
+            bmc_mac_address: None,
+            metadata: None,
+            rack_id: Some(rack_id.clone()),
+            slot_number: Some(12),
+            tray_index: Some(0),
+        },
+    )
+    .await
+    .unwrap();
+    txn.commit().await.unwrap();
+
+    let app = make_test_app(&env);
+
+    let (status, body) = get_body(app.clone(), "/admin/rack/Rack1/elevation").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("NVLink Switch"));
+    assert!(body.contains(&switch_id.to_string()));
+    assert!(body.contains("<td class=\"rack-slot\" rowspan=\"1\">12</td>"));
+
+    let (status, body) = get_body(app.clone(), "/admin/rack/Rack1/elevation.json").await;
+    assert_eq!(status, StatusCode::OK);
+    let elevation: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(elevation["slots"][0]["slot_number"], 12);
+    assert_eq!(elevation["slots"][0]["trays"][0]["name"], "Switch1");
+    assert_eq!(elevation["unplaced"], serde_json::json!([]));
+
+    let (status, _) = get_body(app, "/admin/rack/NoSuchRack/elevation").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,98 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use axum::body::Body;
+use http_body_util::BodyExt;
+use hyper::http::StatusCode;
+use tower::ServiceExt;
+
+use crate::cfg::file::WebUiSerialConsoleConfig;
+use crate::tests::common::api_fixtures::{
+    TestEnvOverrides, create_test_env, create_test_env_with_overrides, get_config,
+};
+use crate::tests::web::{make_test_app, web_request_builder};
+
+const MACHINE_ID: &str = "fm100ht09g4atrqgjb0b83b2to1qa1hfugks9mhutb0umcng1rkr54vliqg";
+
+#[crate::sqlx_test]
+async fn test_console_page_requires_configuration(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    let app = make_test_app(&env);
+
+    let response = app
+        .oneshot(
+            web_request_builder()
+                .uri(format!("/admin/machine/{MACHINE_ID}/console"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[crate::sqlx_test]
+async fn test_console_page_points_at_websocket_url(pool: sqlx::PgPool) {
+    let env = {
+        let mut config = get_config();
+        config.web_ui_serial_console = Some(WebUiSerialConsoleConfig {
+            websocket_url: "wss://carbide.example.com:8081/console/".to_string(),
+            xterm_js_url: "/static/xterm.js".to_string(),
+            xterm_css_url: "/static/xterm.css".to_string(),
+            xterm_js_integrity: format!("sha384-{}", "A".repeat(64)),
+            xterm_css_integrity: format!("sha384-{}", "B".repeat(64)),
+        });
+        create_test_env_with_overrides(pool, TestEnvOverrides::with_config(config)).await
+    };
+    let app = make_test_app(&env);
+
+    let response = app
+        .clone()
+        .oneshot(
+            web_request_builder()
+                .uri(format!("/admin/machine/{MACHINE_ID}/console"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body_bytes = response
+        .into_body()
+        .collect()
+        .await
+        .expect("Empty response body?")
+        .to_bytes();
+    // askama HTML-escapes '/', browsers decode it again when reading attributes.
+    let body = String::from_utf8_lossy(&body_bytes).replace("&#x2f;", "/");
+    assert!(body.contains(&format!(
+        "data-websocket-url=\"wss://carbide.example.com:8081/console/{MACHINE_ID}\""
+    )));
+    assert!(body.contains("/static/xterm.js"));
+    assert!(body.contains(&format!("integrity=\"sha384-{}\"", "A".repeat(64))));
+
+    let response = app
+        .oneshot(
+            web_request_builder()
+                .uri("/admin/machine/not-a-machine/console")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
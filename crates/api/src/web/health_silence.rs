@@ -0,0 +1,157 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::Arc;
+
+use askama::Template;
+use axum::Json;
+use axum::extract::{Path as AxumPath, State as AxumState};
+use axum::response::{Html, IntoResponse, Redirect, Response};
+use hyper::http::StatusCode;
+use rpc::forge as forgerpc;
+use rpc::forge::forge_server::Forge;
+
+use super::Base;
+use crate::api::Api;
+use crate::auth::AuthContext;
+
+#[derive(Template)]
+#[template(path = "health_silence_show.html")]
+struct HealthSilenceShow {
+    silences: Vec<HealthSilenceRowDisplay>,
+}
+
+struct HealthSilenceRowDisplay {
+    id: i64,
+    state: String,
+    active: bool,
+    matcher: String,
+    author: String,
+    reason: String,
+    starts_at: String,
+    expires_at: String,
+    ended_at: String,
+    ended_by: String,
+}
+
+impl From<forgerpc::HealthSilence> for HealthSilenceRowDisplay {
+    fn from(silence: forgerpc::HealthSilence) -> Self {
+        let state = silence.state();
+        Self {
+            id: silence.id,
+            state: state
+                .as_str_name()
+                .trim_start_matches("HEALTH_SILENCE_STATE_")
+                .to_string(),
+            active: matches!(
+                state,
+                forgerpc::HealthSilenceState::Active | forgerpc::HealthSilenceState::Pending
+            ),
+            matcher: silence
+                .matcher
+                .as_ref()
+                .map(|matcher| serde_json::to_string(matcher).unwrap_or_default())
+                .unwrap_or_default(),
+            author: silence.author,
+            reason: silence.reason,
+            starts_at: silence
+                .starts_at
+                .map(|ts| ts.to_string())
+                .unwrap_or_default(),
+            expires_at: silence
+                .expires_at
+                .map(|ts| ts.to_string())
+                .unwrap_or_default(),
+            ended_at: silence
+                .ended_at
+                .map(|ts| ts.to_string())
+                .unwrap_or_default(),
+            ended_by: silence.ended_by.unwrap_or_default(),
+        }
+    }
+}
+
+/// List health silences, including expired ones
+pub async fn show_html(AxumState(state): AxumState<Arc<Api>>) -> Response {
+    let silences = match fetch_silences(state).await {
+        Ok(silences) => silences,
+        Err(err) => {
+            tracing::error!(%err, "list_health_silences");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Error loading health silences",
+            )
+                .into_response();
+        }
+    };
+
+    let tmpl = HealthSilenceShow {
+        silences: silences.into_iter().rev().map(Into::into).collect(),
+    };
+    (StatusCode::OK, Html(tmpl.render().unwrap())).into_response()
+}
+
+pub async fn show_all_json(AxumState(state): AxumState<Arc<Api>>) -> Response {
+    match fetch_silences(state).await {
+        Ok(silences) => (StatusCode::OK, Json(silences)).into_response(),
+        Err(err) => {
+            tracing::error!(%err, "list_health_silences");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Error loading health silences",
+            )
+                .into_response()
+        }
+    }
+}
+
+/// End a health silence before it expires
+pub async fn expire(
+    AxumState(state): AxumState<Arc<Api>>,
+    AxumPath(silence_id): AxumPath<i64>,
+    auth_context: Option<axum::Extension<AuthContext>>,
+) -> Response {
+    let mut request = tonic::Request::new(forgerpc::ExpireHealthSilenceRequest {
+        id: silence_id,
+        ended_by: None,
+    });
+    if let Some(axum::Extension(auth_context)) = auth_context {
+        request.extensions_mut().insert(auth_context);
+    }
+    if let Err(e) = state.expire_health_silence(request).await {
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Unable to end health silence: {e}"),
+        )
+            .into_response();
+    }
+
+    Redirect::to("/admin/health-silence").into_response()
+}
+
+async fn fetch_silences(api: Arc<Api>) -> Result<Vec<forgerpc::HealthSilence>, tonic::Status> {
+    let request = tonic::Request::new(forgerpc::ListHealthSilencesRequest {
+        include_ended: true,
+    });
+    Ok(api
+        .list_health_silences(request)
+        .await?
+        .into_inner()
+        .silences)
+}
+
+impl Base for HealthSilenceShow {}
@@ -0,0 +1,397 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//! Fleet lifecycle dashboard: where hosts are in the ManagedHostState machine, how long they
+//! spend in each state, which hosts have been stuck the longest, and how often transitions
+//! end in `Failed`, computed from the machine state history.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use askama::Template;
+use axum::extract::{Query, State as AxumState};
+use axum::response::{Html, IntoResponse, Response};
+use chrono::{DateTime, Utc};
+use db::DatabaseError;
+use db::fleet_lifecycle::HostLifecycleAttributes;
+use db::state_history::StateHistoryTableId;
+use http::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+use hyper::http::StatusCode;
+use model::machine::ManagedHostState;
+use model::state_history::StateHistoryRecord;
+use serde::{Deserialize, Serialize};
+
+use super::{Base, filters};
+use crate::api::Api;
+use crate::state_controller::io::StateControllerIO;
+use crate::state_controller::machine::io::MachineStateControllerIO;
+
+const DEFAULT_WINDOW_HOURS: u32 = 24;
+const MAX_WINDOW_HOURS: u32 = 24 * 90;
+const WINDOW_CHOICES_HOURS: [u32; 5] = [1, 6, 24, 24 * 7, 24 * 30];
+const LONGEST_STUCK_LIMIT: usize = 25;
+const UNKNOWN: &str = "Unknown";
+
+/// States a host is expected to sit in indefinitely, which are never "stuck".
+const STEADY_STATES: [(&str, &str); 2] = [("ready", ""), ("assigned", "ready")];
+
+#[derive(Debug, Deserialize)]
+pub struct LifecycleQuery {
+    hours: Option<u32>,
+    report: Option<String>,
+}
+
+impl LifecycleQuery {
+    fn window_hours(&self) -> u32 {
+        self.hours
+            .unwrap_or(DEFAULT_WINDOW_HOURS)
+            .clamp(1, MAX_WINDOW_HOURS)
+    }
+}
+
+#[derive(Template)]
+#[template(path = "lifecycle_dashboard.html")]
+struct LifecycleDashboard {
+    hours: u32,
+    window_choices: Vec<u32>,
+    report: FleetLifecycleReport,
+}
+
+impl Base for LifecycleDashboard {}
+
+#[derive(Debug, Default)]
+struct FleetLifecycleReport {
+    state_distribution: Vec<StateCount>,
+    time_in_state: Vec<TimeInState>,
+    longest_stuck: Vec<StuckHost>,
+    transitions: Vec<TransitionFailures>,
+}
+
+#[derive(Debug, Serialize)]
+struct StateCount {
+    state: &'static str,
+    substate: &'static str,
+    count: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct TimeInState {
+    state: &'static str,
+    substate: &'static str,
+    sku: String,
+    vendor: String,
+    count: usize,
+    p50_seconds: i64,
+    p90_seconds: i64,
+    p99_seconds: i64,
+    max_seconds: i64,
+}
+
+#[derive(Debug, Serialize)]
+struct StuckHost {
+    machine_id: String,
+    state: &'static str,
+    substate: &'static str,
+    sku: String,
+    vendor: String,
+    since: DateTime<Utc>,
+    seconds: i64,
+}
+
+#[derive(Debug, Serialize)]
+struct TransitionFailures {
+    state: &'static str,
+    substate: &'static str,
+    transitions: usize,
+    failures: usize,
+    failure_percent: f64,
+}
+
+impl TimeInState {
+    fn p50(&self) -> String {
+        format_seconds(self.p50_seconds)
+    }
+
+    fn p90(&self) -> String {
+        format_seconds(self.p90_seconds)
+    }
+
+    fn p99(&self) -> String {
+        format_seconds(self.p99_seconds)
+    }
+
+    fn max(&self) -> String {
+        format_seconds(self.max_seconds)
+    }
+}
+
+impl StuckHost {
+    fn duration(&self) -> String {
+        format_seconds(self.seconds)
+    }
+}
+
+impl TransitionFailures {
+    fn failure_rate(&self) -> String {
+        format!("{:.1}%", self.failure_percent)
+    }
+}
+
+/// Show the fleet lifecycle dashboard
+pub async fn show_html(
+    AxumState(state): AxumState<Arc<Api>>,
+    Query(query): Query<LifecycleQuery>,
+) -> Response {
+    let hours = query.window_hours();
+    let report = match fetch_report(&state, hours).await {
+        Ok(report) => report,
+        Err(err) => {
+            tracing::error!(%err, "fleet_lifecycle_report");
+            return (StatusCode::INTERNAL_SERVER_ERROR, Html(err.to_string())).into_response();
+        }
+    };
+
+    let display = LifecycleDashboard {
+        hours,
+        window_choices: WINDOW_CHOICES_HOURS.to_vec(),
+        report,
+    };
+    (StatusCode::OK, Html(display.render().unwrap())).into_response()
+}
+
+/// Export one of the dashboard's tables, selected with `report=`, as CSV
+pub async fn export_csv(
+    AxumState(state): AxumState<Arc<Api>>,
+    Query(query): Query<LifecycleQuery>,
+) -> Response {
+    let hours = query.window_hours();
+    let report_name = query.report.as_deref().unwrap_or("time_in_state");
+    if !matches!(
+        report_name,
+        "distribution" | "time_in_state" | "stuck" | "transitions"
+    ) {
+        return (
+            StatusCode::BAD_REQUEST,
+            "report must be one of: distribution, time_in_state, stuck, transitions",
+        )
+            .into_response();
+    }
+
+    let report = match fetch_report(&state, hours).await {
+        Ok(report) => report,
+        Err(err) => {
+            tracing::error!(%err, "fleet_lifecycle_report");
+            return (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response();
+        }
+    };
+
+    let csv = match report_name {
+        "distribution" => to_csv(&report.state_distribution),
+        "stuck" => to_csv(&report.longest_stuck),
+        "transitions" => to_csv(&report.transitions),
+        _ => to_csv(&report.time_in_state),
+    };
+    match csv {
+        Ok(csv) => (
+            StatusCode::OK,
+            [
+                (CONTENT_TYPE, "text/csv".to_string()),
+                (
+                    CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"lifecycle-{report_name}-{hours}h.csv\""),
+                ),
+            ],
+            csv,
+        )
+            .into_response(),
+        Err(err) => {
+            tracing::error!(%err, "fleet_lifecycle_csv");
+            (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response()
+        }
+    }
+}
+
+fn to_csv<T: Serialize>(rows: &[T]) -> Result<String, csv::Error> {
+    let mut writer = csv::Writer::from_writer(vec![]);
+    for row in rows {
+        writer.serialize(row)?;
+    }
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| csv::Error::from(e.into_error()))?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+async fn fetch_report(api: &Api, hours: u32) -> Result<FleetLifecycleReport, DatabaseError> {
+    let now = Utc::now();
+    let window_start = now - chrono::Duration::hours(hours.into());
+    let hosts = db::fleet_lifecycle::find_host_attributes(&api.database_connection).await?;
+    let histories = db::state_history::find_in_window(
+        &api.database_connection,
+        StateHistoryTableId::Machine,
+        window_start,
+    )
+    .await?;
+    Ok(compute_report(hosts, histories, window_start, now))
+}
+
+/// The (state, substate) names used in state controller metrics, or "unknown" for states written
+/// by an older version that no longer deserialize.
+fn state_names(state_json: &str) -> (&'static str, &'static str) {
+    match serde_json::from_str::<ManagedHostState>(state_json) {
+        Ok(state) => MachineStateControllerIO::metric_state_names(&state),
+        Err(_) => ("unknown", ""),
+    }
+}
+
+fn compute_report(
+    hosts: Vec<HostLifecycleAttributes>,
+    histories: HashMap<String, Vec<StateHistoryRecord>>,
+    window_start: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> FleetLifecycleReport {
+    let mut distribution: BTreeMap<(&'static str, &'static str), usize> = BTreeMap::new();
+    let mut durations: BTreeMap<(&'static str, &'static str, String, String), Vec<i64>> =
+        BTreeMap::new();
+    let mut transitions: BTreeMap<(&'static str, &'static str), (usize, usize)> = BTreeMap::new();
+    let mut longest_stuck = Vec::new();
+
+    for host in hosts {
+        let machine_id = host.machine_id.to_string();
+        let sku = host.hw_sku.unwrap_or_else(|| UNKNOWN.to_string());
+        let vendor = host
+            .sys_vendor
+            .filter(|vendor| !vendor.is_empty())
+            .unwrap_or_else(|| UNKNOWN.to_string());
+
+        let current = state_names(&host.controller_state);
+        *distribution.entry(current).or_default() += 1;
+
+        let Some(records) = histories.get(&machine_id) else {
+            continue;
+        };
+        let records = records
+            .iter()
+            .filter_map(|record| Some((state_names(&record.state), record.time?)))
+            .collect::<Vec<_>>();
+
+        // Every state that was left during the window is a completed visit, and a transition.
+        for pair in records.windows(2) {
+            let ((from, entered), (to, left)) = (pair[0], pair[1]);
+            if left < window_start {
+                continue;
+            }
+            durations
+                .entry((from.0, from.1, sku.clone(), vendor.clone()))
+                .or_default()
+                .push((left - entered).num_seconds());
+            let counts = transitions.entry(from).or_default();
+            counts.0 += 1;
+            if to.0 == "failed" {
+                counts.1 += 1;
+            }
+        }
+
+        if let Some(&(state, since)) = records.last()
+            && !STEADY_STATES.contains(&state)
+        {
+            longest_stuck.push(StuckHost {
+                machine_id,
+                state: state.0,
+                substate: state.1,
+                sku,
+                vendor,
+                since,
+                seconds: (now - since).num_seconds(),
+            });
+        }
+    }
+
+    longest_stuck.sort_by(|a, b| b.seconds.cmp(&a.seconds));
+    longest_stuck.truncate(LONGEST_STUCK_LIMIT);
+
+    let mut transitions = transitions
+        .into_iter()
+        .map(
+            |((state, substate), (transitions, failures))| TransitionFailures {
+                state,
+                substate,
+                transitions,
+                failures,
+                failure_percent: failures as f64 * 100.0 / transitions as f64,
+            },
+        )
+        .collect::<Vec<_>>();
+    transitions.sort_by(|a, b| {
+        b.failure_percent
+            .total_cmp(&a.failure_percent)
+            .then(b.transitions.cmp(&a.transitions))
+    });
+
+    FleetLifecycleReport {
+        state_distribution: distribution
+            .into_iter()
+            .map(|((state, substate), count)| StateCount {
+                state,
+                substate,
+                count,
+            })
+            .collect(),
+        time_in_state: durations
+            .into_iter()
+            .map(|((state, substate, sku, vendor), mut seconds)| {
+                seconds.sort_unstable();
+                TimeInState {
+                    state,
+                    substate,
+                    sku,
+                    vendor,
+                    count: seconds.len(),
+                    p50_seconds: percentile(&seconds, 50),
+                    p90_seconds: percentile(&seconds, 90),
+                    p99_seconds: percentile(&seconds, 99),
+                    max_seconds: seconds.last().copied().unwrap_or_default(),
+                }
+            })
+            .collect(),
+        longest_stuck,
+        transitions,
+    }
+}
+
+/// Nearest-rank percentile of an already sorted, non-empty slice.
+fn percentile(sorted: &[i64], percent: usize) -> i64 {
+    let rank = (sorted.len() * percent).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+fn format_seconds(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    let (days, hours, minutes) = (
+        seconds / 86400,
+        (seconds % 86400) / 3600,
+        (seconds % 3600) / 60,
+    );
+    if days > 0 {
+        format!("{days}d {hours}h")
+    } else if hours > 0 {
+        format!("{hours}h {minutes}m")
+    } else if minutes > 0 {
+        format!("{minutes}m {}s", seconds % 60)
+    } else {
+        format!("{seconds}s")
+    }
+}
@@ -465,6 +465,7 @@ struct MachineDetail<'a> {
     has_instance_type: bool,
     nvlink_gpus: Vec<MachineNvLinkGpuDisplay>,
     action_status: Option<ActionStatus<'a>>,
+    serial_console_enabled: bool,
 }
 
 struct MachineCapability {
@@ -670,6 +671,7 @@ impl From<forgerpc::Machine> for MachineDetail<'_> {
             machine_type: get_machine_type(&machine_id),
             is_host,
             network_config: String::new(), // filled in later
+            serial_console_enabled: false, // filled in later
             bmc_info: m.bmc_info,
             history,
             bios_version,
@@ -843,6 +845,7 @@ pub async fn detail(
 
     display.validation_runs = validation_runs;
     display.action_status = ActionStatus::from_query(&params);
+    display.serial_console_enabled = state.runtime_config.web_ui_serial_console.is_some();
 
     if !display.is_host {
         let request = tonic::Request::new(forgerpc::ManagedHostNetworkConfigRequest {
@@ -222,6 +222,7 @@ mod explored_endpoint;
 mod filters;
 mod health;
 mod health_history;
+mod health_silence;
 mod ib_fabric;
 mod ib_partition;
 mod instance;
@@ -229,6 +230,7 @@ mod instance_type;
 mod interface;
 mod ipam;
 mod ipxe_template;
+mod lifecycle;
 mod machine;
 mod machine_validation;
 pub mod managed_host;
@@ -241,10 +243,12 @@ mod nvlink;
 mod operating_system;
 mod power_shelf;
 mod rack;
+mod rack_elevation;
 mod redfish_actions;
 mod redfish_browser;
 mod resource_pool;
 mod search;
+mod serial_console;
 mod sku;
 mod state_history;
 mod switch;
@@ -475,6 +479,12 @@ pub fn routes(api: Arc<Api>) -> eyre::Result<NormalizePath<Router>> {
                 "/explored-endpoint/{endpoint_ip}/enable-lockdown",
                 post(explored_endpoint::enable_lockdown),
             )
+            .route("/health-silence", get(health_silence::show_html))
+            .route("/health-silence.json", get(health_silence::show_all_json))
+            .route(
+                "/health-silence/{silence_id}/expire",
+                post(health_silence::expire),
+            )
             .route("/host", get(machine::show_hosts_html))
             .route("/host.json", get(machine::show_hosts_json))
             .route("/ib-partition", get(ib_partition::show_html))
@@ -524,6 +534,8 @@ pub fn routes(api: Arc<Api>) -> eyre::Result<NormalizePath<Router>> {
                 "/ipam/overlay/segment/{segment_id}",
                 get(ipam::overlay_segment_html),
             )
+            .route("/lifecycle", get(lifecycle::show_html))
+            .route("/lifecycle.csv", get(lifecycle::export_csv))
             .route("/machine", get(machine::show_all_html))
             .route("/machine.json", get(machine::show_all_json))
             .route("/machine/{machine_id}", get(machine::detail))
@@ -540,6 +552,10 @@ pub fn routes(api: Arc<Api>) -> eyre::Result<NormalizePath<Router>> {
                 post(machine::set_dpu_first_boot_order),
             )
             .route("/machine/{machine_id}/health", get(health::machine_health))
+            .route(
+                "/machine/{machine_id}/console",
+                get(serial_console::show_machine_console),
+            )
             .route(
                 "/machine/{machine_id}/health-history",
                 get(health_history::show_health_history),
@@ -583,6 +599,14 @@ pub fn routes(api: Arc<Api>) -> eyre::Result<NormalizePath<Router>> {
             .route("/rack.json", get(rack::show_json))
             .route("/rack/{rack_id}", get(rack::detail))
             .route("/rack/{rack_id}/health", get(health::rack_health))
+            .route(
+                "/rack/{rack_id}/elevation",
+                get(rack_elevation::show_elevation),
+            )
+            .route(
+                "/rack/{rack_id}/elevation.json",
+                get(rack_elevation::show_elevation_json),
+            )
             .route(
                 "/rack/{rack_id}/health/add-report",
                 post(health::add_rack_health_report),
@@ -345,6 +345,7 @@ pub async fn show_detail(
                     network_security_group_ids: Some(forgerpc::NetworkSecurityGroupIdList {
                         ids: vec![network_security_group_id],
                     }),
+                    prefix_list_ids: vec![],
                 },
             ))
             .await
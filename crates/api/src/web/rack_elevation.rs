@@ -0,0 +1,480 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//! Rack elevation: the trays of a rack laid out by slot, color-coded by lifecycle state and
+//! aggregate health, with NVLink partition membership and power shelf status overlaid.
+//!
+//! Power shelves are shown per rack rather than per tray: they feed the shared busbar, and the
+//! inventory has no mapping from shelves or feeds to trays.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use askama::Template;
+use axum::Json;
+use axum::extract::{Path as AxumPath, State as AxumState};
+use axum::response::{Html, IntoResponse, Response};
+use carbide_uuid::nvlink::NvLinkLogicalPartitionId;
+use carbide_uuid::rack::RackId;
+use hyper::http::StatusCode;
+use rpc::forge as forgerpc;
+use rpc::forge::forge_server::Forge;
+
+use super::{Base, filters};
+use crate::api::Api;
+
+const COMPUTE_TRAY: &str = "Compute Tray";
+const NVLINK_SWITCH: &str = "NVLink Switch";
+
+#[derive(Template, serde::Serialize)]
+#[template(path = "rack_elevation.html")]
+struct RackElevation {
+    id: String,
+    state: String,
+    /// Occupied slots, top of the rack first
+    slots: Vec<ElevationSlot>,
+    /// Trays and switches without a known slot
+    unplaced: Vec<ElevationTray>,
+    power_shelves: Vec<ElevationPowerShelf>,
+    power_shelves_on: usize,
+    partitions: Vec<ElevationPartition>,
+}
+
+#[derive(serde::Serialize)]
+struct ElevationSlot {
+    slot_number: i32,
+    trays: Vec<ElevationTray>,
+}
+
+#[derive(serde::Serialize)]
+struct ElevationTray {
+    kind: &'static str,
+    id: String,
+    name: String,
+    tray_index: Option<i32>,
+    state: String,
+    state_class: &'static str,
+    health_alerts: usize,
+    health_class: &'static str,
+    nvlink_domain: String,
+    partitions: Vec<ElevationPartition>,
+}
+
+#[derive(Clone, serde::Serialize)]
+struct ElevationPartition {
+    id: String,
+    name: String,
+    color: String,
+    gpus: usize,
+}
+
+#[derive(serde::Serialize)]
+struct ElevationPowerShelf {
+    id: String,
+    name: String,
+    power_state: String,
+    state: String,
+    state_class: &'static str,
+    health_alerts: usize,
+    health_class: &'static str,
+}
+
+impl ElevationTray {
+    fn is_switch(&self) -> bool {
+        self.kind == NVLINK_SWITCH
+    }
+}
+
+impl Base for RackElevation {}
+
+/// Show the elevation of a rack
+pub async fn show_elevation(
+    AxumState(api): AxumState<Arc<Api>>,
+    AxumPath(rack_id): AxumPath<String>,
+) -> Response {
+    match load_elevation(&api, rack_id).await {
+        Ok(elevation) => (StatusCode::OK, Html(elevation.render().unwrap())).into_response(),
+        Err(response) => response,
+    }
+}
+
+/// Show the elevation of a rack as JSON
+pub async fn show_elevation_json(
+    AxumState(api): AxumState<Arc<Api>>,
+    AxumPath(rack_id): AxumPath<String>,
+) -> Response {
+    match load_elevation(&api, rack_id).await {
+        Ok(elevation) => (StatusCode::OK, Json(elevation)).into_response(),
+        Err(response) => response,
+    }
+}
+
+async fn load_elevation(api: &Api, rack_id: String) -> Result<RackElevation, Response> {
+    let rack_id = match rack_id.parse::<RackId>() {
+        Ok(rack_id) => rack_id,
+        Err(e) => return Err((StatusCode::BAD_REQUEST, e.to_string()).into_response()),
+    };
+
+    let rack = match super::rack::fetch_rack(api, &rack_id).await? {
+        Some(rack) => rack,
+        None => {
+            return Err(
+                (StatusCode::NOT_FOUND, format!("Rack {rack_id} not found")).into_response()
+            );
+        }
+    };
+
+    build_elevation(api, rack).await.map_err(|err| {
+        tracing::error!(%err, %rack_id, "build_rack_elevation");
+        (StatusCode::INTERNAL_SERVER_ERROR, Html(err.to_string())).into_response()
+    })
+}
+
+async fn build_elevation(api: &Api, rack: forgerpc::Rack) -> Result<RackElevation, tonic::Status> {
+    let rack_id = rack.id.clone().unwrap_or_default();
+    let machines = fetch_compute_trays(api, &rack_id).await?;
+    let switches = fetch_switches(api, &rack_id).await?;
+    let power_shelves = fetch_power_shelves(api, &rack_id).await?;
+
+    // Assign each logical partition found on the trays a stable color, in order of first use.
+    let mut partition_order: Vec<NvLinkLogicalPartitionId> = Vec::new();
+    for machine in &machines {
+        for gpu in machine
+            .nvlink_status_observation
+            .iter()
+            .flat_map(|status| status.gpu_status.iter())
+        {
+            if let Some(id) = gpu.logical_partition_id
+                && !partition_order.contains(&id)
+            {
+                partition_order.push(id);
+            }
+        }
+    }
+    let partition_names = fetch_partition_names(api, &partition_order).await;
+    let partition_legend: HashMap<NvLinkLogicalPartitionId, ElevationPartition> = partition_order
+        .iter()
+        .enumerate()
+        .map(|(index, id)| {
+            let partition = ElevationPartition {
+                id: id.to_string(),
+                name: partition_names
+                    .get(id)
+                    .cloned()
+                    .unwrap_or_else(|| id.to_string()),
+                color: partition_color(index),
+                gpus: 0,
+            };
+            (*id, partition)
+        })
+        .collect();
+
+    let mut placed: BTreeMap<i32, Vec<ElevationTray>> = BTreeMap::new();
+    let mut unplaced = Vec::new();
+    let mut partition_gpus: HashMap<NvLinkLogicalPartitionId, usize> = HashMap::new();
+
+    for machine in machines {
+        let mut tray_partitions: BTreeMap<usize, ElevationPartition> = BTreeMap::new();
+        let mut nvlink_domain = String::new();
+        for gpu in machine
+            .nvlink_status_observation
+            .iter()
+            .flat_map(|status| status.gpu_status.iter())
+        {
+            if nvlink_domain.is_empty()
+                && let Some(domain_id) = gpu.domain_id.as_ref()
+            {
+                nvlink_domain = domain_id.to_string();
+            }
+            let Some(id) = gpu.logical_partition_id else {
+                continue;
+            };
+            *partition_gpus.entry(id).or_default() += 1;
+            let index = partition_order.iter().position(|p| *p == id).unwrap();
+            tray_partitions
+                .entry(index)
+                .or_insert_with(|| partition_legend[&id].clone())
+                .gpus += 1;
+        }
+
+        let above_sla = machine
+            .state_sla
+            .as_ref()
+            .map(|sla| sla.time_in_state_above_sla)
+            .unwrap_or_default();
+        let state = filters::state_with_substate_label(&machine.state).unwrap_or_default();
+        let (health_alerts, health_class) = health_summary(machine.health.as_ref());
+        let tray = ElevationTray {
+            kind: COMPUTE_TRAY,
+            id: machine.id.map(|id| id.to_string()).unwrap_or_default(),
+            name: machine
+                .metadata
+                .map(|metadata| metadata.name)
+                .unwrap_or_default(),
+            tray_index: machine
+                .placement_in_rack
+                .as_ref()
+                .and_then(|p| p.tray_index),
+            state_class: state_class(&state, above_sla),
+            state,
+            health_alerts,
+            health_class,
+            nvlink_domain,
+            partitions: tray_partitions.into_values().collect(),
+        };
+        match machine.placement_in_rack.and_then(|p| p.slot_number) {
+            Some(slot) => placed.entry(slot).or_default().push(tray),
+            None => unplaced.push(tray),
+        }
+    }
+
+    for switch in switches {
+        let status = switch.status.unwrap_or_default();
+        let lifecycle = status.lifecycle.unwrap_or_default();
+        let above_sla = lifecycle
+            .sla
+            .as_ref()
+            .map(|sla| sla.time_in_state_above_sla)
+            .unwrap_or_default();
+        let state = filters::state_with_substate_label(&lifecycle.state).unwrap_or_default();
+        let (health_alerts, health_class) = health_summary(status.health.as_ref());
+        let tray = ElevationTray {
+            kind: NVLINK_SWITCH,
+            id: switch.id.map(|id| id.to_string()).unwrap_or_default(),
+            name: switch.config.map(|config| config.name).unwrap_or_default(),
+            tray_index: switch.placement_in_rack.as_ref().and_then(|p| p.tray_index),
+            state_class: state_class(&state, above_sla),
+            state,
+            health_alerts,
+            health_class,
+            nvlink_domain: String::new(),
+            partitions: vec![],
+        };
+        match switch.placement_in_rack.and_then(|p| p.slot_number) {
+            Some(slot) => placed.entry(slot).or_default().push(tray),
+            None => unplaced.push(tray),
+        }
+    }
+
+    let power_shelves: Vec<ElevationPowerShelf> = power_shelves
+        .into_iter()
+        .map(|shelf| {
+            let status = shelf.status.unwrap_or_default();
+            let lifecycle = status.lifecycle.unwrap_or_default();
+            let above_sla = lifecycle
+                .sla
+                .as_ref()
+                .map(|sla| sla.time_in_state_above_sla)
+                .unwrap_or_default();
+            let state = filters::state_with_substate_label(&lifecycle.state).unwrap_or_default();
+            let (health_alerts, health_class) = health_summary(status.health.as_ref());
+            ElevationPowerShelf {
+                id: shelf.id.map(|id| id.to_string()).unwrap_or_default(),
+                name: shelf.config.map(|config| config.name).unwrap_or_default(),
+                power_state: status.power_state.unwrap_or_else(|| "unknown".to_string()),
+                state_class: state_class(&state, above_sla),
+                state,
+                health_alerts,
+                health_class,
+            }
+        })
+        .collect();
+    let power_shelves_on = power_shelves
+        .iter()
+        .filter(|shelf| shelf.power_state.eq_ignore_ascii_case("on"))
+        .count();
+
+    let slots = placed
+        .into_iter()
+        .rev()
+        .map(|(slot_number, mut trays)| {
+            trays.sort_by_key(|tray| tray.tray_index);
+            ElevationSlot { slot_number, trays }
+        })
+        .collect();
+
+    let mut partitions = partition_order
+        .iter()
+        .map(|id| ElevationPartition {
+            gpus: partition_gpus.get(id).copied().unwrap_or_default(),
+            ..partition_legend[id].clone()
+        })
+        .collect::<Vec<_>>();
+    partitions.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(RackElevation {
+        id: rack_id.to_string(),
+        state: filters::state_with_substate_label(
+            rack.status
+                .and_then(|status| status.lifecycle)
+                .map(|lifecycle| lifecycle.state)
+                .unwrap_or_else(|| rack.rack_state.clone()),
+        )
+        .unwrap_or_default(),
+        slots,
+        unplaced,
+        power_shelves,
+        power_shelves_on,
+        partitions,
+    })
+}
+
+/// The same buckets `state_display.html` uses for its bubbles
+fn state_class(state: &str, time_in_state_above_sla: bool) -> &'static str {
+    if state.to_lowercase().contains("failed") || state.to_lowercase().contains("error") {
+        "error"
+    } else if time_in_state_above_sla {
+        "warning"
+    } else if state == "Ready" || state == "Assigned/Ready" {
+        "success"
+    } else {
+        ""
+    }
+}
+
+/// Number of alerts in an aggregate health report, and how severe they are. Alerts which
+/// prevent allocations are errors, others are warnings.
+fn health_summary(health: Option<&rpc::health::HealthReport>) -> (usize, &'static str) {
+    let alerts = health.map(|health| health.alerts.as_slice()).unwrap_or(&[]);
+    let class = if alerts.is_empty() {
+        "success"
+    } else if alerts.iter().any(|alert| {
+        alert
+            .classifications
+            .iter()
+            .any(|c| c == "PreventAllocations")
+    }) {
+        "error"
+    } else {
+        "warning"
+    };
+    (alerts.len(), class)
+}
+
+/// Spread partition colors around the hue circle so neighbouring partitions are distinguishable
+fn partition_color(index: usize) -> String {
+    format!("hsl({}, 65%, 45%)", (index * 137) % 360)
+}
+
+async fn fetch_compute_trays(
+    api: &Api,
+    rack_id: &RackId,
+) -> Result<Vec<forgerpc::Machine>, tonic::Status> {
+    let machine_ids = api
+        .find_machine_ids(tonic::Request::new(forgerpc::MachineSearchConfig {
+            include_predicted_host: true,
+            rack_id: Some(rack_id.clone()),
+            ..Default::default()
+        }))
+        .await?
+        .into_inner()
+        .machine_ids;
+
+    let mut machines = Vec::new();
+    for next_ids in machine_ids.chunks(100) {
+        let next_machines = api
+            .find_machines_by_ids(tonic::Request::new(forgerpc::MachinesByIdsRequest {
+                machine_ids: next_ids.to_vec(),
+                include_history: false,
+            }))
+            .await?
+            .into_inner();
+        machines.extend(next_machines.machines);
+    }
+    Ok(machines)
+}
+
+async fn fetch_switches(
+    api: &Api,
+    rack_id: &RackId,
+) -> Result<Vec<forgerpc::Switch>, tonic::Status> {
+    let switch_ids = api
+        .find_switch_ids(tonic::Request::new(forgerpc::SwitchSearchFilter {
+            rack_id: Some(rack_id.clone()),
+            ..Default::default()
+        }))
+        .await?
+        .into_inner()
+        .ids;
+    if switch_ids.is_empty() {
+        return Ok(vec![]);
+    }
+
+    Ok(api
+        .find_switches_by_ids(tonic::Request::new(forgerpc::SwitchesByIdsRequest {
+            switch_ids,
+        }))
+        .await?
+        .into_inner()
+        .switches)
+}
+
+async fn fetch_power_shelves(
+    api: &Api,
+    rack_id: &RackId,
+) -> Result<Vec<forgerpc::PowerShelf>, tonic::Status> {
+    let power_shelf_ids = api
+        .find_power_shelf_ids(tonic::Request::new(forgerpc::PowerShelfSearchFilter {
+            rack_id: Some(rack_id.clone()),
+            ..Default::default()
+        }))
+        .await?
+        .into_inner()
+        .ids;
+    if power_shelf_ids.is_empty() {
+        return Ok(vec![]);
+    }
+
+    Ok(api
+        .find_power_shelves_by_ids(tonic::Request::new(forgerpc::PowerShelvesByIdsRequest {
+            power_shelf_ids,
+        }))
+        .await?
+        .into_inner()
+        .power_shelves)
+}
+
+/// Names of the given logical partitions. Partitions which can't be loaded are left out, and
+/// are shown by ID instead.
+async fn fetch_partition_names(
+    api: &Api,
+    partition_ids: &[NvLinkLogicalPartitionId],
+) -> HashMap<NvLinkLogicalPartitionId, String> {
+    if partition_ids.is_empty() {
+        return HashMap::new();
+    }
+
+    let request = tonic::Request::new(forgerpc::NvLinkLogicalPartitionsByIdsRequest {
+        partition_ids: partition_ids.to_vec(),
+        include_history: false,
+    });
+    match api.find_nv_link_logical_partitions_by_ids(request).await {
+        Ok(response) => response
+            .into_inner()
+            .partitions
+            .into_iter()
+            .filter_map(|partition| {
+                let name = partition.config?.metadata?.name;
+                Some((partition.id?, name))
+            })
+            .filter(|(_, name)| !name.is_empty())
+            .collect(),
+        Err(err) => {
+            tracing::error!(%err, "find_nv_link_logical_partitions_by_ids");
+            HashMap::new()
+        }
+    }
+}
@@ -0,0 +1,71 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::str::FromStr;
+use std::sync::Arc;
+
+use askama::Template;
+use axum::extract::{Path as AxumPath, State as AxumState};
+use axum::response::{Html, IntoResponse, Response};
+use carbide_uuid::machine::MachineId;
+use hyper::http::StatusCode;
+
+use super::Base;
+use crate::api::Api;
+
+#[derive(Template)]
+#[template(path = "machine_serial_console.html")]
+struct MachineSerialConsole {
+    id: String,
+    websocket_url: String,
+    xterm_js_url: String,
+    xterm_css_url: String,
+    xterm_js_integrity: String,
+    xterm_css_integrity: String,
+}
+
+impl Base for MachineSerialConsole {}
+
+/// Show an interactive serial console for a Machine, served by ssh-console over WebSocket
+pub async fn show_machine_console(
+    AxumState(state): AxumState<Arc<Api>>,
+    AxumPath(machine_id): AxumPath<String>,
+) -> Response {
+    let Ok(machine_id) = MachineId::from_str(&machine_id) else {
+        return (StatusCode::BAD_REQUEST, "invalid machine id").into_response();
+    };
+    let Some(serial_console) = state.runtime_config.web_ui_serial_console.as_ref() else {
+        return (
+            StatusCode::NOT_FOUND,
+            "web_ui_serial_console is not configured",
+        )
+            .into_response();
+    };
+
+    let display = MachineSerialConsole {
+        id: machine_id.to_string(),
+        websocket_url: format!(
+            "{}/{machine_id}",
+            serial_console.websocket_url.trim_end_matches('/')
+        ),
+        xterm_js_url: serial_console.xterm_js_url.clone(),
+        xterm_css_url: serial_console.xterm_css_url.clone(),
+        xterm_js_integrity: serial_console.xterm_js_integrity.clone(),
+        xterm_css_integrity: serial_console.xterm_css_integrity.clone(),
+    };
+
+    (StatusCode::OK, Html(display.render().unwrap())).into_response()
+}
@@ -24,6 +24,8 @@ use serde::{Deserialize, Serialize};
 use crate::redfish::update_service::UpdateServiceConfig;
 use crate::{hw, redfish};
 static NEXT_MAC_ADDRESS: AtomicU32 = AtomicU32::new(1);
+/// Locally administered prefix of the MAC addresses of mock machines
+const MOCK_MAC_PREFIX: [u8; 2] = [0x02, 0x01];
 use crate::{
     DUMMY_FACTORY_DPU_PASSWORD, DUMMY_FACTORY_PASSWORD, DUMMY_FACTORY_USERNAME, HostHardwareType,
 };
@@ -616,10 +618,15 @@ impl MachineInfo {
     }
 }
 
+/// Whether `mac_address` was handed out to a mock machine, rather than belonging to real hardware
+pub fn is_mock_mac_address(mac_address: MacAddress) -> bool {
+    mac_address.bytes()[..2] == MOCK_MAC_PREFIX
+}
+
 fn next_mac() -> MacAddress {
     let next_mac_num = NEXT_MAC_ADDRESS.fetch_add(1, Ordering::Acquire);
 
-    let bytes: Vec<u8> = [0x02u8, 0x01]
+    let bytes: Vec<u8> = MOCK_MAC_PREFIX
         .into_iter()
         .chain(next_mac_num.to_be_bytes())
         .collect();
@@ -14,12 +14,13 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use std::cell::OnceCell;
 use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
 use http::uri;
-use serde::de::Error as SerdeError;
+use serde::de::{Error as SerdeError, MapAccess, SeqAccess, Visitor};
 use serde::{Deserialize, Deserializer};
 
 /// Top-level ACL configuration keyed by authenticated principal identifier.
@@ -44,37 +45,86 @@ impl<'de> Deserialize<'de> for AclConfig {
 }
 
 impl AclConfig {
-    /// Returns whether `principal` is allowed to perform `method` on `path`.
+    /// Returns whether `principal` is allowed to perform `request`.
     ///
     /// The principal's ACL entries are evaluated in order. The first matching
     /// entry wins. If the principal is unknown or no entry matches, this
     /// returns `false`.
-    pub fn allows(&self, principal: &str, method: &http::Method, path: &str) -> bool {
+    pub fn allows(&self, principal: &str, request: &AclRequest) -> bool {
         let Some(entries) = self.config.get(principal) else {
             return false;
         };
         entries
             .iter()
-            .find_map(|entry| entry.action_if_matches(method, path))
+            .find_map(|entry| entry.action_if_matches(request))
             .map(|action| action.is_allowed())
             .unwrap_or(false)
     }
 }
 
+/// The parts of a proxied request which ACL entries are evaluated against.
+pub struct AclRequest<'a> {
+    method: &'a http::Method,
+    path: &'a str,
+    query: Option<&'a str>,
+    body: &'a [u8],
+    // The body is only parsed as JSON if an entry with body predicates is evaluated, and then at
+    // most once. `None` means the body is not valid JSON, or has duplicate keys.
+    body_json: OnceCell<Option<serde_json::Value>>,
+}
+
+impl<'a> AclRequest<'a> {
+    pub fn new(method: &'a http::Method, uri: &'a http::Uri, body: &'a [u8]) -> Self {
+        Self {
+            method,
+            path: uri.path(),
+            query: uri.query(),
+            body,
+            body_json: OnceCell::new(),
+        }
+    }
+
+    fn body_json(&self) -> Option<&serde_json::Value> {
+        self.body_json
+            .get_or_init(|| {
+                serde_json::from_slice::<UniqueKeysJson>(self.body)
+                    .ok()
+                    .map(|json| json.0)
+            })
+            .as_ref()
+    }
+
+    fn query_values(&self, name: &str) -> Vec<String> {
+        let Some(query) = self.query else {
+            return Vec::new();
+        };
+        url::form_urlencoded::parse(query.as_bytes())
+            .filter(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+            .collect()
+    }
+}
+
 /// An entry in the access control list for a client to carbide-bmc-proxy.
 ///
 /// The text form for use in the config takes the form of a single string with a leading `!` if the
 /// entry is disallowed (otherwise the entry is allowed), a list of HTTP verbs, and a wildcarded HTTP
 /// path
 ///
+/// The path may be followed by whitespace-separated [`AclPredicate`]s on the request body or
+/// query parameters, all of which need to match for the entry to match.
+///
 /// Examples:
 ///
 /// - `GET /redfish/v1/**`: Allow GET for anything that begins with /redfish/v1/
 /// - `!POST,PATCH /redfish/v1/Systems/*/SecureBoot/**`: Deny anything in Systems/*/SecureBoot
+/// - `POST /redfish/v1/Systems/*/Actions/ComputerSystem.Reset body:/ResetType=GracefulRestart`:
+///   Allow graceful restarts, but no other reset types
 #[derive(Clone)]
 struct AclEntry {
     verbs: Vec<AclVerb>,
     path: AclPath,
+    predicates: Vec<AclPredicate>,
     action: AclAction,
 }
 
@@ -110,13 +160,17 @@ impl Display for AclEntry {
             write!(f, "/{component}")?;
         }
 
+        for predicate in &self.predicates {
+            write!(f, " {predicate}")?;
+        }
+
         Ok(())
     }
 }
 
 impl AclEntry {
-    fn action_if_matches(&self, method: &http::Method, path: &str) -> Option<AclAction> {
-        if self.matches(method, path) {
+    fn action_if_matches(&self, request: &AclRequest) -> Option<AclAction> {
+        if self.matches(request.method, request.path) && self.predicates_match(request) {
             Some(self.action)
         } else {
             None
@@ -133,47 +187,34 @@ impl AclEntry {
             return false;
         }
 
-        let Some(path) = path.strip_prefix('/') else {
-            return false;
-        };
-        if path.is_empty() {
-            return self.path.components.is_empty();
-        }
-
-        let path_components = path.split('/').collect::<Vec<_>>();
-        if path_components.iter().any(|component| component.is_empty()) {
-            return false;
-        }
-
-        let acl_components = &self.path.components;
-        let double_wildcard_index = acl_components
-            .iter()
-            .position(|component| matches!(component, WildcardPathComponent::DoubleWildcard));
-
-        match double_wildcard_index {
-            None => {
-                acl_components.len() == path_components.len()
-                    && acl_components.iter().zip(path_components.iter()).all(
-                        |(acl_component, path_component)| acl_component.matches(path_component),
-                    )
-            }
-            Some(double_wildcard_index) => {
-                let (prefix, suffix_with_wildcard) = acl_components.split_at(double_wildcard_index);
-                let suffix = &suffix_with_wildcard[1..];
+        self.path.matches(path)
+    }
 
-                if path_components.len() < prefix.len() + suffix.len() {
-                    return false;
+    /// Returns whether all of this entry's predicates match `request`.
+    ///
+    /// If the entry has body predicates but the request body is not valid JSON, the predicates of
+    /// a deny entry match and those of an allow entry don't, so that a body which can't be
+    /// inspected is never allowed because of its contents.
+    fn predicates_match(&self, request: &AclRequest) -> bool {
+        self.predicates.iter().all(|predicate| match predicate {
+            AclPredicate::Body { pointer, matcher } => match request.body_json() {
+                Some(body) => matcher.matches(body.pointer(pointer).map(json_value_as_string)),
+                None => !self.action.is_allowed(),
+            },
+            AclPredicate::Query { name, matcher } => {
+                let values = request.query_values(name);
+                if values.is_empty() {
+                    matcher.matches(None)
+                } else {
+                    match matcher {
+                        ValueMatcher::NoneOf(_) => {
+                            values.into_iter().all(|value| matcher.matches(Some(value)))
+                        }
+                        _ => values.into_iter().any(|value| matcher.matches(Some(value))),
+                    }
                 }
-
-                prefix
-                    .iter()
-                    .zip(path_components.iter())
-                    .all(|(acl_component, path_component)| acl_component.matches(path_component))
-                    && suffix.iter().rev().zip(path_components.iter().rev()).all(
-                        |(acl_component, path_component)| acl_component.matches(path_component),
-                    )
             }
-        }
+        })
     }
 }
 
@@ -200,23 +241,33 @@ impl FromStr for AclEntry {
             (true, input.trim())
         };
 
-        let (verbs, path) = if let Some(pair) = s.split_once(' ') {
-            let verbs = pair
-                .0
-                .trim()
-                .split(',')
-                .map(AclVerb::from_str)
-                .collect::<Result<Vec<_>, _>>()?;
-            let path = pair.1.trim().parse::<AclPath>()?;
-            (verbs, path)
-        } else {
-            let path = s.parse::<AclPath>()?;
-            (Vec::new(), path)
+        let mut tokens = s.split_whitespace().peekable();
+        let verbs = match tokens.peek() {
+            Some(token) if !token.starts_with('/') => {
+                let verbs = token
+                    .split(',')
+                    .map(AclVerb::from_str)
+                    .collect::<Result<Vec<_>, _>>()?;
+                tokens.next();
+                verbs
+            }
+            _ => Vec::new(),
         };
+        let path = tokens
+            .next()
+            .ok_or_else(|| AclPathParseError {
+                orig: input.to_string(),
+                err: "ACL entry is missing a path".to_string(),
+            })?
+            .parse::<AclPath>()?;
+        let predicates = tokens
+            .map(AclPredicate::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
 
         Ok(Self {
             path,
             verbs,
+            predicates,
             action: allowed.into(),
         })
     }
@@ -244,11 +295,273 @@ impl AclAction {
     }
 }
 
+/// A condition on the request body or query parameters which an [`AclEntry`] can require in
+/// addition to its verbs and path.
+///
+/// The text form is `body:<json-pointer>` or `query:<parameter-name>`, optionally followed by
+/// `=value[|value...]` or `!=value[|value...]`:
+///
+/// - `body:/Attributes/TpmState`: The body has a value at the JSON pointer
+/// - `body:/ResetType=GracefulRestart|GracefulShutdown`: The value at the JSON pointer is one of
+///   the given values
+/// - `body:/ResetType!=ForceOff|ForceRestart`: The body has a value at the JSON pointer, and it is
+///   none of the given values
+/// - `query:$expand=.`: The query parameter is present and has one of the given values
+///
+/// Non-string JSON values are compared using their JSON representation (e.g. `true` or `5`).
+#[derive(Clone)]
+enum AclPredicate {
+    Body {
+        pointer: String,
+        matcher: ValueMatcher,
+    },
+    Query {
+        name: String,
+        matcher: ValueMatcher,
+    },
+}
+
+impl FromStr for AclPredicate {
+    type Err = AclPathParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_error = |err: &str| AclPathParseError {
+            orig: s.to_string(),
+            err: err.to_string(),
+        };
+
+        if let Some(rest) = s.strip_prefix("body:") {
+            let (pointer, matcher) = ValueMatcher::split(rest);
+            if !pointer.starts_with('/') {
+                return Err(parse_error(
+                    "Body predicate must be a JSON pointer beginning with '/'",
+                ));
+            }
+            Ok(Self::Body {
+                pointer: pointer.to_string(),
+                matcher,
+            })
+        } else if let Some(rest) = s.strip_prefix("query:") {
+            let (name, matcher) = ValueMatcher::split(rest);
+            if name.is_empty() {
+                return Err(parse_error("Query predicate must name a query parameter"));
+            }
+            Ok(Self::Query {
+                name: name.to_string(),
+                matcher,
+            })
+        } else {
+            Err(parse_error("Predicate must begin with `body:` or `query:`"))
+        }
+    }
+}
+
+impl Display for AclPredicate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AclPredicate::Body { pointer, matcher } => write!(f, "body:{pointer}{matcher}"),
+            AclPredicate::Query { name, matcher } => write!(f, "query:{name}{matcher}"),
+        }
+    }
+}
+
+/// How the value selected by an [`AclPredicate`] is compared.
+#[derive(Clone)]
+enum ValueMatcher {
+    /// The value is present.
+    Present,
+    /// The value is present and equal to one of these.
+    OneOf(Vec<String>),
+    /// The value is present and equal to none of these.
+    NoneOf(Vec<String>),
+}
+
+impl ValueMatcher {
+    /// Splits the text form of a predicate (without its `body:` or `query:` prefix) into the
+    /// selector and the matcher.
+    fn split(s: &str) -> (&str, Self) {
+        let values = |values: &str| values.split('|').map(str::to_string).collect();
+        if let Some((selector, not_values)) = s.split_once("!=") {
+            (selector, Self::NoneOf(values(not_values)))
+        } else if let Some((selector, one_of)) = s.split_once('=') {
+            (selector, Self::OneOf(values(one_of)))
+        } else {
+            (s, Self::Present)
+        }
+    }
+
+    fn matches(&self, value: Option<String>) -> bool {
+        match (self, value) {
+            (_, None) => false,
+            (ValueMatcher::Present, Some(_)) => true,
+            (ValueMatcher::OneOf(values), Some(value)) => values.contains(&value),
+            (ValueMatcher::NoneOf(values), Some(value)) => !values.contains(&value),
+        }
+    }
+}
+
+impl Display for ValueMatcher {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValueMatcher::Present => Ok(()),
+            ValueMatcher::OneOf(values) => write!(f, "={}", values.join("|")),
+            ValueMatcher::NoneOf(values) => write!(f, "!={}", values.join("|")),
+        }
+    }
+}
+
+/// A JSON value which fails to deserialize if any object in it has duplicate keys.
+///
+/// `serde_json::Value` keeps the last of duplicate keys, while a BMC might use the first. Request
+/// bodies with duplicate keys could then pass body predicates with a value the BMC never sees.
+struct UniqueKeysJson(serde_json::Value);
+
+impl<'de> Deserialize<'de> for UniqueKeysJson {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer
+            .deserialize_any(UniqueKeysJsonVisitor)
+            .map(Self)
+    }
+}
+
+struct UniqueKeysJsonVisitor;
+
+impl<'de> Visitor<'de> for UniqueKeysJsonVisitor {
+    type Value = serde_json::Value;
+
+    fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.write_str("any JSON value without duplicate object keys")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(v.into())
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(v.into())
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(v.into())
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E> {
+        Ok(v.into())
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(v.into())
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E> {
+        Ok(v.into())
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(serde_json::Value::Null)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut values = Vec::new();
+        while let Some(UniqueKeysJson(value)) = seq.next_element()? {
+            values.push(value);
+        }
+        Ok(values.into())
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut object = serde_json::Map::new();
+        while let Some(key) = map.next_key::<String>()? {
+            if object.contains_key(&key) {
+                return Err(A::Error::custom(format!("duplicate key {key:?}")));
+            }
+            let UniqueKeysJson(value) = map.next_value()?;
+            object.insert(key, value);
+        }
+        Ok(object.into())
+    }
+}
+
+fn json_value_as_string(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// A wildcarded HTTP path, as used in ACL entries.
 #[derive(Clone)]
-struct AclPath {
+pub struct AclPath {
     components: Vec<WildcardPathComponent>,
 }
 
+impl<'de> Deserialize<'de> for AclPath {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(D::Error::custom)
+    }
+}
+
+impl AclPath {
+    /// Returns whether `path` matches this path, using the wildcard semantics
+    /// described by [`WildcardPathComponent`].
+    pub fn matches(&self, path: &str) -> bool {
+        let Some(path) = path.strip_prefix('/') else {
+            return false;
+        };
+        if path.is_empty() {
+            return self.components.is_empty();
+        }
+
+        let path_components = path.split('/').collect::<Vec<_>>();
+        if path_components.iter().any(|component| component.is_empty()) {
+            return false;
+        }
+
+        let acl_components = &self.components;
+        let double_wildcard_index = acl_components
+            .iter()
+            .position(|component| matches!(component, WildcardPathComponent::DoubleWildcard));
+
+        match double_wildcard_index {
+            None => {
+                acl_components.len() == path_components.len()
+                    && acl_components.iter().zip(path_components.iter()).all(
+                        |(acl_component, path_component)| acl_component.matches(path_component),
+                    )
+            }
+            Some(double_wildcard_index) => {
+                let (prefix, suffix_with_wildcard) = acl_components.split_at(double_wildcard_index);
+                let suffix = &suffix_with_wildcard[1..];
+
+                if path_components.len() < prefix.len() + suffix.len() {
+                    return false;
+                }
+
+                prefix
+                    .iter()
+                    .zip(path_components.iter())
+                    .all(|(acl_component, path_component)| acl_component.matches(path_component))
+                    && suffix.iter().rev().zip(path_components.iter().rev()).all(
+                        |(acl_component, path_component)| acl_component.matches(path_component),
+                    )
+            }
+        }
+    }
+}
+
 impl FromStr for AclPath {
     type Err = AclPathParseError;
 
@@ -453,6 +766,21 @@ mod tests {
         config.acls
     }
 
+    fn allows(acls: &AclConfig, principal: &str, method: http::Method, uri: &str) -> bool {
+        allows_with_body(acls, principal, method, uri, "")
+    }
+
+    fn allows_with_body(
+        acls: &AclConfig,
+        principal: &str,
+        method: http::Method,
+        uri: &str,
+        body: &str,
+    ) -> bool {
+        let uri = uri.parse::<http::Uri>().unwrap();
+        acls.allows(principal, &AclRequest::new(&method, &uri, body.as_bytes()))
+    }
+
     fn round_trip_as_acl_entry(s: &str) -> Result<String, AclPathParseError> {
         s.parse::<AclEntry>().map(|entry| entry.to_string())
     }
@@ -600,34 +928,59 @@ mod tests {
         "#,
         );
 
-        assert!(acls.allows("service_a", &http::Method::GET, "/redfish/v1/Systems"));
-        assert!(acls.allows("service_b", &http::Method::GET, "/redfish/v1/Systems"));
-        assert!(!acls.allows(
+        assert!(allows(
+            &acls,
+            "service_a",
+            http::Method::GET,
+            "/redfish/v1/Systems"
+        ));
+        assert!(allows(
+            &acls,
             "service_b",
-            &http::Method::POST,
+            http::Method::GET,
+            "/redfish/v1/Systems"
+        ));
+        assert!(!allows(
+            &acls,
+            "service_b",
+            http::Method::POST,
             "/redfish/v1/Systems/System1/SecureBoot/Bad"
         ));
-        assert!(!acls.allows("service_a", &http::Method::GET, "/other/stuff"));
-        assert!(!acls.allows("service_b", &http::Method::GET, "/other/stuff"));
+        assert!(!allows(
+            &acls,
+            "service_a",
+            http::Method::GET,
+            "/other/stuff"
+        ));
+        assert!(!allows(
+            &acls,
+            "service_b",
+            http::Method::GET,
+            "/other/stuff"
+        ));
 
-        assert!(acls.allows(
+        assert!(allows(
+            &acls,
             "service_c",
-            &http::Method::GET,
+            http::Method::GET,
             "/redfish/v1/Systems/SomeSystem/foo"
         ));
-        assert!(acls.allows(
+        assert!(allows(
+            &acls,
             "service_c",
-            &http::Method::GET,
+            http::Method::GET,
             "/redfish/v1/Systems/SomeSystem"
         ));
-        assert!(!acls.allows(
+        assert!(!allows(
+            &acls,
             "service_c",
-            &http::Method::GET,
+            http::Method::GET,
             "/redfish/v1/Systems/Bluefield"
         ));
-        assert!(!acls.allows(
+        assert!(!allows(
+            &acls,
             "service_c",
-            &http::Method::GET,
+            http::Method::GET,
             "/redfish/v1/Systems/Bluefield/Other"
         ));
     }
@@ -642,14 +995,16 @@ mod tests {
         "#,
         );
 
-        assert!(!acls.allows(
+        assert!(!allows(
+            &acls,
             "deny_first",
-            &http::Method::GET,
+            http::Method::GET,
             "/redfish/v1/Systems/System1"
         ));
-        assert!(acls.allows(
+        assert!(allows(
+            &acls,
             "allow_first",
-            &http::Method::GET,
+            http::Method::GET,
             "/redfish/v1/Systems/System1"
         ));
     }
@@ -663,7 +1018,228 @@ mod tests {
         "#,
         );
 
-        assert!(!acls.allows("unknown_service", &http::Method::GET, "/redfish/v1/Systems"));
-        assert!(!acls.allows("service_a", &http::Method::GET, "/other/stuff"));
+        assert!(!allows(
+            &acls,
+            "unknown_service",
+            http::Method::GET,
+            "/redfish/v1/Systems"
+        ));
+        assert!(!allows(
+            &acls,
+            "service_a",
+            http::Method::GET,
+            "/other/stuff"
+        ));
+    }
+
+    #[test]
+    fn test_predicate_entry_parsing() {
+        assert_stable_parse(
+            "POST /redfish/v1/Systems/*/Actions/ComputerSystem.Reset body:/ResetType=GracefulRestart|GracefulShutdown",
+        );
+        assert_stable_parse(
+            "! PATCH /redfish/v1/Systems/*/Bios/Settings body:/Attributes/TpmState",
+        );
+        assert_stable_parse("GET /redfish/v1/Chassis query:$expand=.");
+        assert_stable_parse("/redfish/v1/** body:/ResetType!=ForceOff query:force");
+
+        assert!(AclEntry::from_str("POST /foo body:ResetType=On").is_err());
+        assert!(AclEntry::from_str("GET /foo query:").is_err());
+        assert!(AclEntry::from_str("GET /foo header:Accept").is_err());
+        assert!(AclEntry::from_str("GET").is_err());
+    }
+
+    #[test]
+    fn test_body_predicates_select_reset_types() {
+        let acls = parse_acl_config(
+            r#"
+        [acls]
+        operator = [
+          "POST /redfish/v1/Systems/*/Actions/ComputerSystem.Reset body:/ResetType=GracefulRestart|On",
+          "!POST /redfish/v1/Systems/*/Actions/ComputerSystem.Reset",
+          "GET /redfish/v1/**",
+        ]
+        "#,
+        );
+        let reset = "/redfish/v1/Systems/System1/Actions/ComputerSystem.Reset";
+
+        assert!(allows_with_body(
+            &acls,
+            "operator",
+            http::Method::POST,
+            reset,
+            r#"{"ResetType": "GracefulRestart"}"#
+        ));
+        assert!(allows_with_body(
+            &acls,
+            "operator",
+            http::Method::POST,
+            reset,
+            r#"{"ResetType": "On"}"#
+        ));
+        assert!(!allows_with_body(
+            &acls,
+            "operator",
+            http::Method::POST,
+            reset,
+            r#"{"ResetType": "ForceOff"}"#
+        ));
+        assert!(!allows_with_body(
+            &acls,
+            "operator",
+            http::Method::POST,
+            reset,
+            "{}"
+        ));
+        assert!(allows(
+            &acls,
+            "operator",
+            http::Method::GET,
+            "/redfish/v1/Systems/System1"
+        ));
+    }
+
+    #[test]
+    fn test_body_predicates_deny_specific_attributes() {
+        let acls = parse_acl_config(
+            r#"
+        [acls]
+        tuner = [
+          "!PATCH /redfish/v1/Systems/*/Bios/Settings body:/Attributes/TpmState",
+          "!PATCH /redfish/v1/Systems/*/Bios/Settings body:/Attributes/SecureBoot!=Enabled",
+          "PATCH /redfish/v1/Systems/*/Bios/Settings",
+        ]
+        "#,
+        );
+        let bios = "/redfish/v1/Systems/System1/Bios/Settings";
+
+        assert!(allows_with_body(
+            &acls,
+            "tuner",
+            http::Method::PATCH,
+            bios,
+            r#"{"Attributes": {"ProcTurboMode": "Disabled"}}"#
+        ));
+        assert!(allows_with_body(
+            &acls,
+            "tuner",
+            http::Method::PATCH,
+            bios,
+            r#"{"Attributes": {"SecureBoot": "Enabled"}}"#
+        ));
+        assert!(!allows_with_body(
+            &acls,
+            "tuner",
+            http::Method::PATCH,
+            bios,
+            r#"{"Attributes": {"ProcTurboMode": "Disabled", "TpmState": "Off"}}"#
+        ));
+        assert!(!allows_with_body(
+            &acls,
+            "tuner",
+            http::Method::PATCH,
+            bios,
+            r#"{"Attributes": {"SecureBoot": "Disabled"}}"#
+        ));
+    }
+
+    #[test]
+    fn test_unparseable_body_never_allowed_by_body_predicates() {
+        let acls = parse_acl_config(
+            r#"
+        [acls]
+        deny_then_allow = [
+          "!PATCH /redfish/v1/Systems/*/Bios/Settings body:/Attributes/TpmState",
+          "PATCH /redfish/v1/Systems/*/Bios/Settings",
+        ]
+        allow_only = ["PATCH /redfish/v1/Systems/*/Bios/Settings body:/Attributes"]
+        "#,
+        );
+        let bios = "/redfish/v1/Systems/System1/Bios/Settings";
+
+        for principal in ["deny_then_allow", "allow_only"] {
+            assert!(!allows_with_body(
+                &acls,
+                principal,
+                http::Method::PATCH,
+                bios,
+                "not json"
+            ));
+        }
+    }
+
+    #[test]
+    fn test_body_with_duplicate_keys_never_allowed_by_body_predicates() {
+        let acls = parse_acl_config(
+            r#"
+        [acls]
+        service_a = [
+          "!POST /redfish/v1/Systems/*/Actions/ComputerSystem.Reset body:/ResetType=ForceOff",
+          "POST /redfish/v1/Systems/*/Actions/ComputerSystem.Reset",
+        ]
+        service_b = ["POST /redfish/v1/Systems/*/Actions/ComputerSystem.Reset body:/ResetType=GracefulRestart"]
+        "#,
+        );
+        let reset = "/redfish/v1/Systems/System1/Actions/ComputerSystem.Reset";
+        let body = r#"{"ResetType": "ForceOff", "ResetType": "GracefulRestart"}"#;
+
+        for principal in ["service_a", "service_b"] {
+            assert!(!allows_with_body(
+                &acls,
+                principal,
+                http::Method::POST,
+                reset,
+                body
+            ));
+        }
+        assert!(allows_with_body(
+            &acls,
+            "service_b",
+            http::Method::POST,
+            reset,
+            r#"{"ResetType": "GracefulRestart", "Nested": [{"a": 1}, {"a": 2}]}"#
+        ));
+    }
+
+    #[test]
+    fn test_query_predicates() {
+        let acls = parse_acl_config(
+            r#"
+        [acls]
+        service_a = ["!GET /redfish/v1/** query:$expand", "GET /redfish/v1/**"]
+        service_b = ["GET /redfish/v1/** query:$top=1|10"]
+        "#,
+        );
+
+        assert!(allows(
+            &acls,
+            "service_a",
+            http::Method::GET,
+            "/redfish/v1/Chassis"
+        ));
+        assert!(!allows(
+            &acls,
+            "service_a",
+            http::Method::GET,
+            "/redfish/v1/Chassis?$expand=."
+        ));
+        assert!(allows(
+            &acls,
+            "service_b",
+            http::Method::GET,
+            "/redfish/v1/Chassis?$top=10"
+        ));
+        assert!(!allows(
+            &acls,
+            "service_b",
+            http::Method::GET,
+            "/redfish/v1/Chassis?$top=100"
+        ));
+        assert!(!allows(
+            &acls,
+            "service_b",
+            http::Method::GET,
+            "/redfish/v1/Chassis"
+        ));
     }
 }
@@ -0,0 +1,623 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Per-BMC request limiting for carbide-bmc-proxy.
+//!
+//! Every upstream request to a BMC has to acquire one of that BMC's concurrency permits before it
+//! is sent. Requests which can't get a permit wait in a queue until either a permit frees up or
+//! the configured queue timeout elapses.
+//!
+//! Identical GET requests which arrive while an earlier one is still in flight are coalesced: only
+//! the first one is sent to the BMC, and every caller gets a copy of its response. Successful GET
+//! responses for paths matching a configured cache rule are additionally kept for a short TTL.
+//! Requests are only identical if they also come from the same caller with the same
+//! response-affecting headers, see [`RequestKey`].
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::net::IpAddr;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use bytes::Bytes;
+use futures_util::FutureExt;
+use futures_util::future::{BoxFuture, Shared};
+use http::{HeaderMap, HeaderName, HeaderValue, Method, StatusCode};
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::{Counter, Meter, UpDownCounter};
+use serde::Deserialize;
+use tokio::sync::Semaphore;
+
+use crate::acl::AclPath;
+use crate::bmc_proxy::ProxyError;
+
+/// Configuration for how requests to individual BMCs are limited, coalesced and cached.
+#[derive(Clone, Deserialize)]
+#[serde(default)]
+pub struct BmcLimitsConfig {
+    /// How many requests may be in flight to a single BMC at the same time.
+    pub max_concurrent_requests: NonZeroUsize,
+    /// Per-BMC overrides of `max_concurrent_requests`, keyed by BMC IP address.
+    pub max_concurrent_requests_overrides: HashMap<IpAddr, NonZeroUsize>,
+    /// How long a request may wait for a free slot before it is rejected with
+    /// `503 Service Unavailable`.
+    #[serde(with = "humantime_serde")]
+    pub queue_timeout: Duration,
+    /// Whether identical in-flight GET requests to the same BMC share one upstream request.
+    pub coalesce_gets: bool,
+    /// Paths for which successful GET responses are served from a short-lived cache.
+    pub cache: Vec<CacheRule>,
+}
+
+impl Default for BmcLimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent_requests: NonZeroUsize::new(4).unwrap(),
+            max_concurrent_requests_overrides: HashMap::new(),
+            queue_timeout: Duration::from_secs(30),
+            coalesce_gets: true,
+            cache: Vec::new(),
+        }
+    }
+}
+
+/// A path pattern (using ACL path syntax) whose GET responses are cached for `ttl`.
+#[derive(Clone, Deserialize)]
+pub struct CacheRule {
+    pub path: AclPath,
+    #[serde(with = "humantime_serde")]
+    pub ttl: Duration,
+}
+
+/// Identifies GET requests to a BMC which can share a response.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RequestKey {
+    pub path_and_query: String,
+    /// The authenticated principals of the caller, sorted
+    pub principals: Vec<String>,
+    /// The request headers which can change the response, like `Authorization` and `Accept`
+    pub headers: Vec<(HeaderName, HeaderValue)>,
+}
+
+impl RequestKey {
+    fn path(&self) -> &str {
+        self.path_and_query
+            .split_once('?')
+            .map_or(&self.path_and_query, |(path, _)| path)
+    }
+}
+
+/// The parts of an upstream BMC response which are shared between coalesced callers and cached.
+#[derive(Clone)]
+pub struct UpstreamResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Bytes,
+}
+
+type UpstreamResult = Result<UpstreamResponse, ProxyError>;
+type SharedUpstream = Shared<BoxFuture<'static, UpstreamResult>>;
+
+pub struct BmcLimiter {
+    config: BmcLimitsConfig,
+    bmcs: Mutex<HashMap<IpAddr, Arc<BmcSlot>>>,
+    metrics: LimiterMetrics,
+}
+
+impl BmcLimiter {
+    pub fn new(config: BmcLimitsConfig, meter: &Meter) -> Self {
+        Self {
+            config,
+            bmcs: Mutex::new(HashMap::new()),
+            metrics: LimiterMetrics::new(meter),
+        }
+    }
+
+    /// Returns a cached response for a GET of `key` on `bmc_ip`, if there is one which has not
+    /// expired yet.
+    pub fn cached(
+        &self,
+        bmc_ip: IpAddr,
+        method: &Method,
+        key: &RequestKey,
+    ) -> Option<UpstreamResponse> {
+        if *method != Method::GET || self.config.cache.is_empty() {
+            return None;
+        }
+
+        let response = self.bmc(bmc_ip).cached(key)?;
+        self.metrics.cache_hits.add(1, &bmc_attributes(bmc_ip));
+        Some(response)
+    }
+
+    /// Runs the upstream request produced by `upstream` against `bmc_ip` once a concurrency slot
+    /// for that BMC is available.
+    ///
+    /// GET requests are coalesced with an identical in-flight request if there is one, in which
+    /// case `upstream` is never called.
+    pub async fn send<F, Fut>(
+        &self,
+        bmc_ip: IpAddr,
+        method: &Method,
+        key: RequestKey,
+        upstream: F,
+    ) -> UpstreamResult
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = UpstreamResult> + Send + 'static,
+    {
+        let bmc = self.bmc(bmc_ip);
+
+        if *method != Method::GET {
+            return bmc.limited(upstream(), &self.metrics).await;
+        }
+
+        let cache_ttl = self.cache_ttl(&key);
+        if !self.config.coalesce_gets {
+            let result = bmc.limited(upstream(), &self.metrics).await;
+            bmc.maybe_cache(&key, &result, cache_ttl);
+            return result;
+        }
+
+        let shared = {
+            let mut in_flight = bmc
+                .in_flight
+                .lock()
+                .expect("BUG: in-flight request mutex poisoned");
+            if let Some(existing) = in_flight.get(&key) {
+                self.metrics.coalesced.add(1, &bmc_attributes(bmc_ip));
+                existing.clone()
+            } else {
+                let shared = self.spawn_leader(bmc.clone(), key.clone(), upstream(), cache_ttl);
+                in_flight.insert(key, shared.clone());
+                shared
+            }
+        };
+
+        shared.await
+    }
+
+    /// Spawns the request which coalesced callers wait on. It runs in its own task so that it
+    /// completes (and clears its in-flight entry) even if every caller goes away.
+    fn spawn_leader<Fut>(
+        &self,
+        bmc: Arc<BmcSlot>,
+        key: RequestKey,
+        upstream: Fut,
+        cache_ttl: Option<Duration>,
+    ) -> SharedUpstream
+    where
+        Fut: Future<Output = UpstreamResult> + Send + 'static,
+    {
+        let metrics = self.metrics.clone();
+        let handle = tokio::task::Builder::new()
+            .name("bmc-proxy coalesced request")
+            .spawn(async move {
+                let result = bmc.limited(upstream, &metrics).await;
+                bmc.maybe_cache(&key, &result, cache_ttl);
+                bmc.in_flight
+                    .lock()
+                    .expect("BUG: in-flight request mutex poisoned")
+                    .remove(&key);
+                result
+            })
+            // Safety: will only fail if outside tokio runtime
+            .expect("Error spawning coalesced bmc request");
+
+        async move {
+            handle.await.unwrap_or_else(|e| {
+                Err((
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("Upstream request task failed: {e}"),
+                )
+                    .into())
+            })
+        }
+        .boxed()
+        .shared()
+    }
+
+    fn cache_ttl(&self, key: &RequestKey) -> Option<Duration> {
+        self.config
+            .cache
+            .iter()
+            .find(|rule| rule.path.matches(key.path()))
+            .map(|rule| rule.ttl)
+    }
+
+    fn bmc(&self, bmc_ip: IpAddr) -> Arc<BmcSlot> {
+        let mut bmcs = self.bmcs.lock().expect("BUG: BMC slot mutex poisoned");
+        bmcs.entry(bmc_ip)
+            .or_insert_with(|| {
+                let permits = self
+                    .config
+                    .max_concurrent_requests_overrides
+                    .get(&bmc_ip)
+                    .copied()
+                    .unwrap_or(self.config.max_concurrent_requests);
+                Arc::new(BmcSlot::new(bmc_ip, permits, self.config.queue_timeout))
+            })
+            .clone()
+    }
+}
+
+/// Limiting state for a single BMC.
+struct BmcSlot {
+    bmc_ip: IpAddr,
+    semaphore: Arc<Semaphore>,
+    queue_timeout: Duration,
+    in_flight: Mutex<HashMap<RequestKey, SharedUpstream>>,
+    cache: Mutex<HashMap<RequestKey, CachedResponse>>,
+}
+
+struct CachedResponse {
+    response: UpstreamResponse,
+    expires_at: Instant,
+}
+
+impl BmcSlot {
+    fn new(bmc_ip: IpAddr, permits: NonZeroUsize, queue_timeout: Duration) -> Self {
+        Self {
+            bmc_ip,
+            semaphore: Arc::new(Semaphore::new(permits.get())),
+            queue_timeout,
+            in_flight: Mutex::new(HashMap::new()),
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    async fn limited<Fut>(&self, upstream: Fut, metrics: &LimiterMetrics) -> UpstreamResult
+    where
+        Fut: Future<Output = UpstreamResult>,
+    {
+        let attributes = bmc_attributes(self.bmc_ip);
+
+        metrics.queue_depth.add(1, &attributes);
+        let permit =
+            tokio::time::timeout(self.queue_timeout, self.semaphore.clone().acquire_owned()).await;
+        metrics.queue_depth.add(-1, &attributes);
+
+        let _permit = match permit {
+            Ok(Ok(permit)) => permit,
+            Ok(Err(_)) => {
+                return Err((
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "BUG: BMC request semaphore closed",
+                )
+                    .into());
+            }
+            Err(_) => {
+                metrics.queue_timeouts.add(1, &attributes);
+                return Err((
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    format!(
+                        "Timed out after {:?} waiting for a free request slot for BMC {}",
+                        self.queue_timeout, self.bmc_ip
+                    ),
+                )
+                    .into());
+            }
+        };
+
+        upstream.await
+    }
+
+    fn cached(&self, key: &RequestKey) -> Option<UpstreamResponse> {
+        let mut cache = self
+            .cache
+            .lock()
+            .expect("BUG: response cache mutex poisoned");
+        match cache.get(key) {
+            Some(entry) if entry.expires_at > Instant::now() => Some(entry.response.clone()),
+            Some(_) => {
+                cache.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn maybe_cache(&self, key: &RequestKey, result: &UpstreamResult, ttl: Option<Duration>) {
+        let (Ok(response), Some(ttl)) = (result, ttl) else {
+            return;
+        };
+        if !response.status.is_success() {
+            return;
+        }
+
+        let now = Instant::now();
+        let mut cache = self
+            .cache
+            .lock()
+            .expect("BUG: response cache mutex poisoned");
+        cache.retain(|_, entry| entry.expires_at > now);
+        cache.insert(
+            key.clone(),
+            CachedResponse {
+                response: response.clone(),
+                expires_at: now + ttl,
+            },
+        );
+    }
+}
+
+#[derive(Clone)]
+struct LimiterMetrics {
+    queue_depth: UpDownCounter<i64>,
+    queue_timeouts: Counter<u64>,
+    coalesced: Counter<u64>,
+    cache_hits: Counter<u64>,
+}
+
+impl LimiterMetrics {
+    fn new(meter: &Meter) -> Self {
+        Self {
+            queue_depth: meter
+                .i64_up_down_counter("carbide-bmc-proxy.bmc.queue_depth")
+                .with_description("The amount of requests waiting for a free slot to a BMC")
+                .build(),
+            queue_timeouts: meter
+                .u64_counter("carbide-bmc-proxy.bmc.queue_timeouts")
+                .with_description(
+                    "The amount of requests rejected because no slot to a BMC became free in time",
+                )
+                .build(),
+            coalesced: meter
+                .u64_counter("carbide-bmc-proxy.bmc.coalesced_requests")
+                .with_description(
+                    "The amount of GET requests answered by an identical in-flight request",
+                )
+                .build(),
+            cache_hits: meter
+                .u64_counter("carbide-bmc-proxy.bmc.cache_hits")
+                .with_description("The amount of GET requests answered from the response cache")
+                .build(),
+        }
+    }
+}
+
+fn bmc_attributes(bmc_ip: IpAddr) -> [KeyValue; 1] {
+    [KeyValue::new("bmc_ip", bmc_ip.to_string())]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    const BMC_IP: IpAddr = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+
+    fn limiter(config: BmcLimitsConfig) -> Arc<BmcLimiter> {
+        Arc::new(BmcLimiter::new(
+            config,
+            &opentelemetry::global::meter("bmc-limiter-test"),
+        ))
+    }
+
+    fn key(path_and_query: &str) -> RequestKey {
+        RequestKey {
+            path_and_query: path_and_query.to_string(),
+            principals: vec!["service_a".to_string()],
+            headers: vec![],
+        }
+    }
+
+    fn ok_response(body: &'static str) -> UpstreamResult {
+        Ok(UpstreamResponse {
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+            body: Bytes::from_static(body.as_bytes()),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_is_limited_per_bmc() {
+        let limiter = limiter(BmcLimitsConfig {
+            max_concurrent_requests: NonZeroUsize::new(2).unwrap(),
+            ..Default::default()
+        });
+        let running = Arc::new(AtomicUsize::new(0));
+        let max_running = Arc::new(AtomicUsize::new(0));
+
+        let mut tasks = Vec::new();
+        for _ in 0..8 {
+            let limiter = limiter.clone();
+            let running = running.clone();
+            let max_running = max_running.clone();
+            tasks.push(tokio::spawn(async move {
+                limiter
+                    .send(
+                        BMC_IP,
+                        &Method::POST,
+                        key("/redfish/v1/Systems"),
+                        move || async move {
+                            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                            max_running.fetch_max(now, Ordering::SeqCst);
+                            tokio::time::sleep(Duration::from_millis(20)).await;
+                            running.fetch_sub(1, Ordering::SeqCst);
+                            ok_response("{}")
+                        },
+                    )
+                    .await
+            }));
+        }
+
+        for task in tasks {
+            assert!(task.await.unwrap().is_ok());
+        }
+        assert_eq!(max_running.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_queue_timeout_rejects_request() {
+        let limiter = limiter(BmcLimitsConfig {
+            max_concurrent_requests: NonZeroUsize::new(1).unwrap(),
+            queue_timeout: Duration::from_millis(10),
+            ..Default::default()
+        });
+
+        let slow = {
+            let limiter = limiter.clone();
+            tokio::spawn(async move {
+                limiter
+                    .send(BMC_IP, &Method::POST, key("/slow"), || async {
+                        tokio::time::sleep(Duration::from_millis(200)).await;
+                        ok_response("{}")
+                    })
+                    .await
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let result = limiter
+            .send(BMC_IP, &Method::POST, key("/fast"), || async {
+                ok_response("{}")
+            })
+            .await;
+        assert_eq!(
+            result.err().map(|e| e.status),
+            Some(StatusCode::SERVICE_UNAVAILABLE)
+        );
+        assert!(slow.await.unwrap().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_identical_gets_are_coalesced() {
+        let limiter = limiter(BmcLimitsConfig::default());
+        let upstream_calls = Arc::new(AtomicUsize::new(0));
+
+        let mut tasks = Vec::new();
+        for _ in 0..5 {
+            let limiter = limiter.clone();
+            let upstream_calls = upstream_calls.clone();
+            tasks.push(tokio::spawn(async move {
+                limiter
+                    .send(
+                        BMC_IP,
+                        &Method::GET,
+                        key("/redfish/v1/Chassis"),
+                        move || {
+                            upstream_calls.fetch_add(1, Ordering::SeqCst);
+                            async {
+                                tokio::time::sleep(Duration::from_millis(50)).await;
+                                ok_response("chassis")
+                            }
+                        },
+                    )
+                    .await
+            }));
+        }
+
+        for task in tasks {
+            assert_eq!(task.await.unwrap().unwrap().body, "chassis");
+        }
+        assert_eq!(upstream_calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_successful_gets_are_cached_for_matching_paths() {
+        let limiter = limiter(BmcLimitsConfig {
+            cache: vec![CacheRule {
+                path: "/redfish/v1/Chassis/*/Sensors/**".parse().unwrap(),
+                ttl: Duration::from_secs(60),
+            }],
+            ..Default::default()
+        });
+
+        let sensors = key("/redfish/v1/Chassis/1/Sensors?$expand=.");
+        assert!(limiter.cached(BMC_IP, &Method::GET, &sensors).is_none());
+        limiter
+            .send(BMC_IP, &Method::GET, sensors.clone(), || async {
+                ok_response("sensors")
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            limiter.cached(BMC_IP, &Method::GET, &sensors).unwrap().body,
+            "sensors"
+        );
+
+        let systems = key("/redfish/v1/Systems");
+        limiter
+            .send(BMC_IP, &Method::GET, systems.clone(), || async {
+                ok_response("systems")
+            })
+            .await
+            .unwrap();
+        assert!(limiter.cached(BMC_IP, &Method::GET, &systems).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_gets_of_different_callers_are_not_shared() {
+        let limiter = limiter(BmcLimitsConfig {
+            cache: vec![CacheRule {
+                path: "/redfish/v1/**".parse().unwrap(),
+                ttl: Duration::from_secs(60),
+            }],
+            ..Default::default()
+        });
+        let upstream_calls = Arc::new(AtomicUsize::new(0));
+
+        let chassis = key("/redfish/v1/Chassis");
+        let other_principal = RequestKey {
+            principals: vec!["service_b".to_string()],
+            ..chassis.clone()
+        };
+        let other_accept = RequestKey {
+            headers: vec![(
+                http::header::ACCEPT,
+                HeaderValue::from_static("application/yaml"),
+            )],
+            ..chassis.clone()
+        };
+
+        let mut tasks = Vec::new();
+        for key in [
+            chassis.clone(),
+            other_principal.clone(),
+            other_accept.clone(),
+        ] {
+            let limiter = limiter.clone();
+            let upstream_calls = upstream_calls.clone();
+            tasks.push(tokio::spawn(async move {
+                limiter
+                    .send(BMC_IP, &Method::GET, key, move || {
+                        upstream_calls.fetch_add(1, Ordering::SeqCst);
+                        async {
+                            tokio::time::sleep(Duration::from_millis(50)).await;
+                            ok_response("chassis")
+                        }
+                    })
+                    .await
+            }));
+        }
+        for task in tasks {
+            assert!(task.await.unwrap().is_ok());
+        }
+        assert_eq!(upstream_calls.load(Ordering::SeqCst), 3);
+
+        let unseen = RequestKey {
+            principals: vec!["service_c".to_string()],
+            ..chassis.clone()
+        };
+        assert!(limiter.cached(BMC_IP, &Method::GET, &chassis).is_some());
+        assert!(limiter.cached(BMC_IP, &Method::GET, &unseen).is_none());
+    }
+}
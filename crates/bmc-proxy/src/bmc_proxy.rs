@@ -37,6 +37,7 @@ use forge_secrets::credentials::{
     BmcCredentialType, CredentialKey, CredentialManager, CredentialReader, Credentials,
 };
 use forge_secrets::{CredentialConfig, create_credential_manager};
+use http::uri::PathAndQuery;
 use http::{HeaderMap, Method, Request, Response, StatusCode, Uri};
 use hyper::server::conn::http2;
 use hyper_util::rt::{TokioExecutor, TokioIo};
@@ -53,10 +54,15 @@ use tokio_rustls::{TlsAcceptor, rustls};
 use tokio_util::sync::CancellationToken;
 use tower_http::add_extension::AddExtensionLayer;
 
+use crate::acl::AclRequest;
+use crate::bmc_limiter::{BmcLimiter, RequestKey, UpstreamResponse};
 use crate::config::{AuthConfig, TlsConfig};
 
 const TLS_REFRESH_INTERVAL: Duration = Duration::from_secs(5 * 60);
 const MAX_BODY_SIZE: usize = 8 * 1024 * 1024; // 8MiB body size limit (matches nginx ingress controller defaults)
+/// Request headers which can change a BMC's response, so requests only share responses if these match
+const RESPONSE_AFFECTING_HEADERS: [http::HeaderName; 2] =
+    [http::header::AUTHORIZATION, http::header::ACCEPT];
 
 #[derive(thiserror::Error, Debug)]
 pub enum BmcProxyError {
@@ -87,22 +93,35 @@ struct BmcProxyState {
     meter: Meter,
     pg_pool: PgPool,
     credential_manager: Arc<dyn CredentialManager>,
+    limiter: Arc<BmcLimiter>,
 }
 
 impl BmcProxyState {
-    fn allows(&self, request: &Request<Body>) -> bool {
-        let Some(auth_context) = request.extensions().get::<AuthContext<()>>() else {
+    fn allows(&self, parts: &http::request::Parts, body: &[u8]) -> bool {
+        let Some(auth_context) = parts.extensions.get::<AuthContext<()>>() else {
             tracing::error!("BUG: No AuthContext middleware found, all requests will be denied");
             return false;
         };
 
-        auth_context.principals.iter().any(|princ| {
-            self.config.auth.acls.allows(
-                &princ.as_identifier(),
-                request.method(),
-                request.uri().path(),
-            )
-        })
+        let acl_request = AclRequest::new(&parts.method, &parts.uri, body);
+        let allowed = auth_context.principals.iter().any(|princ| {
+            self.config
+                .auth
+                .acls
+                .allows(&princ.as_identifier(), &acl_request)
+        });
+
+        if !allowed && self.config.auth.acl_dry_run {
+            tracing::warn!(
+                principals = ?auth_context.principals.iter().map(Principal::as_identifier).collect::<Vec<_>>(),
+                method = %parts.method,
+                uri = %parts.uri,
+                "ACL dry-run: request would have been denied"
+            );
+            return true;
+        }
+
+        allowed
     }
 }
 
@@ -135,11 +154,14 @@ pub async fn start(
         .await
         .map_err(BmcProxyError::Listen)?;
 
+    let limiter = Arc::new(BmcLimiter::new(config.bmc_limits.clone(), &meter));
+
     let state = BmcProxyState {
         config,
         pg_pool,
         credential_manager,
         meter,
+        limiter,
     };
 
     let app = Router::new()
@@ -451,10 +473,15 @@ async fn proxy_request(
     State(state): State<BmcProxyState>,
     request: Request<Body>,
 ) -> Result<Response<Body>, Response<Body>> {
-    if !state.allows(&request) {
+    let (parts, body) = request.into_parts();
+    let body = axum::body::to_bytes(body, MAX_BODY_SIZE)
+        .await
+        .map_err(|e| error_response((StatusCode::BAD_REQUEST, e.to_string()).into()))?;
+
+    if !state.allows(&parts, &body) {
         return Ok(error_response((StatusCode::FORBIDDEN, "Forbidden").into()));
     }
-    let (parts, body) = request.into_parts();
+
     let target_ip = forwarded_host_ip(&parts.headers)
         .ok_or_else(|| {
             error_response(
@@ -467,26 +494,102 @@ async fn proxy_request(
         })?
         .map_err(|e| error_response((StatusCode::BAD_REQUEST, e.to_string()).into()))?;
 
-    let bmc_mac_address = db::machine_interface::find_by_ip(&state.pg_pool, target_ip)
-        .await
-        .map_err(|e| error_response((StatusCode::BAD_GATEWAY, e.to_string()).into()))?
-        .ok_or_else(|| {
-            error_response(
-                (
-                    StatusCode::BAD_REQUEST,
-                    format!("Unknown BMC IP address: {target_ip}"),
-                )
-                    .into(),
-            )
-        })?
-        .mac_address;
-
     let path_and_query = parts
         .uri
         .into_parts()
         .path_and_query
         .ok_or_else(|| error_response((StatusCode::BAD_REQUEST, "missing path").into()))?;
 
+    let request_key = request_key(&parts.extensions, &parts.headers, &path_and_query);
+    if let Some(cached) = state.limiter.cached(target_ip, &parts.method, &request_key) {
+        return Ok(build_response(cached.status, &cached.headers, cached.body));
+    }
+
+    let upstream = {
+        let state = state.clone();
+        let method = parts.method.clone();
+        let path_and_query = path_and_query.clone();
+        move || {
+            send_upstream(
+                state,
+                target_ip,
+                method,
+                parts.headers,
+                path_and_query,
+                body,
+            )
+        }
+    };
+
+    let upstream_response = state
+        .limiter
+        .send(target_ip, &parts.method, request_key, upstream)
+        .await
+        .map_err(error_response)?;
+
+    Ok(build_response(
+        upstream_response.status,
+        &upstream_response.headers,
+        upstream_response.body,
+    ))
+}
+
+/// The key under which a request may share a response with other requests: the same path, from
+/// the same caller, with the same [`RESPONSE_AFFECTING_HEADERS`].
+fn request_key(
+    extensions: &http::Extensions,
+    headers: &HeaderMap,
+    path_and_query: &PathAndQuery,
+) -> RequestKey {
+    let mut principals: Vec<String> = extensions
+        .get::<AuthContext<()>>()
+        .map(|auth_context| {
+            auth_context
+                .principals
+                .iter()
+                .map(Principal::as_identifier)
+                .collect()
+        })
+        .unwrap_or_default();
+    principals.sort();
+
+    let headers = RESPONSE_AFFECTING_HEADERS
+        .iter()
+        .flat_map(|name| {
+            headers
+                .get_all(name)
+                .iter()
+                .map(|value| (name.clone(), value.clone()))
+        })
+        .collect();
+
+    RequestKey {
+        path_and_query: path_and_query.to_string(),
+        principals,
+        headers,
+    }
+}
+
+/// Sends a request to the BMC at `target_ip`, authenticating with the BMC's credentials.
+async fn send_upstream(
+    state: BmcProxyState,
+    target_ip: IpAddr,
+    method: Method,
+    headers: HeaderMap,
+    path_and_query: PathAndQuery,
+    body: Bytes,
+) -> Result<UpstreamResponse, ProxyError> {
+    let bmc_mac_address = db::machine_interface::find_by_ip(&state.pg_pool, target_ip)
+        .await
+        .map_err(|e| ProxyError::from((StatusCode::BAD_GATEWAY, e.to_string())))?
+        .ok_or_else(|| {
+            ProxyError::from((
+                StatusCode::BAD_REQUEST,
+                format!("Unknown BMC IP address: {target_ip}"),
+            ))
+        })?
+        .mac_address;
+
     let mut bmc_client_info = create_client(
         target_ip,
         bmc_mac_address,
@@ -494,44 +597,44 @@ async fn proxy_request(
         &state.config.bmc_proxy,
     )
     .await
-    .map_err(|e| error_response((StatusCode::BAD_GATEWAY, e.to_string()).into()))?;
+    .map_err(|e| ProxyError::from((StatusCode::BAD_GATEWAY, e.to_string())))?;
 
-    copy_request_headers(&parts.headers, &mut bmc_client_info.header_map);
-
-    let body = axum::body::to_bytes(body, MAX_BODY_SIZE)
-        .await
-        .map_err(|e| error_response((StatusCode::BAD_REQUEST, e.to_string()).into()))?;
+    copy_request_headers(&headers, &mut bmc_client_info.header_map);
 
     let Credentials::UsernamePassword { username, password } = bmc_client_info.credentials;
 
     let mut upstream_uri_parts = bmc_client_info.base_upstream_uri.into_parts();
     upstream_uri_parts.path_and_query = Some(path_and_query);
     let upstream_uri = Uri::from_parts(upstream_uri_parts)
-        .map_err(|e| error_response((StatusCode::BAD_REQUEST, e.to_string()).into()))?;
+        .map_err(|e| ProxyError::from((StatusCode::BAD_REQUEST, e.to_string())))?;
 
     let mut upstream_request = bmc_client_info
         .http_client
-        .request(parts.method.clone(), upstream_uri.to_string())
+        .request(method.clone(), upstream_uri.to_string())
         .basic_auth(username, Some(password))
         .headers(bmc_client_info.header_map);
 
-    if method_supports_body(&parts.method) {
+    if method_supports_body(&method) {
         upstream_request = upstream_request.body(body);
     }
 
     let upstream_response = upstream_request
         .send()
         .await
-        .map_err(|e| error_response((StatusCode::BAD_GATEWAY, e.to_string()).into()))?;
+        .map_err(|e| ProxyError::from((StatusCode::BAD_GATEWAY, e.to_string())))?;
 
     let status = upstream_response.status();
     let headers = upstream_response.headers().clone();
     let body = upstream_response
         .bytes()
         .await
-        .map_err(|e| error_response((StatusCode::BAD_GATEWAY, e.to_string()).into()))?;
+        .map_err(|e| ProxyError::from((StatusCode::BAD_GATEWAY, e.to_string())))?;
 
-    Ok(build_response(status, &headers, body))
+    Ok(UpstreamResponse {
+        status,
+        headers,
+        body,
+    })
 }
 
 async fn authorize_proxy_request(
@@ -667,9 +770,10 @@ fn error_response(error: ProxyError) -> Response<Body> {
     (error.status, error.message).into_response()
 }
 
-struct ProxyError {
-    status: StatusCode,
-    message: String,
+#[derive(Clone, Debug)]
+pub(crate) struct ProxyError {
+    pub(crate) status: StatusCode,
+    pub(crate) message: String,
 }
 
 impl From<(StatusCode, String)> for ProxyError {
@@ -26,6 +26,7 @@ use figment::providers::{Env, Format, Toml};
 use serde::{Deserialize, Serialize};
 
 use crate::acl::AclConfig;
+use crate::bmc_limiter::BmcLimitsConfig;
 
 #[derive(thiserror::Error, Debug)]
 pub enum ConfigError {
@@ -57,6 +58,8 @@ pub struct Config {
     pub tls: TlsConfig,
     pub auth: AuthConfig,
     pub bmc_proxy: Option<HostPortPair>,
+    #[serde(default)]
+    pub bmc_limits: BmcLimitsConfig,
 }
 
 struct Defaults;
@@ -120,6 +123,10 @@ pub struct AuthConfig {
 
     #[serde(default)]
     pub acls: AclConfig,
+
+    /// If true, requests which `acls` deny are logged, but still allowed.
+    #[serde(default)]
+    pub acl_dry_run: bool,
 }
 
 impl Config {
@@ -18,6 +18,7 @@ use std::io;
 use std::sync::Arc;
 
 mod acl;
+mod bmc_limiter;
 mod bmc_proxy;
 mod config;
 mod metrics;
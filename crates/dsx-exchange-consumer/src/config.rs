@@ -112,6 +112,10 @@ pub struct MqttConfig {
     /// Messages are dropped when this limit is exceeded.
     pub queue_capacity: usize,
 
+    /// Connect using MQTT v5 instead of MQTT 3.1.1, which enables
+    /// message properties and request/response exchanges.
+    pub mqtt_v5: bool,
+
     #[serde(default)]
     pub auth: MqttAuthConfig,
 }
@@ -124,6 +128,7 @@ impl Default for MqttConfig {
             client_id: "carbide-dsx-exchange-consumer".to_string(),
             topic_prefix: "BMS/v1".to_string(),
             queue_capacity: 1024,
+            mqtt_v5: false,
             auth: MqttAuthConfig::default(),
         }
     }
@@ -21,7 +21,7 @@ use std::sync::Arc;
 
 use forge_secrets::credentials::CredentialReader;
 use mqttea::QoS;
-use mqttea::client::{ClientOptions, MqtteaClient};
+use mqttea::client::{ClientOptions, MqtteaClient, ProtocolVersion};
 use mqttea::registry::JsonRegistration;
 use tokio::sync::mpsc;
 
@@ -56,7 +56,10 @@ pub async fn connect(
     // QoS 0 is the recommended setting for DSX Exchange integrations.
     // BMS will republish all messages periodically to handle missed messages.
     let options = {
-        let defaults = ClientOptions::default().with_qos(QoS::AtMostOnce);
+        let mut defaults = ClientOptions::default().with_qos(QoS::AtMostOnce);
+        if config.mqtt_v5 {
+            defaults = defaults.with_protocol_version(ProtocolVersion::V5);
+        }
         if let Some(provider) =
             build_credentials_provider(config, credential_reader.clone()).await?
         {
@@ -15,6 +15,8 @@
  * limitations under the License.
  */
 
+use std::net::SocketAddr;
+
 use clap::Parser;
 
 #[derive(Parser)]
@@ -63,6 +65,17 @@ pub struct Options {
         default_value = "169.254.169.254/30"
     )]
     pub interface_cidr: String,
+
+    /// gRPC listen address for the SPIFFE Workload API served to tenant
+    /// workloads. The Workload API is disabled when unset, and it also
+    /// requires the TLS client credentials used to reach carbide-api.
+    #[clap(long, env = "FMDS_SPIFFE_WORKLOAD_API_ADDRESS")]
+    pub spiffe_workload_api_address: Option<SocketAddr>,
+
+    /// Lifetime in seconds of X.509-SVIDs issued to tenant workloads.
+    /// They are rotated at half-life.
+    #[clap(long, default_value = "3600")]
+    pub spiffe_x509_svid_ttl_secs: u64,
 }
 
 impl Options {
@@ -20,4 +20,5 @@ pub mod grpc_server;
 pub mod nic_init;
 pub mod phone_home;
 pub mod rest_server;
+pub mod spiffe;
 pub mod state;
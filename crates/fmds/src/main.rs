@@ -16,6 +16,7 @@
  */
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use axum::extract::Request;
 use clap::Parser;
@@ -23,10 +24,12 @@ use fmds::cfg::Options;
 use fmds::grpc_server::FmdsGrpcServer;
 use fmds::nic_init;
 use fmds::rest_server::get_fmds_router;
+use fmds::spiffe::{ForgeSpiffeUpstream, SvidManager, SvidOptions, WorkloadApiServer};
 use fmds::state::FmdsState;
 use forge_tls::client_config::ClientCert;
 use rpc::fmds::fmds_config_service_server::FmdsConfigServiceServer;
 use rpc::forge_tls_client::ForgeClientConfig;
+use rpc::spiffe_workload::spiffe_workload_api_server::SpiffeWorkloadApiServer;
 use tower_http::trace::{DefaultOnRequest, DefaultOnResponse, TraceLayer};
 use tracing::Level;
 use tracing::metadata::LevelFilter;
@@ -35,6 +38,9 @@ use tracing_subscriber::layer::SubscriberExt as _;
 use tracing_subscriber::prelude::*;
 use tracing_subscriber::util::SubscriberInitExt;
 
+/// Delay before retrying a failed SPIFFE SVID rotation.
+const SPIFFE_RETRY_INTERVAL: Duration = Duration::from_secs(10);
+
 pub fn subscriber() -> impl SubscriberInitExt {
     let env_filter = EnvFilter::builder()
         .with_default_directive(LevelFilter::INFO.into())
@@ -95,6 +101,36 @@ async fn main() -> eyre::Result<()> {
         }
     };
 
+    // Start the SPIFFE Workload API for tenant workloads if configured
+    match (options.spiffe_workload_api_address, &forge_client_config) {
+        (Some(addr), Some(forge_client_config)) => {
+            let manager = Arc::new(SvidManager::new(
+                ForgeSpiffeUpstream::new(options.forge_api.clone(), forge_client_config.clone()),
+                SvidOptions {
+                    x509_svid_ttl: Duration::from_secs(options.spiffe_x509_svid_ttl_secs),
+                    retry_interval: SPIFFE_RETRY_INTERVAL,
+                },
+            ));
+            tokio::spawn(manager.clone().run());
+            tokio::spawn(async move {
+                tracing::info!(%addr, "SPIFFE Workload API listening");
+                if let Err(err) = tonic::transport::Server::builder()
+                    .add_service(SpiffeWorkloadApiServer::new(WorkloadApiServer::new(
+                        manager,
+                    )))
+                    .serve(addr)
+                    .await
+                {
+                    tracing::error!("SPIFFE Workload API server error: {err}");
+                }
+            });
+        }
+        (Some(_), None) => {
+            tracing::warn!("No TLS credentials provided; SPIFFE Workload API will be unavailable");
+        }
+        (None, _) => {}
+    }
+
     let state = Arc::new(FmdsState::new(
         options.forge_api.clone(),
         forge_client_config,
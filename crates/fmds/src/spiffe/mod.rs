@@ -0,0 +1,139 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! SPIFFE Workload API for tenant workloads on the host.
+//!
+//! JWT-SVIDs are signed by carbide-api (`SignMachineIdentity`) and cached per audience set.
+//! X.509-SVIDs are issued locally from a per-machine intermediate CA whose key never leaves FMDS;
+//! carbide-api signs the intermediate (`SignMachineX509Intermediate`) with the tenant root.
+//! [`SvidManager`] rotates both before expiry and publishes updates to the streaming RPCs served
+//! by [`WorkloadApiServer`].
+
+mod svid;
+mod workload_api;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use forge_dpu_agent_utils::utils::create_forge_client;
+use rpc::forge::{
+    MachineIdentityRequest, MachineIdentityTrustBundleRequest, MachineX509IntermediateRequest,
+};
+use rpc::forge_tls_client::ForgeClientConfig;
+pub use svid::{SvidManager, SvidOptions, X509Svid};
+pub use workload_api::WorkloadApiServer;
+
+/// JWT-SVID as returned by carbide-api.
+#[derive(Clone, Debug)]
+pub struct JwtSvid {
+    pub token: String,
+    pub expires_in: Duration,
+}
+
+/// SPIFFE ID of this machine and the JWT trust bundle of its tenant. The X.509 authorities come
+/// with the signed intermediate instead.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrustBundle {
+    pub spiffe_id: String,
+    pub trust_domain_id: String,
+    /// JWKS document for validating JWT-SVIDs.
+    pub jwks: String,
+}
+
+/// Intermediate CA certificate signed by carbide-api for the key in the submitted CSR.
+#[derive(Clone, Debug)]
+pub struct SignedIntermediate {
+    pub certificate: Vec<u8>,
+    pub x509_authorities: Vec<Vec<u8>>,
+    pub spiffe_id: String,
+    pub trust_domain_id: String,
+    pub expires_in: Duration,
+}
+
+/// Source of identity material; carbide-api in production.
+#[async_trait]
+pub trait SpiffeUpstream: Send + Sync + 'static {
+    async fn sign_jwt_svid(&self, audience: &[String]) -> eyre::Result<JwtSvid>;
+    async fn trust_bundle(&self) -> eyre::Result<TrustBundle>;
+    async fn sign_intermediate(&self, csr_der: Vec<u8>) -> eyre::Result<SignedIntermediate>;
+}
+
+/// [`SpiffeUpstream`] backed by the carbide-api machine identity RPCs, authenticated with the
+/// DPU client certificate.
+pub struct ForgeSpiffeUpstream {
+    forge_api: String,
+    forge_client_config: Arc<ForgeClientConfig>,
+}
+
+impl ForgeSpiffeUpstream {
+    pub fn new(forge_api: String, forge_client_config: Arc<ForgeClientConfig>) -> Self {
+        Self {
+            forge_api,
+            forge_client_config,
+        }
+    }
+}
+
+#[async_trait]
+impl SpiffeUpstream for ForgeSpiffeUpstream {
+    async fn sign_jwt_svid(&self, audience: &[String]) -> eyre::Result<JwtSvid> {
+        let mut client = create_forge_client(&self.forge_api, &self.forge_client_config).await?;
+        let response = client
+            .sign_machine_identity(tonic::Request::new(MachineIdentityRequest {
+                audience: audience.to_vec(),
+            }))
+            .await?
+            .into_inner();
+        Ok(JwtSvid {
+            token: response.access_token,
+            expires_in: Duration::from_secs(response.expires_in_sec.into()),
+        })
+    }
+
+    async fn trust_bundle(&self) -> eyre::Result<TrustBundle> {
+        let mut client = create_forge_client(&self.forge_api, &self.forge_client_config).await?;
+        let response = client
+            .get_machine_identity_trust_bundle(tonic::Request::new(
+                MachineIdentityTrustBundleRequest {},
+            ))
+            .await?
+            .into_inner();
+        Ok(TrustBundle {
+            spiffe_id: response.spiffe_id,
+            trust_domain_id: response.trust_domain_id,
+            jwks: response.jwks,
+        })
+    }
+
+    async fn sign_intermediate(&self, csr_der: Vec<u8>) -> eyre::Result<SignedIntermediate> {
+        let mut client = create_forge_client(&self.forge_api, &self.forge_client_config).await?;
+        let response = client
+            .sign_machine_x509_intermediate(tonic::Request::new(MachineX509IntermediateRequest {
+                csr: csr_der,
+            }))
+            .await?
+            .into_inner();
+        Ok(SignedIntermediate {
+            certificate: response.certificate,
+            x509_authorities: response.x509_authorities,
+            spiffe_id: response.spiffe_id,
+            trust_domain_id: response.trust_domain_id,
+            expires_in: Duration::from_secs(response.expires_in_sec.into()),
+        })
+    }
+}
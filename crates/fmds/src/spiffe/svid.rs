@@ -0,0 +1,423 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use eyre::eyre;
+use rcgen::string::Ia5String;
+use rcgen::{
+    Certificate, CertificateParams, DistinguishedName, DnType, ExtendedKeyUsagePurpose, IsCa,
+    KeyPair, KeyUsagePurpose, SanType,
+};
+use time::OffsetDateTime;
+use tokio::sync::watch;
+
+use super::{SpiffeUpstream, TrustBundle};
+
+/// Tolerated clock skew between the DPU and workloads when back-dating `notBefore`.
+const NOT_BEFORE_SKEW: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Debug)]
+pub struct SvidOptions {
+    /// Lifetime of issued X.509-SVIDs. They are rotated at half-life.
+    pub x509_svid_ttl: Duration,
+    /// Delay before retrying a failed rotation.
+    pub retry_interval: Duration,
+}
+
+/// X.509-SVID with its private key, as served by `FetchX509SVID`.
+#[derive(Debug)]
+pub struct X509Svid {
+    pub spiffe_id: String,
+    pub trust_domain_id: String,
+    /// DER encoded leaf certificate followed by the intermediate.
+    pub chain: Vec<u8>,
+    /// DER encoded PKCS#8 private key of the leaf.
+    pub private_key: Vec<u8>,
+    /// DER encoded root certificates, concatenated.
+    pub bundle: Vec<u8>,
+}
+
+/// Locally held intermediate CA signed by carbide-api.
+pub(super) struct Intermediate {
+    key: KeyPair,
+    issuer: Certificate,
+    der: Vec<u8>,
+    spiffe_id: String,
+    trust_domain_id: String,
+    bundle: Vec<u8>,
+    expires_at: Instant,
+}
+
+struct CachedJwt {
+    token: String,
+    refresh_at: Instant,
+    expires_at: Instant,
+}
+
+/// Fetches, caches, and rotates the SVIDs served by the Workload API.
+pub struct SvidManager<U> {
+    upstream: U,
+    options: SvidOptions,
+    jwt_cache: Mutex<HashMap<Vec<String>, CachedJwt>>,
+    x509: watch::Sender<Option<Arc<X509Svid>>>,
+    bundle: watch::Sender<Option<Arc<TrustBundle>>>,
+}
+
+impl<U: SpiffeUpstream> SvidManager<U> {
+    pub fn new(upstream: U, options: SvidOptions) -> Self {
+        Self {
+            upstream,
+            options,
+            jwt_cache: Mutex::new(HashMap::new()),
+            x509: watch::Sender::new(None),
+            bundle: watch::Sender::new(None),
+        }
+    }
+
+    pub fn subscribe_x509(&self) -> watch::Receiver<Option<Arc<X509Svid>>> {
+        self.x509.subscribe()
+    }
+
+    pub fn subscribe_bundle(&self) -> watch::Receiver<Option<Arc<TrustBundle>>> {
+        self.bundle.subscribe()
+    }
+
+    pub fn current_bundle(&self) -> Option<Arc<TrustBundle>> {
+        self.bundle.borrow().clone()
+    }
+
+    /// Returns a JWT-SVID for the audience set, fetching a new one once the cached token is past
+    /// half of its lifetime. A still valid cached token is returned if the refresh fails.
+    pub async fn jwt_svid(&self, audience: &[String]) -> eyre::Result<String> {
+        let mut key = audience.to_vec();
+        key.sort();
+        key.dedup();
+
+        let now = Instant::now();
+        let cached = self
+            .jwt_cache
+            .lock()
+            .unwrap()
+            .get(&key)
+            .filter(|cached| cached.expires_at > now)
+            .map(|cached| (cached.token.clone(), cached.refresh_at));
+        if let Some((token, refresh_at)) = &cached
+            && *refresh_at > now
+        {
+            return Ok(token.clone());
+        }
+
+        let svid = match self.upstream.sign_jwt_svid(&key).await {
+            Ok(svid) => svid,
+            Err(e) => {
+                return match cached {
+                    Some((token, _)) => {
+                        tracing::warn!(error = %e, "JWT-SVID refresh failed; serving cached token");
+                        Ok(token)
+                    }
+                    None => Err(e),
+                };
+            }
+        };
+
+        let now = Instant::now();
+        let mut cache = self.jwt_cache.lock().unwrap();
+        cache.retain(|_, cached| cached.expires_at > now);
+        cache.insert(
+            key,
+            CachedJwt {
+                token: svid.token.clone(),
+                refresh_at: now + svid.expires_in / 2,
+                expires_at: now + svid.expires_in,
+            },
+        );
+        Ok(svid.token)
+    }
+
+    /// Keeps the trust bundle and X.509-SVID fresh until the task is dropped.
+    pub async fn run(self: Arc<Self>) {
+        let mut intermediate = None;
+        loop {
+            let delay = match self.rotate(&mut intermediate).await {
+                Ok(refresh_in) => refresh_in,
+                Err(e) => {
+                    tracing::warn!(error = %e, "SPIFFE SVID rotation failed");
+                    self.options.retry_interval
+                }
+            };
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Refreshes the trust bundle, renews the intermediate when it would expire within two SVID
+    /// lifetimes, and issues a new X.509-SVID. Returns when the next rotation is due.
+    pub(super) async fn rotate(
+        &self,
+        intermediate: &mut Option<Intermediate>,
+    ) -> eyre::Result<Duration> {
+        let bundle = self.upstream.trust_bundle().await?;
+        self.bundle.send_if_modified(|current| {
+            if current.as_deref() == Some(&bundle) {
+                return false;
+            }
+            *current = Some(Arc::new(bundle));
+            true
+        });
+
+        let now = Instant::now();
+        let renew = intermediate.as_ref().is_none_or(|intermediate| {
+            intermediate.expires_at.saturating_duration_since(now) < self.options.x509_svid_ttl * 2
+        });
+        if renew {
+            *intermediate = Some(self.new_intermediate().await?);
+        }
+        let Some(intermediate) = intermediate.as_ref() else {
+            return Err(eyre!("no intermediate CA available"));
+        };
+
+        let ttl = self
+            .options
+            .x509_svid_ttl
+            .min(intermediate.expires_at.saturating_duration_since(now));
+        let svid = issue_x509_svid(intermediate, ttl)?;
+        self.x509.send_replace(Some(Arc::new(svid)));
+        tracing::info!(
+            spiffe_id = %intermediate.spiffe_id,
+            ttl_sec = ttl.as_secs(),
+            "Issued X.509-SVID"
+        );
+
+        Ok((ttl / 2).max(self.options.retry_interval))
+    }
+
+    async fn new_intermediate(&self) -> eyre::Result<Intermediate> {
+        let key = KeyPair::generate()?;
+        let csr = CertificateParams::default().serialize_request(&key)?;
+        let requested_at = Instant::now();
+        let signed = self.upstream.sign_intermediate(csr.der().to_vec()).await?;
+
+        // rcgen signs with an issuer `Certificate`; rebuild one carrying the intermediate's
+        // subject from the DER returned by carbide-api.
+        let issuer = CertificateParams::from_ca_cert_der(&signed.certificate.clone().into())?
+            .self_signed(&key)?;
+
+        tracing::info!(
+            spiffe_id = %signed.spiffe_id,
+            expires_in_sec = signed.expires_in.as_secs(),
+            "Renewed X.509-SVID intermediate CA"
+        );
+
+        Ok(Intermediate {
+            key,
+            issuer,
+            der: signed.certificate,
+            spiffe_id: signed.spiffe_id,
+            trust_domain_id: signed.trust_domain_id,
+            bundle: signed.x509_authorities.concat(),
+            expires_at: requested_at + signed.expires_in,
+        })
+    }
+}
+
+fn issue_x509_svid(intermediate: &Intermediate, ttl: Duration) -> eyre::Result<X509Svid> {
+    let key = KeyPair::generate()?;
+    let now = OffsetDateTime::now_utc();
+
+    let mut params = CertificateParams::default();
+    params.distinguished_name = DistinguishedName::new();
+    params
+        .distinguished_name
+        .push(DnType::OrganizationName, "SPIFFE");
+    params.subject_alt_names = vec![SanType::URI(Ia5String::try_from(
+        intermediate.spiffe_id.as_str(),
+    )?)];
+    params.is_ca = IsCa::ExplicitNoCa;
+    params.key_usages = vec![
+        KeyUsagePurpose::DigitalSignature,
+        KeyUsagePurpose::KeyAgreement,
+    ];
+    params.extended_key_usages = vec![
+        ExtendedKeyUsagePurpose::ServerAuth,
+        ExtendedKeyUsagePurpose::ClientAuth,
+    ];
+    params.use_authority_key_identifier_extension = true;
+    params.not_before = now - NOT_BEFORE_SKEW;
+    params.not_after = now + ttl;
+
+    let leaf = params.signed_by(&key, &intermediate.issuer, &intermediate.key)?;
+
+    Ok(X509Svid {
+        spiffe_id: intermediate.spiffe_id.clone(),
+        trust_domain_id: intermediate.trust_domain_id.clone(),
+        chain: [leaf.der().as_ref(), intermediate.der.as_slice()].concat(),
+        private_key: key.serialize_der(),
+        bundle: intermediate.bundle.clone(),
+    })
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use async_trait::async_trait;
+    use rcgen::{BasicConstraints, CertificateSigningRequestParams};
+
+    use super::*;
+    use crate::spiffe::{JwtSvid, SignedIntermediate};
+
+    pub(crate) const SPIFFE_ID: &str = "spiffe://td.example/tenant/machine";
+    pub(crate) const TRUST_DOMAIN_ID: &str = "spiffe://td.example";
+
+    pub(crate) struct FakeUpstream {
+        root_key: KeyPair,
+        root: Certificate,
+        pub jwt_calls: AtomicUsize,
+        pub intermediate_calls: AtomicUsize,
+        pub jwks: String,
+    }
+
+    impl FakeUpstream {
+        pub(crate) fn new() -> Self {
+            let root_key = KeyPair::generate().unwrap();
+            let mut params = CertificateParams::default();
+            params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+            let root = params.self_signed(&root_key).unwrap();
+            Self {
+                root_key,
+                root,
+                jwt_calls: AtomicUsize::new(0),
+                intermediate_calls: AtomicUsize::new(0),
+                jwks: r#"{"keys":[]}"#.to_string(),
+            }
+        }
+
+        pub(crate) fn root_der(&self) -> Vec<u8> {
+            self.root.der().to_vec()
+        }
+    }
+
+    #[async_trait]
+    impl SpiffeUpstream for FakeUpstream {
+        async fn sign_jwt_svid(&self, audience: &[String]) -> eyre::Result<JwtSvid> {
+            let call = self.jwt_calls.fetch_add(1, Ordering::SeqCst);
+            Ok(JwtSvid {
+                token: format!("{}-{call}", audience.join(",")),
+                expires_in: Duration::from_secs(3600),
+            })
+        }
+
+        async fn trust_bundle(&self) -> eyre::Result<TrustBundle> {
+            Ok(TrustBundle {
+                spiffe_id: SPIFFE_ID.to_string(),
+                trust_domain_id: TRUST_DOMAIN_ID.to_string(),
+                jwks: self.jwks.clone(),
+            })
+        }
+
+        async fn sign_intermediate(&self, csr_der: Vec<u8>) -> eyre::Result<SignedIntermediate> {
+            self.intermediate_calls.fetch_add(1, Ordering::SeqCst);
+            let mut csr = CertificateSigningRequestParams::from_der(&csr_der.into())?;
+            csr.params.is_ca = IsCa::Ca(BasicConstraints::Constrained(0));
+            let certificate = csr.signed_by(&self.root, &self.root_key)?;
+            Ok(SignedIntermediate {
+                certificate: certificate.der().to_vec(),
+                x509_authorities: vec![self.root_der()],
+                spiffe_id: SPIFFE_ID.to_string(),
+                trust_domain_id: TRUST_DOMAIN_ID.to_string(),
+                expires_in: Duration::from_secs(86400),
+            })
+        }
+    }
+
+    pub(crate) fn options() -> SvidOptions {
+        SvidOptions {
+            x509_svid_ttl: Duration::from_secs(3600),
+            retry_interval: Duration::from_secs(10),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_jwt_svid_cached_per_audience_set() {
+        let manager = SvidManager::new(FakeUpstream::new(), options());
+
+        let first = manager
+            .jwt_svid(&["b".to_string(), "a".to_string()])
+            .await
+            .unwrap();
+        let second = manager
+            .jwt_svid(&["a".to_string(), "b".to_string(), "a".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(first, "a,b-0");
+        assert_eq!(first, second);
+
+        let other = manager.jwt_svid(&["c".to_string()]).await.unwrap();
+        assert_eq!(other, "c-1");
+        assert_eq!(manager.upstream.jwt_calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_rotate_publishes_x509_svid_and_bundle() {
+        let manager = SvidManager::new(FakeUpstream::new(), options());
+        let mut x509 = manager.subscribe_x509();
+        assert!(x509.borrow().is_none());
+
+        let mut intermediate = None;
+        let refresh_in = manager.rotate(&mut intermediate).await.unwrap();
+        assert_eq!(refresh_in, Duration::from_secs(1800));
+
+        assert!(x509.has_changed().unwrap());
+        let svid = x509.borrow_and_update().clone().unwrap();
+        assert_eq!(svid.spiffe_id, SPIFFE_ID);
+        assert_eq!(svid.bundle, manager.upstream.root_der());
+        let intermediate_der = &intermediate.as_ref().unwrap().der;
+        assert!(svid.chain.ends_with(intermediate_der));
+        assert!(svid.chain.len() > intermediate_der.len());
+
+        let bundle = manager.current_bundle().unwrap();
+        assert_eq!(bundle.trust_domain_id, TRUST_DOMAIN_ID);
+    }
+
+    #[tokio::test]
+    async fn test_rotate_reuses_intermediate_until_near_expiry() {
+        let manager = SvidManager::new(FakeUpstream::new(), options());
+        let mut bundle = manager.subscribe_bundle();
+
+        let mut intermediate = None;
+        manager.rotate(&mut intermediate).await.unwrap();
+        assert!(bundle.has_changed().unwrap());
+        bundle.mark_unchanged();
+
+        manager.rotate(&mut intermediate).await.unwrap();
+        assert_eq!(
+            manager.upstream.intermediate_calls.load(Ordering::SeqCst),
+            1
+        );
+        // Unchanged trust bundles are not re-published.
+        assert!(!bundle.has_changed().unwrap());
+
+        intermediate.as_mut().unwrap().expires_at = Instant::now() + Duration::from_secs(60);
+        manager.rotate(&mut intermediate).await.unwrap();
+        assert_eq!(
+            manager.upstream.intermediate_calls.load(Ordering::SeqCst),
+            2
+        );
+    }
+}
@@ -0,0 +1,463 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use eyre::{bail, eyre};
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use rpc::spiffe_workload::spiffe_workload_api_server::SpiffeWorkloadApi;
+use rpc::spiffe_workload::{
+    JwtBundlesRequest, JwtBundlesResponse, Jwtsvid, JwtsvidRequest, JwtsvidResponse,
+    ValidateJwtsvidRequest, ValidateJwtsvidResponse, X509BundlesRequest, X509BundlesResponse,
+    X509svid, X509svidRequest, X509svidResponse,
+};
+use serde_json::{Map, Value};
+use tokio_stream::wrappers::WatchStream;
+use tokio_stream::{Stream, StreamExt};
+use tonic::{Request, Response, Status};
+
+use super::{SpiffeUpstream, SvidManager, TrustBundle};
+
+/// Metadata header every Workload API request must carry, so that a workload cannot be tricked
+/// into forwarding requests (e.g. via SSRF) to the API.
+const SECURITY_HEADER: &str = "workload.spiffe.io";
+
+type ResponseStream<T> = Pin<Box<dyn Stream<Item = Result<T, Status>> + Send>>;
+
+pub struct WorkloadApiServer<U> {
+    manager: Arc<SvidManager<U>>,
+}
+
+impl<U> WorkloadApiServer<U> {
+    pub fn new(manager: Arc<SvidManager<U>>) -> Self {
+        Self { manager }
+    }
+}
+
+fn check_security_header<T>(request: &Request<T>) -> Result<(), Status> {
+    match request
+        .metadata()
+        .get(SECURITY_HEADER)
+        .and_then(|value| value.to_str().ok())
+    {
+        Some("true") => Ok(()),
+        _ => Err(Status::invalid_argument(
+            "security header missing from request",
+        )),
+    }
+}
+
+#[tonic::async_trait]
+impl<U: SpiffeUpstream> SpiffeWorkloadApi for WorkloadApiServer<U> {
+    async fn fetch_jwtsvid(
+        &self,
+        request: Request<JwtsvidRequest>,
+    ) -> Result<Response<JwtsvidResponse>, Status> {
+        check_security_header(&request)?;
+        let request = request.into_inner();
+        if request.audience.is_empty() {
+            return Err(Status::invalid_argument("audience must be specified"));
+        }
+
+        let bundle = self
+            .manager
+            .current_bundle()
+            .ok_or_else(|| Status::unavailable("workload identity is not available yet"))?;
+        if !request.spiffe_id.is_empty() && request.spiffe_id != bundle.spiffe_id {
+            return Err(Status::permission_denied(format!(
+                "workload is not entitled to {}",
+                request.spiffe_id
+            )));
+        }
+
+        let token = self
+            .manager
+            .jwt_svid(&request.audience)
+            .await
+            .map_err(|e| {
+                tracing::warn!(error = %e, "Failed to fetch JWT-SVID");
+                Status::unavailable(format!("could not fetch JWT-SVID: {e}"))
+            })?;
+
+        Ok(Response::new(JwtsvidResponse {
+            svids: vec![Jwtsvid {
+                spiffe_id: bundle.spiffe_id.clone(),
+                svid: token,
+                hint: String::new(),
+            }],
+        }))
+    }
+
+    type FetchJWTBundlesStream = ResponseStream<JwtBundlesResponse>;
+
+    async fn fetch_jwt_bundles(
+        &self,
+        request: Request<JwtBundlesRequest>,
+    ) -> Result<Response<Self::FetchJWTBundlesStream>, Status> {
+        check_security_header(&request)?;
+        let stream = WatchStream::new(self.manager.subscribe_bundle()).filter_map(|bundle| {
+            bundle.map(|bundle| {
+                Ok(JwtBundlesResponse {
+                    bundles: HashMap::from([(
+                        bundle.trust_domain_id.clone(),
+                        bundle.jwks.clone().into_bytes(),
+                    )]),
+                })
+            })
+        });
+        Ok(Response::new(Box::pin(stream)))
+    }
+
+    async fn validate_jwtsvid(
+        &self,
+        request: Request<ValidateJwtsvidRequest>,
+    ) -> Result<Response<ValidateJwtsvidResponse>, Status> {
+        check_security_header(&request)?;
+        let request = request.into_inner();
+        if request.audience.is_empty() {
+            return Err(Status::invalid_argument("audience must be specified"));
+        }
+        if request.svid.is_empty() {
+            return Err(Status::invalid_argument("svid must be specified"));
+        }
+
+        let bundle = self
+            .manager
+            .current_bundle()
+            .ok_or_else(|| Status::unavailable("trust bundle is not available yet"))?;
+        let (spiffe_id, claims) = validate_jwt_svid(&bundle, &request.svid, &request.audience)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        Ok(Response::new(ValidateJwtsvidResponse {
+            spiffe_id,
+            claims: Some(to_prost_struct(claims)),
+        }))
+    }
+
+    type FetchX509SVIDStream = ResponseStream<X509svidResponse>;
+
+    async fn fetch_x509svid(
+        &self,
+        request: Request<X509svidRequest>,
+    ) -> Result<Response<Self::FetchX509SVIDStream>, Status> {
+        check_security_header(&request)?;
+        let stream = WatchStream::new(self.manager.subscribe_x509()).filter_map(|svid| {
+            svid.map(|svid| {
+                Ok(X509svidResponse {
+                    svids: vec![X509svid {
+                        spiffe_id: svid.spiffe_id.clone(),
+                        x509_svid: svid.chain.clone(),
+                        x509_svid_key: svid.private_key.clone(),
+                        bundle: svid.bundle.clone(),
+                        hint: String::new(),
+                    }],
+                    crl: Vec::new(),
+                    federated_bundles: HashMap::new(),
+                })
+            })
+        });
+        Ok(Response::new(Box::pin(stream)))
+    }
+
+    type FetchX509BundlesStream = ResponseStream<X509BundlesResponse>;
+
+    async fn fetch_x509_bundles(
+        &self,
+        request: Request<X509BundlesRequest>,
+    ) -> Result<Response<Self::FetchX509BundlesStream>, Status> {
+        check_security_header(&request)?;
+        let stream = WatchStream::new(self.manager.subscribe_x509()).filter_map(|svid| {
+            svid.map(|svid| {
+                Ok(X509BundlesResponse {
+                    crl: Vec::new(),
+                    bundles: HashMap::from([(svid.trust_domain_id.clone(), svid.bundle.clone())]),
+                })
+            })
+        });
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+/// Verifies a JWT-SVID against the trust bundle and audience, returning its SPIFFE ID and claims.
+/// Tenant signing keys are always ES256.
+fn validate_jwt_svid(
+    bundle: &TrustBundle,
+    token: &str,
+    audience: &str,
+) -> eyre::Result<(String, Map<String, Value>)> {
+    let header = jsonwebtoken::decode_header(token)?;
+    // Other tokens signed with the tenant key, like media sanitization reports, set their own type
+    if let Some(typ) = header.typ.as_deref()
+        && !matches!(typ, "JWT" | "JOSE")
+    {
+        bail!("token type {typ:?} is not a JWT-SVID");
+    }
+    let kid = header
+        .kid
+        .as_deref()
+        .ok_or_else(|| eyre!("JWT-SVID has no key ID"))?;
+    let jwks: JwkSet = serde_json::from_str(&bundle.jwks)?;
+    let jwk = jwks
+        .find(kid)
+        .ok_or_else(|| eyre!("key ID {kid:?} is not in the trust bundle"))?;
+
+    let mut validation = Validation::new(Algorithm::ES256);
+    validation.set_audience(&[audience]);
+    validation.set_required_spec_claims(&["sub", "aud", "exp"]);
+    let claims = jsonwebtoken::decode::<Map<String, Value>>(
+        token,
+        &DecodingKey::from_jwk(jwk)?,
+        &validation,
+    )?
+    .claims;
+
+    let spiffe_id = claims
+        .get("sub")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    if !spiffe_id.starts_with(&format!("{}/", bundle.trust_domain_id)) {
+        bail!(
+            "subject {spiffe_id:?} is not in trust domain {}",
+            bundle.trust_domain_id
+        );
+    }
+    Ok((spiffe_id, claims))
+}
+
+fn to_prost_struct(map: Map<String, Value>) -> prost_types::Struct {
+    prost_types::Struct {
+        fields: map
+            .into_iter()
+            .map(|(key, value)| (key, to_prost_value(value)))
+            .collect(),
+    }
+}
+
+fn to_prost_value(value: Value) -> prost_types::Value {
+    use prost_types::value::Kind;
+
+    let kind = match value {
+        Value::Null => Kind::NullValue(prost_types::NullValue::NullValue.into()),
+        Value::Bool(value) => Kind::BoolValue(value),
+        Value::Number(value) => Kind::NumberValue(value.as_f64().unwrap_or_default()),
+        Value::String(value) => Kind::StringValue(value),
+        Value::Array(values) => Kind::ListValue(prost_types::ListValue {
+            values: values.into_iter().map(to_prost_value).collect(),
+        }),
+        Value::Object(map) => Kind::StructValue(to_prost_struct(map)),
+    };
+    prost_types::Value { kind: Some(kind) }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use jsonwebtoken::{EncodingKey, Header};
+    use rcgen::KeyPair;
+    use serde_json::json;
+
+    use super::*;
+    use crate::spiffe::svid::tests::{FakeUpstream, SPIFFE_ID, TRUST_DOMAIN_ID, options};
+
+    fn with_security_header<T>(message: T) -> Request<T> {
+        let mut request = Request::new(message);
+        request
+            .metadata_mut()
+            .insert(SECURITY_HEADER, "true".parse().unwrap());
+        request
+    }
+
+    /// Test signing key plus the JWKS publishing its public half under `kid-1`.
+    fn signing_key() -> (EncodingKey, String) {
+        let key = KeyPair::generate().unwrap();
+        // Uncompressed SEC1 point: 0x04 || x || y.
+        let point = key.public_key_raw();
+        let b64 = |bytes: &[u8]| {
+            use base64::Engine;
+            base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes)
+        };
+        let jwks = json!({
+            "keys": [{
+                "kty": "EC",
+                "use": "jwt-svid",
+                "crv": "P-256",
+                "kid": "kid-1",
+                "alg": "ES256",
+                "x": b64(&point[1..33]),
+                "y": b64(&point[33..65]),
+            }]
+        });
+        (
+            EncodingKey::from_ec_der(&key.serialize_der()),
+            jwks.to_string(),
+        )
+    }
+
+    fn sign(key: &EncodingKey, claims: Value) -> String {
+        sign_with_typ(key, claims, "JWT")
+    }
+
+    fn sign_with_typ(key: &EncodingKey, claims: Value, typ: &str) -> String {
+        let mut header = Header::new(Algorithm::ES256);
+        header.kid = Some("kid-1".to_string());
+        header.typ = Some(typ.to_string());
+        jsonwebtoken::encode(&header, &claims, key).unwrap()
+    }
+
+    fn bundle(jwks: String) -> TrustBundle {
+        TrustBundle {
+            spiffe_id: SPIFFE_ID.to_string(),
+            trust_domain_id: TRUST_DOMAIN_ID.to_string(),
+            jwks,
+        }
+    }
+
+    fn exp() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            + 300
+    }
+
+    #[test]
+    fn test_validate_jwt_svid() {
+        let (key, jwks) = signing_key();
+        let bundle = bundle(jwks);
+        let token = sign(
+            &key,
+            json!({ "sub": SPIFFE_ID, "aud": "svc", "exp": exp(), "team": "a" }),
+        );
+
+        let (spiffe_id, claims) = validate_jwt_svid(&bundle, &token, "svc").unwrap();
+        assert_eq!(spiffe_id, SPIFFE_ID);
+        assert_eq!(claims["team"], "a");
+        assert!(validate_jwt_svid(&bundle, &token, "other").is_err());
+    }
+
+    #[test]
+    fn test_validate_jwt_svid_rejects_foreign_trust_domain() {
+        let (key, jwks) = signing_key();
+        let token = sign(
+            &key,
+            json!({ "sub": "spiffe://other.example/x", "aud": "svc", "exp": exp() }),
+        );
+        let err = validate_jwt_svid(&bundle(jwks), &token, "svc").unwrap_err();
+        assert!(err.to_string().contains("not in trust domain"));
+    }
+
+    #[test]
+    fn test_validate_jwt_svid_rejects_media_sanitization_report() {
+        let (key, jwks) = signing_key();
+        let token = sign_with_typ(
+            &key,
+            json!({ "sub": SPIFFE_ID, "aud": "svc", "exp": exp() }),
+            "media-sanitization-report+jwt",
+        );
+        let err = validate_jwt_svid(&bundle(jwks), &token, "svc").unwrap_err();
+        assert!(err.to_string().contains("is not a JWT-SVID"));
+    }
+
+    #[test]
+    fn test_to_prost_struct_converts_nested_values() {
+        let claims = json!({ "aud": ["a", "b"], "n": 1, "meta": { "ok": true } });
+        let Value::Object(claims) = claims else {
+            unreachable!()
+        };
+        let converted = to_prost_struct(claims);
+        let Some(prost_types::value::Kind::ListValue(aud)) = &converted.fields["aud"].kind else {
+            panic!("aud should be a list");
+        };
+        assert_eq!(aud.values.len(), 2);
+        assert_eq!(
+            converted.fields["n"].kind,
+            Some(prost_types::value::Kind::NumberValue(1.0))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_requests_require_security_header() {
+        let manager = Arc::new(SvidManager::new(FakeUpstream::new(), options()));
+        let server = WorkloadApiServer::new(manager);
+
+        let err = server
+            .fetch_jwtsvid(Request::new(JwtsvidRequest {
+                audience: vec!["svc".to_string()],
+                spiffe_id: String::new(),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_jwtsvid() {
+        let manager = Arc::new(SvidManager::new(FakeUpstream::new(), options()));
+        let server = WorkloadApiServer::new(manager.clone());
+
+        let request = || {
+            with_security_header(JwtsvidRequest {
+                audience: vec!["svc".to_string()],
+                spiffe_id: String::new(),
+            })
+        };
+        // No trust bundle fetched yet.
+        let err = server.fetch_jwtsvid(request()).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::Unavailable);
+
+        manager.rotate(&mut None).await.unwrap();
+        let response = server.fetch_jwtsvid(request()).await.unwrap().into_inner();
+        assert_eq!(response.svids.len(), 1);
+        assert_eq!(response.svids[0].spiffe_id, SPIFFE_ID);
+        assert_eq!(response.svids[0].svid, "svc-0");
+
+        let err = server
+            .fetch_jwtsvid(with_security_header(JwtsvidRequest {
+                audience: vec!["svc".to_string()],
+                spiffe_id: "spiffe://td.example/someone-else".to_string(),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::PermissionDenied);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_x509svid_streams_current_svid() {
+        let manager = Arc::new(SvidManager::new(FakeUpstream::new(), options()));
+        manager.rotate(&mut None).await.unwrap();
+        let server = WorkloadApiServer::new(manager.clone());
+
+        let mut stream = server
+            .fetch_x509svid(with_security_header(X509svidRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+        let response = stream.next().await.unwrap().unwrap();
+        assert_eq!(response.svids.len(), 1);
+        assert_eq!(response.svids[0].spiffe_id, SPIFFE_ID);
+        assert!(!response.svids[0].x509_svid_key.is_empty());
+
+        manager.rotate(&mut None).await.unwrap();
+        let rotated = stream.next().await.unwrap().unwrap();
+        assert_ne!(rotated.svids[0].x509_svid, response.svids[0].x509_svid);
+    }
+}
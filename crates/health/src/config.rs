@@ -322,6 +322,9 @@ pub struct ProcessorsConfig {
 
     /// Rack-level leak processor: aggregates tray leak reports per rack.
     pub rack_leak: Configurable<RackLeakProcessorConfig>,
+
+    /// User-defined expression rules evaluated over collector events.
+    pub expression_rules: Configurable<ExpressionRulesProcessorConfig>,
 }
 
 impl Default for ProcessorsConfig {
@@ -329,6 +332,7 @@ impl Default for ProcessorsConfig {
         Self {
             leak_detection: Configurable::Enabled(LeakDetectionProcessorConfig::default()),
             rack_leak: Configurable::Enabled(RackLeakProcessorConfig::default()),
+            expression_rules: Configurable::Disabled,
         }
     }
 }
@@ -364,6 +368,156 @@ impl Default for RackLeakProcessorConfig {
     }
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExpressionRulesProcessorConfig {
+    /// Rules evaluated against every matching collector event.
+    pub rules: Vec<ExpressionRuleConfig>,
+}
+
+impl ExpressionRulesProcessorConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        let mut names = std::collections::HashSet::new();
+        for rule in &self.rules {
+            if rule.name.is_empty() {
+                return Err("processors.expression_rules: rule name must not be empty".to_string());
+            }
+            if !names.insert(rule.name.as_str()) {
+                return Err(format!(
+                    "processors.expression_rules: duplicate rule name '{}'",
+                    rule.name
+                ));
+            }
+            if rule.probe_id.is_empty() {
+                return Err(format!(
+                    "processors.expression_rules: rule '{}' must set probe_id",
+                    rule.name
+                ));
+            }
+            if rule.classifications.iter().any(String::is_empty) {
+                return Err(format!(
+                    "processors.expression_rules: rule '{}' has an empty classification",
+                    rule.name
+                ));
+            }
+            rule.window
+                .validate()
+                .map_err(|e| format!("processors.expression_rules: rule '{}': {e}", rule.name))?;
+            // Log lines of unrelated events share one series, so any window
+            // that treats a non-matching line as a negative observation would
+            // let them clear the alert.
+            if rule.event == RuleEventKind::Log
+                && !matches!(rule.window, RuleWindowConfig::Count { .. })
+            {
+                return Err(format!(
+                    "processors.expression_rules: log rule '{}' requires a count window",
+                    rule.name
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Event kind a rule is evaluated against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleEventKind {
+    Metric,
+    Log,
+    Firmware,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExpressionRuleConfig {
+    /// Unique rule name, used in logs and default alert messages.
+    pub name: String,
+
+    /// Event kind the condition is evaluated against.
+    pub event: RuleEventKind,
+
+    /// Boolean expression evaluated for every event of the configured kind.
+    pub condition: String,
+
+    /// Probe ID reported on alerts raised by this rule.
+    pub probe_id: String,
+
+    /// Classifications attached to alerts raised by this rule.
+    #[serde(default)]
+    pub classifications: Vec<String>,
+
+    /// Alert message; `{field}` placeholders are replaced with event fields.
+    #[serde(default)]
+    pub message: Option<String>,
+
+    /// Windowing applied to condition results before an alert is raised.
+    #[serde(default)]
+    pub window: RuleWindowConfig,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RuleWindowConfig {
+    /// Fire whenever the latest observation matches.
+    #[default]
+    Instant,
+
+    /// Fire when at least `n` of the last `m` observations match.
+    NOfM { n: usize, m: usize },
+
+    /// Fire once the condition has matched continuously for `duration`.
+    Sustained {
+        #[serde(with = "humantime_serde")]
+        duration: Duration,
+    },
+
+    /// Fire when the reading of matching samples changes by at least
+    /// `threshold` per `per` (one second by default).
+    RateOfChange {
+        threshold: f64,
+        #[serde(with = "humantime_serde", default = "default_rate_period")]
+        per: Duration,
+    },
+
+    /// Fire while at least `n` matching events were observed within the last
+    /// `within`. Only matching events are counted, so the alert clears once
+    /// they age out of the window rather than on events that do not match.
+    /// The only window allowed for log rules.
+    Count {
+        n: usize,
+        #[serde(with = "humantime_serde")]
+        within: Duration,
+    },
+}
+
+fn default_rate_period() -> Duration {
+    Duration::from_secs(1)
+}
+
+impl RuleWindowConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            Self::Instant => Ok(()),
+            Self::NOfM { n, m } if *n == 0 || n > m => Err(format!(
+                "n_of_m window requires 0 < n <= m (n = {n}, m = {m})"
+            )),
+            Self::NOfM { .. } => Ok(()),
+            Self::Sustained { duration } if duration.is_zero() => {
+                Err("sustained window duration must be greater than 0".to_string())
+            }
+            Self::Sustained { .. } => Ok(()),
+            Self::RateOfChange { threshold, per } if *threshold <= 0.0 || per.is_zero() => {
+                Err("rate_of_change window requires a positive threshold and period".to_string())
+            }
+            Self::RateOfChange { .. } => Ok(()),
+            Self::Count { n, within } if *n == 0 || within.is_zero() => {
+                Err("count window requires a positive n and duration".to_string())
+            }
+            Self::Count { .. } => Ok(()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct SensorCollectorConfig {
@@ -644,6 +798,10 @@ impl Config {
             );
         }
 
+        if let Configurable::Enabled(expression_rules) = &self.processors.expression_rules {
+            expression_rules.validate()?;
+        }
+
         if let Configurable::Enabled(health_report) = &self.sinks.health_report
             && health_report.workers == 0
         {
@@ -1245,4 +1403,114 @@ switch_serial = "SN-SW-001"
         assert!(config.periodic.is_none());
         assert!(config.validate().is_ok());
     }
+
+    #[test]
+    fn test_expression_rules_config_parsing() {
+        let toml = r#"
+            [[rules]]
+            name = "inlet-temperature"
+            event = "metric"
+            condition = "name == 'Inlet Temp' && value > 45"
+            probe_id = "InletTemperature"
+            classifications = ["SensorWarning"]
+            window = { type = "n_of_m", n = 3, m = 5 }
+
+            [[rules]]
+            name = "psu-flapping"
+            event = "log"
+            condition = "body.contains('PSU')"
+            probe_id = "PsuEvents"
+            message = "PSU event on {endpoint}: {body}"
+            window = { type = "count", n = 3, within = "5m" }
+
+            [[rules]]
+            name = "temperature-ramp"
+            event = "metric"
+            condition = "metric_type == 'temperature'"
+            probe_id = "TemperatureRamp"
+            window = { type = "rate_of_change", threshold = 5.0, per = "1m" }
+        "#;
+        let config: ExpressionRulesProcessorConfig = Figment::new()
+            .merge(Toml::string(toml))
+            .extract()
+            .expect("should parse");
+
+        assert_eq!(config.rules.len(), 3);
+        assert_eq!(config.rules[0].event, RuleEventKind::Metric);
+        assert_eq!(
+            config.rules[0].window,
+            RuleWindowConfig::NOfM { n: 3, m: 5 }
+        );
+        assert_eq!(config.rules[1].event, RuleEventKind::Log);
+        assert_eq!(
+            config.rules[1].window,
+            RuleWindowConfig::Count {
+                n: 3,
+                within: Duration::from_secs(300)
+            }
+        );
+        assert_eq!(
+            config.rules[2].window,
+            RuleWindowConfig::RateOfChange {
+                threshold: 5.0,
+                per: Duration::from_secs(60)
+            }
+        );
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_expression_rules_config_validation() {
+        let rule = ExpressionRuleConfig {
+            name: "rule".to_string(),
+            event: RuleEventKind::Metric,
+            condition: "value > 1".to_string(),
+            probe_id: "Probe".to_string(),
+            classifications: vec![],
+            message: None,
+            window: RuleWindowConfig::NOfM { n: 4, m: 3 },
+        };
+        let config = ExpressionRulesProcessorConfig {
+            rules: vec![rule.clone()],
+        };
+        assert!(config.validate().is_err());
+
+        let config = ExpressionRulesProcessorConfig {
+            rules: vec![
+                ExpressionRuleConfig {
+                    window: RuleWindowConfig::Instant,
+                    ..rule.clone()
+                },
+                ExpressionRuleConfig {
+                    window: RuleWindowConfig::Instant,
+                    ..rule.clone()
+                },
+            ],
+        };
+        assert!(config.validate().is_err());
+
+        let log_rule = |window| ExpressionRulesProcessorConfig {
+            rules: vec![ExpressionRuleConfig {
+                event: RuleEventKind::Log,
+                window,
+                ..rule.clone()
+            }],
+        };
+        assert!(
+            log_rule(RuleWindowConfig::Sustained {
+                duration: Duration::from_secs(60)
+            })
+            .validate()
+            .is_err()
+        );
+        assert!(log_rule(RuleWindowConfig::Instant).validate().is_err());
+        assert!(
+            log_rule(RuleWindowConfig::Count {
+                n: 2,
+                within: Duration::from_secs(60)
+            })
+            .validate()
+            .is_ok()
+        );
+    }
 }
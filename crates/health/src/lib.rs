@@ -42,8 +42,8 @@ use crate::endpoint::{CompositeEndpointSource, EndpointSource, StaticEndpointSou
 use crate::limiter::{BucketLimiter, NoopLimiter, RateLimiter};
 use crate::metrics::{MetricsManager, run_metrics_server};
 use crate::processor::{
-    EventProcessingPipeline, EventProcessor, HealthReportProcessor, LeakEventProcessor,
-    RackLeakProcessor,
+    EventProcessingPipeline, EventProcessor, ExpressionRuleProcessor, HealthReportProcessor,
+    LeakEventProcessor, RackLeakProcessor,
 };
 use crate::sharding::ShardManager;
 use crate::sink::event_mapper::{OpenBmcEventMapper, RedfishEventMapper};
@@ -177,6 +177,13 @@ fn build_data_sink(
         )));
     }
 
+    if let Configurable::Enabled(ref expression_rules_cfg) = config.processors.expression_rules {
+        processors.push(Arc::new(
+            ExpressionRuleProcessor::new(expression_rules_cfg)
+                .map_err(HealthError::GenericError)?,
+        ));
+    }
+
     if let Configurable::Enabled(ref sink_cfg) = config.sinks.log_file {
         sinks.push(Arc::new(
             LogFileSink::new(sink_cfg).map_err(HealthError::GenericError)?,
@@ -0,0 +1,858 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! A small CEL-like expression language used by rule conditions.
+//!
+//! Supported syntax:
+//! - literals: numbers, `'single'`/`"double"` quoted strings, `true`, `false`, `null`
+//! - field access: `value`, `labels.chassis`, `sensor.upper_critical`
+//! - operators: `||`, `&&`, `!`, `==`, `!=`, `<`, `<=`, `>`, `>=`, `+`, `-`, `*`, `/`, `%`
+//! - membership: `severity in ['Critical', 'Fatal']`
+//! - functions: `has(field)`, `abs(x)`, `min(a, b)`, `max(a, b)`
+//! - methods: `s.contains(x)`, `s.startsWith(x)`, `s.endsWith(x)`, `s.lowerAscii()`,
+//!   `s.upperAscii()`, `s.size()`, `s.matches('regex')`
+//!
+//! Referencing a field that is not bound for the current event is an evaluation
+//! error; use `has(field)` to guard optional fields.
+
+use std::fmt;
+
+use regex::Regex;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+}
+
+impl Value {
+    fn type_name(&self) -> &'static str {
+        match self {
+            Self::Null => "null",
+            Self::Bool(_) => "bool",
+            Self::Number(_) => "number",
+            Self::String(_) => "string",
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Null => write!(f, "null"),
+            Self::Bool(v) => write!(f, "{v}"),
+            Self::Number(v) => write!(f, "{v}"),
+            Self::String(v) => write!(f, "{v}"),
+        }
+    }
+}
+
+/// Resolves dotted field paths (e.g. `labels.chassis`) to values.
+pub trait Bindings {
+    fn resolve(&self, path: &str) -> Option<Value>;
+}
+
+#[derive(Debug, thiserror::Error, PartialEq)]
+pub enum ExprError {
+    #[error("parse error at offset {position}: {message}")]
+    Parse { position: usize, message: String },
+
+    #[error("evaluation error: {0}")]
+    Eval(String),
+}
+
+fn eval_error(message: impl Into<String>) -> ExprError {
+    ExprError::Eval(message.into())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BinaryOp {
+    Or,
+    And,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum UnaryOp {
+    Not,
+    Neg,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Method {
+    Contains,
+    StartsWith,
+    EndsWith,
+    LowerAscii,
+    UpperAscii,
+    Size,
+}
+
+impl Method {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "contains" => Some(Self::Contains),
+            "startsWith" => Some(Self::StartsWith),
+            "endsWith" => Some(Self::EndsWith),
+            "lowerAscii" => Some(Self::LowerAscii),
+            "upperAscii" => Some(Self::UpperAscii),
+            "size" => Some(Self::Size),
+            _ => None,
+        }
+    }
+
+    fn arity(self) -> usize {
+        match self {
+            Self::Contains | Self::StartsWith | Self::EndsWith => 1,
+            Self::LowerAscii | Self::UpperAscii | Self::Size => 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Function {
+    Abs,
+    Min,
+    Max,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "abs" => Some(Self::Abs),
+            "min" => Some(Self::Min),
+            "max" => Some(Self::Max),
+            _ => None,
+        }
+    }
+
+    fn arity(self) -> usize {
+        match self {
+            Self::Abs => 1,
+            Self::Min | Self::Max => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Literal(Value),
+    Field(String),
+    Has(String),
+    Unary(UnaryOp, Box<Node>),
+    Binary(BinaryOp, Box<Node>, Box<Node>),
+    In(Box<Node>, Vec<Node>),
+    Method(Method, Box<Node>, Vec<Node>),
+    Matches(Box<Node>, Regex),
+    Call(Function, Vec<Node>),
+}
+
+/// A parsed expression, ready to be evaluated against event bindings.
+#[derive(Debug, Clone)]
+pub struct Expression {
+    source: String,
+    root: Node,
+}
+
+impl Expression {
+    pub fn parse(source: &str) -> Result<Self, ExprError> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser {
+            tokens,
+            position: 0,
+        };
+        let root = parser.parse_or()?;
+        if let Some(token) = parser.peek() {
+            return Err(ExprError::Parse {
+                position: token.offset,
+                message: format!("unexpected token {:?}", token.kind),
+            });
+        }
+
+        Ok(Self {
+            source: source.to_string(),
+            root,
+        })
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn evaluate(&self, bindings: &dyn Bindings) -> Result<Value, ExprError> {
+        eval(&self.root, bindings)
+    }
+
+    /// Evaluates the expression and requires a boolean result.
+    pub fn evaluate_bool(&self, bindings: &dyn Bindings) -> Result<bool, ExprError> {
+        match self.evaluate(bindings)? {
+            Value::Bool(result) => Ok(result),
+            other => Err(eval_error(format!(
+                "expected bool result, got {}",
+                other.type_name()
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Number(f64),
+    String(String),
+    Ident(String),
+    Dot,
+    Comma,
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Not,
+    And,
+    Or,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Percent,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    offset: usize,
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, ExprError> {
+    let chars: Vec<(usize, char)> = source.char_indices().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let (offset, c) = chars[i];
+        let next = chars.get(i + 1).map(|(_, c)| *c);
+
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        let (kind, width) = match (c, next) {
+            ('&', Some('&')) => (TokenKind::And, 2),
+            ('|', Some('|')) => (TokenKind::Or, 2),
+            ('=', Some('=')) => (TokenKind::Eq, 2),
+            ('!', Some('=')) => (TokenKind::Ne, 2),
+            ('<', Some('=')) => (TokenKind::Le, 2),
+            ('>', Some('=')) => (TokenKind::Ge, 2),
+            ('!', _) => (TokenKind::Not, 1),
+            ('<', _) => (TokenKind::Lt, 1),
+            ('>', _) => (TokenKind::Gt, 1),
+            ('+', _) => (TokenKind::Plus, 1),
+            ('-', _) => (TokenKind::Minus, 1),
+            ('*', _) => (TokenKind::Star, 1),
+            ('/', _) => (TokenKind::Slash, 1),
+            ('%', _) => (TokenKind::Percent, 1),
+            ('.', _) => (TokenKind::Dot, 1),
+            (',', _) => (TokenKind::Comma, 1),
+            ('(', _) => (TokenKind::LParen, 1),
+            (')', _) => (TokenKind::RParen, 1),
+            ('[', _) => (TokenKind::LBracket, 1),
+            (']', _) => (TokenKind::RBracket, 1),
+            ('\'' | '"', _) => {
+                let mut value = String::new();
+                let mut j = i + 1;
+                loop {
+                    let Some(&(_, ch)) = chars.get(j) else {
+                        return Err(ExprError::Parse {
+                            position: offset,
+                            message: "unterminated string literal".to_string(),
+                        });
+                    };
+                    if ch == c {
+                        break;
+                    }
+                    if ch == '\\' {
+                        j += 1;
+                        match chars.get(j).map(|(_, ch)| *ch) {
+                            Some('n') => value.push('\n'),
+                            Some('t') => value.push('\t'),
+                            Some(escaped) => value.push(escaped),
+                            None => {
+                                return Err(ExprError::Parse {
+                                    position: offset,
+                                    message: "unterminated string literal".to_string(),
+                                });
+                            }
+                        }
+                    } else {
+                        value.push(ch);
+                    }
+                    j += 1;
+                }
+                (TokenKind::String(value), j + 1 - i)
+            }
+            (c, _) if c.is_ascii_digit() => {
+                let mut j = i;
+                while chars
+                    .get(j)
+                    .is_some_and(|(_, ch)| ch.is_ascii_digit() || *ch == '.')
+                {
+                    j += 1;
+                }
+                let end = chars.get(j).map_or(source.len(), |(o, _)| *o);
+                let literal = &source[offset..end];
+                let number = literal.parse::<f64>().map_err(|_| ExprError::Parse {
+                    position: offset,
+                    message: format!("invalid number '{literal}'"),
+                })?;
+                (TokenKind::Number(number), j - i)
+            }
+            (c, _) if c.is_alphabetic() || c == '_' => {
+                let mut j = i;
+                while chars
+                    .get(j)
+                    .is_some_and(|(_, ch)| ch.is_alphanumeric() || *ch == '_')
+                {
+                    j += 1;
+                }
+                let end = chars.get(j).map_or(source.len(), |(o, _)| *o);
+                (TokenKind::Ident(source[offset..end].to_string()), j - i)
+            }
+            _ => {
+                return Err(ExprError::Parse {
+                    position: offset,
+                    message: format!("unexpected character '{c}'"),
+                });
+            }
+        };
+
+        tokens.push(Token { kind, offset });
+        i += width;
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn peek_kind(&self) -> Option<&TokenKind> {
+        self.peek().map(|token| &token.kind)
+    }
+
+    fn offset(&self) -> usize {
+        self.peek()
+            .or_else(|| self.tokens.last())
+            .map_or(0, |token| token.offset)
+    }
+
+    fn error<T>(&self, message: impl Into<String>) -> Result<T, ExprError> {
+        Err(ExprError::Parse {
+            position: self.offset(),
+            message: message.into(),
+        })
+    }
+
+    fn consume(&mut self, kind: &TokenKind) -> bool {
+        if self.peek_kind() == Some(kind) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, kind: TokenKind) -> Result<(), ExprError> {
+        if self.consume(&kind) {
+            Ok(())
+        } else {
+            self.error(format!("expected {kind:?}"))
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Node, ExprError> {
+        let mut node = self.parse_and()?;
+        while self.consume(&TokenKind::Or) {
+            let rhs = self.parse_and()?;
+            node = Node::Binary(BinaryOp::Or, Box::new(node), Box::new(rhs));
+        }
+        Ok(node)
+    }
+
+    fn parse_and(&mut self) -> Result<Node, ExprError> {
+        let mut node = self.parse_comparison()?;
+        while self.consume(&TokenKind::And) {
+            let rhs = self.parse_comparison()?;
+            node = Node::Binary(BinaryOp::And, Box::new(node), Box::new(rhs));
+        }
+        Ok(node)
+    }
+
+    fn parse_comparison(&mut self) -> Result<Node, ExprError> {
+        let lhs = self.parse_additive()?;
+
+        let op = match self.peek_kind() {
+            Some(TokenKind::Eq) => BinaryOp::Eq,
+            Some(TokenKind::Ne) => BinaryOp::Ne,
+            Some(TokenKind::Lt) => BinaryOp::Lt,
+            Some(TokenKind::Le) => BinaryOp::Le,
+            Some(TokenKind::Gt) => BinaryOp::Gt,
+            Some(TokenKind::Ge) => BinaryOp::Ge,
+            Some(TokenKind::Ident(ident)) if ident == "in" => {
+                self.position += 1;
+                self.expect(TokenKind::LBracket)?;
+                let items = self.parse_list(TokenKind::RBracket)?;
+                return Ok(Node::In(Box::new(lhs), items));
+            }
+            _ => return Ok(lhs),
+        };
+
+        self.position += 1;
+        let rhs = self.parse_additive()?;
+        Ok(Node::Binary(op, Box::new(lhs), Box::new(rhs)))
+    }
+
+    fn parse_additive(&mut self) -> Result<Node, ExprError> {
+        let mut node = self.parse_multiplicative()?;
+        loop {
+            let op = match self.peek_kind() {
+                Some(TokenKind::Plus) => BinaryOp::Add,
+                Some(TokenKind::Minus) => BinaryOp::Sub,
+                _ => return Ok(node),
+            };
+            self.position += 1;
+            let rhs = self.parse_multiplicative()?;
+            node = Node::Binary(op, Box::new(node), Box::new(rhs));
+        }
+    }
+
+    fn parse_multiplicative(&mut self) -> Result<Node, ExprError> {
+        let mut node = self.parse_unary()?;
+        loop {
+            let op = match self.peek_kind() {
+                Some(TokenKind::Star) => BinaryOp::Mul,
+                Some(TokenKind::Slash) => BinaryOp::Div,
+                Some(TokenKind::Percent) => BinaryOp::Rem,
+                _ => return Ok(node),
+            };
+            self.position += 1;
+            let rhs = self.parse_unary()?;
+            node = Node::Binary(op, Box::new(node), Box::new(rhs));
+        }
+    }
+
+    fn parse_unary(&mut self) -> Result<Node, ExprError> {
+        if self.consume(&TokenKind::Not) {
+            return Ok(Node::Unary(UnaryOp::Not, Box::new(self.parse_unary()?)));
+        }
+        if self.consume(&TokenKind::Minus) {
+            return Ok(Node::Unary(UnaryOp::Neg, Box::new(self.parse_unary()?)));
+        }
+        self.parse_postfix()
+    }
+
+    fn parse_postfix(&mut self) -> Result<Node, ExprError> {
+        let mut node = self.parse_primary()?;
+
+        while self.consume(&TokenKind::Dot) {
+            let Some(TokenKind::Ident(name)) = self.peek_kind().cloned() else {
+                return self.error("expected field or method name after '.'");
+            };
+            self.position += 1;
+
+            if !self.consume(&TokenKind::LParen) {
+                node = match node {
+                    Node::Field(path) => Node::Field(format!("{path}.{name}")),
+                    _ => return self.error(format!("cannot access field '{name}' here")),
+                };
+                continue;
+            }
+
+            let args = self.parse_list(TokenKind::RParen)?;
+
+            if name == "matches" {
+                let [Node::Literal(Value::String(pattern))] = args.as_slice() else {
+                    return self.error("matches() expects a single string literal pattern");
+                };
+                let regex = Regex::new(pattern).map_err(|e| ExprError::Parse {
+                    position: self.offset(),
+                    message: format!("invalid regex '{pattern}': {e}"),
+                })?;
+                node = Node::Matches(Box::new(node), regex);
+                continue;
+            }
+
+            let Some(method) = Method::from_name(&name) else {
+                return self.error(format!("unknown method '{name}'"));
+            };
+            if args.len() != method.arity() {
+                return self.error(format!(
+                    "{name}() expects {} argument(s), got {}",
+                    method.arity(),
+                    args.len()
+                ));
+            }
+            node = Node::Method(method, Box::new(node), args);
+        }
+
+        Ok(node)
+    }
+
+    fn parse_primary(&mut self) -> Result<Node, ExprError> {
+        let Some(token) = self.peek().cloned() else {
+            return self.error("unexpected end of expression");
+        };
+        self.position += 1;
+
+        match token.kind {
+            TokenKind::Number(number) => Ok(Node::Literal(Value::Number(number))),
+            TokenKind::String(value) => Ok(Node::Literal(Value::String(value))),
+            TokenKind::LParen => {
+                let node = self.parse_or()?;
+                self.expect(TokenKind::RParen)?;
+                Ok(node)
+            }
+            TokenKind::Ident(ident) => match ident.as_str() {
+                "true" => Ok(Node::Literal(Value::Bool(true))),
+                "false" => Ok(Node::Literal(Value::Bool(false))),
+                "null" => Ok(Node::Literal(Value::Null)),
+                _ if self.consume(&TokenKind::LParen) => self.parse_call(ident),
+                _ => Ok(Node::Field(ident)),
+            },
+            other => Err(ExprError::Parse {
+                position: token.offset,
+                message: format!("unexpected token {other:?}"),
+            }),
+        }
+    }
+
+    fn parse_call(&mut self, name: String) -> Result<Node, ExprError> {
+        let args = self.parse_list(TokenKind::RParen)?;
+
+        if name == "has" {
+            let [Node::Field(path)] = args.as_slice() else {
+                return self.error("has() expects a single field reference");
+            };
+            return Ok(Node::Has(path.clone()));
+        }
+
+        let Some(function) = Function::from_name(&name) else {
+            return self.error(format!("unknown function '{name}'"));
+        };
+        if args.len() != function.arity() {
+            return self.error(format!(
+                "{name}() expects {} argument(s), got {}",
+                function.arity(),
+                args.len()
+            ));
+        }
+        Ok(Node::Call(function, args))
+    }
+
+    fn parse_list(&mut self, close: TokenKind) -> Result<Vec<Node>, ExprError> {
+        let mut items = Vec::new();
+        if self.consume(&close) {
+            return Ok(items);
+        }
+        loop {
+            items.push(self.parse_or()?);
+            if self.consume(&close) {
+                return Ok(items);
+            }
+            self.expect(TokenKind::Comma)?;
+        }
+    }
+}
+
+fn eval(node: &Node, bindings: &dyn Bindings) -> Result<Value, ExprError> {
+    match node {
+        Node::Literal(value) => Ok(value.clone()),
+        Node::Field(path) => bindings
+            .resolve(path)
+            .ok_or_else(|| eval_error(format!("no such field '{path}'"))),
+        Node::Has(path) => Ok(Value::Bool(bindings.resolve(path).is_some())),
+        Node::Unary(op, operand) => match (op, eval(operand, bindings)?) {
+            (UnaryOp::Not, Value::Bool(v)) => Ok(Value::Bool(!v)),
+            (UnaryOp::Neg, Value::Number(v)) => Ok(Value::Number(-v)),
+            (op, other) => Err(eval_error(format!(
+                "cannot apply {op:?} to {}",
+                other.type_name()
+            ))),
+        },
+        Node::Binary(BinaryOp::Or, lhs, rhs) => {
+            if as_bool(eval(lhs, bindings)?)? {
+                return Ok(Value::Bool(true));
+            }
+            Ok(Value::Bool(as_bool(eval(rhs, bindings)?)?))
+        }
+        Node::Binary(BinaryOp::And, lhs, rhs) => {
+            if !as_bool(eval(lhs, bindings)?)? {
+                return Ok(Value::Bool(false));
+            }
+            Ok(Value::Bool(as_bool(eval(rhs, bindings)?)?))
+        }
+        Node::Binary(op, lhs, rhs) => binary(*op, eval(lhs, bindings)?, eval(rhs, bindings)?),
+        Node::In(needle, haystack) => {
+            let needle = eval(needle, bindings)?;
+            for item in haystack {
+                if eval(item, bindings)? == needle {
+                    return Ok(Value::Bool(true));
+                }
+            }
+            Ok(Value::Bool(false))
+        }
+        Node::Matches(target, regex) => {
+            let target = as_string(eval(target, bindings)?)?;
+            Ok(Value::Bool(regex.is_match(&target)))
+        }
+        Node::Method(method, target, args) => {
+            let target = as_string(eval(target, bindings)?)?;
+            let arg = match args.first() {
+                Some(arg) => Some(as_string(eval(arg, bindings)?)?),
+                None => None,
+            };
+            let arg = arg.as_deref().unwrap_or_default();
+            Ok(match method {
+                Method::Contains => Value::Bool(target.contains(arg)),
+                Method::StartsWith => Value::Bool(target.starts_with(arg)),
+                Method::EndsWith => Value::Bool(target.ends_with(arg)),
+                Method::LowerAscii => Value::String(target.to_ascii_lowercase()),
+                Method::UpperAscii => Value::String(target.to_ascii_uppercase()),
+                Method::Size => Value::Number(target.chars().count() as f64),
+            })
+        }
+        Node::Call(function, args) => {
+            let args = args
+                .iter()
+                .map(|arg| eval(arg, bindings).and_then(as_number))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Value::Number(match function {
+                Function::Abs => args[0].abs(),
+                Function::Min => args[0].min(args[1]),
+                Function::Max => args[0].max(args[1]),
+            }))
+        }
+    }
+}
+
+fn as_bool(value: Value) -> Result<bool, ExprError> {
+    match value {
+        Value::Bool(v) => Ok(v),
+        other => Err(eval_error(format!(
+            "expected bool, got {}",
+            other.type_name()
+        ))),
+    }
+}
+
+fn as_number(value: Value) -> Result<f64, ExprError> {
+    match value {
+        Value::Number(v) => Ok(v),
+        other => Err(eval_error(format!(
+            "expected number, got {}",
+            other.type_name()
+        ))),
+    }
+}
+
+fn as_string(value: Value) -> Result<String, ExprError> {
+    match value {
+        Value::String(v) => Ok(v),
+        other => Err(eval_error(format!(
+            "expected string, got {}",
+            other.type_name()
+        ))),
+    }
+}
+
+fn binary(op: BinaryOp, lhs: Value, rhs: Value) -> Result<Value, ExprError> {
+    match (op, lhs, rhs) {
+        (BinaryOp::Eq, lhs, rhs) => Ok(Value::Bool(lhs == rhs)),
+        (BinaryOp::Ne, lhs, rhs) => Ok(Value::Bool(lhs != rhs)),
+        (BinaryOp::Add, Value::String(lhs), Value::String(rhs)) => {
+            Ok(Value::String(lhs + rhs.as_str()))
+        }
+        (op, Value::Number(lhs), Value::Number(rhs)) => Ok(match op {
+            BinaryOp::Lt => Value::Bool(lhs < rhs),
+            BinaryOp::Le => Value::Bool(lhs <= rhs),
+            BinaryOp::Gt => Value::Bool(lhs > rhs),
+            BinaryOp::Ge => Value::Bool(lhs >= rhs),
+            BinaryOp::Add => Value::Number(lhs + rhs),
+            BinaryOp::Sub => Value::Number(lhs - rhs),
+            BinaryOp::Mul => Value::Number(lhs * rhs),
+            BinaryOp::Div if rhs == 0.0 => return Err(eval_error("division by zero")),
+            BinaryOp::Div => Value::Number(lhs / rhs),
+            BinaryOp::Rem if rhs == 0.0 => return Err(eval_error("division by zero")),
+            BinaryOp::Rem => Value::Number(lhs % rhs),
+            BinaryOp::Or | BinaryOp::And | BinaryOp::Eq | BinaryOp::Ne => {
+                unreachable!("handled before numeric dispatch")
+            }
+        }),
+        (
+            op @ (BinaryOp::Lt | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Ge),
+            Value::String(lhs),
+            Value::String(rhs),
+        ) => Ok(Value::Bool(match op {
+            BinaryOp::Lt => lhs < rhs,
+            BinaryOp::Le => lhs <= rhs,
+            BinaryOp::Gt => lhs > rhs,
+            _ => lhs >= rhs,
+        })),
+        (op, lhs, rhs) => Err(eval_error(format!(
+            "cannot apply {op:?} to {} and {}",
+            lhs.type_name(),
+            rhs.type_name()
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    struct MapBindings(HashMap<&'static str, Value>);
+
+    impl Bindings for MapBindings {
+        fn resolve(&self, path: &str) -> Option<Value> {
+            self.0.get(path).cloned()
+        }
+    }
+
+    fn bindings() -> MapBindings {
+        MapBindings(HashMap::from([
+            ("value", Value::Number(72.5)),
+            ("name", Value::String("Inlet Temp".to_string())),
+            ("severity", Value::String("Critical".to_string())),
+            ("labels.chassis", Value::String("GPU_Tray_1".to_string())),
+        ]))
+    }
+
+    fn eval_str(source: &str) -> Result<Value, ExprError> {
+        Expression::parse(source)?.evaluate(&bindings())
+    }
+
+    #[test]
+    fn test_arithmetic_and_comparison() {
+        assert_eq!(eval_str("value > 70"), Ok(Value::Bool(true)));
+        assert_eq!(eval_str("value * 2 - 5 == 140"), Ok(Value::Bool(true)));
+        assert_eq!(
+            eval_str("-value < 0 && !(value >= 80)"),
+            Ok(Value::Bool(true))
+        );
+        assert_eq!(eval_str("abs(60 - value) > 10"), Ok(Value::Bool(true)));
+        assert_eq!(eval_str("max(value, 100) % 7"), Ok(Value::Number(2.0)));
+    }
+
+    #[test]
+    fn test_strings_and_methods() {
+        assert_eq!(
+            eval_str("labels.chassis.startsWith('GPU') && name.lowerAscii().contains(\"inlet\")"),
+            Ok(Value::Bool(true))
+        );
+        assert_eq!(
+            eval_str("name.matches('^Inlet\\\\s')"),
+            Ok(Value::Bool(true))
+        );
+        assert_eq!(
+            eval_str("name + '!' == 'Inlet Temp!'"),
+            Ok(Value::Bool(true))
+        );
+        assert_eq!(eval_str("name.size()"), Ok(Value::Number(10.0)));
+    }
+
+    #[test]
+    fn test_membership_and_has() {
+        assert_eq!(
+            eval_str("severity in ['Critical', 'Fatal']"),
+            Ok(Value::Bool(true))
+        );
+        assert_eq!(eval_str("severity in []"), Ok(Value::Bool(false)));
+        assert_eq!(eval_str("has(labels.chassis)"), Ok(Value::Bool(true)));
+        assert_eq!(eval_str("has(labels.slot)"), Ok(Value::Bool(false)));
+    }
+
+    #[test]
+    fn test_short_circuit_guards_missing_fields() {
+        assert_eq!(
+            eval_str("has(labels.slot) && labels.slot == 'A'"),
+            Ok(Value::Bool(false))
+        );
+        assert!(matches!(
+            eval_str("labels.slot == 'A'"),
+            Err(ExprError::Eval(_))
+        ));
+    }
+
+    #[test]
+    fn test_type_errors() {
+        assert!(matches!(eval_str("name > 3"), Err(ExprError::Eval(_))));
+        assert!(matches!(eval_str("value / 0"), Err(ExprError::Eval(_))));
+        assert!(
+            Expression::parse("value")
+                .unwrap()
+                .evaluate_bool(&bindings())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for source in [
+            "value >",
+            "(value > 3",
+            "'unterminated",
+            "value > 3 3",
+            "unknown(1)",
+            "name.frobnicate()",
+            "name.matches(name)",
+            "name.matches('(')",
+            "abs(1, 2)",
+            "value # 3",
+        ] {
+            assert!(
+                matches!(Expression::parse(source), Err(ExprError::Parse { .. })),
+                "expected parse error for {source:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,657 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+mod expr;
+mod window;
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Instant;
+
+use dashmap::DashMap;
+use expr::{Bindings, Expression, Value};
+use window::WindowState;
+
+use super::{EventContext, EventProcessor};
+use crate::config::{ExpressionRulesProcessorConfig, RuleEventKind, RuleWindowConfig};
+use crate::metrics::MetricLabel;
+use crate::sink::{
+    Classification, CollectorEvent, FirmwareInfo, HealthReport, HealthReportAlert,
+    HealthReportSuccess, LogRecord, Probe, ReportSource, SensorHealthData,
+};
+
+struct CompiledRule {
+    name: String,
+    event: RuleEventKind,
+    condition: Expression,
+    window: RuleWindowConfig,
+    probe_id: Probe,
+    classifications: Vec<Classification>,
+    message: Option<String>,
+}
+
+struct SeriesState {
+    window: WindowState,
+    alert: Option<HealthReportAlert>,
+}
+
+#[derive(Default)]
+struct EndpointRuleState {
+    // Keyed by (rule index, series key) so reports are emitted in a stable order.
+    series: BTreeMap<(usize, String), SeriesState>,
+}
+
+/// Evaluates user-defined expression rules over collector events and emits
+/// an `ExpressionRules` health report per endpoint.
+///
+/// Metric rules are reported once per collection cycle, on
+/// `MetricCollectionEnd`. Log and firmware rules are reported as soon as the
+/// set of firing alerts changes.
+pub struct ExpressionRuleProcessor {
+    rules: Vec<CompiledRule>,
+    endpoints: DashMap<String, EndpointRuleState>,
+}
+
+// Probe IDs and classifications are plain `&'static str`s; rules are compiled
+// once at startup so leaking the configured names is bounded.
+fn intern(value: &str) -> &'static str {
+    Box::leak(value.to_owned().into_boxed_str())
+}
+
+impl ExpressionRuleProcessor {
+    pub fn new(config: &ExpressionRulesProcessorConfig) -> Result<Self, String> {
+        let rules = config
+            .rules
+            .iter()
+            .map(|rule| {
+                let condition = Expression::parse(&rule.condition).map_err(|e| {
+                    format!("invalid condition for expression rule '{}': {e}", rule.name)
+                })?;
+
+                Ok(CompiledRule {
+                    name: rule.name.clone(),
+                    event: rule.event,
+                    condition,
+                    window: rule.window.clone(),
+                    probe_id: Probe::Custom(intern(&rule.probe_id)),
+                    classifications: rule
+                        .classifications
+                        .iter()
+                        .map(|classification| Classification::Custom(intern(classification)))
+                        .collect(),
+                    message: rule.message.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(Self {
+            rules,
+            endpoints: DashMap::new(),
+        })
+    }
+
+    fn observe(&self, context: &EventContext, fields: EventFields<'_>, now: Instant) -> bool {
+        let bindings = EventBindings { context, fields };
+        let series_key = fields.series_key();
+        let mut changed = false;
+
+        let mut endpoint = self
+            .endpoints
+            .entry(context.endpoint_key().to_owned())
+            .or_default();
+
+        for (index, rule) in self.rules.iter().enumerate() {
+            if rule.event != fields.kind() {
+                continue;
+            }
+
+            let matched = match rule.condition.evaluate_bool(&bindings) {
+                Ok(matched) => matched,
+                Err(error) => {
+                    tracing::debug!(
+                        endpoint = %context.endpoint_key(),
+                        rule = %rule.name,
+                        %error,
+                        "Expression rule evaluation failed, treating as not matched"
+                    );
+                    false
+                }
+            };
+
+            let series = endpoint
+                .series
+                .entry((index, series_key.to_owned()))
+                .or_insert_with(|| SeriesState {
+                    window: WindowState::new(&rule.window),
+                    alert: None,
+                });
+
+            let firing = series
+                .window
+                .observe(&rule.window, matched, fields.value(), now);
+
+            if firing {
+                if series.alert.is_none() {
+                    changed = true;
+                }
+                series.alert = Some(HealthReportAlert {
+                    probe_id: rule.probe_id,
+                    target: fields.target(),
+                    message: render_message(rule, &bindings),
+                    classifications: rule.classifications.clone(),
+                });
+            } else if series.alert.take().is_some() {
+                changed = true;
+            }
+        }
+
+        changed
+    }
+
+    fn build_report(&self, context: &EventContext) -> HealthReport {
+        let mut alerts = Vec::new();
+        if let Some(endpoint) = self.endpoints.get(context.endpoint_key()) {
+            alerts.extend(
+                endpoint
+                    .series
+                    .values()
+                    .filter_map(|series| series.alert.clone()),
+            );
+        }
+
+        let mut successes: Vec<HealthReportSuccess> = Vec::new();
+        for rule in &self.rules {
+            let alerting = alerts.iter().any(|alert| alert.probe_id == rule.probe_id);
+            let reported = successes
+                .iter()
+                .any(|success| success.probe_id == rule.probe_id);
+            if !alerting && !reported {
+                successes.push(HealthReportSuccess {
+                    probe_id: rule.probe_id,
+                    target: None,
+                });
+            }
+        }
+
+        HealthReport {
+            source: ReportSource::ExpressionRules,
+            observed_at: Some(chrono::Utc::now()),
+            successes,
+            alerts,
+        }
+    }
+
+    fn has_rules_for(&self, kind: RuleEventKind) -> bool {
+        self.rules.iter().any(|rule| rule.event == kind)
+    }
+}
+
+fn render_message(rule: &CompiledRule, bindings: &EventBindings<'_>) -> String {
+    let Some(template) = rule.message.as_deref() else {
+        return format!(
+            "Expression rule '{}' triggered: {}",
+            rule.name,
+            rule.condition.source()
+        );
+    };
+
+    let mut message = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        message.push_str(&rest[..start]);
+        let placeholder = &rest[start + 1..];
+        match placeholder.find('}') {
+            Some(end) => {
+                let field = &placeholder[..end];
+                match bindings.resolve(field) {
+                    Some(value) => message.push_str(&value.to_string()),
+                    None => message.push_str(&rest[start..start + end + 2]),
+                }
+                rest = &placeholder[end + 1..];
+            }
+            None => {
+                message.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    message.push_str(rest);
+    message
+}
+
+#[derive(Clone, Copy)]
+enum EventFields<'a> {
+    Metric(&'a SensorHealthData),
+    Log(&'a LogRecord),
+    Firmware(&'a FirmwareInfo),
+}
+
+impl EventFields<'_> {
+    fn kind(&self) -> RuleEventKind {
+        match self {
+            Self::Metric(_) => RuleEventKind::Metric,
+            Self::Log(_) => RuleEventKind::Log,
+            Self::Firmware(_) => RuleEventKind::Firmware,
+        }
+    }
+
+    fn series_key(&self) -> &str {
+        match self {
+            Self::Metric(metric) => &metric.key,
+            Self::Log(_) => "",
+            Self::Firmware(firmware) => &firmware.component,
+        }
+    }
+
+    fn target(&self) -> Option<String> {
+        match self {
+            Self::Metric(metric) => Some(
+                metric
+                    .context
+                    .as_ref()
+                    .map_or_else(|| metric.key.clone(), |ctx| ctx.sensor_id.clone()),
+            ),
+            Self::Log(_) => None,
+            Self::Firmware(firmware) => Some(firmware.component.clone()),
+        }
+    }
+
+    fn value(&self) -> Option<f64> {
+        match self {
+            Self::Metric(metric) => Some(metric.value),
+            Self::Log(_) | Self::Firmware(_) => None,
+        }
+    }
+}
+
+struct EventBindings<'a> {
+    context: &'a EventContext,
+    fields: EventFields<'a>,
+}
+
+fn string(value: &str) -> Option<Value> {
+    Some(Value::String(value.to_owned()))
+}
+
+fn label(labels: &[MetricLabel], name: &str) -> Option<Value> {
+    labels
+        .iter()
+        .find(|(key, _)| key == name)
+        .and_then(|(_, value)| string(value))
+}
+
+impl Bindings for EventBindings<'_> {
+    fn resolve(&self, path: &str) -> Option<Value> {
+        match path {
+            "endpoint" => return string(self.context.endpoint_key()),
+            "collector" => return string(self.context.collector_type),
+            "serial_number" => return self.context.serial_number().and_then(string),
+            "machine_id" => {
+                return self
+                    .context
+                    .machine_id()
+                    .map(|id| Value::String(id.to_string()));
+            }
+            "rack_id" => {
+                return self
+                    .context
+                    .rack_id()
+                    .map(|id| Value::String(id.to_string()));
+            }
+            "target" => return self.fields.target().map(Value::String),
+            _ => {}
+        }
+
+        match self.fields {
+            EventFields::Metric(metric) => {
+                if let Some(name) = path.strip_prefix("labels.") {
+                    return label(&metric.labels, name);
+                }
+                if let Some(field) = path.strip_prefix("sensor.") {
+                    let sensor = metric.context.as_ref()?;
+                    let threshold = match field {
+                        "id" => return string(&sensor.sensor_id),
+                        "entity_type" => return string(&sensor.entity_type),
+                        "health" => return Some(Value::String(format!("{:?}", sensor.bmc_health))),
+                        "upper_fatal" => sensor.upper_fatal,
+                        "lower_fatal" => sensor.lower_fatal,
+                        "upper_critical" => sensor.upper_critical,
+                        "lower_critical" => sensor.lower_critical,
+                        "upper_caution" => sensor.upper_caution,
+                        "lower_caution" => sensor.lower_caution,
+                        "range_max" => sensor.range_max,
+                        "range_min" => sensor.range_min,
+                        _ => None,
+                    };
+                    return threshold.map(Value::Number);
+                }
+                match path {
+                    "key" => string(&metric.key),
+                    "name" => string(&metric.name),
+                    "metric_type" => string(&metric.metric_type),
+                    "unit" => string(&metric.unit),
+                    "value" => Some(Value::Number(metric.value)),
+                    _ => None,
+                }
+            }
+            EventFields::Log(log) => {
+                if let Some(name) = path.strip_prefix("attributes.") {
+                    return label(&log.attributes, name);
+                }
+                match path {
+                    "body" => string(&log.body),
+                    "severity" => string(&log.severity),
+                    _ => None,
+                }
+            }
+            EventFields::Firmware(firmware) => {
+                if let Some(name) = path.strip_prefix("attributes.") {
+                    return label(&firmware.attributes, name);
+                }
+                match path {
+                    "component" => string(&firmware.component),
+                    "version" => string(&firmware.version),
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+impl EventProcessor for ExpressionRuleProcessor {
+    fn processor_type(&self) -> &'static str {
+        "expression_rule_processor"
+    }
+
+    fn process_event(&self, context: &EventContext, event: &CollectorEvent) -> Vec<CollectorEvent> {
+        let fields = match event {
+            CollectorEvent::Metric(metric) => {
+                self.observe(context, EventFields::Metric(metric), Instant::now());
+                return Vec::new();
+            }
+            CollectorEvent::MetricCollectionEnd => {
+                if !self.has_rules_for(RuleEventKind::Metric) {
+                    return Vec::new();
+                }
+                let report = self.build_report(context);
+                return vec![CollectorEvent::HealthReport(Arc::new(report))];
+            }
+            CollectorEvent::CollectorRemoved => {
+                self.endpoints.remove(context.endpoint_key());
+                return Vec::new();
+            }
+            CollectorEvent::Log(log) => EventFields::Log(log),
+            CollectorEvent::Firmware(firmware) => EventFields::Firmware(firmware),
+            CollectorEvent::MetricCollectionStart | CollectorEvent::HealthReport(_) => {
+                return Vec::new();
+            }
+        };
+
+        if !self.observe(context, fields, Instant::now()) {
+            return Vec::new();
+        }
+
+        vec![CollectorEvent::HealthReport(Arc::new(
+            self.build_report(context),
+        ))]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+    use std::net::{IpAddr, Ipv4Addr};
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    use mac_address::MacAddress;
+
+    use super::*;
+    use crate::config::ExpressionRuleConfig;
+    use crate::endpoint::BmcAddr;
+
+    fn context() -> EventContext {
+        EventContext {
+            endpoint_key: "42:9e:b1:bd:9d:dd".to_string(),
+            addr: BmcAddr {
+                ip: IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)),
+                port: Some(443),
+                mac: MacAddress::from_str("42:9e:b1:bd:9d:dd").expect("valid mac"),
+            },
+            collector_type: "test",
+            metadata: None,
+            rack_id: None,
+        }
+    }
+
+    fn rule(
+        name: &str,
+        event: RuleEventKind,
+        condition: &str,
+        window: RuleWindowConfig,
+    ) -> ExpressionRuleConfig {
+        ExpressionRuleConfig {
+            name: name.to_string(),
+            event,
+            condition: condition.to_string(),
+            probe_id: format!("{name}-probe"),
+            classifications: vec!["CustomClassification".to_string()],
+            message: None,
+            window,
+        }
+    }
+
+    fn processor(rules: Vec<ExpressionRuleConfig>) -> ExpressionRuleProcessor {
+        ExpressionRuleProcessor::new(&ExpressionRulesProcessorConfig { rules })
+            .expect("valid rules")
+    }
+
+    fn metric(key: &str, value: f64) -> CollectorEvent {
+        CollectorEvent::Metric(
+            SensorHealthData {
+                key: key.to_string(),
+                name: "temperature".to_string(),
+                metric_type: "temperature".to_string(),
+                unit: "celsius".to_string(),
+                value,
+                labels: vec![(Cow::Borrowed("chassis"), "GPU_Tray_1".to_string())],
+                context: None,
+            }
+            .into(),
+        )
+    }
+
+    fn log(body: &str, severity: &str) -> CollectorEvent {
+        CollectorEvent::Log(
+            LogRecord {
+                body: body.to_string(),
+                severity: severity.to_string(),
+                attributes: vec![],
+            }
+            .into(),
+        )
+    }
+
+    fn single_report(events: Vec<CollectorEvent>) -> Arc<HealthReport> {
+        assert_eq!(events.len(), 1);
+        let CollectorEvent::HealthReport(report) = &events[0] else {
+            panic!("expected health report event");
+        };
+        assert_eq!(report.source, ReportSource::ExpressionRules);
+        report.clone()
+    }
+
+    #[test]
+    fn test_invalid_condition_is_rejected() {
+        let result = ExpressionRuleProcessor::new(&ExpressionRulesProcessorConfig {
+            rules: vec![rule(
+                "broken",
+                RuleEventKind::Metric,
+                "value >",
+                RuleWindowConfig::Instant,
+            )],
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_metric_rule_reports_on_collection_end() {
+        let processor = processor(vec![rule(
+            "hot",
+            RuleEventKind::Metric,
+            "labels.chassis.startsWith('GPU') && value > 80",
+            RuleWindowConfig::Instant,
+        )]);
+        let ctx = context();
+
+        assert!(
+            processor
+                .process_event(&ctx, &metric("inlet", 85.0))
+                .is_empty()
+        );
+        assert!(
+            processor
+                .process_event(&ctx, &metric("outlet", 40.0))
+                .is_empty()
+        );
+
+        let report =
+            single_report(processor.process_event(&ctx, &CollectorEvent::MetricCollectionEnd));
+        assert!(report.successes.is_empty());
+        assert_eq!(report.alerts.len(), 1);
+        assert_eq!(report.alerts[0].probe_id, Probe::Custom("hot-probe"));
+        assert_eq!(report.alerts[0].target.as_deref(), Some("inlet"));
+        assert_eq!(
+            report.alerts[0].classifications,
+            vec![Classification::Custom("CustomClassification")]
+        );
+
+        processor.process_event(&ctx, &metric("inlet", 70.0));
+        let report =
+            single_report(processor.process_event(&ctx, &CollectorEvent::MetricCollectionEnd));
+        assert!(report.alerts.is_empty());
+        assert_eq!(report.successes.len(), 1);
+        assert_eq!(report.successes[0].probe_id, Probe::Custom("hot-probe"));
+    }
+
+    #[test]
+    fn test_count_log_rule_reports_on_transition() {
+        let mut psu = rule(
+            "psu",
+            RuleEventKind::Log,
+            "severity in ['Critical', 'Fatal'] && body.contains('PSU')",
+            RuleWindowConfig::Count {
+                n: 2,
+                within: Duration::from_secs(60),
+            },
+        );
+        psu.message = Some("{severity} on {endpoint}: {body} {missing}".to_string());
+        let processor = processor(vec![psu]);
+        let ctx = context();
+        let start = Instant::now();
+        let observe = |body: &str, severity: &str, secs: u64| {
+            let CollectorEvent::Log(log) = log(body, severity) else {
+                unreachable!();
+            };
+            processor.observe(
+                &ctx,
+                EventFields::Log(&log),
+                start + Duration::from_secs(secs),
+            )
+        };
+
+        assert!(!observe("PSU 1 lost input", "Critical", 0));
+        assert!(observe("PSU 2 lost input", "Critical", 10));
+        let report = processor.build_report(&ctx);
+        assert_eq!(report.alerts.len(), 1);
+        assert_eq!(
+            report.alerts[0].message,
+            "Critical on 42:9e:b1:bd:9d:dd: PSU 2 lost input {missing}"
+        );
+
+        // Unrelated log lines are not counted, and do not clear the alert.
+        assert!(!observe("Fan speed nominal", "OK", 20));
+        assert!(!observe("Fan speed nominal", "OK", 30));
+        assert_eq!(processor.build_report(&ctx).alerts.len(), 1);
+
+        // The alert clears once the matching lines aged out of the window.
+        assert!(observe("Fan speed nominal", "OK", 70));
+        assert!(processor.build_report(&ctx).alerts.is_empty());
+    }
+
+    #[test]
+    fn test_log_rule_reports_through_process_event() {
+        let processor = processor(vec![rule(
+            "psu",
+            RuleEventKind::Log,
+            "body.contains('PSU')",
+            RuleWindowConfig::Count {
+                n: 1,
+                within: Duration::from_secs(3600),
+            },
+        )]);
+        let ctx = context();
+
+        assert!(
+            processor
+                .process_event(&ctx, &log("Fan speed nominal", "OK"))
+                .is_empty()
+        );
+        let report =
+            single_report(processor.process_event(&ctx, &log("PSU 1 lost input", "Critical")));
+        assert_eq!(report.alerts.len(), 1);
+        assert!(
+            processor
+                .process_event(&ctx, &log("Fan speed nominal", "OK"))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_evaluation_errors_do_not_match() {
+        let processor = processor(vec![rule(
+            "slot",
+            RuleEventKind::Metric,
+            "labels.slot == 'A'",
+            RuleWindowConfig::Instant,
+        )]);
+        let ctx = context();
+
+        processor.process_event(&ctx, &metric("inlet", 85.0));
+        let report =
+            single_report(processor.process_event(&ctx, &CollectorEvent::MetricCollectionEnd));
+        assert!(report.alerts.is_empty());
+    }
+
+    #[test]
+    fn test_collector_removed_clears_state() {
+        let processor = processor(vec![rule(
+            "hot",
+            RuleEventKind::Metric,
+            "value > 80",
+            RuleWindowConfig::Instant,
+        )]);
+        let ctx = context();
+
+        processor.process_event(&ctx, &metric("inlet", 85.0));
+        processor.process_event(&ctx, &CollectorEvent::CollectorRemoved);
+
+        let report =
+            single_report(processor.process_event(&ctx, &CollectorEvent::MetricCollectionEnd));
+        assert!(report.alerts.is_empty());
+    }
+}
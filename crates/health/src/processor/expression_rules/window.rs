@@ -0,0 +1,205 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::VecDeque;
+use std::time::Instant;
+
+use crate::config::RuleWindowConfig;
+
+/// Per-series window state; decides whether a rule is firing from the
+/// sequence of condition results observed for one series.
+pub(super) enum WindowState {
+    Instant,
+    NOfM(VecDeque<bool>),
+    Sustained(Option<Instant>),
+    RateOfChange(Option<(Instant, f64)>),
+    Count(VecDeque<Instant>),
+}
+
+impl WindowState {
+    pub(super) fn new(window: &RuleWindowConfig) -> Self {
+        match window {
+            RuleWindowConfig::Instant => Self::Instant,
+            RuleWindowConfig::NOfM { m, .. } => Self::NOfM(VecDeque::with_capacity(*m)),
+            RuleWindowConfig::Sustained { .. } => Self::Sustained(None),
+            RuleWindowConfig::RateOfChange { .. } => Self::RateOfChange(None),
+            RuleWindowConfig::Count { n, .. } => Self::Count(VecDeque::with_capacity(*n)),
+        }
+    }
+
+    /// Records one observation and returns whether the rule is firing.
+    ///
+    /// For rate-of-change windows `matched` selects the samples that take part
+    /// in the rate computation and `value` carries the reading.
+    pub(super) fn observe(
+        &mut self,
+        window: &RuleWindowConfig,
+        matched: bool,
+        value: Option<f64>,
+        now: Instant,
+    ) -> bool {
+        match (self, window) {
+            (Self::Instant, _) => matched,
+            (Self::NOfM(history), RuleWindowConfig::NOfM { n, m }) => {
+                history.push_back(matched);
+                while history.len() > *m {
+                    history.pop_front();
+                }
+                history.iter().filter(|matched| **matched).count() >= *n
+            }
+            (Self::Sustained(since), RuleWindowConfig::Sustained { duration }) => {
+                if !matched {
+                    *since = None;
+                    return false;
+                }
+                let since = *since.get_or_insert(now);
+                now.saturating_duration_since(since) >= *duration
+            }
+            (Self::RateOfChange(last), RuleWindowConfig::RateOfChange { threshold, per }) => {
+                let (true, Some(value)) = (matched, value) else {
+                    *last = None;
+                    return false;
+                };
+                let previous = last.replace((now, value));
+                let Some((previous_at, previous_value)) = previous else {
+                    return false;
+                };
+                let elapsed = now.saturating_duration_since(previous_at);
+                if elapsed.is_zero() {
+                    return false;
+                }
+                let rate = (value - previous_value).abs() / elapsed.as_secs_f64();
+                rate * per.as_secs_f64() >= *threshold
+            }
+            (Self::Count(matches), RuleWindowConfig::Count { n, within }) => {
+                // Non-matching observations only age out old matches
+                while matches
+                    .front()
+                    .is_some_and(|at| now.saturating_duration_since(*at) >= *within)
+                {
+                    matches.pop_front();
+                }
+                if matched {
+                    matches.push_back(now);
+                }
+                matches.len() >= *n
+            }
+            (state, window) => {
+                // Window configuration is fixed for the processor lifetime.
+                *state = Self::new(window);
+                state.observe(window, matched, value, now)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn run(window: RuleWindowConfig, samples: &[(u64, bool, f64)]) -> Vec<bool> {
+        let start = Instant::now();
+        let mut state = WindowState::new(&window);
+        samples
+            .iter()
+            .map(|(secs, matched, value)| {
+                state.observe(
+                    &window,
+                    *matched,
+                    Some(*value),
+                    start + Duration::from_secs(*secs),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_n_of_m_window() {
+        let window = RuleWindowConfig::NOfM { n: 2, m: 3 };
+        let samples = [
+            (0, true, 0.0),
+            (1, false, 0.0),
+            (2, true, 0.0),
+            (3, false, 0.0),
+            (4, false, 0.0),
+        ];
+        assert_eq!(
+            run(window, &samples),
+            vec![false, false, true, false, false]
+        );
+    }
+
+    #[test]
+    fn test_sustained_window() {
+        let window = RuleWindowConfig::Sustained {
+            duration: Duration::from_secs(60),
+        };
+        let samples = [
+            (0, true, 0.0),
+            (30, true, 0.0),
+            (60, true, 0.0),
+            (90, false, 0.0),
+            (120, true, 0.0),
+        ];
+        assert_eq!(
+            run(window, &samples),
+            vec![false, false, true, false, false]
+        );
+    }
+
+    #[test]
+    fn test_count_window() {
+        let window = RuleWindowConfig::Count {
+            n: 2,
+            within: Duration::from_secs(60),
+        };
+        let samples = [
+            (0, true, 0.0),
+            (10, false, 0.0),
+            (20, true, 0.0),
+            (30, false, 0.0),
+            (70, false, 0.0),
+            (75, true, 0.0),
+        ];
+        assert_eq!(
+            run(window, &samples),
+            vec![false, false, true, true, false, true]
+        );
+    }
+
+    #[test]
+    fn test_rate_of_change_window() {
+        let window = RuleWindowConfig::RateOfChange {
+            threshold: 5.0,
+            per: Duration::from_secs(60),
+        };
+        let samples = [
+            (0, true, 40.0),
+            (60, true, 42.0),
+            (120, true, 50.0),
+            (180, true, 45.0),
+            (240, false, 90.0),
+            (300, true, 40.0),
+        ];
+        assert_eq!(
+            run(window, &samples),
+            vec![false, false, true, true, false, false]
+        );
+    }
+}
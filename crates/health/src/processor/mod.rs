@@ -20,9 +20,11 @@ use std::collections::VecDeque;
 use std::sync::Arc;
 use std::time::Instant;
 
+mod expression_rules;
 mod health_report;
 mod leak_events;
 mod rack_leak;
+pub use expression_rules::ExpressionRuleProcessor;
 pub use health_report::HealthReportProcessor;
 pub use leak_events::LeakEventProcessor;
 pub use rack_leak::RackLeakProcessor;
@@ -148,6 +148,7 @@ pub enum ReportSource {
     BmcSensors,
     TrayLeakDetection,
     RackLeakDetection,
+    ExpressionRules,
 }
 
 impl ReportSource {
@@ -156,6 +157,7 @@ impl ReportSource {
             Self::BmcSensors => "bmc-sensors",
             Self::TrayLeakDetection => "tray-leak-detection",
             Self::RackLeakDetection => "rack-leak-detection",
+            Self::ExpressionRules => "expression-rules",
         }
     }
 }
@@ -164,6 +166,8 @@ impl ReportSource {
 pub enum Probe {
     Sensor,
     LeakDetection,
+    /// Probe ID defined in configuration, e.g. by an expression rule.
+    Custom(&'static str),
 }
 
 impl Probe {
@@ -171,6 +175,7 @@ impl Probe {
         match self {
             Self::Sensor => "BmcSensor",
             Self::LeakDetection => "BmcLeakDetection",
+            Self::Custom(id) => id,
         }
     }
 }
@@ -184,6 +189,8 @@ pub enum Classification {
     SensorFailure,
     Leak,
     LeakDetector,
+    /// Classification defined in configuration, e.g. by an expression rule.
+    Custom(&'static str),
 }
 
 impl Classification {
@@ -196,6 +203,7 @@ impl Classification {
             Self::SensorFailure => "SensorFailure",
             Self::Leak => "Leak",
             Self::LeakDetector => "LeakDetector",
+            Self::Custom(classification) => classification,
         }
     }
 }
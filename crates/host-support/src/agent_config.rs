@@ -76,6 +76,8 @@ pub struct AgentConfig {
     pub telemetry: TelemetryConfig,
     #[serde(default)]
     pub hbn: HBNConfig,
+    #[serde(default, rename = "commit-confirm")]
+    pub commit_confirm: CommitConfirmConfig,
     #[serde(default)]
     pub period: IterationTime,
     #[serde(default)]
@@ -216,6 +218,71 @@ impl Default for HBNConfig {
     }
 }
 
+/// Commit-confirm for NVUE configs: after applying a new config the agent waits for the
+/// probes to pass, and restores the previous config if they don't by the deadline.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CommitConfirmConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// How long a new config has to pass all probes. Must stay well below the agent's
+    /// systemd WatchdogSec.
+    #[serde(default = "default_commit_confirm_deadline_secs")]
+    pub deadline_secs: u64,
+    /// How often the probes are re-run until they pass or the deadline expires
+    #[serde(default = "default_commit_confirm_probe_interval_secs")]
+    pub probe_interval_secs: u64,
+    #[serde(default = "default_commit_confirm_probes")]
+    pub probes: Vec<CommitConfirmProbe>,
+    /// How long a rolled back config is refused before it is tried again.
+    /// A different config from carbide-api is always tried immediately.
+    #[serde(default = "default_commit_confirm_retry_after_secs")]
+    pub retry_after_secs: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CommitConfirmProbe {
+    /// BGP sessions to the TORs are established
+    BgpPeers,
+    /// BGP sessions to the route servers are established
+    RouteServers,
+    /// carbide-api answers
+    CarbideApi,
+}
+
+fn default_commit_confirm_deadline_secs() -> u64 {
+    120
+}
+
+fn default_commit_confirm_probe_interval_secs() -> u64 {
+    5
+}
+
+fn default_commit_confirm_probes() -> Vec<CommitConfirmProbe> {
+    vec![
+        CommitConfirmProbe::BgpPeers,
+        CommitConfirmProbe::RouteServers,
+        CommitConfirmProbe::CarbideApi,
+    ]
+}
+
+fn default_commit_confirm_retry_after_secs() -> u64 {
+    1800
+}
+
+impl Default for CommitConfirmConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            deadline_secs: default_commit_confirm_deadline_secs(),
+            probe_interval_secs: default_commit_confirm_probe_interval_secs(),
+            probes: default_commit_confirm_probes(),
+            retry_after_secs: default_commit_confirm_retry_after_secs(),
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct UpdateConfig {
@@ -338,6 +405,11 @@ metrics-address = "0.0.0.0:8888"
 root-dir = "/tmp/hbn-root"
 skip-reload = true
 
+[commit-confirm]
+enabled = true
+deadline-secs = 60
+probes = ["bgp-peers", "carbide-api"]
+
 [period]
 main-loop-active-secs = 10
 main-loop-idle-secs = 30
@@ -369,6 +441,14 @@ addresses = ["168.254.169.254/30"]
         assert_eq!(config.hbn.root_dir, PathBuf::from("/tmp/hbn-root"));
         assert!(config.hbn.skip_reload);
 
+        assert!(config.commit_confirm.enabled);
+        assert_eq!(config.commit_confirm.deadline_secs, 60);
+        assert_eq!(config.commit_confirm.probe_interval_secs, 5);
+        assert_eq!(
+            config.commit_confirm.probes,
+            vec![CommitConfirmProbe::BgpPeers, CommitConfirmProbe::CarbideApi]
+        );
+
         assert_eq!(
             config.updates.override_upgrade_cmd,
             Some("update".to_string())
@@ -399,6 +479,7 @@ interface-id = \"91609f10-c91d-470d-a260-6293ea0c1200\"
 
         assert_eq!(config.hbn.root_dir, PathBuf::from(HBN_DEFAULT_ROOT));
         assert!(!config.hbn.skip_reload);
+        assert_eq!(config.commit_confirm, CommitConfirmConfig::default());
 
         assert!(config.updates.override_upgrade_cmd.is_none());
     }
@@ -26,7 +26,7 @@ use health_report::{
     HealthAlertClassification, HealthProbeAlert, HealthProbeId, HealthProbeSuccess, HealthReport,
 };
 
-use crate::Event;
+use crate::rules::Event;
 
 #[derive(thiserror::Error, Debug)]
 pub enum ReportingError {
@@ -46,7 +46,7 @@ pub enum ReportingError {
     TokioJoinError(#[from] tokio::task::JoinError),
 }
 
-pub(crate) fn get_client_cert_info(
+pub fn get_client_cert_info(
     client_cert_path: Option<String>,
     client_key_path: Option<String>,
 ) -> ClientCert {
@@ -73,7 +73,7 @@ pub(crate) fn get_client_cert_info(
     )
 }
 
-pub(crate) fn get_forge_root_ca_path(forge_root_ca_path: Option<String>) -> String {
+pub fn get_forge_root_ca_path(forge_root_ca_path: Option<String>) -> String {
     // First from command line, second env var.
     if let Some(forge_root_ca_path) = forge_root_ca_path {
         return forge_root_ca_path;
@@ -90,7 +90,7 @@ pub(crate) fn get_forge_root_ca_path(forge_root_ca_path: Option<String>) -> Stri
     )
 }
 
-pub(crate) async fn create_forge_client(
+pub async fn create_forge_client(
     root_ca: String,
     client_cert: String,
     client_key: String,
@@ -139,7 +139,7 @@ async fn send_one_report(
     Ok(())
 }
 
-pub(crate) async fn send_health_alerts(
+pub async fn send_health_alerts(
     client: &mut ForgeClientT,
     events: &VecDeque<Event>,
     pipeline: &str,
@@ -0,0 +1,130 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! On-disk state of the pipelines: read positions and rule engine state of every source, so a
+//! restarted parser resumes where it stopped instead of skipping or re-reading logs.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::LogParserError;
+use crate::input::Cursor;
+use crate::rules::SourceState;
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// keyed by [`Pipeline::key`](crate::Pipeline::key)
+    #[serde(default)]
+    pub pipelines: BTreeMap<String, PipelineCheckpoint>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct PipelineCheckpoint {
+    /// keyed by [`SourceDescriptor::id`](crate::SourceDescriptor::id)
+    #[serde(default)]
+    pub sources: BTreeMap<String, SourceCheckpoint>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct SourceCheckpoint {
+    pub cursor: Cursor,
+    pub state: SourceState,
+}
+
+/// A json checkpoint file, replaced atomically on every save.
+#[derive(Debug, Clone)]
+pub struct CheckpointStore {
+    path: PathBuf,
+}
+
+impl CheckpointStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Loads the checkpoint, `None` if none was saved yet.
+    pub async fn load(&self) -> Result<Option<Checkpoint>, LogParserError> {
+        match tokio::fs::read(&self.path).await {
+            Ok(data) => Ok(Some(serde_json::from_slice(&data)?)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(LogParserError::io(self.path.display(), e)),
+        }
+    }
+
+    pub async fn save(&self, checkpoint: &Checkpoint) -> Result<(), LogParserError> {
+        let data = serde_json::to_vec(checkpoint)?;
+        let mut temp = self.path.clone().into_os_string();
+        temp.push(".tmp");
+        let temp = PathBuf::from(temp);
+
+        tokio::fs::write(&temp, data)
+            .await
+            .map_err(|e| LogParserError::io(temp.display(), e))?;
+        tokio::fs::rename(&temp, &self.path)
+            .await
+            .map_err(|e| LogParserError::io(self.path.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = CheckpointStore::new(dir.path().join("state.json"));
+        assert!(store.load().await.unwrap().is_none());
+
+        let mut checkpoint = Checkpoint::default();
+        checkpoint
+            .pipelines
+            .entry("console.json".to_string())
+            .or_default()
+            .sources
+            .insert(
+                "/var/log/consoles/m1_10.0.0.1.log".to_string(),
+                SourceCheckpoint {
+                    cursor: Cursor {
+                        offset: 42,
+                        length: 42,
+                        inode: Some(7),
+                        position: None,
+                    },
+                    state: SourceState {
+                        pending_event: Some("EccStorm".to_string()),
+                        pending_event_count: 2,
+                        pending_event_ts: Some(100),
+                        events: Default::default(),
+                    },
+                },
+            );
+        store.save(&checkpoint).await.unwrap();
+
+        let loaded = store.load().await.unwrap().unwrap();
+        let source = &loaded.pipelines["console.json"].sources["/var/log/consoles/m1_10.0.0.1.log"];
+        assert_eq!(source.cursor.offset, 42);
+        assert_eq!(source.cursor.inode, Some(7));
+        assert_eq!(source.state.pending_event_count, 2);
+    }
+}
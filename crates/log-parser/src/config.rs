@@ -0,0 +1,250 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Event definition pipelines, one json file each.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::LogParserError;
+use crate::input::{
+    FileInput, JournalInput, LogInput, RedfishEndpoint, RedfishLogServiceInput,
+    SSH_CONSOLE_FILENAME_FORMAT, SSH_CONSOLE_LOGS_PATH,
+};
+use crate::rules::EventType;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct PipelineConfig {
+    /// file name of the definition this was loaded from
+    #[serde(skip)]
+    pub filename: Option<String>,
+    pub pipeline: String,
+    pub delimiter: Option<String>,
+    /// regex matching the log file names, named captures become event ids (file inputs only)
+    pub filename_format: Option<String>,
+    /// directory or log file to look at (no recursion), used when `input` is not set
+    pub logs_path: Option<String>,
+    /// where to read logs from, defaults to the files under `logs_path`
+    pub input: Option<InputConfig>,
+    /// rules for events to parse for
+    pub events: Vec<EventType>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum InputConfig {
+    /// console logs written by ssh-console, `machine_id` is taken from the file name
+    SshConsole {
+        #[serde(default = "default_ssh_console_logs_path")]
+        logs_path: String,
+    },
+    /// `journalctl -o export` output
+    Journald {
+        #[serde(default = "default_journalctl")]
+        command: String,
+        /// extra journalctl arguments, e.g. `["-u", "kubelet"]`
+        #[serde(default)]
+        args: Vec<String>,
+        /// ids attached to every event
+        #[serde(default)]
+        fields: HashMap<String, String>,
+    },
+    /// BMC Redfish LogService entries
+    Redfish {
+        endpoints: Vec<RedfishEndpointConfig>,
+        #[serde(default)]
+        insecure_tls: bool,
+    },
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct RedfishEndpointConfig {
+    /// url of the LogService `Entries` collection
+    pub url: String,
+    pub username: String,
+    pub password: Option<String>,
+    /// read the password from this file instead
+    pub password_file: Option<String>,
+    /// ids attached to every event
+    #[serde(default)]
+    pub fields: HashMap<String, String>,
+}
+
+fn default_ssh_console_logs_path() -> String {
+    SSH_CONSOLE_LOGS_PATH.to_string()
+}
+
+fn default_journalctl() -> String {
+    "journalctl".to_string()
+}
+
+impl PipelineConfig {
+    /// read the json event definition pipeline specified
+    /// for event definitions with regex patterns and constraints
+    pub async fn read(path: &Path) -> Result<Self, LogParserError> {
+        let json = tokio::fs::read_to_string(path)
+            .await
+            .map_err(|e| LogParserError::io(path.display(), e))?;
+        let mut config: Self = serde_json::from_str(&json)?;
+        config.filename = Some(
+            path.file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string(),
+        );
+        Ok(config)
+    }
+
+    /// read every definition in the given files or directories
+    pub async fn read_all(paths: &[String]) -> Result<Vec<Self>, LogParserError> {
+        let mut configs = Vec::new();
+        for path in paths {
+            let path = Path::new(path);
+            if path.is_dir() {
+                let mut files: Vec<_> = path
+                    .read_dir()
+                    .map_err(|e| LogParserError::io(path.display(), e))?
+                    .flatten()
+                    .map(|entry| entry.path())
+                    .filter(|path| path.is_file())
+                    .collect();
+                files.sort();
+                for file in files {
+                    configs.push(Self::read(&file).await?);
+                }
+            } else {
+                configs.push(Self::read(path).await?);
+            }
+        }
+        Ok(configs)
+    }
+
+    /// name the pipeline checkpoints are stored under
+    pub fn key(&self) -> &str {
+        self.filename.as_deref().unwrap_or(&self.pipeline)
+    }
+
+    pub fn delimiter(&self) -> u8 {
+        self.delimiter
+            .as_ref()
+            .and_then(|delimiter| delimiter.as_bytes().first().copied())
+            .unwrap_or(b'\n')
+    }
+
+    pub async fn build_input(&self) -> Result<Box<dyn LogInput>, LogParserError> {
+        let Some(input) = &self.input else {
+            let (Some(logs_path), Some(filename_format)) = (&self.logs_path, &self.filename_format)
+            else {
+                return Err(LogParserError::Config(format!(
+                    "pipeline {} requires either input or logs_path and filename_format",
+                    self.key()
+                )));
+            };
+            return Ok(Box::new(FileInput::new(
+                logs_path,
+                filename_format,
+                self.delimiter(),
+            )?));
+        };
+
+        Ok(match input {
+            InputConfig::SshConsole { logs_path } => Box::new(FileInput::new(
+                logs_path,
+                self.filename_format
+                    .as_deref()
+                    .unwrap_or(SSH_CONSOLE_FILENAME_FORMAT),
+                self.delimiter(),
+            )?),
+            InputConfig::Journald {
+                command,
+                args,
+                fields,
+            } => Box::new(JournalInput::new(
+                command.clone(),
+                args.clone(),
+                fields.clone(),
+            )),
+            InputConfig::Redfish {
+                endpoints,
+                insecure_tls,
+            } => {
+                let mut resolved = Vec::with_capacity(endpoints.len());
+                for endpoint in endpoints {
+                    let password = match (&endpoint.password, &endpoint.password_file) {
+                        (Some(password), _) => password.clone(),
+                        (None, Some(file)) => tokio::fs::read_to_string(file)
+                            .await
+                            .map_err(|e| LogParserError::io(file, e))?
+                            .trim_end()
+                            .to_string(),
+                        (None, None) => {
+                            return Err(LogParserError::Config(format!(
+                                "Redfish endpoint {} requires password or password_file",
+                                endpoint.url
+                            )));
+                        }
+                    };
+                    resolved.push(RedfishEndpoint {
+                        url: endpoint.url.clone(),
+                        username: endpoint.username.clone(),
+                        password,
+                        fields: endpoint.fields.clone(),
+                    });
+                }
+                Box::new(RedfishLogServiceInput::new(resolved, *insecure_tls)?)
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_legacy_and_tagged_inputs() {
+        let legacy: PipelineConfig = serde_json::from_str(
+            r#"{"pipeline": "console", "filename_format": "(?P<machine_id>.*)\\.log",
+                "logs_path": "/var/log/consoles", "events": []}"#,
+        )
+        .unwrap();
+        assert!(legacy.input.is_none());
+        assert_eq!(legacy.delimiter(), b'\n');
+
+        let journald: PipelineConfig = serde_json::from_str(
+            r#"{"pipeline": "kubelet", "input": {"type": "journald", "args": ["-u", "kubelet"]},
+                "events": []}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            journald.input,
+            Some(InputConfig::Journald { ref command, ref args, .. })
+                if command == "journalctl" && args.len() == 2
+        ));
+
+        let console: PipelineConfig = serde_json::from_str(
+            r#"{"pipeline": "console", "input": {"type": "ssh_console"}, "events": []}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            console.input,
+            Some(InputConfig::SshConsole { ref logs_path }) if logs_path == SSH_CONSOLE_LOGS_PATH
+        ));
+    }
+}
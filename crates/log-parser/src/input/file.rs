@@ -0,0 +1,457 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::io::{Read, SeekFrom};
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use async_trait::async_trait;
+use regex::Regex;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+use super::{Cursor, LogInput, LogRecord, SourceDescriptor};
+use crate::LogParserError;
+
+/// Where ssh-console writes console logs by default.
+pub const SSH_CONSOLE_LOGS_PATH: &str = "/var/log/consoles";
+
+/// ssh-console names console logs `<machine_id>_<bmc ip>.log` and rotates them to `.log.0`,
+/// `.log.1`, ...
+pub const SSH_CONSOLE_FILENAME_FORMAT: &str = r"^(?P<machine_id>[^_]+)_(?P<bmc_ip>.+)\.log$";
+
+/// cap each read to 1MB
+const MAX_READ: u64 = 0x100000;
+
+/// Tails the files under a directory (no recursion), or a single file, whose names match a
+/// pattern. Named captures of the pattern become the source fields.
+///
+/// Files are reopened for every read, so that we're not affected by truncation, deletion, or
+/// stale file handles. When a file is replaced by rotation, the unread tail of the rotated file
+/// (`<name>.0`, `<name>.1`, or their `.gz` variants) is read before the new file. Matching `.gz`
+/// files are read once, in full.
+pub struct FileInput {
+    logs_path: PathBuf,
+    filename_regex: Regex,
+    delimiter: u8,
+}
+
+enum Rotated {
+    Plain(PathBuf),
+    Gzip(PathBuf),
+}
+
+impl FileInput {
+    pub fn new(
+        logs_path: impl Into<PathBuf>,
+        filename_format: &str,
+        delimiter: u8,
+    ) -> Result<Self, LogParserError> {
+        let filename_format = filename_format.replace("\\\\", "\\");
+        if filename_format.is_empty() {
+            return Err(LogParserError::Config(format!(
+                "Invalid filename_format regex pattern {filename_format}"
+            )));
+        }
+        Ok(Self {
+            logs_path: logs_path.into(),
+            filename_regex: Regex::new(filename_format.as_str())?,
+            delimiter,
+        })
+    }
+
+    fn descriptor(&self, path: &Path, file_name: &str) -> Option<SourceDescriptor> {
+        let captures = self.filename_regex.captures(file_name)?;
+        let fields = self
+            .filename_regex
+            .capture_names()
+            .flatten()
+            .filter_map(|name| {
+                captures
+                    .name(name)
+                    .map(|value| (name.to_string(), value.as_str().to_string()))
+            })
+            .collect();
+        Some(SourceDescriptor {
+            id: path.to_string_lossy().into_owned(),
+            fields,
+        })
+    }
+
+    /// Splits data that will not grow any further (rotated or compressed files) into records.
+    fn complete_records(&self, data: &[u8]) -> Vec<LogRecord> {
+        split_records(data, self.delimiter).collect()
+    }
+}
+
+#[async_trait]
+impl LogInput for FileInput {
+    async fn discover(&mut self) -> Result<Vec<SourceDescriptor>, LogParserError> {
+        let path = self.logs_path.as_path();
+        let metadata = tokio::fs::metadata(path)
+            .await
+            .map_err(|e| LogParserError::io(path.display(), e))?;
+
+        if metadata.is_file() {
+            let name = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or_default();
+            let descriptor = self.descriptor(path, name);
+            if descriptor.is_none() {
+                tracing::warn!(
+                    file_name = name,
+                    filename_format = %self.filename_regex,
+                    "File name did not match the filename_format pattern"
+                );
+            }
+            return Ok(descriptor.into_iter().collect());
+        }
+
+        let mut sources = Vec::new();
+        let mut entries = tokio::fs::read_dir(path)
+            .await
+            .map_err(|e| LogParserError::io(path.display(), e))?;
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| LogParserError::io(path.display(), e))?
+        {
+            let entry_path = entry.path();
+            let is_file = tokio::fs::metadata(&entry_path)
+                .await
+                .is_ok_and(|metadata| metadata.is_file());
+            if is_file
+                && let Some(name) = entry.file_name().to_str()
+                && let Some(descriptor) = self.descriptor(&entry_path, name)
+            {
+                sources.push(descriptor);
+            }
+        }
+        sources.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(sources)
+    }
+
+    async fn read(
+        &mut self,
+        source: &SourceDescriptor,
+        cursor: &mut Cursor,
+    ) -> Result<Vec<LogRecord>, LogParserError> {
+        let path = Path::new(&source.id);
+        let metadata = tokio::fs::metadata(path)
+            .await
+            .map_err(|e| LogParserError::io(&source.id, e))?;
+        let length = metadata.len();
+        let inode = metadata.ino();
+
+        if is_gzip(path) {
+            if cursor.length == length && cursor.inode == Some(inode) {
+                return Ok(Vec::new());
+            }
+            let data = read_gzip(path).await?;
+            let offset = if cursor.inode == Some(inode) {
+                (cursor.offset as usize).min(data.len())
+            } else {
+                0
+            };
+            *cursor = Cursor {
+                offset: data.len() as u64,
+                length,
+                inode: Some(inode),
+                position: None,
+            };
+            return Ok(self.complete_records(&data[offset..]));
+        }
+
+        let mut records = Vec::new();
+        if let Some(previous) = cursor.inode
+            && previous != inode
+        {
+            // the file was replaced: finish reading its rotated predecessor first
+            match find_rotated(path, previous).await {
+                Some(Rotated::Plain(rotated)) => {
+                    let data = read_from(&rotated, cursor.offset).await?;
+                    records.extend(self.complete_records(&data));
+                }
+                Some(Rotated::Gzip(rotated)) => {
+                    let data = read_gzip(&rotated).await?;
+                    let offset = (cursor.offset as usize).min(data.len());
+                    records.extend(self.complete_records(&data[offset..]));
+                }
+                None => tracing::error!(
+                    source = %source.id,
+                    "File was replaced and its rotated copy was not found; unread lines are lost"
+                ),
+            }
+            cursor.offset = 0;
+            cursor.length = 0;
+        }
+        cursor.inode = Some(inode);
+
+        if length == cursor.length {
+            // file unchanged (except if a very strange emitter truncates and writes the exact same length as seen before)
+            return Ok(records);
+        }
+        cursor.length = length;
+        if length < cursor.offset {
+            // log has been truncated
+            cursor.offset = 0;
+        }
+
+        let mut file = tokio::fs::File::open(path)
+            .await
+            .map_err(|e| LogParserError::io(&source.id, e))?;
+        while cursor.offset < length {
+            let chunk = (length - cursor.offset).min(MAX_READ);
+            let mut buffer = vec![0u8; chunk as usize];
+            file.seek(SeekFrom::Start(cursor.offset))
+                .await
+                .map_err(|e| LogParserError::io(&source.id, e))?;
+            file.read_exact(&mut buffer)
+                .await
+                .map_err(|e| LogParserError::io(&source.id, e))?;
+
+            // only consume up to the last delimiter; a partial last line is read once complete
+            match buffer.iter().rposition(|&c| c == self.delimiter) {
+                Some(last) => {
+                    cursor.offset += last as u64 + 1;
+                    buffer.truncate(last + 1);
+                    records.extend(split_records(&buffer, self.delimiter));
+                }
+                None if chunk == MAX_READ => {
+                    tracing::warn!(
+                        source = %source.id,
+                        buffer_size = chunk,
+                        "Buffer did not contain the delimiter"
+                    );
+                    cursor.offset += chunk;
+                }
+                None => break,
+            }
+        }
+        Ok(records)
+    }
+
+    async fn seek_to_end(
+        &mut self,
+        source: &SourceDescriptor,
+        cursor: &mut Cursor,
+    ) -> Result<(), LogParserError> {
+        let path = Path::new(&source.id);
+        let metadata = tokio::fs::metadata(path)
+            .await
+            .map_err(|e| LogParserError::io(&source.id, e))?;
+        let offset = if is_gzip(path) {
+            read_gzip(path).await?.len() as u64
+        } else {
+            metadata.len()
+        };
+        *cursor = Cursor {
+            offset,
+            length: metadata.len(),
+            inode: Some(metadata.ino()),
+            position: None,
+        };
+        Ok(())
+    }
+}
+
+fn split_records(data: &[u8], delimiter: u8) -> impl Iterator<Item = LogRecord> + '_ {
+    data.split(move |&c| c == delimiter)
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| LogRecord::new(String::from_utf8_lossy(segment)))
+}
+
+fn is_gzip(path: &Path) -> bool {
+    path.extension().is_some_and(|extension| extension == "gz")
+}
+
+/// Finds the rotated copy of `path`: an uncompressed copy is identified by its inode, a
+/// compressed one is assumed to be the most recently modified.
+async fn find_rotated(path: &Path, inode: u64) -> Option<Rotated> {
+    let sibling = |suffix: &str| PathBuf::from(format!("{}.{suffix}", path.display()));
+
+    for suffix in ["0", "1"] {
+        let candidate = sibling(suffix);
+        if let Ok(metadata) = tokio::fs::metadata(&candidate).await
+            && metadata.ino() == inode
+        {
+            return Some(Rotated::Plain(candidate));
+        }
+    }
+
+    let mut newest: Option<(SystemTime, PathBuf)> = None;
+    for suffix in ["0.gz", "1.gz"] {
+        let candidate = sibling(suffix);
+        if let Ok(metadata) = tokio::fs::metadata(&candidate).await
+            && let Ok(modified) = metadata.modified()
+            && newest.as_ref().is_none_or(|(newest, _)| modified > *newest)
+        {
+            newest = Some((modified, candidate));
+        }
+    }
+    newest.map(|(_, candidate)| Rotated::Gzip(candidate))
+}
+
+async fn read_from(path: &Path, offset: u64) -> Result<Vec<u8>, LogParserError> {
+    let mut file = tokio::fs::File::open(path)
+        .await
+        .map_err(|e| LogParserError::io(path.display(), e))?;
+    file.seek(SeekFrom::Start(offset))
+        .await
+        .map_err(|e| LogParserError::io(path.display(), e))?;
+    let mut data = Vec::new();
+    file.read_to_end(&mut data)
+        .await
+        .map_err(|e| LogParserError::io(path.display(), e))?;
+    Ok(data)
+}
+
+async fn read_gzip(path: &Path) -> Result<Vec<u8>, LogParserError> {
+    let owned = path.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let file = std::fs::File::open(&owned)?;
+        let mut data = Vec::new();
+        flate2::read::MultiGzDecoder::new(file).read_to_end(&mut data)?;
+        Ok(data)
+    })
+    .await
+    .map_err(|e| LogParserError::io(path.display(), std::io::Error::other(e)))?
+    .map_err(|e| LogParserError::io(path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    fn lines(records: Vec<LogRecord>) -> Vec<String> {
+        records.into_iter().map(|record| record.line).collect()
+    }
+
+    fn append(path: &Path, data: &str) {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap();
+        file.write_all(data.as_bytes()).unwrap();
+    }
+
+    fn gzip(path: &Path, data: &str) {
+        let file = std::fs::File::create(path).unwrap();
+        let mut encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+        encoder.write_all(data.as_bytes()).unwrap();
+        encoder.finish().unwrap();
+    }
+
+    async fn single_source(input: &mut FileInput) -> SourceDescriptor {
+        let mut sources = input.discover().await.unwrap();
+        assert_eq!(sources.len(), 1);
+        sources.remove(0)
+    }
+
+    #[tokio::test]
+    async fn test_discover_ssh_console_logs() {
+        let dir = tempfile::tempdir().unwrap();
+        append(&dir.path().join("fm100abc_10.0.0.1.log"), "");
+        append(&dir.path().join("fm100abc_10.0.0.1.log.0"), "");
+        append(&dir.path().join("unrelated.txt"), "");
+
+        let mut input = FileInput::new(dir.path(), SSH_CONSOLE_FILENAME_FORMAT, b'\n').unwrap();
+        let source = single_source(&mut input).await;
+        assert_eq!(source.fields["machine_id"], "fm100abc");
+        assert_eq!(source.fields["bmc_ip"], "10.0.0.1");
+    }
+
+    #[tokio::test]
+    async fn test_tail_waits_for_complete_lines_and_handles_truncation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("console.log");
+        append(&path, "one\ntw");
+
+        let mut input = FileInput::new(dir.path(), r"\.log$", b'\n').unwrap();
+        let source = single_source(&mut input).await;
+        let mut cursor = Cursor::default();
+
+        let records = input.read(&source, &mut cursor).await.unwrap();
+        assert_eq!(lines(records), vec!["one"]);
+        assert_eq!(cursor.offset, 4);
+
+        append(&path, "o\nthree\n");
+        let records = input.read(&source, &mut cursor).await.unwrap();
+        assert_eq!(lines(records), vec!["two", "three"]);
+        assert!(input.read(&source, &mut cursor).await.unwrap().is_empty());
+
+        std::fs::write(&path, "new\n").unwrap();
+        let records = input.read(&source, &mut cursor).await.unwrap();
+        assert_eq!(lines(records), vec!["new"]);
+    }
+
+    #[tokio::test]
+    async fn test_reads_rotated_tail_before_new_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("console.log");
+        append(&path, "before\n");
+
+        let mut input = FileInput::new(dir.path(), r"\.log$", b'\n').unwrap();
+        let source = single_source(&mut input).await;
+        let mut cursor = Cursor::default();
+        input.seek_to_end(&source, &mut cursor).await.unwrap();
+
+        append(&path, "missed\n");
+        std::fs::rename(&path, dir.path().join("console.log.0")).unwrap();
+        append(&path, "after\n");
+
+        let records = input.read(&source, &mut cursor).await.unwrap();
+        assert_eq!(lines(records), vec!["missed", "after"]);
+    }
+
+    #[tokio::test]
+    async fn test_reads_gzip_rotated_tail() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("syslog");
+        append(&path, "before\n");
+
+        let mut input = FileInput::new(&path, "^syslog$", b'\n').unwrap();
+        let source = single_source(&mut input).await;
+        let mut cursor = Cursor::default();
+        input.seek_to_end(&source, &mut cursor).await.unwrap();
+
+        std::fs::remove_file(&path).unwrap();
+        gzip(&dir.path().join("syslog.1.gz"), "before\nmissed\n");
+        append(&path, "after\n");
+
+        let records = input.read(&source, &mut cursor).await.unwrap();
+        assert_eq!(lines(records), vec!["missed", "after"]);
+    }
+
+    #[tokio::test]
+    async fn test_gzip_source_read_once() {
+        let dir = tempfile::tempdir().unwrap();
+        gzip(&dir.path().join("old.log.gz"), "a\nb");
+
+        let mut input = FileInput::new(dir.path(), r"\.log\.gz$", b'\n').unwrap();
+        let source = single_source(&mut input).await;
+        let mut cursor = Cursor::default();
+
+        let records = input.read(&source, &mut cursor).await.unwrap();
+        assert_eq!(lines(records), vec!["a", "b"]);
+        assert!(input.read(&source, &mut cursor).await.unwrap().is_empty());
+    }
+}
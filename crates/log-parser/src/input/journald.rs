@@ -0,0 +1,243 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::DateTime;
+
+use super::{Cursor, LogInput, LogRecord, SourceDescriptor};
+use crate::LogParserError;
+
+/// Reads the systemd journal through `journalctl -o export`, resuming from the journal cursor.
+pub struct JournalInput {
+    command: String,
+    args: Vec<String>,
+    source: SourceDescriptor,
+}
+
+impl JournalInput {
+    /// `args` are passed to `journalctl` as is, e.g. `["-u", "kubelet"]` or `["--directory", path]`.
+    /// `fields` are attached to every event (set `machine_id` to report against a machine).
+    pub fn new(command: String, args: Vec<String>, fields: HashMap<String, String>) -> Self {
+        let id = std::iter::once(command.as_str())
+            .chain(args.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(" ");
+        Self {
+            command,
+            args,
+            source: SourceDescriptor { id, fields },
+        }
+    }
+
+    async fn export(&self, extra_args: &[String]) -> Result<Vec<u8>, LogParserError> {
+        let output = tokio::process::Command::new(&self.command)
+            .args(["-o", "export", "--no-pager"])
+            .args(&self.args)
+            .args(extra_args)
+            .output()
+            .await
+            .map_err(|e| LogParserError::io(&self.command, e))?;
+        if !output.status.success() {
+            return Err(LogParserError::InvalidInput {
+                source_id: self.source.id.clone(),
+                message: format!(
+                    "{} exited with {}: {}",
+                    self.command,
+                    output.status,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ),
+            });
+        }
+        Ok(output.stdout)
+    }
+}
+
+#[async_trait]
+impl LogInput for JournalInput {
+    async fn discover(&mut self) -> Result<Vec<SourceDescriptor>, LogParserError> {
+        Ok(vec![self.source.clone()])
+    }
+
+    async fn read(
+        &mut self,
+        source: &SourceDescriptor,
+        cursor: &mut Cursor,
+    ) -> Result<Vec<LogRecord>, LogParserError> {
+        let extra_args: Vec<String> = cursor
+            .position
+            .iter()
+            .map(|position| format!("--after-cursor={position}"))
+            .collect();
+        let entries = parse_export(&self.export(&extra_args).await?).map_err(|message| {
+            LogParserError::InvalidInput {
+                source_id: source.id.clone(),
+                message,
+            }
+        })?;
+
+        let mut records = Vec::with_capacity(entries.len());
+        for mut entry in entries {
+            if let Some(position) = entry.remove("__CURSOR") {
+                cursor.position = Some(position);
+            }
+            let Some(line) = entry.remove("MESSAGE") else {
+                continue;
+            };
+            let timestamp = entry
+                .get("__REALTIME_TIMESTAMP")
+                .and_then(|micros| micros.parse::<i64>().ok())
+                .and_then(DateTime::from_timestamp_micros);
+            records.push(LogRecord { timestamp, line });
+        }
+        Ok(records)
+    }
+
+    async fn seek_to_end(
+        &mut self,
+        source: &SourceDescriptor,
+        cursor: &mut Cursor,
+    ) -> Result<(), LogParserError> {
+        let entries = parse_export(&self.export(&["-n".to_string(), "1".to_string()]).await?)
+            .map_err(|message| LogParserError::InvalidInput {
+                source_id: source.id.clone(),
+                message,
+            })?;
+        cursor.position = entries
+            .into_iter()
+            .last()
+            .and_then(|mut entry| entry.remove("__CURSOR"));
+        Ok(())
+    }
+}
+
+/// Parses the [journal export format](https://systemd.io/JOURNAL_EXPORT_FORMATS/): entries are
+/// separated by an empty line, fields are either `NAME=value` lines or, for binary values, `NAME`
+/// followed by a little endian 64 bit length, the data, and a newline.
+pub fn parse_export(data: &[u8]) -> Result<Vec<HashMap<String, String>>, String> {
+    let mut entries = Vec::new();
+    let mut entry = HashMap::new();
+    let mut rest = data;
+
+    while !rest.is_empty() {
+        let end = rest.iter().position(|&c| c == b'\n').unwrap_or(rest.len());
+        let line = &rest[..end];
+        rest = rest.get(end + 1..).unwrap_or_default();
+
+        if line.is_empty() {
+            if !entry.is_empty() {
+                entries.push(std::mem::take(&mut entry));
+            }
+            continue;
+        }
+
+        if let Some(separator) = line.iter().position(|&c| c == b'=') {
+            entry.insert(
+                String::from_utf8_lossy(&line[..separator]).into_owned(),
+                String::from_utf8_lossy(&line[separator + 1..]).into_owned(),
+            );
+            continue;
+        }
+
+        // binary field: the line holds the name; the size and data follow
+        let name = String::from_utf8_lossy(line).into_owned();
+        let Some((size, remaining)) = rest.split_first_chunk::<8>() else {
+            return Err(format!("truncated size of binary field {name}"));
+        };
+        let size = u64::from_le_bytes(*size) as usize;
+        if remaining.len() < size {
+            return Err(format!("truncated data of binary field {name}"));
+        }
+        entry.insert(
+            name,
+            String::from_utf8_lossy(&remaining[..size]).into_owned(),
+        );
+        rest = remaining[size..]
+            .strip_prefix(b"\n")
+            .unwrap_or(&remaining[size..]);
+    }
+    if !entry.is_empty() {
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_export_text_and_binary_fields() {
+        let mut data =
+            b"__CURSOR=s=1;i=1\n__REALTIME_TIMESTAMP=1700000000000000\nMESSAGE=first\n\n".to_vec();
+        data.extend_from_slice(b"__CURSOR=s=1;i=2\nMESSAGE\n");
+        data.extend_from_slice(&11u64.to_le_bytes());
+        data.extend_from_slice(b"two\nlines\x01x\n");
+        data.extend_from_slice(b"PRIORITY=3\n\n");
+
+        let entries = parse_export(&data).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0]["MESSAGE"], "first");
+        assert_eq!(entries[0]["__REALTIME_TIMESTAMP"], "1700000000000000");
+        assert_eq!(entries[1]["MESSAGE"], "two\nlines\u{1}x");
+        assert_eq!(entries[1]["PRIORITY"], "3");
+        assert_eq!(entries[1]["__CURSOR"], "s=1;i=2");
+    }
+
+    #[test]
+    fn test_parse_export_truncated_binary_field() {
+        let mut data = b"MESSAGE\n".to_vec();
+        data.extend_from_slice(&100u64.to_le_bytes());
+        data.extend_from_slice(b"short");
+        assert!(parse_export(&data).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_read_resumes_after_cursor() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("journalctl");
+        // prints its arguments as the message so the test can check the cursor is passed along
+        std::fs::write(
+            &script,
+            "#!/bin/sh\nprintf '__CURSOR=c2\\n__REALTIME_TIMESTAMP=1000000\\nMESSAGE=%s\\n\\n' \"$*\"\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::os::unix::fs::PermissionsExt::from_mode(0o755))
+            .unwrap();
+
+        let mut input = JournalInput::new(
+            script.to_string_lossy().into_owned(),
+            vec!["-u".to_string(), "kubelet".to_string()],
+            HashMap::new(),
+        );
+        let source = input.discover().await.unwrap().remove(0);
+        let mut cursor = Cursor {
+            position: Some("c1".to_string()),
+            ..Default::default()
+        };
+
+        let records = input.read(&source, &mut cursor).await.unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(
+            records[0].line,
+            "-o export --no-pager -u kubelet --after-cursor=c1"
+        );
+        assert_eq!(records[0].timestamp.unwrap().timestamp(), 1);
+        assert_eq!(cursor.position.as_deref(), Some("c2"));
+    }
+}
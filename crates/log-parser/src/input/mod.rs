@@ -0,0 +1,96 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Log inputs a [`Pipeline`](crate::Pipeline) reads records from.
+
+mod file;
+mod journald;
+mod redfish;
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+pub use file::{FileInput, SSH_CONSOLE_FILENAME_FORMAT, SSH_CONSOLE_LOGS_PATH};
+pub use journald::{JournalInput, parse_export};
+pub use redfish::{RedfishEndpoint, RedfishLogServiceInput};
+use serde::{Deserialize, Serialize};
+
+use crate::LogParserError;
+
+/// One log stream of an input, e.g. a log file or a BMC LogService.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceDescriptor {
+    /// Stable identifier, used to key checkpoints.
+    pub id: String,
+    /// Identifiers attached to events from this source (`machine_id` selects the reported machine).
+    pub fields: HashMap<String, String>,
+}
+
+/// Resume position within a source. Inputs use the fields that apply to them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cursor {
+    /// Byte offset of the next unread record.
+    #[serde(default)]
+    pub offset: u64,
+    /// Last seen length of the source.
+    #[serde(default)]
+    pub length: u64,
+    /// Identity (inode) of the file `offset` refers to, used to detect rotation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inode: Option<u64>,
+    /// Opaque position of record based inputs (journal cursor, last LogService entry).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogRecord {
+    /// When the record was logged, if the input knows; otherwise the time it was read is used.
+    pub timestamp: Option<DateTime<Utc>>,
+    pub line: String,
+}
+
+impl LogRecord {
+    pub fn new(line: impl Into<String>) -> Self {
+        Self {
+            timestamp: None,
+            line: line.into(),
+        }
+    }
+}
+
+#[async_trait]
+pub trait LogInput: Send + Sync {
+    /// Lists the sources currently available.
+    async fn discover(&mut self) -> Result<Vec<SourceDescriptor>, LogParserError>;
+
+    /// Reads the records appended to `source` since `cursor` and advances it.
+    async fn read(
+        &mut self,
+        source: &SourceDescriptor,
+        cursor: &mut Cursor,
+    ) -> Result<Vec<LogRecord>, LogParserError>;
+
+    /// Moves `cursor` to the current end of `source` without reading, so that history present
+    /// before monitoring started does not raise alerts.
+    async fn seek_to_end(
+        &mut self,
+        source: &SourceDescriptor,
+        cursor: &mut Cursor,
+    ) -> Result<(), LogParserError>;
+}
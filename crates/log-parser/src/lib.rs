@@ -0,0 +1,68 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Log parsing library behind `forge-log-parser`.
+//!
+//! A [`Pipeline`] reads records from a [`LogInput`] (plain or gzip-rotated files, ssh-console
+//! console logs, journald export output, Redfish LogService entries), runs them through the
+//! [`RuleSet`] of its event definitions, and keeps per-source [`SourceState`]. Read positions and
+//! rule state can be persisted with a [`CheckpointStore`] so restarts neither re-alert nor miss
+//! events. Embedders (e.g. a health collector) drive [`Pipeline::poll`] and consume the queued
+//! [`Event`]s; [`carbide_reporting`] turns them into carbide health reports.
+
+pub mod carbide_reporting;
+pub mod checkpoint;
+pub mod config;
+pub mod input;
+pub mod pipeline;
+pub mod rules;
+
+pub use checkpoint::{Checkpoint, CheckpointStore, PipelineCheckpoint, SourceCheckpoint};
+pub use config::{InputConfig, PipelineConfig};
+pub use input::{Cursor, LogInput, LogRecord, SourceDescriptor};
+pub use pipeline::Pipeline;
+pub use rules::{Event, EventConstraints, EventSeverity, EventType, RuleSet, SourceState};
+
+#[derive(thiserror::Error, Debug)]
+pub enum LogParserError {
+    #[error("Invalid configuration: {0}")]
+    Config(String),
+
+    #[error("Invalid regex: {0}")]
+    Regex(#[from] regex::Error),
+
+    #[error("I/O error on {path}: {error}")]
+    Io { path: String, error: std::io::Error },
+
+    #[error("Error while handling json: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Invalid input data from {source_id}: {message}")]
+    InvalidInput { source_id: String, message: String },
+
+    #[error("HTTP request to {url} failed: {error}")]
+    Http { url: String, error: reqwest::Error },
+}
+
+impl LogParserError {
+    pub(crate) fn io(path: impl std::fmt::Display, error: std::io::Error) -> Self {
+        Self::Io {
+            path: path.to_string(),
+            error,
+        }
+    }
+}
@@ -0,0 +1,287 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::{BTreeMap, HashSet};
+
+use chrono::Utc;
+
+use crate::LogParserError;
+use crate::checkpoint::{PipelineCheckpoint, SourceCheckpoint};
+use crate::config::PipelineConfig;
+use crate::input::{Cursor, LogInput, SourceDescriptor};
+use crate::rules::{RuleSet, SourceState};
+
+/// One event definition: an input and the rules its records are run through.
+pub struct Pipeline {
+    name: String,
+    key: String,
+    input: Box<dyn LogInput>,
+    rules: RuleSet,
+    sources: BTreeMap<String, TrackedSource>,
+    /// checkpointed sources not discovered (yet)
+    restored: BTreeMap<String, SourceCheckpoint>,
+    start_at_end: bool,
+    first_poll: bool,
+    dirty: bool,
+}
+
+struct TrackedSource {
+    descriptor: SourceDescriptor,
+    cursor: Cursor,
+    state: SourceState,
+}
+
+impl Pipeline {
+    pub fn new(
+        name: impl Into<String>,
+        key: impl Into<String>,
+        input: Box<dyn LogInput>,
+        rules: RuleSet,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            key: key.into(),
+            input,
+            rules,
+            sources: BTreeMap::new(),
+            restored: BTreeMap::new(),
+            start_at_end: false,
+            first_poll: true,
+            dirty: false,
+        }
+    }
+
+    pub async fn from_config(config: PipelineConfig) -> Result<Self, LogParserError> {
+        let input = config.build_input().await?;
+        let key = config.key().to_string();
+        Ok(Self::new(
+            config.pipeline,
+            key,
+            input,
+            RuleSet::new(config.events)?,
+        ))
+    }
+
+    /// When set, sources found by the first poll that have no checkpoint are read from their
+    /// current end, so that history does not raise stale alerts. Sources appearing later are
+    /// always read from the start.
+    pub fn start_at_end(mut self, start_at_end: bool) -> Self {
+        self.start_at_end = start_at_end;
+        self
+    }
+
+    /// The pipeline name events are reported under.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The name checkpoints of this pipeline are stored under.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn rules(&self) -> &RuleSet {
+        &self.rules
+    }
+
+    /// Resumes the sources of a previous run from their checkpoint.
+    pub fn restore(&mut self, checkpoint: &PipelineCheckpoint) {
+        self.restored.extend(
+            checkpoint
+                .sources
+                .iter()
+                .map(|(id, source)| (id.clone(), source.clone())),
+        );
+    }
+
+    /// Discovers sources and runs the new records of each through the rules. Errors of single
+    /// sources are logged and don't stop the others.
+    pub async fn poll(&mut self) -> Result<(), LogParserError> {
+        let discovered = self.input.discover().await?;
+        let discovered_ids: HashSet<&str> = discovered.iter().map(|d| d.id.as_str()).collect();
+        let before = self.sources.len();
+        self.sources
+            .retain(|id, _| discovered_ids.contains(id.as_str()));
+        self.dirty |= self.sources.len() != before;
+
+        for descriptor in discovered {
+            if !self.sources.contains_key(&descriptor.id) {
+                let source = self.track(descriptor).await;
+                self.sources.insert(source.descriptor.id.clone(), source);
+                self.dirty = true;
+            }
+        }
+        self.first_poll = false;
+
+        for source in self.sources.values_mut() {
+            let records = match self
+                .input
+                .read(&source.descriptor, &mut source.cursor)
+                .await
+            {
+                Ok(records) => records,
+                Err(e) => {
+                    tracing::warn!(
+                        pipeline = %self.key,
+                        source = %source.descriptor.id,
+                        error = %e,
+                        "Failed to read log source"
+                    );
+                    continue;
+                }
+            };
+            if records.is_empty() {
+                continue;
+            }
+            let now = Utc::now();
+            for record in records {
+                self.rules.process_line(
+                    &mut source.state,
+                    &source.descriptor.fields,
+                    record.timestamp.unwrap_or(now).timestamp(),
+                    &record.line,
+                );
+            }
+            self.dirty = true;
+        }
+        Ok(())
+    }
+
+    async fn track(&mut self, descriptor: SourceDescriptor) -> TrackedSource {
+        if let Some(restored) = self.restored.remove(&descriptor.id) {
+            return TrackedSource {
+                descriptor,
+                cursor: restored.cursor,
+                state: restored.state,
+            };
+        }
+        let mut cursor = Cursor::default();
+        if self.first_poll
+            && self.start_at_end
+            && let Err(e) = self.input.seek_to_end(&descriptor, &mut cursor).await
+        {
+            tracing::warn!(
+                pipeline = %self.key,
+                source = %descriptor.id,
+                error = %e,
+                "Failed to seek to the end of log source"
+            );
+        }
+        TrackedSource {
+            descriptor,
+            cursor,
+            state: SourceState::default(),
+        }
+    }
+
+    /// The tracked sources and their rule state, including queued events.
+    pub fn sources(&self) -> impl Iterator<Item = (&SourceDescriptor, &SourceState)> {
+        self.sources
+            .values()
+            .map(|source| (&source.descriptor, &source.state))
+    }
+
+    pub fn checkpoint(&self) -> PipelineCheckpoint {
+        let mut sources = self.restored.clone();
+        sources.extend(self.sources.iter().map(|(id, source)| {
+            (
+                id.clone(),
+                SourceCheckpoint {
+                    cursor: source.cursor.clone(),
+                    state: source.state.clone(),
+                },
+            )
+        }));
+        PipelineCheckpoint { sources }
+    }
+
+    /// Whether anything changed since the last call, i.e. the checkpoint needs saving.
+    pub fn take_dirty(&mut self) -> bool {
+        std::mem::take(&mut self.dirty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+    use crate::input::FileInput;
+    use crate::rules::{EventSeverity, EventType};
+
+    fn pipeline(dir: &std::path::Path) -> Pipeline {
+        let rules = RuleSet::new(vec![EventType {
+            regex_string: "panic".to_string(),
+            name: "Panic".to_string(),
+            description: None,
+            target: None,
+            severity: EventSeverity::Critical,
+            ignore_case: false,
+            alert: true,
+            clears: Vec::new(),
+            constraints: None,
+        }])
+        .unwrap();
+        let input = FileInput::new(dir, r"^(?P<machine_id>[^_]+)_.*\.log$", b'\n').unwrap();
+        Pipeline::new("console", "console.json", Box::new(input), rules).start_at_end(true)
+    }
+
+    fn append(path: &std::path::Path, data: &str) {
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap()
+            .write_all(data.as_bytes())
+            .unwrap();
+    }
+
+    fn event_count(pipeline: &Pipeline) -> usize {
+        pipeline
+            .sources()
+            .map(|(_, state)| state.events.len())
+            .sum()
+    }
+
+    #[tokio::test]
+    async fn test_resume_from_checkpoint() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = dir.path().join("m1_10.0.0.1.log");
+        append(&log, "old panic\n");
+
+        let mut first = pipeline(dir.path());
+        first.poll().await.unwrap();
+        // history is skipped
+        assert_eq!(event_count(&first), 0);
+
+        append(&log, "panic 1\n");
+        first.poll().await.unwrap();
+        assert_eq!(event_count(&first), 1);
+        assert!(first.take_dirty());
+        let checkpoint = first.checkpoint();
+
+        // lines written while the parser was down are neither missed nor re-read
+        append(&log, "panic 2\n");
+        let mut second = pipeline(dir.path());
+        second.restore(&checkpoint);
+        second.poll().await.unwrap();
+        let (descriptor, state) = second.sources().next().unwrap();
+        assert_eq!(descriptor.fields["machine_id"], "m1");
+        let lines: Vec<_> = state.events.iter().map(|e| e.log_entry.as_str()).collect();
+        assert_eq!(lines, vec!["panic 1", "panic 2"]);
+    }
+}
@@ -0,0 +1,380 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Event rule engine: matches log lines against [`EventType`] regexes, applies
+//! [`EventConstraints`], and queues the resulting [`Event`]s per source.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::LogParserError;
+
+/// Number of events kept per source; the oldest are dropped first.
+pub const MAX_EVENTS: usize = 128;
+
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, Eq, PartialEq)]
+pub enum EventSeverity {
+    Critical,
+    Warning,
+    Information,
+    Debug,
+}
+
+impl fmt::Display for EventSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// for summary events that occur based on the frequency or pattern of other events occurring
+#[derive(Deserialize, Debug, Clone)]
+pub struct EventConstraints {
+    /// the event has occurred if it met a certain frequency of occurrence
+    /// repeated > count inside of duration (seconds)
+    /// defaults are zero for event occurring every time
+    pub duration: Option<i64>,
+    pub count: Option<u32>,
+    /// alternatively (instead of frequency), the event has occurred if a pattern of events occurred
+    /// event is preceded by one or more events specified by names in chronological order
+    /// i.e.: [oldest event in pattern, oldest + 1, ..., latest event]
+    pub preceded_by: Option<Vec<String>>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct EventType {
+    pub regex_string: String,
+    /// identifier for the event
+    pub name: String,
+    pub description: Option<String>,
+    pub target: Option<String>,
+    pub severity: EventSeverity,
+    pub ignore_case: bool,
+    pub alert: bool,
+    pub clears: Vec<String>,
+    pub constraints: Option<EventConstraints>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Event {
+    pub name: String,
+    pub description: Option<String>,
+    pub target: Option<String>,
+    pub severity: EventSeverity,
+    pub alert: bool,
+    pub cleared: bool,
+    pub timestamp: DateTime<Utc>,
+    pub log_entry: String,
+    pub machine_id: String,
+    pub ids: HashMap<String, String>,
+}
+
+/// Rule engine state of one log source.
+///
+/// We track 2 kinds of prior events:
+/// the events that occurred, if a new one depends on or clears them
+/// a pending event that requires a certain number of occurrences in a time window or frequency
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct SourceState {
+    pub pending_event: Option<String>,
+    pub pending_event_count: u32,
+    /// first time this event was detected, cleared/reset when the event constraint duration expires
+    pub pending_event_ts: Option<i64>,
+    pub events: VecDeque<Event>,
+}
+
+/// The compiled event definitions of one pipeline.
+#[derive(Debug)]
+pub struct RuleSet {
+    event_types: Vec<EventType>,
+    regexes: Vec<Regex>,
+}
+
+impl RuleSet {
+    /// Compiles the event regexes. Patterns given with escaped backslashes (`\\d`) are unescaped.
+    pub fn new(event_types: Vec<EventType>) -> Result<Self, LogParserError> {
+        let mut regexes = Vec::with_capacity(event_types.len());
+        for event_type in &event_types {
+            let regex_string = event_type.regex_string.replace("\\\\", "\\");
+            if regex_string.is_empty() {
+                return Err(LogParserError::Config(format!(
+                    "Invalid event regex pattern {}",
+                    &event_type.regex_string
+                )));
+            }
+            regexes.push(Regex::new(regex_string.as_str())?);
+        }
+        Ok(Self {
+            event_types,
+            regexes,
+        })
+    }
+
+    pub fn event_types(&self) -> &[EventType] {
+        &self.event_types
+    }
+
+    /// Runs one log line through every rule, queueing matched events on `state`.
+    /// `fields` are the identifiers of the source (e.g. `machine_id`) attached to each event.
+    pub fn process_line(
+        &self,
+        state: &mut SourceState,
+        fields: &HashMap<String, String>,
+        timestamp: i64,
+        line: &str,
+    ) {
+        let lowercase = self
+            .event_types
+            .iter()
+            .any(|event_type| event_type.ignore_case)
+            .then(|| line.to_ascii_lowercase());
+
+        for (event_type, regex) in self.event_types.iter().zip(self.regexes.iter()) {
+            // check if ignore case is specified and match lowercase string (regex specified MUST be lowercase)
+            let haystack = match (&lowercase, event_type.ignore_case) {
+                (Some(lowercase), true) => lowercase.as_str(),
+                _ => line,
+            };
+            if !regex.is_match(haystack) {
+                continue;
+            }
+            let matched = Match {
+                event_type,
+                fields,
+                timestamp,
+                line,
+            };
+            if let Some(constraints) = &event_type.constraints {
+                check_constraints(&matched, constraints, state);
+            } else {
+                queue_event(&matched, state);
+            }
+        }
+    }
+}
+
+struct Match<'a> {
+    event_type: &'a EventType,
+    fields: &'a HashMap<String, String>,
+    timestamp: i64,
+    line: &'a str,
+}
+
+fn clear_prior_events(event_type: &EventType, state: &mut SourceState) {
+    for event in state.events.iter_mut() {
+        for clear_events in event_type.clears.iter() {
+            if event.name.contains(clear_events) {
+                event.cleared = true;
+            }
+        }
+    }
+}
+
+fn queue_event(matched: &Match<'_>, state: &mut SourceState) {
+    let event_type = matched.event_type;
+    if !event_type.clears.is_empty() {
+        clear_prior_events(event_type, state);
+    }
+    let event = Event {
+        name: event_type.name.clone(),
+        description: event_type.description.clone(),
+        target: event_type.target.clone(),
+        severity: event_type.severity,
+        alert: event_type.alert,
+        cleared: false,
+        timestamp: DateTime::from_timestamp(matched.timestamp, 0).unwrap_or_default(),
+        log_entry: matched.line.to_string(),
+        machine_id: matched
+            .fields
+            .get("machine_id")
+            .cloned()
+            .unwrap_or_default(),
+        ids: matched.fields.clone(),
+    };
+    if state.events.len() >= MAX_EVENTS {
+        let _ = state.events.pop_front();
+    }
+    state.events.push_back(event);
+}
+
+fn check_constraints(matched: &Match<'_>, constraints: &EventConstraints, state: &mut SourceState) {
+    let event_type = matched.event_type;
+    let timestamp = matched.timestamp;
+    // duration and count of occurences of this event
+    if constraints.count.is_some() && constraints.duration.is_some() {
+        if Some(&event_type.name) == state.pending_event.as_ref() {
+            state.pending_event_count += 1;
+            if let Some(start_ts) = state.pending_event_ts
+                && let Some(duration) = constraints.duration
+                && (timestamp - start_ts) > duration
+            {
+                // time window expired, reset it
+                state.pending_event_ts = Some(timestamp);
+                state.pending_event_count = 1;
+            }
+        } else {
+            // setup the pending summary event.
+            // we only support tracking one summary event across multiple regular events
+            state.pending_event = Some(event_type.name.clone());
+            state.pending_event_count = 1;
+            state.pending_event_ts = Some(timestamp);
+        }
+        // now check the count
+        if let Some(count) = constraints.count
+            && state.pending_event_count >= count
+        {
+            state.pending_event_count = 0;
+            state.pending_event_ts = None;
+            state.pending_event = None;
+            // send the summary event, it has met the constraints and considered as occurred
+            queue_event(matched, state);
+        }
+    } else if let Some(event_pattern) = constraints.preceded_by.as_ref() {
+        // walk through the pattern and queue and check every event name matches
+        let event_pattern_matched = event_pattern
+            .iter()
+            .rev()
+            .zip(state.events.iter().rev())
+            .all(|(event_name, prior_event)| *event_name == prior_event.name);
+        if event_pattern_matched {
+            queue_event(matched, state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event_type(name: &str, regex: &str) -> EventType {
+        EventType {
+            regex_string: regex.to_string(),
+            name: name.to_string(),
+            description: None,
+            target: None,
+            severity: EventSeverity::Warning,
+            ignore_case: false,
+            alert: true,
+            clears: Vec::new(),
+            constraints: None,
+        }
+    }
+
+    fn names(state: &SourceState) -> Vec<(&str, bool)> {
+        state
+            .events
+            .iter()
+            .map(|event| (event.name.as_str(), event.cleared))
+            .collect()
+    }
+
+    #[test]
+    fn test_match_clear_and_machine_id() {
+        let rules = RuleSet::new(vec![
+            EventType {
+                ignore_case: true,
+                ..event_type("KernelPanic", "kernel panic")
+            },
+            EventType {
+                alert: false,
+                clears: vec!["KernelPanic".to_string()],
+                ..event_type("Booted", "login:")
+            },
+        ])
+        .unwrap();
+        let fields = HashMap::from([("machine_id".to_string(), "m1".to_string())]);
+        let mut state = SourceState::default();
+
+        rules.process_line(&mut state, &fields, 10, "Kernel Panic - not syncing");
+        rules.process_line(&mut state, &fields, 20, "unrelated");
+        assert_eq!(names(&state), vec![("KernelPanic", false)]);
+        assert_eq!(state.events[0].machine_id, "m1");
+
+        rules.process_line(&mut state, &fields, 30, "host login: ");
+        assert_eq!(
+            names(&state),
+            vec![("KernelPanic", true), ("Booted", false)]
+        );
+    }
+
+    #[test]
+    fn test_count_in_duration_constraint() {
+        let rules = RuleSet::new(vec![EventType {
+            constraints: Some(EventConstraints {
+                duration: Some(60),
+                count: Some(3),
+                preceded_by: None,
+            }),
+            ..event_type("EccStorm", "ECC error")
+        }])
+        .unwrap();
+        let fields = HashMap::new();
+        let mut state = SourceState::default();
+
+        rules.process_line(&mut state, &fields, 0, "ECC error");
+        rules.process_line(&mut state, &fields, 10, "ECC error");
+        // outside the window: restarts counting
+        rules.process_line(&mut state, &fields, 100, "ECC error");
+        assert!(state.events.is_empty());
+        assert_eq!(state.pending_event_count, 1);
+
+        rules.process_line(&mut state, &fields, 110, "ECC error");
+        rules.process_line(&mut state, &fields, 120, "ECC error");
+        assert_eq!(names(&state), vec![("EccStorm", false)]);
+        assert!(state.pending_event.is_none());
+    }
+
+    #[test]
+    fn test_preceded_by_constraint() {
+        let rules = RuleSet::new(vec![
+            event_type("A", "^a$"),
+            event_type("B", "^b$"),
+            EventType {
+                constraints: Some(EventConstraints {
+                    duration: None,
+                    count: None,
+                    preceded_by: Some(vec!["A".to_string(), "B".to_string()]),
+                }),
+                ..event_type("AThenB", "^c$")
+            },
+        ])
+        .unwrap();
+        let fields = HashMap::new();
+        let mut state = SourceState::default();
+
+        for line in ["b", "a", "c"] {
+            rules.process_line(&mut state, &fields, 0, line);
+        }
+        assert_eq!(names(&state), vec![("B", false), ("A", false)]);
+
+        for line in ["b", "c"] {
+            rules.process_line(&mut state, &fields, 0, line);
+        }
+        assert_eq!(
+            names(&state),
+            vec![("B", false), ("A", false), ("B", false), ("AThenB", false)]
+        );
+    }
+
+    #[test]
+    fn test_rejects_empty_regex() {
+        assert!(RuleSet::new(vec![event_type("Empty", "")]).is_err());
+    }
+}
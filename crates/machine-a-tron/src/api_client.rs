@@ -215,6 +215,8 @@ impl ApiClient {
                 last_dhcp_requests: vec![],
                 dpu_extension_service_version: None,
                 dpu_extension_services: vec![],
+                network_config_rollback: None,
+                applied_nvue_config: None,
             })
             .await
             .map_err(ClientApiError::InvocationError)
@@ -310,6 +312,63 @@ impl ApiClient {
             .map_err(ClientApiError::InvocationError)
     }
 
+    pub async fn release_instance(&self, instance_id: InstanceId) -> ClientApiResult<()> {
+        self.0
+            .release_instance(rpc::forge::InstanceReleaseRequest {
+                id: Some(instance_id),
+                issue: None,
+                is_repair_tenant: None,
+            })
+            .await
+            .map_err(ClientApiError::InvocationError)
+            .map(|_| ())
+    }
+
+    /// Requests reprovisioning of all DPUs of a host. Carbide only starts reprovisioning hosts
+    /// which carry a HostUpdateInProgress alert, so this places one first (the state machine
+    /// removes it again once reprovisioning completes.)
+    pub async fn trigger_dpu_reprovisioning(
+        &self,
+        host_machine_id: MachineId,
+        update_firmware: bool,
+    ) -> ClientApiResult<()> {
+        self.0
+            .insert_machine_health_report(rpc::forge::InsertMachineHealthReportRequest {
+                machine_id: Some(host_machine_id),
+                health_report_entry: Some(rpc::forge::HealthReportEntry {
+                    report: Some(rpc::health::HealthReport {
+                        source: "host-update".to_string(),
+                        triggered_by: None,
+                        observed_at: None,
+                        successes: Vec::new(),
+                        alerts: vec![rpc::health::HealthProbeAlert {
+                            id: "HostUpdateInProgress".to_string(),
+                            target: Some("machine-a-tron".to_string()),
+                            in_alert_since: None,
+                            message: "Reprovisioning requested by machine-a-tron scenario"
+                                .to_string(),
+                            tenant_message: None,
+                            classifications: vec!["PreventAllocations".to_string()],
+                        }],
+                    }),
+                    mode: rpc::forge::HealthReportApplyMode::Merge as i32,
+                }),
+            })
+            .await
+            .map_err(ClientApiError::InvocationError)?;
+
+        self.0
+            .trigger_dpu_reprovisioning(rpc::forge::DpuReprovisioningRequest {
+                dpu_id: None,
+                machine_id: Some(host_machine_id),
+                mode: rpc::forge::dpu_reprovisioning_request::Mode::Set as i32,
+                initiator: rpc::forge::UpdateInitiator::AdminCli as i32,
+                update_firmware,
+            })
+            .await
+            .map_err(ClientApiError::InvocationError)
+    }
+
     pub async fn force_delete_machine(
         &self,
         machine_id: String,
@@ -462,6 +521,7 @@ impl ApiClient {
                 result: 0,
                 message: "".to_string(),
             }),
+            media_sanitization: vec![],
             result: 0,
         };
 
@@ -63,6 +63,30 @@ pub struct MachineATronArgs {
         env = "MACHINE_A_TRON_CONFIG_PATH"
     )]
     pub config_file: String,
+
+    #[clap(long, env = "MACHINE_A_TRON_SCENARIO")]
+    #[clap(
+        help = "Run the given YAML scenario without the TUI, then exit. Fails if any phase of the scenario fails."
+    )]
+    pub scenario: Option<String>,
+
+    #[clap(long, env = "MACHINE_A_TRON_LOAD", conflicts_with = "scenario")]
+    #[clap(
+        help = "Generate API load as described by the given YAML load profile without the TUI, then exit."
+    )]
+    pub load: Option<String>,
+
+    #[clap(long, requires = "load")]
+    #[clap(
+        help = "Let the load profile act on behalf of machines machine-a-tron did not create, including real hardware."
+    )]
+    pub allow_real_machines: bool,
+
+    #[clap(long)]
+    #[clap(
+        help = "Directory to write the scenario report.json and junit.xml, or the load-report.json into"
+    )]
+    pub report_dir: Option<String>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
@@ -20,7 +20,7 @@ use std::sync::{Arc, Mutex, RwLock};
 use std::time::{Duration, Instant};
 
 use bmc_mock::{
-    BmcCommand, HostMachineInfo, MachineInfo, SetSystemPowerResult, SystemPowerControl,
+    BmcCommand, HostMachineInfo, MachineInfo, SetSystemPowerResult, SystemPowerControl, bug,
 };
 use carbide_uuid::machine::MachineId;
 use eyre::Context;
@@ -368,6 +368,14 @@ impl HostMachine {
                 self.api_state = api_state;
                 HandleMessageResult::ContinuePolling
             }
+            HostMachineMessage::InjectBmcBugs(args, reply) => {
+                let injected_bugs = self.state_machine.injected_bugs();
+                if let Some(injected_bugs) = &injected_bugs {
+                    injected_bugs.update_args(args);
+                }
+                _ = reply.send(injected_bugs.is_some());
+                HandleMessageResult::ContinuePolling
+            }
         }
     }
 
@@ -490,6 +498,7 @@ enum HostMachineMessage {
     AttachToUI(Option<mpsc::Sender<UiUpdate>>),
     SetPaused(bool),
     SetApiState(String),
+    InjectBmcBugs(bug::Args, oneshot::Sender<bool>),
 }
 
 #[derive(Debug)]
@@ -548,6 +557,22 @@ impl HostMachineHandle {
         ))
     }
 
+    /// Replaces the bugs injected into this host's BMC mock. Fails if the BMC mock has not been
+    /// started yet (ie. the host has not gotten a BMC address via DHCP.)
+    pub async fn inject_bmc_bugs(&self, args: bug::Args) -> eyre::Result<()> {
+        let (tx, rx) = oneshot::channel();
+        self.0
+            .message_tx
+            .send(HostMachineMessage::InjectBmcBugs(args, tx))?;
+        if !rx.await? {
+            return Err(eyre::eyre!(
+                "BMC mock for host {} is not running",
+                self.0.mat_id
+            ));
+        }
+        Ok(())
+    }
+
     pub fn attach_to_tui(&self, tui_event_tx: Option<mpsc::Sender<UiUpdate>>) -> eyre::Result<()> {
         Ok(self
             .0
@@ -21,11 +21,17 @@ mod config;
 mod dhcp_wrapper;
 mod dpu_machine;
 mod host_machine;
+mod load;
+mod load_generator;
+mod load_report;
 mod machine_a_tron;
 mod machine_fsm;
 mod machine_state_machine;
 mod machine_utils;
 mod mock_ssh_server;
+mod scenario;
+mod scenario_report;
+mod scenario_runner;
 mod subnet;
 mod tabs;
 mod tui;
@@ -41,12 +47,18 @@ pub use config::{
 };
 pub use dpu_machine::DpuMachineHandle;
 pub use host_machine::HostMachineHandle;
+pub use load::LoadProfile;
+pub use load_generator::LoadGenerator;
+pub use load_report::LoadReport;
 pub use machine_a_tron::{AppEvent, MachineATron};
 pub use machine_state_machine::BmcRegistrationMode;
 pub use mock_ssh_server::{
     Credentials as MockSshCredentials, MockSshServerHandle, PromptBehavior,
     spawn as spawn_mock_ssh_server,
 };
+pub use scenario::Scenario;
+pub use scenario_report::ScenarioReport;
+pub use scenario_runner::ScenarioRunner;
 pub use tui::{Tui, UiUpdate};
 pub use tui_host_logs::TuiHostLogs;
 
@@ -0,0 +1,247 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::path::Path;
+use std::time::Duration;
+
+use duration_str::deserialize_duration;
+use serde::Deserialize;
+
+use crate::config::MachineATronConfig;
+
+/// A [`LoadProfile`] describes a load test against carbide-api: how many DPU agents, scouts and
+/// health reporters to simulate, how often each of them calls the API, and how many DHCP
+/// discoveries to send. It is loaded from YAML and run by [`crate::LoadGenerator`], which produces
+/// a [`crate::LoadReport`].
+///
+/// Simulated agents do not run the machine-a-tron state machines. Instead each of them acts on
+/// behalf of one of the machine-a-tron machines carbide already knows about (agents are assigned
+/// round-robin, so there can be more agents than machines), which makes it cheap to simulate
+/// thousands of them.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct LoadProfile {
+    pub name: String,
+    /// The machine config section to take the DHCP relay address, template directory and DPU
+    /// agent version from
+    pub machines: String,
+    /// How long to generate load for, including the warmup
+    #[serde(deserialize_with = "deserialize_duration")]
+    pub duration: Duration,
+    /// Requests completing during this initial period are not included in the report
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub warmup: Duration,
+    #[serde(default)]
+    pub dpu_agents: Option<DpuAgentLoad>,
+    #[serde(default)]
+    pub scouts: Option<ScoutLoad>,
+    #[serde(default)]
+    pub health_reports: Option<HealthReportLoad>,
+    #[serde(default)]
+    pub dhcp: Option<DhcpLoad>,
+    #[serde(default)]
+    pub metrics: Option<MetricsScrape>,
+}
+
+/// Every `interval`, each DPU agent fetches its network config with GetManagedHostNetworkConfig
+/// and acknowledges it with RecordDpuNetworkStatus, like forge-dpu-agent does.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct DpuAgentLoad {
+    pub count: u32,
+    #[serde(
+        default = "default_dpu_agent_interval",
+        deserialize_with = "deserialize_duration"
+    )]
+    pub interval: Duration,
+}
+
+/// Every `interval`, each scout polls ForgeAgentControl for its next action.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ScoutLoad {
+    pub count: u32,
+    #[serde(
+        default = "default_scout_interval",
+        deserialize_with = "deserialize_duration"
+    )]
+    pub interval: Duration,
+}
+
+/// Every `interval`, each reporter inserts a host health report with `probes` successful probes
+/// through InsertMachineHealthReport. The reports are removed again once the load test ends.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct HealthReportLoad {
+    pub count: u32,
+    #[serde(
+        default = "default_health_report_interval",
+        deserialize_with = "deserialize_duration"
+    )]
+    pub interval: Duration,
+    #[serde(default = "default_health_report_probes")]
+    pub probes: u32,
+}
+
+/// Sends `rate_per_second` DiscoverDhcp requests through the admin DHCP relay of the machine
+/// config section. MAC addresses are taken from a pool of `mac_pool_size` locally administered
+/// addresses. The machine interfaces carbide creates for them are deleted once the load test ends.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct DhcpLoad {
+    pub rate_per_second: f64,
+    #[serde(default = "default_mac_pool_size")]
+    pub mac_pool_size: u32,
+}
+
+/// Scrapes the carbide-api prometheus endpoint for state controller iteration latencies.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct MetricsScrape {
+    pub url: String,
+    #[serde(
+        default = "default_scrape_interval",
+        deserialize_with = "deserialize_duration"
+    )]
+    pub scrape_interval: Duration,
+}
+
+impl LoadProfile {
+    pub fn from_file(path: &Path) -> eyre::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| eyre::eyre!("could not read load profile {}: {e}", path.display()))?;
+        Self::from_yaml(&contents)
+    }
+
+    pub fn from_yaml(yaml: &str) -> eyre::Result<Self> {
+        Ok(serde_yaml::from_str(yaml)?)
+    }
+
+    pub fn validate(&self, config: &MachineATronConfig) -> eyre::Result<()> {
+        if !config.machines.contains_key(&self.machines) {
+            return Err(eyre::eyre!(
+                "load profile {} uses unknown machine config {}",
+                self.name,
+                self.machines
+            ));
+        }
+        if self.warmup >= self.duration {
+            return Err(eyre::eyre!(
+                "load profile {} has a warmup at least as long as its duration",
+                self.name
+            ));
+        }
+        if let Some(dhcp) = &self.dhcp
+            && (dhcp.rate_per_second <= 0.0 || dhcp.mac_pool_size == 0)
+        {
+            return Err(eyre::eyre!(
+                "load profile {} needs a positive DHCP rate and MAC pool size",
+                self.name
+            ));
+        }
+        let intervals = [
+            self.dpu_agents.as_ref().map(|l| l.interval),
+            self.scouts.as_ref().map(|l| l.interval),
+            self.health_reports.as_ref().map(|l| l.interval),
+            self.metrics.as_ref().map(|l| l.scrape_interval),
+        ];
+        if intervals.into_iter().flatten().any(|i| i.is_zero()) {
+            return Err(eyre::eyre!(
+                "load profile {} has a zero interval",
+                self.name
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn default_dpu_agent_interval() -> Duration {
+    Duration::from_secs(30)
+}
+
+fn default_scout_interval() -> Duration {
+    Duration::from_secs(60)
+}
+
+fn default_health_report_interval() -> Duration {
+    Duration::from_secs(60)
+}
+
+fn default_health_report_probes() -> u32 {
+    10
+}
+
+fn default_mac_pool_size() -> u32 {
+    100
+}
+
+fn default_scrape_interval() -> Duration {
+    Duration::from_secs(15)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_example_load_profile() {
+        let config: MachineATronConfig = toml::from_str(
+            r#"
+carbide_api_url = "https://carbide-api.forge:443"
+interface = "br-77cbb29de011"
+
+[machines.config]
+host_count = 0
+dpu_per_host_count = 1
+dpu_reboot_delay = 1
+host_reboot_delay = 1
+vpc_count = 0
+subnets_per_vpc = 0
+admin_dhcp_relay_address = "192.168.176.1"
+oob_dhcp_relay_address = "192.168.192.1"
+"#,
+        )
+        .expect("Could not parse config");
+
+        let profile = LoadProfile::from_yaml(include_str!("../config/load-profile.yaml"))
+            .expect("Could not parse example load profile");
+        profile
+            .validate(&config)
+            .expect("Example load profile is invalid");
+
+        assert_eq!(profile.duration, Duration::from_secs(15 * 60));
+        assert_eq!(profile.warmup, Duration::from_secs(60));
+        assert_eq!(
+            profile.dpu_agents,
+            Some(DpuAgentLoad {
+                count: 4000,
+                interval: Duration::from_secs(30),
+            })
+        );
+        assert_eq!(profile.health_reports.unwrap().probes, 10);
+        assert_eq!(profile.dhcp.unwrap().mac_pool_size, 500);
+        assert_eq!(
+            profile.metrics.unwrap().scrape_interval,
+            Duration::from_secs(15)
+        );
+
+        let profile = LoadProfile {
+            warmup: profile.duration,
+            ..LoadProfile::from_yaml(include_str!("../config/load-profile.yaml")).unwrap()
+        };
+        assert!(profile.validate(&config).is_err());
+    }
+}
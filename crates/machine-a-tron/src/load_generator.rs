@@ -0,0 +1,483 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::collections::{BTreeMap, BTreeSet};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use carbide_uuid::machine::{MachineId, MachineInterfaceId};
+use chrono::Utc;
+use mac_address::MacAddress;
+use rpc::forge::{InterfaceDeleteQuery, MachineSearchConfig};
+use tokio::task::JoinSet;
+use tokio::time::MissedTickBehavior;
+
+use crate::api_client::{ClientApiError, DpuNetworkStatusArgs};
+use crate::config::{MachineATronContext, MachineConfig};
+use crate::load::{LoadProfile, MetricsScrape};
+use crate::load_report::{
+    IterationLatencyReport, IterationLatencyScrape, LoadReport, RpcStats, parse_iteration_latencies,
+};
+use crate::machine_state_machine::network_status_interfaces;
+
+/// Health report source used for the reports inserted by the load generator
+const HEALTH_REPORT_SOURCE: &str = "machine-a-tron-load";
+
+/// Generates API load as described by a [`LoadProfile`], by simulating DPU agents, scouts, health
+/// reporters and DHCP clients on behalf of the machines carbide already knows about.
+///
+/// Unless `allow_real_machines` is set, only machines with a machine-a-tron BMC mock are used, so a
+/// load test against a site with real hardware doesn't report health or network status for it.
+pub struct LoadGenerator {
+    app_context: Arc<MachineATronContext>,
+    allow_real_machines: bool,
+}
+
+/// Collects RPC latencies, ignoring requests which completed during the warmup
+#[derive(Clone)]
+struct StatsRecorder {
+    measure_from: Instant,
+    stats: Arc<Mutex<BTreeMap<&'static str, RpcStats>>>,
+}
+
+impl StatsRecorder {
+    fn record<T>(&self, rpc: &'static str, started: Instant, result: &Result<T, ClientApiError>) {
+        let now = Instant::now();
+        if now < self.measure_from {
+            return;
+        }
+        let error = result.as_ref().err().map(|e| match e {
+            ClientApiError::InvocationError(status) => format!("{:?}", status.code()),
+            ClientApiError::ConfigError(_) => "ConfigError".to_string(),
+            ClientApiError::ConnectFailed(_) => "ConnectFailed".to_string(),
+        });
+        self.stats
+            .lock()
+            .unwrap()
+            .entry(rpc)
+            .or_default()
+            .record(now - started, error);
+    }
+}
+
+impl LoadGenerator {
+    pub fn new(app_context: Arc<MachineATronContext>, allow_real_machines: bool) -> Self {
+        Self {
+            app_context,
+            allow_real_machines,
+        }
+    }
+
+    /// Applies the load for the duration of the profile. Only returns an error if the load could
+    /// not be started; failed requests are recorded in the report.
+    pub async fn run(self, profile: &LoadProfile) -> eyre::Result<LoadReport> {
+        profile.validate(&self.app_context.app_config)?;
+        let machine_config = self.app_context.app_config.machines[&profile.machines].clone();
+
+        let dpu_ids = self.find_machine_ids(true).await?;
+        let host_ids = self.find_machine_ids(false).await?;
+        if profile.dpu_agents.is_some() && dpu_ids.is_empty() {
+            return Err(self.no_machines_error("DPUs", "agents"));
+        }
+        if (profile.scouts.is_some() || profile.health_reports.is_some()) && host_ids.is_empty() {
+            return Err(self.no_machines_error("hosts", "scouts"));
+        }
+
+        tracing::info!(
+            dpus = dpu_ids.len(),
+            hosts = host_ids.len(),
+            "Running load profile {}",
+            profile.name
+        );
+        let started_at = Utc::now();
+        let recorder = StatsRecorder {
+            measure_from: Instant::now() + profile.warmup,
+            stats: Default::default(),
+        };
+        let scrapes = Arc::new(Mutex::new(Vec::new()));
+        let dhcp_interfaces = Arc::new(Mutex::new(BTreeSet::new()));
+        let mut tasks = JoinSet::new();
+
+        if let Some(load) = &profile.dpu_agents {
+            warn_if_shared("DPU agents", load.count, dpu_ids.len());
+            for i in 0..load.count {
+                let dpu_id = dpu_ids[i as usize % dpu_ids.len()];
+                let app_context = self.app_context.clone();
+                let machine_config = machine_config.clone();
+                let recorder = recorder.clone();
+                tasks.spawn(run_periodic(
+                    load.interval * i / load.count,
+                    load.interval,
+                    move || {
+                        dpu_agent_tick(
+                            app_context.clone(),
+                            machine_config.clone(),
+                            recorder.clone(),
+                            dpu_id,
+                        )
+                    },
+                ));
+            }
+        }
+
+        if let Some(load) = &profile.scouts {
+            warn_if_shared("scouts", load.count, host_ids.len());
+            for i in 0..load.count {
+                let host_id = host_ids[i as usize % host_ids.len()];
+                let app_context = self.app_context.clone();
+                let recorder = recorder.clone();
+                tasks.spawn(run_periodic(
+                    load.interval * i / load.count,
+                    load.interval,
+                    move || {
+                        let app_context = app_context.clone();
+                        let recorder = recorder.clone();
+                        async move {
+                            let started = Instant::now();
+                            let result = app_context
+                                .forge_api_client
+                                .forge_agent_control(host_id)
+                                .await
+                                .map_err(ClientApiError::from);
+                            recorder.record("ForgeAgentControl", started, &result);
+                        }
+                    },
+                ));
+            }
+        }
+
+        let mut reported_hosts = BTreeSet::new();
+        if let Some(load) = &profile.health_reports {
+            warn_if_shared("health reporters", load.count, host_ids.len());
+            for i in 0..load.count {
+                let host_id = host_ids[i as usize % host_ids.len()];
+                reported_hosts.insert(host_id);
+                let app_context = self.app_context.clone();
+                let recorder = recorder.clone();
+                let probes = load.probes;
+                tasks.spawn(run_periodic(
+                    load.interval * i / load.count,
+                    load.interval,
+                    move || {
+                        health_report_tick(app_context.clone(), recorder.clone(), host_id, probes)
+                    },
+                ));
+            }
+        }
+
+        if let Some(load) = &profile.dhcp {
+            let app_context = self.app_context.clone();
+            let machine_config = machine_config.clone();
+            let recorder = recorder.clone();
+            let dhcp_interfaces = dhcp_interfaces.clone();
+            let (rate, mac_pool_size) = (load.rate_per_second, load.mac_pool_size);
+            tasks.spawn(async move {
+                // DHCP discoveries are sent open-loop at a fixed rate, so slow responses don't
+                // reduce the request rate.
+                let mut ticker = tokio::time::interval(Duration::from_secs_f64(1.0 / rate));
+                ticker.set_missed_tick_behavior(MissedTickBehavior::Burst);
+                let mut in_flight = JoinSet::new();
+                for i in 0u32.. {
+                    ticker.tick().await;
+                    while in_flight.try_join_next().is_some() {}
+                    let api_client = app_context.api_client();
+                    let machine_config = machine_config.clone();
+                    let recorder = recorder.clone();
+                    let dhcp_interfaces = dhcp_interfaces.clone();
+                    in_flight.spawn(async move {
+                        let started = Instant::now();
+                        let result = api_client
+                            .discover_dhcp(
+                                load_mac_address(i % mac_pool_size),
+                                machine_config.template_dir.clone(),
+                                machine_config.admin_dhcp_relay_address.to_string(),
+                                None,
+                            )
+                            .await;
+                        recorder.record("DiscoverDhcp", started, &result);
+                        if let Ok(rpc::forge::DhcpRecord {
+                            machine_interface_id: Some(interface_id),
+                            ..
+                        }) = result
+                        {
+                            dhcp_interfaces.lock().unwrap().insert(interface_id);
+                        }
+                    });
+                }
+            });
+        }
+
+        if let Some(metrics) = &profile.metrics {
+            let metrics = metrics.clone();
+            let scrapes = scrapes.clone();
+            let start = tokio::time::Instant::from_std(recorder.measure_from);
+            tasks.spawn(async move {
+                let mut ticker = tokio::time::interval_at(start, metrics.scrape_interval);
+                ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+                loop {
+                    ticker.tick().await;
+                    if let Some(scrape) = scrape_metrics(&metrics).await {
+                        scrapes.lock().unwrap().push(scrape);
+                    }
+                }
+            });
+        }
+
+        tokio::time::sleep(profile.duration).await;
+        tasks.shutdown().await;
+        let measured = recorder.measure_from.elapsed();
+
+        if let Some(metrics) = &profile.metrics
+            && let Some(scrape) = scrape_metrics(metrics).await
+        {
+            scrapes.lock().unwrap().push(scrape);
+        }
+
+        for host_id in reported_hosts {
+            if let Err(e) = self
+                .app_context
+                .forge_api_client
+                .remove_machine_health_report(rpc::forge::RemoveMachineHealthReportRequest {
+                    machine_id: Some(host_id),
+                    source: HEALTH_REPORT_SOURCE.to_string(),
+                })
+                .await
+            {
+                tracing::warn!(%host_id, "Failed to remove load test health report: {e}");
+            }
+        }
+
+        let dhcp_interfaces = std::mem::take(&mut *dhcp_interfaces.lock().unwrap());
+        self.delete_interfaces(dhcp_interfaces).await;
+
+        let rpcs = recorder
+            .stats
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(rpc, stats)| (rpc.to_string(), stats.report(measured)))
+            .collect();
+        let iteration_latencies = IterationLatencyReport::from_scrapes(&scrapes.lock().unwrap());
+        Ok(LoadReport {
+            profile: profile.name.clone(),
+            started_at,
+            measured_secs: measured.as_secs_f64(),
+            dpu_count: dpu_ids.len(),
+            host_count: host_ids.len(),
+            rpcs,
+            iteration_latencies,
+        })
+    }
+
+    /// Finds the DPUs or hosts to act on behalf of
+    async fn find_machine_ids(&self, dpus: bool) -> eyre::Result<Vec<MachineId>> {
+        let machine_ids = self
+            .app_context
+            .forge_api_client
+            .find_machine_ids(MachineSearchConfig {
+                include_dpus: dpus,
+                exclude_hosts: dpus,
+                ..Default::default()
+            })
+            .await?
+            .machine_ids;
+        if self.allow_real_machines {
+            return Ok(machine_ids);
+        }
+
+        // Max of 100 machine IDs at a time
+        let mut simulated_ids = Vec::new();
+        for chunk in machine_ids.chunks(100) {
+            let machines = self
+                .app_context
+                .api_client()
+                .get_machines(chunk.to_vec())
+                .await?;
+            simulated_ids.extend(
+                machines
+                    .into_iter()
+                    .filter(is_simulated_machine)
+                    .filter_map(|machine| machine.id),
+            );
+        }
+        let skipped = machine_ids.len() - simulated_ids.len();
+        if skipped > 0 {
+            tracing::info!(
+                "Skipping {skipped} machines not simulated by machine-a-tron; pass --allow-real-machines to include them"
+            );
+        }
+        Ok(simulated_ids)
+    }
+
+    /// Deletes the machine interfaces carbide created for the simulated DHCP clients
+    async fn delete_interfaces(&self, interface_ids: BTreeSet<MachineInterfaceId>) {
+        for interface_id in interface_ids {
+            if let Err(e) = self
+                .app_context
+                .forge_api_client
+                .delete_interface(InterfaceDeleteQuery {
+                    id: Some(interface_id),
+                })
+                .await
+            {
+                tracing::warn!(%interface_id, "Failed to delete load test machine interface: {e}");
+            }
+        }
+    }
+
+    fn no_machines_error(&self, machines: &str, agents: &str) -> eyre::Report {
+        if self.allow_real_machines {
+            eyre::eyre!("carbide does not know any {machines} to simulate {agents} for")
+        } else {
+            eyre::eyre!(
+                "carbide does not know any machine-a-tron {machines} to simulate {agents} for; pass --allow-real-machines to use real ones"
+            )
+        }
+    }
+}
+
+/// Whether the BMC of `machine` is a machine-a-tron BMC mock
+fn is_simulated_machine(machine: &rpc::Machine) -> bool {
+    machine
+        .bmc_info
+        .as_ref()
+        .and_then(|bmc_info| bmc_info.mac.as_deref())
+        .and_then(|mac| mac.parse::<MacAddress>().ok())
+        .is_some_and(bmc_mock::is_mock_mac_address)
+}
+
+/// Calls `tick` every `interval`, starting after `offset`. Offsets are used to spread agents
+/// evenly over their interval instead of having all of them call the API at once.
+async fn run_periodic<F, Fut>(offset: Duration, interval: Duration, mut tick: F)
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let mut ticker = tokio::time::interval_at(tokio::time::Instant::now() + offset, interval);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+        ticker.tick().await;
+        tick().await;
+    }
+}
+
+/// What forge-dpu-agent does every network status interval: fetch the network config and
+/// acknowledge it.
+async fn dpu_agent_tick(
+    app_context: Arc<MachineATronContext>,
+    machine_config: Arc<MachineConfig>,
+    recorder: StatsRecorder,
+    dpu_id: MachineId,
+) {
+    let started = Instant::now();
+    let network_config = app_context
+        .forge_api_client
+        .get_managed_host_network_config(dpu_id)
+        .await
+        .map_err(ClientApiError::from);
+    recorder.record("GetManagedHostNetworkConfig", started, &network_config);
+    let Ok(network_config) = network_config else {
+        return;
+    };
+
+    let (instance_network_config_version, interfaces) =
+        match network_status_interfaces(&network_config, None) {
+            Ok(status) => status,
+            Err(e) => {
+                tracing::warn!(%dpu_id, "Not acknowledging network config: {e}");
+                return;
+            }
+        };
+    let started = Instant::now();
+    let result = app_context
+        .api_client()
+        .record_dpu_network_status(DpuNetworkStatusArgs {
+            dpu_machine_id: dpu_id,
+            network_config_version: network_config.managed_host_config_version.clone(),
+            instance_network_config_version,
+            instance_config_version: None,
+            instance_id: network_config.instance_id,
+            interfaces,
+            machine_config: &machine_config,
+        })
+        .await;
+    recorder.record("RecordDpuNetworkStatus", started, &result);
+}
+
+async fn health_report_tick(
+    app_context: Arc<MachineATronContext>,
+    recorder: StatsRecorder,
+    host_id: MachineId,
+    probes: u32,
+) {
+    let report = rpc::health::HealthReport {
+        source: HEALTH_REPORT_SOURCE.to_string(),
+        triggered_by: None,
+        observed_at: None,
+        successes: (0..probes)
+            .map(|i| rpc::health::HealthProbeSuccess {
+                id: "MachineATronLoad".to_string(),
+                target: Some(format!("probe-{i}")),
+            })
+            .collect(),
+        alerts: Vec::new(),
+    };
+    let started = Instant::now();
+    let result = app_context
+        .forge_api_client
+        .insert_machine_health_report(rpc::forge::InsertMachineHealthReportRequest {
+            machine_id: Some(host_id),
+            health_report_entry: Some(rpc::forge::HealthReportEntry {
+                report: Some(report),
+                mode: rpc::forge::HealthReportApplyMode::Merge as i32,
+            }),
+        })
+        .await
+        .map_err(ClientApiError::from);
+    recorder.record("InsertMachineHealthReport", started, &result);
+}
+
+async fn scrape_metrics(metrics: &MetricsScrape) -> Option<IterationLatencyScrape> {
+    let result = async {
+        reqwest::get(&metrics.url)
+            .await?
+            .error_for_status()?
+            .text()
+            .await
+    };
+    match result.await {
+        Ok(text) => Some(parse_iteration_latencies(&text)),
+        Err(e) => {
+            tracing::warn!(url = %metrics.url, "Failed to scrape carbide-api metrics: {e}");
+            None
+        }
+    }
+}
+
+/// A locally administered MAC address for the `index`th simulated DHCP client
+fn load_mac_address(index: u32) -> MacAddress {
+    let [_, a, b, c] = index.to_be_bytes();
+    MacAddress::new([0x02, 0x4d, 0x41, a, b, c])
+}
+
+fn warn_if_shared(agents: &str, count: u32, machines: usize) {
+    if count as usize > machines {
+        tracing::warn!(
+            "Simulating {count} {agents} for {machines} machines; some machines will be shared"
+        );
+    }
+}
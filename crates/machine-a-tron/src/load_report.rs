@@ -0,0 +1,427 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// Upper bounds (in milliseconds) of the buckets used for RPC latencies. Slower requests end up in
+/// an overflow bucket.
+const LATENCY_BUCKETS_MS: [f64; 14] = [
+    1.0, 2.0, 5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1000.0, 2500.0, 5000.0, 10000.0, 30000.0,
+];
+
+/// The outcome of a [`crate::LoadProfile`] run, as produced by [`crate::LoadGenerator`]: latency
+/// and error statistics for every RPC that was called, and the state controller iteration times
+/// carbide-api reported while the load was applied.
+#[derive(Clone, Debug, Serialize)]
+pub struct LoadReport {
+    pub profile: String,
+    pub started_at: DateTime<Utc>,
+    /// The time over which the statistics were collected, excluding the warmup
+    pub measured_secs: f64,
+    /// Number of distinct DPUs and hosts the simulated agents were spread over
+    pub dpu_count: usize,
+    pub host_count: usize,
+    pub rpcs: BTreeMap<String, RpcReport>,
+    /// Iteration latency per state controller, if metrics were scraped
+    pub iteration_latencies: BTreeMap<String, IterationLatencyReport>,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct RpcReport {
+    pub requests: u64,
+    /// Number of failed requests per gRPC status code (or other error kind)
+    pub errors: BTreeMap<String, u64>,
+    pub error_rate: f64,
+    pub requests_per_sec: f64,
+    pub mean_ms: f64,
+    pub p50_ms: f64,
+    pub p90_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+    /// Cumulative request counts per latency bucket, keyed by the bucket's upper bound in ms
+    pub buckets: Vec<(String, u64)>,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct IterationLatencyReport {
+    pub iterations: u64,
+    pub mean_ms: f64,
+    pub p50_ms: f64,
+    pub p99_ms: f64,
+    /// The highest mean iteration latency observed between two consecutive scrapes
+    pub worst_interval_mean_ms: f64,
+}
+
+/// A fixed-bucket latency histogram. Percentiles are estimated as the upper bound of the bucket
+/// they fall into, like prometheus' `histogram_quantile` does without interpolation.
+#[derive(Clone, Debug, Default)]
+pub struct LatencyHistogram {
+    /// One count per bucket in [`LATENCY_BUCKETS_MS`], plus the overflow bucket
+    counts: [u64; LATENCY_BUCKETS_MS.len() + 1],
+    count: u64,
+    sum_ms: f64,
+    max_ms: f64,
+}
+
+impl LatencyHistogram {
+    pub fn record(&mut self, latency: Duration) {
+        let ms = latency.as_secs_f64() * 1000.0;
+        let bucket = LATENCY_BUCKETS_MS
+            .iter()
+            .position(|le| ms <= *le)
+            .unwrap_or(LATENCY_BUCKETS_MS.len());
+        self.counts[bucket] += 1;
+        self.count += 1;
+        self.sum_ms += ms;
+        self.max_ms = self.max_ms.max(ms);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn mean_ms(&self) -> f64 {
+        if self.count == 0 {
+            return 0.0;
+        }
+        self.sum_ms / self.count as f64
+    }
+
+    pub fn percentile_ms(&self, p: f64) -> f64 {
+        if self.count == 0 {
+            return 0.0;
+        }
+        let rank = ((self.count as f64 * p).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (i, count) in self.counts.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                // The estimate can never exceed the slowest request that was actually seen
+                return LATENCY_BUCKETS_MS
+                    .get(i)
+                    .map_or(self.max_ms, |le| le.min(self.max_ms));
+            }
+        }
+        self.max_ms
+    }
+
+    fn cumulative_buckets(&self) -> Vec<(String, u64)> {
+        let mut seen = 0;
+        self.counts
+            .iter()
+            .enumerate()
+            .map(|(i, count)| {
+                seen += count;
+                let le = LATENCY_BUCKETS_MS
+                    .get(i)
+                    .map_or("+Inf".to_string(), |le| le.to_string());
+                (le, seen)
+            })
+            .collect()
+    }
+}
+
+/// Latencies and errors collected for one RPC
+#[derive(Clone, Debug, Default)]
+pub struct RpcStats {
+    pub latencies: LatencyHistogram,
+    pub errors: BTreeMap<String, u64>,
+}
+
+impl RpcStats {
+    pub fn record(&mut self, latency: Duration, error: Option<String>) {
+        self.latencies.record(latency);
+        if let Some(error) = error {
+            *self.errors.entry(error).or_default() += 1;
+        }
+    }
+
+    pub fn report(&self, measured: Duration) -> RpcReport {
+        let requests = self.latencies.count();
+        let failed: u64 = self.errors.values().sum();
+        RpcReport {
+            requests,
+            errors: self.errors.clone(),
+            error_rate: if requests == 0 {
+                0.0
+            } else {
+                failed as f64 / requests as f64
+            },
+            requests_per_sec: requests as f64 / measured.as_secs_f64().max(f64::EPSILON),
+            mean_ms: self.latencies.mean_ms(),
+            p50_ms: self.latencies.percentile_ms(0.5),
+            p90_ms: self.latencies.percentile_ms(0.9),
+            p99_ms: self.latencies.percentile_ms(0.99),
+            max_ms: self.latencies.max_ms,
+            buckets: self.latencies.cumulative_buckets(),
+        }
+    }
+}
+
+/// The `*_iteration_latency_milliseconds` histograms from one scrape of carbide-api's metrics
+/// endpoint, keyed by the metric name prefix (e.g. `carbide_machines`).
+pub type IterationLatencyScrape = BTreeMap<String, PromHistogram>;
+
+/// A cumulative prometheus histogram, as scraped
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PromHistogram {
+    /// (upper bound, cumulative count), sorted by upper bound
+    pub buckets: Vec<(f64, f64)>,
+    pub sum: f64,
+    pub count: f64,
+}
+
+const ITERATION_LATENCY_SUFFIX: &str = "_iteration_latency_milliseconds";
+
+/// Extracts the iteration latency histograms from a prometheus text exposition. The periodic
+/// enqueuer histograms, which share the suffix, are skipped. Series of the same metric with
+/// different labels (other than `le`) are added together.
+pub fn parse_iteration_latencies(text: &str) -> IterationLatencyScrape {
+    let mut scrape = IterationLatencyScrape::new();
+    for line in text.lines() {
+        if line.starts_with('#') {
+            continue;
+        }
+        let Some((series, value)) = line.rsplit_once(' ') else {
+            continue;
+        };
+        let Ok(value) = value.parse::<f64>() else {
+            continue;
+        };
+        let (name, labels) = series.split_once('{').unwrap_or((series, ""));
+        let Some((prefix, kind)) = name.rsplit_once(ITERATION_LATENCY_SUFFIX) else {
+            continue;
+        };
+        if prefix.ends_with("_enqueuer") {
+            continue;
+        }
+        let histogram = scrape.entry(prefix.to_string()).or_default();
+        match kind {
+            "_sum" => histogram.sum += value,
+            "_count" => histogram.count += value,
+            "_bucket" => {
+                let Some(le) = label_value(labels, "le").and_then(|le| match le {
+                    "+Inf" => Some(f64::INFINITY),
+                    le => le.parse::<f64>().ok(),
+                }) else {
+                    continue;
+                };
+                match histogram.buckets.iter_mut().find(|(b, _)| *b == le) {
+                    Some((_, count)) => *count += value,
+                    None => histogram.buckets.push((le, value)),
+                }
+                histogram.buckets.sort_by(|a, b| a.0.total_cmp(&b.0));
+            }
+            _ => {}
+        }
+    }
+    scrape
+}
+
+fn label_value<'a>(labels: &'a str, name: &str) -> Option<&'a str> {
+    labels
+        .trim_end_matches('}')
+        .split(',')
+        .filter_map(|label| label.split_once('='))
+        .find(|(key, _)| key.trim() == name)
+        .map(|(_, value)| value.trim().trim_matches('"'))
+}
+
+impl PromHistogram {
+    /// The observations made between `earlier` and `self`
+    fn since(&self, earlier: Option<&PromHistogram>) -> PromHistogram {
+        let Some(earlier) = earlier else {
+            return self.clone();
+        };
+        PromHistogram {
+            buckets: self
+                .buckets
+                .iter()
+                .map(|(le, count)| {
+                    let before = earlier
+                        .buckets
+                        .iter()
+                        .find(|(b, _)| b == le)
+                        .map_or(0.0, |(_, c)| *c);
+                    (*le, count - before)
+                })
+                .collect(),
+            sum: self.sum - earlier.sum,
+            count: self.count - earlier.count,
+        }
+    }
+
+    fn mean(&self) -> Option<f64> {
+        (self.count > 0.0).then(|| self.sum / self.count)
+    }
+
+    fn quantile(&self, q: f64) -> f64 {
+        let rank = self.count * q;
+        self.buckets
+            .iter()
+            .find(|(_, count)| *count >= rank)
+            // Observations beyond the largest finite bucket can only be bounded by the mean
+            .map_or(0.0, |(le, _)| {
+                if le.is_finite() {
+                    *le
+                } else {
+                    self.mean().unwrap_or_default()
+                }
+            })
+    }
+}
+
+impl IterationLatencyReport {
+    /// Summarizes the iterations performed between the first and the last of a series of scrapes
+    pub fn from_scrapes(scrapes: &[IterationLatencyScrape]) -> BTreeMap<String, Self> {
+        let (Some(first), Some(last)) = (scrapes.first(), scrapes.last()) else {
+            return BTreeMap::new();
+        };
+        let mut reports = BTreeMap::new();
+        for (name, histogram) in last {
+            let total = histogram.since(first.get(name));
+            if total.count <= 0.0 {
+                continue;
+            }
+            let worst_interval_mean_ms = scrapes
+                .windows(2)
+                .filter_map(|w| w[1].get(name)?.since(w[0].get(name)).mean())
+                .fold(0.0, f64::max);
+            reports.insert(
+                name.clone(),
+                Self {
+                    iterations: total.count as u64,
+                    mean_ms: total.mean().unwrap_or_default(),
+                    p50_ms: total.quantile(0.5),
+                    p99_ms: total.quantile(0.99),
+                    worst_interval_mean_ms,
+                },
+            );
+        }
+        reports
+    }
+}
+
+impl LoadReport {
+    /// A one-line-per-RPC summary for the console
+    pub fn summary(&self) -> String {
+        let mut out = format!(
+            "load profile {}: {:.0}s measured, agents spread over {} DPUs and {} hosts\n",
+            self.profile, self.measured_secs, self.dpu_count, self.host_count
+        );
+        for (rpc, report) in &self.rpcs {
+            _ = writeln!(
+                out,
+                "  {rpc:<32} {:>8} req {:>8.1} rps  errors {:>6.2}%  p50={:.0}ms p90={:.0}ms p99={:.0}ms max={:.0}ms",
+                report.requests,
+                report.requests_per_sec,
+                report.error_rate * 100.0,
+                report.p50_ms,
+                report.p90_ms,
+                report.p99_ms,
+                report.max_ms
+            );
+        }
+        for (controller, report) in &self.iteration_latencies {
+            _ = writeln!(
+                out,
+                "  {controller:<32} {:>8} iterations  mean={:.0}ms p50={:.0}ms p99={:.0}ms worst-interval-mean={:.0}ms",
+                report.iterations,
+                report.mean_ms,
+                report.p50_ms,
+                report.p99_ms,
+                report.worst_interval_mean_ms
+            );
+        }
+        out
+    }
+
+    pub fn to_json(&self) -> eyre::Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn write_to_dir(&self, dir: &Path) -> eyre::Result<()> {
+        std::fs::create_dir_all(dir)?;
+        std::fs::write(dir.join("load-report.json"), self.to_json()?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_histogram_percentiles() {
+        let mut stats = RpcStats::default();
+        for ms in 1..=100 {
+            let error = (ms % 10 == 0).then(|| "Unavailable".to_string());
+            stats.record(Duration::from_millis(ms), error);
+        }
+        let report = stats.report(Duration::from_secs(10));
+
+        assert_eq!(report.requests, 100);
+        assert_eq!(report.errors.get("Unavailable"), Some(&10));
+        assert_eq!(report.error_rate, 0.1);
+        assert_eq!(report.requests_per_sec, 10.0);
+        assert_eq!(report.mean_ms, 50.5);
+        assert_eq!(report.p50_ms, 50.0);
+        assert_eq!(report.p90_ms, 100.0);
+        assert_eq!(report.max_ms, 100.0);
+        assert_eq!(report.buckets[0], ("1".to_string(), 1));
+        assert_eq!(report.buckets.last(), Some(&("+Inf".to_string(), 100)));
+    }
+
+    #[test]
+    fn test_iteration_latencies_from_scrapes() {
+        let scrape = |count: u64, slow: u64| {
+            parse_iteration_latencies(&format!(
+                r#"# HELP carbide_machines_iteration_latency_milliseconds The overall time it took to handle state for all objects
+# TYPE carbide_machines_iteration_latency_milliseconds histogram
+carbide_machines_iteration_latency_milliseconds_bucket{{otel_scope_name="carbide-api",le="100"}} {}
+carbide_machines_iteration_latency_milliseconds_bucket{{otel_scope_name="carbide-api",le="1000"}} {count}
+carbide_machines_iteration_latency_milliseconds_bucket{{otel_scope_name="carbide-api",le="+Inf"}} {count}
+carbide_machines_iteration_latency_milliseconds_sum{{otel_scope_name="carbide-api"}} {}
+carbide_machines_iteration_latency_milliseconds_count{{otel_scope_name="carbide-api"}} {count}
+carbide_machines_enqueuer_iteration_latency_milliseconds_count{{otel_scope_name="carbide-api"}} 7
+carbide_api_grpc_server_duration_milliseconds_count 12
+"#,
+                count - slow,
+                (count - slow) * 50 + slow * 500
+            ))
+        };
+        let scrapes = [scrape(10, 0), scrape(20, 0), scrape(30, 10)];
+        assert_eq!(scrapes[0].len(), 1);
+
+        let reports = IterationLatencyReport::from_scrapes(&scrapes);
+        assert_eq!(
+            reports.get("carbide_machines"),
+            Some(&IterationLatencyReport {
+                iterations: 20,
+                mean_ms: 275.0,
+                p50_ms: 100.0,
+                p99_ms: 1000.0,
+                worst_interval_mean_ms: 500.0,
+            })
+        );
+    }
+}
@@ -17,13 +17,14 @@
 use std::collections::HashSet;
 use std::sync::Arc;
 
+use bmc_mock::HostHardwareType;
 use futures::future::try_join_all;
 use rpc::forge::VpcVirtualizationType;
 use tokio::sync::mpsc;
 use uuid::Uuid;
 
 use crate::PersistedHostMachine;
-use crate::config::MachineATronContext;
+use crate::config::{MachineATronContext, MachineConfig};
 use crate::host_machine::{HostMachine, HostMachineHandle};
 use crate::machine_utils::get_next_free_machine;
 use crate::subnet::Subnet;
@@ -97,7 +98,51 @@ impl MachineATron {
             })
             .collect();
 
-        for machine in &machines {
+        self.add_expected_machines(&machines).await;
+
+        Ok(machines)
+    }
+
+    /// Constructs `count` new hosts from the given machine config section, ignoring its
+    /// `host_count` and any persisted machines. If `hw_type` is set, it overrides the hardware
+    /// type of the config section.
+    pub async fn make_hosts(
+        &self,
+        config_name: &str,
+        count: u32,
+        hw_type: Option<HostHardwareType>,
+        paused: bool,
+    ) -> eyre::Result<Vec<HostMachineHandle>> {
+        let Some(config) = self.app_context.app_config.machines.get(config_name) else {
+            return Err(eyre::eyre!("no machine config named {config_name}"));
+        };
+        let config = match hw_type {
+            Some(hw_type) if hw_type != config.hw_type => Arc::new(MachineConfig {
+                hw_type,
+                ..config.as_ref().clone()
+            }),
+            _ => config.clone(),
+        };
+
+        tracing::info!("Constructing {count} machines for config {config_name}");
+        let machines: Vec<HostMachineHandle> = (0..count)
+            .map(|_| {
+                HostMachine::new(
+                    self.app_context.clone(),
+                    config_name.to_string(),
+                    config.clone(),
+                )
+                .start(paused)
+            })
+            .collect();
+
+        self.add_expected_machines(&machines).await;
+
+        Ok(machines)
+    }
+
+    async fn add_expected_machines(&self, machines: &[HostMachineHandle]) {
+        for machine in machines {
             // Inform the API that we have finished our reboot (ie. scout is now running)
             self.app_context
                 .api_client()
@@ -112,8 +157,6 @@ impl MachineATron {
                 })
                 .ok();
         }
-
-        Ok(machines)
     }
 
     pub async fn run(
@@ -122,57 +165,11 @@ impl MachineATron {
         tui_event_tx: Option<mpsc::Sender<UiUpdate>>,
         mut app_rx: mpsc::Receiver<AppEvent>,
     ) -> eyre::Result<()> {
-        let mut vpc_handles: Vec<Vpc> = Vec::new();
-        let mut subnet_handles: Vec<Subnet> = Vec::new();
         // Represents the mat_id of machines which are Assigned to a forge Instance
         let mut assigned_mat_ids: HashSet<Uuid> = HashSet::new();
 
-        if let Some(host_str) = self
-            .app_context
-            .app_config
-            .configure_carbide_bmc_proxy_host
-            .as_ref()
-        {
-            let host_port_str =
-                format!("{}:{}", host_str, self.app_context.app_config.bmc_mock_port);
-            tracing::info!("Configuring carbide API to use {host_port_str} as bmc_proxy",);
-            _ = self
-                .app_context
-                .api_client()
-                .configure_bmc_proxy_host(host_port_str)
-                .await
-                .inspect_err(
-                    |e| tracing::warn!(error = ?e, "Could not configure carbide bmc_proxy"),
-                )
-        }
-
-        for (_config_name, config) in self.app_context.app_config.machines.iter() {
-            let network_virtualization_type =
-                parse_network_virtualization_type(config.network_virtualization_type.as_deref());
-            for _ in 0..config.vpc_count {
-                let app_context = self.app_context.clone();
-                let vpc = Vpc::new(
-                    app_context,
-                    tui_event_tx.clone(),
-                    network_virtualization_type,
-                )
-                .await;
-
-                for _ in 0..config.subnets_per_vpc {
-                    let app_context = self.app_context.clone();
-
-                    match Subnet::new(app_context, tui_event_tx.clone(), &vpc).await {
-                        Ok(subnet) => {
-                            subnet_handles.push(subnet);
-                        }
-                        Err(e) => {
-                            tracing::error!("Error creating network segment: {}", e);
-                        }
-                    }
-                }
-                vpc_handles.push(vpc);
-            }
-        }
+        self.configure_bmc_proxy().await;
+        let networks = self.create_networks(tui_event_tx.clone()).await;
 
         for machine_handle in &machine_handles {
             machine_handle.attach_to_tui(tui_event_tx.clone())?;
@@ -248,37 +245,37 @@ impl MachineATron {
             }
         }
 
-        // Following block does not remove the entries from the VPC table due to possible references by other places.
-        // It rather soft deletes the VPCs by updating the deleted column of a vpc.
         if self.app_context.app_config.cleanup_on_quit {
-            for vpc in vpc_handles {
-                tracing::info!("Attempting to delete VPC with id: {} from db.", vpc.vpc_id);
-                if let Err(e) = self
-                    .app_context
-                    .forge_api_client
-                    .delete_vpc(vpc.vpc_id)
-                    .await
-                {
-                    tracing::error!("Delete VPC Api call failed with {}", e)
-                }
-            }
+            self.delete_networks(networks).await;
+        }
+        self.remove_bmc_proxy().await;
 
-            for subnet in subnet_handles {
-                tracing::info!(
-                    "Attempting to delete network segment with id: {} from db.",
-                    subnet.segment_id
-                );
-                if let Err(e) = self
-                    .app_context
-                    .forge_api_client
-                    .delete_network_segment(subnet.segment_id)
-                    .await
-                {
-                    tracing::error!("Delete network segment Api call failed with {}", e)
-                }
-            }
+        tracing::info!("machine-a-tron finished");
+        Ok(())
+    }
+
+    pub(crate) async fn configure_bmc_proxy(&self) {
+        if let Some(host_str) = self
+            .app_context
+            .app_config
+            .configure_carbide_bmc_proxy_host
+            .as_ref()
+        {
+            let host_port_str =
+                format!("{}:{}", host_str, self.app_context.app_config.bmc_mock_port);
+            tracing::info!("Configuring carbide API to use {host_port_str} as bmc_proxy",);
+            _ = self
+                .app_context
+                .api_client()
+                .configure_bmc_proxy_host(host_port_str)
+                .await
+                .inspect_err(
+                    |e| tracing::warn!(error = ?e, "Could not configure carbide bmc_proxy"),
+                )
         }
+    }
 
+    pub(crate) async fn remove_bmc_proxy(&self) {
         if self
             .app_context
             .app_config
@@ -295,10 +292,83 @@ impl MachineATron {
                     |e| tracing::warn!(error = ?e, "Could not configure carbide bmc_proxy"),
                 )
         }
+    }
 
-        tracing::info!("machine-a-tron finished");
-        Ok(())
+    /// Creates the VPCs and network segments requested by each machine config section.
+    pub(crate) async fn create_networks(
+        &self,
+        tui_event_tx: Option<mpsc::Sender<UiUpdate>>,
+    ) -> SimulatedNetworks {
+        let mut networks = SimulatedNetworks::default();
+
+        for (_config_name, config) in self.app_context.app_config.machines.iter() {
+            let network_virtualization_type =
+                parse_network_virtualization_type(config.network_virtualization_type.as_deref());
+            for _ in 0..config.vpc_count {
+                let app_context = self.app_context.clone();
+                let vpc = Vpc::new(
+                    app_context,
+                    tui_event_tx.clone(),
+                    network_virtualization_type,
+                )
+                .await;
+
+                for _ in 0..config.subnets_per_vpc {
+                    let app_context = self.app_context.clone();
+
+                    match Subnet::new(app_context, tui_event_tx.clone(), &vpc).await {
+                        Ok(subnet) => {
+                            networks.subnets.push(subnet);
+                        }
+                        Err(e) => {
+                            tracing::error!("Error creating network segment: {}", e);
+                        }
+                    }
+                }
+                networks.vpcs.push(vpc);
+            }
+        }
+
+        networks
     }
+
+    pub(crate) async fn delete_networks(&self, networks: SimulatedNetworks) {
+        // Following block does not remove the entries from the VPC table due to possible references by other places.
+        // It rather soft deletes the VPCs by updating the deleted column of a vpc.
+        for vpc in networks.vpcs {
+            tracing::info!("Attempting to delete VPC with id: {} from db.", vpc.vpc_id);
+            if let Err(e) = self
+                .app_context
+                .forge_api_client
+                .delete_vpc(vpc.vpc_id)
+                .await
+            {
+                tracing::error!("Delete VPC Api call failed with {}", e)
+            }
+        }
+
+        for subnet in networks.subnets {
+            tracing::info!(
+                "Attempting to delete network segment with id: {} from db.",
+                subnet.segment_id
+            );
+            if let Err(e) = self
+                .app_context
+                .forge_api_client
+                .delete_network_segment(subnet.segment_id)
+                .await
+            {
+                tracing::error!("Delete network segment Api call failed with {}", e)
+            }
+        }
+    }
+}
+
+/// The VPCs and network segments machine-a-tron created for its machine config sections
+#[derive(Default)]
+pub(crate) struct SimulatedNetworks {
+    vpcs: Vec<Vpc>,
+    subnets: Vec<Subnet>,
 }
 
 fn parse_network_virtualization_type(s: Option<&str>) -> Option<VpcVirtualizationType> {
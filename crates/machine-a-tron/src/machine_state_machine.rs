@@ -22,6 +22,7 @@ use std::net::{Ipv4Addr, SocketAddr};
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
+use bmc_mock::bug::InjectedBugs;
 use bmc_mock::{
     BmcCommand, BmcState, BootOptionKind, Callbacks, HostMachineInfo, HostnameQuerying,
     MachineInfo, MockPowerState, POWER_CYCLE_DELAY, SetSystemPowerError, SetSystemPowerResult,
@@ -814,70 +815,11 @@ impl MachineStateMachine {
         machine_id: MachineId,
         network_config: &ManagedHostNetworkConfigResponse,
     ) -> Result<(), MachineStateError> {
-        let mut instance_network_config_version: Option<String> = None;
         let instance_config_version: Option<String> = None;
-        let mut interfaces = vec![];
-
-        if network_config.use_admin_network {
-            let iface = network_config
-                .admin_interface
-                .as_ref()
-                .expect("use_admin_network true so admin_interface should be Some");
-            let addresses = build_dual_stack_list(
-                iface.ip.clone(),
-                iface.ipv6_interface_config.as_ref().map(|v6| v6.ip.clone()),
-            );
-            let prefixes = build_dual_stack_list(
-                iface.interface_prefix.clone(),
-                iface
-                    .ipv6_interface_config
-                    .as_ref()
-                    .map(|v6| v6.interface_prefix.clone()),
-            );
-            interfaces = vec![rpc::forge::InstanceInterfaceStatusObservation {
-                function_type: iface.function_type,
-                virtual_function_id: None,
-                mac_address: self.machine_info.host_mac_address().map(|a| a.to_string()),
-                addresses,
-                prefixes,
-                gateways: vec![iface.gateway.clone()],
-                network_security_group: None,
-                internal_uuid: None,
-            }]
-        } else {
-            instance_network_config_version =
-                Some(network_config.instance_network_config_version.clone());
-
-            for iface in network_config.tenant_interfaces.iter() {
-                let addresses = build_dual_stack_list(
-                    iface.ip.clone(),
-                    iface.ipv6_interface_config.as_ref().map(|v6| v6.ip.clone()),
-                );
-                let prefixes = build_dual_stack_list(
-                    iface.interface_prefix.clone(),
-                    iface
-                        .ipv6_interface_config
-                        .as_ref()
-                        .map(|v6| v6.interface_prefix.clone()),
-                );
-                interfaces.push(rpc::forge::InstanceInterfaceStatusObservation {
-                    function_type: iface.function_type,
-                    virtual_function_id: iface.virtual_function_id,
-                    mac_address: self.machine_info.host_mac_address().map(|a| a.to_string()),
-                    addresses,
-                    prefixes,
-                    gateways: vec![iface.gateway.clone()],
-                    network_security_group: iface.network_security_group.as_ref().map(|s| {
-                        rpc::forge::NetworkSecurityGroupStatus {
-                            source: s.source,
-                            id: s.id.clone(),
-                            version: s.version.clone(),
-                        }
-                    }),
-                    internal_uuid: None,
-                });
-            }
-        };
+        let (instance_network_config_version, interfaces) = network_status_interfaces(
+            network_config,
+            self.machine_info.host_mac_address().map(|a| a.to_string()),
+        )?;
 
         self.app_context
             .api_client()
@@ -928,6 +870,14 @@ impl MachineStateMachine {
         MaybeOsImage(self.fsm.booted_os())
     }
 
+    /// The bugs injected into this machine's BMC mock, or None if the BMC mock has not been
+    /// started yet.
+    pub fn injected_bugs(&self) -> Option<Arc<InjectedBugs>> {
+        self.bmc_state
+            .as_ref()
+            .map(|state| state.injected_bugs.clone())
+    }
+
     async fn run_bmc_mock(
         &self,
         ip_address: Ipv4Addr,
@@ -987,6 +937,85 @@ impl Display for MachineStateMachine {
     }
 }
 
+/// Builds the interface observations a DPU agent reports back in RecordDpuNetworkStatus for the
+/// given network config, along with the instance network config version it acknowledges.
+pub(crate) fn network_status_interfaces(
+    network_config: &ManagedHostNetworkConfigResponse,
+    host_mac_address: Option<String>,
+) -> Result<
+    (
+        Option<String>,
+        Vec<rpc::forge::InstanceInterfaceStatusObservation>,
+    ),
+    MachineStateError,
+> {
+    let mut instance_network_config_version: Option<String> = None;
+    let mut interfaces = vec![];
+
+    if network_config.use_admin_network {
+        let iface = network_config
+            .admin_interface
+            .as_ref()
+            .ok_or(MachineStateError::MissingAdminInterface)?;
+        let addresses = build_dual_stack_list(
+            iface.ip.clone(),
+            iface.ipv6_interface_config.as_ref().map(|v6| v6.ip.clone()),
+        );
+        let prefixes = build_dual_stack_list(
+            iface.interface_prefix.clone(),
+            iface
+                .ipv6_interface_config
+                .as_ref()
+                .map(|v6| v6.interface_prefix.clone()),
+        );
+        interfaces = vec![rpc::forge::InstanceInterfaceStatusObservation {
+            function_type: iface.function_type,
+            virtual_function_id: None,
+            mac_address: host_mac_address.clone(),
+            addresses,
+            prefixes,
+            gateways: vec![iface.gateway.clone()],
+            network_security_group: None,
+            internal_uuid: None,
+        }]
+    } else {
+        instance_network_config_version =
+            Some(network_config.instance_network_config_version.clone());
+
+        for iface in network_config.tenant_interfaces.iter() {
+            let addresses = build_dual_stack_list(
+                iface.ip.clone(),
+                iface.ipv6_interface_config.as_ref().map(|v6| v6.ip.clone()),
+            );
+            let prefixes = build_dual_stack_list(
+                iface.interface_prefix.clone(),
+                iface
+                    .ipv6_interface_config
+                    .as_ref()
+                    .map(|v6| v6.interface_prefix.clone()),
+            );
+            interfaces.push(rpc::forge::InstanceInterfaceStatusObservation {
+                function_type: iface.function_type,
+                virtual_function_id: iface.virtual_function_id,
+                mac_address: host_mac_address.clone(),
+                addresses,
+                prefixes,
+                gateways: vec![iface.gateway.clone()],
+                network_security_group: iface.network_security_group.as_ref().map(|s| {
+                    rpc::forge::NetworkSecurityGroupStatus {
+                        source: s.source,
+                        id: s.id.clone(),
+                        version: s.version.clone(),
+                    }
+                }),
+                internal_uuid: None,
+            });
+        }
+    };
+
+    Ok((instance_network_config_version, interfaces))
+}
+
 /// Represents the image that can be booted to via PXE or installed on-device
 #[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -1034,6 +1063,8 @@ pub enum MachineStateError {
     MissingMachineId,
     #[error("No mac addresses specified for machine")]
     NoMachineMacAddress,
+    #[error("Network config uses the admin network, but has no admin interface")]
+    MissingAdminInterface,
     #[error("No DHCP info for BMC. This is bug.")]
     NoBmcDhcpInfo,
     #[error("No DHCP info for machine. This is bug.")]
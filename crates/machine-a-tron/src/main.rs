@@ -28,9 +28,9 @@ use forge_tls::client_config::{
     get_client_cert_info, get_config_from_file, get_forge_root_ca_path, get_proxy_info,
 };
 use machine_a_tron::{
-    AppEvent, BmcMockRegistry, BmcRegistrationMode, MachineATron, MachineATronArgs,
-    MachineATronConfig, MachineATronContext, MockSshServerHandle, PromptBehavior, Tui, TuiHostLogs,
-    api_throttler, spawn_mock_ssh_server,
+    AppEvent, BmcMockRegistry, BmcRegistrationMode, LoadGenerator, LoadProfile, MachineATron,
+    MachineATronArgs, MachineATronConfig, MachineATronContext, MockSshServerHandle, PromptBehavior,
+    Scenario, ScenarioRunner, Tui, TuiHostLogs, api_throttler, spawn_mock_ssh_server,
 };
 use rpc::forge_tls_client::{ApiConfig, ForgeClientConfig};
 use rpc::protos::forge_api_client::ForgeApiClient;
@@ -81,7 +81,27 @@ async fn main() -> Result<(), Box<dyn Error>> {
         Err(format!("config: {} is not file", args.config_file.as_str()))?;
     }
     let fig = Figment::new().merge(Toml::file(config_path));
-    let app_config: MachineATronConfig = fig.extract()?;
+    let mut app_config: MachineATronConfig = fig.extract()?;
+
+    // Scenarios run headless, and are validated up front so a typo doesn't cost a full bring-up
+    let scenario = args
+        .scenario
+        .as_deref()
+        .map(|path| Scenario::from_file(Path::new(path)))
+        .transpose()?;
+    if let Some(scenario) = &scenario {
+        scenario.validate(&app_config)?;
+        app_config.tui_enabled = false;
+    }
+    let load_profile = args
+        .load
+        .as_deref()
+        .map(|path| LoadProfile::from_file(Path::new(path)))
+        .transpose()?;
+    if let Some(load_profile) = &load_profile {
+        load_profile.validate(&app_config)?;
+        app_config.tui_enabled = false;
+    }
     let tui_host_logs = if app_config.tui_enabled {
         Some(TuiHostLogs::start_new(100))
     } else {
@@ -150,6 +170,18 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let info = app_context.forge_api_client.version(false).await?;
     tracing::info!("version: {}", info.build_version);
 
+    // Load generation acts on behalf of machines carbide already knows, so needs no BMC mocks
+    if let Some(load_profile) = load_profile {
+        let report = LoadGenerator::new(app_context.clone(), args.allow_real_machines)
+            .run(&load_profile)
+            .await?;
+        print!("{}", report.summary());
+        if let Some(report_dir) = &args.report_dir {
+            report.write_to_dir(Path::new(report_dir))?;
+        }
+        return Ok(());
+    }
+
     let mut mat = MachineATron::new(app_context.clone());
 
     // If we're using a combined BMC mock that routes to each mock machine using headers, launch it now
@@ -200,6 +232,24 @@ async fn main() -> Result<(), Box<dyn Error>> {
             }
         };
 
+    if let Some(scenario) = scenario {
+        let report = ScenarioRunner::new(app_context.clone())
+            .run(&scenario)
+            .await?;
+        print!("{}", report.summary());
+        if let Some(report_dir) = &args.report_dir {
+            report.write_to_dir(Path::new(report_dir))?;
+        }
+
+        if let Some((mut bmc_mock_handle, _mock_ssh_server_handle)) = maybe_bmc_mock_handles {
+            bmc_mock_handle.stop().await?;
+        }
+        if !report.passed {
+            Err(format!("scenario {} failed", scenario.name))?;
+        }
+        return Ok(());
+    }
+
     let machine_handles = mat.make_machines(true).await?;
 
     // Persist them once in case of unclean shutdown
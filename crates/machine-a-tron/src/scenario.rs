@@ -0,0 +1,369 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::collections::HashSet;
+use std::path::Path;
+use std::time::Duration;
+
+use bmc_mock::{HostHardwareType, bug};
+use duration_str::deserialize_duration;
+use serde::Deserialize;
+
+use crate::config::MachineATronConfig;
+
+/// A [`Scenario`] describes a headless machine-a-tron run: a list of phases which are executed in
+/// order against carbide, each optionally followed by an expectation on the carbide state of the
+/// hosts it touched. It is loaded from YAML and run by [`crate::ScenarioRunner`], which produces a
+/// [`crate::ScenarioReport`].
+///
+/// Scenarios are meant for CI: they bring up fresh hosts every time, and never read or write
+/// machines persisted in `persist_dir`.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Keep running the remaining phases after a phase failed. By default they are skipped.
+    #[serde(default)]
+    pub continue_on_failure: bool,
+    /// Delete the hosts, VPCs and network segments created by the scenario once it finished.
+    #[serde(default = "default_true")]
+    pub cleanup: bool,
+    pub phases: Vec<Phase>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Phase {
+    pub name: String,
+    /// Which of the hosts brought up so far this phase acts on. Ignored for `bring_up`, which acts
+    /// on the hosts it creates.
+    #[serde(default)]
+    pub hosts: HostSelector,
+    #[serde(flatten)]
+    pub action: PhaseAction,
+    #[serde(default)]
+    pub expect: Option<Expectation>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum PhaseAction {
+    /// Construct `count` new hosts from the `machines` config section and start them.
+    BringUp {
+        machines: String,
+        count: u32,
+        /// Overrides the hardware type of the config section
+        #[serde(default)]
+        hw_type: Option<HostHardwareType>,
+    },
+    /// Allocate an instance on every selected host that does not have one yet.
+    AllocateInstances {
+        #[serde(default = "default_network_segment")]
+        network_segment: String,
+    },
+    /// Request reprovisioning of all DPUs of every selected host.
+    ReprovisionDpus {
+        #[serde(default)]
+        update_firmware: bool,
+    },
+    /// Inject a fault into the BMC mock of every selected host, replacing any earlier fault.
+    InjectBmcFault { fault: BmcFault },
+    /// Remove all faults from the BMC mock of every selected host.
+    ClearBmcFaults,
+    /// Release the instances the scenario allocated on the selected hosts.
+    ReleaseInstances,
+    /// Do nothing for `duration`. Useful to let a fault take effect before asserting on it.
+    Wait {
+        #[serde(deserialize_with = "deserialize_duration")]
+        duration: Duration,
+    },
+}
+
+impl PhaseAction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PhaseAction::BringUp { .. } => "bring_up",
+            PhaseAction::AllocateInstances { .. } => "allocate_instances",
+            PhaseAction::ReprovisionDpus { .. } => "reprovision_dpus",
+            PhaseAction::InjectBmcFault { .. } => "inject_bmc_fault",
+            PhaseAction::ClearBmcFaults => "clear_bmc_faults",
+            PhaseAction::ReleaseInstances => "release_instances",
+            PhaseAction::Wait { .. } => "wait",
+        }
+    }
+}
+
+/// Selects hosts out of the ones the scenario brought up so far, in the order they were brought
+/// up. The default selects all of them.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct HostSelector {
+    /// Only select the hosts created by the `bring_up` phase of this name
+    #[serde(default)]
+    pub phase: Option<String>,
+    /// Select at most this many hosts
+    #[serde(default)]
+    pub count: Option<u32>,
+}
+
+/// Asserts that every host a phase acted on reaches a carbide state (as reported by
+/// FindMachinesByIds, e.g. `Ready` or `Assigned/Ready`) within a time limit, counted from the start
+/// of the phase.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Expectation {
+    pub state: String,
+    #[serde(deserialize_with = "deserialize_duration")]
+    pub within: Duration,
+    /// Only count the state once the host was seen in a different state first. Needed for actions
+    /// like reprovisioning, which start and end in the same state.
+    #[serde(default)]
+    pub require_transition: bool,
+}
+
+/// A fault to inject into a host's BMC mock. See [`bmc_mock::bug::InjectedBugs`].
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct BmcFault {
+    /// Report the host's DPUs as missing from the chassis network adapters
+    #[serde(default)]
+    pub all_dpu_lost_on_host: bool,
+    #[serde(default)]
+    pub long_response: Option<LongResponseFault>,
+    #[serde(default)]
+    pub http_error: Option<HttpErrorFault>,
+}
+
+/// Delays responses to redfish requests
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct LongResponseFault {
+    /// Only delay requests to this path. Delays all requests if unset.
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(deserialize_with = "deserialize_duration")]
+    pub delay: Duration,
+}
+
+/// Answers the next `count` requests to a redfish path with an HTTP error
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct HttpErrorFault {
+    pub path: String,
+    pub status: u16,
+    #[serde(default = "default_http_error_count")]
+    pub count: usize,
+    #[serde(default)]
+    pub method: Option<String>,
+}
+
+impl From<&BmcFault> for bug::Args {
+    fn from(fault: &BmcFault) -> Self {
+        bug::Args {
+            all_dpu_lost_on_host: Some(fault.all_dpu_lost_on_host),
+            long_response: fault.long_response.as_ref().map(|f| bug::LongResponse {
+                path: f.path.clone(),
+                timeout: Some(f.delay),
+            }),
+            http_error: fault.http_error.as_ref().map(|f| bug::HttpErrorRule {
+                path: f.path.clone(),
+                status: f.status,
+                remaining: f.count,
+                method: f.method.clone(),
+            }),
+        }
+    }
+}
+
+impl Scenario {
+    pub fn from_file(path: &Path) -> eyre::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| eyre::eyre!("could not read scenario {}: {e}", path.display()))?;
+        Self::from_yaml(&contents)
+    }
+
+    pub fn from_yaml(yaml: &str) -> eyre::Result<Self> {
+        Ok(serde_yaml::from_str(yaml)?)
+    }
+
+    /// Checks that the scenario can run against the given config: phase names are unique,
+    /// `bring_up` phases reference existing machine config sections, and host selectors reference
+    /// earlier `bring_up` phases.
+    pub fn validate(&self, config: &MachineATronConfig) -> eyre::Result<()> {
+        if self.phases.is_empty() {
+            return Err(eyre::eyre!("scenario {} has no phases", self.name));
+        }
+
+        let mut phase_names = HashSet::new();
+        let mut bring_up_phases = HashSet::new();
+        for phase in &self.phases {
+            if !phase_names.insert(phase.name.as_str()) {
+                return Err(eyre::eyre!("duplicate phase name {}", phase.name));
+            }
+
+            if let PhaseAction::BringUp {
+                machines, count, ..
+            } = &phase.action
+            {
+                if !config.machines.contains_key(machines) {
+                    return Err(eyre::eyre!(
+                        "phase {} brings up hosts from unknown machine config {machines}",
+                        phase.name
+                    ));
+                }
+                if *count == 0 {
+                    return Err(eyre::eyre!("phase {} brings up 0 hosts", phase.name));
+                }
+                bring_up_phases.insert(phase.name.as_str());
+            } else if let Some(selected_phase) = &phase.hosts.phase
+                && !bring_up_phases.contains(selected_phase.as_str())
+            {
+                return Err(eyre::eyre!(
+                    "phase {} selects hosts of {selected_phase}, which is not an earlier bring_up phase",
+                    phase.name
+                ));
+            }
+        }
+
+        if bring_up_phases.is_empty() {
+            return Err(eyre::eyre!("scenario {} never brings up hosts", self.name));
+        }
+
+        Ok(())
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_network_segment() -> String {
+    "subnet_0".to_string()
+}
+
+fn default_http_error_count() -> usize {
+    1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config() -> MachineATronConfig {
+        toml::from_str(
+            r#"
+carbide_api_url = "https://carbide-api.forge:443"
+interface = "br-77cbb29de011"
+
+[machines.config]
+host_count = 0
+dpu_per_host_count = 1
+dpu_reboot_delay = 1
+host_reboot_delay = 1
+vpc_count = 1
+subnets_per_vpc = 1
+admin_dhcp_relay_address = "192.168.176.1"
+oob_dhcp_relay_address = "192.168.192.1"
+"#,
+        )
+        .expect("Could not parse config")
+    }
+
+    #[test]
+    fn test_parse_example_scenario() {
+        let scenario = Scenario::from_yaml(include_str!("../config/lifecycle-scenario.yaml"))
+            .expect("Could not parse example scenario");
+        scenario
+            .validate(&test_config())
+            .expect("Example scenario is invalid");
+
+        assert!(scenario.cleanup);
+        assert!(!scenario.continue_on_failure);
+        assert_eq!(
+            scenario.phases[0].action,
+            PhaseAction::BringUp {
+                machines: "config".to_string(),
+                count: 4,
+                hw_type: Some(HostHardwareType::DellPowerEdgeR750),
+            }
+        );
+        assert_eq!(
+            scenario.phases[0].expect,
+            Some(Expectation {
+                state: "Ready".to_string(),
+                within: Duration::from_secs(30 * 60),
+                require_transition: false,
+            })
+        );
+        assert!(scenario.phases.iter().any(|p| matches!(
+            &p.action,
+            PhaseAction::InjectBmcFault { fault } if fault.http_error.is_some()
+        )));
+    }
+
+    #[test]
+    fn test_validate_rejects_unknown_references() {
+        let scenario = Scenario::from_yaml(
+            r#"
+name: bad
+phases:
+  - name: up
+    action: bring_up
+    machines: nonexistent
+    count: 1
+"#,
+        )
+        .unwrap();
+        assert!(scenario.validate(&test_config()).is_err());
+
+        let scenario = Scenario::from_yaml(
+            r#"
+name: bad
+phases:
+  - name: allocate
+    action: allocate_instances
+    hosts:
+      phase: up
+  - name: up
+    action: bring_up
+    machines: config
+    count: 1
+"#,
+        )
+        .unwrap();
+        assert!(scenario.validate(&test_config()).is_err());
+    }
+
+    #[test]
+    fn test_bmc_fault_to_injected_bug_args() {
+        let fault = BmcFault {
+            all_dpu_lost_on_host: false,
+            long_response: None,
+            http_error: Some(HttpErrorFault {
+                path: "/redfish/v1/Systems/System.Embedded.1".to_string(),
+                status: 500,
+                count: 3,
+                method: None,
+            }),
+        };
+        let args = bug::Args::from(&fault);
+        let http_error = args.http_error.expect("http_error should be set");
+        assert_eq!(http_error.status, 500);
+        assert_eq!(http_error.remaining, 3);
+        assert!(args.long_response.is_none());
+    }
+}
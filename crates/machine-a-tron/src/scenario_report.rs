@@ -0,0 +1,333 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::fmt::Write;
+use std::path::Path;
+use std::time::Duration;
+
+use bmc_mock::HostHardwareType;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+/// The outcome of a [`crate::Scenario`] run, as produced by [`crate::ScenarioRunner`]. It can be
+/// written as JSON (for further processing) and as JUnit XML (for CI test reporting), where every
+/// phase is a test case.
+#[derive(Clone, Debug, Serialize)]
+pub struct ScenarioReport {
+    pub scenario: String,
+    pub started_at: DateTime<Utc>,
+    pub duration_secs: f64,
+    pub passed: bool,
+    pub phases: Vec<PhaseReport>,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PhaseOutcome {
+    Passed,
+    Failed,
+    /// The phase did not run because an earlier phase failed
+    Skipped,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct PhaseReport {
+    pub name: String,
+    pub action: String,
+    pub outcome: PhaseOutcome,
+    pub duration_secs: f64,
+    /// Why the phase failed or was skipped
+    pub message: Option<String>,
+    pub expected_state: Option<String>,
+    /// Distribution of the per-host time-to-state, for hosts that reached the expected state
+    pub time_to_state: Option<TimeToStateSummary>,
+    pub hosts: Vec<HostReport>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct HostReport {
+    pub mat_id: Uuid,
+    pub machine_id: Option<String>,
+    pub hw_type: HostHardwareType,
+    /// Error returned while performing the phase's action on this host
+    pub error: Option<String>,
+    /// Time from the start of the phase until carbide reported the expected state. None if the
+    /// phase had no expectation, or the host never reached the state.
+    pub time_to_state_secs: Option<f64>,
+    /// The carbide state of the host at the end of the phase
+    pub final_state: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct TimeToStateSummary {
+    pub min_secs: f64,
+    pub p50_secs: f64,
+    pub p90_secs: f64,
+    pub max_secs: f64,
+}
+
+impl TimeToStateSummary {
+    pub fn from_durations(durations: &[Duration]) -> Option<Self> {
+        let mut secs: Vec<f64> = durations.iter().map(Duration::as_secs_f64).collect();
+        secs.sort_by(f64::total_cmp);
+        let percentile = |p: f64| secs[((secs.len() - 1) as f64 * p).round() as usize];
+        Some(Self {
+            min_secs: *secs.first()?,
+            p50_secs: percentile(0.5),
+            p90_secs: percentile(0.9),
+            max_secs: *secs.last()?,
+        })
+    }
+}
+
+impl PhaseReport {
+    pub fn skipped(name: String, action: String) -> Self {
+        Self {
+            name,
+            action,
+            outcome: PhaseOutcome::Skipped,
+            duration_secs: 0.0,
+            message: Some("skipped after an earlier phase failed".to_string()),
+            expected_state: None,
+            time_to_state: None,
+            hosts: Vec::new(),
+        }
+    }
+}
+
+impl ScenarioReport {
+    pub fn count(&self, outcome: PhaseOutcome) -> usize {
+        self.phases.iter().filter(|p| p.outcome == outcome).count()
+    }
+
+    /// A one-line-per-phase summary for the console
+    pub fn summary(&self) -> String {
+        let mut out = format!(
+            "scenario {}: {} ({} passed, {} failed, {} skipped) in {:.1}s\n",
+            self.scenario,
+            if self.passed { "PASSED" } else { "FAILED" },
+            self.count(PhaseOutcome::Passed),
+            self.count(PhaseOutcome::Failed),
+            self.count(PhaseOutcome::Skipped),
+            self.duration_secs,
+        );
+        for phase in &self.phases {
+            _ = write!(
+                out,
+                "  {:<8} {} ({}) {:.1}s",
+                format!("{:?}", phase.outcome).to_uppercase(),
+                phase.name,
+                phase.action,
+                phase.duration_secs
+            );
+            if let Some(t) = &phase.time_to_state {
+                _ = write!(
+                    out,
+                    " time-to-state min={:.1}s p50={:.1}s p90={:.1}s max={:.1}s",
+                    t.min_secs, t.p50_secs, t.p90_secs, t.max_secs
+                );
+            }
+            if let Some(message) = &phase.message {
+                _ = write!(out, ": {message}");
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    pub fn to_json(&self) -> eyre::Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn to_junit_xml(&self) -> String {
+        let suite = xml_escape(&self.scenario);
+        let tests = self.phases.len();
+        let failures = self.count(PhaseOutcome::Failed);
+        let skipped = self.count(PhaseOutcome::Skipped);
+        let time = self.duration_secs;
+
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        _ = writeln!(
+            out,
+            "<testsuites name=\"machine-a-tron\" tests=\"{tests}\" failures=\"{failures}\" skipped=\"{skipped}\" time=\"{time:.3}\">"
+        );
+        _ = writeln!(
+            out,
+            "  <testsuite name=\"{suite}\" tests=\"{tests}\" failures=\"{failures}\" skipped=\"{skipped}\" time=\"{time:.3}\" timestamp=\"{}\">",
+            self.started_at.format("%Y-%m-%dT%H:%M:%S")
+        );
+        for phase in &self.phases {
+            _ = writeln!(
+                out,
+                "    <testcase classname=\"{suite}\" name=\"{}\" time=\"{:.3}\">",
+                xml_escape(&format!("{} ({})", phase.name, phase.action)),
+                phase.duration_secs
+            );
+            let message = xml_escape(phase.message.as_deref().unwrap_or_default());
+            match phase.outcome {
+                PhaseOutcome::Passed => {}
+                PhaseOutcome::Failed => {
+                    _ = writeln!(out, "      <failure message=\"{message}\"/>");
+                }
+                PhaseOutcome::Skipped => {
+                    _ = writeln!(out, "      <skipped message=\"{message}\"/>");
+                }
+            }
+            if !phase.hosts.is_empty() {
+                out.push_str("      <system-out>");
+                for host in &phase.hosts {
+                    out.push_str(&xml_escape(&host.describe(phase.expected_state.as_deref())));
+                    out.push('\n');
+                }
+                out.push_str("</system-out>\n");
+            }
+            out.push_str("    </testcase>\n");
+        }
+        out.push_str("  </testsuite>\n</testsuites>\n");
+        out
+    }
+
+    /// Writes `report.json` and `junit.xml` into `dir`, creating it if needed
+    pub fn write_to_dir(&self, dir: &Path) -> eyre::Result<()> {
+        std::fs::create_dir_all(dir)?;
+        std::fs::write(dir.join("report.json"), self.to_json()?)?;
+        std::fs::write(dir.join("junit.xml"), self.to_junit_xml())?;
+        Ok(())
+    }
+}
+
+impl HostReport {
+    fn describe(&self, expected_state: Option<&str>) -> String {
+        let mut out = format!(
+            "host {} ({}, machine {})",
+            self.mat_id,
+            self.hw_type,
+            self.machine_id.as_deref().unwrap_or("unknown")
+        );
+        if let Some(error) = &self.error {
+            _ = write!(out, ": error: {error}");
+        }
+        match (expected_state, self.time_to_state_secs) {
+            (Some(state), Some(secs)) => _ = write!(out, ": reached {state} after {secs:.1}s"),
+            (Some(state), None) => _ = write!(out, ": did not reach {state}"),
+            (None, _) => {}
+        }
+        if let Some(final_state) = &self.final_state {
+            _ = write!(out, " (final state {final_state})");
+        }
+        out
+    }
+}
+
+fn xml_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_report() -> ScenarioReport {
+        ScenarioReport {
+            scenario: "lifecycle".to_string(),
+            started_at: DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+            duration_secs: 125.0,
+            passed: false,
+            phases: vec![
+                PhaseReport {
+                    name: "bring-up".to_string(),
+                    action: "bring_up".to_string(),
+                    outcome: PhaseOutcome::Failed,
+                    duration_secs: 120.0,
+                    message: Some("1 of 2 hosts did not reach <Ready>".to_string()),
+                    expected_state: Some("Ready".to_string()),
+                    time_to_state: TimeToStateSummary::from_durations(&[Duration::from_secs(100)]),
+                    hosts: vec![
+                        HostReport {
+                            mat_id: Uuid::nil(),
+                            machine_id: None,
+                            hw_type: HostHardwareType::DellPowerEdgeR750,
+                            error: None,
+                            time_to_state_secs: Some(100.0),
+                            final_state: Some("Ready".to_string()),
+                        },
+                        HostReport {
+                            mat_id: Uuid::nil(),
+                            machine_id: None,
+                            hw_type: HostHardwareType::DellPowerEdgeR750,
+                            error: None,
+                            time_to_state_secs: None,
+                            final_state: Some("HostInitializing/WaitingForDiscovery".to_string()),
+                        },
+                    ],
+                },
+                PhaseReport::skipped("allocate".to_string(), "allocate_instances".to_string()),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_time_to_state_summary() {
+        assert_eq!(TimeToStateSummary::from_durations(&[]), None);
+
+        let durations: Vec<Duration> = (1..=10).map(Duration::from_secs).collect();
+        let summary = TimeToStateSummary::from_durations(&durations).unwrap();
+        assert_eq!(summary.min_secs, 1.0);
+        assert_eq!(summary.max_secs, 10.0);
+        assert_eq!(summary.p50_secs, 6.0);
+        assert_eq!(summary.p90_secs, 9.0);
+    }
+
+    #[test]
+    fn test_junit_xml() {
+        let xml = test_report().to_junit_xml();
+        assert!(xml.contains(
+            "<testsuite name=\"lifecycle\" tests=\"2\" failures=\"1\" skipped=\"1\" time=\"125.000\" timestamp=\"2023-11-14T22:13:20\">"
+        ));
+        assert!(xml.contains("<failure message=\"1 of 2 hosts did not reach &lt;Ready&gt;\"/>"));
+        assert!(xml.contains("<skipped message=\"skipped after an earlier phase failed\"/>"));
+        assert!(xml.contains("reached Ready after 100.0s"));
+        assert!(
+            xml.contains("did not reach Ready (final state HostInitializing/WaitingForDiscovery)")
+        );
+    }
+
+    #[test]
+    fn test_json_report() {
+        let json: serde_json::Value =
+            serde_json::from_str(&test_report().to_json().unwrap()).unwrap();
+        assert_eq!(json["passed"], false);
+        assert_eq!(json["phases"][0]["outcome"], "failed");
+        assert_eq!(
+            json["phases"][0]["hosts"][0]["hw_type"],
+            "dell_poweredge_r750"
+        );
+        assert_eq!(json["phases"][0]["time_to_state"]["max_secs"], 100.0);
+        assert_eq!(json["phases"][1]["outcome"], "skipped");
+    }
+}
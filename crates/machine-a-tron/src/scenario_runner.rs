@@ -0,0 +1,334 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use bmc_mock::bug;
+use carbide_uuid::instance::InstanceId;
+use chrono::Utc;
+use futures::future::join_all;
+
+use crate::config::MachineATronContext;
+use crate::host_machine::HostMachineHandle;
+use crate::machine_a_tron::MachineATron;
+use crate::scenario::{Expectation, HostSelector, Phase, PhaseAction, Scenario};
+use crate::scenario_report::{
+    HostReport, PhaseOutcome, PhaseReport, ScenarioReport, TimeToStateSummary,
+};
+
+/// Runs a [`Scenario`] against carbide without the TUI, and reports how each phase went.
+pub struct ScenarioRunner {
+    app_context: Arc<MachineATronContext>,
+    mat: MachineATron,
+    hosts: Vec<ScenarioHost>,
+}
+
+/// A host brought up by the scenario
+struct ScenarioHost {
+    handle: HostMachineHandle,
+    bring_up_phase: String,
+    instance_id: Option<InstanceId>,
+}
+
+impl ScenarioRunner {
+    pub fn new(app_context: Arc<MachineATronContext>) -> Self {
+        Self {
+            mat: MachineATron::new(app_context.clone()),
+            app_context,
+            hosts: Vec::new(),
+        }
+    }
+
+    /// Runs all phases of the scenario in order. Only returns an error if the scenario does not fit
+    /// the machine-a-tron config; failures while running are recorded in the report.
+    pub async fn run(mut self, scenario: &Scenario) -> eyre::Result<ScenarioReport> {
+        scenario.validate(&self.app_context.app_config)?;
+
+        tracing::info!("Running scenario {}", scenario.name);
+        let started_at = Utc::now();
+        let start = Instant::now();
+
+        self.mat.configure_bmc_proxy().await;
+        let networks = self.mat.create_networks(None).await;
+
+        let mut phases = Vec::with_capacity(scenario.phases.len());
+        let mut passed = true;
+        for phase in &scenario.phases {
+            if !passed && !scenario.continue_on_failure {
+                phases.push(PhaseReport::skipped(
+                    phase.name.clone(),
+                    phase.action.name().to_string(),
+                ));
+                continue;
+            }
+
+            tracing::info!(phase = %phase.name, action = phase.action.name(), "Starting phase");
+            let report = self.run_phase(phase).await;
+            tracing::info!(
+                phase = %phase.name,
+                outcome = ?report.outcome,
+                message = report.message.as_deref().unwrap_or_default(),
+                "Finished phase"
+            );
+            passed &= report.outcome == PhaseOutcome::Passed;
+            phases.push(report);
+        }
+
+        for host in &self.hosts {
+            host.handle.abort();
+        }
+        if scenario.cleanup {
+            let api_client = self.app_context.api_client();
+            for host in self.hosts.drain(..) {
+                _ = host
+                    .handle
+                    .delete_from_api(api_client.clone())
+                    .await
+                    .inspect_err(|e| tracing::warn!(error = ?e, "Could not delete host"));
+            }
+            self.mat.delete_networks(networks).await;
+        }
+        self.mat.remove_bmc_proxy().await;
+
+        Ok(ScenarioReport {
+            scenario: scenario.name.clone(),
+            started_at,
+            duration_secs: start.elapsed().as_secs_f64(),
+            passed,
+            phases,
+        })
+    }
+
+    async fn run_phase(&mut self, phase: &Phase) -> PhaseReport {
+        let start = Instant::now();
+
+        // Indices into self.hosts of the hosts this phase acts on, and the error the action
+        // returned for each of them
+        let (targets, errors, message) = match self.run_action(phase).await {
+            Ok((targets, errors)) => (targets, errors, None),
+            Err(e) => (Vec::new(), Vec::new(), Some(format!("{e:#}"))),
+        };
+
+        let mut time_to_state = vec![None; targets.len()];
+        if let Some(expect) = &phase.expect {
+            let deadline = start + expect.within;
+            let poll_interval = self.app_context.app_config.api_refresh_interval;
+            time_to_state = join_all(targets.iter().zip(&errors).map(|(i, error)| {
+                let handle = &self.hosts[*i].handle;
+                async move {
+                    if error.is_some() {
+                        return None;
+                    }
+                    wait_for_state(handle, expect, start, deadline, poll_interval).await
+                }
+            }))
+            .await;
+        }
+
+        let mut hosts = Vec::with_capacity(targets.len());
+        for ((i, error), time_to_state) in targets.iter().zip(errors).zip(&time_to_state) {
+            let handle = &self.hosts[*i].handle;
+            hosts.push(HostReport {
+                mat_id: handle.mat_id(),
+                machine_id: handle.observed_machine_id().map(|id| id.to_string()),
+                hw_type: handle.host_info().hw_type,
+                error,
+                time_to_state_secs: time_to_state.map(|d| d.as_secs_f64()),
+                final_state: handle.api_state().await.ok(),
+            });
+        }
+
+        let message = message.or_else(|| phase_failure(phase, &hosts));
+        PhaseReport {
+            name: phase.name.clone(),
+            action: phase.action.name().to_string(),
+            outcome: if message.is_some() {
+                PhaseOutcome::Failed
+            } else {
+                PhaseOutcome::Passed
+            },
+            duration_secs: start.elapsed().as_secs_f64(),
+            message,
+            expected_state: phase.expect.as_ref().map(|e| e.state.clone()),
+            time_to_state: TimeToStateSummary::from_durations(
+                &time_to_state.into_iter().flatten().collect::<Vec<_>>(),
+            ),
+            hosts,
+        }
+    }
+
+    /// Performs the phase's action. Returns the hosts the action was performed on, along with the
+    /// error (if any) for each of them.
+    async fn run_action(
+        &mut self,
+        phase: &Phase,
+    ) -> eyre::Result<(Vec<usize>, Vec<Option<String>>)> {
+        let api_client = self.app_context.api_client();
+
+        let targets = match &phase.action {
+            PhaseAction::BringUp {
+                machines,
+                count,
+                hw_type,
+            } => {
+                let handles = self
+                    .mat
+                    .make_hosts(machines, *count, *hw_type, true)
+                    .await?;
+                let first = self.hosts.len();
+                for handle in handles {
+                    handle.resume()?;
+                    self.hosts.push(ScenarioHost {
+                        handle,
+                        bring_up_phase: phase.name.clone(),
+                        instance_id: None,
+                    });
+                }
+                return Ok((
+                    (first..self.hosts.len()).collect(),
+                    vec![None; *count as usize],
+                ));
+            }
+            PhaseAction::AllocateInstances { .. } => {
+                self.select(&phase.hosts, |h| h.instance_id.is_none())
+            }
+            PhaseAction::ReleaseInstances => self.select(&phase.hosts, |h| h.instance_id.is_some()),
+            _ => self.select(&phase.hosts, |_| true),
+        };
+        if targets.is_empty() && !matches!(phase.action, PhaseAction::Wait { .. }) {
+            return Err(eyre::eyre!("no hosts matched the host selector"));
+        }
+
+        let mut errors = Vec::with_capacity(targets.len());
+        for i in &targets {
+            let host = &mut self.hosts[*i];
+            let result: eyre::Result<()> = match &phase.action {
+                PhaseAction::BringUp { .. } | PhaseAction::Wait { .. } => Ok(()),
+                PhaseAction::AllocateInstances { network_segment } => {
+                    match host.handle.observed_machine_id() {
+                        Some(machine_id) => api_client
+                            .allocate_instance(machine_id, network_segment)
+                            .await
+                            .map(|instance| host.instance_id = instance.id)
+                            .map_err(Into::into),
+                        None => Err(eyre::eyre!("host has no machine ID yet")),
+                    }
+                }
+                PhaseAction::ReprovisionDpus { update_firmware } => {
+                    match host.handle.observed_machine_id() {
+                        Some(machine_id) => api_client
+                            .trigger_dpu_reprovisioning(machine_id, *update_firmware)
+                            .await
+                            .map_err(Into::into),
+                        None => Err(eyre::eyre!("host has no machine ID yet")),
+                    }
+                }
+                PhaseAction::InjectBmcFault { fault } => {
+                    host.handle.inject_bmc_bugs(fault.into()).await
+                }
+                PhaseAction::ClearBmcFaults => {
+                    host.handle.inject_bmc_bugs(bug::Args::default()).await
+                }
+                PhaseAction::ReleaseInstances => match host.instance_id.take() {
+                    Some(instance_id) => api_client
+                        .release_instance(instance_id)
+                        .await
+                        .map_err(Into::into),
+                    None => Ok(()),
+                },
+            };
+            errors.push(result.err().map(|e| format!("{e:#}")));
+        }
+
+        if let PhaseAction::Wait { duration } = &phase.action {
+            tokio::time::sleep(*duration).await;
+        }
+
+        Ok((targets, errors))
+    }
+
+    fn select(
+        &self,
+        selector: &HostSelector,
+        filter: impl Fn(&ScenarioHost) -> bool,
+    ) -> Vec<usize> {
+        self.hosts
+            .iter()
+            .enumerate()
+            .filter(|(_, h)| {
+                selector
+                    .phase
+                    .as_ref()
+                    .is_none_or(|phase| &h.bring_up_phase == phase)
+                    && filter(h)
+            })
+            .map(|(i, _)| i)
+            .take(selector.count.map(|c| c as usize).unwrap_or(usize::MAX))
+            .collect()
+    }
+}
+
+/// Waits until carbide reports the expected state for the host, returning the time since the start
+/// of the phase, or None if the deadline passed first.
+async fn wait_for_state(
+    handle: &HostMachineHandle,
+    expect: &Expectation,
+    phase_start: Instant,
+    deadline: Instant,
+    poll_interval: Duration,
+) -> Option<Duration> {
+    let mut left_other_state = !expect.require_transition;
+    loop {
+        let state = handle.api_state().await.ok()?;
+        if state != expect.state {
+            left_other_state = true;
+        } else if left_other_state {
+            return Some(phase_start.elapsed());
+        }
+
+        let now = Instant::now();
+        if now >= deadline {
+            return None;
+        }
+        tokio::time::sleep(poll_interval.min(deadline - now)).await;
+    }
+}
+
+fn phase_failure(phase: &Phase, hosts: &[HostReport]) -> Option<String> {
+    let errors = hosts.iter().filter(|h| h.error.is_some()).count();
+    if errors > 0 {
+        return Some(format!(
+            "{} failed on {errors} of {} hosts",
+            phase.action.name(),
+            hosts.len()
+        ));
+    }
+
+    let expect = phase.expect.as_ref()?;
+    let missed = hosts
+        .iter()
+        .filter(|h| h.time_to_state_secs.is_none())
+        .count();
+    (missed > 0).then(|| {
+        format!(
+            "{missed} of {} hosts did not reach {} within {}s",
+            hosts.len(),
+            expect.state,
+            expect.within.as_secs()
+        )
+    })
+}
@@ -131,6 +131,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             Some(PublishOptions {
                 qos: Some(qos),
                 retain: None,
+                ..Default::default()
             }),
         )
         .await?;
@@ -23,15 +23,22 @@
 // MQTT connection management, message routing, and statistics tracking.
 
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, PoisonError};
+use std::time::Duration;
 
-use rumqttc::{AsyncClient, Event, EventLoop, MqttOptions, Packet, QoS};
-use tokio::sync::{Mutex, RwLock, Semaphore, mpsc};
+use rumqttc::QoS;
+use tokio::sync::{Mutex, OnceCell, RwLock, Semaphore, mpsc, oneshot};
 use tracing::{debug, error, info, warn};
 
 use crate::auth::CredentialsProvider;
-use crate::client::{ClientOptions, ClosureAdapter, ErasedHandler, ReceivedMessage};
+use crate::client::transport::{IncomingPublish, TransportEventLoop, TransportSettings};
+use crate::client::{
+    ClientOptions, ClosureAdapter, ErasedHandler, MessageProperties, ProtocolVersion,
+    ReceivedMessage, Transport, TransportEvent,
+};
 use crate::errors::MqtteaClientError;
+use crate::outbox::Outbox;
 use crate::registry::MqttRegistry;
 use crate::registry::types::PublishOptions;
 use crate::stats::{PublishStats, PublishStatsTracker, QueueStats, QueueStatsTracker};
@@ -43,20 +50,25 @@ const DEFAULT_RETAIN: bool = false;
 const DEFAULT_MESSAGE_CHANNEL_CAPACITY: usize = 1000;
 
 const DEFAULT_CLIENT_QUEUE_SIZE: usize = 5000;
+const DEFAULT_RESPONSE_TOPIC_PREFIX: &str = "mqttea/responses";
+
+// PendingRequests maps the correlation data of in-flight requests to
+// the channel waiting for the response.
+type PendingRequests = std::sync::Mutex<HashMap<Vec<u8>, oneshot::Sender<IncomingPublish>>>;
 
 // MqtteaClient provides client-scoped MQTT functionality with embedded registry.
 // Each client instance has its own registry for complete isolation between clients.
 pub struct MqtteaClient {
     // client is the underlying MQTT client for actual network
-    // communication.
-    client: Arc<AsyncClient>,
+    // communication, speaking either MQTT 3.1.1 or MQTT v5.
+    client: Arc<Transport>,
     // client_id is the client ID that we pass to the
     // underlying rumqttc::AsyncClient. The AsyncClient
     // itself doesn't provide access to it, so we store
     // it here for logging/identification purposes.
     client_id: String,
     // event_loop is stored to be used in start() method
-    event_loop: Arc<Mutex<Option<EventLoop>>>,
+    event_loop: Arc<Mutex<Option<TransportEventLoop>>>,
     // client_options is used when no explicit PublishOptions are provided
     // for a given message type or topic pattern. If this is None, then
     // the default consts are used as fallback.
@@ -82,6 +94,18 @@ pub struct MqtteaClient {
     // parallel processing of messages (the default is to
     // just process messages sequentially).
     concurrency_semaphore: Arc<Semaphore>,
+    // outbox is the optional durable outbox. When set, publishes are
+    // persisted to disk and delivered by a background drain task
+    // instead of being handed straight to the AsyncClient.
+    outbox: Option<Arc<Outbox>>,
+    // response_topic is where responses to this client's requests are
+    // sent, subscribed to on the first call to request().
+    response_topic: String,
+    responses_subscribed: OnceCell<()>,
+    // pending_requests routes responses back to request() callers.
+    pending_requests: Arc<PendingRequests>,
+    // request_counter makes correlation data unique per request.
+    request_counter: AtomicU64,
 }
 
 impl MqtteaClient {
@@ -97,37 +121,49 @@ impl MqtteaClient {
         client_id: &str,
         client_options: Option<ClientOptions>,
     ) -> Result<Arc<Self>, MqtteaClientError> {
-        let mut mqtt_options = MqttOptions::new(client_id, broker_host, broker_port);
-        mqtt_options.set_keep_alive(
-            client_options
-                .as_ref()
-                .and_then(|opts| opts.keep_alive)
-                .unwrap_or(DEFAULT_KEEP_ALIVE),
-        );
-        mqtt_options.set_clean_session(false);
-
         // Fetch credentials from provider if configured.
-        if let Some(provider) = client_options
+        let credentials = match client_options
             .as_ref()
             .and_then(|opts| opts.credentials_provider.as_ref())
         {
-            let credentials = provider.get_credentials().await?;
-            mqtt_options.set_credentials(credentials.username, credentials.password);
-        }
+            Some(provider) => Some(provider.get_credentials().await?),
+            None => None,
+        };
 
-        let (client, event_loop) = AsyncClient::new(
-            mqtt_options,
-            client_options
+        let (client, event_loop) = Transport::new(TransportSettings {
+            protocol_version: client_options
+                .as_ref()
+                .and_then(|opts| opts.protocol_version)
+                .unwrap_or_default(),
+            client_id,
+            broker_host,
+            broker_port,
+            keep_alive: client_options
+                .as_ref()
+                .and_then(|opts| opts.keep_alive)
+                .unwrap_or(DEFAULT_KEEP_ALIVE),
+            credentials,
+            channel_capacity: client_options
                 .as_ref()
                 .and_then(|opts| opts.message_channel_capacity)
                 .unwrap_or(DEFAULT_MESSAGE_CHANNEL_CAPACITY),
-        );
+        });
         let handlers: Arc<RwLock<HashMap<String, ErasedHandler>>> =
             Arc::new(RwLock::new(HashMap::new()));
 
         let queue_stats = Arc::new(QueueStatsTracker::new());
         let publish_stats = Arc::new(PublishStatsTracker::new());
 
+        // Open the outbox (if configured) up front, so anything left
+        // over from a previous run is counted in the stats right away.
+        let outbox = match client_options
+            .as_ref()
+            .and_then(|opts| opts.outbox.as_ref())
+        {
+            Some(outbox_options) => Some(Outbox::open(outbox_options, publish_stats.clone())?),
+            None => None,
+        };
+
         // Create client-scoped registry instead of using global static.
         let registry = Arc::new(RwLock::new(MqttRegistry::new()));
 
@@ -142,7 +178,17 @@ impl MqtteaClient {
             .as_ref()
             .and_then(|opts| opts.credentials_provider.clone());
 
-        info!("Created MQTT client for {}:{}", broker_host, broker_port);
+        let response_topic = client_options
+            .as_ref()
+            .and_then(|opts| opts.response_topic.clone())
+            .unwrap_or_else(|| format!("{DEFAULT_RESPONSE_TOPIC_PREFIX}/{client_id}"));
+
+        info!(
+            "Created MQTT client for {}:{} ({:?})",
+            broker_host,
+            broker_port,
+            client.protocol_version()
+        );
 
         Ok(Arc::new(Self {
             client: Arc::new(client),
@@ -155,6 +201,11 @@ impl MqtteaClient {
             queue_stats,
             publish_stats,
             registry,
+            outbox,
+            response_topic,
+            responses_subscribed: OnceCell::new(),
+            pending_requests: Arc::new(PendingRequests::default()),
+            request_counter: AtomicU64::new(0),
         }))
     }
 
@@ -208,15 +259,36 @@ impl MqtteaClient {
         let queue_stats_producer = self.queue_stats.clone();
         let registry_clone = self.registry.clone();
         let credentials_provider = self.credentials_provider.clone();
+        let outbox = self.outbox.clone();
+        let pending_requests = self.pending_requests.clone();
+        let response_topic = self.response_topic.clone();
         let mut backoff_strategy = SuperBasicBackoff::new();
         tokio::spawn(async move {
             loop {
                 match event_loop.poll().await {
                     Ok(event) => {
-                        if let Event::Incoming(Packet::Publish(publish)) = event {
-                            if let Some(msg) =
-                                ReceivedMessage::from_publish(&publish, registry_clone.clone())
-                                    .await
+                        if let Some(ref outbox) = outbox {
+                            outbox.handle_event(&event);
+                        }
+                        if let TransportEvent::Publish(publish) = event {
+                            // Responses to our own requests go straight
+                            // to the waiting request() caller.
+                            let publish =
+                                match take_response(&pending_requests, &response_topic, publish) {
+                                    Some(publish) => publish,
+                                    None => {
+                                        backoff_strategy.reset();
+                                        continue;
+                                    }
+                                };
+                            let topic = publish.topic.clone();
+                            if let Some(msg) = ReceivedMessage::from_parts(
+                                publish.topic,
+                                publish.payload,
+                                publish.properties,
+                                registry_clone.clone(),
+                            )
+                            .await
                             {
                                 let payload_size = msg.payload_size;
                                 match message_queue_tx.try_send(msg) {
@@ -229,7 +301,7 @@ impl MqtteaClient {
                                     Err(mpsc::error::TrySendError::Full(_)) => {
                                         warn!(
                                             "Message queue full, dropping message from topic: {}",
-                                            publish.topic
+                                            topic
                                         );
                                         queue_stats_producer.increment_dropped(payload_size);
                                         tokio::time::sleep(backoff_strategy.next_delay()).await;
@@ -247,7 +319,7 @@ impl MqtteaClient {
                             } else {
                                 queue_stats_producer.increment_unmatched_topics();
                                 if warn_on_unmatched_topic {
-                                    warn!("No registered pattern matched topic: {}", publish.topic);
+                                    warn!("No registered pattern matched topic: {}", topic);
                                 }
                             }
                         }
@@ -255,6 +327,9 @@ impl MqtteaClient {
                     Err(e) => {
                         error!("MQTT event loop connection error: {:?}", e);
                         queue_stats_producer.increment_event_loop_errors();
+                        if let Some(ref outbox) = outbox {
+                            outbox.set_connected(false);
+                        }
 
                         // Refresh credentials before reconnection attempt if a provider is configured.
                         // This ensures we use fresh tokens (e.g., OAuth2) for the next connection.
@@ -266,10 +341,7 @@ impl MqtteaClient {
                             match provider.get_credentials().await {
                                 Ok(credentials) => {
                                     debug!("Refreshed credentials for reconnection");
-                                    event_loop.mqtt_options.set_credentials(
-                                        credentials.username,
-                                        credentials.password,
-                                    );
+                                    event_loop.set_credentials(credentials);
                                 }
                                 Err(cred_err) => {
                                     error!(
@@ -286,6 +358,13 @@ impl MqtteaClient {
             }
         });
 
+        // Outbox drain task. If the outbox is enabled, this hands
+        // persisted messages to the AsyncClient in order while we
+        // are connected to the broker.
+        if let Some(outbox) = self.outbox.clone() {
+            tokio::spawn(outbox.drain(self.client.clone()));
+        }
+
         // Message processing task. This looks for new ReceivedMessages that are
         // pushed into our local message queue by the event loop task above,
         // and will [attempt to] deserialize + fire off the callback handler
@@ -300,7 +379,14 @@ impl MqtteaClient {
                 let handlers_guard = handlers_clone.read().await;
 
                 if let Some(handler) = handlers_guard.get(&msg.type_name) {
-                    match handler(handler_client.clone(), msg.payload, msg.topic).await {
+                    match handler(
+                        handler_client.clone(),
+                        msg.payload,
+                        msg.topic,
+                        msg.properties,
+                    )
+                    .await
+                    {
                         Ok(_) => {
                             queue_stats_processor
                                 .decrement_pending_increment_processed(payload_size);
@@ -390,18 +476,124 @@ impl MqtteaClient {
         H: MessageHandler<T> + 'static,
     {
         let handler = Arc::new(handler);
-        let type_erased_handler: ErasedHandler = Box::new(move |client, payload, topic| {
+        self.register_erased_handler::<T, _, _>(move |client, message, topic, _properties| {
             let handler = handler.clone();
-            Box::pin(async move {
-                // Get the registry to deserialize the message
-                let registry_guard = client.registry.read().await;
-                let message = registry_guard.deserialize_message::<T>(&payload)?;
-                drop(registry_guard);
-
+            async move {
                 handler.handle(client, message, topic).await;
                 Ok(())
-            })
-        });
+            }
+        })
+        .await;
+    }
+
+    // on_message_with_properties is on_message for handlers that also want
+    // the MQTT v5 properties the message was sent with (content type, user
+    // properties, response topic, etc). With MQTT 3.1.1, the properties
+    // are always empty.
+    pub async fn on_message_with_properties<T, F, Fut>(&self, handler: F)
+    where
+        T: Send + Sync + 'static,
+        F: Fn(Arc<MqtteaClient>, T, String, MessageProperties) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = ()> + Send + 'static,
+    {
+        let handler_cb = Arc::new(handler);
+        let concurrency_semaphore = self.concurrency_semaphore.clone();
+
+        self.register_erased_handler::<T, _, _>(move |client, message, topic, properties| {
+            let handler_internal = handler_cb.clone();
+            let semaphore_internal = concurrency_semaphore.clone();
+            async move {
+                // Same as on_message: run in our own task, bounded by
+                // the max_concurrency semaphore.
+                tokio::spawn(async move {
+                    let _permit = match semaphore_internal.acquire().await {
+                        Ok(permit) => permit,
+                        Err(e) => {
+                            error!(
+                                "failed to acquire semaphore permit for message_type={}: {e}",
+                                std::any::type_name::<T>().to_string()
+                            );
+                            return;
+                        }
+                    };
+                    handler_internal(client, message, topic, properties).await;
+                });
+                Ok(())
+            }
+        })
+        .await;
+    }
+
+    // on_request registers a handler for requests of type Req (sent with
+    // request() by another client), whose return value is sent back as
+    // the response. Requests that arrive without a response topic (e.g.
+    // a plain send_message, or MQTT 3.1.1) are still handled, but the
+    // response is dropped. Both Req and Resp must be registered.
+    pub async fn on_request<Req, Resp, F, Fut>(&self, handler: F)
+    where
+        Req: Send + Sync + 'static,
+        Resp: Send + Sync + 'static,
+        F: Fn(Arc<MqtteaClient>, Req, String) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Resp> + Send + 'static,
+    {
+        let handler = Arc::new(handler);
+        self.on_message_with_properties::<Req, _, _>(move |client, request, topic, properties| {
+            let handler = handler.clone();
+            async move {
+                let response = handler(client.clone(), request, topic.clone()).await;
+                let Some(response_topic) = properties.response_topic else {
+                    warn!("Request on topic '{topic}' has no response topic, dropping response");
+                    return;
+                };
+                let response_properties = MessageProperties {
+                    correlation_data: properties.correlation_data,
+                    ..Default::default()
+                };
+                if let Err(e) = client
+                    .send_direct(&response_topic, &response, response_properties)
+                    .await
+                {
+                    error!("Failed to send response to '{response_topic}': {e}");
+                }
+            }
+        })
+        .await;
+    }
+
+    // register_erased_handler wraps a typed handler into an ErasedHandler
+    // that deserializes the payload with the registry first. If the sender
+    // announced a serialization format (MQTT v5) that doesn't match the
+    // registered one, the message is rejected instead of being
+    // misinterpreted.
+    async fn register_erased_handler<T, F, Fut>(&self, handler: F)
+    where
+        T: Send + Sync + 'static,
+        F: Fn(Arc<MqtteaClient>, T, String, MessageProperties) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Result<(), MqtteaClientError>> + Send + 'static,
+    {
+        let handler = Arc::new(handler);
+        let type_erased_handler: ErasedHandler = Box::new(
+            move |client, payload, topic, properties| {
+                let handler = handler.clone();
+                Box::pin(async move {
+                    // Get the registry to deserialize the message
+                    let registry_guard = client.registry.read().await;
+                    if let Some(announced) = properties.format()
+                        && let Some(info) = registry_guard.get_type_info::<T>()
+                        && info.format != announced
+                    {
+                        return Err(MqtteaClientError::UnsupportedSerializationType(format!(
+                            "message on topic '{topic}' is {announced:?}, but {} is registered as {:?}",
+                            info.type_name, info.format
+                        )));
+                    }
+                    let message = registry_guard.deserialize_message::<T>(&payload)?;
+                    drop(registry_guard);
+
+                    handler(client, message, topic, properties).await
+                })
+            },
+        );
 
         let mut handlers_guard = self.handlers.write().await;
         handlers_guard.insert(std::any::type_name::<T>().to_string(), type_erased_handler);
@@ -413,10 +605,7 @@ impl MqtteaClient {
 
     // subscribe subscribes to a topic with the specified QoS.
     pub async fn subscribe(&self, topic: &str, qos: QoS) -> Result<(), MqtteaClientError> {
-        self.client
-            .subscribe(topic, qos)
-            .await
-            .map_err(MqtteaClientError::ConnectionError)?;
+        self.client.subscribe(topic, qos).await?;
 
         info!("Subscribed to topic: {} (QoS: {:?})", topic, qos);
         Ok(())
@@ -440,31 +629,85 @@ impl MqtteaClient {
         publish_options: Option<PublishOptions>,
         payload: Vec<u8>,
     ) -> Result<(), MqtteaClientError> {
-        let payload_size = payload.len();
+        let properties = expiry_properties(publish_options);
+        self.publish_internal(topic, publish_options, payload, properties, None)
+            .await
+    }
 
-        // Try to get the QoS and retain from the provided PublishOptions,
-        // and if not set, then fall back to the client-wide PublishOptions,
-        // and if not set, then fall back to the const defaults for each.
-        let qos = publish_options
-            .and_then(|opts| opts.qos)
-            .or_else(|| {
-                self.client_options
-                    .as_ref()
-                    .and_then(|client_opts| client_opts.publish_options)
-                    .and_then(|opts| opts.qos)
-            })
-            .unwrap_or(DEFAULT_QOS);
-        let retain = publish_options
-            .and_then(|opts| opts.retain)
-            .or_else(|| {
-                self.client_options
-                    .as_ref()
-                    .and_then(|client_opts| client_opts.publish_options)
-                    .and_then(|opts| opts.retain)
-            })
-            .unwrap_or(DEFAULT_RETAIN);
+    // publish_with_properties sends raw bytes with explicit MQTT v5
+    // properties. With MQTT 3.1.1, the properties are not sent.
+    pub async fn publish_with_properties(
+        &self,
+        topic: &str,
+        publish_options: Option<PublishOptions>,
+        payload: Vec<u8>,
+        properties: MessageProperties,
+    ) -> Result<(), MqtteaClientError> {
+        self.publish_internal(topic, publish_options, payload, Some(properties), None)
+            .await
+    }
 
-        match self.client.publish(topic, qos, retain, payload).await {
+    // publish_with_dedup_id is publish_with_opts with a caller-provided
+    // dedup ID. If the outbox is enabled and a message with the same
+    // dedup ID is still waiting for delivery, this message is skipped,
+    // which makes retrying a publish safe. Without an outbox, the dedup
+    // ID is ignored.
+    pub async fn publish_with_dedup_id(
+        &self,
+        topic: &str,
+        publish_options: Option<PublishOptions>,
+        payload: Vec<u8>,
+        dedup_id: impl Into<String>,
+    ) -> Result<(), MqtteaClientError> {
+        let properties = expiry_properties(publish_options);
+        self.publish_internal(
+            topic,
+            publish_options,
+            payload,
+            properties,
+            Some(dedup_id.into()),
+        )
+        .await
+    }
+
+    // publish_internal either persists the message to the outbox (if
+    // enabled), or hands it straight to the transport.
+    async fn publish_internal(
+        &self,
+        topic: &str,
+        publish_options: Option<PublishOptions>,
+        payload: Vec<u8>,
+        properties: Option<MessageProperties>,
+        dedup_id: Option<String>,
+    ) -> Result<(), MqtteaClientError> {
+        let (qos, retain) = self.resolve_qos_and_retain(publish_options);
+
+        if let Some(ref outbox) = self.outbox {
+            outbox.enqueue_with_properties(topic, qos, retain, payload, dedup_id, properties)?;
+            debug!("Persisted message to outbox for topic: {}", topic);
+            return Ok(());
+        }
+
+        self.publish_to_transport(topic, qos, retain, payload, properties.as_ref())
+            .await
+    }
+
+    // publish_to_transport hands a message to the transport, bypassing
+    // the outbox, and records publish stats.
+    async fn publish_to_transport(
+        &self,
+        topic: &str,
+        qos: QoS,
+        retain: bool,
+        payload: Vec<u8>,
+        properties: Option<&MessageProperties>,
+    ) -> Result<(), MqtteaClientError> {
+        let payload_size = payload.len();
+        match self
+            .client
+            .publish(topic.to_string(), qos, retain, payload, properties)
+            .await
+        {
             Ok(_) => {
                 self.publish_stats.increment_published(payload_size);
                 debug!("Published message to topic: {}", topic);
@@ -472,36 +715,194 @@ impl MqtteaClient {
             }
             Err(e) => {
                 self.publish_stats.increment_failed();
-                Err(MqtteaClientError::ConnectionError(e))
+                Err(e)
             }
         }
     }
 
+    // resolve_qos_and_retain tries to get the QoS and retain from the
+    // provided PublishOptions, and if not set, then falls back to the
+    // client-wide PublishOptions, and if not set, then falls back to the
+    // const defaults for each.
+    fn resolve_qos_and_retain(&self, publish_options: Option<PublishOptions>) -> (QoS, bool) {
+        let client_publish_options = self
+            .client_options
+            .as_ref()
+            .and_then(|client_opts| client_opts.publish_options);
+        let qos = publish_options
+            .and_then(|opts| opts.qos)
+            .or_else(|| client_publish_options.and_then(|opts| opts.qos))
+            .unwrap_or(DEFAULT_QOS);
+        let retain = publish_options
+            .and_then(|opts| opts.retain)
+            .or_else(|| client_publish_options.and_then(|opts| opts.retain))
+            .unwrap_or(DEFAULT_RETAIN);
+        (qos, retain)
+    }
+
     // send_message sends a message to a specific topic using
-    // client-scoped serialization.
+    // client-scoped serialization. With MQTT v5, the message carries
+    // its content type, format, and schema version as properties.
     pub async fn send_message<T>(&self, topic: &str, message: &T) -> Result<(), MqtteaClientError>
+    where
+        T: 'static,
+    {
+        self.send_message_with_properties(topic, message, MessageProperties::default())
+            .await
+    }
+
+    // send_message_with_properties is send_message with additional
+    // caller-provided properties. Anything set in properties overrides
+    // the defaults derived from the message type, and user properties
+    // are appended.
+    pub async fn send_message_with_properties<T>(
+        &self,
+        topic: &str,
+        message: &T,
+        properties: MessageProperties,
+    ) -> Result<(), MqtteaClientError>
+    where
+        T: 'static,
+    {
+        let (payload, publish_options, properties) =
+            self.prepare_message(message, properties).await?;
+        self.publish_internal(topic, publish_options, payload, Some(properties), None)
+            .await
+    }
+
+    // request sends a request message and waits for the typed response
+    // (MQTT v5 only). The request carries this client's response topic
+    // and unique correlation data, and whoever handles it with
+    // on_request sends the response back there. Requests bypass the
+    // outbox, since the caller is waiting on them.
+    pub async fn request<Req, Resp>(
+        &self,
+        topic: &str,
+        request: &Req,
+        timeout: Duration,
+    ) -> Result<Resp, MqtteaClientError>
+    where
+        Req: 'static,
+        Resp: 'static,
+    {
+        if self.client.protocol_version() != ProtocolVersion::V5 {
+            return Err(MqtteaClientError::unsupported_protocol_feature(
+                "request/response requires MQTT v5",
+            ));
+        }
+
+        self.responses_subscribed
+            .get_or_try_init(|| async {
+                self.subscribe(&self.response_topic, QoS::AtLeastOnce).await
+            })
+            .await?;
+
+        let correlation_data = format!(
+            "{}-{}",
+            self.client_id,
+            self.request_counter.fetch_add(1, Ordering::Relaxed)
+        )
+        .into_bytes();
+        let (response_tx, response_rx) = oneshot::channel();
+        self.pending_requests
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(correlation_data.clone(), response_tx);
+
+        let request_properties = MessageProperties {
+            response_topic: Some(self.response_topic.clone()),
+            correlation_data: Some(correlation_data.clone()),
+            ..Default::default()
+        };
+        let response = match self.send_direct(topic, request, request_properties).await {
+            Ok(()) => tokio::time::timeout(timeout, response_rx).await,
+            Err(e) => {
+                self.forget_request(&correlation_data);
+                return Err(e);
+            }
+        };
+        let response = match response {
+            Ok(Ok(response)) => response,
+            _ => {
+                self.forget_request(&correlation_data);
+                return Err(MqtteaClientError::RequestTimeout(topic.to_string()));
+            }
+        };
+
+        let registry_guard = self.registry.read().await;
+        registry_guard.deserialize_message::<Resp>(&response.payload)
+    }
+
+    // forget_request drops a pending request that will never complete.
+    fn forget_request(&self, correlation_data: &[u8]) {
+        self.pending_requests
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(correlation_data);
+    }
+
+    // send_direct serializes and publishes a message straight to the
+    // transport, bypassing the outbox. Used for requests and responses,
+    // which are only useful while someone is waiting for them. These
+    // are never retained.
+    async fn send_direct<T>(
+        &self,
+        topic: &str,
+        message: &T,
+        properties: MessageProperties,
+    ) -> Result<(), MqtteaClientError>
+    where
+        T: 'static,
+    {
+        let (payload, publish_options, properties) =
+            self.prepare_message(message, properties).await?;
+        let (qos, _) = self.resolve_qos_and_retain(publish_options);
+        self.publish_to_transport(topic, qos, false, payload, Some(&properties))
+            .await
+    }
+
+    // prepare_message serializes a message with the registry, and builds
+    // its properties from the registered type, merged with any
+    // caller-provided ones.
+    async fn prepare_message<T>(
+        &self,
+        message: &T,
+        overrides: MessageProperties,
+    ) -> Result<(Vec<u8>, Option<PublishOptions>, MessageProperties), MqtteaClientError>
     where
         T: 'static,
     {
         let registry_guard = self.registry.read().await;
         let payload = registry_guard.serialize_message(message)?;
-        // Get QoS from type info or use default.
-        let publish_options = registry_guard
-            .get_type_info::<T>()
-            .and_then(|info| info.publish_options);
+        // Get publish options and format from type info.
+        let type_info = registry_guard.get_type_info::<T>();
+        let publish_options = type_info.and_then(|info| info.publish_options);
+        let mut properties = type_info
+            .map(|info| MessageProperties::for_format(info.format, publish_options))
+            .unwrap_or_default();
         drop(registry_guard);
 
-        self.publish_with_opts(topic, publish_options, payload)
-            .await
+        if overrides.content_type.is_some() {
+            properties.content_type = overrides.content_type;
+        }
+        if overrides.message_expiry.is_some() {
+            properties.message_expiry = overrides.message_expiry;
+        }
+        if overrides.response_topic.is_some() {
+            properties.response_topic = overrides.response_topic;
+        }
+        if overrides.correlation_data.is_some() {
+            properties.correlation_data = overrides.correlation_data;
+        }
+        properties.user_properties.extend(overrides.user_properties);
+
+        Ok((payload, publish_options, properties))
     }
 
     // disconnect gracefully shuts down the MQTT client connection. Should
     // be called before dropping the client to ensure clean shutdown
     pub async fn disconnect(&self) -> Result<(), MqtteaClientError> {
-        self.client
-            .disconnect()
-            .await
-            .map_err(MqtteaClientError::ConnectionError)?;
+        self.client.disconnect().await?;
 
         info!("MQTT client disconnected");
         Ok(())
@@ -577,3 +978,51 @@ impl SuperBasicBackoff {
         self.current = std::time::Duration::from_millis(100);
     }
 }
+
+// expiry_properties builds the properties for a raw publish, which
+// only carry the message expiry from PublishOptions (if any).
+fn expiry_properties(publish_options: Option<PublishOptions>) -> Option<MessageProperties> {
+    publish_options
+        .and_then(|opts| opts.message_expiry)
+        .map(|message_expiry| MessageProperties {
+            message_expiry: Some(message_expiry),
+            ..Default::default()
+        })
+}
+
+// take_response hands a received publish to the request() call waiting
+// for it, if it is a response to one of our requests. Returns the
+// publish back if it isn't.
+fn take_response(
+    pending_requests: &PendingRequests,
+    response_topic: &str,
+    publish: IncomingPublish,
+) -> Option<IncomingPublish> {
+    if publish.topic != response_topic {
+        return Some(publish);
+    }
+    let sender = publish
+        .properties
+        .correlation_data
+        .as_ref()
+        .and_then(|correlation_data| {
+            pending_requests
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .remove(correlation_data)
+        });
+    match sender {
+        Some(sender) => {
+            // The caller may have timed out in the meantime.
+            let _ = sender.send(publish);
+            None
+        }
+        None => {
+            warn!(
+                "Dropping response on topic {} with unknown correlation data",
+                response_topic
+            );
+            None
+        }
+    }
+}
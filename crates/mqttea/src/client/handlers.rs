@@ -27,18 +27,19 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 
-use crate::client::MqtteaClient;
+use crate::client::{MessageProperties, MqtteaClient};
 use crate::errors::MqtteaClientError;
 use crate::traits::MessageHandler;
 
 // ErasedHandler enables storing handlers for different message types in the
-// same collection: type-erased function that takes client, raw payload bytes
-// and topic -- returns a future.
+// same collection: type-erased function that takes client, raw payload bytes,
+// topic and message properties -- returns a future.
 pub type ErasedHandler = Box<
     dyn Fn(
             Arc<MqtteaClient>,
             Vec<u8>,
             String,
+            MessageProperties,
         ) -> std::pin::Pin<
             Box<dyn std::future::Future<Output = Result<(), MqtteaClientError>> + Send>,
         > + Send
@@ -29,6 +29,7 @@ use rumqttc::Publish;
 use tokio::sync::RwLock;
 use tracing::debug;
 
+use crate::client::MessageProperties;
 use crate::registry::MqttRegistry;
 
 // ReceivedMessage stores a parsed MQTT message ready for processing. It
@@ -44,6 +45,9 @@ pub struct ReceivedMessage {
     pub payload: Vec<u8>,
     // payload_size caches the payload size for efficient statistics tracking.
     pub payload_size: usize,
+    // properties are the MQTT v5 properties the message was sent
+    // with (always empty for MQTT 3.1.1).
+    pub properties: MessageProperties,
 }
 
 impl ReceivedMessage {
@@ -54,8 +58,23 @@ impl ReceivedMessage {
         publish: &Publish,
         registry: Arc<RwLock<MqttRegistry>>,
     ) -> Option<Self> {
-        let topic = publish.topic.clone();
-        let payload = publish.payload.to_vec();
+        Self::from_parts(
+            publish.topic.clone(),
+            publish.payload.to_vec(),
+            MessageProperties::default(),
+            registry,
+        )
+        .await
+    }
+
+    // from_parts builds a ReceivedMessage from an already-decoded
+    // publish, for either protocol version.
+    pub async fn from_parts(
+        topic: String,
+        payload: Vec<u8>,
+        properties: MessageProperties,
+        registry: Arc<RwLock<MqttRegistry>>,
+    ) -> Option<Self> {
         let payload_size = payload.len();
 
         debug!("Looking for pattern match for topic: {}", topic);
@@ -67,6 +86,7 @@ impl ReceivedMessage {
                 type_name: type_info.type_name.clone(),
                 payload,
                 payload_size,
+                properties,
             })
     }
 }
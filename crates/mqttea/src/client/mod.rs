@@ -25,12 +25,18 @@ mod core;
 mod handlers;
 mod messages;
 mod options;
+mod properties;
 mod registry;
 mod topic_patterns;
+mod transport;
 
 pub use core::MqtteaClient;
 
 pub use handlers::{ClosureAdapter, ErasedHandler};
 pub use messages::ReceivedMessage;
-pub use options::{ClientCredentials, ClientOptions, ClientTlsConfig, ClientTlsIdentity};
+pub use options::{
+    ClientCredentials, ClientOptions, ClientTlsConfig, ClientTlsIdentity, ProtocolVersion,
+};
+pub use properties::{MessageProperties, USER_PROPERTY_FORMAT, USER_PROPERTY_SCHEMA_VERSION};
 pub use topic_patterns::TopicPatterns;
+pub(crate) use transport::{Transport, TransportEvent};
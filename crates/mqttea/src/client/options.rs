@@ -23,6 +23,7 @@ use rumqttc::QoS;
 use tokio::time::Duration;
 
 use crate::auth::{CredentialsProvider, StaticCredentials};
+use crate::outbox::OutboxOptions;
 use crate::registry::types::PublishOptions;
 
 // ClientOptions are optional parameters that can be
@@ -67,6 +68,27 @@ pub struct ClientOptions {
     // processed concurrently. If unset, defaults to 1, which is
     // effectively sequential processing.
     pub max_concurrency: Option<usize>,
+    // outbox enables the durable disk-backed outbox. When set, every
+    // publish is persisted first and delivered in order once the broker
+    // is reachable, instead of failing or being lost while disconnected.
+    pub outbox: Option<OutboxOptions>,
+    // protocol_version selects the MQTT protocol version to speak to
+    // the broker. MQTT v5 is needed for message properties (content
+    // type, user properties, message expiry) and request/response.
+    // Defaults to ProtocolVersion::V311.
+    pub protocol_version: Option<ProtocolVersion>,
+    // response_topic is the topic this client receives responses on
+    // when making requests (MQTT v5 only).
+    // Defaults to mqttea/responses/<client_id>.
+    pub response_topic: Option<String>,
+}
+
+// ProtocolVersion is the MQTT protocol version the client speaks.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ProtocolVersion {
+    #[default]
+    V311,
+    V5,
 }
 
 impl ClientOptions {
@@ -106,6 +128,21 @@ impl ClientOptions {
         self
     }
 
+    pub fn with_protocol_version(mut self, protocol_version: ProtocolVersion) -> Self {
+        self.protocol_version = Some(protocol_version);
+        self
+    }
+
+    pub fn with_response_topic(mut self, response_topic: impl Into<String>) -> Self {
+        self.response_topic = Some(response_topic.into());
+        self
+    }
+
+    pub fn with_outbox(mut self, outbox: OutboxOptions) -> Self {
+        self.outbox = Some(outbox);
+        self
+    }
+
     /// Set a credentials provider for dynamic credential fetching.
     ///
     /// Use this for OAuth2 or other token-based authentication where
@@ -0,0 +1,147 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// src/client/properties.rs
+// Protocol-agnostic MQTT v5 message properties.
+//
+// MessageProperties carries the subset of MQTT v5 publish properties
+// mqttea cares about. When the client speaks MQTT 3.1.1 they are
+// simply not sent (and are always empty on received messages).
+
+use std::time::Duration;
+
+use rumqttc::v5::mqttbytes::v5::PublishProperties;
+use serde::{Deserialize, Serialize};
+
+use crate::registry::types::{PublishOptions, SerializationFormat};
+
+// USER_PROPERTY_FORMAT is the user property carrying the
+// SerializationFormat name of the payload.
+pub const USER_PROPERTY_FORMAT: &str = "mqttea-format";
+// USER_PROPERTY_SCHEMA_VERSION is the user property carrying the
+// schema version from the message type's PublishOptions.
+pub const USER_PROPERTY_SCHEMA_VERSION: &str = "mqttea-schema-version";
+
+// MessageProperties stores MQTT v5 properties for a sent or
+// received message.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct MessageProperties {
+    // content_type is the MIME type of the payload.
+    pub content_type: Option<String>,
+    // user_properties are arbitrary key/value pairs, in order.
+    pub user_properties: Vec<(String, String)>,
+    // message_expiry is how long the broker keeps the message
+    // before discarding it. For received messages, this is the
+    // time remaining.
+    pub message_expiry: Option<Duration>,
+    // response_topic is where the receiver should send its
+    // response, for request/response exchanges.
+    pub response_topic: Option<String>,
+    // correlation_data ties a response back to its request.
+    pub correlation_data: Option<Vec<u8>>,
+}
+
+impl MessageProperties {
+    // for_format builds the properties mqttea sends with a registered
+    // message type: the content type and format of the payload, plus
+    // the schema version and message expiry from PublishOptions.
+    pub fn for_format(
+        format: SerializationFormat,
+        publish_options: Option<PublishOptions>,
+    ) -> Self {
+        let mut properties = Self {
+            content_type: Some(format.content_type().to_string()),
+            user_properties: vec![(USER_PROPERTY_FORMAT.to_string(), format.name().to_string())],
+            ..Default::default()
+        };
+        if let Some(opts) = publish_options {
+            properties.message_expiry = opts.message_expiry;
+            if let Some(schema_version) = opts.schema_version {
+                properties.user_properties.push((
+                    USER_PROPERTY_SCHEMA_VERSION.to_string(),
+                    schema_version.to_string(),
+                ));
+            }
+        }
+        properties
+    }
+
+    // user_property returns the first user property with the given key.
+    pub fn user_property(&self, key: &str) -> Option<&str> {
+        self.user_properties
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    // format returns the SerializationFormat the sender says the payload
+    // is in, from the mqttea-format user property, or failing that, the
+    // content type. Returns None if the sender didn't say (or isn't
+    // using MQTT v5).
+    pub fn format(&self) -> Option<SerializationFormat> {
+        self.user_property(USER_PROPERTY_FORMAT)
+            .and_then(SerializationFormat::from_name)
+            .or_else(|| {
+                self.content_type
+                    .as_deref()
+                    .and_then(SerializationFormat::from_content_type)
+            })
+    }
+
+    // schema_version returns the sender's schema version, if any.
+    pub fn schema_version(&self) -> Option<&str> {
+        self.user_property(USER_PROPERTY_SCHEMA_VERSION)
+    }
+
+    // is_empty checks if no properties are set.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    // to_v5 converts to rumqttc's v5 PublishProperties.
+    pub(crate) fn to_v5(&self) -> PublishProperties {
+        PublishProperties {
+            content_type: self.content_type.clone(),
+            user_properties: self.user_properties.clone(),
+            // Expiry intervals are whole seconds on the wire; round up
+            // so a sub-second expiry doesn't turn into "never expires".
+            message_expiry_interval: self.message_expiry.map(|expiry| {
+                u32::try_from(expiry.as_secs() + u64::from(expiry.subsec_nanos() > 0))
+                    .unwrap_or(u32::MAX)
+            }),
+            response_topic: self.response_topic.clone(),
+            correlation_data: self.correlation_data.clone().map(Into::into),
+            ..Default::default()
+        }
+    }
+
+    // from_v5 converts from rumqttc's v5 PublishProperties.
+    pub(crate) fn from_v5(properties: &PublishProperties) -> Self {
+        Self {
+            content_type: properties.content_type.clone(),
+            user_properties: properties.user_properties.clone(),
+            message_expiry: properties
+                .message_expiry_interval
+                .map(|secs| Duration::from_secs(secs.into())),
+            response_topic: properties.response_topic.clone(),
+            correlation_data: properties
+                .correlation_data
+                .as_ref()
+                .map(|data| data.to_vec()),
+        }
+    }
+}
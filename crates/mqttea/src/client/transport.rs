@@ -0,0 +1,255 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// src/client/transport.rs
+// Thin wrapper over the rumqttc MQTT 3.1.1 and MQTT v5 clients.
+//
+// rumqttc implements each protocol version as its own client, event
+// loop, and packet types. Transport and TransportEventLoop hide which
+// one is in use, so the rest of the client only deals with
+// TransportEvents and MessageProperties.
+
+use std::time::Duration;
+
+use rumqttc::v5::mqttbytes::QoS as QoSV5;
+use rumqttc::{Event, Outgoing, Packet, QoS, v5};
+use thiserror::Error;
+
+use crate::client::{ClientCredentials, MessageProperties, ProtocolVersion};
+use crate::errors::MqtteaClientError;
+
+// Transport is the sending half of the connection.
+pub(crate) enum Transport {
+    V311(rumqttc::AsyncClient),
+    V5(v5::AsyncClient),
+}
+
+// TransportEventLoop is the receiving half of the connection,
+// which also drives all network I/O.
+pub(crate) enum TransportEventLoop {
+    V311(Box<rumqttc::EventLoop>),
+    V5(Box<v5::EventLoop>),
+}
+
+// TransportEvent is the subset of event loop events the client
+// acts on, with protocol differences smoothed over.
+pub(crate) enum TransportEvent {
+    ConnAck,
+    Publish(IncomingPublish),
+    PubAck(u16),
+    PubComp(u16),
+    OutgoingPublish(u16),
+    Other,
+}
+
+// IncomingPublish is a received publish packet.
+pub(crate) struct IncomingPublish {
+    pub topic: String,
+    pub payload: Vec<u8>,
+    // properties is always empty for MQTT 3.1.1.
+    pub properties: MessageProperties,
+}
+
+// TransportError is an event loop connection error.
+#[derive(Debug, Error)]
+pub(crate) enum TransportError {
+    #[error(transparent)]
+    V311(rumqttc::ConnectionError),
+    #[error(transparent)]
+    V5(v5::ConnectionError),
+}
+
+// TransportSettings is everything needed to set up the connection.
+pub(crate) struct TransportSettings<'a> {
+    pub protocol_version: ProtocolVersion,
+    pub client_id: &'a str,
+    pub broker_host: &'a str,
+    pub broker_port: u16,
+    pub keep_alive: Duration,
+    pub credentials: Option<ClientCredentials>,
+    pub channel_capacity: usize,
+}
+
+impl Transport {
+    // new creates the client and event loop for the configured protocol
+    // version. Sessions are persistent (clean session/clean start off),
+    // so subscriptions and in-flight messages survive reconnects.
+    pub(crate) fn new(settings: TransportSettings<'_>) -> (Self, TransportEventLoop) {
+        match settings.protocol_version {
+            ProtocolVersion::V311 => {
+                let mut mqtt_options = rumqttc::MqttOptions::new(
+                    settings.client_id,
+                    settings.broker_host,
+                    settings.broker_port,
+                );
+                mqtt_options.set_keep_alive(settings.keep_alive);
+                mqtt_options.set_clean_session(false);
+                if let Some(credentials) = settings.credentials {
+                    mqtt_options.set_credentials(credentials.username, credentials.password);
+                }
+                let (client, event_loop) =
+                    rumqttc::AsyncClient::new(mqtt_options, settings.channel_capacity);
+                (
+                    Self::V311(client),
+                    TransportEventLoop::V311(Box::new(event_loop)),
+                )
+            }
+            ProtocolVersion::V5 => {
+                let mut mqtt_options = v5::MqttOptions::new(
+                    settings.client_id,
+                    settings.broker_host,
+                    settings.broker_port,
+                );
+                mqtt_options.set_keep_alive(settings.keep_alive);
+                mqtt_options.set_clean_start(false);
+                if let Some(credentials) = settings.credentials {
+                    mqtt_options.set_credentials(credentials.username, credentials.password);
+                }
+                let (client, event_loop) =
+                    v5::AsyncClient::new(mqtt_options, settings.channel_capacity);
+                (
+                    Self::V5(client),
+                    TransportEventLoop::V5(Box::new(event_loop)),
+                )
+            }
+        }
+    }
+
+    pub(crate) fn protocol_version(&self) -> ProtocolVersion {
+        match self {
+            Self::V311(_) => ProtocolVersion::V311,
+            Self::V5(_) => ProtocolVersion::V5,
+        }
+    }
+
+    // publish hands a message to the event loop. Properties are
+    // dropped when speaking MQTT 3.1.1.
+    pub(crate) async fn publish(
+        &self,
+        topic: String,
+        qos: QoS,
+        retain: bool,
+        payload: Vec<u8>,
+        properties: Option<&MessageProperties>,
+    ) -> Result<(), MqtteaClientError> {
+        match self {
+            Self::V311(client) => Ok(client.publish(topic, qos, retain, payload).await?),
+            Self::V5(client) => {
+                let qos = qos_to_v5(qos);
+                match properties {
+                    Some(properties) => Ok(client
+                        .publish_with_properties(topic, qos, retain, payload, properties.to_v5())
+                        .await?),
+                    None => Ok(client.publish(topic, qos, retain, payload).await?),
+                }
+            }
+        }
+    }
+
+    pub(crate) async fn subscribe(&self, topic: &str, qos: QoS) -> Result<(), MqtteaClientError> {
+        match self {
+            Self::V311(client) => Ok(client.subscribe(topic, qos).await?),
+            Self::V5(client) => Ok(client.subscribe(topic, qos_to_v5(qos)).await?),
+        }
+    }
+
+    pub(crate) async fn disconnect(&self) -> Result<(), MqtteaClientError> {
+        match self {
+            Self::V311(client) => Ok(client.disconnect().await?),
+            Self::V5(client) => Ok(client.disconnect().await?),
+        }
+    }
+}
+
+impl TransportEventLoop {
+    // poll drives the connection and returns the next event.
+    pub(crate) async fn poll(&mut self) -> Result<TransportEvent, TransportError> {
+        match self {
+            Self::V311(event_loop) => {
+                let event = event_loop.poll().await.map_err(TransportError::V311)?;
+                Ok(match event {
+                    Event::Incoming(Packet::ConnAck(_)) => TransportEvent::ConnAck,
+                    Event::Incoming(Packet::Publish(publish)) => {
+                        TransportEvent::Publish(IncomingPublish {
+                            topic: publish.topic,
+                            payload: publish.payload.to_vec(),
+                            properties: MessageProperties::default(),
+                        })
+                    }
+                    Event::Incoming(Packet::PubAck(ack)) => TransportEvent::PubAck(ack.pkid),
+                    Event::Incoming(Packet::PubComp(comp)) => TransportEvent::PubComp(comp.pkid),
+                    Event::Outgoing(Outgoing::Publish(pkid)) => {
+                        TransportEvent::OutgoingPublish(pkid)
+                    }
+                    _ => TransportEvent::Other,
+                })
+            }
+            Self::V5(event_loop) => {
+                let event = event_loop.poll().await.map_err(TransportError::V5)?;
+                Ok(match event {
+                    v5::Event::Incoming(v5::Incoming::ConnAck(_)) => TransportEvent::ConnAck,
+                    v5::Event::Incoming(v5::Incoming::Publish(publish)) => {
+                        TransportEvent::Publish(IncomingPublish {
+                            topic: String::from_utf8_lossy(&publish.topic).into_owned(),
+                            payload: publish.payload.to_vec(),
+                            properties: publish
+                                .properties
+                                .as_ref()
+                                .map(MessageProperties::from_v5)
+                                .unwrap_or_default(),
+                        })
+                    }
+                    v5::Event::Incoming(v5::Incoming::PubAck(ack)) => {
+                        TransportEvent::PubAck(ack.pkid)
+                    }
+                    v5::Event::Incoming(v5::Incoming::PubComp(comp)) => {
+                        TransportEvent::PubComp(comp.pkid)
+                    }
+                    v5::Event::Outgoing(Outgoing::Publish(pkid)) => {
+                        TransportEvent::OutgoingPublish(pkid)
+                    }
+                    _ => TransportEvent::Other,
+                })
+            }
+        }
+    }
+
+    // set_credentials updates the credentials used on the next
+    // reconnection attempt.
+    pub(crate) fn set_credentials(&mut self, credentials: ClientCredentials) {
+        match self {
+            Self::V311(event_loop) => {
+                event_loop
+                    .mqtt_options
+                    .set_credentials(credentials.username, credentials.password);
+            }
+            Self::V5(event_loop) => {
+                event_loop
+                    .options
+                    .set_credentials(credentials.username, credentials.password);
+            }
+        }
+    }
+}
+
+fn qos_to_v5(qos: QoS) -> QoSV5 {
+    match qos {
+        QoS::AtMostOnce => QoSV5::AtMostOnce,
+        QoS::AtLeastOnce => QoSV5::AtLeastOnce,
+        QoS::ExactlyOnce => QoSV5::ExactlyOnce,
+    }
+}
@@ -29,6 +29,11 @@ pub enum MqtteaClientError {
     // (network issues, auth failures).
     #[error("MQTT connection error: {0}")]
     ConnectionError(#[from] rumqttc::ClientError),
+    // ConnectionErrorV5 is ConnectionError for clients speaking
+    // MQTT v5, which rumqttc implements as a separate client. Boxed,
+    // since it carries the whole failed request.
+    #[error("MQTT v5 connection error: {0}")]
+    ConnectionErrorV5(Box<rumqttc::v5::ClientError>),
     // SerializationError occurs when converting messages to bytes
     // fails (malformed data).
     #[error("Message serialization error: {0}")]
@@ -84,6 +89,25 @@ pub enum MqtteaClientError {
     // CredentialsError occurs when fetching credentials from a provider fails.
     #[error("Credentials provider error: {0}")]
     CredentialsError(String),
+    // OutboxError occurs when the durable outbox can't persist or
+    // load messages (disk errors, outbox full of in-flight messages).
+    #[error("Outbox error: {0}")]
+    OutboxError(String),
+    // RequestTimeout occurs when no response to a request() arrives
+    // before the timeout.
+    #[error("Request timed out waiting for response on topic: {0}")]
+    RequestTimeout(String),
+    // UnsupportedProtocolFeature occurs when using a feature the
+    // configured MQTT protocol version doesn't support (e.g.
+    // request/response over MQTT 3.1.1).
+    #[error("Unsupported protocol feature: {0}")]
+    UnsupportedProtocolFeature(String),
+}
+
+impl From<rumqttc::v5::ClientError> for MqtteaClientError {
+    fn from(error: rumqttc::v5::ClientError) -> Self {
+        Self::ConnectionErrorV5(Box::new(error))
+    }
 }
 
 // Convenience implementations for creating common error types.
@@ -123,9 +147,22 @@ impl MqtteaClientError {
         Self::CredentialsError(message.into())
     }
 
+    // Create an OutboxError.
+    pub fn outbox_error(message: impl Into<String>) -> Self {
+        Self::OutboxError(message.into())
+    }
+
+    // Create an UnsupportedProtocolFeature error.
+    pub fn unsupported_protocol_feature(message: impl Into<String>) -> Self {
+        Self::UnsupportedProtocolFeature(message.into())
+    }
+
     // Check if this error is related to network connectivity.
     pub fn is_connection_error(&self) -> bool {
-        matches!(self, Self::ConnectionError(_))
+        matches!(
+            self,
+            Self::ConnectionError(_) | Self::ConnectionErrorV5(_) | Self::RequestTimeout(_)
+        )
     }
 
     // Check if this error is related to message format/parsing.
@@ -22,6 +22,7 @@ pub mod auth;
 pub mod client;
 pub mod errors;
 pub mod message_types;
+pub mod outbox;
 pub mod registry;
 pub mod stats;
 pub mod traits;
@@ -32,9 +33,10 @@ pub use auth::{
     ClientCredentialsProvider, ClientId, ClientSecret, CredentialsProvider, OAuth2Config,
     OAuth2TokenProvider, StaticCredentials, TokenCredentialsProvider, TokenProvider,
 };
-pub use client::{MqtteaClient, TopicPatterns};
+pub use client::{MessageProperties, MqtteaClient, ProtocolVersion, TopicPatterns};
 pub use errors::MqtteaClientError;
 pub use message_types::RawMessage;
+pub use outbox::{OutboxOptions, RetentionPolicy};
 pub use registry::{MessageTypeInfo, MqttRegistry, SerializationFormat};
 pub use rumqttc::QoS;
 pub use stats::{PublishStats, QueueStats};
@@ -0,0 +1,30 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// src/outbox/mod.rs
+// Durable disk-backed outbox for outgoing messages.
+//
+// When enabled via ClientOptions::with_outbox, every publish is first
+// written to disk and then drained to the broker in order, so messages
+// published while the broker is unreachable survive reconnects and
+// process restarts.
+
+mod options;
+mod store;
+
+pub use options::{OutboxOptions, RetentionPolicy};
+pub use store::{Outbox, PendingMessage};
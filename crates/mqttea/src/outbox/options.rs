@@ -0,0 +1,136 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// src/outbox/options.rs
+// Configuration options for the durable outbox.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+pub const DEFAULT_OUTBOX_MAX_MESSAGES: usize = 100_000;
+pub const DEFAULT_OUTBOX_MAX_BYTES: usize = 256 * 1024 * 1024;
+pub const DEFAULT_OUTBOX_MAX_IN_FLIGHT: usize = 100;
+
+// OutboxOptions configures where the outbox lives on disk and how
+// large it is allowed to grow. Anything left as None falls back to
+// the DEFAULT_OUTBOX_* consts.
+#[derive(Clone, Debug)]
+pub struct OutboxOptions {
+    // directory is where outbox entries are persisted, one file per
+    // message. It is created if it doesn't exist. Each client needs
+    // its own directory.
+    pub directory: PathBuf,
+    // max_messages is the maximum number of messages kept on disk.
+    // When full, the oldest messages not yet handed to the broker
+    // are dropped to make room.
+    pub max_messages: Option<usize>,
+    // max_bytes is the maximum total payload size kept on disk,
+    // enforced the same way as max_messages.
+    pub max_bytes: Option<usize>,
+    // max_in_flight is the maximum number of messages handed to the
+    // broker connection that haven't been acknowledged yet.
+    pub max_in_flight: Option<usize>,
+    // retention is a list of per-topic retention policies. The first
+    // policy whose topic filter matches a message's topic applies;
+    // messages matching no policy are kept until delivered or evicted.
+    pub retention: Vec<RetentionPolicy>,
+}
+
+impl OutboxOptions {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+            max_messages: None,
+            max_bytes: None,
+            max_in_flight: None,
+            retention: Vec::new(),
+        }
+    }
+
+    pub fn with_max_messages(mut self, max_messages: usize) -> Self {
+        self.max_messages = Some(max_messages);
+        self
+    }
+
+    pub fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    pub fn with_max_in_flight(mut self, max_in_flight: usize) -> Self {
+        self.max_in_flight = Some(max_in_flight);
+        self
+    }
+
+    pub fn with_retention(mut self, policy: RetentionPolicy) -> Self {
+        self.retention.push(policy);
+        self
+    }
+}
+
+// RetentionPolicy limits how long, and how many, messages for a set
+// of topics are kept in the outbox while they wait for the broker.
+// For example, a "latest state only" topic can keep a single message,
+// while an audit topic keeps everything for a day.
+#[derive(Clone, Debug)]
+pub struct RetentionPolicy {
+    // topic_filter is an MQTT topic filter, and supports the
+    // single-level (+) and multi-level (#) wildcards.
+    pub topic_filter: String,
+    // max_age is how long a message may wait in the outbox before it
+    // is expired instead of being published.
+    pub max_age: Option<Duration>,
+    // max_messages is how many messages matching this policy may be
+    // waiting at once. Older ones are dropped first.
+    pub max_messages: Option<usize>,
+}
+
+impl RetentionPolicy {
+    pub fn new(topic_filter: impl Into<String>) -> Self {
+        Self {
+            topic_filter: topic_filter.into(),
+            max_age: None,
+            max_messages: None,
+        }
+    }
+
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    pub fn with_max_messages(mut self, max_messages: usize) -> Self {
+        self.max_messages = Some(max_messages);
+        self
+    }
+
+    // matches checks if the topic matches this policy's topic filter,
+    // using standard MQTT wildcard semantics.
+    pub fn matches(&self, topic: &str) -> bool {
+        let mut filter_levels = self.topic_filter.split('/');
+        let mut topic_levels = topic.split('/');
+        loop {
+            match (filter_levels.next(), topic_levels.next()) {
+                (Some("#"), _) => return true,
+                (Some("+"), Some(_)) => continue,
+                (Some(filter), Some(level)) if filter == level => continue,
+                (None, None) => return true,
+                _ => return false,
+            }
+        }
+    }
+}
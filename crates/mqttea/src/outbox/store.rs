@@ -0,0 +1,633 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// src/outbox/store.rs
+// Disk-backed outbox storage and delivery tracking.
+//
+// Each message is written to its own file, named by a monotonically
+// increasing sequence number, so replay order is just filename order
+// and a crash can at worst leave behind a partially written temp
+// file. A message file is only deleted once the broker has
+// acknowledged it (or, for QoS 0, once it has been written to the
+// connection), which gives at-least-once delivery across reconnects
+// and restarts.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rumqttc::QoS;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
+use tracing::{debug, error, warn};
+
+use crate::client::{MessageProperties, Transport, TransportEvent};
+use crate::errors::MqtteaClientError;
+use crate::outbox::options::{
+    DEFAULT_OUTBOX_MAX_BYTES, DEFAULT_OUTBOX_MAX_IN_FLIGHT, DEFAULT_OUTBOX_MAX_MESSAGES,
+    OutboxOptions, RetentionPolicy,
+};
+use crate::stats::PublishStatsTracker;
+
+const ENTRY_EXTENSION: &str = "msg";
+const TEMP_EXTENSION: &str = "tmp";
+
+// EntryHeader is the first line of every outbox file, followed
+// by the raw payload bytes.
+#[derive(Debug, Serialize, Deserialize)]
+struct EntryHeader {
+    seq: u64,
+    topic: String,
+    qos: u8,
+    retain: bool,
+    dedup_id: Option<String>,
+    created_at: DateTime<Utc>,
+    #[serde(default)]
+    properties: Option<MessageProperties>,
+}
+
+// OutboxEntry is the in-memory index entry for a message on disk.
+// Payloads are only read back when the message is being sent.
+#[derive(Debug)]
+struct OutboxEntry {
+    topic: String,
+    qos: QoS,
+    retain: bool,
+    dedup_id: Option<String>,
+    created_at: DateTime<Utc>,
+    payload_size: usize,
+    // message_expiry is the MQTT v5 message expiry the message was
+    // published with; it counts time spent in the outbox too.
+    message_expiry: Option<Duration>,
+    // policy is the index of the matching RetentionPolicy, if any.
+    policy: Option<usize>,
+}
+
+// OutboxState tracks everything on disk, plus where each message is
+// in its delivery lifecycle:
+// - seq >= cursor: waiting to be handed to the MQTT client.
+// - awaiting_outgoing: handed to the MQTT client, but not yet
+//   written to the connection (and so without a packet ID).
+// - awaiting_ack: written to the connection, keyed by packet ID.
+#[derive(Debug, Default)]
+struct OutboxState {
+    entries: BTreeMap<u64, OutboxEntry>,
+    dedup_ids: HashMap<String, u64>,
+    next_seq: u64,
+    cursor: u64,
+    awaiting_outgoing: VecDeque<u64>,
+    awaiting_ack: HashMap<u16, u64>,
+    connected: bool,
+    bytes: usize,
+}
+
+impl OutboxState {
+    fn in_flight(&self) -> usize {
+        self.awaiting_outgoing.len() + self.awaiting_ack.len()
+    }
+}
+
+// PendingMessage is a message read back from the outbox that is
+// ready to be handed to the MQTT client.
+#[derive(Debug, Clone)]
+pub struct PendingMessage {
+    pub seq: u64,
+    pub topic: String,
+    pub qos: QoS,
+    pub retain: bool,
+    pub dedup_id: Option<String>,
+    // properties are the MQTT v5 properties to publish with, with
+    // message_expiry reduced by the time spent in the outbox.
+    pub properties: Option<MessageProperties>,
+    pub payload: Vec<u8>,
+}
+
+// Outbox is the durable queue sitting in front of the MQTT client.
+// MqtteaClient enqueues into it on publish, a background task drains
+// it while connected, and the event loop feeds it packet events so
+// it knows when messages can be deleted.
+#[derive(Debug)]
+pub struct Outbox {
+    directory: PathBuf,
+    max_messages: usize,
+    max_bytes: usize,
+    max_in_flight: usize,
+    retention: Vec<RetentionPolicy>,
+    state: Mutex<OutboxState>,
+    // notify wakes the drain task when there may be something new
+    // to send (new message, reconnect, or an ack freeing a slot).
+    notify: Notify,
+    stats: Arc<PublishStatsTracker>,
+}
+
+impl Outbox {
+    // open creates the outbox directory if needed and loads any messages
+    // left over from a previous run, which will be replayed in order
+    // once the client connects.
+    pub fn open(
+        options: &OutboxOptions,
+        stats: Arc<PublishStatsTracker>,
+    ) -> Result<Arc<Self>, MqtteaClientError> {
+        fs::create_dir_all(&options.directory).map_err(|e| {
+            MqtteaClientError::outbox_error(format!(
+                "failed to create outbox directory {}: {e}",
+                options.directory.display()
+            ))
+        })?;
+
+        let outbox = Self {
+            directory: options.directory.clone(),
+            max_messages: options.max_messages.unwrap_or(DEFAULT_OUTBOX_MAX_MESSAGES),
+            max_bytes: options.max_bytes.unwrap_or(DEFAULT_OUTBOX_MAX_BYTES),
+            max_in_flight: options
+                .max_in_flight
+                .unwrap_or(DEFAULT_OUTBOX_MAX_IN_FLIGHT)
+                .max(1),
+            retention: options.retention.clone(),
+            state: Mutex::new(OutboxState::default()),
+            notify: Notify::new(),
+            stats,
+        };
+        outbox.load()?;
+        Ok(Arc::new(outbox))
+    }
+
+    // load rebuilds the in-memory index from the outbox directory.
+    // Unreadable entries and leftover temp files are removed.
+    fn load(&self) -> Result<(), MqtteaClientError> {
+        let dir_entries = fs::read_dir(&self.directory).map_err(|e| {
+            MqtteaClientError::outbox_error(format!(
+                "failed to read outbox directory {}: {e}",
+                self.directory.display()
+            ))
+        })?;
+
+        let mut state = self.lock_state();
+        for dir_entry in dir_entries.flatten() {
+            let path = dir_entry.path();
+            match path.extension().and_then(|ext| ext.to_str()) {
+                Some(ENTRY_EXTENSION) => {}
+                Some(TEMP_EXTENSION) => {
+                    let _ = fs::remove_file(&path);
+                    continue;
+                }
+                _ => continue,
+            }
+
+            let (header, payload_size) = match read_entry(&path) {
+                Ok((header, payload)) => (header, payload.len()),
+                Err(e) => {
+                    warn!("Removing unreadable outbox entry {}: {e}", path.display());
+                    let _ = fs::remove_file(&path);
+                    continue;
+                }
+            };
+
+            let entry = OutboxEntry {
+                policy: self.policy_for(&header.topic),
+                message_expiry: header
+                    .properties
+                    .as_ref()
+                    .and_then(|properties| properties.message_expiry),
+                topic: header.topic,
+                qos: qos_from_u8(header.qos),
+                retain: header.retain,
+                dedup_id: header.dedup_id,
+                created_at: header.created_at,
+                payload_size,
+            };
+            if let Some(dedup_id) = &entry.dedup_id {
+                state.dedup_ids.insert(dedup_id.clone(), header.seq);
+            }
+            state.bytes += payload_size;
+            state.next_seq = state.next_seq.max(header.seq + 1);
+            state.entries.insert(header.seq, entry);
+            self.stats.increment_outbox_pending(payload_size);
+        }
+
+        if !state.entries.is_empty() {
+            debug!(
+                "Loaded {} message(s) from outbox {}",
+                state.entries.len(),
+                self.directory.display()
+            );
+        }
+        Ok(())
+    }
+
+    // enqueue persists a message to the outbox. If a dedup_id is given and
+    // a message with the same dedup_id is still waiting in the outbox, the
+    // new message is discarded, so callers can safely retry enqueueing the
+    // same logical message.
+    pub fn enqueue(
+        &self,
+        topic: &str,
+        qos: QoS,
+        retain: bool,
+        payload: Vec<u8>,
+        dedup_id: Option<String>,
+    ) -> Result<(), MqtteaClientError> {
+        self.enqueue_with_properties(topic, qos, retain, payload, dedup_id, None)
+    }
+
+    // enqueue_with_properties is enqueue for messages carrying MQTT v5
+    // properties, which are persisted alongside the message.
+    pub fn enqueue_with_properties(
+        &self,
+        topic: &str,
+        qos: QoS,
+        retain: bool,
+        payload: Vec<u8>,
+        dedup_id: Option<String>,
+        properties: Option<MessageProperties>,
+    ) -> Result<(), MqtteaClientError> {
+        let payload_size = payload.len();
+        if payload_size > self.max_bytes {
+            self.stats.increment_outbox_dropped();
+            return Err(MqtteaClientError::outbox_error(format!(
+                "message of {payload_size} bytes exceeds outbox max_bytes {}",
+                self.max_bytes
+            )));
+        }
+
+        let mut state = self.lock_state();
+        if let Some(dedup_id) = &dedup_id
+            && state.dedup_ids.contains_key(dedup_id)
+        {
+            debug!("Outbox already contains message with dedup_id {dedup_id}, skipping");
+            self.stats.increment_outbox_deduplicated();
+            return Ok(());
+        }
+
+        let policy = self.policy_for(topic);
+        if let Some(max_messages) = policy.and_then(|index| self.retention[index].max_messages) {
+            while self.count_waiting_for_policy(&state, policy) >= max_messages {
+                if !self.evict_oldest(&mut state, policy) {
+                    break;
+                }
+            }
+        }
+        while state.entries.len() >= self.max_messages
+            || state.bytes + payload_size > self.max_bytes
+        {
+            if !self.evict_oldest(&mut state, None) {
+                self.stats.increment_outbox_dropped();
+                return Err(MqtteaClientError::outbox_error(
+                    "outbox is full of in-flight messages",
+                ));
+            }
+        }
+
+        let seq = state.next_seq;
+        let header = EntryHeader {
+            seq,
+            topic: topic.to_string(),
+            qos: qos as u8,
+            retain,
+            dedup_id: dedup_id.clone(),
+            created_at: Utc::now(),
+            properties,
+        };
+        self.write_entry(&header, &payload)?;
+
+        state.next_seq += 1;
+        state.bytes += payload_size;
+        if let Some(dedup_id) = &dedup_id {
+            state.dedup_ids.insert(dedup_id.clone(), seq);
+        }
+        state.entries.insert(
+            seq,
+            OutboxEntry {
+                topic: header.topic,
+                qos,
+                retain,
+                dedup_id,
+                created_at: header.created_at,
+                payload_size,
+                message_expiry: header
+                    .properties
+                    .as_ref()
+                    .and_then(|properties| properties.message_expiry),
+                policy,
+            },
+        );
+        drop(state);
+
+        self.stats.increment_outbox_pending(payload_size);
+        self.stats.increment_outbox_persisted();
+        self.notify.notify_one();
+        Ok(())
+    }
+
+    // next_pending returns the next message to hand to the MQTT client,
+    // in sequence order, skipping (and removing) anything that has
+    // outlived its retention policy. Returns None while disconnected or
+    // when max_in_flight messages are already awaiting acknowledgement.
+    //
+    // The returned message is considered in flight as soon as this
+    // returns; if handing it to the client fails, call release.
+    pub fn next_pending(&self) -> Option<PendingMessage> {
+        let mut state = self.lock_state();
+        if !state.connected || state.in_flight() >= self.max_in_flight {
+            return None;
+        }
+
+        let now = Utc::now();
+        loop {
+            let (seq, entry) = state
+                .entries
+                .range(state.cursor..)
+                .next()
+                .map(|(seq, entry)| (*seq, entry))?;
+
+            if self.is_expired(entry, now) {
+                debug!("Expiring outbox message {seq} for topic {}", entry.topic);
+                self.remove_entry(&mut state, seq);
+                self.stats.increment_outbox_expired();
+                continue;
+            }
+
+            let (header, payload) = match read_entry(&self.entry_path(seq)) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    error!("Dropping unreadable outbox message {seq}: {e}");
+                    self.remove_entry(&mut state, seq);
+                    self.stats.increment_outbox_dropped();
+                    continue;
+                }
+            };
+
+            let mut properties = header.properties;
+            if let Some(ref mut properties) = properties
+                && let Some(message_expiry) = properties.message_expiry
+            {
+                let waited = (now - entry.created_at).to_std().unwrap_or_default();
+                properties.message_expiry = Some(message_expiry.saturating_sub(waited));
+            }
+
+            let message = PendingMessage {
+                seq,
+                topic: entry.topic.clone(),
+                qos: entry.qos,
+                retain: entry.retain,
+                dedup_id: entry.dedup_id.clone(),
+                properties,
+                payload,
+            };
+            state.cursor = seq + 1;
+            state.awaiting_outgoing.push_back(seq);
+            return Some(message);
+        }
+    }
+
+    // release puts a message returned by next_pending back at the front
+    // of the outbox, e.g. because the MQTT client refused it.
+    pub fn release(&self, seq: u64) {
+        let mut state = self.lock_state();
+        state.awaiting_outgoing.retain(|pending| *pending != seq);
+        state.cursor = state.cursor.min(seq);
+    }
+
+    // set_connected records whether the client currently has a broker
+    // connection. Nothing is drained while disconnected, so the backlog
+    // stays on disk instead of piling up in memory.
+    pub fn set_connected(&self, connected: bool) {
+        self.lock_state().connected = connected;
+        if connected {
+            self.notify.notify_one();
+        }
+    }
+
+    // on_outgoing_publish is called when the MQTT client writes a publish
+    // to the connection. Publishes go out in the order they were handed
+    // to the client, so the oldest message awaiting a packet ID gets this
+    // one. Retransmissions reuse a packet ID we already know and are
+    // ignored. QoS 0 messages (packet ID 0) are never acknowledged, so
+    // they are done as soon as they are written.
+    pub fn on_outgoing_publish(&self, pkid: u16) {
+        let mut state = self.lock_state();
+        if pkid != 0 && state.awaiting_ack.contains_key(&pkid) {
+            return;
+        }
+        let Some(seq) = state.awaiting_outgoing.pop_front() else {
+            return;
+        };
+        if pkid == 0 {
+            self.remove_entry(&mut state, seq);
+            drop(state);
+            self.notify.notify_one();
+        } else {
+            state.awaiting_ack.insert(pkid, seq);
+        }
+    }
+
+    // on_ack is called when the broker acknowledges a publish (PUBACK for
+    // QoS 1, PUBCOMP for QoS 2). The message is removed from disk.
+    pub fn on_ack(&self, pkid: u16) {
+        let mut state = self.lock_state();
+        if let Some(seq) = state.awaiting_ack.remove(&pkid) {
+            self.remove_entry(&mut state, seq);
+            drop(state);
+            self.notify.notify_one();
+        }
+    }
+
+    // handle_event feeds an MQTT event loop event into the outbox.
+    pub(crate) fn handle_event(&self, event: &TransportEvent) {
+        match event {
+            TransportEvent::ConnAck => self.set_connected(true),
+            TransportEvent::PubAck(pkid) | TransportEvent::PubComp(pkid) => self.on_ack(*pkid),
+            TransportEvent::OutgoingPublish(pkid) => self.on_outgoing_publish(*pkid),
+            TransportEvent::Publish(_) | TransportEvent::Other => {}
+        }
+    }
+
+    // drain hands outbox messages to the MQTT client, in order, for as
+    // long as the client exists. Spawned by MqtteaClient::connect.
+    pub(crate) async fn drain(self: Arc<Self>, client: Arc<Transport>) {
+        loop {
+            let Some(message) = self.next_pending() else {
+                self.notify.notified().await;
+                continue;
+            };
+
+            let payload_size = message.payload.len();
+            match client
+                .publish(
+                    message.topic,
+                    message.qos,
+                    message.retain,
+                    message.payload,
+                    message.properties.as_ref(),
+                )
+                .await
+            {
+                Ok(_) => self.stats.increment_published(payload_size),
+                Err(e) => {
+                    // The only way this fails is if the event loop is gone,
+                    // so stop draining; the message stays on disk.
+                    error!("Failed to hand outbox message to MQTT client: {e}");
+                    self.release(message.seq);
+                    self.stats.increment_failed();
+                    return;
+                }
+            }
+        }
+    }
+
+    // len returns the number of messages currently in the outbox,
+    // including ones in flight.
+    pub fn len(&self) -> usize {
+        self.lock_state().entries.len()
+    }
+
+    // is_empty checks if every message has been delivered.
+    pub fn is_empty(&self) -> bool {
+        self.lock_state().entries.is_empty()
+    }
+
+    // bytes returns the total payload size currently in the outbox.
+    pub fn bytes(&self) -> usize {
+        self.lock_state().bytes
+    }
+
+    fn lock_state(&self) -> MutexGuard<'_, OutboxState> {
+        // A poisoned lock only means another thread panicked mid-update;
+        // the index is still usable, and the files on disk are the
+        // source of truth on the next restart anyway.
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn policy_for(&self, topic: &str) -> Option<usize> {
+        self.retention
+            .iter()
+            .position(|policy| policy.matches(topic))
+    }
+
+    // is_expired checks the message against its retention policy's
+    // max_age, as well as its own MQTT v5 message expiry.
+    fn is_expired(&self, entry: &OutboxEntry, now: DateTime<Utc>) -> bool {
+        let max_age = entry
+            .policy
+            .and_then(|index| self.retention[index].max_age)
+            .into_iter()
+            .chain(entry.message_expiry)
+            .min();
+        let Some(max_age) = max_age else {
+            return false;
+        };
+        (now - entry.created_at)
+            .to_std()
+            .is_ok_and(|age| age > max_age)
+    }
+
+    // count_waiting_for_policy counts messages for a retention policy
+    // that haven't been handed to the MQTT client yet.
+    fn count_waiting_for_policy(&self, state: &OutboxState, policy: Option<usize>) -> usize {
+        state
+            .entries
+            .range(state.cursor..)
+            .filter(|(_, entry)| entry.policy == policy)
+            .count()
+    }
+
+    // evict_oldest drops the oldest message that hasn't been handed to
+    // the MQTT client yet, optionally limited to one retention policy.
+    // Returns false if there was nothing to evict.
+    fn evict_oldest(&self, state: &mut OutboxState, policy: Option<usize>) -> bool {
+        let victim = state
+            .entries
+            .range(state.cursor..)
+            .find(|(_, entry)| policy.is_none() || entry.policy == policy)
+            .map(|(seq, _)| *seq);
+        match victim {
+            Some(seq) => {
+                warn!("Outbox full, dropping message {seq}");
+                self.remove_entry(state, seq);
+                self.stats.increment_outbox_dropped();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn remove_entry(&self, state: &mut OutboxState, seq: u64) {
+        let Some(entry) = state.entries.remove(&seq) else {
+            return;
+        };
+        if let Some(dedup_id) = &entry.dedup_id {
+            state.dedup_ids.remove(dedup_id);
+        }
+        state.bytes -= entry.payload_size;
+        self.stats.decrement_outbox_pending(entry.payload_size);
+
+        let path = self.entry_path(seq);
+        if let Err(e) = fs::remove_file(&path) {
+            warn!("Failed to remove outbox entry {}: {e}", path.display());
+        }
+    }
+
+    fn entry_path(&self, seq: u64) -> PathBuf {
+        self.directory.join(format!("{seq:020}.{ENTRY_EXTENSION}"))
+    }
+
+    // write_entry writes to a temp file and renames it into place, so
+    // a crash never leaves a partially written entry behind.
+    fn write_entry(&self, header: &EntryHeader, payload: &[u8]) -> Result<(), MqtteaClientError> {
+        let path = self.entry_path(header.seq);
+        let temp_path = path.with_extension(TEMP_EXTENSION);
+        let write = || -> std::io::Result<()> {
+            let mut file = fs::File::create(&temp_path)?;
+            serde_json::to_writer(&mut file, header)?;
+            file.write_all(b"\n")?;
+            file.write_all(payload)?;
+            file.sync_all()?;
+            fs::rename(&temp_path, &path)
+        };
+        write().map_err(|e| {
+            let _ = fs::remove_file(&temp_path);
+            MqtteaClientError::outbox_error(format!(
+                "failed to write outbox entry {}: {e}",
+                path.display()
+            ))
+        })
+    }
+}
+
+fn read_entry(path: &Path) -> std::io::Result<(EntryHeader, Vec<u8>)> {
+    let contents = fs::read(path)?;
+    let newline = contents
+        .iter()
+        .position(|byte| *byte == b'\n')
+        .ok_or_else(|| std::io::Error::other("missing entry header"))?;
+    let header: EntryHeader = serde_json::from_slice(&contents[..newline])?;
+    Ok((header, contents[newline + 1..].to_vec()))
+}
+
+fn qos_from_u8(qos: u8) -> QoS {
+    match qos {
+        0 => QoS::AtMostOnce,
+        2 => QoS::ExactlyOnce,
+        _ => QoS::AtLeastOnce,
+    }
+}
@@ -21,6 +21,8 @@
 // Contains shared registry bits like serialization formats and
 // message metadata that are used throughout this code.
 
+use std::time::Duration;
+
 use rumqttc::QoS;
 
 use crate::errors::MqtteaClientError;
@@ -36,6 +38,53 @@ pub enum SerializationFormat {
     Raw,
 }
 
+impl SerializationFormat {
+    // name returns the short name used in the mqttea-format user
+    // property on MQTT v5 messages.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Protobuf => "protobuf",
+            Self::Json => "json",
+            Self::Yaml => "yaml",
+            Self::Raw => "raw",
+        }
+    }
+
+    // from_name parses a name returned by name().
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "protobuf" => Some(Self::Protobuf),
+            "json" => Some(Self::Json),
+            "yaml" => Some(Self::Yaml),
+            "raw" => Some(Self::Raw),
+            _ => None,
+        }
+    }
+
+    // content_type returns the MIME type used as the MQTT v5
+    // content-type property for this format.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Protobuf => "application/x-protobuf",
+            Self::Json => "application/json",
+            Self::Yaml => "application/yaml",
+            Self::Raw => "application/octet-stream",
+        }
+    }
+
+    // from_content_type maps a content type back to a format,
+    // ignoring any parameters (e.g. "application/json; charset=utf-8").
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        match content_type.split(';').next().unwrap_or_default().trim() {
+            "application/x-protobuf" | "application/protobuf" => Some(Self::Protobuf),
+            "application/json" => Some(Self::Json),
+            "application/yaml" | "application/x-yaml" => Some(Self::Yaml),
+            "application/octet-stream" => Some(Self::Raw),
+            _ => None,
+        }
+    }
+}
+
 // MessageTypeInfo stores metadata about a registered message type.
 // Contains all information needed to route and configure message
 // handling for a specific type.
@@ -65,6 +114,13 @@ pub struct PublishOptions {
     pub qos: Option<QoS>,
     // retain is the MQTT retain override for this message type.
     pub retain: Option<bool>,
+    // message_expiry is how long the broker should hold on to the
+    // message before discarding it (MQTT v5 only).
+    pub message_expiry: Option<Duration>,
+    // schema_version is sent in the mqttea-schema-version user
+    // property, so receivers can tell which revision of a message
+    // type they were sent (MQTT v5 only).
+    pub schema_version: Option<&'static str>,
 }
 impl PublishOptions {
     pub fn with_qos(mut self, qos: QoS) -> Self {
@@ -76,6 +132,16 @@ impl PublishOptions {
         self.retain = Some(retain);
         self
     }
+
+    pub fn with_message_expiry(mut self, message_expiry: Duration) -> Self {
+        self.message_expiry = Some(message_expiry);
+        self
+    }
+
+    pub fn with_schema_version(mut self, schema_version: &'static str) -> Self {
+        self.schema_version = Some(schema_version);
+        self
+    }
 }
 
 // SerializeHandler converts any message to bytes for
@@ -36,6 +36,26 @@ pub struct PublishStats {
     // total_bytes_published is total size of messages
     // successfully sent (throughput metric).
     pub total_bytes_published: usize,
+    // outbox_pending_messages is count of messages currently
+    // persisted in the outbox waiting for delivery (current
+    // backlog). Always 0 when the outbox is disabled.
+    pub outbox_pending_messages: usize,
+    // outbox_pending_bytes is total size of messages currently
+    // persisted in the outbox.
+    pub outbox_pending_bytes: usize,
+    // total_outbox_persisted is count of messages written to
+    // the outbox since startup/reset.
+    pub total_outbox_persisted: usize,
+    // total_outbox_dropped is count of messages dropped from (or
+    // refused by) the outbox because it was full.
+    pub total_outbox_dropped: usize,
+    // total_outbox_expired is count of messages removed from the
+    // outbox after exceeding their retention policy's max_age.
+    pub total_outbox_expired: usize,
+    // total_outbox_deduplicated is count of messages skipped
+    // because a message with the same dedup ID was already
+    // waiting in the outbox.
+    pub total_outbox_deduplicated: usize,
 }
 
 // PublishStatsTracker enables thread-safe updates to publish
@@ -52,6 +72,24 @@ pub struct PublishStatsTracker {
     // published_bytes tracks total size of messages
     // successfully published.
     published_bytes: Arc<AtomicUsize>,
+    // outbox_pending_count tracks current number of messages
+    // persisted in the outbox.
+    outbox_pending_count: Arc<AtomicUsize>,
+    // outbox_pending_bytes tracks current total size of messages
+    // persisted in the outbox.
+    outbox_pending_bytes: Arc<AtomicUsize>,
+    // outbox_persisted_count tracks total number of messages
+    // written to the outbox.
+    outbox_persisted_count: Arc<AtomicUsize>,
+    // outbox_dropped_count tracks total number of messages
+    // dropped because the outbox was full.
+    outbox_dropped_count: Arc<AtomicUsize>,
+    // outbox_expired_count tracks total number of messages
+    // expired by a retention policy.
+    outbox_expired_count: Arc<AtomicUsize>,
+    // outbox_deduplicated_count tracks total number of messages
+    // skipped as duplicates.
+    outbox_deduplicated_count: Arc<AtomicUsize>,
 }
 
 impl Default for PublishStatsTracker {
@@ -69,6 +107,12 @@ impl PublishStatsTracker {
             published_count: Arc::new(AtomicUsize::new(0)),
             failed_count: Arc::new(AtomicUsize::new(0)),
             published_bytes: Arc::new(AtomicUsize::new(0)),
+            outbox_pending_count: Arc::new(AtomicUsize::new(0)),
+            outbox_pending_bytes: Arc::new(AtomicUsize::new(0)),
+            outbox_persisted_count: Arc::new(AtomicUsize::new(0)),
+            outbox_dropped_count: Arc::new(AtomicUsize::new(0)),
+            outbox_expired_count: Arc::new(AtomicUsize::new(0)),
+            outbox_deduplicated_count: Arc::new(AtomicUsize::new(0)),
         }
     }
 
@@ -90,13 +134,58 @@ impl PublishStatsTracker {
         self.failed_count.fetch_add(1, Ordering::Relaxed);
     }
 
+    // increment_outbox_pending will record a message entering the outbox
+    // backlog, either newly persisted or loaded from disk at startup.
+    pub fn increment_outbox_pending(&self, bytes: usize) {
+        self.outbox_pending_count.fetch_add(1, Ordering::Relaxed);
+        self.outbox_pending_bytes
+            .fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    // decrement_outbox_pending will record a message leaving the outbox
+    // backlog, whether delivered, dropped, or expired.
+    pub fn decrement_outbox_pending(&self, bytes: usize) {
+        self.outbox_pending_count.fetch_sub(1, Ordering::Relaxed);
+        self.outbox_pending_bytes
+            .fetch_sub(bytes, Ordering::Relaxed);
+    }
+
+    // increment_outbox_persisted will record a message written to the outbox.
+    pub fn increment_outbox_persisted(&self) {
+        self.outbox_persisted_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    // increment_outbox_dropped will record a message lost because the
+    // outbox was full (e.g. broker down longer than max_messages allows).
+    pub fn increment_outbox_dropped(&self) {
+        self.outbox_dropped_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    // increment_outbox_expired will record a message that outlived its
+    // retention policy before it could be delivered.
+    pub fn increment_outbox_expired(&self) {
+        self.outbox_expired_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    // increment_outbox_deduplicated will record a message skipped because
+    // its dedup ID was already waiting in the outbox.
+    pub fn increment_outbox_deduplicated(&self) {
+        self.outbox_deduplicated_count
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
     // reset_counters will clear all publish counters back to zero.
     // Useful for periodic reporting, testing, or monitoring system resets.
     // (e.g. reset hourly stats for sliding window metrics)
+    // Note: We don't reset outbox pending counts as they reflect current state.
     pub fn reset_counters(&self) {
         self.published_count.store(0, Ordering::Relaxed);
         self.failed_count.store(0, Ordering::Relaxed);
         self.published_bytes.store(0, Ordering::Relaxed);
+        self.outbox_persisted_count.store(0, Ordering::Relaxed);
+        self.outbox_dropped_count.store(0, Ordering::Relaxed);
+        self.outbox_expired_count.store(0, Ordering::Relaxed);
+        self.outbox_deduplicated_count.store(0, Ordering::Relaxed);
     }
 
     // to_stats will create an immutable snapshot of current publish
@@ -109,6 +198,12 @@ impl PublishStatsTracker {
             total_published: self.published_count.load(Ordering::Relaxed),
             total_failed: self.failed_count.load(Ordering::Relaxed),
             total_bytes_published: self.published_bytes.load(Ordering::Relaxed),
+            outbox_pending_messages: self.outbox_pending_count.load(Ordering::Relaxed),
+            outbox_pending_bytes: self.outbox_pending_bytes.load(Ordering::Relaxed),
+            total_outbox_persisted: self.outbox_persisted_count.load(Ordering::Relaxed),
+            total_outbox_dropped: self.outbox_dropped_count.load(Ordering::Relaxed),
+            total_outbox_expired: self.outbox_expired_count.load(Ordering::Relaxed),
+            total_outbox_deduplicated: self.outbox_deduplicated_count.load(Ordering::Relaxed),
         }
     }
 }
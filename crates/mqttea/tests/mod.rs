@@ -22,6 +22,8 @@
 mod auth;
 mod client;
 mod errors;
+mod outbox;
+mod properties;
 mod registry;
 mod stats;
 mod traits;
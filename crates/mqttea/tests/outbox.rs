@@ -0,0 +1,339 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// tests/outbox.rs
+// Unit tests for the durable outbox, covering persistence, ordered
+// replay, retention policies, dedup IDs, and delivery tracking.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use mqttea::client::ClientOptions;
+use mqttea::outbox::{Outbox, OutboxOptions, RetentionPolicy};
+use mqttea::stats::PublishStatsTracker;
+use mqttea::{MqtteaClient, QoS};
+
+fn open_outbox(options: &OutboxOptions) -> (Arc<Outbox>, Arc<PublishStatsTracker>) {
+    let stats = Arc::new(PublishStatsTracker::new());
+    let outbox = Outbox::open(options, stats.clone()).unwrap();
+    (outbox, stats)
+}
+
+fn enqueue(outbox: &Outbox, topic: &str, payload: &str) {
+    outbox
+        .enqueue(topic, QoS::AtLeastOnce, false, payload.into(), None)
+        .unwrap();
+}
+
+// drain_payloads hands out everything currently pending and acks it,
+// returning the payloads in the order they were handed out.
+fn drain_payloads(outbox: &Outbox) -> Vec<String> {
+    let mut payloads = Vec::new();
+    let mut pkid = 1;
+    while let Some(message) = outbox.next_pending() {
+        outbox.on_outgoing_publish(pkid);
+        outbox.on_ack(pkid);
+        pkid += 1;
+        payloads.push(String::from_utf8(message.payload).unwrap());
+    }
+    payloads
+}
+
+#[test]
+fn test_retention_policy_matches() {
+    let exact = RetentionPolicy::new("cats/whiskers/status");
+    assert!(exact.matches("cats/whiskers/status"));
+    assert!(!exact.matches("cats/mittens/status"));
+    assert!(!exact.matches("cats/whiskers"));
+
+    let single = RetentionPolicy::new("cats/+/status");
+    assert!(single.matches("cats/whiskers/status"));
+    assert!(single.matches("cats/mittens/status"));
+    assert!(!single.matches("cats/whiskers/status/extra"));
+
+    let multi = RetentionPolicy::new("cats/#");
+    assert!(multi.matches("cats"));
+    assert!(multi.matches("cats/whiskers/status"));
+    assert!(!multi.matches("dogs/rex"));
+}
+
+#[test]
+fn test_outbox_persists_and_replays_in_order() {
+    let dir = tempfile::tempdir().unwrap();
+    let options = OutboxOptions::new(dir.path());
+
+    {
+        let (outbox, stats) = open_outbox(&options);
+        enqueue(&outbox, "cats/whiskers", "meow-1");
+        enqueue(&outbox, "cats/mittens", "meow-2");
+        enqueue(&outbox, "cats/whiskers", "meow-3");
+        assert_eq!(outbox.len(), 3);
+        assert_eq!(outbox.bytes(), 18);
+
+        let publish_stats = stats.to_stats();
+        assert_eq!(publish_stats.outbox_pending_messages, 3);
+        assert_eq!(publish_stats.outbox_pending_bytes, 18);
+        assert_eq!(publish_stats.total_outbox_persisted, 3);
+    }
+
+    // Reopening the outbox (e.g. after a restart) picks everything
+    // back up, and replays it in the original order.
+    let (outbox, stats) = open_outbox(&options);
+    assert_eq!(outbox.len(), 3);
+    assert_eq!(stats.to_stats().outbox_pending_messages, 3);
+
+    outbox.set_connected(true);
+    assert_eq!(drain_payloads(&outbox), vec!["meow-1", "meow-2", "meow-3"]);
+    assert!(outbox.is_empty());
+    assert_eq!(stats.to_stats().outbox_pending_messages, 0);
+    assert_eq!(stats.to_stats().outbox_pending_bytes, 0);
+}
+
+#[test]
+fn test_outbox_holds_messages_while_disconnected() {
+    let dir = tempfile::tempdir().unwrap();
+    let (outbox, _) = open_outbox(&OutboxOptions::new(dir.path()));
+
+    enqueue(&outbox, "dogs/rex", "woof");
+    assert!(outbox.next_pending().is_none());
+
+    outbox.set_connected(true);
+    assert!(outbox.next_pending().is_some());
+    outbox.set_connected(false);
+    enqueue(&outbox, "dogs/rex", "woof-again");
+    assert!(outbox.next_pending().is_none());
+}
+
+#[test]
+fn test_outbox_removes_messages_only_after_ack() {
+    let dir = tempfile::tempdir().unwrap();
+    let (outbox, _) = open_outbox(&OutboxOptions::new(dir.path()));
+    outbox.set_connected(true);
+
+    enqueue(&outbox, "birds/tweety", "chirp");
+    let message = outbox.next_pending().unwrap();
+    assert_eq!(message.topic, "birds/tweety");
+
+    outbox.on_outgoing_publish(7);
+    assert_eq!(outbox.len(), 1);
+
+    // A retransmission with the same packet ID doesn't change anything.
+    outbox.on_outgoing_publish(7);
+    // An ack for an unknown packet ID is ignored.
+    outbox.on_ack(8);
+    assert_eq!(outbox.len(), 1);
+
+    outbox.on_ack(7);
+    assert!(outbox.is_empty());
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+}
+
+#[test]
+fn test_outbox_qos0_removed_once_written() {
+    let dir = tempfile::tempdir().unwrap();
+    let (outbox, _) = open_outbox(&OutboxOptions::new(dir.path()));
+    outbox.set_connected(true);
+
+    outbox
+        .enqueue("fish/nemo", QoS::AtMostOnce, false, b"blub".to_vec(), None)
+        .unwrap();
+    let message = outbox.next_pending().unwrap();
+    assert_eq!(message.qos, QoS::AtMostOnce);
+
+    outbox.on_outgoing_publish(0);
+    assert!(outbox.is_empty());
+}
+
+#[test]
+fn test_outbox_release_requeues_message() {
+    let dir = tempfile::tempdir().unwrap();
+    let (outbox, _) = open_outbox(&OutboxOptions::new(dir.path()));
+    outbox.set_connected(true);
+
+    enqueue(&outbox, "cats/whiskers", "meow-1");
+    enqueue(&outbox, "cats/whiskers", "meow-2");
+
+    let first = outbox.next_pending().unwrap();
+    outbox.release(first.seq);
+    assert_eq!(drain_payloads(&outbox), vec!["meow-1", "meow-2"]);
+}
+
+#[test]
+fn test_outbox_max_in_flight() {
+    let dir = tempfile::tempdir().unwrap();
+    let (outbox, _) = open_outbox(&OutboxOptions::new(dir.path()).with_max_in_flight(2));
+    outbox.set_connected(true);
+
+    for i in 0..3 {
+        enqueue(&outbox, "cats/whiskers", &format!("meow-{i}"));
+    }
+    assert!(outbox.next_pending().is_some());
+    assert!(outbox.next_pending().is_some());
+    assert!(outbox.next_pending().is_none());
+
+    outbox.on_outgoing_publish(1);
+    outbox.on_ack(1);
+    assert!(outbox.next_pending().is_some());
+}
+
+#[test]
+fn test_outbox_dedup_ids() {
+    let dir = tempfile::tempdir().unwrap();
+    let (outbox, stats) = open_outbox(&OutboxOptions::new(dir.path()));
+    outbox.set_connected(true);
+
+    for _ in 0..3 {
+        outbox
+            .enqueue(
+                "cats/whiskers",
+                QoS::AtLeastOnce,
+                false,
+                b"fed".to_vec(),
+                Some("whiskers-breakfast".to_string()),
+            )
+            .unwrap();
+    }
+    assert_eq!(outbox.len(), 1);
+    assert_eq!(stats.to_stats().total_outbox_deduplicated, 2);
+
+    // Once delivered, the same dedup ID can be used again.
+    assert_eq!(drain_payloads(&outbox), vec!["fed"]);
+    outbox
+        .enqueue(
+            "cats/whiskers",
+            QoS::AtLeastOnce,
+            false,
+            b"fed".to_vec(),
+            Some("whiskers-breakfast".to_string()),
+        )
+        .unwrap();
+    assert_eq!(outbox.len(), 1);
+}
+
+#[test]
+fn test_outbox_max_messages_drops_oldest() {
+    let dir = tempfile::tempdir().unwrap();
+    let (outbox, stats) = open_outbox(&OutboxOptions::new(dir.path()).with_max_messages(2));
+
+    enqueue(&outbox, "cats/whiskers", "meow-1");
+    enqueue(&outbox, "cats/whiskers", "meow-2");
+    enqueue(&outbox, "cats/whiskers", "meow-3");
+    assert_eq!(outbox.len(), 2);
+    assert_eq!(stats.to_stats().total_outbox_dropped, 1);
+
+    outbox.set_connected(true);
+    assert_eq!(drain_payloads(&outbox), vec!["meow-2", "meow-3"]);
+}
+
+#[test]
+fn test_outbox_max_bytes_never_drops_in_flight() {
+    let dir = tempfile::tempdir().unwrap();
+    let (outbox, stats) = open_outbox(&OutboxOptions::new(dir.path()).with_max_bytes(10));
+    outbox.set_connected(true);
+
+    enqueue(&outbox, "cats/whiskers", "meow-1");
+    outbox.next_pending().unwrap();
+
+    // The only message is in flight, so there's nothing to evict.
+    let result = outbox.enqueue(
+        "cats/whiskers",
+        QoS::AtLeastOnce,
+        false,
+        b"meow-2".to_vec(),
+        None,
+    );
+    assert!(result.is_err());
+    assert_eq!(stats.to_stats().total_outbox_dropped, 1);
+    assert_eq!(outbox.len(), 1);
+}
+
+#[test]
+fn test_outbox_retention_max_messages_per_topic() {
+    let dir = tempfile::tempdir().unwrap();
+    let options = OutboxOptions::new(dir.path())
+        .with_retention(RetentionPolicy::new("cats/+/status").with_max_messages(1));
+    let (outbox, _) = open_outbox(&options);
+
+    enqueue(&outbox, "cats/whiskers/status", "sleeping");
+    enqueue(&outbox, "cats/whiskers/events", "ate");
+    enqueue(&outbox, "cats/whiskers/status", "awake");
+    enqueue(&outbox, "cats/whiskers/events", "played");
+
+    outbox.set_connected(true);
+    assert_eq!(drain_payloads(&outbox), vec!["ate", "awake", "played"]);
+}
+
+#[test]
+fn test_outbox_retention_max_age() {
+    let dir = tempfile::tempdir().unwrap();
+    let options = OutboxOptions::new(dir.path())
+        .with_retention(RetentionPolicy::new("dogs/#").with_max_age(Duration::from_millis(1)));
+    let (outbox, stats) = open_outbox(&options);
+
+    enqueue(&outbox, "dogs/rex", "woof");
+    enqueue(&outbox, "cats/whiskers", "meow");
+    std::thread::sleep(Duration::from_millis(20));
+
+    outbox.set_connected(true);
+    assert_eq!(drain_payloads(&outbox), vec!["meow"]);
+    assert_eq!(stats.to_stats().total_outbox_expired, 1);
+}
+
+#[test]
+fn test_outbox_ignores_unreadable_entries() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("00000000000000000000.msg"), b"garbage").unwrap();
+    std::fs::write(dir.path().join("00000000000000000001.tmp"), b"partial").unwrap();
+
+    let (outbox, _) = open_outbox(&OutboxOptions::new(dir.path()));
+    assert!(outbox.is_empty());
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+}
+
+#[tokio::test]
+async fn test_client_publish_persists_to_outbox() {
+    let dir = tempfile::tempdir().unwrap();
+    let client = MqtteaClient::new(
+        "localhost",
+        1883,
+        "test-outbox-client",
+        Some(ClientOptions::default().with_outbox(OutboxOptions::new(dir.path()))),
+    )
+    .await
+    .unwrap();
+
+    // Not connected, so the message sits in the outbox.
+    client
+        .publish("cats/whiskers", b"meow".to_vec())
+        .await
+        .unwrap();
+    client
+        .publish_with_dedup_id("cats/whiskers", None, b"meow".to_vec(), "meow-1")
+        .await
+        .unwrap();
+    client
+        .publish_with_dedup_id("cats/whiskers", None, b"meow".to_vec(), "meow-1")
+        .await
+        .unwrap();
+
+    let stats = client.publish_stats();
+    assert_eq!(stats.outbox_pending_messages, 2);
+    assert_eq!(stats.outbox_pending_bytes, 8);
+    assert_eq!(stats.total_outbox_persisted, 2);
+    assert_eq!(stats.total_outbox_deduplicated, 1);
+    assert_eq!(stats.total_published, 0);
+}
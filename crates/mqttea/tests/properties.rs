@@ -0,0 +1,243 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// tests/properties.rs
+// Unit tests for MQTT v5 message properties, serialization format
+// metadata, and protocol version handling.
+
+use std::time::Duration;
+
+use mqttea::client::{
+    ClientOptions, MessageProperties, ProtocolVersion, USER_PROPERTY_FORMAT,
+    USER_PROPERTY_SCHEMA_VERSION,
+};
+use mqttea::outbox::{Outbox, OutboxOptions};
+use mqttea::registry::traits::JsonRegistration;
+use mqttea::registry::types::PublishOptions;
+use mqttea::stats::PublishStatsTracker;
+use mqttea::{MqtteaClient, MqtteaClientError, QoS, SerializationFormat};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct CatFeedingRequest {
+    cat: String,
+    grams: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct CatFeedingResponse {
+    fed: bool,
+}
+
+#[test]
+fn test_serialization_format_names_round_trip() {
+    for format in [
+        SerializationFormat::Protobuf,
+        SerializationFormat::Json,
+        SerializationFormat::Yaml,
+        SerializationFormat::Raw,
+    ] {
+        assert_eq!(SerializationFormat::from_name(format.name()), Some(format));
+        assert_eq!(
+            SerializationFormat::from_content_type(format.content_type()),
+            Some(format)
+        );
+    }
+    assert_eq!(SerializationFormat::from_name("xml"), None);
+}
+
+#[test]
+fn test_serialization_format_content_type_parameters() {
+    assert_eq!(
+        SerializationFormat::from_content_type("application/json; charset=utf-8"),
+        Some(SerializationFormat::Json)
+    );
+    assert_eq!(SerializationFormat::from_content_type("text/plain"), None);
+}
+
+#[test]
+fn test_properties_for_format() {
+    let publish_options = PublishOptions::default()
+        .with_schema_version("2")
+        .with_message_expiry(Duration::from_secs(60));
+    let properties =
+        MessageProperties::for_format(SerializationFormat::Json, Some(publish_options));
+
+    assert_eq!(properties.content_type.as_deref(), Some("application/json"));
+    assert_eq!(properties.user_property(USER_PROPERTY_FORMAT), Some("json"));
+    assert_eq!(
+        properties.user_property(USER_PROPERTY_SCHEMA_VERSION),
+        Some("2")
+    );
+    assert_eq!(properties.schema_version(), Some("2"));
+    assert_eq!(properties.format(), Some(SerializationFormat::Json));
+    assert_eq!(properties.message_expiry, Some(Duration::from_secs(60)));
+    assert!(properties.response_topic.is_none());
+}
+
+#[test]
+fn test_properties_format_falls_back_to_content_type() {
+    let properties = MessageProperties {
+        content_type: Some("application/x-protobuf".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(properties.format(), Some(SerializationFormat::Protobuf));
+    assert!(properties.schema_version().is_none());
+
+    // The mqttea-format user property wins over the content type.
+    let properties = MessageProperties {
+        content_type: Some("application/x-protobuf".to_string()),
+        user_properties: vec![(USER_PROPERTY_FORMAT.to_string(), "yaml".to_string())],
+        ..Default::default()
+    };
+    assert_eq!(properties.format(), Some(SerializationFormat::Yaml));
+}
+
+#[test]
+fn test_properties_empty() {
+    assert!(MessageProperties::default().is_empty());
+    assert!(MessageProperties::default().format().is_none());
+    assert!(!MessageProperties::for_format(SerializationFormat::Raw, None).is_empty());
+}
+
+#[tokio::test]
+async fn test_client_creation_v5() {
+    let client = MqtteaClient::new(
+        "localhost",
+        1883,
+        "test-v5-client",
+        Some(ClientOptions::default().with_protocol_version(ProtocolVersion::V5)),
+    )
+    .await;
+    assert!(client.is_ok());
+}
+
+#[tokio::test]
+async fn test_request_requires_v5() {
+    let client = MqtteaClient::new("localhost", 1883, "test-v311-client", None)
+        .await
+        .unwrap();
+    client
+        .register_json_message::<CatFeedingRequest>("feed-cat")
+        .await
+        .unwrap();
+    client
+        .register_json_message::<CatFeedingResponse>("cat-fed")
+        .await
+        .unwrap();
+
+    let result = client
+        .request::<CatFeedingRequest, CatFeedingResponse>(
+            "cats/whiskers/feed-cat",
+            &CatFeedingRequest {
+                cat: "whiskers".to_string(),
+                grams: 50,
+            },
+            Duration::from_millis(10),
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(MqtteaClientError::UnsupportedProtocolFeature(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_request_times_out_without_responder() {
+    let client = MqtteaClient::new(
+        "localhost",
+        1883,
+        "test-v5-request-client",
+        Some(ClientOptions::default().with_protocol_version(ProtocolVersion::V5)),
+    )
+    .await
+    .unwrap();
+    client
+        .register_json_message::<CatFeedingRequest>("feed-cat")
+        .await
+        .unwrap();
+
+    // Never connected, so nobody will ever answer.
+    let result = client
+        .request::<CatFeedingRequest, CatFeedingResponse>(
+            "cats/whiskers/feed-cat",
+            &CatFeedingRequest {
+                cat: "whiskers".to_string(),
+                grams: 50,
+            },
+            Duration::from_millis(10),
+        )
+        .await;
+    let err = result.unwrap_err();
+    assert!(matches!(err, MqtteaClientError::RequestTimeout(_)));
+    assert!(err.is_connection_error());
+}
+
+#[test]
+fn test_outbox_persists_properties() {
+    let dir = tempfile::tempdir().unwrap();
+    let options = OutboxOptions::new(dir.path());
+    let properties = MessageProperties::for_format(
+        SerializationFormat::Json,
+        Some(PublishOptions::default().with_schema_version("3")),
+    );
+
+    {
+        let outbox = Outbox::open(&options, PublishStatsTracker::new().into()).unwrap();
+        outbox
+            .enqueue_with_properties(
+                "cats/whiskers",
+                QoS::AtLeastOnce,
+                false,
+                b"{}".to_vec(),
+                None,
+                Some(properties.clone()),
+            )
+            .unwrap();
+    }
+
+    let outbox = Outbox::open(&options, PublishStatsTracker::new().into()).unwrap();
+    outbox.set_connected(true);
+    let message = outbox.next_pending().unwrap();
+    assert_eq!(message.properties, Some(properties));
+}
+
+#[test]
+fn test_outbox_expires_by_message_expiry() {
+    let dir = tempfile::tempdir().unwrap();
+    let stats = std::sync::Arc::new(PublishStatsTracker::new());
+    let outbox = Outbox::open(&OutboxOptions::new(dir.path()), stats.clone()).unwrap();
+
+    outbox
+        .enqueue_with_properties(
+            "cats/whiskers",
+            QoS::AtLeastOnce,
+            false,
+            b"meow".to_vec(),
+            None,
+            Some(MessageProperties {
+                message_expiry: Some(Duration::from_millis(1)),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+    std::thread::sleep(Duration::from_millis(20));
+
+    outbox.set_connected(true);
+    assert!(outbox.next_pending().is_none());
+    assert_eq!(stats.to_stats().total_outbox_expired, 1);
+}
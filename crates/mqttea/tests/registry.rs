@@ -229,6 +229,7 @@ fn test_custom_raw_registration() {
         Some(PublishOptions {
             qos: Some(QoS::AtMostOnce),
             retain: None,
+            ..Default::default()
         }),
     );
 
@@ -509,6 +510,7 @@ fn test_list_registered_types() {
             Some(PublishOptions {
                 qos: Some(QoS::AtMostOnce),
                 retain: None,
+                ..Default::default()
             }),
         )
         .unwrap();
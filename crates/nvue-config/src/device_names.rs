@@ -0,0 +1,71 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use version_compare::{Part, Version};
+
+/// Names of the devices HBN exposes, which changed with HBN 2.3
+#[derive(Clone)]
+pub struct HBNDeviceNames {
+    pub uplinks: [&'static str; 2],
+    pub reps: [&'static str; 2],
+    pub virt_rep_begin: &'static str,
+    pub sfs: [&'static str; 2],
+    pub sf_id: &'static str,
+}
+
+impl HBNDeviceNames {
+    pub fn pre_23() -> HBNDeviceNames {
+        HBNDeviceNames {
+            uplinks: ["p0_sf", "p1_sf"],
+            reps: ["pf0hpf_sf", "pf1hpf_sf"],
+            virt_rep_begin: "pf0vf",
+            sfs: ["pf0dpu0_sf", "pf0dpu2_sf"],
+            sf_id: "_sf",
+        }
+    }
+
+    pub fn hbn_23() -> HBNDeviceNames {
+        HBNDeviceNames {
+            uplinks: ["p0_if", "p1_if"],
+            reps: ["pf0hpf_if", "pf1hpf_if"],
+            virt_rep_begin: "pf0vf",
+            sfs: ["pf0dpu1", "pf0dpu3"],
+            sf_id: "_if",
+        }
+    }
+    pub fn new(hbn_version: Version) -> Self {
+        let min_version: Version = Version::from_parts(
+            "2.3.0-doca2.8.0",
+            vec![
+                Part::Number(2),
+                Part::Number(3),
+                Part::Number(0),
+                Part::Text("doca"),
+                Part::Number(2),
+                Part::Number(8),
+                Part::Number(0),
+            ],
+        );
+        if hbn_version < min_version {
+            HBNDeviceNames::pre_23()
+        } else {
+            HBNDeviceNames::hbn_23()
+        }
+    }
+    pub fn build_virt(&self, virt_rep_id: u32) -> String {
+        format!("{}{}{}", self.virt_rep_begin, virt_rep_id, self.sf_id)
+    }
+}
@@ -0,0 +1,27 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//! Rendering of the NVUE startup config of a DPU.
+//!
+//! forge-dpu-agent applies the rendered config on the DPU. carbide-api renders the same
+//! document to preview the config of a DPU, without depending on the agent.
+
+mod device_names;
+mod render;
+pub mod template;
+
+pub use device_names::HBNDeviceNames;
+pub use render::{NvueRenderContext, build_nvue_config, render_nvue_config};
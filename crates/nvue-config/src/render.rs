@@ -0,0 +1,453 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashMap;
+
+use ::rpc::forge::{
+    self as rpc, FlatInterfaceConfig, NetworkSecurityGroupRuleAction,
+    NetworkSecurityGroupRuleProtocol,
+};
+use carbide_network::virtualization::VpcVirtualizationType;
+use eyre::WrapErr;
+use version_compare::Version;
+
+use crate::HBNDeviceNames;
+use crate::template::{self, NetworkSecurityGroupRule};
+
+/// Inputs of the NVUE config that come from the DPU rather than from carbide-api
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NvueRenderContext {
+    pub hbn_version: String,
+    pub dpu_hostname: String,
+    pub dpu_search_domain: String,
+    /// The config is applied through the startup file on the DPU OS, rather than the NVUE REST API
+    pub is_dpu_os: bool,
+}
+
+/// Renders the NVUE config a DPU in `context` applies for network config `nc`.
+///
+/// forge-dpu-agent applies this document, and carbide-api renders it to preview the config of a
+/// DPU without pushing it.
+pub fn render_nvue_config(
+    vpc_virtualization_type: VpcVirtualizationType,
+    nc: &rpc::ManagedHostNetworkConfigResponse,
+    context: &NvueRenderContext,
+) -> eyre::Result<String> {
+    let hbn_version = Version::from(context.hbn_version.as_str())
+        .ok_or_else(|| eyre::eyre!("Invalid HBN version {}", context.hbn_version))?;
+    build_nvue_config(
+        vpc_virtualization_type,
+        nc,
+        HBNDeviceNames::new(hbn_version),
+        context,
+    )
+}
+
+/// Like [`render_nvue_config`], for a caller that already resolved the HBN device names.
+pub fn build_nvue_config(
+    vpc_virtualization_type: VpcVirtualizationType,
+    nc: &rpc::ManagedHostNetworkConfigResponse,
+    hbn_device_names: HBNDeviceNames,
+    context: &NvueRenderContext,
+) -> eyre::Result<String> {
+    let l_ip_str = match &nc.managed_host_config {
+        None => {
+            return Err(eyre::eyre!("Missing managed_host_config in response"));
+        }
+        Some(cfg) => {
+            if cfg.loopback_ip.is_empty() {
+                return Err(eyre::eyre!("Missing loopback IP"));
+            }
+            &cfg.loopback_ip
+        }
+    };
+    let loopback_ip = l_ip_str.parse().wrap_err_with(|| l_ip_str.clone())?;
+
+    let access_vlans = if nc.use_admin_network {
+        let admin_interface = nc
+            .admin_interface
+            .as_ref()
+            .ok_or_else(|| eyre::eyre!("Missing admin_interface"))?;
+        vec![template::VlanConfig {
+            vlan_id: admin_interface.vlan_id,
+            network: admin_interface.interface_prefix.clone(),
+            ip: admin_interface.ip.clone(),
+            ipv6_vlan_config: admin_interface.ipv6_interface_config.as_ref().map(|v6| {
+                template::Ipv6VlanConfig {
+                    network: v6.interface_prefix.clone(),
+                    ip: v6.ip.clone(),
+                }
+            }),
+        }]
+    } else {
+        let mut access_vlans = Vec::with_capacity(nc.tenant_interfaces.len());
+        for net in &nc.tenant_interfaces {
+            access_vlans.push(template::VlanConfig {
+                vlan_id: net.vlan_id,
+                network: net.interface_prefix.clone(),
+                ip: net.ip.clone(),
+                ipv6_vlan_config: net.ipv6_interface_config.as_ref().map(|v6| {
+                    template::Ipv6VlanConfig {
+                        network: v6.interface_prefix.clone(),
+                        ip: v6.ip.clone(),
+                    }
+                }),
+            });
+        }
+        access_vlans
+    };
+
+    let (has_stateful_nsg, network_security_groups) =
+        build_network_security_group_rules(&nc.tenant_interfaces)?;
+
+    // If we aren't on the admin network _or_ if we are the primary DPU
+    // then we should be enabled for tenancy (i.e. VRFs and related config)
+    let tenancy_enabled = !nc.use_admin_network || nc.is_primary_dpu;
+
+    let physical_name = hbn_device_names.reps[0].to_string();
+    let networks = if nc.use_admin_network {
+        if nc.is_primary_dpu {
+            let admin_interface = nc
+                .admin_interface
+                .as_ref()
+                .ok_or_else(|| eyre::eyre!("Missing admin_interface"))?;
+            vec![template::PortConfig {
+                interface_name: physical_name,
+                is_phy: true,
+                vlan: admin_interface.vlan_id as u16,
+                vni: if nc.network_virtualization_type() == ::rpc::forge::VpcVirtualizationType::Fnn
+                {
+                    Some(admin_interface.vni)
+                } else {
+                    None
+                },
+                l3_vni: if nc.network_virtualization_type()
+                    == ::rpc::forge::VpcVirtualizationType::Fnn
+                {
+                    Some(admin_interface.vpc_vni)
+                } else {
+                    None
+                },
+                gateway_cidr: admin_interface.gateway.clone(),
+                ipv6_port_config: admin_interface.ipv6_interface_config.as_ref().map(|v6| {
+                    template::Ipv6PortConfig {
+                        gateway_cidr: v6.interface_prefix.clone(),
+                        svi_ip: v6.svi_ip.clone(),
+                    }
+                }),
+                vpc_prefixes: admin_interface.vpc_prefixes.clone(),
+                vpc_peer_prefixes: admin_interface.vpc_peer_prefixes.clone(),
+                vpc_peer_vnis: admin_interface.vpc_peer_vnis.clone(),
+                svi_ip: admin_interface.svi_ip.clone(),
+                tenant_vrf_loopback_ip: admin_interface.tenant_vrf_loopback_ip.clone(),
+                network_security_group_id: None, // NSGs are not applied on the admin network.
+                is_l2_segment: if nc.network_virtualization_type()
+                    == ::rpc::forge::VpcVirtualizationType::Fnn
+                {
+                    admin_interface.is_l2_segment
+                } else {
+                    // Why false in legacy case? ¯\_(ツ)_/¯
+                    false
+                },
+            }]
+        } else {
+            vec![]
+        }
+    } else {
+        let mut ifs = Vec::with_capacity(nc.tenant_interfaces.len());
+        for net in &nc.tenant_interfaces {
+            let name = if net.function_type == rpc::InterfaceFunctionType::Physical as i32 {
+                physical_name.clone()
+            } else {
+                match net.virtual_function_id {
+                    Some(id) => hbn_device_names.build_virt(id),
+                    None => {
+                        eyre::bail!("Missing virtual function id");
+                    }
+                }
+            };
+
+            // For dual-stack FNN, the DPU-side IPv6 address is the network address
+            // of the /127 linknet (the ::0 end). The ::1 end is the host.
+            ifs.push(template::PortConfig {
+                interface_name: name,
+                is_phy: net.function_type == rpc::InterfaceFunctionType::Physical as i32,
+                vlan: net.vlan_id as u16,
+                vni: Some(net.vni), // TODO should this be nc.vni_device?
+                l3_vni: Some(net.vpc_vni),
+                gateway_cidr: net.gateway.clone(),
+                ipv6_port_config: net.ipv6_interface_config.as_ref().map(|v6| {
+                    template::Ipv6PortConfig {
+                        gateway_cidr: v6.interface_prefix.clone(),
+                        svi_ip: v6.svi_ip.clone(),
+                    }
+                }),
+                vpc_prefixes: net.vpc_prefixes.clone(),
+                vpc_peer_prefixes: net.vpc_peer_prefixes.clone(),
+                vpc_peer_vnis: net.vpc_peer_vnis.clone(),
+                svi_ip: net.svi_ip.clone(),
+                tenant_vrf_loopback_ip: net.tenant_vrf_loopback_ip.clone(),
+                network_security_group_id: net
+                    .network_security_group
+                    .as_ref()
+                    .map(|n| n.id.clone()),
+                is_l2_segment: net.is_l2_segment,
+            });
+        }
+        ifs
+    };
+
+    // We should explicitly guard against the absence of interfaces.
+    // A follow-up should probably do some work to split out tenant enabled vs. disabled DPUs more clearly.
+    if tenancy_enabled && networks.is_empty() {
+        return Err(eyre::eyre!(
+            "BUG: network config provided without interfaces"
+        ));
+    }
+
+    // Currently there's only one quarantine mode, BlockAllTraffic, so we block everything if it's set at all.
+    let is_quarantined = nc
+        .managed_host_config
+        .as_ref()
+        .is_some_and(|c| c.quarantine_state.is_some());
+
+    let network_security_policy_override_rules = if is_quarantined {
+        tracing::info!("managed host is quarantined! Disabling network access via nvue");
+
+        build_quarantined_network_security_group_rules()
+    } else {
+        nc.network_security_policy_overrides
+            .iter()
+            .map(|r| r.try_into())
+            .collect::<Result<Vec<NetworkSecurityGroupRule>, eyre::Error>>()?
+    };
+
+    let is_dpu_os = context.is_dpu_os;
+    let conf = template::NvueConfig {
+        is_fnn: false,
+        is_dpu_os,
+        fmds_gateway_vlan: if !is_dpu_os {
+            nc.tenant_interfaces
+                .iter()
+                .find(|i| i.function_type == rpc::InterfaceFunctionType::Physical as i32)
+                .map(|i| i.vlan_id as u16)
+        } else {
+            None
+        },
+        vpc_virtualization_type,
+        site_global_vpc_vni: nc.site_global_vpc_vni,
+        use_admin_network: nc.use_admin_network,
+        tenancy_enabled,
+        loopback_ip,
+        vf_intercept_bridge_port_name: nc.traffic_intercept_config.as_ref().and_then(|vc| {
+            vc.bridging
+                .as_ref()
+                .map(|b| b.vf_intercept_bridge_port.clone())
+        }),
+        vf_intercept_bridge_sf: nc.traffic_intercept_config.as_ref().and_then(|vc| {
+            vc.bridging
+                .as_ref()
+                .map(|b| b.vf_intercept_bridge_sf.clone())
+        }),
+        host_intercept_bridge_port_name: nc.traffic_intercept_config.as_ref().and_then(|vc| {
+            vc.bridging
+                .as_ref()
+                .map(|b| b.host_intercept_bridge_port.clone())
+        }),
+        secondary_overlay_vtep_ip: nc
+            .traffic_intercept_config
+            .as_ref()
+            .and_then(|vc| vc.additional_overlay_vtep_ip.clone()),
+        internal_bridge_routing_prefix: nc.traffic_intercept_config.as_ref().and_then(|vc| {
+            vc.bridging
+                .as_ref()
+                .map(|b| b.internal_bridge_routing_prefix.clone())
+        }),
+        traffic_intercept_public_prefixes: nc
+            .traffic_intercept_config
+            .as_ref()
+            .map(|vc| vc.public_prefixes.clone())
+            .unwrap_or_default(),
+        asn: nc.asn,
+        datacenter_asn: nc.datacenter_asn,
+        common_internal_route_target: nc.common_internal_route_target.map(|rt| {
+            template::RouteTargetConfig {
+                asn: rt.asn,
+                vni: rt.vni,
+            }
+        }),
+        additional_route_target_imports: nc
+            .additional_route_target_imports
+            .iter()
+            .map(|rt| template::RouteTargetConfig {
+                asn: rt.asn,
+                vni: rt.vni,
+            })
+            .collect(),
+        dpu_hostname: context.dpu_hostname.clone(),
+        dpu_search_domain: context.dpu_search_domain.clone(),
+        hbn_version: Some(context.hbn_version.clone()),
+        uplinks: hbn_device_names
+            .uplinks
+            .into_iter()
+            .map(String::from)
+            .collect(),
+        dhcp_servers: nc.dhcp_servers.clone(),
+        route_servers: nc.route_servers.clone(),
+        ct_port_configs: networks,
+        ct_vrf_name: format!("vpc_{}", nc.vpc_vni.unwrap_or_default()),
+        ct_access_vlans: access_vlans,
+        deny_prefixes: nc.deny_prefixes.clone(),
+        site_fabric_prefixes: nc.site_fabric_prefixes.clone(),
+        anycast_site_prefixes: nc.anycast_site_prefixes.clone(),
+        tenant_host_asn: nc.tenant_host_asn,
+        stateful_acls_enabled: nc.stateful_acls_enabled && has_stateful_nsg,
+
+        // For now, the isolation options boil down to a boolean,
+        // but the match will make sure we catch and adjust accordingly
+        // if that changes in the future.
+        use_vpc_isolation: match nc.vpc_isolation_behavior() {
+            rpc::VpcIsolationBehaviorType::VpcIsolationInvalid => {
+                return Err(eyre::eyre!("received invalid VPC-isolation config"));
+            }
+            rpc::VpcIsolationBehaviorType::VpcIsolationMutual => true,
+            //  There's no isolation.
+            rpc::VpcIsolationBehaviorType::VpcIsolationOpen => false,
+        },
+
+        network_security_policy_override_rules,
+        network_security_groups,
+        ct_l3_vni: nc.vpc_vni,
+        ct_vrf_loopback: "FNN".to_string(),
+        l3_domains: vec![],
+        ct_routing_profile: if nc.network_virtualization_type()
+            == ::rpc::forge::VpcVirtualizationType::Fnn
+            && nc.routing_profile.is_none()
+        {
+            return Err(eyre::eyre!(
+                "BUG: FNN config provided without routing-profile"
+            ));
+        } else {
+            nc.routing_profile
+                .as_ref()
+                .map(|rp| template::RoutingProfile {
+                    leak_default_route_from_underlay: rp.leak_default_route_from_underlay,
+                    leak_tenant_host_routes_to_underlay: rp.leak_tenant_host_routes_to_underlay,
+                    tenant_leak_communities_accepted: rp.tenant_leak_communities_accepted,
+                    route_target_imports: rp
+                        .route_target_imports
+                        .iter()
+                        .map(|rt| template::RouteTargetConfig {
+                            asn: rt.asn,
+                            vni: rt.vni,
+                        })
+                        .collect(),
+                    route_targets_on_exports: rp
+                        .route_targets_on_exports
+                        .iter()
+                        .map(|rt| template::RouteTargetConfig {
+                            asn: rt.asn,
+                            vni: rt.vni,
+                        })
+                        .collect(),
+                })
+        },
+        bgp_leaf_session_password: nc.bgp_leaf_session_password.clone(),
+    };
+
+    template::build(conf)
+}
+
+fn build_network_security_group_rules(
+    interfaces: &[FlatInterfaceConfig],
+) -> eyre::Result<(bool, Vec<template::NetworkSecurityGroup>)> {
+    let mut network_security_groups = HashMap::<String, template::NetworkSecurityGroup>::new();
+    let mut has_stateful = false;
+    for iface in interfaces {
+        if let Some(ref nsg) = iface.network_security_group {
+            let rules = nsg
+                .rules
+                .iter()
+                .map(NetworkSecurityGroupRule::try_from)
+                .collect::<Result<Vec<NetworkSecurityGroupRule>, _>>()?;
+
+            has_stateful |= nsg.stateful_egress;
+
+            network_security_groups
+                .entry(nsg.id.clone())
+                .or_insert_with(|| template::NetworkSecurityGroup {
+                    id: nsg.id.clone(),
+                    rules,
+                    stateful_egress: nsg.stateful_egress,
+                });
+        }
+    }
+    Ok((
+        has_stateful,
+        network_security_groups.into_values().collect(),
+    ))
+}
+
+/// Build a set of security group rules that deny all traffic.
+///
+/// Builds rules for ipv6 and ipv4, both ingress and ingress, denying traffic to all address
+/// prefixes.
+fn build_quarantined_network_security_group_rules() -> Vec<NetworkSecurityGroupRule> {
+    let build_rule = |ingress, ipv6| {
+        let catchall_prefix = if ipv6 {
+            vec!["::/0".to_string()]
+        } else {
+            vec!["0.0.0.0/0".to_string()]
+        };
+
+        template::NetworkSecurityGroupRule {
+            id: format!(
+                "quarantine_{}_{}",
+                if ipv6 { "ipv6" } else { "ipv4" },
+                if ingress { "ingress" } else { "egress" }
+            ),
+            ingress,
+            ipv6,
+            priority: 0,
+            src_port_start: None,
+            src_port_end: None,
+            dst_port_start: None,
+            dst_port_end: None,
+            can_match_any_protocol: true,
+            can_be_stateful: false,
+            protocol: NetworkSecurityGroupRuleProtocol::to_string_from_enum_i32(
+                NetworkSecurityGroupRuleProtocol::NsgRuleProtoAny.into(),
+            )
+            .expect("BUG: cannot convert `any` protocol to string?")
+            .to_lowercase(),
+            action: NetworkSecurityGroupRuleAction::to_string_from_enum_i32(
+                NetworkSecurityGroupRuleAction::NsgRuleActionDeny.into(),
+            )
+            .expect("BUG: cannot convert deny action to string?")
+            .to_lowercase(),
+            src_prefixes: catchall_prefix.clone(),
+            dst_prefixes: catchall_prefix,
+        }
+    };
+
+    vec![
+        build_rule(false, false),
+        build_rule(false, true),
+        build_rule(true, false),
+        build_rule(true, true),
+    ]
+}
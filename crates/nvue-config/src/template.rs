@@ -0,0 +1,1956 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashMap;
+
+use ::rpc::forge as rpc;
+use carbide_network::ip::prefix::{IpNet, Ipv4Net};
+use carbide_network::sanitized_mac;
+use carbide_network::virtualization::VpcVirtualizationType;
+use eyre::WrapErr;
+use gtmpl_derive::Gtmpl;
+use mac_address::MacAddress;
+use serde::Deserialize;
+
+const TMPL_ETV_WITH_NVUE: &str = include_str!("../templates/nvue_startup_etv.conf");
+const TMPL_FNN: &str = include_str!("../templates/nvue_startup_fnn.conf");
+
+/// Returns the NVUE template for the given virtualization type.
+pub fn template_for(vtype: VpcVirtualizationType) -> eyre::Result<&'static str> {
+    match vtype {
+        VpcVirtualizationType::EthernetVirtualizer
+        | VpcVirtualizationType::EthernetVirtualizerWithNvue => Ok(TMPL_ETV_WITH_NVUE),
+        VpcVirtualizationType::Fnn => Ok(TMPL_FNN),
+    }
+}
+
+/// This value is added to the priority value specified
+/// by users for their NSG rules.
+const NETWORK_SECURITY_GROUP_RULE_PRIORITY_START: u32 = 2000;
+
+/// This limits the number of rules we'll allow into the set for
+/// nvue.  We do not expect to ever hit this as the rules should
+/// have been limited before they reached the DPU.  It's purpose here
+/// is defense-in-depth.
+///
+/// We have something similar on the controller side, though the limit
+/// there is likely to stay in the hundreds.  The limit here will
+/// likely always be far larger because we're only concerned with
+/// protecting the DPU from getting a rule set that would expand
+/// into something big enough to exhaust its physical resources.
+/// We want a limit small enough to protect us but big enough that we
+/// don't have to remember to keep bumping this up as we decide nvue
+/// can handle more rules.
+///
+/// *_NOTE: This is a limit per unique NSG.  Multiple interfaces could
+/// each have a unique NSG associated, either directly or via different VPC
+/// associations per interface._*
+const NETWORK_SECURITY_GROUP_RULE_COUNT_MAX: usize = 10000;
+
+/// split_prefixes_by_family splits a list of CIDR prefix strings
+/// into IPv4 and IPv6 buckets. Each bucket gets sequential indices
+/// starting at `start_index`. Unparseable prefixes are warned and
+/// dropped (because NVUE would fail on invalid addresses anyway).
+fn split_prefixes_by_family(prefixes: &[String], start_index: usize) -> (Vec<Prefix>, Vec<Prefix>) {
+    let valid: Vec<_> = prefixes
+        .iter()
+        .filter_map(|s| match s.parse::<IpNet>() {
+            Ok(net) => Some((s.clone(), net)),
+            Err(e) => {
+                tracing::warn!(prefix = %s, error = %e, "dropping unparseable prefix");
+                None
+            }
+        })
+        .collect();
+
+    let (v4, v6): (Vec<_>, Vec<_>) = valid
+        .into_iter()
+        .partition(|(_, net)| matches!(net, IpNet::V4(_)));
+
+    let make_prefixes = |items: Vec<(String, IpNet)>| -> Vec<Prefix> {
+        items
+            .into_iter()
+            .enumerate()
+            .map(|(idx, (s, _))| Prefix {
+                Index: format!("{}", idx + start_index),
+                Prefix: s,
+            })
+            .collect()
+    };
+
+    (make_prefixes(v4), make_prefixes(v6))
+}
+
+pub fn build(conf: NvueConfig) -> eyre::Result<String> {
+    let template = template_for(conf.vpc_virtualization_type)?;
+    let is_dpu_os = conf.is_dpu_os;
+    let fmds_gateway_vlan = conf.fmds_gateway_vlan;
+    let host_interfaces: Vec<TmplHostInterfaces> = conf
+        .ct_access_vlans
+        .into_iter()
+        .map(|vl| TmplHostInterfaces {
+            ID: vl.vlan_id,
+            HostIP: vl.ip,
+            HostIPv6: vl.ipv6_vlan_config.as_ref().map(|v6| v6.ip.clone()),
+            HostRoute: vl.network,
+        })
+        .collect();
+
+    // This assumes that the routing profile is expected to be same for
+    // all VPCs of a tenant _and_ for all VPCs that an instance might span.
+    // That should be ok for now, and a smaller MR later that moves
+    // profiles into FlatInterfaceConfig could change that.
+    // For now, we clone later so that we can put this into each TmplVpc
+    // and make a later transition easier.
+    let routing_profile = conf
+        .ct_routing_profile
+        .as_ref()
+        .map(|rt| TmplRoutingProfile {
+            TenantLeakCommunitiesAccepted: rt.tenant_leak_communities_accepted,
+            LeakDefaultRouteFromUnderlay: rt.leak_default_route_from_underlay,
+            LeakTenantHostRoutesToUnderlay: rt.leak_tenant_host_routes_to_underlay,
+            RouteTargetImports: rt
+                .route_target_imports
+                .iter()
+                .map(|rt| TmplRouteTargetConfig {
+                    ASN: rt.asn,
+                    VNI: rt.vni,
+                })
+                .collect(),
+            RouteTargetsOnExports: rt
+                .route_targets_on_exports
+                .iter()
+                .map(|rt| TmplRouteTargetConfig {
+                    ASN: rt.asn,
+                    VNI: rt.vni,
+                })
+                .collect(),
+        });
+
+    // There are two assumptions about pre-FNN...
+    // 1 - ManagedHostNetworkConfigResponse only has rules inherited either from VPC or Instance,
+    //     and VPC-spanning per-interface isn't possible.
+    // 2 - There is only one DPU.
+    //
+    // Both of these are valid assumptions right now because L3 EVPN and multi-DPU are only possible with FNN,
+    // We probably shouldn't warn anymore, but we probably should drop info for now.
+
+    if conf.network_security_groups.len() > 1 {
+        tracing::info!(
+            "Found more than one interface with network security group applied in ManagedHostNetworkConfigResponse, so rules will be merged when FNN is not in use.",
+        );
+    }
+
+    let mut nsg_id_index_map = HashMap::<String, u16>::new();
+    let mut network_security_groups =
+        Vec::<TmplNetworkSecurityGroup>::with_capacity(conf.network_security_groups.len());
+
+    // Pre-FNN will still expect a merged/flattened version.
+    let mut merged_ingress_ipv4_nsg_rules = vec![];
+    let mut merged_ingress_ipv6_nsg_rules = vec![];
+    let mut merged_egress_ipv4_nsg_rules = vec![];
+    let mut merged_egress_ipv6_nsg_rules = vec![];
+
+    let has_network_security_group = !conf.network_security_groups.is_empty();
+
+    for (i, nsg) in conf.network_security_groups.into_iter().enumerate() {
+        let idx = i.try_into().wrap_err(format!(
+            "number of unique network security groups exceeds {} limit",
+            u16::MAX
+        ))?;
+
+        nsg_id_index_map.insert(nsg.id, idx);
+
+        let (ingress_ipv4_rules, egress_ipv4_rules, ingress_ipv6_rules, egress_ipv6_rules) =
+            prepare_network_security_group_rules(nsg.rules)?;
+
+        merged_ingress_ipv4_nsg_rules.extend_from_slice(&ingress_ipv4_rules);
+        merged_ingress_ipv6_nsg_rules.extend_from_slice(&ingress_ipv6_rules);
+        merged_egress_ipv4_nsg_rules.extend_from_slice(&egress_ipv4_rules);
+        merged_egress_ipv6_nsg_rules.extend_from_slice(&egress_ipv6_rules);
+
+        network_security_groups.push(TmplNetworkSecurityGroup {
+            Index: idx,
+            StatefulEgress: nsg.stateful_egress,
+            IngressNetworkSecurityGroupRulesIpv4: ingress_ipv4_rules,
+            IngressNetworkSecurityGroupRulesIpv6: ingress_ipv6_rules,
+            EgressNetworkSecurityGroupRulesIpv4: egress_ipv4_rules,
+            EgressNetworkSecurityGroupRulesIpv6: egress_ipv6_rules,
+        });
+    }
+
+    let mut port_configs = Vec::with_capacity(conf.ct_port_configs.len());
+    let mut vpc_configs = HashMap::<u32, TmplVpc>::new();
+
+    // For non-FNN (ETV), tenant-wide VPC peer prefixes and VNIs come from
+    // the first port config that has them. Extract these before the loop.
+    let (vpc_peer_prefixes, vpc_peer_prefixes_ipv6) = conf
+        .ct_port_configs
+        .iter()
+        .find(|p| !p.vpc_peer_prefixes.is_empty())
+        .map(|p| split_prefixes_by_family(&p.vpc_peer_prefixes, 1))
+        .unwrap_or_default();
+    let vpc_peer_vnis: Vec<TmplVni> = conf
+        .ct_port_configs
+        .iter()
+        .find(|p| !p.vpc_peer_vnis.is_empty())
+        .map(|p| {
+            p.vpc_peer_vnis
+                .iter()
+                .map(|vni| TmplVni { Vni: *vni })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if conf
+        .ct_port_configs
+        .iter()
+        .filter(|p| !p.vpc_peer_prefixes.is_empty())
+        .count()
+        > 1
+    {
+        tracing::info!(
+            "Found more than one tenant interface, so VPC peering details of only the first found will be used when FNN is not in use."
+        );
+    }
+
+    let mut has_any_vpc_tenant_host_leak_to_underlay = false;
+    let mut fmds_gateway_matched = false;
+
+    for (base_i, network) in conf.ct_port_configs.into_iter().enumerate() {
+        let svi_mac = vni_to_svi_mac(network.vni.unwrap_or(0))?.to_string();
+        let (vpc_ipv4, vpc_ipv6) =
+            split_prefixes_by_family(&network.vpc_prefixes, (base_i + 1) * 10);
+        let port = TmplConfigPort {
+            InterfaceName: network.interface_name.clone(),
+            Index: format!("{}", (base_i + 1) * 10),
+            VlanID: network.vlan,
+            IsPhy: network.is_phy,
+            L2VNI: network.vni.map(|x| x.to_string()).unwrap_or("".to_string()),
+            IPs: {
+                std::iter::once(network.gateway_cidr.clone())
+                    .chain(
+                        network
+                            .ipv6_port_config
+                            .as_ref()
+                            .map(|v6| v6.gateway_cidr.clone()),
+                    )
+                    .collect()
+            },
+            SviIPs: std::iter::once(network.svi_ip)
+                .chain(std::iter::once(
+                    network
+                        .ipv6_port_config
+                        .as_ref()
+                        .and_then(|v6| v6.svi_ip.clone()),
+                ))
+                .flatten()
+                .collect(),
+            SviMAC: svi_mac,
+            VrfName: format!("vpc_{}", network.l3_vni.unwrap_or_default()),
+            HasVpcPeerPrefixes: !network.vpc_peer_prefixes.is_empty(),
+            HasVpcPeerPrefixesIpv6: network
+                .vpc_peer_prefixes
+                .iter()
+                .any(|p| matches!(p.parse::<IpNet>(), Ok(IpNet::V6(_)))),
+            HasVpcPrefixes: !vpc_ipv4.is_empty(),
+            VpcPrefixes: vpc_ipv4,
+            HasVpcPrefixesIpv6: !vpc_ipv6.is_empty(),
+            VpcPrefixesIpv6: vpc_ipv6,
+            IsL2Segment: network.is_l2_segment,
+            StorageTarget: false, // XXX (Classic, L3)
+            HasNetworkSecurityGroup: network.network_security_group_id.is_some(),
+            NetworkSecurityGroupIndex: network
+                .network_security_group_id
+                .as_ref()
+                .map(|nid| {
+                    nsg_id_index_map
+                    .get(nid)
+                    .copied()
+                    .ok_or_else(|| {
+                    eyre::eyre!(
+                        "BUG: PortConfig references network security group ID that does not exist",
+                    )
+                    })
+                })
+                .transpose()?,
+            HasFmdsGateway: !is_dpu_os && (fmds_gateway_vlan == Some(network.vlan)),
+        };
+        if port.HasFmdsGateway {
+            fmds_gateway_matched = true;
+        }
+
+        has_any_vpc_tenant_host_leak_to_underlay = has_any_vpc_tenant_host_leak_to_underlay
+            || routing_profile
+                .as_ref()
+                .map(|p| p.LeakTenantHostRoutesToUnderlay)
+                .unwrap_or_default();
+        let (vpc_peer_ipv4, vpc_peer_ipv6) =
+            split_prefixes_by_family(&network.vpc_peer_prefixes, 1);
+
+        vpc_configs
+            .entry(network.l3_vni.unwrap_or_default())
+            .and_modify(|v| {
+                v.PortPrefixes.extend_from_slice(&port.VpcPrefixes);
+                v.PortPrefixesIpv6.extend_from_slice(&port.VpcPrefixesIpv6);
+                v.PortConfigs.push(port.clone());
+            })
+            .or_insert_with(|| TmplVpc {
+                VrfName: port.VrfName.clone(),
+                L3VNI: network.l3_vni.unwrap_or_default(),
+                VrfLoopback: network.tenant_vrf_loopback_ip.unwrap_or_default(),
+                // TODO: This is wasteful because it should be specific to a VPC.
+                // Otherwise, all VPCs will have a BGP peer config for each
+                // interface, regardless of whether the interface is owned by
+                // that VPC.
+                HostInterfaces: host_interfaces.clone(),
+                PortConfigs: vec![port.clone()],
+                HasVpcPeerPrefixes: !vpc_peer_ipv4.is_empty(),
+                VpcPeerPrefixes: vpc_peer_ipv4,
+                HasVpcPeerPrefixesIpv6: !vpc_peer_ipv6.is_empty(),
+                VpcPeerPrefixesIpv6: vpc_peer_ipv6,
+                HasVpcPeerVnis: !network.vpc_peer_vnis.is_empty(),
+                VpcPeerVnis: network
+                    .vpc_peer_vnis
+                    .iter()
+                    .map(|vni| TmplVni { Vni: *vni })
+                    .collect(),
+                RoutingProfile: routing_profile.clone(),
+                PortPrefixes: port.VpcPrefixes.clone(),
+                PortPrefixesIpv6: port.VpcPrefixesIpv6.clone(),
+            });
+
+        port_configs.push(port);
+    }
+
+    if let Some(vlan) = fmds_gateway_vlan
+        && !fmds_gateway_matched
+    {
+        tracing::error!(
+            vlan,
+            "fmds_gateway_vlan not found in any port config; FMDS gateway address will not be configured"
+        );
+    }
+
+    let include_bridge = !port_configs.is_empty() && port_configs.iter().all(|b| b.IsL2Segment);
+
+    let (
+        ingress_ipv4_override_rules,
+        egress_ipv4_override_rules,
+        ingress_ipv6_override_rules,
+        egress_ipv6_override_rules,
+    ) = prepare_network_security_group_rules(conf.network_security_policy_override_rules)?;
+
+    // The original VPC isolation would add site fabric prefixes to deny prefixes,
+    // with site_fabric_prefixes coming first.
+    // This is just an easy way to maintain the ordering of the original behavior.
+    let deny_prefix_index_offset = conf.site_fabric_prefixes.len();
+
+    let has_static_advertisements = conf.secondary_overlay_vtep_ip.is_some();
+
+    let (
+        has_internal_bridging,
+        vf_intercept_bridge_ip,
+        vf_intercept_hbn_representor_ip,
+        public_prefix_internal_next_hop,
+        intercept_bridge_prefix_len,
+        // IPv4 only for now. Internal HBN bridge plumbing uses 169.254.x.x
+        // link-local addressing for DPU to HBN communication. An IPv6 equivalent
+        // (fe80:: or similar) may be needed in the future for dual-stack bridging.
+    ) = if let Some(bridge_prefix) = conf
+        .internal_bridge_routing_prefix
+        .map(|p| p.parse::<Ipv4Net>())
+        .transpose()?
+    {
+        let prefix_len = bridge_prefix.prefix_len();
+        let mut hosts = bridge_prefix.hosts();
+
+        // 1st host is for the VF intercept bridge.
+        let Some(vf_intercept_bridge_ip) = hosts.next() else {
+            return Err(eyre::eyre!(
+                "expected VF intercept bridge IP not found in bridge routing prefix supplied by internal_bridge_routing_prefix",
+            ));
+        };
+
+        // 2nd host address is used within the HBN container on the SF being used for
+        // intercepted VF traffic.
+        let Some(vf_intercept_hbn_representor_ip) = hosts.next() else {
+            return Err(eyre::eyre!(
+                "expected VF intercept HBN representor IP not found in bridge routing prefix supplied by internal_bridge_routing_prefix",
+            ));
+        };
+
+        // 3rd host is for use by a traffic intercept user.  This lets the user know, a priori, of an IP
+        // within the stretched L2 domain of the combined br-hbn and custom bridges.
+        // This allows them to route traffic directly to and out of the HBN pod.
+        let Some(public_prefix_internal_next_hop) = hosts.next() else {
+            return Err(eyre::eyre!(
+                "expected public_prefix_internal_next_hop bridge IP not found in bridge routing prefix supplied by internal_bridge_routing_prefix",
+            ));
+        };
+
+        // >= 4th host is currently unused.
+
+        (
+            true,
+            format!("{vf_intercept_bridge_ip}"),
+            format!("{vf_intercept_hbn_representor_ip}"),
+            format!("{public_prefix_internal_next_hop}"),
+            prefix_len,
+        )
+    } else {
+        (
+            false,
+            String::default(),
+            String::default(),
+            String::default(),
+            0,
+        )
+    };
+
+    let mut vpcs = vpc_configs.into_values().collect::<Vec<TmplVpc>>();
+    vpcs.sort_by(|a, b| a.L3VNI.cmp(&b.L3VNI));
+
+    let (traffic_intercept_ipv4, traffic_intercept_ipv6) =
+        split_prefixes_by_family(&conf.traffic_intercept_public_prefixes, 1);
+    let (anycast_ipv4, anycast_ipv6) = split_prefixes_by_family(&conf.anycast_site_prefixes, 1000);
+    let (site_fabric_ipv4, site_fabric_ipv6) =
+        split_prefixes_by_family(&conf.site_fabric_prefixes, 1000);
+    let (deny_ipv4, deny_ipv6) =
+        split_prefixes_by_family(&conf.deny_prefixes, 1000 + deny_prefix_index_offset);
+
+    let params = TmplNvue {
+        HasBgpLeafSessionPassword: conf.bgp_leaf_session_password.is_some(),
+        BgpLeafSessionPassword: conf.bgp_leaf_session_password.unwrap_or_default(),
+        UseAdminNetwork: conf.use_admin_network,
+        LoopbackIP: conf.loopback_ip,
+        HasSiteGlobalVpcVni: conf.site_global_vpc_vni.is_some(),
+        SiteGlobalVpcVni: conf.site_global_vpc_vni.unwrap_or_default(),
+        HasStaticAdvertisements: has_static_advertisements,
+        HasSecondaryOverlayVTEP: conf.secondary_overlay_vtep_ip.is_some(),
+        SecondaryOverlayVtepIP: conf.secondary_overlay_vtep_ip.unwrap_or_default(),
+        HasInternalBridgeRouting: has_internal_bridging,
+        VfInterceptBridgeIP: vf_intercept_bridge_ip,
+        InterceptBridgePrefixLen: intercept_bridge_prefix_len,
+        PublicPrefixInternalNextHop: public_prefix_internal_next_hop,
+        VfInterceptHbnRepresentorIp: vf_intercept_hbn_representor_ip,
+        VfInterceptBridgeSf: conf.vf_intercept_bridge_sf.unwrap_or_default(),
+        HasAnyVpcTenantHostLeakToUnderlay: has_any_vpc_tenant_host_leak_to_underlay,
+        TrafficInterceptPublicPrefixes: traffic_intercept_ipv4,
+        TrafficInterceptPublicPrefixesIpv6: traffic_intercept_ipv6,
+        ASN: conf.asn,
+        DatacenterASN: conf.datacenter_asn,
+        UseCommonInternalTenantRouteTarget: conf.common_internal_route_target.is_some(),
+        CommonInternalRouteTarget: conf.common_internal_route_target.map(|rt| {
+            TmplRouteTargetConfig {
+                ASN: rt.asn,
+                VNI: rt.vni,
+            }
+        }),
+        AdditionalRouteTargetImports: conf
+            .additional_route_target_imports
+            .iter()
+            .map(|rt| TmplRouteTargetConfig {
+                ASN: rt.asn,
+                VNI: rt.vni,
+            })
+            .collect(),
+        DPUHostname: conf.dpu_hostname,
+        SearchDomain: conf.dpu_search_domain,
+        Uplinks: conf.uplinks.clone(),
+        RouteServers: conf.route_servers.clone(),
+        DHCPServers: conf.dhcp_servers.clone(),
+        AnycastSitePrefixes: anycast_ipv4,
+        AnycastSitePrefixesIpv6: anycast_ipv6,
+        HasSiteFabricPrefixes: !site_fabric_ipv4.is_empty(),
+        SiteFabricPrefixes: site_fabric_ipv4,
+        HasSiteFabricPrefixesIpv6: !site_fabric_ipv6.is_empty(),
+        SiteFabricPrefixesIpv6: site_fabric_ipv6,
+        HasDenyPrefixes: !deny_ipv4.is_empty(),
+        DenyPrefixes: deny_ipv4,
+        HasDenyPrefixesIpv6: !deny_ipv6.is_empty(),
+        DenyPrefixesIpv6: deny_ipv6,
+        StatefulAclsEnabled: conf.stateful_acls_enabled,
+        UseVpcIsolation: conf.use_vpc_isolation,
+        HasIpv4IngressSecurityPolicyOverrideRules: !ingress_ipv4_override_rules.is_empty(),
+        HasIpv4EgressSecurityPolicyOverrideRules: !egress_ipv4_override_rules.is_empty(),
+        HasIpv6IngressSecurityPolicyOverrideRules: !ingress_ipv6_override_rules.is_empty(),
+        HasIpv6EgressSecurityPolicyOverrideRules: !egress_ipv6_override_rules.is_empty(),
+        Ipv4IngressNetworkSecurityPolicyOverrideRules: ingress_ipv4_override_rules,
+        Ipv4EgressNetworkSecurityPolicyOverrideRules: egress_ipv4_override_rules,
+        Ipv6IngressNetworkSecurityPolicyOverrideRules: ingress_ipv6_override_rules,
+        Ipv6EgressNetworkSecurityPolicyOverrideRules: egress_ipv6_override_rules,
+        HbnVersion: conf.hbn_version,
+        Tenant: TmplComputeTenant {
+            RoutingProfile: routing_profile,
+            Vpcs: vpcs,
+            VrfName: conf.ct_vrf_name,
+            L3VNI: conf.ct_l3_vni.unwrap_or_default().to_string(),
+            PortConfigs: port_configs,
+            HasHostASN: conf.tenant_host_asn.is_some(),
+            HostASN: conf.tenant_host_asn.unwrap_or_default(),
+            HostInterfaces: host_interfaces,
+            NetworkSecurityGroups: network_security_groups,
+            HasNetworkSecurityGroup: has_network_security_group,
+            HasIpv4IngressSecurityGroupRules: !merged_ingress_ipv4_nsg_rules.is_empty(),
+            HasIpv4EgressSecurityGroupRules: !merged_egress_ipv4_nsg_rules.is_empty(),
+            HasIpv6IngressSecurityGroupRules: !merged_ingress_ipv6_nsg_rules.is_empty(),
+            HasIpv6EgressSecurityGroupRules: !merged_egress_ipv6_nsg_rules.is_empty(),
+            IngressNetworkSecurityGroupRulesIpv4: merged_ingress_ipv4_nsg_rules,
+            EgressNetworkSecurityGroupRulesIpv4: merged_egress_ipv4_nsg_rules,
+            IngressNetworkSecurityGroupRulesIpv6: merged_ingress_ipv6_nsg_rules,
+            EgressNetworkSecurityGroupRulesIpv6: merged_egress_ipv6_nsg_rules,
+            HasVpcPeerPrefixes: !vpc_peer_prefixes.is_empty(),
+            VpcPeerPrefixes: vpc_peer_prefixes,
+            HasVpcPeerPrefixesIpv6: !vpc_peer_prefixes_ipv6.is_empty(),
+            VpcPeerPrefixesIpv6: vpc_peer_prefixes_ipv6,
+            HasVpcPeerVnis: !vpc_peer_vnis.is_empty(),
+            VpcPeerVnis: vpc_peer_vnis,
+        },
+        // XXX: Unused placeholders for later.
+        IsStorageClient: false,                   // XXX (Classic, L3)
+        StorageDpuIP: "127.9.9.9".to_string(),    // XXX (Classic, L3)
+        l3vnistorageVLAN: "vlan1337".to_string(), // XXX (Classic, L3)
+        StorageL3VNI: 0,                          // XXX (Classic, L3)
+        StorageLoopback: "127.8.8.8".to_string(), // XXX (Classic, L3)
+        DPUstorageprefix: "127.7.7.7/32".to_string(),
+        IncludeBridge: include_bridge,
+        IsDpuOs: is_dpu_os,
+    };
+
+    gtmpl::template(template, params).map_err(|e| {
+        println!("ERR filling template: {e}",);
+        e.into()
+    })
+}
+
+/// Prepares a set of network security groups rules for template use.
+/// In the process, it expands the rules and evaluates whether they
+/// exceed predefined limits.
+///
+/// * `rules` - A list of network security group rules
+#[allow(clippy::type_complexity)]
+fn prepare_network_security_group_rules(
+    rules: Vec<NetworkSecurityGroupRule>,
+) -> Result<
+    (
+        Vec<TmplNetworkSecurityGroupRule>,
+        Vec<TmplNetworkSecurityGroupRule>,
+        Vec<TmplNetworkSecurityGroupRule>,
+        Vec<TmplNetworkSecurityGroupRule>,
+    ),
+    eyre::Error,
+> {
+    let mut ingress_ipv4_rules: Vec<&NetworkSecurityGroupRule> = vec![];
+    let mut egress_ipv4_rules: Vec<&NetworkSecurityGroupRule> = vec![];
+    let mut ingress_ipv6_rules: Vec<&NetworkSecurityGroupRule> = vec![];
+    let mut egress_ipv6_rules: Vec<&NetworkSecurityGroupRule> = vec![];
+
+    let mut total_rule_count: usize = 0;
+
+    for rule in rules.iter() {
+        // Calculate and accumulate what the number of rules
+        // would be after expansion so we can cut things off
+        // and err if we got a bad payload that could risk
+        // the DPU itself.
+        total_rule_count = match total_rule_count.overflowing_add(
+            rule.src_prefixes
+                .len()
+                .saturating_mul(rule.dst_prefixes.len())
+                .saturating_mul(
+                    (rule
+                        .src_port_end
+                        .unwrap_or_default()
+                        .saturating_sub(rule.src_port_start.unwrap_or_default())
+                        + 1) as usize,
+                )
+                .saturating_mul(
+                    (rule
+                        .dst_port_end
+                        .unwrap_or_default()
+                        .saturating_sub(rule.dst_port_start.unwrap_or_default())
+                        + 1) as usize,
+                ),
+        ) {
+            (_, true) => {
+                return Err(eyre::eyre!(
+                    "supplied network security group rule count exceeds limit of {}",
+                    NETWORK_SECURITY_GROUP_RULE_COUNT_MAX
+                ));
+            }
+            (v, false) => v,
+        };
+
+        if total_rule_count > NETWORK_SECURITY_GROUP_RULE_COUNT_MAX {
+            return Err(eyre::eyre!(
+                "supplied network security group rule count exceeds limit of {}",
+                NETWORK_SECURITY_GROUP_RULE_COUNT_MAX
+            ));
+        }
+
+        match (rule.ingress, rule.ipv6) {
+            (true, false) => ingress_ipv4_rules.push(rule),
+            (false, false) => egress_ipv4_rules.push(rule),
+            (true, true) => ingress_ipv6_rules.push(rule),
+            (false, true) => egress_ipv6_rules.push(rule),
+        }
+    }
+
+    // Order the rules by priority
+    ingress_ipv4_rules.sort_by_key(|nsg| nsg.priority);
+    egress_ipv4_rules.sort_by_key(|nsg| nsg.priority);
+    ingress_ipv6_rules.sort_by_key(|nsg| nsg.priority);
+    egress_ipv6_rules.sort_by_key(|nsg| nsg.priority);
+
+    Ok((
+        expand_network_security_group_rules(ingress_ipv4_rules),
+        expand_network_security_group_rules(egress_ipv4_rules),
+        expand_network_security_group_rules(ingress_ipv6_rules),
+        expand_network_security_group_rules(egress_ipv6_rules),
+    ))
+}
+
+/// Expands a set of network security group rules.
+/// Source and destination port ranges and prefix lists will
+/// be expanded to a set of individual rules.
+/// A new vector of template-ready expanded network security
+/// groups will be returned.
+///
+/// * `nsgs` - A list of references to network security groups to expand.
+fn expand_network_security_group_rules(
+    rules: Vec<&NetworkSecurityGroupRule>,
+) -> Vec<TmplNetworkSecurityGroupRule> {
+    let mut tmpl_rules: Vec<TmplNetworkSecurityGroupRule> = vec![];
+
+    // NVUE config keys rules on priority, meaning no two rules can
+    // have the same priority in a given list.
+    // NOTE: This implicitly gives us at least one rule limit:
+    // If no two rules in a list can have the same priority, and the
+    // priority value max in nvue is limited to an unsigned 16-bit value,
+    // that u16 max priority number becomes the max number of rules in a
+    // given list.
+
+    for rule in rules {
+        for src_prefix in &rule.src_prefixes {
+            for dst_prefix in &rule.dst_prefixes {
+                if let (Some(src_start), Some(src_end)) = (rule.src_port_start, rule.src_port_end) {
+                    if let (Some(dst_start), Some(dst_end)) =
+                        (rule.dst_port_start, rule.dst_port_end)
+                    {
+                        for si in src_start..=src_end {
+                            for di in dst_start..=dst_end {
+                                tmpl_rules.push(TmplNetworkSecurityGroupRule {
+                                    Id: rule.id.clone(),
+                                    HasSrcPort: true,
+                                    SrcPort: si,
+                                    HasDstPort: true,
+                                    DstPort: di,
+                                    CanMatchAnyProtocol: rule.can_match_any_protocol,
+                                    CanBeStateful: rule.can_be_stateful,
+                                    Protocol: rule.protocol.clone(),
+                                    Action: rule.action.clone(),
+                                    SrcPrefix: src_prefix.clone(),
+                                    DstPrefix: dst_prefix.clone(),
+                                    OriginalPriority: rule.priority,
+                                    Priority: tmpl_rules.len() as u32
+                                        + NETWORK_SECURITY_GROUP_RULE_PRIORITY_START,
+                                });
+                            }
+                        }
+                    } else {
+                        for si in src_start..=src_end {
+                            tmpl_rules.push(TmplNetworkSecurityGroupRule {
+                                Id: rule.id.clone(),
+                                HasSrcPort: true,
+                                SrcPort: si,
+                                HasDstPort: false,
+                                DstPort: 0,
+                                CanMatchAnyProtocol: rule.can_match_any_protocol,
+                                CanBeStateful: rule.can_be_stateful,
+                                Protocol: rule.protocol.clone(),
+                                Action: rule.action.clone(),
+                                SrcPrefix: src_prefix.clone(),
+                                DstPrefix: dst_prefix.clone(),
+                                OriginalPriority: rule.priority,
+                                Priority: tmpl_rules.len() as u32
+                                    + NETWORK_SECURITY_GROUP_RULE_PRIORITY_START,
+                            });
+                        }
+                    }
+                } else if let (Some(dst_start), Some(dst_end)) =
+                    (rule.dst_port_start, rule.dst_port_end)
+                {
+                    for di in dst_start..=dst_end {
+                        tmpl_rules.push(TmplNetworkSecurityGroupRule {
+                            Id: rule.id.clone(),
+                            HasSrcPort: false,
+                            SrcPort: 0,
+                            HasDstPort: true,
+                            DstPort: di,
+                            CanMatchAnyProtocol: rule.can_match_any_protocol,
+                            CanBeStateful: rule.can_be_stateful,
+                            Protocol: rule.protocol.clone(),
+                            Action: rule.action.clone(),
+                            SrcPrefix: src_prefix.clone(),
+                            DstPrefix: dst_prefix.clone(),
+                            OriginalPriority: rule.priority,
+                            Priority: tmpl_rules.len() as u32
+                                + NETWORK_SECURITY_GROUP_RULE_PRIORITY_START,
+                        });
+                    }
+                } else {
+                    tmpl_rules.push(TmplNetworkSecurityGroupRule {
+                        Id: rule.id.clone(),
+                        HasSrcPort: false,
+                        SrcPort: 0,
+                        HasDstPort: false,
+                        DstPort: 0,
+                        CanMatchAnyProtocol: rule.can_match_any_protocol,
+                        CanBeStateful: rule.can_be_stateful,
+                        Protocol: rule.protocol.clone(),
+                        Action: rule.action.clone(),
+                        SrcPrefix: src_prefix.clone(),
+                        DstPrefix: dst_prefix.clone(),
+                        OriginalPriority: rule.priority,
+                        Priority: tmpl_rules.len() as u32
+                            + NETWORK_SECURITY_GROUP_RULE_PRIORITY_START,
+                    });
+                }
+            }
+        }
+    }
+
+    tmpl_rules
+}
+/// vni_to_svimac takes an VNI (which is a 24 bit integer whose range
+/// is 0-16777215), pads it with zeroes (so its 12 characters long), and
+/// then turns it into a MAC address for the purpose of having a consistent
+/// SVI MAC address value for all DPUs in a given VPC.
+///
+/// e.g, an L2VNI of 1637817 would result in an SviMAC of 00:00:01:63:78:17
+/// for all DPUs in the VPC.
+fn vni_to_svi_mac(vni: u32) -> eyre::Result<MacAddress> {
+    sanitized_mac(&format!("{vni:012}"))
+}
+
+#[derive(Clone, Deserialize, Debug)]
+pub struct RouteTargetConfig {
+    pub asn: u32,
+    pub vni: u32,
+}
+
+// What we need to configure NVUE
+pub struct NvueConfig {
+    pub is_fnn: bool,
+    pub is_dpu_os: bool,
+    /// In containerized mode, the vlan ID of the pf0hpf-facing SVI that should
+    /// carry 169.254.169.253/30 as the FMDS gateway address.
+    pub fmds_gateway_vlan: Option<u16>,
+    pub vpc_virtualization_type: VpcVirtualizationType,
+    pub use_admin_network: bool,
+    pub tenancy_enabled: bool,
+    pub loopback_ip: String,
+    pub asn: u32,
+    pub datacenter_asn: u32,
+    pub site_global_vpc_vni: Option<u32>,
+    pub common_internal_route_target: Option<RouteTargetConfig>,
+    pub additional_route_target_imports: Vec<RouteTargetConfig>,
+    pub bgp_leaf_session_password: Option<String>,
+
+    pub secondary_overlay_vtep_ip: Option<String>,
+    pub vf_intercept_bridge_port_name: Option<String>,
+    pub vf_intercept_bridge_sf: Option<String>,
+    pub host_intercept_bridge_port_name: Option<String>,
+    pub internal_bridge_routing_prefix: Option<String>,
+    pub traffic_intercept_public_prefixes: Vec<String>,
+
+    pub dpu_hostname: String,
+    pub dpu_search_domain: String,
+    pub hbn_version: Option<String>,
+    pub uplinks: Vec<String>,
+    pub route_servers: Vec<String>,
+    pub dhcp_servers: Vec<String>,
+    pub l3_domains: Vec<L3Domain>,
+    pub deny_prefixes: Vec<String>,
+    pub site_fabric_prefixes: Vec<String>,
+    pub anycast_site_prefixes: Vec<String>,
+    pub tenant_host_asn: Option<u32>,
+    pub use_vpc_isolation: bool,
+    pub stateful_acls_enabled: bool,
+
+    pub network_security_groups: Vec<NetworkSecurityGroup>,
+
+    pub network_security_policy_override_rules: Vec<NetworkSecurityGroupRule>,
+
+    // Currently we have a single tenant, hence the single ct_ prefix.
+    // Later this will be Vec<ComputeTenant>.
+
+    // ct_vrf_name is the VRF name. This value needs to be 15 characters
+    // or less, somehow derived from the VPC, and is the same for all
+    // DPUs in a VPC. To achieve this, we currently take the L3VNI of the
+    // VPC, and assign this as "vrf_<l3vni>". This ensures we keep the
+    // character count below 15, and by using the L3VNI, we're able to
+    // directly correlate that back to the VPC.
+    pub ct_vrf_name: String,
+    pub ct_l3_vni: Option<u32>,
+    pub ct_vrf_loopback: String,
+    pub ct_port_configs: Vec<PortConfig>,
+    pub ct_access_vlans: Vec<VlanConfig>,
+    pub ct_routing_profile: Option<RoutingProfile>,
+}
+
+#[derive(Clone, Deserialize, Debug)]
+pub struct RoutingProfile {
+    pub leak_default_route_from_underlay: bool,
+    pub leak_tenant_host_routes_to_underlay: bool,
+    pub route_target_imports: Vec<RouteTargetConfig>,
+    pub route_targets_on_exports: Vec<RouteTargetConfig>,
+    pub tenant_leak_communities_accepted: bool,
+}
+
+#[derive(Clone, Deserialize, Debug)]
+pub struct NetworkSecurityGroup {
+    pub id: String,
+    pub stateful_egress: bool,
+    pub rules: Vec<NetworkSecurityGroupRule>,
+}
+
+#[derive(Clone, Deserialize, Debug)]
+pub struct NetworkSecurityGroupRule {
+    pub id: String,
+    pub ingress: bool,
+    pub ipv6: bool,
+    pub priority: u32,
+    pub src_port_start: Option<u32>,
+    pub src_port_end: Option<u32>,
+    pub dst_port_start: Option<u32>,
+    pub dst_port_end: Option<u32>,
+    pub can_match_any_protocol: bool,
+    pub can_be_stateful: bool,
+    pub protocol: String,
+    pub action: String,
+    pub src_prefixes: Vec<String>,
+    pub dst_prefixes: Vec<String>,
+}
+
+impl TryFrom<&rpc::ResolvedNetworkSecurityGroupRule> for NetworkSecurityGroupRule {
+    type Error = eyre::Error;
+
+    fn try_from(
+        resolved_rule: &rpc::ResolvedNetworkSecurityGroupRule,
+    ) -> Result<Self, Self::Error> {
+        let Some(ref rule) = resolved_rule.rule else {
+            return Err(eyre::eyre!("BUG: attempting to convert empty NSG rule"));
+        };
+
+        Ok(NetworkSecurityGroupRule {
+            id: rule.id.clone().unwrap_or_default(),
+            ingress: rule.direction()
+                == rpc::NetworkSecurityGroupRuleDirection::NsgRuleDirectionIngress,
+            can_match_any_protocol: rule.protocol()
+                == rpc::NetworkSecurityGroupRuleProtocol::NsgRuleProtoAny,
+            // We'll only automatically handle stateful tracking for egress rules
+            // that specify TCP/UDP, a dst port, and NO src port because it becomes
+            // extremely difficult for users to get rule combinations for common
+            // use-cases if the stateful option isn't narrowly implemented.
+            // ICMP is _technically_ documented as valid for stateful tracking,
+            // but it's not throroughly tested/validated, and the user would have
+            // no way to achieve non-stateful ICMP if stateful is enabled for the
+            // NSG, so it's being left out.
+            can_be_stateful: rule.direction()
+                == rpc::NetworkSecurityGroupRuleDirection::NsgRuleDirectionEgress
+                && matches!(
+                    rule.protocol(),
+                    rpc::NetworkSecurityGroupRuleProtocol::NsgRuleProtoTcp
+                        | rpc::NetworkSecurityGroupRuleProtocol::NsgRuleProtoUdp
+                )
+                && rule.dst_port_start.is_some()
+                && rule.src_port_start.is_none(),
+            ipv6: rule.ipv6,
+            priority: rule.priority,
+            src_port_start: rule.src_port_start,
+            src_port_end: rule.src_port_end,
+            dst_port_start: rule.dst_port_start,
+            dst_port_end: rule.dst_port_end,
+            protocol: rpc::NetworkSecurityGroupRuleProtocol::to_string_from_enum_i32(
+                rule.protocol,
+            )?
+            .to_lowercase(),
+            action: rpc::NetworkSecurityGroupRuleAction::to_string_from_enum_i32(rule.action)?
+                .to_lowercase(),
+            src_prefixes: resolved_rule.src_prefixes.clone(),
+            dst_prefixes: resolved_rule.dst_prefixes.clone(),
+        })
+    }
+}
+
+pub struct VlanConfig {
+    pub vlan_id: u32,
+    pub network: String,
+    pub ip: String,
+    pub ipv6_vlan_config: Option<Ipv6VlanConfig>,
+}
+
+#[derive(Clone, Deserialize, Debug)]
+pub struct Ipv6VlanConfig {
+    pub network: String,
+    pub ip: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct L3Domain {
+    pub l3_domain_name: String,
+    pub services: Vec<String>,
+}
+
+/// IPv6 configuration for a port.
+#[derive(Clone, Deserialize, Debug)]
+pub struct Ipv6PortConfig {
+    /// DPU-side IPv6 address in CIDR notation (e.g. "2001:db8::0/127").
+    /// For FNN L3 linknets, this is the ::0 end of the /127 (RFC 6164).
+    pub gateway_cidr: String,
+    /// SVI IP for L2 segments -- the DPU's gateway address on the VLAN.
+    pub svi_ip: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct PortConfig {
+    pub interface_name: String,
+    pub vlan: u16,
+    pub vni: Option<u32>, // In FNN, admin network has both an l2vni and an l3vni
+    pub l3_vni: Option<u32>,
+    pub gateway_cidr: String,
+    /// Optional IPv6 configuration for dual-stack interfaces.
+    pub ipv6_port_config: Option<Ipv6PortConfig>,
+    pub vpc_prefixes: Vec<String>,
+    pub vpc_peer_prefixes: Vec<String>,
+    pub vpc_peer_vnis: Vec<u32>,
+    /// SVI IP for L2 segments -- the DPU's gateway address on the VLAN (IPv4).
+    pub svi_ip: Option<String>,
+    pub tenant_vrf_loopback_ip: Option<String>,
+    pub is_l2_segment: bool,
+    pub is_phy: bool,
+    pub network_security_group_id: Option<String>,
+}
+
+//
+// Go template objects, hence allow(non_snake_case)
+//
+
+#[allow(non_snake_case)]
+#[derive(Clone, Gtmpl, Debug)]
+struct TmplNvue {
+    UseAdminNetwork: bool, // akak service network
+    HasSiteGlobalVpcVni: bool,
+    SiteGlobalVpcVni: u32,
+    LoopbackIP: String,
+    HasSecondaryOverlayVTEP: bool,
+    HasStaticAdvertisements: bool,
+    SecondaryOverlayVtepIP: String,
+    HasInternalBridgeRouting: bool,
+    /// This IP is used in a static route in NVUE
+    /// to send traffic over to a bridge used by a traffic
+    /// intercept user for further processing.
+    VfInterceptBridgeIP: String,
+    /// This _might_ be the same IP as VfInterceptBridgeIP,
+    /// or it might not.  See the details of VfInterceptBridgeIP.
+    PublicPrefixInternalNextHop: String,
+    /// An IP from the same subnet as VfInterceptBridgeIP
+    VfInterceptHbnRepresentorIp: String,
+    /// The SF used to route traffic VF traffic to the HBN pod.
+    VfInterceptBridgeSf: String,
+
+    /// Does any VPC at all have a routing profile that says
+    /// tenant routes should leak to the underlay?
+    HasAnyVpcTenantHostLeakToUnderlay: bool,
+
+    /// The size of the of the prefix used for the internal
+    /// bridge routing.
+    InterceptBridgePrefixLen: u8,
+
+    TrafficInterceptPublicPrefixes: Vec<Prefix>,
+    TrafficInterceptPublicPrefixesIpv6: Vec<Prefix>,
+
+    ASN: u32,
+    DatacenterASN: u32,
+    UseCommonInternalTenantRouteTarget: bool,
+    CommonInternalRouteTarget: Option<TmplRouteTargetConfig>,
+    AdditionalRouteTargetImports: Vec<TmplRouteTargetConfig>,
+
+    DPUHostname: String,  // The first part of the FQDN
+    SearchDomain: String, // The rest of the FQDN
+    Uplinks: Vec<String>,
+    RouteServers: Vec<String>,
+
+    /// Format: IPv4 address of (per tenant) dhcp server
+    DHCPServers: Vec<String>, // Previously 'Servers'
+
+    /// Format: CIDR of the infastructure prefixes to block. Origin is carbide-api config file.
+    DenyPrefixes: Vec<Prefix>,
+    DenyPrefixesIpv6: Vec<Prefix>,
+
+    HasDenyPrefixes: bool,
+    HasDenyPrefixesIpv6: bool,
+
+    /// Format: CIDR of the site prefixes for tenant use.  If VPC isolation is applied,
+    /// and there is no network security group applied overriding the behavior,
+    /// these will be blocked as well.
+    SiteFabricPrefixes: Vec<Prefix>,
+    SiteFabricPrefixesIpv6: Vec<Prefix>,
+
+    HasSiteFabricPrefixes: bool,
+    HasSiteFabricPrefixesIpv6: bool,
+
+    /// Format: CIDR of the site prefixes that tenants are allowed to
+    /// from the host to the DPU.
+    AnycastSitePrefixes: Vec<Prefix>,
+    AnycastSitePrefixesIpv6: Vec<Prefix>,
+
+    // Whether VPC-isolation should be applied.
+    UseVpcIsolation: bool,
+
+    HbnVersion: Option<String>,
+
+    /// Whether stateful ACLs are possible and we
+    /// should perform any extra config to prepare
+    /// for them.
+    StatefulAclsEnabled: bool,
+
+    /// Whether there are global policies that should be evaluated
+    /// after deny prefixes but before any tenant-defined rules.
+    HasIpv4IngressSecurityPolicyOverrideRules: bool,
+    HasIpv4EgressSecurityPolicyOverrideRules: bool,
+    HasIpv6IngressSecurityPolicyOverrideRules: bool,
+    HasIpv6EgressSecurityPolicyOverrideRules: bool,
+    Ipv4IngressNetworkSecurityPolicyOverrideRules: Vec<TmplNetworkSecurityGroupRule>,
+    Ipv4EgressNetworkSecurityPolicyOverrideRules: Vec<TmplNetworkSecurityGroupRule>,
+    Ipv6IngressNetworkSecurityPolicyOverrideRules: Vec<TmplNetworkSecurityGroupRule>,
+    Ipv6EgressNetworkSecurityPolicyOverrideRules: Vec<TmplNetworkSecurityGroupRule>,
+
+    /// For when we have more than one tenant
+    Tenant: TmplComputeTenant,
+
+    // XXX: These are unused placeholders for later.
+    // StorageDpuIP is an interface that should exist on
+    // client nodes that are NOT storage targets, so in the
+    // case where StorageTarget is false, we would expect
+    // there to be a StorageDpuIP.
+    IsStorageClient: bool,    // XXX (Classic, L3)
+    StorageDpuIP: String,     // XXX (Classic, L3)
+    l3vnistorageVLAN: String, // XXX (Classic, L3)
+    StorageL3VNI: u32,        // XXX (Classic, L3)
+    StorageLoopback: String,  // XXX (Classic, L3)
+    DPUstorageprefix: String, // XXX (Classic, L3)
+    IncludeBridge: bool,
+    IsDpuOs: bool,
+
+    HasBgpLeafSessionPassword: bool,
+    /// A password to use for the BGP session with the
+    /// leaf TOR.
+    BgpLeafSessionPassword: String,
+}
+
+#[allow(non_snake_case)]
+#[derive(Clone, Gtmpl, Debug)]
+struct TmplRouteTargetConfig {
+    ASN: u32,
+    VNI: u32,
+}
+
+/// Template-ready representation of a network security group rule.
+/// Direction (ingress/egress), ipv (4/6), and priority
+/// ordering will be grouped and ordered in advance.
+/// Priority is still included mostly as a convenience,
+/// but we'll also pad the value to a minimum of 100
+/// so that there's room for low-priority "system rules"
+/// to be inserted if needed.
+#[allow(non_snake_case)]
+#[derive(Clone, Gtmpl, Debug)]
+struct TmplNetworkSecurityGroupRule {
+    Id: String,
+    HasSrcPort: bool,
+    SrcPort: u32,
+    HasDstPort: bool,
+    DstPort: u32,
+    CanMatchAnyProtocol: bool,
+    CanBeStateful: bool,
+    Protocol: String,
+    Action: String,
+    SrcPrefix: String,
+    DstPrefix: String,
+    Priority: u32,
+    OriginalPriority: u32,
+}
+
+#[allow(non_snake_case)]
+#[derive(Clone, Gtmpl, Debug)]
+struct TmplRoutingProfile {
+    LeakTenantHostRoutesToUnderlay: bool,
+    LeakDefaultRouteFromUnderlay: bool,
+    RouteTargetImports: Vec<TmplRouteTargetConfig>,
+    RouteTargetsOnExports: Vec<TmplRouteTargetConfig>,
+    TenantLeakCommunitiesAccepted: bool,
+}
+
+#[allow(non_snake_case)]
+#[derive(Clone, Gtmpl, Debug)]
+struct TmplComputeTenant {
+    Vpcs: Vec<TmplVpc>,
+    PortConfigs: Vec<TmplConfigPort>,
+    NetworkSecurityGroups: Vec<TmplNetworkSecurityGroup>,
+
+    // TODO:  Everything thing from here down should remain for pre-FNN purposes,
+    //        but they now live in the Vecs above to support multiple VPCs per DPU.
+    //
+    /// Tenant name/id with a max of 15 chars, because it's also used for the interface name.
+    /// Linux is limited to 15 chars for interface names.
+    VrfName: String,
+
+    /// L3VNI VPC-specifc VNI, which is globally unique. GNI allocates us
+    /// a pool of VNIs to assign as we see fit, so we carve out blocks
+    /// per-site, and then manage them via the VPC_VNI (vpc-vni) resource
+    /// pool.
+    // TODO(chet): Does this need to be a string?
+    L3VNI: String,
+
+    HasHostASN: bool,
+    /// An ASN allocated for tenants to use
+    /// when they peer with the DPU.
+    /// If configured, the DPU will expect the host
+    /// to peer with this ASN.  If left unset
+    /// remote-as external will be used, allowing
+    /// any ASN.
+    HostASN: u32,
+
+    HostInterfaces: Vec<TmplHostInterfaces>,
+
+    HasVpcPeerPrefixes: bool,
+    VpcPeerPrefixes: Vec<Prefix>,
+    HasVpcPeerPrefixesIpv6: bool,
+    VpcPeerPrefixesIpv6: Vec<Prefix>,
+
+    HasVpcPeerVnis: bool,
+    VpcPeerVnis: Vec<TmplVni>,
+
+    RoutingProfile: Option<TmplRoutingProfile>,
+
+    HasNetworkSecurityGroup: bool,
+    IngressNetworkSecurityGroupRulesIpv4: Vec<TmplNetworkSecurityGroupRule>,
+    IngressNetworkSecurityGroupRulesIpv6: Vec<TmplNetworkSecurityGroupRule>,
+    EgressNetworkSecurityGroupRulesIpv4: Vec<TmplNetworkSecurityGroupRule>,
+    EgressNetworkSecurityGroupRulesIpv6: Vec<TmplNetworkSecurityGroupRule>,
+    HasIpv4IngressSecurityGroupRules: bool,
+    HasIpv4EgressSecurityGroupRules: bool,
+    HasIpv6IngressSecurityGroupRules: bool,
+    HasIpv6EgressSecurityGroupRules: bool,
+    // /////////////
+}
+
+#[allow(non_snake_case)]
+#[derive(Clone, Gtmpl, Debug)]
+struct TmplNetworkSecurityGroup {
+    Index: u16,
+    StatefulEgress: bool,
+    IngressNetworkSecurityGroupRulesIpv4: Vec<TmplNetworkSecurityGroupRule>,
+    IngressNetworkSecurityGroupRulesIpv6: Vec<TmplNetworkSecurityGroupRule>,
+    EgressNetworkSecurityGroupRulesIpv4: Vec<TmplNetworkSecurityGroupRule>,
+    EgressNetworkSecurityGroupRulesIpv6: Vec<TmplNetworkSecurityGroupRule>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Clone, Gtmpl, Debug)]
+struct TmplVpc {
+    /// Tenant name/id with a max of 15 chars, because it's also used for the interface name.
+    /// Linux is limited to 15 chars for interface names.
+    VrfName: String,
+
+    /// VPC-specifc VNI, which must be unique within a given site but
+    /// not necessarily globally unique.
+    L3VNI: u32,
+
+    // VrfLoopback is the tenant loopback IP assigned to each DPU.
+    // It was originally expected to be allocated from an interface-specific
+    // /30 (as the first IP in the allocation), but it's actually allocated
+    // from a dedicated resource-pool, handed out as un-related /32s, and
+    // interfaces in FNN get /31s.
+    /// The tenant loopback IP assigned to each DPU.
+    VrfLoopback: String,
+
+    HostInterfaces: Vec<TmplHostInterfaces>,
+    PortConfigs: Vec<TmplConfigPort>,
+
+    HasVpcPeerPrefixes: bool,
+    VpcPeerPrefixes: Vec<Prefix>,
+    HasVpcPeerPrefixesIpv6: bool,
+    VpcPeerPrefixesIpv6: Vec<Prefix>,
+
+    // The relationship between interface:VPC is 1:1 but VPC:interface is 1:M.
+    // So, a single VPC could have multiple, per-port, VpcPrefixes.  We can
+    // accumulate these and pass them into the template for ease-of-use.
+    /// The list of prefixes for all ports/interfaces that belong to this VPC.
+    PortPrefixes: Vec<Prefix>,
+    PortPrefixesIpv6: Vec<Prefix>,
+
+    HasVpcPeerVnis: bool,
+    VpcPeerVnis: Vec<TmplVni>,
+
+    RoutingProfile: Option<TmplRoutingProfile>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Clone, Debug, Gtmpl)]
+struct TmplHostInterfaces {
+    ID: u32,
+    HostIP: String,
+    /// IPv6 host address (if dual-stack).
+    HostIPv6: Option<String>,
+
+    // HostRoute in the context of FNN-L3 is the /30 prefix allocation.
+    // This used to be populated as the HostIP + "/32", but then with
+    // the advent of interface prefix allocations (where ETV is just a /32,
+    // and FNN-L3 is a /31), HostRoute became the allocation (which was
+    // a drop-in replacement for ETV/Classic environments).
+    HostRoute: String,
+}
+
+#[allow(non_snake_case)]
+#[derive(Clone, Gtmpl, Debug)]
+struct TmplConfigPort {
+    InterfaceName: String,
+    Index: String,
+    VlanID: u16,
+
+    /// Format: 24bit integer (usable range: 4096 to 16777215).
+    /// Empty string if no tenant
+    L2VNI: String, // Previously called VNIDevice
+    IPs: Vec<String>, // with mask, 1.1.1.1/20
+
+    /// In a symmetrical EVPN configuration, an SVI (vlan interfaces) requires a separate IP that
+    /// is not the gateway address. Typically the 2nd usable ip in the prefix is being used,
+    /// e.g 10.1.1.2 in the 10.1.1.0/24 prefix.
+    /// Format: Standard IPv4 notation
+    SviIPs: Vec<String>,
+
+    /// VRR, the distributed gateway, needs a manually defined MAC address. This can be overlapping
+    /// on the different VTEPs, but it is very convenient to be unique on the same VTEP.
+    ///
+    /// In other words, this is the same value for all DPUs in a given VPC.
+    ///
+    /// TO MAKE THIS THE SAME FOR A GIVEN VPC, we take the L2VNI (which is a 24bit integer),
+    /// pad it with zeroes so its 12 characters long, and then shove some colons in there.
+    ///
+    /// For example, for a VPC with an L2VNI of 1683714, the SviMAC would
+    /// be configured as 00:00:01:68:37:14.
+    ///
+    /// Format: 48bit mac address in standard hex notation, e.g: 00:00:00:00:00:10
+    SviMAC: String,
+
+    /// The name of the VRF this interface belongs to.
+    VrfName: String,
+
+    HasVpcPrefixes: bool,
+
+    /// Tenant VPCs we should allow them to access
+    VpcPrefixes: Vec<Prefix>,
+
+    // XXX: all of these added so the L3 template can build, need
+    // to really actually wire them up.
+    StorageTarget: bool, // XXX (Classic, L3)
+
+    // does this segment support L2?
+    IsL2Segment: bool,
+
+    IsPhy: bool,
+
+    HasVpcPeerPrefixes: bool,
+    HasVpcPrefixesIpv6: bool,
+    VpcPrefixesIpv6: Vec<Prefix>,
+    HasVpcPeerPrefixesIpv6: bool,
+
+    HasNetworkSecurityGroup: bool,
+    NetworkSecurityGroupIndex: Option<u16>,
+    HasFmdsGateway: bool,
+}
+
+#[allow(non_snake_case)]
+#[derive(Clone, Gtmpl, Debug)]
+struct Prefix {
+    Index: String,
+    Prefix: String,
+}
+
+#[allow(non_snake_case)]
+#[derive(Clone, Gtmpl, Debug)]
+struct TmplVni {
+    Vni: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_prefixes_by_family() {
+        let prefixes = vec![
+            "10.0.0.0/8".to_string(),
+            "2001:db8::/32".to_string(),
+            "192.168.0.0/16".to_string(),
+            "fd00::/8".to_string(),
+        ];
+        let (ipv4, ipv6) = split_prefixes_by_family(&prefixes, 1000);
+
+        assert_eq!(ipv4.len(), 2);
+        assert_eq!(ipv6.len(), 2);
+
+        assert_eq!(ipv4[0].Index, "1000");
+        assert_eq!(ipv4[0].Prefix, "10.0.0.0/8");
+        assert_eq!(ipv4[1].Index, "1001");
+        assert_eq!(ipv4[1].Prefix, "192.168.0.0/16");
+
+        assert_eq!(ipv6[0].Index, "1000");
+        assert_eq!(ipv6[0].Prefix, "2001:db8::/32");
+        assert_eq!(ipv6[1].Index, "1001");
+        assert_eq!(ipv6[1].Prefix, "fd00::/8");
+    }
+
+    #[test]
+    fn test_split_prefixes_ipv4_only() {
+        let prefixes = vec!["10.0.0.0/8".to_string(), "172.16.0.0/12".to_string()];
+        let (ipv4, ipv6) = split_prefixes_by_family(&prefixes, 1);
+
+        assert_eq!(ipv4.len(), 2);
+        assert!(ipv6.is_empty());
+    }
+
+    #[test]
+    fn test_split_prefixes_ipv6_only() {
+        let prefixes = vec!["2001:db8::/32".to_string(), "fd00::/8".to_string()];
+        let (ipv4, ipv6) = split_prefixes_by_family(&prefixes, 1);
+
+        assert!(ipv4.is_empty());
+        assert_eq!(ipv6.len(), 2);
+    }
+
+    #[test]
+    fn test_split_prefixes_empty() {
+        let prefixes: Vec<String> = vec![];
+        let (ipv4, ipv6) = split_prefixes_by_family(&prefixes, 1000);
+
+        assert!(ipv4.is_empty());
+        assert!(ipv6.is_empty());
+    }
+
+    #[test]
+    fn test_split_prefixes_unparseable_dropped() {
+        let prefixes = vec!["not-a-cidr".to_string(), "10.0.0.0/8".to_string()];
+        let (ipv4, ipv6) = split_prefixes_by_family(&prefixes, 1);
+
+        assert_eq!(ipv4.len(), 1);
+        assert_eq!(ipv4[0].Prefix, "10.0.0.0/8");
+        assert_eq!(ipv4[0].Index, "1");
+        assert!(ipv6.is_empty());
+    }
+
+    #[test]
+    fn test_split_prefixes_ipv4_mapped_ipv6() {
+        // IPv4-mapped IPv6 addresses (::ffff:x.x.x.x) parse as V6
+        let prefixes = vec![
+            "::ffff:192.0.2.33/128".to_string(),
+            "10.0.0.0/8".to_string(),
+            "2001:db8::/32".to_string(),
+        ];
+        let (ipv4, ipv6) = split_prefixes_by_family(&prefixes, 1);
+
+        assert_eq!(ipv4.len(), 1);
+        assert_eq!(ipv4[0].Prefix, "10.0.0.0/8");
+
+        assert_eq!(ipv6.len(), 2);
+        assert_eq!(ipv6[0].Prefix, "::ffff:192.0.2.33/128");
+        assert_eq!(ipv6[1].Prefix, "2001:db8::/32");
+    }
+
+    /// Helper to build a minimal NvueConfig for template rendering tests.
+    /// Uses EthernetVirtualizer (ETV) by default.
+    fn minimal_nvue_config() -> NvueConfig {
+        NvueConfig {
+            bgp_leaf_session_password: None,
+            is_fnn: false,
+            is_dpu_os: true,
+            fmds_gateway_vlan: None,
+            vpc_virtualization_type: VpcVirtualizationType::EthernetVirtualizer,
+            use_admin_network: false,
+            tenancy_enabled: true,
+            loopback_ip: "10.0.0.1".to_string(),
+            asn: 65000,
+            datacenter_asn: 11414,
+            site_global_vpc_vni: None,
+            common_internal_route_target: None,
+            additional_route_target_imports: vec![],
+            secondary_overlay_vtep_ip: None,
+            vf_intercept_bridge_port_name: None,
+            vf_intercept_bridge_sf: None,
+            host_intercept_bridge_port_name: None,
+            internal_bridge_routing_prefix: None,
+            traffic_intercept_public_prefixes: vec![],
+            dpu_hostname: "test-dpu".to_string(),
+            dpu_search_domain: "test.local".to_string(),
+            hbn_version: None,
+            uplinks: vec!["p0_if".to_string()],
+            route_servers: vec![],
+            dhcp_servers: vec![],
+            l3_domains: vec![],
+            deny_prefixes: vec![],
+            site_fabric_prefixes: vec![],
+            anycast_site_prefixes: vec![],
+            tenant_host_asn: Some(65100),
+            use_vpc_isolation: false,
+            stateful_acls_enabled: false,
+            network_security_groups: vec![],
+            network_security_policy_override_rules: vec![],
+            ct_vrf_name: "vpc_100".to_string(),
+            ct_l3_vni: Some(100),
+            ct_vrf_loopback: "10.0.0.2".to_string(),
+            ct_port_configs: vec![],
+            ct_access_vlans: vec![],
+            ct_routing_profile: None,
+        }
+    }
+
+    #[test]
+    fn test_template_for_etv() {
+        assert!(template_for(VpcVirtualizationType::EthernetVirtualizer).is_ok());
+    }
+
+    #[test]
+    fn test_template_for_etv_with_nvue() {
+        // EthernetVirtualizerWithNvue is the same as EthernetVirtualizer
+        // now, and while we shouldn't see it coming through anymore, lets
+        // still make sure we support it.
+        assert!(template_for(VpcVirtualizationType::EthernetVirtualizerWithNvue).is_ok());
+    }
+
+    #[test]
+    fn test_template_for_fnn() {
+        assert!(template_for(VpcVirtualizationType::Fnn).is_ok());
+    }
+
+    /// Helper to compare build() output against a golden file, using the same
+    /// diff-based comparison as the ethernet_virtualization tests.
+    fn assert_build_matches_golden(conf: NvueConfig, golden_file: &str) {
+        let output = build(conf).expect("build should succeed");
+        let expected = golden_file;
+        if output != expected {
+            let report: String = diff::lines(&output, expected)
+                .into_iter()
+                .map(|line| match line {
+                    diff::Result::Both(line, _) => format!(" {line}\n"),
+                    diff::Result::Left(line) => format!("-{line}\n"),
+                    diff::Result::Right(line) => format!("+{line}\n"),
+                })
+                .collect();
+            eprintln!("Golden file diff:\n{report}");
+            panic!("build output does not match golden file");
+        }
+    }
+
+    #[test]
+    fn test_build_accepts_ethernet_virtualizer() {
+        let mut conf = minimal_nvue_config();
+        conf.vpc_virtualization_type = VpcVirtualizationType::EthernetVirtualizer;
+        assert!(build(conf).is_ok());
+    }
+
+    #[test]
+    fn test_build_etv_no_ipv6_acls_even_with_ipv6_data() {
+        // ETV templates do not support IPv6 ACLs. Even when IPv6 prefix data
+        // is provided, the ETV template should not emit any IPv6 ACL blocks.
+        let mut conf = minimal_nvue_config();
+        conf.use_vpc_isolation = true;
+        conf.deny_prefixes = vec!["192.0.2.0/24".into(), "2001:db8:dead::/48".into()];
+        conf.site_fabric_prefixes = vec!["10.0.0.0/16".into(), "fd00::/48".into()];
+        conf.ct_port_configs = vec![PortConfig {
+            interface_name: "pf0vf0_if".into(),
+            vlan: 100,
+            vni: Some(1000),
+            l3_vni: Some(100),
+            gateway_cidr: "10.0.1.1/24".into(),
+            vpc_prefixes: vec!["10.0.1.0/24".into(), "2001:db8:1::/48".into()],
+            vpc_peer_prefixes: vec!["10.0.2.0/24".into(), "2001:db8:2::/48".into()],
+            vpc_peer_vnis: vec![],
+            svi_ip: None,
+            tenant_vrf_loopback_ip: None,
+            is_l2_segment: true,
+            is_phy: false,
+            network_security_group_id: None,
+            ipv6_port_config: None,
+        }];
+        conf.ct_access_vlans = vec![VlanConfig {
+            vlan_id: 100,
+            network: "10.0.1.0/24".into(),
+            ip: "10.0.1.2".into(),
+            ipv6_vlan_config: None,
+        }];
+        assert_build_matches_golden(
+            conf,
+            include_str!("../templates/tests/nvue_build_etv_ipv6_data.yaml.expected"),
+        );
+    }
+
+    #[test]
+    fn test_build_fnn_ipv6_acls() {
+        let mut conf = minimal_nvue_config();
+        conf.is_fnn = true;
+        conf.vpc_virtualization_type = VpcVirtualizationType::Fnn;
+        conf.use_vpc_isolation = true;
+        conf.deny_prefixes = vec!["192.0.2.0/24".into(), "2001:db8:bad::/48".into()];
+        conf.site_fabric_prefixes = vec!["10.0.0.0/16".into(), "fd00:abcd::/32".into()];
+        conf.ct_routing_profile = Some(RoutingProfile {
+            tenant_leak_communities_accepted: false,
+            leak_default_route_from_underlay: false,
+            leak_tenant_host_routes_to_underlay: false,
+            route_target_imports: vec![],
+            route_targets_on_exports: vec![],
+        });
+        conf.ct_port_configs = vec![PortConfig {
+            interface_name: "pf0vf0_if".into(),
+            vlan: 100,
+            vni: Some(1000),
+            l3_vni: Some(100),
+            gateway_cidr: "10.0.1.1/24".into(),
+            vpc_prefixes: vec!["10.0.1.0/24".into(), "2001:db8:1::/48".into()],
+            vpc_peer_prefixes: vec!["10.0.2.0/24".into(), "2001:db8:2::/48".into()],
+            vpc_peer_vnis: vec![],
+            svi_ip: Some("10.0.1.254".into()),
+            tenant_vrf_loopback_ip: Some("10.0.0.2".into()),
+            is_l2_segment: false,
+            is_phy: false,
+            network_security_group_id: None,
+            ipv6_port_config: None,
+        }];
+        conf.ct_access_vlans = vec![VlanConfig {
+            vlan_id: 100,
+            network: "10.0.1.0/24".into(),
+            ip: "10.0.1.2".into(),
+            ipv6_vlan_config: None,
+        }];
+        assert_build_matches_golden(
+            conf,
+            include_str!("../templates/tests/nvue_build_fnn_ipv6_acls.yaml.expected"),
+        );
+    }
+
+    #[test]
+    fn test_build_ipv4_only_no_ipv6_acls() {
+        let mut conf = minimal_nvue_config();
+        conf.use_vpc_isolation = true;
+        conf.deny_prefixes = vec!["192.0.2.0/24".into()];
+        conf.site_fabric_prefixes = vec!["10.0.0.0/16".into()];
+        conf.ct_port_configs = vec![PortConfig {
+            interface_name: "pf0vf0_if".into(),
+            vlan: 100,
+            vni: Some(1000),
+            l3_vni: Some(100),
+            gateway_cidr: "10.0.1.1/24".into(),
+            vpc_prefixes: vec!["10.0.1.0/24".into()],
+            vpc_peer_prefixes: vec!["10.0.2.0/24".into()],
+            vpc_peer_vnis: vec![],
+            svi_ip: None,
+            tenant_vrf_loopback_ip: None,
+            is_l2_segment: true,
+            is_phy: false,
+            network_security_group_id: None,
+            ipv6_port_config: None,
+        }];
+        conf.ct_access_vlans = vec![VlanConfig {
+            vlan_id: 100,
+            network: "10.0.1.0/24".into(),
+            ip: "10.0.1.2".into(),
+            ipv6_vlan_config: None,
+        }];
+        assert_build_matches_golden(
+            conf,
+            include_str!("../templates/tests/nvue_build_etv_ipv4_only.yaml.expected"),
+        );
+    }
+
+    #[test]
+    fn test_build_fnn_ipv6_only_vpc_prefixes() {
+        // When vpc_prefixes contains only IPv6 entries, HasVpcPrefixes (IPv4)
+        // should be false, and HasVpcPrefixesIpv6 should be true.
+        let mut conf = minimal_nvue_config();
+        conf.is_fnn = true;
+        conf.vpc_virtualization_type = VpcVirtualizationType::Fnn;
+        conf.use_vpc_isolation = true;
+        conf.deny_prefixes = vec!["192.0.2.0/24".into()];
+        conf.site_fabric_prefixes = vec!["10.0.0.0/16".into(), "fd00::/48".into()];
+        conf.ct_routing_profile = Some(RoutingProfile {
+            tenant_leak_communities_accepted: false,
+            leak_default_route_from_underlay: false,
+            leak_tenant_host_routes_to_underlay: false,
+
+            route_target_imports: vec![],
+            route_targets_on_exports: vec![],
+        });
+        conf.ct_port_configs = vec![PortConfig {
+            interface_name: "pf0vf0_if".into(),
+            vlan: 100,
+            vni: Some(1000),
+            l3_vni: Some(100),
+            gateway_cidr: "10.0.1.1/24".into(),
+            vpc_prefixes: vec!["2001:db8:1::/48".into(), "2001:db8:2::/48".into()],
+            vpc_peer_prefixes: vec![],
+            vpc_peer_vnis: vec![],
+            svi_ip: Some("10.0.1.254".into()),
+            tenant_vrf_loopback_ip: Some("10.0.0.2".into()),
+            is_l2_segment: false,
+            is_phy: false,
+            network_security_group_id: None,
+            ipv6_port_config: None,
+        }];
+        conf.ct_access_vlans = vec![VlanConfig {
+            vlan_id: 100,
+            network: "10.0.1.0/24".into(),
+            ip: "10.0.1.2".into(),
+            ipv6_vlan_config: None,
+        }];
+        assert_build_matches_golden(
+            conf,
+            include_str!("../templates/tests/nvue_build_fnn_ipv6_only_vpc.yaml.expected"),
+        );
+    }
+
+    #[test]
+    fn test_build_deny_prefix_index_offset() {
+        // When site_fabric_prefixes has entries, deny prefix indices should
+        // start after them (offset by site_fabric_prefixes.len()).
+        let mut conf = minimal_nvue_config();
+        conf.deny_prefixes = vec!["192.0.2.0/24".into(), "2001:db8:bad::/48".into()];
+        conf.site_fabric_prefixes = vec![
+            "10.0.0.0/16".into(),
+            "172.16.0.0/12".into(),
+            "fd00::/48".into(),
+        ];
+        conf.ct_port_configs = vec![PortConfig {
+            interface_name: "pf0vf0_if".into(),
+            vlan: 100,
+            vni: Some(1000),
+            l3_vni: Some(100),
+            gateway_cidr: "10.0.1.1/24".into(),
+            vpc_prefixes: vec!["10.0.1.0/24".into()],
+            vpc_peer_prefixes: vec![],
+            vpc_peer_vnis: vec![],
+            svi_ip: None,
+            tenant_vrf_loopback_ip: None,
+            is_l2_segment: true,
+            is_phy: false,
+            network_security_group_id: None,
+            ipv6_port_config: None,
+        }];
+        conf.ct_access_vlans = vec![VlanConfig {
+            vlan_id: 100,
+            network: "10.0.1.0/24".into(),
+            ip: "10.0.1.2".into(),
+            ipv6_vlan_config: None,
+        }];
+        assert_build_matches_golden(
+            conf,
+            include_str!("../templates/tests/nvue_build_etv_deny_prefix_offset.yaml.expected"),
+        );
+    }
+
+    #[test]
+    fn test_build_fnn_multi_port_ipv6_accumulation() {
+        // When multiple ports belong to the same VPC (same l3_vni),
+        // PortPrefixesIpv6 should accumulate from all ports.
+        let mut conf = minimal_nvue_config();
+        conf.is_fnn = true;
+        conf.vpc_virtualization_type = VpcVirtualizationType::Fnn;
+        conf.use_vpc_isolation = true;
+        conf.site_fabric_prefixes = vec!["10.0.0.0/16".into(), "fd00::/32".into()];
+        conf.ct_routing_profile = Some(RoutingProfile {
+            tenant_leak_communities_accepted: false,
+            leak_default_route_from_underlay: false,
+            leak_tenant_host_routes_to_underlay: false,
+            route_target_imports: vec![],
+            route_targets_on_exports: vec![],
+        });
+        conf.ct_port_configs = vec![
+            PortConfig {
+                interface_name: "pf0vf0_if".into(),
+                vlan: 100,
+                vni: Some(1000),
+                l3_vni: Some(200),
+                gateway_cidr: "10.0.1.1/24".into(),
+                vpc_prefixes: vec!["10.0.1.0/24".into(), "2001:db8:1::/48".into()],
+                vpc_peer_prefixes: vec![],
+                vpc_peer_vnis: vec![],
+                svi_ip: Some("10.0.1.254".into()),
+                tenant_vrf_loopback_ip: Some("10.0.0.2".into()),
+                is_l2_segment: false,
+                is_phy: false,
+                network_security_group_id: None,
+                ipv6_port_config: None,
+            },
+            PortConfig {
+                interface_name: "pf0hpf_if".into(),
+                vlan: 101,
+                vni: Some(1001),
+                l3_vni: Some(200),
+                gateway_cidr: "10.0.2.1/24".into(),
+                vpc_prefixes: vec!["10.0.2.0/24".into(), "2001:db8:2::/48".into()],
+                vpc_peer_prefixes: vec![],
+                vpc_peer_vnis: vec![],
+                svi_ip: Some("10.0.2.254".into()),
+                tenant_vrf_loopback_ip: Some("10.0.0.2".into()),
+                is_l2_segment: false,
+                is_phy: false,
+                network_security_group_id: None,
+                ipv6_port_config: None,
+            },
+        ];
+        conf.ct_access_vlans = vec![
+            VlanConfig {
+                vlan_id: 100,
+                network: "10.0.1.0/24".into(),
+                ip: "10.0.1.2".into(),
+                ipv6_vlan_config: None,
+            },
+            VlanConfig {
+                vlan_id: 101,
+                network: "10.0.2.0/24".into(),
+                ip: "10.0.2.2".into(),
+                ipv6_vlan_config: None,
+            },
+        ];
+        assert_build_matches_golden(
+            conf,
+            include_str!("../templates/tests/nvue_build_fnn_multi_port_ipv6.yaml.expected"),
+        );
+    }
+
+    #[test]
+    fn test_build_fnn_dual_stack_interface() {
+        // When ipv6.gateway_cidr is set, the NVUE template should configure
+        // both IPv4 and IPv6 addresses on the interface.
+        let mut conf = minimal_nvue_config();
+        conf.is_fnn = true;
+        conf.vpc_virtualization_type = VpcVirtualizationType::Fnn;
+        conf.use_vpc_isolation = true;
+        conf.site_fabric_prefixes = vec!["10.0.0.0/16".into(), "fd00::/32".into()];
+        conf.ct_routing_profile = Some(RoutingProfile {
+            leak_default_route_from_underlay: false,
+            leak_tenant_host_routes_to_underlay: false,
+            tenant_leak_communities_accepted: false,
+            route_target_imports: vec![],
+            route_targets_on_exports: vec![],
+        });
+        conf.ct_port_configs = vec![PortConfig {
+            interface_name: "pf0vf0_if".into(),
+            vlan: 100,
+            vni: Some(1000),
+            l3_vni: Some(100),
+            gateway_cidr: "10.0.1.0/31".into(),
+            ipv6_port_config: Some(Ipv6PortConfig {
+                gateway_cidr: "2001:db8::0/127".into(),
+                svi_ip: None,
+            }),
+            vpc_prefixes: vec!["10.0.1.0/24".into(), "2001:db8::/48".into()],
+            vpc_peer_prefixes: vec![],
+            vpc_peer_vnis: vec![],
+            svi_ip: Some("10.0.1.254".into()),
+            tenant_vrf_loopback_ip: Some("10.0.0.2".into()),
+            is_l2_segment: false,
+            is_phy: false,
+            network_security_group_id: None,
+        }];
+        conf.ct_access_vlans = vec![VlanConfig {
+            vlan_id: 100,
+            network: "10.0.1.0/31".into(),
+            ip: "10.0.1.1".into(),
+            ipv6_vlan_config: Some(Ipv6VlanConfig {
+                network: "2001:db8::0/127".into(),
+                ip: "2001:db8::1".into(),
+            }),
+        }];
+        assert_build_matches_golden(
+            conf,
+            include_str!("../templates/tests/nvue_build_fnn_dual_stack.yaml.expected"),
+        );
+    }
+
+    /// `serde_yaml::Value::Null` indicates a YAML key with no value (i.e. a
+    /// bare `key:` followed by nothing). In cases like this, YAML parsing fails,
+    /// and NVUE subsequently rejects with something like `Error: 'set' operation
+    /// values must not be 'null'`.
+    /// This helper exists so we can check for empty leaf renderings in general for
+    /// any tests that would like to check for such a situation.
+    fn has_null_leaf(v: &serde_yaml::Value) -> bool {
+        match v {
+            serde_yaml::Value::Null => true,
+            serde_yaml::Value::Mapping(m) => m.values().any(has_null_leaf),
+            serde_yaml::Value::Sequence(s) => s.iter().any(has_null_leaf),
+            _ => false,
+        }
+    }
+
+    /// When a DPU has no tenant VPCs assigned, the rendered FNN YAML must contain
+    /// no null config leaves (like a `list:` with no entries, or `from-vrf:` with
+    /// no subsequent config, etc).
+    #[test]
+    fn test_build_fnn_with_no_vpcs_emits_no_yaml_nulls() {
+        let mut conf = minimal_nvue_config();
+        conf.is_fnn = true;
+        conf.vpc_virtualization_type = VpcVirtualizationType::Fnn;
+        // For this one, we'll intentionally leave ct_port_configs / ct_access_vlans empty
+        // to ensure $tenant.Vpcs is empty in the template, and that route-import
+        // blocks that would otherwise have an empty VPC VRF list are excluded.
+        assert!(conf.ct_port_configs.is_empty());
+
+        let output = build(conf).expect("build should succeed with empty ct_port_configs");
+        let parsed: serde_yaml::Value =
+            serde_yaml::from_str(&output).expect("rendered YAML must parse");
+
+        assert!(
+            !has_null_leaf(&parsed),
+            "rendered YAML contains a null leaf:\n\n{output}"
+        );
+    }
+
+    fn phy_port_config(vlan: u16) -> PortConfig {
+        PortConfig {
+            interface_name: "pf0hpf_if".into(),
+            vlan,
+            vni: Some(1000),
+            l3_vni: Some(100),
+            gateway_cidr: "10.0.1.1/24".into(),
+            vpc_prefixes: vec![],
+            vpc_peer_prefixes: vec![],
+            vpc_peer_vnis: vec![],
+            svi_ip: Some("10.0.1.2".into()),
+            tenant_vrf_loopback_ip: None,
+            is_l2_segment: true,
+            is_phy: true,
+            network_security_group_id: None,
+            ipv6_port_config: None,
+        }
+    }
+
+    // In container mode with fmds_gateway_vlan matching the phy port vlan, the
+    // FMDS gateway address should appear in the rendered output.
+    #[test]
+    fn test_fmds_gateway_etv_container_mode_emits_address() {
+        let mut conf = minimal_nvue_config();
+        conf.is_dpu_os = false;
+        conf.fmds_gateway_vlan = Some(274);
+        conf.ct_port_configs = vec![phy_port_config(274)];
+        let output = build(conf).expect("build should succeed");
+        assert!(
+            output.contains("169.254.169.253/30"),
+            "expected FMDS gateway address in output:\n{output}"
+        );
+    }
+
+    // In DPU-OS mode the FMDS gateway address must NOT appear on the vlan SVI
+    // (it lives on pf0dpu1_if instead, managed by the startup template).
+    #[test]
+    fn test_fmds_gateway_etv_dpu_os_mode_suppressed() {
+        let mut conf = minimal_nvue_config();
+        conf.is_dpu_os = true;
+        // Even if fmds_gateway_vlan is accidentally set, DPU-OS mode must ignore it.
+        conf.fmds_gateway_vlan = Some(274);
+        conf.ct_port_configs = vec![phy_port_config(274)];
+        let output = build(conf).expect("build should succeed");
+        // The ETV template uses pf0dpu1_if for the gateway in DPU-OS mode, not the vlan SVI.
+        assert!(
+            !output.contains("169.254.169.253/30") || output.contains("pf0dpu1_if"),
+            "DPU-OS mode should not put 169.254.169.253/30 on the vlan SVI"
+        );
+    }
+
+    // fmds_gateway_vlan pointing at a vlan that does not exist in ct_port_configs
+    // should log an error but still return a valid (if incomplete) config.
+    #[test]
+    fn test_fmds_gateway_vlan_not_in_port_configs_still_builds() {
+        let mut conf = minimal_nvue_config();
+        conf.is_dpu_os = false;
+        conf.fmds_gateway_vlan = Some(999); // no port has vlan 999
+        conf.ct_port_configs = vec![phy_port_config(274)];
+        // Should succeed (just logs an error, does not fail the build).
+        let output = build(conf).expect("build should succeed even when vlan is missing");
+        assert!(
+            !output.contains("169.254.169.253/30"),
+            "mismatched vlan should not produce FMDS address in output"
+        );
+    }
+
+    // fmds_gateway_vlan=None should never emit the FMDS gateway address.
+    #[test]
+    fn test_fmds_gateway_none_emits_no_address() {
+        let mut conf = minimal_nvue_config();
+        conf.is_dpu_os = false;
+        conf.fmds_gateway_vlan = None;
+        conf.ct_port_configs = vec![phy_port_config(274)];
+        let output = build(conf).expect("build should succeed");
+        assert!(
+            !output.contains("169.254.169.253/30"),
+            "no fmds_gateway_vlan should not produce FMDS address in output"
+        );
+    }
+
+    fn minimal_fnn_routing_profile() -> RoutingProfile {
+        RoutingProfile {
+            tenant_leak_communities_accepted: false,
+            leak_default_route_from_underlay: false,
+            leak_tenant_host_routes_to_underlay: false,
+            route_target_imports: vec![],
+            route_targets_on_exports: vec![],
+        }
+    }
+
+    // Same checks for FNN template.
+    #[test]
+    fn test_fmds_gateway_fnn_container_mode_emits_address() {
+        let mut conf = minimal_nvue_config();
+        conf.is_fnn = true;
+        conf.vpc_virtualization_type = VpcVirtualizationType::Fnn;
+        conf.is_dpu_os = false;
+        conf.fmds_gateway_vlan = Some(274);
+        conf.ct_routing_profile = Some(minimal_fnn_routing_profile());
+        conf.ct_port_configs = vec![phy_port_config(274)];
+        let output = build(conf).expect("build should succeed");
+        assert!(
+            output.contains("169.254.169.253/30"),
+            "expected FMDS gateway address in FNN output:\n{output}"
+        );
+    }
+
+    #[test]
+    fn test_fmds_gateway_fnn_dpu_os_mode_suppressed() {
+        let mut conf = minimal_nvue_config();
+        conf.is_fnn = true;
+        conf.vpc_virtualization_type = VpcVirtualizationType::Fnn;
+        conf.is_dpu_os = true;
+        conf.fmds_gateway_vlan = Some(274);
+        conf.ct_routing_profile = Some(minimal_fnn_routing_profile());
+        conf.ct_port_configs = vec![phy_port_config(274)];
+        let output = build(conf).expect("build should succeed");
+        assert!(
+            !output.contains("169.254.169.253/30") || output.contains("pf0dpu1_if"),
+            "DPU-OS mode should not put 169.254.169.253/30 on the vlan SVI in FNN"
+        );
+    }
+}
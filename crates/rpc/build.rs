@@ -65,6 +65,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .extern_path(".common.VpcPeeringId", "::carbide_uuid::vpc_peering::VpcPeeringId")
         .extern_path(".common.VpcPrefixId", "::carbide_uuid::vpc::VpcPrefixId")
         .extern_path(".common.ComputeAllocationId", "::carbide_uuid::compute_allocation::ComputeAllocationId")
+        .extern_path(".common.PrefixListId", "::carbide_uuid::prefix_list::PrefixListId")
         .extern_path(".common.OperatingSystemId", "::carbide_uuid::operating_system::OperatingSystemId")
         .extern_path(".common.IpxeTemplateId", "::carbide_uuid::ipxe_template::IpxeTemplateId")
         .extern_path(".common.MachineValidationId", "::carbide_uuid::machine_validation::MachineValidationId")
@@ -258,6 +259,20 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         )
         .type_attribute("forge.MachineList", "#[derive(serde::Serialize)]")
         .type_attribute("forge.MachineEvent", "#[derive(serde::Serialize)]")
+        .type_attribute(
+            "forge.GetManagedHostAtResponse",
+            "#[derive(serde::Serialize)]",
+        )
+        .type_attribute("forge.ManagedHostStateAt", "#[derive(serde::Serialize)]")
+        .type_attribute("forge.MachineStateAt", "#[derive(serde::Serialize)]")
+        .type_attribute("forge.HealthHistoryRecord", "#[derive(serde::Serialize)]")
+        .type_attribute("forge.ConfigVersionRecord", "#[derive(serde::Serialize)]")
+        .type_attribute("forge.InterfaceAddressesAt", "#[derive(serde::Serialize)]")
+        .type_attribute("forge.InstanceStateAt", "#[derive(serde::Serialize)]")
+        .type_attribute(
+            "forge.ManagedHostFieldChange",
+            "#[derive(serde::Serialize)]",
+        )
         .type_attribute("forge.MachineInterface", "#[derive(serde::Serialize)]")
         .type_attribute(
             "forge.InfinibandStatusObservation",
@@ -281,6 +296,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             "forge.ManagedHostNetworkConfigResponse",
             "#[derive(serde::Serialize)]",
         )
+        .type_attribute("forge.AppliedNvueConfig", "#[derive(serde::Serialize)]")
+        .type_attribute(
+            "forge.RenderDpuNetworkConfigResponse",
+            "#[derive(serde::Serialize)]",
+        )
         .type_attribute(
             "forge.RoutingProfile",
             "#[derive(serde::Serialize)]",
@@ -635,6 +655,58 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             "forge.NetworkSecurityGroupPropagationObjectStatus",
             "#[derive(serde::Deserialize, serde::Serialize)]",
         )
+        .type_attribute(
+            "forge.PrefixList",
+            "#[derive(serde::Serialize)]",
+        )
+        .type_attribute(
+            "forge.PrefixListAttributes",
+            "#[derive(serde::Serialize)]",
+        )
+        .type_attribute(
+            "forge.MediaSanitizationVerification",
+            "#[derive(serde::Serialize)]",
+        )
+        .type_attribute(
+            "forge.MediaSanitizationRecord",
+            "#[derive(serde::Serialize)]",
+        )
+        .type_attribute(
+            "forge.MediaSanitizationReport",
+            "#[derive(serde::Serialize)]",
+        )
+        .type_attribute(
+            "forge.HealthSilenceMatcher",
+            "#[derive(serde::Serialize)]",
+        )
+        .type_attribute(
+            "forge.HealthSilence",
+            "#[derive(serde::Serialize)]",
+        )
+        .type_attribute(
+            "forge.HealthSilenceList",
+            "#[derive(serde::Serialize)]",
+        )
+        .type_attribute(
+            "forge.ResolvedConnectivityEndpoint",
+            "#[derive(serde::Serialize)]",
+        )
+        .type_attribute(
+            "forge.ConnectivityStep",
+            "#[derive(serde::Serialize)]",
+        )
+        .type_attribute(
+            "forge.AnalyzeConnectivityResponse",
+            "#[derive(serde::Serialize)]",
+        )
+        .type_attribute(
+            "forge.ReachabilityMatrixEntry",
+            "#[derive(serde::Serialize)]",
+        )
+        .type_attribute(
+            "forge.GetVpcReachabilityMatrixResponse",
+            "#[derive(serde::Serialize)]",
+        )
         .type_attribute("Sku", "#[derive(serde::Serialize, serde::Deserialize)]")
         .field_attribute("Sku.schema_version", "#[serde(default)]")
         .field_attribute("Sku.associated_machine_ids", "#[serde(default)]")
@@ -852,6 +924,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 "proto/site_explorer.proto",
                 "proto/dns.proto",
                 "proto/fmds.proto",
+                "proto/spiffe_workload.proto",
             ],
             &["proto"],
         )
@@ -906,6 +979,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 ".common.ComputeAllocationId",
                 "::carbide_uuid::compute_allocation::ComputeAllocationId",
             ),
+            (
+                ".common.PrefixListId",
+                "::carbide_uuid::prefix_list::PrefixListId",
+            ),
             (
                 ".common.OperatingSystemId",
                 "::carbide_uuid::operating_system::OperatingSystemId",
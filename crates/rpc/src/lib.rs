@@ -58,7 +58,7 @@ pub use crate::protos::machine_discovery::{
     self, BlockDevice, Cpu, DiscoveryInfo, DmiData, NetworkInterface, NvmeDevice,
     PciDeviceProperties,
 };
-pub use crate::protos::{fmds, health, site_explorer};
+pub use crate::protos::{fmds, health, site_explorer, spiffe_workload};
 
 pub mod errors;
 pub mod forge_tls_client;
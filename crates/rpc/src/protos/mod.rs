@@ -51,6 +51,12 @@ pub mod dns;
 #[rustfmt::skip]
 pub mod fmds;
 
+// The SPIFFE Workload API proto has no package, so prost emits it as `_.rs`.
+#[allow(non_snake_case, unknown_lints, clippy::all)]
+#[rustfmt::skip]
+#[path = "_.rs"]
+pub mod spiffe_workload;
+
 #[allow(clippy::all, deprecated)]
 #[rustfmt::skip]
 pub mod forge_api_client;
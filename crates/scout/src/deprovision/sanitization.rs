@@ -0,0 +1,1002 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Storage media sanitization following NIST SP 800-88.
+//!
+//! Every NVMe controller and every SATA/SAS disk is sanitized with the
+//! strongest method it supports. Software RAID arrays are stopped and the
+//! superblocks of their members erased first. Before sanitizing, sectors
+//! spread across each device are sampled, and after sanitizing they are read
+//! back: any sector that held data and still reads the same fails the
+//! verification. The outcome for each device is reported to carbide-api as a
+//! [`rpc::MediaSanitizationRecord`].
+
+use std::collections::HashMap;
+use std::fs;
+use std::os::unix::fs::FileExt;
+use std::path::Path;
+use std::time::Duration;
+
+use ::rpc::forge as rpc;
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use scout::CarbideClientError;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use tracing::Instrument;
+
+use crate::deprovision::cmdrun;
+use crate::deprovision::scrabbing::{NvmeParams, clean_this_nvme, get_nvme_params};
+
+static HDPARM_PROG: &str = "/usr/sbin/hdparm";
+static SG_SANITIZE_PROG: &str = "/usr/bin/sg_sanitize";
+static SG_FORMAT_PROG: &str = "/usr/bin/sg_format";
+static MDADM_PROG: &str = "/usr/sbin/mdadm";
+static BLOCKDEV_PROG: &str = "/usr/sbin/blockdev";
+static LSBLK_PROG: &str = "/usr/bin/lsblk";
+
+/// The temporary ATA security password. The ATA security feature set requires
+/// a password to be set before SECURITY ERASE UNIT, and clears it again.
+static ATA_SECURITY_PASSWORD: &str = "carbide";
+
+/// How many sectors are sampled per device for verification.
+const VERIFICATION_SAMPLES: u64 = 64;
+/// The size of a sampled sector. Large enough for 512 byte and 4K sector devices.
+const VERIFICATION_SECTOR_SIZE: u64 = 4096;
+/// How long to wait for a recreated NVMe namespace to show up as a block device.
+const NVME_NAMESPACE_WAIT: Duration = Duration::from_secs(10);
+
+lazy_static::lazy_static! {
+    static ref NVME_CTRL_RE: Regex = Regex::new(r"^(nvme[0-9]+)(n[0-9]+(p[0-9]+)?)?$").unwrap();
+    static ref MDSTAT_MEMBER_RE: Regex = Regex::new(r"^([a-z0-9]+)\[[0-9]+\]").unwrap();
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum Transport {
+    Nvme,
+    Sata,
+    Sas,
+}
+
+impl Transport {
+    fn as_str(self) -> &'static str {
+        match self {
+            Transport::Nvme => "nvme",
+            Transport::Sata => "sata",
+            Transport::Sas => "sas",
+        }
+    }
+}
+
+/// The sanitize command used for a device.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct SanitizeMethod {
+    pub name: &'static str,
+    /// `model::media_sanitization::NIST_CATEGORY_*`
+    pub nist_category: &'static str,
+}
+
+impl SanitizeMethod {
+    pub const fn purge(name: &'static str) -> Self {
+        Self {
+            name,
+            nist_category: "purge",
+        }
+    }
+
+    pub const fn clear(name: &'static str) -> Self {
+        Self {
+            name,
+            nist_category: "clear",
+        }
+    }
+}
+
+/// A storage device to sanitize.
+#[derive(Clone, Debug, PartialEq)]
+struct MediaDevice {
+    /// What gets sanitized: an NVMe controller or a disk
+    device: String,
+    /// The block device sampled for verification. For NVMe controllers this
+    /// is their first namespace, which is recreated during sanitization.
+    block_device: String,
+    transport: Transport,
+    serial_number: String,
+    model: String,
+    capacity_bytes: u64,
+}
+
+/// The records of all devices, and whether all of them were sanitized and verified.
+pub(super) struct SanitizationOutcome {
+    pub records: Vec<rpc::MediaSanitizationRecord>,
+}
+
+impl SanitizationOutcome {
+    /// A summary of the devices that failed sanitization or verification, if any.
+    pub fn errors(&self) -> Option<String> {
+        let errors: Vec<String> = self
+            .records
+            .iter()
+            .filter(|r| r.result() == rpc::machine_cleanup_info::CleanupResult::Error)
+            .map(|r| {
+                format!(
+                    "MEDIA_SANITIZATION_ERROR (device: {}; serial: {}; method: {}): {}",
+                    r.device, r.serial_number, r.method, r.message
+                )
+            })
+            .collect();
+        if errors.is_empty() {
+            None
+        } else {
+            Some(errors.join("\n"))
+        }
+    }
+}
+
+/// Sanitizes all NVMe, SATA and SAS storage of the machine.
+///
+/// Failures are recorded per device, so that a device which can't be
+/// discovered or disassembled from its RAID array doesn't stop the others
+/// from being sanitized.
+pub(super) async fn sanitize_all_media() -> SanitizationOutcome {
+    let mut records = Vec::new();
+    let mut devices = discover_nvme_controllers().await;
+    match discover_disks().await {
+        Ok(disks) => devices.extend(disks),
+        Err(e) => {
+            tracing::error!(error = %e, "Could not discover SATA/SAS disks");
+            records.push(failure_record(
+                "unknown".to_string(),
+                format!("discovering SATA/SAS disks failed: {e}"),
+            ));
+        }
+    }
+
+    if devices.is_empty() {
+        tracing::info!("No storage devices found to sanitize");
+        return SanitizationOutcome { records };
+    }
+
+    let raid_arrays = match fs::read_to_string("/proc/mdstat") {
+        Ok(mdstat) => parse_mdstat(&mdstat),
+        Err(_) => vec![],
+    };
+    let mut raid_membership = disassemble_raid_arrays(&raid_arrays).await;
+
+    tracing::info!(device_count = devices.len(), "Starting media sanitization");
+    let start_time = std::time::Instant::now();
+
+    let tasks: Vec<_> = devices
+        .into_iter()
+        .map(|device| {
+            let membership = raid_membership.remove(&device.device).unwrap_or_default();
+            let span = tracing::info_span!("media_sanitization", device = %device.device);
+            tokio::spawn(sanitize_device(device, membership).instrument(span))
+        })
+        .collect();
+
+    for task in futures_util::future::join_all(tasks).await {
+        records.push(task.expect("media sanitization task panicked"));
+    }
+
+    // RAID members on devices which aren't sanitized, e.g. virtual disks, can
+    // still reassemble
+    for (device, membership) in raid_membership {
+        if !membership.errors.is_empty() {
+            let mut record = failure_record(device, membership.errors.join("; "));
+            record.raid_arrays = membership.arrays;
+            records.push(record);
+        }
+    }
+
+    let outcome = SanitizationOutcome { records };
+    tracing::info!(
+        device_count = outcome.records.len(),
+        failed = outcome.errors().is_some(),
+        total_duration = ?start_time.elapsed(),
+        "Media sanitization completed"
+    );
+    outcome
+}
+
+/// The record of a device which could not be sanitized at all
+fn failure_record(device: String, message: String) -> rpc::MediaSanitizationRecord {
+    let now = timestamp(Utc::now());
+    rpc::MediaSanitizationRecord {
+        device,
+        result: rpc::machine_cleanup_info::CleanupResult::Error as i32,
+        message,
+        started_at: Some(now),
+        completed_at: Some(now),
+        ..Default::default()
+    }
+}
+
+async fn sanitize_device(
+    device: MediaDevice,
+    raid_membership: RaidMembership,
+) -> rpc::MediaSanitizationRecord {
+    let started_at = Utc::now();
+    tracing::info!(
+        transport = device.transport.as_str(),
+        serial = %device.serial_number,
+        model = %device.model,
+        "Starting sanitization"
+    );
+
+    let before = match sample_device(&device.block_device).await {
+        Ok(samples) => Some(samples),
+        Err(e) => {
+            tracing::warn!(error = %e, "Could not sample device before sanitization");
+            None
+        }
+    };
+
+    let result = match device.transport {
+        Transport::Nvme => clean_this_nvme(&device.device).await,
+        Transport::Sata => ata_security_erase(&device.device).await,
+        Transport::Sas => scsi_sanitize(&device.device).await,
+    };
+
+    let (method, result) = match result {
+        Ok(method) => {
+            let verification = match before {
+                Some(before) => match resample_device(&device, &before).await {
+                    Ok(after) => Some(verify(&before, &after)),
+                    Err(e) => {
+                        tracing::warn!(error = %e, "Could not read back device after sanitization");
+                        None
+                    }
+                },
+                None => None,
+            };
+            let failure = verification.as_ref().filter(|v| !v.passed).map(|v| {
+                format!(
+                    "verification failed: {} of {} sampled sectors still hold their data",
+                    v.sectors_unchanged, v.sectors_sampled
+                )
+            });
+            let result = match failure {
+                Some(message) => Err((verification, message)),
+                None => Ok(verification),
+            };
+            (Some(method), result)
+        }
+        Err(e) => (None, Err((None, e.to_string()))),
+    };
+
+    // A member whose array could not be stopped or whose superblock could not
+    // be erased can still reassemble, whatever the wipe did
+    let result = if raid_membership.errors.is_empty() {
+        result
+    } else {
+        let raid_error = raid_membership.errors.join("; ");
+        match result {
+            Ok(verification) => Err((verification, raid_error)),
+            Err((verification, message)) => Err((verification, format!("{raid_error}; {message}"))),
+        }
+    };
+
+    let completed_at = Utc::now();
+    let (verification, cleanup_result, message) = match result {
+        Ok(verification) => {
+            tracing::info!(duration = ?(completed_at - started_at), ?method, ?verification, "Sanitization completed");
+            (
+                verification,
+                rpc::machine_cleanup_info::CleanupResult::Ok,
+                String::new(),
+            )
+        }
+        Err((verification, message)) => {
+            tracing::error!(duration = ?(completed_at - started_at), ?method, error = %message, "Sanitization failed");
+            (
+                verification,
+                rpc::machine_cleanup_info::CleanupResult::Error,
+                message,
+            )
+        }
+    };
+
+    rpc::MediaSanitizationRecord {
+        device: device.device,
+        transport: device.transport.as_str().to_string(),
+        serial_number: device.serial_number,
+        model: device.model,
+        capacity_bytes: device.capacity_bytes,
+        method: method.map(|m| m.name).unwrap_or_default().to_string(),
+        nist_category: method
+            .map(|m| m.nist_category)
+            .unwrap_or_default()
+            .to_string(),
+        raid_arrays: raid_membership.arrays,
+        result: cleanup_result as i32,
+        message,
+        started_at: Some(timestamp(started_at)),
+        completed_at: Some(timestamp(completed_at)),
+        verification,
+    }
+}
+
+fn timestamp(time: DateTime<Utc>) -> ::rpc::Timestamp {
+    ::rpc::Timestamp::from(time)
+}
+
+/* ********************************** */
+/*              Discovery             */
+/* ********************************** */
+
+/// All NVMe controllers, whether or not they currently have namespaces.
+async fn discover_nvme_controllers() -> Vec<MediaDevice> {
+    let mut controllers: Vec<String> = match fs::read_dir("/dev") {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter(|name| {
+                NVME_CTRL_RE
+                    .captures(name)
+                    .is_some_and(|caps| caps.get(2).is_none())
+            })
+            .collect(),
+        Err(_) => vec![],
+    };
+    controllers.sort();
+
+    let mut devices = Vec::with_capacity(controllers.len());
+    for name in controllers {
+        let device = format!("/dev/{name}");
+        // Failing to identify the controller is reported by the sanitization itself
+        let params: Option<NvmeParams> = get_nvme_params(&device).await.ok();
+        devices.push(MediaDevice {
+            block_device: format!("{device}n1"),
+            transport: Transport::Nvme,
+            serial_number: params
+                .as_ref()
+                .map(|p| p.sn.trim().to_string())
+                .unwrap_or_default(),
+            model: params
+                .as_ref()
+                .map(|p| p.mn.trim().to_string())
+                .unwrap_or_default(),
+            capacity_bytes: params.as_ref().map(|p| p.tnvmcap).unwrap_or_default(),
+            device,
+        });
+    }
+    devices
+}
+
+#[derive(Deserialize, Debug)]
+struct LsblkOutput {
+    blockdevices: Vec<LsblkDevice>,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct LsblkDevice {
+    name: String,
+    #[serde(rename = "type")]
+    device_type: String,
+    tran: Option<String>,
+    serial: Option<String>,
+    model: Option<String>,
+    // Older lsblk versions print numbers as strings
+    size: serde_json::Value,
+    rm: serde_json::Value,
+}
+
+impl LsblkDevice {
+    fn size(&self) -> u64 {
+        match &self.size {
+            serde_json::Value::Number(n) => n.as_u64().unwrap_or_default(),
+            serde_json::Value::String(s) => s.parse().unwrap_or_default(),
+            _ => 0,
+        }
+    }
+
+    fn removable(&self) -> bool {
+        match &self.rm {
+            serde_json::Value::Bool(b) => *b,
+            serde_json::Value::String(s) => s == "1",
+            serde_json::Value::Number(n) => n.as_u64() == Some(1),
+            _ => false,
+        }
+    }
+}
+
+fn parse_lsblk(output: &str) -> Result<Vec<MediaDevice>, CarbideClientError> {
+    let output: LsblkOutput = serde_json::from_str(output)
+        .map_err(|e| CarbideClientError::GenericError(format!("lsblk parse error: {e}")))?;
+
+    Ok(output
+        .blockdevices
+        .into_iter()
+        .filter(|d| d.device_type == "disk" && !d.removable())
+        .filter_map(|d| {
+            let transport = match d.tran.as_deref() {
+                Some("sata") => Transport::Sata,
+                Some("sas") => Transport::Sas,
+                // NVMe is sanitized per controller, USB media is the boot
+                // media of manual installs, and virtual disks have no
+                // sanitize commands.
+                _ => return None,
+            };
+            let model = d.model.clone().unwrap_or_default().trim().to_string();
+            // BOSS volumes are erased through Redfish by the state controller
+            if model.contains("BOSS") {
+                return None;
+            }
+            let device = format!("/dev/{}", d.name);
+            Some(MediaDevice {
+                block_device: device.clone(),
+                device,
+                transport,
+                serial_number: d.serial.clone().unwrap_or_default().trim().to_string(),
+                model,
+                capacity_bytes: d.size(),
+            })
+        })
+        .collect())
+}
+
+/// All SATA and SAS disks.
+async fn discover_disks() -> Result<Vec<MediaDevice>, CarbideClientError> {
+    let output = cmdrun::run_prog(
+        LSBLK_PROG,
+        [
+            "--json",
+            "--nodeps",
+            "--bytes",
+            "--output",
+            "NAME,TYPE,TRAN,SERIAL,MODEL,SIZE,RM",
+        ],
+    )
+    .await?;
+    parse_lsblk(&output)
+}
+
+/* ********************************** */
+/*            Software RAID           */
+/* ********************************** */
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct RaidArray {
+    name: String,
+    /// Kernel names of the member devices, usually partitions
+    members: Vec<String>,
+}
+
+fn parse_mdstat(mdstat: &str) -> Vec<RaidArray> {
+    mdstat
+        .lines()
+        .filter_map(|line| {
+            let (name, rest) = line.split_once(" : ")?;
+            let name = name.trim();
+            if !name.starts_with("md") {
+                return None;
+            }
+            let members = rest
+                .split_whitespace()
+                .filter_map(|token| MDSTAT_MEMBER_RE.captures(token))
+                .map(|caps| caps[1].to_string())
+                .collect();
+            Some(RaidArray {
+                name: name.to_string(),
+                members,
+            })
+        })
+        .collect()
+}
+
+/// The device that gets sanitized for a RAID member: the disk a partition is
+/// on, or the controller of an NVMe namespace.
+fn sanitized_device_of(member: &str) -> String {
+    if let Some(caps) = NVME_CTRL_RE.captures(member) {
+        return format!("/dev/{}", &caps[1]);
+    }
+    let parent = fs::canonicalize(format!("/sys/class/block/{member}"))
+        .ok()
+        .filter(|path| path.join("partition").exists())
+        .and_then(|path| {
+            path.parent()
+                .and_then(Path::file_name)
+                .map(|name| name.to_string_lossy().to_string())
+        });
+    format!("/dev/{}", parent.as_deref().unwrap_or(member))
+}
+
+/// The software RAID arrays a sanitized device was a member of
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct RaidMembership {
+    arrays: Vec<String>,
+    /// Why stopping an array or erasing a superblock failed for the device
+    errors: Vec<String>,
+}
+
+/// Stops all arrays and erases the RAID superblocks of their members, so that
+/// they don't reassemble. Returns the arrays each device was a member of, and
+/// what failed for it.
+async fn disassemble_raid_arrays(arrays: &[RaidArray]) -> HashMap<String, RaidMembership> {
+    let mut membership: HashMap<String, RaidMembership> = HashMap::new();
+    for array in arrays {
+        let array_device = format!("/dev/{}", array.name);
+        tracing::info!(array = %array_device, members = ?array.members, "Stopping software RAID array");
+        let stop_error = cmdrun::run_prog(MDADM_PROG, ["--stop", array_device.as_str()])
+            .await
+            .err();
+        if let Some(e) = &stop_error {
+            tracing::error!(array = %array_device, error = %e, "Could not stop software RAID array");
+        }
+        for member in &array.members {
+            let device_membership = membership.entry(sanitized_device_of(member)).or_default();
+            device_membership.arrays.push(array_device.clone());
+            if let Some(e) = &stop_error {
+                device_membership
+                    .errors
+                    .push(format!("stopping RAID array {array_device} failed: {e}"));
+                continue;
+            }
+            let member_device = format!("/dev/{member}");
+            if let Err(e) =
+                cmdrun::run_prog(MDADM_PROG, ["--zero-superblock", member_device.as_str()]).await
+            {
+                tracing::error!(member = %member_device, error = %e, "Could not erase RAID superblock");
+                device_membership.errors.push(format!(
+                    "erasing the RAID superblock of {member_device} failed: {e}"
+                ));
+            }
+        }
+    }
+    membership
+}
+
+/* ********************************** */
+/*              SATA/SAS              */
+/* ********************************** */
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct AtaSecurity {
+    supported: bool,
+    enabled: bool,
+    locked: bool,
+    frozen: bool,
+    enhanced_erase: bool,
+}
+
+/// Parses the `Security:` section of `hdparm -I`.
+fn parse_ata_security(identify: &str) -> AtaSecurity {
+    let mut security = AtaSecurity::default();
+    let mut in_section = false;
+    for line in identify.lines() {
+        if line.starts_with("Security:") {
+            in_section = true;
+            continue;
+        }
+        if !in_section {
+            continue;
+        }
+        if !line.starts_with(char::is_whitespace) && !line.trim().is_empty() {
+            break;
+        }
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let (negated, words) = match words.split_first() {
+            Some((&"not", rest)) => (true, rest),
+            _ => (false, words.as_slice()),
+        };
+        match words {
+            ["supported"] => security.supported = !negated,
+            ["supported:", "enhanced", "erase"] => security.enhanced_erase = !negated,
+            ["enabled"] => security.enabled = !negated,
+            ["locked"] => security.locked = !negated,
+            ["frozen"] => security.frozen = !negated,
+            _ => {}
+        }
+    }
+    security
+}
+
+/// Erases a SATA disk with the ATA security feature set.
+async fn ata_security_erase(device: &str) -> Result<SanitizeMethod, CarbideClientError> {
+    let identify = cmdrun::run_prog(HDPARM_PROG, ["-I", device]).await?;
+    let security = parse_ata_security(&identify);
+    tracing::debug!(?security, "ATA security state");
+
+    if !security.supported {
+        return Err(CarbideClientError::GenericError(
+            "the ATA security feature set is not supported".to_string(),
+        ));
+    }
+    if security.frozen {
+        return Err(CarbideClientError::GenericError(
+            "the drive's security state is frozen, it needs a power cycle to unfreeze".to_string(),
+        ));
+    }
+    if security.locked {
+        return Err(CarbideClientError::GenericError(
+            "the drive is locked with an unknown password".to_string(),
+        ));
+    }
+
+    if !security.enabled {
+        cmdrun::run_prog(
+            HDPARM_PROG,
+            [
+                "--user-master",
+                "u",
+                "--security-set-pass",
+                ATA_SECURITY_PASSWORD,
+                device,
+            ],
+        )
+        .await?;
+    }
+
+    let (erase_flag, method) = if security.enhanced_erase {
+        (
+            "--security-erase-enhanced",
+            SanitizeMethod::purge("ata-security-erase-enhanced"),
+        )
+    } else {
+        (
+            "--security-erase",
+            SanitizeMethod::clear("ata-security-erase"),
+        )
+    };
+    cmdrun::run_prog(
+        HDPARM_PROG,
+        [
+            "--user-master",
+            "u",
+            erase_flag,
+            ATA_SECURITY_PASSWORD,
+            device,
+        ],
+    )
+    .await?;
+    Ok(method)
+}
+
+/// Sanitizes a SAS disk with the strongest SCSI SANITIZE service action it
+/// supports, falling back to FORMAT UNIT.
+async fn scsi_sanitize(device: &str) -> Result<SanitizeMethod, CarbideClientError> {
+    let attempts: [(&[&str], SanitizeMethod); 3] = [
+        (&["--crypto"], SanitizeMethod::purge("scsi-sanitize-crypto")),
+        (&["--block"], SanitizeMethod::purge("scsi-sanitize-block")),
+        (
+            &["--overwrite", "--zero"],
+            SanitizeMethod::purge("scsi-sanitize-overwrite"),
+        ),
+    ];
+
+    for (action, method) in attempts {
+        let mut args = vec!["--quick", "--wait"];
+        args.extend_from_slice(action);
+        args.push(device);
+        match cmdrun::run_prog(SG_SANITIZE_PROG, args).await {
+            Ok(_) => return Ok(method),
+            Err(e) => tracing::debug!(method = method.name, error = %e, "SCSI SANITIZE failed"),
+        }
+    }
+
+    tracing::warn!("SCSI SANITIZE is not supported, falling back to FORMAT UNIT");
+    cmdrun::run_prog(SG_FORMAT_PROG, ["--format", "--wait", device]).await?;
+    Ok(SanitizeMethod::clear("scsi-format-unit"))
+}
+
+/* ********************************** */
+/*             Verification           */
+/* ********************************** */
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct SectorSample {
+    offset: u64,
+    digest: [u8; 32],
+    /// Whether every byte of the sector had the same value. Such a sector
+    /// can't tell whether it was overwritten.
+    blank: bool,
+}
+
+/// Offsets of `count` sectors spread evenly from the first to the last
+/// sector of a device of `size` bytes.
+fn sample_offsets(size: u64, count: u64) -> Vec<u64> {
+    let sectors = size / VERIFICATION_SECTOR_SIZE;
+    if sectors == 0 || count == 0 {
+        return vec![];
+    }
+    let last = sectors - 1;
+    let mut offsets: Vec<u64> = (0..count)
+        .map(|i| {
+            let sector = if count == 1 {
+                0
+            } else {
+                // u128 as `last * i` can overflow for large devices
+                (u128::from(last) * u128::from(i) / u128::from(count - 1)) as u64
+            };
+            sector * VERIFICATION_SECTOR_SIZE
+        })
+        .collect();
+    offsets.dedup();
+    offsets
+}
+
+fn sample_sector(buffer: &[u8], offset: u64) -> SectorSample {
+    SectorSample {
+        offset,
+        digest: Sha256::digest(buffer).into(),
+        blank: buffer.windows(2).all(|w| w[0] == w[1]),
+    }
+}
+
+/// Discards the kernel's cached pages of a block device, so that samples are
+/// read from the media.
+async fn flush_buffers(block_device: &str) -> Result<(), CarbideClientError> {
+    cmdrun::run_prog(BLOCKDEV_PROG, ["--flushbufs", block_device])
+        .await
+        .map(|_| ())
+}
+
+fn read_samples(block_device: &str, offsets: &[u64]) -> std::io::Result<Vec<Option<SectorSample>>> {
+    let mut file = fs::File::open(block_device)?;
+    let size = std::io::Seek::seek(&mut file, std::io::SeekFrom::End(0))?;
+    let mut buffer = vec![0u8; VERIFICATION_SECTOR_SIZE as usize];
+    offsets
+        .iter()
+        .map(|&offset| {
+            if offset + VERIFICATION_SECTOR_SIZE > size {
+                return Ok(None);
+            }
+            file.read_exact_at(&mut buffer, offset)?;
+            Ok(Some(sample_sector(&buffer, offset)))
+        })
+        .collect()
+}
+
+fn device_size(block_device: &str) -> std::io::Result<u64> {
+    let mut file = fs::File::open(block_device)?;
+    std::io::Seek::seek(&mut file, std::io::SeekFrom::End(0))
+}
+
+async fn sample_device(block_device: &str) -> Result<Vec<SectorSample>, CarbideClientError> {
+    flush_buffers(block_device).await?;
+    let block_device = block_device.to_string();
+    tokio::task::spawn_blocking(move || {
+        let offsets = sample_offsets(device_size(&block_device)?, VERIFICATION_SAMPLES);
+        read_samples(&block_device, &offsets).map(|samples| samples.into_iter().flatten().collect())
+    })
+    .await
+    .map_err(|e| CarbideClientError::GenericError(format!("sampling task failed: {e}")))?
+    .map_err(CarbideClientError::from)
+}
+
+async fn resample_device(
+    device: &MediaDevice,
+    before: &[SectorSample],
+) -> Result<Vec<Option<SectorSample>>, CarbideClientError> {
+    if device.transport == Transport::Nvme {
+        // The namespace was deleted and recreated
+        cmdrun::run_prog(
+            crate::deprovision::scrabbing::NVME_CLI_PROG,
+            ["ns-rescan", device.device.as_str()],
+        )
+        .await?;
+        let deadline = tokio::time::Instant::now() + NVME_NAMESPACE_WAIT;
+        while !Path::new(&device.block_device).exists() {
+            if tokio::time::Instant::now() > deadline {
+                return Err(CarbideClientError::GenericError(format!(
+                    "{} did not reappear after sanitization",
+                    device.block_device
+                )));
+            }
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+    }
+
+    flush_buffers(&device.block_device).await?;
+    let block_device = device.block_device.clone();
+    let offsets: Vec<u64> = before.iter().map(|s| s.offset).collect();
+    tokio::task::spawn_blocking(move || read_samples(&block_device, &offsets))
+        .await
+        .map_err(|e| CarbideClientError::GenericError(format!("sampling task failed: {e}")))?
+        .map_err(CarbideClientError::from)
+}
+
+/// Compares the samples taken before and after sanitization. A sector that
+/// held data and reads back the same was not sanitized. Sectors beyond the end
+/// of a device that shrunk, e.g. a recreated NVMe namespace, are not counted.
+fn verify(
+    before: &[SectorSample],
+    after: &[Option<SectorSample>],
+) -> rpc::MediaSanitizationVerification {
+    let mut sectors_sampled = 0;
+    let mut sectors_unchanged = 0;
+    for (before, after) in before.iter().zip(after) {
+        let Some(after) = after else {
+            continue;
+        };
+        sectors_sampled += 1;
+        if !before.blank && before.digest == after.digest {
+            sectors_unchanged += 1;
+        }
+    }
+    rpc::MediaSanitizationVerification {
+        sectors_sampled,
+        sectors_unchanged,
+        passed: sectors_sampled > 0 && sectors_unchanged == 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lsblk() {
+        let output = r#"{
+            "blockdevices": [
+                {"name": "sda", "type": "disk", "tran": "sata", "serial": "S3Z1NB0K", "model": "SAMSUNG MZ7LH960", "size": 960197124096, "rm": false},
+                {"name": "sdb", "type": "disk", "tran": "sas", "serial": "ZA1", "model": "ST4000NM0025 ", "size": "4000787030016", "rm": "0"},
+                {"name": "sdc", "type": "disk", "tran": "usb", "serial": "U1", "model": "Flash", "size": 1000, "rm": true},
+                {"name": "sdd", "type": "disk", "tran": "sata", "serial": "B1", "model": "DELLBOSS VD", "size": 1000, "rm": false},
+                {"name": "nvme0n1", "type": "disk", "tran": "nvme", "serial": "N1", "model": "NVMe", "size": 1000, "rm": false},
+                {"name": "sr0", "type": "rom", "tran": "sata", "serial": null, "model": null, "size": 1000, "rm": true},
+                {"name": "vda", "type": "disk", "tran": null, "serial": null, "model": null, "size": 1000, "rm": false}
+            ]
+        }"#;
+        let devices = parse_lsblk(output).unwrap();
+        assert_eq!(
+            devices,
+            vec![
+                MediaDevice {
+                    device: "/dev/sda".to_string(),
+                    block_device: "/dev/sda".to_string(),
+                    transport: Transport::Sata,
+                    serial_number: "S3Z1NB0K".to_string(),
+                    model: "SAMSUNG MZ7LH960".to_string(),
+                    capacity_bytes: 960197124096,
+                },
+                MediaDevice {
+                    device: "/dev/sdb".to_string(),
+                    block_device: "/dev/sdb".to_string(),
+                    transport: Transport::Sas,
+                    serial_number: "ZA1".to_string(),
+                    model: "ST4000NM0025".to_string(),
+                    capacity_bytes: 4000787030016,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_mdstat() {
+        let mdstat = "Personalities : [raid1] [raid0]
+md127 : active raid1 sdb1[1] sda1[0](F)
+      976630464 blocks super 1.2 [2/2] [UU]
+      bitmap: 0/8 pages [0KB], 65536KB chunk
+
+md1 : active raid0 nvme1n1[1] nvme0n1p2[0]
+      1875120128 blocks super 1.2 512k chunks
+
+unused devices: <none>
+";
+        assert_eq!(
+            parse_mdstat(mdstat),
+            vec![
+                RaidArray {
+                    name: "md127".to_string(),
+                    members: vec!["sdb1".to_string(), "sda1".to_string()],
+                },
+                RaidArray {
+                    name: "md1".to_string(),
+                    members: vec!["nvme1n1".to_string(), "nvme0n1p2".to_string()],
+                },
+            ]
+        );
+        assert_eq!(sanitized_device_of("nvme0n1p2"), "/dev/nvme0");
+        assert_eq!(sanitized_device_of("nvme1n1"), "/dev/nvme1");
+    }
+
+    #[test]
+    fn test_parse_ata_security() {
+        let identify = "
+Commands/features:
+	Enabled	Supported:
+	   *	SMART feature set
+Security: 
+	Master password revision code = 65534
+		supported
+	not	enabled
+	not	locked
+		frozen
+	not	expired: security count
+		supported: enhanced erase
+	2min for SECURITY ERASE UNIT. 2min for ENHANCED SECURITY ERASE UNIT.
+Logical Unit WWN Device Identifier: 5002538e4084bcc2
+";
+        assert_eq!(
+            parse_ata_security(identify),
+            AtaSecurity {
+                supported: true,
+                enabled: false,
+                locked: false,
+                frozen: true,
+                enhanced_erase: true,
+            }
+        );
+
+        let identify = "Security: 
+	Master password revision code = 65534
+		supported
+	not	enabled
+	not	locked
+	not	frozen
+	not	expired: security count
+	not	supported: enhanced erase
+";
+        let security = parse_ata_security(identify);
+        assert!(security.supported && !security.frozen && !security.enhanced_erase);
+    }
+
+    #[test]
+    fn test_sample_offsets() {
+        let sector = VERIFICATION_SECTOR_SIZE;
+        assert_eq!(sample_offsets(0, 64), Vec::<u64>::new());
+        assert_eq!(sample_offsets(sector - 1, 64), Vec::<u64>::new());
+        // Small devices have fewer sectors than samples
+        assert_eq!(sample_offsets(3 * sector, 64), vec![0, sector, 2 * sector]);
+
+        // Samples span the whole device, including its last sector
+        let size = 8_000_000_000_000u64;
+        let offsets = sample_offsets(size, 64);
+        assert_eq!(offsets.len(), 64);
+        assert_eq!(offsets[0], 0);
+        assert_eq!(*offsets.last().unwrap(), (size / sector - 1) * sector);
+        assert!(offsets.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_verify() {
+        let data = sample_sector(&[1, 2, 3, 4], 0);
+        let zeros = sample_sector(&[0; 4], 4096);
+        let other = sample_sector(&[5, 6, 7, 8], 0);
+        assert!(!data.blank);
+        assert!(zeros.blank);
+
+        // Data overwritten, blank sector unchanged
+        let v = verify(
+            &[data.clone(), zeros.clone()],
+            &[Some(other.clone()), Some(zeros.clone())],
+        );
+        assert_eq!(
+            (v.sectors_sampled, v.sectors_unchanged, v.passed),
+            (2, 0, true)
+        );
+
+        // Data still present
+        let v = verify(
+            &[data.clone(), zeros.clone()],
+            &[Some(data.clone()), Some(zeros)],
+        );
+        assert_eq!(
+            (v.sectors_sampled, v.sectors_unchanged, v.passed),
+            (2, 1, false)
+        );
+
+        // Nothing could be read back
+        let v = verify(&[data], &[None]);
+        assert_eq!(
+            (v.sectors_sampled, v.sectors_unchanged, v.passed),
+            (0, 0, false)
+        );
+    }
+}
@@ -14,8 +14,10 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use std::collections::HashMap;
 use std::fs;
 use std::str::FromStr;
+use std::time::Duration;
 
 use ::rpc::forge as rpc;
 use carbide_host_support::hardware_enumeration::discovery_ibs;
@@ -24,11 +26,11 @@ use regex::Regex;
 use scout::CarbideClientError;
 use serde::Deserialize;
 use smbioslib::SMBiosSystemInformation;
-use tracing::Instrument;
 
 use crate::cfg::Options;
 use crate::client::create_forge_client;
 use crate::deprovision::cmdrun;
+use crate::deprovision::sanitization::{SanitizeMethod, sanitize_all_media};
 use crate::{CarbideClientResult, IN_QEMU_VM};
 
 fn check_memory_overwrite_efi_var() -> Result<(), CarbideClientError> {
@@ -60,7 +62,7 @@ fn check_memory_overwrite_efi_var() -> Result<(), CarbideClientError> {
     }
 }
 
-static NVME_CLI_PROG: &str = "/usr/sbin/nvme";
+pub(super) static NVME_CLI_PROG: &str = "/usr/sbin/nvme";
 static LENOVO_NVMI_CLI_PROG_CANDIDATES: [&str; 4] = [
     "/opt/forge/bin/mnv_cli",
     "/opt/forge/mnv_cli",
@@ -78,13 +80,12 @@ fn resolve_lenovo_mnv_cli_prog() -> Option<&'static str> {
 lazy_static::lazy_static! {
     static ref NVME_NS_RE: Regex = Regex::new(r".*:(0x[0-9]+)").unwrap();
     static ref NVME_NSID_RE: Regex = Regex::new(r".*nsid:([0-9]+)").unwrap();
-    static ref NVME_DEV_RE: Regex = Regex::new(r"/dev/nvme[0-9]+$").unwrap();
 }
 
 #[derive(Deserialize, Debug)]
-struct NvmeParams {
+pub(super) struct NvmeParams {
     // size of NVME drive in bytes
-    tnvmcap: u64,
+    pub tnvmcap: u64,
 
     // controller ID
     cntlid: u64,
@@ -92,16 +93,26 @@ struct NvmeParams {
     // Optional Admin Command Support (OACS)
     oacs: u64,
 
+    // Sanitize Capabilities (SANICAP)
+    #[serde(default)]
+    sanicap: u64,
+
     // serial number
-    sn: String,
+    pub sn: String,
 
     // manufacturer
-    mn: String,
+    pub mn: String,
 
     // firmware version
     fr: String,
 }
 
+#[derive(Deserialize, Debug)]
+struct NvmeSanitizeLog {
+    // Sanitize Status (SSTAT)
+    sstat: u64,
+}
+
 #[derive(Deserialize, Debug)]
 struct NvmeLbaFormat {
     // metadata size
@@ -120,7 +131,7 @@ struct NvmeNamespaceParams {
     lbafs: Vec<NvmeLbaFormat>,
 }
 
-async fn get_nvme_params(nvmename: &str) -> Result<NvmeParams, CarbideClientError> {
+pub(super) async fn get_nvme_params(nvmename: &str) -> Result<NvmeParams, CarbideClientError> {
     let nvme_params_lines =
         cmdrun::run_prog(NVME_CLI_PROG, ["id-ctrl", nvmename, "-o", "json"]).await?;
     let nvme_drive_params = match serde_json::from_str(&nvme_params_lines) {
@@ -230,7 +241,75 @@ async fn get_best_lba_format(nvmename: &str) -> Result<(u8, u64), CarbideClientE
     }
 }
 
-async fn clean_this_nvme(nvmename: &String) -> Result<(), CarbideClientError> {
+/// The `nvme sanitize` actions, strongest first, with the SANICAP bit that
+/// tells whether a controller supports them.
+const NVME_SANITIZE_ACTIONS: [(&str, u64, SanitizeMethod); 2] = [
+    (
+        "4",
+        0x1,
+        SanitizeMethod::purge("nvme-sanitize-crypto-erase"),
+    ),
+    ("2", 0x2, SanitizeMethod::purge("nvme-sanitize-block-erase")),
+];
+const NVME_SANITIZE_POLL_INTERVAL: Duration = Duration::from_secs(10);
+const NVME_SANITIZE_TIMEOUT: Duration = Duration::from_secs(4 * 60 * 60);
+
+/// Whether a controller supports any of the [`NVME_SANITIZE_ACTIONS`].
+fn supports_sanitize(nvme_drive_params: &NvmeParams) -> bool {
+    NVME_SANITIZE_ACTIONS
+        .iter()
+        .any(|(_, sanicap_bit, _)| nvme_drive_params.sanicap & sanicap_bit != 0)
+}
+
+/// Sanitizes the whole NVM subsystem of a controller with the strongest
+/// sanitize action it supports, and waits for the sanitize to complete.
+async fn sanitize_nvme(
+    nvmename: &str,
+    nvme_drive_params: &NvmeParams,
+) -> Result<SanitizeMethod, CarbideClientError> {
+    let (action, _, method) = NVME_SANITIZE_ACTIONS
+        .iter()
+        .find(|(_, sanicap_bit, _)| nvme_drive_params.sanicap & sanicap_bit != 0)
+        .ok_or_else(|| {
+            CarbideClientError::GenericError(format!(
+                "{nvmename} supports neither a secure erase format nor sanitize"
+            ))
+        })?;
+
+    tracing::info!(method = method.name, "Sanitizing {}", nvmename);
+    cmdrun::run_prog(NVME_CLI_PROG, ["sanitize", nvmename, "-a", *action]).await?;
+
+    let deadline = tokio::time::Instant::now() + NVME_SANITIZE_TIMEOUT;
+    loop {
+        tokio::time::sleep(NVME_SANITIZE_POLL_INTERVAL).await;
+        let log_lines =
+            cmdrun::run_prog(NVME_CLI_PROG, ["sanitize-log", nvmename, "-o", "json"]).await?;
+        // nvme-cli keys the log by the device name
+        let logs: HashMap<String, NvmeSanitizeLog> =
+            serde_json::from_str(&log_lines).map_err(|e| {
+                CarbideClientError::GenericError(format!("nvme sanitize-log parse error: {e}"))
+            })?;
+        let status = logs.values().next().map(|log| log.sstat & 0x7);
+        match status {
+            // Completed successfully, with or without deallocation
+            Some(1) | Some(4) => return Ok(*method),
+            // In progress
+            Some(2) => {}
+            _ => {
+                return Err(CarbideClientError::GenericError(format!(
+                    "nvme sanitize of {nvmename} failed with status {status:?}"
+                )));
+            }
+        }
+        if tokio::time::Instant::now() > deadline {
+            return Err(CarbideClientError::GenericError(format!(
+                "nvme sanitize of {nvmename} did not complete within {NVME_SANITIZE_TIMEOUT:?}"
+            )));
+        }
+    }
+}
+
+pub(super) async fn clean_this_nvme(nvmename: &str) -> Result<SanitizeMethod, CarbideClientError> {
     tracing::debug!("cleaning {}", nvmename);
 
     let nvme_drive_params = get_nvme_params(nvmename).await?;
@@ -249,6 +328,10 @@ async fn clean_this_nvme(nvmename: &String) -> Result<(), CarbideClientError> {
         nvme_drive_params.fr
     );
 
+    // format with "-s2" is a cryptographic erase
+    let mut method = SanitizeMethod::purge("nvme-format-crypto-erase");
+    let mut format_error = None;
+
     if nvme_drive_params.mn.trim() == "M.2 NVMe 2-Bay RAID Kit" {
         let lenovo_mnv_cli_prog = resolve_lenovo_mnv_cli_prog().ok_or_else(|| {
             CarbideClientError::GenericError(format!(
@@ -312,6 +395,8 @@ async fn clean_this_nvme(nvmename: &String) -> Result<(), CarbideClientError> {
             ],
         )
         .await?;
+        // Format NVM with Secure Erase Settings = 1
+        method = SanitizeMethod::purge("nvme-format-user-data-erase");
     } else {
         // list all namespaces
         let nvmens_output = cmdrun::run_prog(NVME_CLI_PROG, ["list-ns", nvmename, "-a"]).await?;
@@ -332,8 +417,10 @@ async fn clean_this_nvme(nvmename: &String) -> Result<(), CarbideClientError> {
                 Ok(_) => (),
                 Err(e) => {
                     if namespaces_supported {
-                        // format can fail if there is a wrong params for namespace. We delete it anyway.
+                        // format can fail if there is a wrong params for namespace. We delete it anyway,
+                        // and sanitize the controller instead if it supports that.
                         tracing::debug!("nvme format error: {}", e);
+                        format_error.get_or_insert(e);
                     } else {
                         return Err(e);
                     }
@@ -345,6 +432,24 @@ async fn clean_this_nvme(nvmename: &String) -> Result<(), CarbideClientError> {
             }
         }
 
+        // Deleting the namespaces does not erase their data. Without sanitize
+        // support the format error is reported once the namespace has been
+        // recreated, so that the drive stays usable.
+        let mut unsanitized = None;
+        if let Some(format_error) = format_error {
+            if supports_sanitize(&nvme_drive_params) {
+                method = sanitize_nvme(nvmename, &nvme_drive_params)
+                    .await
+                    .map_err(|e| {
+                        CarbideClientError::GenericError(format!(
+                            "{format_error}; sanitizing instead failed: {e}"
+                        ))
+                    })?;
+            } else {
+                unsanitized = Some(format_error);
+            }
+        }
+
         if namespaces_supported {
             let (flbas_index, sector_size) = get_best_lba_format(nvmename).await?;
             let sectors = nvme_drive_params.tnvmcap / sector_size;
@@ -393,113 +498,13 @@ async fn clean_this_nvme(nvmename: &String) -> Result<(), CarbideClientError> {
             )
             .await?;
         }
-    }
-    tracing::debug!("Cleanup completed for nvme device {}", nvmename);
-    Ok(())
-}
-
-/// Failed NVMe device cleanup with error context
-struct CleanupFailure {
-    device: String,
-    duration: std::time::Duration,
-    error: CarbideClientError,
-}
-
-async fn all_nvme_cleanup() -> Result<(), CarbideClientError> {
-    let mut nvme_devicepaths: Vec<String> = Vec::new();
-    if let Ok(paths) = fs::read_dir("/dev") {
-        for entry in paths {
-            let path = match entry {
-                Ok(o) => o.path(),
-                Err(_) => continue,
-            };
-            if path.is_dir() {
-                continue;
-            }
-
-            let nvmename = path.to_string_lossy().to_string();
-            if NVME_DEV_RE.is_match(&nvmename) {
-                nvme_devicepaths.push(nvmename);
-            }
-        }
-    }
-
-    let device_count = nvme_devicepaths.len();
-    if device_count == 0 {
-        tracing::info!("No NVMe devices found to clean");
-        return Ok(());
-    }
-
-    tracing::info!(device_count, "Starting NVMe cleanup");
-    let start_time = std::time::Instant::now();
-
-    // Spawn async tasks for each NVMe device cleanup
-    let cleanup_futures: Vec<_> = nvme_devicepaths
-        .into_iter()
-        .map(|nvmename| {
-            let device = nvmename.clone();
-            let span = tracing::info_span!("nvme_cleanup", device = %nvmename);
 
-            tokio::spawn(
-                async move {
-                    let device_start = std::time::Instant::now();
-
-                    tracing::info!("Starting cleanup");
-                    let result = clean_this_nvme(&nvmename).await;
-                    let duration = device_start.elapsed();
-
-                    match result {
-                        Ok(()) => {
-                            tracing::info!(?duration, "Cleanup completed successfully");
-                            Ok(())
-                        }
-                        Err(error) => {
-                            tracing::error!(?duration, %error, "Cleanup failed");
-                            Err(CleanupFailure {
-                                device,
-                                duration,
-                                error,
-                            })
-                        }
-                    }
-                }
-                .instrument(span),
-            )
-        })
-        .collect();
-
-    // Wait for all cleanup tasks to complete
-    let results = futures_util::future::join_all(cleanup_futures).await;
-    let total_duration = start_time.elapsed();
-
-    // Collect and categorize results
-    let mut errors: Vec<String> = Vec::new();
-    let mut success_count = 0;
-
-    for join_result in results {
-        let cleanup_result = join_result.expect("nvme cleanup task panicked");
-        match cleanup_result {
-            Ok(()) => success_count += 1,
-            Err(failure) => errors.push(format!(
-                "NVME_CLEAN_ERROR (device: {}; duration: {:?}): {}",
-                failure.device, failure.duration, failure.error,
-            )),
+        if let Some(format_error) = unsanitized {
+            return Err(format_error);
         }
     }
-
-    tracing::info!(
-        device_count,
-        success_count,
-        error_count = errors.len(),
-        ?total_duration,
-        "NVMe cleanup completed"
-    );
-
-    if !errors.is_empty() {
-        return Err(CarbideClientError::GenericError(errors.join("\n")));
-    }
-
-    Ok(())
+    tracing::debug!("Cleanup completed for nvme device {}", nvmename);
+    Ok(method)
 }
 
 // #[derive(Debug)]
@@ -719,6 +724,7 @@ async fn do_cleanup(machine_id: &MachineId) -> CarbideClientResult<rpc::MachineC
         ram: None,
         mem_overwrite: None,
         ib: None,
+        media_sanitization: vec![],
         result: rpc::machine_cleanup_info::CleanupResult::Ok as _,
     };
 
@@ -729,24 +735,27 @@ async fn do_cleanup(machine_id: &MachineId) -> CarbideClientResult<rpc::MachineC
     };
 
     if stdin_link == "/dev/null" {
-        match all_nvme_cleanup().await {
-            Ok(_) => {
+        let outcome = sanitize_all_media().await;
+        let errors = outcome.errors();
+        cleanup_result.media_sanitization = outcome.records;
+        match errors {
+            None => {
                 cleanup_result.nvme = Some(rpc::machine_cleanup_info::CleanupStepResult {
                     result: rpc::machine_cleanup_info::CleanupResult::Ok as _,
                     message: "OK".to_string(),
                 });
             }
-            Err(e) => {
-                tracing::error!("{}", e);
+            Some(errors) => {
+                tracing::error!("{}", errors);
                 cleanup_result.nvme = Some(rpc::machine_cleanup_info::CleanupStepResult {
                     result: rpc::machine_cleanup_info::CleanupResult::Error as _,
-                    message: e.to_string(),
+                    message: errors,
                 });
                 cleanup_result.result = rpc::machine_cleanup_info::CleanupResult::Error as _;
             }
         }
     } else {
-        tracing::info!("stdin == {}. Skip nvme cleanup.", stdin_link);
+        tracing::info!("stdin == {}. Skip media sanitization.", stdin_link);
     }
 
     match check_memory_overwrite_efi_var() {
@@ -849,12 +858,12 @@ pub async fn run_no_api(tpm_path: &str) -> Result<(), CarbideClientError> {
     crate::tpm::clear_tpm(tpm_path)?;
 
     if stdin_link == "/dev/null" {
-        match all_nvme_cleanup().await {
-            Ok(_) => tracing::debug!("nvme cleanup OK"),
-            Err(e) => tracing::error!("nvme cleanup error: {}", e),
+        match sanitize_all_media().await.errors() {
+            None => tracing::debug!("media sanitization OK"),
+            Some(errors) => tracing::error!("media sanitization error: {}", errors),
         }
     } else {
-        tracing::info!("stdin == {}. Skip nvme cleanup.", stdin_link);
+        tracing::info!("stdin == {}. Skip media sanitization.", stdin_link);
     }
 
     // P1 errors are propagated (fail startup), P2 errors are handled internally in reset_ib_devices()
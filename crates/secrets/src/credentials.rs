@@ -347,6 +347,7 @@ pub enum MqttCredentialType {
     Dpa,
     DsxExchangeEventBus,
     DsxExchangeConsumer,
+    AlertDispatch,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -578,6 +579,7 @@ impl CredentialKey {
                 MqttCredentialType::DsxExchangeConsumer => {
                     Cow::from("mqtt/dsx-exchange-consumer/auth")
                 }
+                MqttCredentialType::AlertDispatch => Cow::from("mqtt/alert-dispatch/auth"),
             },
             CredentialKey::MachineIdentityEncryptionKey { key_id } => {
                 Cow::from(format!("machine_identity/encryption_keys/{key_id}"))
@@ -809,6 +811,12 @@ mod tests {
                 },
                 "mqtt/",
             ),
+            (
+                CredentialKey::MqttAuth {
+                    credential_type: MqttCredentialType::AlertDispatch,
+                },
+                "mqtt/",
+            ),
         ];
 
         for (key, expected_prefix) in &cases {
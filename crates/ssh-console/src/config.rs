@@ -115,6 +115,21 @@ pub struct Config {
     pub log_rotate_max_rotated_files: usize,
     #[serde(default = "Defaults::cert_authorization")]
     pub openssh_certificate_authorization: CertAuthorization,
+    #[serde(default)]
+    pub web_console: Option<WebConsoleConfig>,
+}
+
+/// Configuration for the WebSocket console endpoint used by the carbide web UI. Fields are
+/// documented in the `[web_console]` example of [`Config::into_annotated_config_file`].
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct WebConsoleConfig {
+    #[serde(default = "Defaults::web_console_listen_address")]
+    pub listen_address: SocketAddr,
+    pub private_cookiejar_key_path: PathBuf,
+    #[serde(default)]
+    pub admin_groups: Vec<String>,
+    #[serde(default)]
+    pub allowed_origins: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
@@ -223,6 +238,7 @@ impl Config {
             log_rotate_max_size,
             log_rotate_max_rotated_files,
             openssh_certificate_authorization,
+            web_console: _,
         } = self;
         let api_poll_interval = format!("{}s", api_poll_interval.as_secs());
         let reconnect_interval_base = format!("{}s", reconnect_interval_base.as_secs());
@@ -363,6 +379,19 @@ user_field = {cert_authorization_keyid_format_user_field:?}
 role_field = {cert_authorization_keyid_format_role_field:?}
 role_separator = {cert_authorization_keyid_format_role_separator:?}
 
+## Optional: Serve serial consoles over WebSocket to the carbide web UI. Sessions are authenticated
+## with the web UI's encrypted session cookies, so this listener must be reachable on the web UI's
+## hostname (e.g. a different port, or a path routed by the ingress) over TLS.
+# [web_console]
+# listen_address = "[::]:8081"
+# # File holding the same key as carbide-api's CARBIDE_WEB_PRIVATE_COOKIEJAR_KEY
+# private_cookiejar_key_path = "/var/run/secrets/carbide-web/cookiejar-key"
+# # Web UI groups allowed to open consoles. Sessions for these groups can open any console, like
+# # admin_certificate_role does for SSH.
+# admin_groups = ["<group>"]
+# # Origins allowed to open WebSocket connections (the web UI's URL)
+# allowed_origins = ["https://carbide.example.com"]
+
 ## Optional: For development mode, you can hardcode a list of BMC's to talk to.
 # [[bmcs]]
 # # machine_id: the machine ID this BMC overrides
@@ -471,6 +500,7 @@ impl Default for Config {
             override_bmc_ssh_host: None,
             admin_certificate_role: None,
             openssh_certificate_ca_fingerprints: vec![],
+            web_console: None,
         }
     }
 }
@@ -511,6 +541,12 @@ impl Defaults {
             .expect("BUG: default listen_address is invalid")
     }
 
+    pub fn web_console_listen_address() -> SocketAddr {
+        "[::]:8081"
+            .parse()
+            .expect("BUG: default web_console listen_address is invalid")
+    }
+
     pub fn host_key_path() -> PathBuf {
         "/etc/ssh/ssh_host_ed25519_key".into()
     }
@@ -724,6 +760,32 @@ mod tests {
         assert_eq!(partial_config, Config::default());
     }
 
+    #[test]
+    fn test_web_console_config() {
+        let config = indoc! {r#"
+        [web_console]
+        private_cookiejar_key_path = "/tmp/cookiejar-key"
+        admin_groups = ["carbide-admins"]
+        allowed_origins = ["https://carbide.example.com"]
+        "#};
+
+        let config = toml::from_str::<Config>(config).expect("Couldn't parse config toml");
+        let web_console = config.web_console.expect("web_console should be set");
+        assert_eq!(
+            web_console.listen_address,
+            Defaults::web_console_listen_address()
+        );
+        assert_eq!(
+            web_console.private_cookiejar_key_path,
+            PathBuf::from("/tmp/cookiejar-key")
+        );
+        assert_eq!(web_console.admin_groups, vec!["carbide-admins".to_string()]);
+        assert_eq!(
+            web_console.allowed_origins,
+            vec!["https://carbide.example.com".to_string()]
+        );
+    }
+
     #[test]
     fn test_authz_partial_config_no_strategy() {
         let partial_config = indoc! {r#"
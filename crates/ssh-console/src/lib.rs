@@ -20,6 +20,7 @@ mod io_util;
 mod metrics;
 mod ssh_cert_parsing;
 mod ssh_server;
+mod web_console;
 
 mod console_logger;
 mod frontend;
@@ -57,22 +58,35 @@ pub async fn spawn(config: Config) -> Result<SpawnHandle, SpawnError> {
         .map_err(|_| SpawnError::ClientPoolUnknownFailure)?;
 
     // 2) Start SSH server itself
+    let server_metrics = Arc::new(ssh_server::ServerMetrics::new(&metrics.meter, &config));
     let server = ssh_server::spawn(
         config.clone(),
         forge_api_client.clone(),
         bmc_client_pool.connection_store(),
-        &metrics.meter,
+        server_metrics.clone(),
     )
     .await?;
 
-    // 3) Start metrics server
+    // 3) Start the web console server, if configured
+    let web_console_server = web_console::spawn(
+        config.clone(),
+        forge_api_client.clone(),
+        bmc_client_pool.connection_store(),
+        server_metrics,
+    )
+    .await?;
+
+    // 4) Start metrics server
     let metrics_handle = metrics::spawn(config.clone(), metrics).await?;
 
-    // 4) Wait for a shutdown signal, then shut down the above
+    // 5) Wait for a shutdown signal, then shut down the above
     let (shutdown_tx, shutdown_rx) = oneshot::channel();
     let join_handle = tokio::spawn(async move {
         shutdown_rx.await.ok();
         metrics_handle.shutdown_and_wait().await;
+        if let Some(web_console_server) = web_console_server {
+            web_console_server.shutdown_and_wait().await;
+        }
         bmc_client_pool.shutdown_and_wait().await;
         server.shutdown_and_wait().await;
     });
@@ -89,6 +103,8 @@ pub enum SpawnError {
     ClientPoolUnknownFailure,
     #[error("Error spawning SSH server: {0}")]
     SshServerSpawn(#[from] ssh_server::SpawnError),
+    #[error("Error spawning web console server: {0}")]
+    WebConsoleSpawn(#[from] web_console::SpawnError),
     #[error("Error spawning metrics server: {0}")]
     MetricsSpawn(#[from] metrics::SpawnError),
 }
@@ -38,9 +38,8 @@ pub async fn spawn(
     config: Arc<Config>,
     forge_api_client: ForgeApiClient,
     bmc_connection_store: BmcConnectionStore,
-    meter: &Meter,
+    metrics: Arc<ServerMetrics>,
 ) -> Result<Handle, SpawnError> {
-    let metrics = Arc::new(ServerMetrics::new(meter, &config));
     let listen_address = config.listen_address;
     use SpawnError::*;
 
@@ -204,7 +203,7 @@ pub struct ServerMetrics {
 }
 
 impl ServerMetrics {
-    fn new(meter: &Meter, config: &Config) -> ServerMetrics {
+    pub(crate) fn new(meter: &Meter, config: &Config) -> ServerMetrics {
         Self {
             total_clients: meter
                 .i64_up_down_counter("ssh_console_total_clients")
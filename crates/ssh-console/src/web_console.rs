@@ -0,0 +1,561 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//! Serve BMC serial consoles over WebSocket, for the carbide web UI's console page.
+//!
+//! Clients are authenticated with the web UI's own session: carbide-api stores the OAuth2 session
+//! in encrypted ("private") cookies, and ssh-console is configured with the same cookie jar key so
+//! it can decrypt and check them. Web UI sessions are authorized like admin SSH certificates: if
+//! the session's group is one of the configured `admin_groups`, any console can be opened.
+//!
+//! Protocol: console output is sent to the client as binary messages. Binary messages from the
+//! client are forwarded to the BMC as keyboard input, and text messages are JSON
+//! [`ClientMessage`]s for out-of-band requests like terminal resizes.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use axum::Router;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{ConnectInfo, Path, State};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum_extra::extract::cookie::{Key, PrivateCookieJar};
+use futures::{SinkExt, StreamExt};
+use http::{HeaderMap, StatusCode, header};
+use lazy_static::lazy_static;
+use rpc::forge_api_client::ForgeApiClient;
+use russh::ChannelMsg;
+use serde::Deserialize;
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+
+use crate::bmc::client::BmcConnectionSubscription;
+use crate::bmc::client_pool::{BmcConnectionStore, GetConnectionError};
+use crate::bmc::message_proxy::ToBmcMessage;
+use crate::config::Config;
+use crate::shutdown_handle::ShutdownHandle;
+use crate::ssh_server::ServerMetrics;
+
+static BANNER_WEB: &str = "\
++------------------------------------------------------------------------------+\r\n\
+|                NVIDIA Carbide Web Serial Console (beta)                      |\r\n\
++------------------------------------------------------------------------------+\r\n\
+";
+
+lazy_static! {
+    static ref WEB_SESSION_AUTH_FAILURE_METRIC: [opentelemetry::KeyValue; 1] =
+        [opentelemetry::KeyValue::new("auth_type", "web_session")];
+}
+
+/// Spawn the web console server if `[web_console]` is configured, returning `None` otherwise.
+pub async fn spawn(
+    config: Arc<Config>,
+    forge_api_client: ForgeApiClient,
+    bmc_connection_store: BmcConnectionStore,
+    metrics: Arc<ServerMetrics>,
+) -> Result<Option<Handle>, SpawnError> {
+    use SpawnError::*;
+    let Some(web_console_config) = config.web_console.as_ref() else {
+        return Ok(None);
+    };
+    let listen_address = web_console_config.listen_address;
+    let key_path = web_console_config
+        .private_cookiejar_key_path
+        .to_string_lossy()
+        .to_string();
+
+    // carbide-api takes the key from an environment variable as-is, but secrets mounted as files
+    // usually end with a newline.
+    let key_bytes =
+        std::fs::read(&web_console_config.private_cookiejar_key_path).map_err(|error| {
+            ReadingCookiejarKey {
+                path: key_path.clone(),
+                error,
+            }
+        })?;
+    let cookiejar_key =
+        Key::try_from(key_bytes.trim_ascii_end()).map_err(|error| InvalidCookiejarKey {
+            path: key_path,
+            error: error.to_string(),
+        })?;
+
+    let state = Arc::new(WebConsoleState {
+        forge_api_client,
+        bmc_connection_store,
+        metrics,
+        session_auth: WebSessionAuth {
+            cookiejar_key,
+            admin_groups: web_console_config.admin_groups.clone(),
+            allowed_origins: web_console_config.allowed_origins.clone(),
+        },
+        config: config.clone(),
+    });
+
+    let router = Router::new()
+        .route("/console/{machine_or_instance_id}", get(console))
+        .with_state(state);
+
+    let listener = TcpListener::bind(listen_address)
+        .await
+        .map_err(|error| Listening {
+            addr: listen_address,
+            error,
+        })?;
+    tracing::info!("web console listening on {}", listen_address);
+
+    let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+    let join_handle = tokio::spawn(async move {
+        let result = axum::serve(
+            listener,
+            router.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .with_graceful_shutdown(async move {
+            shutdown_rx.await.ok();
+            tracing::info!("web console service shutting down");
+        })
+        .await;
+        if let Err(error) = result {
+            tracing::error!(%error, "web console server exited with error");
+        }
+    });
+
+    Ok(Some(Handle {
+        shutdown_tx,
+        join_handle,
+    }))
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum SpawnError {
+    #[error("Error reading web console cookie jar key at {path}: {error}")]
+    ReadingCookiejarKey { path: String, error: std::io::Error },
+    #[error("Invalid web console cookie jar key at {path}: {error}")]
+    InvalidCookiejarKey { path: String, error: String },
+    #[error("Error listening on {addr}: {error}")]
+    Listening {
+        addr: SocketAddr,
+        error: std::io::Error,
+    },
+}
+
+pub struct Handle {
+    shutdown_tx: oneshot::Sender<()>,
+    join_handle: JoinHandle<()>,
+}
+
+impl ShutdownHandle<()> for Handle {
+    fn into_parts(self) -> (oneshot::Sender<()>, JoinHandle<()>) {
+        (self.shutdown_tx, self.join_handle)
+    }
+}
+
+struct WebConsoleState {
+    config: Arc<Config>,
+    forge_api_client: ForgeApiClient,
+    bmc_connection_store: BmcConnectionStore,
+    metrics: Arc<ServerMetrics>,
+    session_auth: WebSessionAuth,
+}
+
+struct WebSessionAuth {
+    cookiejar_key: Key,
+    admin_groups: Vec<String>,
+    allowed_origins: Vec<String>,
+}
+
+impl WebSessionAuth {
+    /// Check the web UI session in the request's cookies, returning the name of the user.
+    fn authenticate(&self, headers: &HeaderMap) -> Result<String, WebAuthError> {
+        use WebAuthError::*;
+
+        // Browsers always send Origin on WebSocket requests. Checking it stops other sites from
+        // opening a console with the user's cookies.
+        if let Some(origin) = headers.get(header::ORIGIN) {
+            let origin = origin.to_str().unwrap_or_default();
+            if !self.allowed_origins.iter().any(|allowed| allowed == origin) {
+                return Err(OriginNotAllowed(origin.to_owned()));
+            }
+        }
+
+        let cookiejar = PrivateCookieJar::from_headers(headers, self.cookiejar_key.clone());
+
+        // Same check as the web UI: `sid` holds the session's expiration timestamp.
+        let Some(expiration_timestamp) = cookiejar
+            .get("sid")
+            .and_then(|cookie| cookie.value().parse::<u64>().ok())
+        else {
+            return Err(MissingSession);
+        };
+        let now_seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("implausible future date")
+            .as_secs();
+        if now_seconds >= expiration_timestamp {
+            return Err(SessionExpired);
+        }
+
+        let group = cookiejar
+            .get("group_name")
+            .map(|cookie| cookie.value().to_owned());
+        if !group
+            .as_ref()
+            .is_some_and(|group| self.admin_groups.contains(group))
+        {
+            return Err(GroupNotAllowed(group));
+        }
+
+        Ok(cookiejar
+            .get("unique_name")
+            .map(|cookie| cookie.value().to_owned())
+            .unwrap_or_else(|| "<unknown>".to_string()))
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+enum WebAuthError {
+    #[error("origin {0:?} is not in allowed_origins")]
+    OriginNotAllowed(String),
+    #[error("no web UI session")]
+    MissingSession,
+    #[error("web UI session expired")]
+    SessionExpired,
+    #[error("web UI group {0:?} is not in admin_groups")]
+    GroupNotAllowed(Option<String>),
+}
+
+/// Messages clients can send as WebSocket text messages.
+#[derive(Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ClientMessage {
+    Resize { cols: u32, rows: u32 },
+}
+
+async fn console(
+    State(state): State<Arc<WebConsoleState>>,
+    Path(machine_string): Path<String>,
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    ws: WebSocketUpgrade,
+) -> Response {
+    let peer_addr = peer_addr.to_string();
+
+    let user = match state.session_auth.authenticate(&headers) {
+        Ok(user) => user,
+        Err(error) if state.config.insecure => {
+            tracing::info!(
+                peer_addr,
+                %error,
+                "Overriding web session rejection because we are in insecure (testing) mode"
+            );
+            "<insecure>".to_string()
+        }
+        Err(error) => {
+            tracing::warn!(
+                peer_addr,
+                machine = machine_string,
+                %error,
+                "web console authentication failed",
+            );
+            state
+                .metrics
+                .client_auth_failures_total
+                .add(1, WEB_SESSION_AUTH_FAILURE_METRIC.as_slice());
+            return (StatusCode::FORBIDDEN, error.to_string()).into_response();
+        }
+    };
+
+    let bmc_connection = match state
+        .bmc_connection_store
+        .get_connection(
+            &machine_string,
+            &state.config,
+            &state.forge_api_client,
+            state.metrics.clone(),
+        )
+        .await
+    {
+        Ok(bmc_connection) => bmc_connection,
+        Err(error) => {
+            tracing::warn!(
+                peer_addr,
+                machine = machine_string,
+                %error,
+                "Could not get BMC connection for web console"
+            );
+            let status = match error {
+                GetConnectionError::InstanceIdLookupFailure { .. } => StatusCode::BAD_GATEWAY,
+                _ => StatusCode::NOT_FOUND,
+            };
+            return (status, error.to_string()).into_response();
+        }
+    };
+
+    tracing::info!(
+        peer_addr,
+        %user,
+        machine = machine_string,
+        "web session auth succeeded, opening web console"
+    );
+    ws.on_upgrade(move |socket| proxy_session(socket, bmc_connection, peer_addr))
+}
+
+/// Proxy messages between the BMC and the WebSocket client until either side goes away.
+async fn proxy_session(
+    socket: WebSocket,
+    bmc_connection: BmcConnectionSubscription,
+    peer_addr: String,
+) {
+    let machine_id = bmc_connection.machine_id;
+    let Some(mut from_bmc_rx) = bmc_connection
+        .to_frontend_msg_weak_tx
+        .upgrade()
+        .map(|tx| tx.subscribe())
+    else {
+        tracing::warn!(
+            peer_addr,
+            %machine_id,
+            "BMC connection dropped before we could subscribe to messages"
+        );
+        return;
+    };
+
+    let (mut ws_tx, mut ws_rx) = socket.split();
+    if ws_tx
+        .send(Message::Binary(BANNER_WEB.as_bytes().into()))
+        .await
+        .is_err()
+    {
+        return;
+    }
+
+    // Tell the backend to return any "pending line": data since the last newline
+    let (pending_line_reply_tx, pending_line_reply_rx) = oneshot::channel();
+    bmc_connection
+        .to_bmc_msg_tx
+        .send(ToBmcMessage::EchoConnectionMessage {
+            reply_tx: pending_line_reply_tx,
+        })
+        .await
+        .ok();
+    if let Ok(pending_line) = pending_line_reply_rx.await {
+        ws_tx.send(Message::Binary(pending_line.into())).await.ok();
+    }
+
+    loop {
+        tokio::select! {
+            res = from_bmc_rx.recv() => match res {
+                Ok(msg) => {
+                    let msg = Arc::<ChannelMsg>::from(msg);
+                    let data = match msg.as_ref() {
+                        ChannelMsg::Data { data } | ChannelMsg::ExtendedData { data, .. } => {
+                            data.iter().as_slice().to_vec()
+                        }
+                        ChannelMsg::Eof | ChannelMsg::Close => break,
+                        _ => continue,
+                    };
+                    if let Err(error) = ws_tx.send(Message::Binary(data.into())).await {
+                        tracing::debug!(
+                            peer_addr,
+                            %error,
+                            "error sending message to web console client, likely disconnected"
+                        );
+                        break;
+                    }
+                }
+                Err(_) => {
+                    tracing::debug!(peer_addr, "BMC channel closed when writing message to web console client");
+                    break;
+                }
+            },
+            res = ws_rx.next() => {
+                let to_bmc_msg = match res {
+                    Some(Ok(Message::Binary(data))) => ChannelMsg::Data {
+                        data: data.to_vec().into(),
+                    },
+                    Some(Ok(Message::Text(text))) => match serde_json::from_str(text.as_str()) {
+                        Ok(ClientMessage::Resize { cols, rows }) => ChannelMsg::WindowChange {
+                            col_width: cols,
+                            row_height: rows,
+                            pix_width: 0,
+                            pix_height: 0,
+                        },
+                        Err(error) => {
+                            tracing::debug!(peer_addr, %error, "ignoring invalid web console client message");
+                            continue;
+                        }
+                    },
+                    // Pings are answered by axum
+                    Some(Ok(Message::Ping(_) | Message::Pong(_))) => continue,
+                    Some(Ok(Message::Close(_))) | None => {
+                        tracing::debug!(peer_addr, "web console client disconnected");
+                        break;
+                    }
+                    Some(Err(error)) => {
+                        tracing::debug!(peer_addr, %error, "error reading from web console client");
+                        break;
+                    }
+                };
+                if bmc_connection
+                    .to_bmc_msg_tx
+                    .send(ToBmcMessage::ChannelMsg(to_bmc_msg))
+                    .await
+                    .is_err()
+                {
+                    tracing::debug!(peer_addr, %machine_id, "BMC disconnected while forwarding web console input");
+                    break;
+                }
+            }
+        }
+    }
+
+    ws_tx.close().await.ok();
+    tracing::info!(peer_addr, "end web console connection");
+}
+
+#[cfg(test)]
+mod tests {
+    use axum_extra::extract::cookie::Cookie;
+    use http::HeaderValue;
+
+    use super::*;
+
+    const ORIGIN: &str = "https://carbide.example.com";
+
+    fn session_auth() -> WebSessionAuth {
+        WebSessionAuth {
+            cookiejar_key: Key::generate(),
+            admin_groups: vec!["carbide-admins".to_string()],
+            allowed_origins: vec![ORIGIN.to_string()],
+        }
+    }
+
+    /// Build request headers carrying the given cookies, encrypted the same way the web UI does.
+    fn request_headers(key: &Key, cookies: &[(&'static str, String)]) -> HeaderMap {
+        let jar = cookies
+            .iter()
+            .fold(PrivateCookieJar::new(key.clone()), |jar, (name, value)| {
+                jar.add(Cookie::new(*name, value.clone()))
+            });
+        let cookie_header = jar
+            .into_response()
+            .headers()
+            .get_all(header::SET_COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok()?.split(';').next().map(str::to_owned))
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::COOKIE,
+            HeaderValue::from_str(&cookie_header).unwrap(),
+        );
+        headers.insert(header::ORIGIN, HeaderValue::from_static(ORIGIN));
+        headers
+    }
+
+    fn now_seconds() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+
+    #[test]
+    fn test_valid_session_is_accepted() {
+        let auth = session_auth();
+        let headers = request_headers(
+            &auth.cookiejar_key,
+            &[
+                ("sid", (now_seconds() + 600).to_string()),
+                ("group_name", "carbide-admins".to_string()),
+                ("unique_name", "jdoe".to_string()),
+            ],
+        );
+        assert_eq!(auth.authenticate(&headers).unwrap(), "jdoe");
+    }
+
+    #[test]
+    fn test_expired_session_is_rejected() {
+        let auth = session_auth();
+        let headers = request_headers(
+            &auth.cookiejar_key,
+            &[
+                ("sid", (now_seconds() - 1).to_string()),
+                ("group_name", "carbide-admins".to_string()),
+            ],
+        );
+        assert!(matches!(
+            auth.authenticate(&headers),
+            Err(WebAuthError::SessionExpired)
+        ));
+    }
+
+    #[test]
+    fn test_session_from_other_key_is_rejected() {
+        let auth = session_auth();
+        let headers = request_headers(
+            &Key::generate(),
+            &[
+                ("sid", (now_seconds() + 600).to_string()),
+                ("group_name", "carbide-admins".to_string()),
+            ],
+        );
+        assert!(matches!(
+            auth.authenticate(&headers),
+            Err(WebAuthError::MissingSession)
+        ));
+    }
+
+    #[test]
+    fn test_group_not_in_admin_groups_is_rejected() {
+        let auth = session_auth();
+        let headers = request_headers(
+            &auth.cookiejar_key,
+            &[
+                ("sid", (now_seconds() + 600).to_string()),
+                ("group_name", "carbide-viewers".to_string()),
+            ],
+        );
+        assert!(matches!(
+            auth.authenticate(&headers),
+            Err(WebAuthError::GroupNotAllowed(Some(_)))
+        ));
+    }
+
+    #[test]
+    fn test_foreign_origin_is_rejected() {
+        let auth = session_auth();
+        let mut headers = request_headers(
+            &auth.cookiejar_key,
+            &[
+                ("sid", (now_seconds() + 600).to_string()),
+                ("group_name", "carbide-admins".to_string()),
+            ],
+        );
+        headers.insert(
+            header::ORIGIN,
+            HeaderValue::from_static("https://evil.example.com"),
+        );
+        assert!(matches!(
+            auth.authenticate(&headers),
+            Err(WebAuthError::OriginNotAllowed(_))
+        ));
+    }
+}
@@ -55,6 +55,17 @@ pub struct IterationConfig {
     /// The duration of this needs to be longer than the time between state handler
     /// invocations for the object
     pub metric_hold_time: std::time::Duration,
+
+    /// Configures whether queued objects are partitioned between the state processors
+    /// of all carbide instances. If `None`, every processor dequeues whichever objects
+    /// are queued.
+    pub sharding: Option<ShardingConfig>,
+
+    /// Configures how quickly queued objects age into the next higher priority class.
+    /// An object which has been waiting for this duration is dequeued alongside objects
+    /// of the next higher class. This prevents periodically enqueued objects from
+    /// starving while explicitly requested work keeps arriving.
+    pub priority_aging: Duration,
 }
 
 impl Default for IterationConfig {
@@ -71,6 +82,41 @@ impl Default for IterationConfig {
             processor_dispatch_interval: Duration::from_secs(2),
             metric_emission_interval: Duration::from_secs(60),
             metric_hold_time: Duration::from_secs(5 * 60),
+            sharding: None,
+            priority_aging: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Settings for partitioning queued objects between state processors
+///
+/// Objects are mapped onto a fixed amount of shards by hashing their ID, and shards are
+/// assigned to the processors which are currently alive by consistent hashing. Each
+/// processor dequeues objects of the shards assigned to it first, and only takes objects
+/// of other shards once its own shards have nothing left to process. If a processor joins
+/// or leaves, only the shards that move to or from it change owner.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ShardingConfig {
+    /// The amount of shards that objects are partitioned into. This needs to be the same
+    /// on all carbide instances. More shards spread objects more evenly between processors.
+    pub num_shards: u32,
+
+    /// Configures how often a processor announces that it is alive and checks
+    /// whether shards need to be rebalanced
+    pub heartbeat_interval: Duration,
+
+    /// Configures after which time without heartbeat a processor is considered gone,
+    /// and its shards get reassigned to other processors. Needs to be longer than
+    /// `heartbeat_interval`.
+    pub processor_expiry: Duration,
+}
+
+impl Default for ShardingConfig {
+    fn default() -> Self {
+        Self {
+            num_shards: 256,
+            heartbeat_interval: Duration::from_secs(10),
+            processor_expiry: Duration::from_secs(60),
         }
     }
 }
@@ -32,6 +32,7 @@ pub use enqueuer::Enqueuer;
 
 pub mod periodic_enqueuer;
 pub mod processor;
+pub mod sharding;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct ControllerIterationId(pub i64);
@@ -63,6 +64,39 @@ impl<'r> FromRow<'r, PgRow> for ControllerIteration {
     }
 }
 
+/// The priority class of an object in the queue
+///
+/// Objects with a higher priority class are dequeued first. Within a class,
+/// objects are dequeued in a round-robin fashion across fairness keys, and
+/// in the order they had been queued for objects with the same fairness key.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum QueuePriority {
+    /// The object got enqueued by the periodic re-evaluation of all objects
+    Periodic = 0,
+    /// The object is in an active transition, and got requeued after its state changed
+    Transition = 1,
+    /// State handling for the object was explicitly requested, e.g. by an API call
+    Requested = 2,
+}
+
+impl QueuePriority {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QueuePriority::Periodic => "periodic",
+            QueuePriority::Transition => "transition",
+            QueuePriority::Requested => "requested",
+        }
+    }
+
+    fn from_db(value: i16) -> Self {
+        match value {
+            i16::MIN..=0 => QueuePriority::Periodic,
+            1 => QueuePriority::Transition,
+            2..=i16::MAX => QueuePriority::Requested,
+        }
+    }
+}
+
 /// Metadata for a single state controller iteration
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct QueuedObject {
@@ -71,15 +105,42 @@ pub struct QueuedObject {
     /// Identifies the processor which is executing the state handler
     /// The value of this field will be NULL in case the object is not yet processed
     pub processed_by: Option<String>,
+    /// The priority class the object is queued with
+    pub priority: QueuePriority,
+    /// Objects with the same fairness key (e.g. the same tenant) are dequeued in turn
+    /// with objects of other keys, instead of all at once
+    pub fairness_key: Option<String>,
 }
 
 impl<'r> FromRow<'r, PgRow> for QueuedObject {
     fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
         let object_id = row.try_get("object_id")?;
         let processed_by: Option<String> = row.try_get("processed_by")?;
+        let priority: i16 = row.try_get("priority")?;
+        let fairness_key: Option<String> = row.try_get("fairness_key")?;
         Ok(QueuedObject {
             object_id,
             processed_by,
+            priority: QueuePriority::from_db(priority),
+            fairness_key,
+        })
+    }
+}
+
+/// A queued object which got acquired by a processor
+#[derive(Debug, Clone, PartialEq)]
+pub struct AcquiredObject {
+    pub object: QueuedObject,
+    /// How long the object had been waiting in the queue before it got acquired
+    pub queue_wait: std::time::Duration,
+}
+
+impl<'r> FromRow<'r, PgRow> for AcquiredObject {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let queue_wait_secs: f64 = row.try_get("queue_wait_secs")?;
+        Ok(AcquiredObject {
+            object: QueuedObject::from_row(row)?,
+            queue_wait: std::time::Duration::from_secs_f64(queue_wait_secs.max(0.0)),
         })
     }
 }
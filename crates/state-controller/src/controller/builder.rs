@@ -15,7 +15,7 @@
  * limitations under the License.
  */
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use db::work_lock_manager::WorkLockManagerHandle;
@@ -27,6 +27,7 @@ use crate::config::IterationConfig;
 use crate::controller::StateController;
 use crate::controller::periodic_enqueuer::{EnqueuerMetricsEmitter, PeriodicEnqueuer};
 use crate::controller::processor::{ProcessorMetricsEmitter, StateProcessor};
+use crate::controller::sharding::ProcessorSharding;
 use crate::io::StateControllerIO;
 use crate::metrics::MetricHolder;
 use crate::state_change_emitter::StateChangeEmitter;
@@ -43,6 +44,9 @@ pub enum StateControllerBuildError {
     #[error("Missing parameter {0}")]
     MissingArgument(&'static str),
 
+    #[error("Invalid parameter {0}: {1}")]
+    InvalidArgument(&'static str, &'static str),
+
     #[error("Task spawn error: {0}")]
     IOError(#[from] std::io::Error),
 }
@@ -156,6 +160,26 @@ impl<IO: StateControllerIO> Builder<IO> {
                 "max_concurrency",
             ));
         }
+        if self.iteration_config.priority_aging.is_zero() {
+            return Err(StateControllerBuildError::MissingArgument("priority_aging"));
+        }
+        if self
+            .iteration_config
+            .sharding
+            .is_some_and(|sharding| sharding.num_shards == 0)
+        {
+            return Err(StateControllerBuildError::MissingArgument("num_shards"));
+        }
+        if self
+            .iteration_config
+            .sharding
+            .is_some_and(|sharding| sharding.processor_expiry <= sharding.heartbeat_interval)
+        {
+            return Err(StateControllerBuildError::InvalidArgument(
+                "processor_expiry",
+                "must be longer than heartbeat_interval",
+            ));
+        }
         let controller_name = object_type_for_metrics.unwrap_or_else(|| "undefined".to_string());
 
         let services = self
@@ -213,7 +237,8 @@ impl<IO: StateControllerIO> Builder<IO> {
             state_change_emitter: self.state_change_emitter,
             in_flight: HashSet::new(),
             completed_objects: HashSet::new(),
-            requeue_objects: HashSet::new(),
+            requeue_objects: HashMap::new(),
+            fairness_keys: HashMap::new(),
             task_sender,
             task_receiver,
             object_metrics: Default::default(),
@@ -222,6 +247,7 @@ impl<IO: StateControllerIO> Builder<IO> {
             last_metric_emission_time: std::time::Instant::now(),
             processor_span,
             processor_id,
+            sharding: self.iteration_config.sharding.map(ProcessorSharding::new),
         };
 
         let controller = StateController::<IO> {
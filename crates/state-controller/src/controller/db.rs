@@ -21,10 +21,15 @@ use db::work_lock_manager::{AcquireLockError, WorkLockManagerHandle};
 use db::{BIND_LIMIT, DatabaseError};
 use sqlx::{PgConnection, PgPool};
 
+use crate::controller::sharding::OwnedShards;
 use crate::controller::{
-    ControllerIteration, ControllerIterationId, LockedControllerIteration, QueuedObject,
+    AcquiredObject, ControllerIteration, ControllerIterationId, LockedControllerIteration,
+    QueuePriority, QueuedObject,
 };
 
+/// The table in which state processors register themselves if sharding is enabled
+const PROCESSORS_TABLE_NAME: &str = "state_controller_processors";
+
 /// Inserts a new entry into the iteration table
 async fn create_iteration(
     txn: &mut PgConnection,
@@ -110,10 +115,18 @@ pub enum LockIterationTableError {
 /// Enqueues object IDs for processing into the queued objects table with name `table_id`
 /// If the object is enqueued, then keep the current entry. That guarantees that the object will be processed
 /// with the oldest possible run id and that the processed_by field won't get lost.
+/// The only thing that changes for an already queued object is its priority, which is raised
+/// to `priority` if that is higher. In that case the entry also takes over the `fairness_key`,
+/// and its `queued_at` is reset, so that it ages and queues up behind the objects that were
+/// already waiting in its new priority class.
+///
+/// Returns the number of objects which were not queued before.
 pub async fn queue_objects(
     txn: &mut PgConnection,
     table_id: &str,
     queued_objects: &[String],
+    priority: QueuePriority,
+    fairness_key: Option<&str>,
 ) -> Result<usize, DatabaseError> {
     // Object IDs need to be sorted in order to avoid a deadlock on concurrent calls to this
     // method.
@@ -142,20 +155,26 @@ pub async fn queue_objects(
     for queued_objects in sorted.chunks(OBJECTS_PER_QUERY) {
         let mut builder = sqlx::QueryBuilder::new("INSERT INTO ");
         builder.push(table_id);
-        builder.push("(object_id)");
+        builder.push("(object_id, priority, fairness_key)");
 
         builder.push_values(queued_objects, |mut b, object_id| {
             b.push_bind(object_id);
+            b.push_bind(priority as i16);
+            b.push_bind(fairness_key);
         });
 
-        builder.push("ON CONFLICT (object_id) DO NOTHING");
-        let query = builder.build();
+        builder.push(" ON CONFLICT (object_id) DO UPDATE SET priority = EXCLUDED.priority, fairness_key = EXCLUDED.fairness_key, queued_at = now() WHERE ");
+        builder.push(table_id);
+        builder.push(".priority < EXCLUDED.priority");
+        // xmax is only 0 for rows which got inserted rather than updated
+        builder.push(" RETURNING (xmax = 0) AS inserted");
+        let query = builder.build_query_as::<(bool,)>();
 
-        let result = query
-            .execute(&mut *txn)
+        let rows = query
+            .fetch_all(&mut *txn)
             .await
             .map_err(|e| DatabaseError::new("StateController::queue_object", e))?;
-        num_enqueued += result.rows_affected() as usize;
+        num_enqueued += rows.iter().filter(|(inserted,)| *inserted).count();
     }
 
     Ok(num_enqueued)
@@ -181,27 +200,54 @@ pub async fn fetch_queued_objects(
 /// current processor.
 /// The objects will be marked as `processed_by` with the given ID - which will avoid
 /// other processors to pick up the objects.
+/// If `shards` is set, objects which belong to one of the given shards are fetched first.
+/// Objects of other shards are only fetched if the given shards have no more objects
+/// to process, so that idle processors help out busy or stuck ones.
+///
+/// Objects are fetched in order of their priority class. Objects of the same class are
+/// fetched round-robin across fairness keys, and oldest first for the same fairness key.
+/// To prevent starvation of lower priority classes, the priority of an object is raised
+/// by one class for every `priority_aging` it has been waiting in the queue.
 pub async fn acquire_queued_objects(
     txn: &mut PgConnection,
     table_id: &str,
     count: u32, // u32 to avoid u64 numbers getting passed that are not valid in postgres
     processor_id: &str,
     max_outdated: std::time::Duration,
-) -> Result<Vec<QueuedObject>, DatabaseError> {
-    // Grab the oldest ones first
+    priority_aging: std::time::Duration,
+    shards: Option<&OwnedShards>,
+) -> Result<Vec<AcquiredObject>, DatabaseError> {
+    let owned_shard = match shards {
+        Some(shards) => format!("{} = ANY($4)", shard_of_object(shards.num_shards)),
+        None => "true".to_string(),
+    };
     let query = format!(
-        "WITH dequeued_ids AS (
-            SELECT object_id FROM {table_id} WHERE (processed_by IS NULL OR processing_started_at + $1::interval < now())
-            ORDER BY processing_started_at ASC
-            FOR UPDATE SKIP LOCKED
+        "WITH candidates AS (
+            SELECT object_id,
+                priority + floor(extract(epoch FROM now() - queued_at)::float8 / $3::float8) AS effective_priority,
+                row_number() OVER (PARTITION BY priority, fairness_key ORDER BY queued_at ASC) AS fair_rank,
+                {owned_shard} AS owned_shard
+            FROM {table_id} WHERE (processed_by IS NULL OR processing_started_at + $1::interval < now())
+        ),
+        dequeued_ids AS (
+            SELECT q.object_id FROM {table_id} q JOIN candidates c ON c.object_id = q.object_id
+            ORDER BY c.owned_shard DESC, c.effective_priority DESC, c.fair_rank ASC, q.queued_at ASC
+            FOR UPDATE OF q SKIP LOCKED
             LIMIT {count}
         )
-        UPDATE {table_id} SET processed_by=$2, processing_started_at=now() WHERE object_id in (SELECT object_id FROM dequeued_ids) RETURNING *"
+        UPDATE {table_id} SET processed_by=$2, processing_started_at=now() WHERE object_id in (SELECT object_id FROM dequeued_ids)
+        RETURNING *, extract(epoch FROM now() - queued_at)::float8 AS queue_wait_secs"
     );
 
-    let result = sqlx::query_as(&query)
+    let mut query = sqlx::query_as(&query)
         .bind(max_outdated)
         .bind(processor_id)
+        .bind(priority_aging.as_secs_f64());
+    if let Some(shards) = shards {
+        let shards: Vec<i64> = shards.shards.iter().map(|shard| *shard as i64).collect();
+        query = query.bind(shards);
+    }
+    let result = query
         .fetch_all(txn)
         .await
         .map_err(|e| DatabaseError::new("StateController::acquire_queued_objects", e))?;
@@ -209,6 +255,101 @@ pub async fn acquire_queued_objects(
     Ok(result)
 }
 
+/// Returns the SQL expression which maps the `object_id` column of a queued objects
+/// table onto one of `num_shards` shards.
+/// The hash is calculated by the database, so that all processors agree on it.
+pub(crate) fn shard_of_object(num_shards: u32) -> String {
+    format!("mod(mod(hashtextextended(object_id, 0), {num_shards}) + {num_shards}, {num_shards})")
+}
+
+/// Counts the queued objects in every shard. Shards without queued objects are omitted.
+pub async fn count_queued_objects_per_shard(
+    txn: &mut PgConnection,
+    table_id: &str,
+    num_shards: u32,
+) -> Result<Vec<(u32, u64)>, DatabaseError> {
+    let query = format!(
+        "SELECT {} AS shard, COUNT(*) FROM {table_id} GROUP BY shard",
+        shard_of_object(num_shards)
+    );
+    let result: Vec<(i64, i64)> = sqlx::query_as(&query)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::new("StateController::count_queued_objects_per_shard", e))?;
+
+    Ok(result
+        .into_iter()
+        .map(|(shard, count)| (shard as u32, count as u64))
+        .collect())
+}
+
+/// Registers a processor for the queued objects table `table_id`, or refreshes
+/// its heartbeat if it is already registered
+pub async fn heartbeat_processor(
+    txn: &mut PgConnection,
+    table_id: &str,
+    processor_id: &str,
+) -> Result<(), DatabaseError> {
+    let query = format!(
+        "INSERT INTO {PROCESSORS_TABLE_NAME} (queue_table, processor_id) VALUES ($1, $2)
+        ON CONFLICT (queue_table, processor_id) DO UPDATE SET last_heartbeat = now()"
+    );
+    sqlx::query(&query)
+        .bind(table_id)
+        .bind(processor_id)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::new("StateController::heartbeat_processor", e))?;
+
+    Ok(())
+}
+
+/// Removes processors whose last heartbeat is older than `expiry` from the registry,
+/// and returns the IDs of the remaining ones in sorted order
+pub async fn fetch_live_processors(
+    txn: &mut PgConnection,
+    table_id: &str,
+    expiry: std::time::Duration,
+) -> Result<Vec<String>, DatabaseError> {
+    let query = format!(
+        "DELETE FROM {PROCESSORS_TABLE_NAME} WHERE queue_table = $1 AND last_heartbeat + $2::interval < now()"
+    );
+    sqlx::query(&query)
+        .bind(table_id)
+        .bind(expiry)
+        .execute(&mut *txn)
+        .await
+        .map_err(|e| DatabaseError::new("StateController::fetch_live_processors", e))?;
+
+    let query = format!(
+        "SELECT processor_id FROM {PROCESSORS_TABLE_NAME} WHERE queue_table = $1 ORDER BY processor_id"
+    );
+    sqlx::query_scalar(&query)
+        .bind(table_id)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::new("StateController::fetch_live_processors", e))
+}
+
+/// Removes a processor from the registry, so that other processors take over its shards
+/// without waiting for its heartbeat to expire
+pub async fn deregister_processor(
+    txn: &mut PgConnection,
+    table_id: &str,
+    processor_id: &str,
+) -> Result<(), DatabaseError> {
+    let query =
+        format!("DELETE FROM {PROCESSORS_TABLE_NAME} WHERE queue_table = $1 AND processor_id = $2");
+    sqlx::query(&query)
+        .bind(table_id)
+        .bind(processor_id)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::new("StateController::deregister_processor", e))?;
+
+    Ok(())
+}
+
 pub async fn delete_queued_objects(
     txn: &mut PgConnection,
     table_id: &str,
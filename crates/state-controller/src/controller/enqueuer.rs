@@ -17,7 +17,7 @@
 
 use ::db::DatabaseError;
 
-use super::db;
+use super::{QueuePriority, db};
 use crate::io::StateControllerIO;
 
 /// Allows to request state handling for objects of a certain type
@@ -36,13 +36,39 @@ impl<IO: StateControllerIO> Enqueuer<IO> {
     }
 
     /// Requests state handling for the given object
+    ///
+    /// The object is queued with [`QueuePriority::Requested`], and will therefore be
+    /// processed ahead of objects which are only periodically re-evaluated.
+    /// Returns `false` if the object was already queued. Its priority is still raised then.
     pub async fn enqueue_object(&self, object_id: &IO::ObjectId) -> Result<bool, DatabaseError> {
+        self.enqueue(object_id, None).await
+    }
+
+    /// Requests state handling for the given object on behalf of a tenant
+    ///
+    /// Objects requested by different tenants are processed in turn, so that
+    /// a tenant which requests a lot of objects can not starve other tenants.
+    pub async fn enqueue_object_for_tenant(
+        &self,
+        object_id: &IO::ObjectId,
+        tenant_organization_id: &str,
+    ) -> Result<bool, DatabaseError> {
+        self.enqueue(object_id, Some(tenant_organization_id)).await
+    }
+
+    async fn enqueue(
+        &self,
+        object_id: &IO::ObjectId,
+        fairness_key: Option<&str>,
+    ) -> Result<bool, DatabaseError> {
         let mut conn = self.pool.acquire().await.map_err(DatabaseError::acquire)?;
 
         let num_enqueued = db::queue_objects(
             &mut conn,
             IO::DB_QUEUED_OBJECTS_TABLE_NAME,
             &[object_id.to_string()],
+            QueuePriority::Requested,
+            fairness_key,
         )
         .await?;
 
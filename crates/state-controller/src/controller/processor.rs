@@ -22,12 +22,13 @@ use std::time::{Duration, Instant};
 use ::db::DatabaseError;
 use model::controller_outcome::PersistentStateHandlerOutcome;
 use opentelemetry::KeyValue;
-use opentelemetry::metrics::{Counter, Histogram, Meter};
+use opentelemetry::metrics::{Counter, Gauge, Histogram, Meter};
 use sqlx_query_tracing::SqlxQueryDataAggregation;
 use tokio_util::sync::CancellationToken;
 use tracing::Instrument;
 
-use super::db;
+use super::sharding::ProcessorSharding;
+use super::{AcquiredObject, QueuePriority, db};
 use crate::config::IterationConfig;
 use crate::db_write_batch::DbWriteBatch;
 use crate::io::StateControllerIO;
@@ -67,8 +68,10 @@ pub(super) struct StateProcessor<IO: StateControllerIO> {
     /// in the database has not yet been deleted.
     pub(super) completed_objects: HashSet<IO::ObjectId>,
     /// Objects for which another object handling task should be queued since
-    /// the state handler returned `Transition`
-    pub(super) requeue_objects: HashSet<IO::ObjectId>,
+    /// the state handler returned `Transition`, together with their fairness key
+    pub(super) requeue_objects: HashMap<IO::ObjectId, Option<String>>,
+    /// Fairness keys of in-flight objects, which are retained if the objects get requeued
+    pub(super) fairness_keys: HashMap<IO::ObjectId, String>,
     pub(super) task_sender: tokio::sync::mpsc::UnboundedSender<ObjectHandlingTaskResult<IO>>,
     pub(super) task_receiver: tokio::sync::mpsc::UnboundedReceiver<ObjectHandlingTaskResult<IO>>,
     /// The last time a log message had been emitted
@@ -81,6 +84,8 @@ pub(super) struct StateProcessor<IO: StateControllerIO> {
     pub(super) processor_id: String,
     /// Emitter for broadcasting state change events to registered hooks.
     pub(super) state_change_emitter: Arc<StateChangeEmitter<IO::ObjectId, IO::ControllerState>>,
+    /// The shards this processor owns, if sharding is enabled
+    pub(super) sharding: Option<ProcessorSharding>,
 }
 pub(super) struct ObjectHandlingTaskResult<IO: StateControllerIO> {
     object_id: IO::ObjectId,
@@ -182,7 +187,7 @@ impl<IO: StateControllerIO> StateProcessor<IO> {
                         biased;
                     _ = &mut cancelled_future => {
                         tracing::info!(controller=IO::LOG_SPAN_CONTROLLER_NAME, "State processor stop was requested");
-                        return;
+                        break;
                     }
                     _ = tokio::time::sleep(sleep_time) => {},
                 }
@@ -191,9 +196,111 @@ impl<IO: StateControllerIO> StateProcessor<IO> {
                     controller = IO::LOG_SPAN_CONTROLLER_NAME,
                     "State processor stop was requested"
                 );
-                return;
+                break;
             }
         }
+
+        self.deregister().await;
+    }
+
+    /// Removes the processor from the shard registry, so that the remaining processors
+    /// take over its shards immediately
+    async fn deregister(&mut self) {
+        if self.sharding.is_none() {
+            return;
+        }
+        let result = async {
+            let mut txn = self.pool.begin().await?;
+            db::deregister_processor(
+                &mut txn,
+                IO::DB_QUEUED_OBJECTS_TABLE_NAME,
+                &self.processor_id,
+            )
+            .await?;
+            txn.commit().await?;
+            Ok::<_, IterationError>(())
+        }
+        .await;
+        if let Err(err) = result {
+            tracing::warn!(controller = IO::LOG_SPAN_CONTROLLER_NAME, %err, "Failed to deregister state processor");
+        }
+    }
+
+    /// Announces that the processor is alive and recalculates the shards it owns,
+    /// if sharding is enabled and the configured heartbeat interval has passed
+    async fn refresh_shard_assignment(&mut self) -> Result<(), IterationError> {
+        let now = Instant::now();
+        let Some(sharding) = self.sharding.as_mut() else {
+            return Ok(());
+        };
+        if !sharding.heartbeat_due(now) {
+            return Ok(());
+        }
+
+        let mut txn = self.pool.begin().await?;
+        db::heartbeat_processor(
+            &mut txn,
+            IO::DB_QUEUED_OBJECTS_TABLE_NAME,
+            &self.processor_id,
+        )
+        .await?;
+        txn.commit().await?;
+
+        let mut txn = self.pool.begin().await?;
+        let live_processors = db::fetch_live_processors(
+            &mut txn,
+            IO::DB_QUEUED_OBJECTS_TABLE_NAME,
+            sharding.config.processor_expiry,
+        )
+        .await?;
+        let queued_per_shard = db::count_queued_objects_per_shard(
+            &mut txn,
+            IO::DB_QUEUED_OBJECTS_TABLE_NAME,
+            sharding.config.num_shards,
+        )
+        .await?;
+        txn.commit().await?;
+
+        let was_assigned = !sharding.live_processors.is_empty();
+        let lost_shards = sharding.update(live_processors, &self.processor_id, now);
+        if let Some(lost_shards) = &lost_shards {
+            tracing::info!(
+                controller = IO::LOG_SPAN_CONTROLLER_NAME,
+                processors = sharding.live_processors.len(),
+                owned_shards = sharding.owned.shards.len(),
+                lost_shards = lost_shards.len(),
+                "State processor shards were rebalanced"
+            );
+        }
+
+        if let Some(emitter) = &self.metric_emitter {
+            if was_assigned && lost_shards.is_some() {
+                emitter.shard_rebalances_counter.add(1, &[]);
+            }
+            emitter
+                .processors_gauge
+                .record(sharding.live_processors.len() as u64, &[]);
+            emitter
+                .owned_shards_gauge
+                .record(sharding.owned.shards.len() as u64, &[]);
+            // Shards which moved to another processor are reported by it from now on
+            for shard in lost_shards.unwrap_or_default() {
+                emitter
+                    .shard_queued_objects_gauge
+                    .record(0, &[KeyValue::new("shard", shard as i64)]);
+            }
+            for shard in sharding.owned.shards.iter() {
+                let queued = queued_per_shard
+                    .iter()
+                    .find(|(s, _)| s == shard)
+                    .map_or(0, |(_, count)| *count);
+                emitter
+                    .shard_queued_objects_gauge
+                    .record(queued, &[KeyValue::new("shard", *shard as i64)]);
+            }
+        }
+
+        Ok(())
     }
 
     /// Calculates how many additional object handling tasks can be spawned
@@ -384,7 +491,9 @@ impl<IO: StateControllerIO> StateProcessor<IO> {
     async fn dequeue_and_dispatch_object_handling_tasks(
         &mut self,
     ) -> Result<usize, IterationError> {
-        // Determine how many new objects can still be processed and dequeue that amount
+        self.refresh_shard_assignment().await?;
+
+        // Determine how many new objects can still be processed and dequeue that amount.
         let capacity = self.remaining_capacity();
         let objects = if capacity > 0 {
             // Acquire new object handling tasks
@@ -400,6 +509,8 @@ impl<IO: StateControllerIO> StateProcessor<IO> {
                 capacity,
                 &self.processor_id,
                 self.iteration_config.max_object_handling_time * 3,
+                self.iteration_config.priority_aging,
+                self.sharding.as_ref().map(|sharding| &sharding.owned),
             )
             .await?;
             txn.commit().await?;
@@ -408,27 +519,38 @@ impl<IO: StateControllerIO> StateProcessor<IO> {
             Vec::new()
         };
 
-        let objects: Vec<IO::ObjectId> = objects
+        if let Some(emitter) = &self.metric_emitter {
+            for acquired in objects.iter() {
+                emitter.record_queue_wait_time(acquired);
+            }
+        }
+
+        let objects: Vec<(IO::ObjectId, Option<String>)> = objects
             .into_iter()
-            .filter_map(|object| match IO::ObjectId::from_str(&object.object_id) {
-                Ok(id) => Some(id),
-                Err(_) => {
-                    tracing::error!(
-                        controller = IO::LOG_SPAN_CONTROLLER_NAME,
-                        "Can not convert queued object ID \"{}\" to IO::ObjectID format",
-                        object.object_id
-                    );
-                    None
-                }
-            })
+            .filter_map(
+                |acquired| match IO::ObjectId::from_str(&acquired.object.object_id) {
+                    Ok(id) => Some((id, acquired.object.fairness_key)),
+                    Err(_) => {
+                        tracing::error!(
+                            controller = IO::LOG_SPAN_CONTROLLER_NAME,
+                            "Can not convert queued object ID \"{}\" to IO::ObjectID format",
+                            acquired.object.object_id
+                        );
+                        None
+                    }
+                },
+            )
             .collect();
 
         let num_dispatched_tasks = objects.len();
         self.stats_since_last_log.num_dispatched_tasks += num_dispatched_tasks;
 
         // Send off the new objects for processing
-        for object_id in objects {
+        for (object_id, fairness_key) in objects {
             self.dispatch_object_handling_task(object_id.clone());
+            if let Some(fairness_key) = fairness_key {
+                self.fairness_keys.insert(object_id.clone(), fairness_key);
+            }
             self.in_flight.insert(object_id);
         }
 
@@ -521,15 +643,30 @@ impl<IO: StateControllerIO> StateProcessor<IO> {
             return Ok(());
         }
 
-        let queue_objects: Vec<String> = self
-            .requeue_objects
-            .iter()
-            .map(|id| id.to_string())
-            .collect();
-        let mut txn = self.pool.begin().await?;
-        let num_requeued =
-            db::queue_objects(&mut txn, IO::DB_QUEUED_OBJECTS_TABLE_NAME, &queue_objects).await?;
-        txn.commit().await?;
+        // Objects in an active transition are requeued ahead of periodically
+        // enqueued objects, but keep their fairness key.
+        // Every fairness key uses a separate transaction, since `queue_objects`
+        // only avoids deadlocks for the objects passed to a single call.
+        let mut queue_objects: HashMap<Option<&str>, Vec<String>> = HashMap::new();
+        for (id, fairness_key) in self.requeue_objects.iter() {
+            queue_objects
+                .entry(fairness_key.as_deref())
+                .or_default()
+                .push(id.to_string());
+        }
+        let mut num_requeued = 0;
+        for (fairness_key, queue_objects) in queue_objects {
+            let mut txn = self.pool.begin().await?;
+            num_requeued += db::queue_objects(
+                &mut txn,
+                IO::DB_QUEUED_OBJECTS_TABLE_NAME,
+                &queue_objects,
+                QueuePriority::Transition,
+                fairness_key,
+            )
+            .await?;
+            txn.commit().await?;
+        }
 
         self.stats_since_last_log.num_requeued_objects += num_requeued;
         if let Some(emitter) = &self.metric_emitter {
@@ -549,10 +686,12 @@ impl<IO: StateControllerIO> StateProcessor<IO> {
         // and remove them later in order to not forget about these in case there
         // is a transient database error
         self.completed_objects.insert(task_result.object_id.clone());
+        let fairness_key = self.fairness_keys.remove(&task_result.object_id);
         // If the state handler returned `Transition`, then run the handler again
         // as soon as possible.
         if allow_requeue && task_result.metrics.common.next_state.is_some() {
-            self.requeue_objects.insert(task_result.object_id.clone());
+            self.requeue_objects
+                .insert(task_result.object_id.clone(), fairness_key);
         }
 
         self.stats_since_last_log.num_completed_tasks += 1;
@@ -780,9 +919,14 @@ async fn process_object<IO: StateControllerIO>(
 #[derive(Debug)]
 pub(super) struct ProcessorMetricsEmitter {
     iteration_latency: Histogram<f64>,
+    queue_wait_time: Histogram<f64>,
     dispatched_tasks_counter: Counter<u64>,
     completed_tasks_counter: Counter<u64>,
     requeued_tasks_counter: Counter<u64>,
+    shard_rebalances_counter: Counter<u64>,
+    processors_gauge: Gauge<u64>,
+    owned_shards_gauge: Gauge<u64>,
+    shard_queued_objects_gauge: Gauge<u64>,
     db: sqlx_query_tracing::DatabaseMetricEmitters,
 }
 
@@ -798,6 +942,14 @@ impl ProcessorMetricsEmitter {
             .with_unit("ms")
             .build();
 
+        let queue_wait_time = meter
+            .f64_histogram(format!("{object_type}_queue_wait_time"))
+            .with_description(format!(
+                "The time objects of type {object_type} had been waiting in the queue before they got dequeued by the state processor"
+            ))
+            .with_unit("ms")
+            .build();
+
         let dispatched_tasks_counter = meter
             .u64_counter(format!("{object_type}_object_tasks_dispatched"))
             .with_description(format!(
@@ -819,12 +971,45 @@ impl ProcessorMetricsEmitter {
             ))
             .build();
 
+        let shard_rebalances_counter = meter
+            .u64_counter(format!("{object_type}_shard_rebalances"))
+            .with_description(format!(
+                "The amount of times the shards owned by this state processor for objects of type {object_type} changed"
+            ))
+            .build();
+
+        let processors_gauge = meter
+            .u64_gauge(format!("{object_type}_shard_processors"))
+            .with_description(format!(
+                "The amount of state processors which share the shards for objects of type {object_type}"
+            ))
+            .build();
+
+        let owned_shards_gauge = meter
+            .u64_gauge(format!("{object_type}_owned_shards"))
+            .with_description(format!(
+                "The amount of shards for objects of type {object_type} owned by this state processor"
+            ))
+            .build();
+
+        let shard_queued_objects_gauge = meter
+            .u64_gauge(format!("{object_type}_shard_queued_objects"))
+            .with_description(format!(
+                "The amount of queued objects of type {object_type} per shard owned by this state processor"
+            ))
+            .build();
+
         Self {
             iteration_latency,
+            queue_wait_time,
             db,
             dispatched_tasks_counter,
             completed_tasks_counter,
             requeued_tasks_counter,
+            shard_rebalances_counter,
+            processors_gauge,
+            owned_shards_gauge,
+            shard_queued_objects_gauge,
         }
     }
 
@@ -839,6 +1024,13 @@ impl ProcessorMetricsEmitter {
         self.db.emit(db_metrics, attrs);
     }
 
+    fn record_queue_wait_time(&self, acquired: &AcquiredObject) {
+        self.queue_wait_time.record(
+            1000.0 * acquired.queue_wait.as_secs_f64(),
+            &[KeyValue::new("priority", acquired.object.priority.as_str())],
+        );
+    }
+
     fn emit_run_counters_and_histograms(&self, run_metrics: &ProcessorIterationMetrics) {
         self.iteration_latency.record(
             1000.0 * run_metrics.iteration_started_at.elapsed().as_secs_f64(),
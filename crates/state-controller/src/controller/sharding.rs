@@ -0,0 +1,137 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Partitioning of queued objects between the state processors of multiple carbide instances
+
+use std::time::Instant;
+
+use crate::config::ShardingConfig;
+
+/// The shards a processor is responsible for
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OwnedShards {
+    /// The total amount of shards
+    pub num_shards: u32,
+    /// The shards owned by the processor, in ascending order
+    pub shards: Vec<u32>,
+}
+
+/// Returns the shards out of `num_shards` which are owned by `processor_id`, if the
+/// shards are split between `processors`
+pub fn assign_shards(num_shards: u32, processors: &[String], processor_id: &str) -> OwnedShards {
+    OwnedShards {
+        num_shards,
+        shards: (0..num_shards)
+            .filter(|shard| shard_owner(*shard, processors) == Some(processor_id))
+            .collect(),
+    }
+}
+
+/// Determines the owner of a shard using rendezvous (highest random weight) hashing:
+/// The shard is owned by the processor with the highest hash of processor ID and shard.
+///
+/// In contrast to a simple modulo, this means that if a processor joins it only takes
+/// over the shards it has the highest weight for, and if a processor leaves only its
+/// own shards are redistributed.
+pub fn shard_owner(shard: u32, processors: &[String]) -> Option<&str> {
+    processors
+        .iter()
+        .max_by_key(|processor| (shard_weight(processor, shard), processor.as_str()))
+        .map(String::as_str)
+}
+
+fn shard_weight(processor_id: &str, shard: u32) -> u64 {
+    // All processors need to agree on the weights, even if they run different
+    // versions of carbide. Therefore a fixed FNV-1a hash is used instead of
+    // the std library hashers, whose output is not guaranteed to be stable.
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in processor_id.as_bytes().iter().chain(&shard.to_le_bytes()) {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    // FNV-1a alone distributes inputs which only differ in their last bytes poorly.
+    // The splitmix64 finalizer fixes that.
+    hash ^= hash >> 30;
+    hash = hash.wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    hash ^= hash >> 27;
+    hash = hash.wrapping_mul(0x94d0_49bb_1331_11eb);
+    hash ^ (hash >> 31)
+}
+
+/// Tracks which shards a state processor currently owns
+#[derive(Debug)]
+pub(super) struct ProcessorSharding {
+    pub(super) config: ShardingConfig,
+    /// When the processor last announced itself. `None` before the first heartbeat
+    last_heartbeat: Option<Instant>,
+    /// The processors which were alive at the last heartbeat
+    pub(super) live_processors: Vec<String>,
+    pub(super) owned: OwnedShards,
+}
+
+impl ProcessorSharding {
+    pub(super) fn new(config: ShardingConfig) -> Self {
+        Self {
+            config,
+            last_heartbeat: None,
+            live_processors: Vec::new(),
+            owned: OwnedShards {
+                num_shards: config.num_shards,
+                shards: Vec::new(),
+            },
+        }
+    }
+
+    pub(super) fn heartbeat_due(&self, now: Instant) -> bool {
+        self.last_heartbeat.is_none_or(|last| {
+            now.saturating_duration_since(last) >= self.config.heartbeat_interval
+        })
+    }
+
+    /// Recalculates the owned shards after a heartbeat, based on the processors that
+    /// are alive. Returns the shards that the processor no longer owns, or `None` if
+    /// the assignment did not change.
+    pub(super) fn update(
+        &mut self,
+        mut live_processors: Vec<String>,
+        processor_id: &str,
+        now: Instant,
+    ) -> Option<Vec<u32>> {
+        self.last_heartbeat = Some(now);
+        // The own heartbeat might not be visible yet if it was written in a different
+        // transaction. The processor however is certainly alive.
+        if !live_processors.iter().any(|p| p == processor_id) {
+            live_processors.push(processor_id.to_string());
+            live_processors.sort();
+        }
+        let owned = assign_shards(self.config.num_shards, &live_processors, processor_id);
+        self.live_processors = live_processors;
+        if owned == self.owned {
+            return None;
+        }
+
+        let lost = self
+            .owned
+            .shards
+            .iter()
+            .copied()
+            .filter(|shard| !owned.shards.contains(shard))
+            .collect();
+        self.owned = owned;
+        Some(lost)
+    }
+}
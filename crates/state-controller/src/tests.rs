@@ -33,7 +33,8 @@ use tokio::task::JoinSet;
 use tokio_util::sync::CancellationToken;
 
 use crate::config::IterationConfig;
-use crate::controller::{self, Enqueuer, QueuedObject, StateController};
+use crate::controller::sharding::{assign_shards, shard_owner};
+use crate::controller::{self, Enqueuer, QueuePriority, QueuedObject, StateController};
 use crate::io::StateControllerIO;
 use crate::metrics::NoopMetricsEmitter;
 use crate::state_change_emitter::{StateChangeEmitterBuilder, StateChangeEvent, StateChangeHook};
@@ -170,6 +171,8 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
         &mut txn,
         TestStateControllerIO::DB_QUEUED_OBJECTS_TABLE_NAME,
         &["0".to_string()],
+        QueuePriority::Periodic,
+        None,
     )
     .await
     .unwrap();
@@ -178,6 +181,8 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
         &mut txn,
         TestStateControllerIO::DB_QUEUED_OBJECTS_TABLE_NAME,
         &["1".to_string(), "2".to_string()],
+        QueuePriority::Periodic,
+        None,
     )
     .await
     .unwrap();
@@ -196,14 +201,20 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
             QueuedObject {
                 object_id: "0".to_string(),
                 processed_by: None,
+                priority: QueuePriority::Periodic,
+                fairness_key: None,
             },
             QueuedObject {
                 object_id: "1".to_string(),
                 processed_by: None,
+                priority: QueuePriority::Periodic,
+                fairness_key: None,
             },
             QueuedObject {
                 object_id: "2".to_string(),
                 processed_by: None,
+                priority: QueuePriority::Periodic,
+                fairness_key: None,
             },
         ]
     );
@@ -217,6 +228,8 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
         &mut txn,
         TestStateControllerIO::DB_QUEUED_OBJECTS_TABLE_NAME,
         &["0".to_string()],
+        QueuePriority::Periodic,
+        None,
     )
     .await
     .unwrap();
@@ -225,6 +238,8 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
         &mut txn,
         TestStateControllerIO::DB_QUEUED_OBJECTS_TABLE_NAME,
         &["3".to_string(), "2".to_string()],
+        QueuePriority::Periodic,
+        None,
     )
     .await
     .unwrap();
@@ -242,18 +257,26 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
             QueuedObject {
                 object_id: "0".to_string(),
                 processed_by: None,
+                priority: QueuePriority::Periodic,
+                fairness_key: None,
             },
             QueuedObject {
                 object_id: "1".to_string(),
                 processed_by: None,
+                priority: QueuePriority::Periodic,
+                fairness_key: None,
             },
             QueuedObject {
                 object_id: "2".to_string(),
                 processed_by: None,
+                priority: QueuePriority::Periodic,
+                fairness_key: None,
             },
             QueuedObject {
                 object_id: "3".to_string(),
                 processed_by: None,
+                priority: QueuePriority::Periodic,
+                fairness_key: None,
             },
         ]
     );
@@ -270,9 +293,14 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
         2,
         &processor_id1,
         std::time::Duration::from_secs(60),
+        std::time::Duration::from_secs(60),
+        None,
     )
     .await
-    .unwrap();
+    .unwrap()
+    .into_iter()
+    .map(|acquired| acquired.object)
+    .collect::<Vec<_>>();
     queued.sort_by(|a, b| a.object_id.cmp(&b.object_id));
     assert_eq!(
         queued,
@@ -280,10 +308,14 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
             QueuedObject {
                 object_id: "0".to_string(),
                 processed_by: Some(processor_id1.clone()),
+                priority: QueuePriority::Periodic,
+                fairness_key: None,
             },
             QueuedObject {
                 object_id: "1".to_string(),
                 processed_by: Some(processor_id1.clone()),
+                priority: QueuePriority::Periodic,
+                fairness_key: None,
             },
         ]
     );
@@ -293,15 +325,22 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
         1,
         &processor_id2,
         std::time::Duration::from_secs(60),
+        std::time::Duration::from_secs(60),
+        None,
     )
     .await
-    .unwrap();
+    .unwrap()
+    .into_iter()
+    .map(|acquired| acquired.object)
+    .collect::<Vec<_>>();
     queued2.sort_by(|a, b| a.object_id.cmp(&b.object_id));
     assert_eq!(
         queued2,
         vec![QueuedObject {
             object_id: "2".to_string(),
             processed_by: Some(processor_id2.clone()),
+            priority: QueuePriority::Periodic,
+            fairness_key: None,
         },]
     );
 
@@ -344,14 +383,20 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
             QueuedObject {
                 object_id: "0".to_string(),
                 processed_by: Some(processor_id1.clone()),
+                priority: QueuePriority::Periodic,
+                fairness_key: None,
             },
             QueuedObject {
                 object_id: "2".to_string(),
                 processed_by: Some(processor_id2.clone()),
+                priority: QueuePriority::Periodic,
+                fairness_key: None,
             },
             QueuedObject {
                 object_id: "3".to_string(),
                 processed_by: None,
+                priority: QueuePriority::Periodic,
+                fairness_key: None,
             },
         ]
     );
@@ -367,6 +412,8 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
         2,
         &processor_id1,
         std::time::Duration::from_millis(500),
+        std::time::Duration::from_secs(60),
+        None,
     )
     .await
     .unwrap();
@@ -376,6 +423,7 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
         .iter()
         .filter(|queued| {
             queued
+                .object
                 .processed_by
                 .as_ref()
                 .is_some_and(|by| by == &processor_id1)
@@ -391,6 +439,280 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_shard_assignment_only_moves_shards_of_changed_processors() {
+    const NUM_SHARDS: u32 = 64;
+    let processors: Vec<String> = (0..4).map(|idx| format!("processor-{idx}")).collect();
+
+    let owners: Vec<&str> = (0..NUM_SHARDS)
+        .map(|shard| shard_owner(shard, &processors).unwrap())
+        .collect();
+    let mut total_owned = 0;
+    for processor in processors.iter() {
+        let owned = assign_shards(NUM_SHARDS, &processors, processor);
+        assert!(
+            (4..=28).contains(&owned.shards.len()),
+            "{processor} owns {} of {NUM_SHARDS} shards",
+            owned.shards.len()
+        );
+        total_owned += owned.shards.len();
+    }
+    assert_eq!(total_owned, NUM_SHARDS as usize);
+
+    // A joining processor only takes over shards, but does not shuffle the remaining ones
+    let mut joined = processors.clone();
+    joined.push("processor-4".to_string());
+    for shard in 0..NUM_SHARDS {
+        let owner = shard_owner(shard, &joined).unwrap();
+        assert!(owner == owners[shard as usize] || owner == "processor-4");
+    }
+
+    // If a processor leaves, only its shards get a new owner
+    let left: Vec<String> = processors
+        .iter()
+        .filter(|p| *p != "processor-1")
+        .cloned()
+        .collect();
+    for shard in 0..NUM_SHARDS {
+        let owner = shard_owner(shard, &left).unwrap();
+        if owners[shard as usize] != "processor-1" {
+            assert_eq!(owner, owners[shard as usize]);
+        }
+    }
+
+    assert_eq!(shard_owner(0, &[]), None);
+}
+
+#[carbide_macros::sqlx_test]
+async fn test_acquire_sharded_queued_objects(pool: sqlx::PgPool) -> eyre::Result<()> {
+    create_test_state_controller_tables(&pool).await;
+    const NUM_SHARDS: u32 = 16;
+    const TABLE: &str = TestStateControllerIO::DB_QUEUED_OBJECTS_TABLE_NAME;
+
+    let mut txn = pool.begin().await?;
+    let object_ids: Vec<String> = (0..100).map(|idx| idx.to_string()).collect();
+    controller::db::queue_objects(&mut txn, TABLE, &object_ids, QueuePriority::Periodic, None)
+        .await?;
+
+    // Register two processors
+    let processors = vec!["processor-a".to_string(), "processor-b".to_string()];
+    for processor in processors.iter() {
+        controller::db::heartbeat_processor(&mut txn, TABLE, processor).await?;
+    }
+    let live =
+        controller::db::fetch_live_processors(&mut txn, TABLE, Duration::from_secs(60)).await?;
+    assert_eq!(live, processors);
+    txn.commit().await?;
+
+    let per_shard =
+        controller::db::count_queued_objects_per_shard(&mut txn, TABLE, NUM_SHARDS).await?;
+    assert!(per_shard.iter().all(|(shard, _)| *shard < NUM_SHARDS));
+    assert_eq!(per_shard.iter().map(|(_, count)| count).sum::<u64>(), 100);
+    let owned = assign_shards(NUM_SHARDS, &live, "processor-a");
+    let num_owned_objects: u64 = per_shard
+        .iter()
+        .filter(|(shard, _)| owned.shards.contains(shard))
+        .map(|(_, count)| count)
+        .sum();
+    assert!(num_owned_objects > 0 && num_owned_objects < 100);
+    txn.commit().await?;
+
+    // A processor acquires the objects in its own shards first
+    let mut txn = pool.begin().await?;
+    let queued = controller::db::acquire_queued_objects(
+        &mut txn,
+        TABLE,
+        num_owned_objects as usize,
+        "processor-a",
+        Duration::from_secs(60),
+        Duration::from_secs(60),
+        Some(&owned),
+    )
+    .await?;
+    let mut acquired: Vec<String> = queued
+        .into_iter()
+        .map(|acquired| acquired.object.object_id)
+        .collect();
+    assert_eq!(acquired.len() as u64, num_owned_objects);
+    let query = format!(
+        "SELECT DISTINCT {}::BIGINT FROM {TABLE} WHERE object_id = ANY($1)",
+        controller::db::shard_of_object(NUM_SHARDS)
+    );
+    let shards: Vec<i64> = sqlx::query_scalar(&query)
+        .bind(&acquired)
+        .fetch_all(&mut *txn)
+        .await?;
+    assert!(
+        shards
+            .iter()
+            .all(|shard| owned.shards.contains(&(*shard as u32)))
+    );
+
+    // Once its own shards are drained, it takes over the objects of other shards
+    let queued = controller::db::acquire_queued_objects(
+        &mut txn,
+        TABLE,
+        100,
+        "processor-a",
+        Duration::from_secs(60),
+        Duration::from_secs(60),
+        Some(&owned),
+    )
+    .await?;
+    assert_eq!(queued.len() as u64, 100 - num_owned_objects);
+    acquired.extend(queued.into_iter().map(|acquired| acquired.object.object_id));
+    acquired.sort_by_key(|id| id.parse::<u32>().unwrap());
+    assert_eq!(acquired, object_ids);
+
+    // Deregistered processors are no longer considered alive
+    controller::db::deregister_processor(&mut txn, TABLE, "processor-a").await?;
+    let live =
+        controller::db::fetch_live_processors(&mut txn, TABLE, Duration::from_secs(60)).await?;
+    assert_eq!(live, vec!["processor-b".to_string()]);
+    txn.commit().await?;
+
+    // So are processors whose heartbeat expired
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    let mut txn = pool.begin().await?;
+    let live =
+        controller::db::fetch_live_processors(&mut txn, TABLE, Duration::from_millis(500)).await?;
+    assert!(live.is_empty());
+    txn.commit().await?;
+
+    Ok(())
+}
+
+/// Acquires the queued objects of the test table one at a time, and returns them
+/// in the order they had been acquired
+async fn acquire_one_by_one(
+    pool: &sqlx::PgPool,
+    priority_aging: Duration,
+) -> eyre::Result<Vec<controller::AcquiredObject>> {
+    let mut acquired = Vec::new();
+    loop {
+        let mut txn = pool.begin().await?;
+        let queued = controller::db::acquire_queued_objects(
+            &mut txn,
+            TestStateControllerIO::DB_QUEUED_OBJECTS_TABLE_NAME,
+            1,
+            "processor",
+            Duration::from_secs(60),
+            priority_aging,
+            None,
+        )
+        .await?;
+        txn.commit().await?;
+        if queued.is_empty() {
+            return Ok(acquired);
+        }
+        acquired.extend(queued);
+    }
+}
+
+#[carbide_macros::sqlx_test]
+async fn test_acquire_prioritized_queued_objects(pool: sqlx::PgPool) -> eyre::Result<()> {
+    create_test_state_controller_tables(&pool).await;
+    const TABLE: &str = TestStateControllerIO::DB_QUEUED_OBJECTS_TABLE_NAME;
+
+    // Every object is queued in its own transaction, so that they get distinct queue times
+    let objects = [
+        ("p0", QueuePriority::Periodic, None),
+        ("p1", QueuePriority::Periodic, None),
+        ("p2", QueuePriority::Periodic, None),
+        ("p3", QueuePriority::Periodic, None),
+        ("t0", QueuePriority::Transition, None),
+        ("a0", QueuePriority::Requested, Some("tenant-a")),
+        ("a1", QueuePriority::Requested, Some("tenant-a")),
+        ("a2", QueuePriority::Requested, Some("tenant-a")),
+        ("b0", QueuePriority::Requested, Some("tenant-b")),
+    ];
+    for (object_id, priority, fairness_key) in objects {
+        let mut txn = pool.begin().await?;
+        controller::db::queue_objects(
+            &mut txn,
+            TABLE,
+            &[object_id.to_string()],
+            priority,
+            fairness_key,
+        )
+        .await?;
+        txn.commit().await?;
+    }
+
+    // Queuing an object again only ever raises its priority, and does not count as enqueuing it
+    let mut txn = pool.begin().await?;
+    let num_enqueued = controller::db::queue_objects(
+        &mut txn,
+        TABLE,
+        &["t0".to_string()],
+        QueuePriority::Periodic,
+        None,
+    )
+    .await?;
+    assert_eq!(num_enqueued, 0);
+    let num_enqueued = controller::db::queue_objects(
+        &mut txn,
+        TABLE,
+        &["p3".to_string()],
+        QueuePriority::Requested,
+        None,
+    )
+    .await?;
+    assert_eq!(num_enqueued, 0);
+    txn.commit().await?;
+
+    // Requested objects come first, and are interleaved across tenants.
+    // p3 is queued as requested object from the time its priority was raised on.
+    let acquired = acquire_one_by_one(&pool, Duration::from_secs(3600)).await?;
+    let order: Vec<&str> = acquired
+        .iter()
+        .map(|acquired| acquired.object.object_id.as_str())
+        .collect();
+    assert_eq!(
+        order,
+        vec!["a0", "b0", "p3", "a1", "a2", "t0", "p0", "p1", "p2"]
+    );
+    assert_eq!(acquired[1].object.fairness_key.as_deref(), Some("tenant-b"));
+    assert_eq!(acquired[2].object.priority, QueuePriority::Requested);
+    assert_eq!(acquired[5].object.priority, QueuePriority::Transition);
+
+    // Objects which have been waiting for long enough overtake higher priority classes
+    sqlx::query(&format!("DELETE FROM {TABLE}"))
+        .execute(&pool)
+        .await?;
+    let mut txn = pool.begin().await?;
+    controller::db::queue_objects(
+        &mut txn,
+        TABLE,
+        &["old".to_string()],
+        QueuePriority::Periodic,
+        None,
+    )
+    .await?;
+    txn.commit().await?;
+    tokio::time::sleep(Duration::from_millis(1500)).await;
+    let mut txn = pool.begin().await?;
+    controller::db::queue_objects(
+        &mut txn,
+        TABLE,
+        &["new".to_string()],
+        QueuePriority::Requested,
+        None,
+    )
+    .await?;
+    txn.commit().await?;
+
+    let acquired = acquire_one_by_one(&pool, Duration::from_millis(500)).await?;
+    let order: Vec<&str> = acquired
+        .iter()
+        .map(|acquired| acquired.object.object_id.as_str())
+        .collect();
+    assert_eq!(order, vec!["old", "new"]);
+    assert!(acquired[0].queue_wait >= Duration::from_secs(1));
+
+    Ok(())
+}
+
 #[derive(Debug, Default)]
 struct TestStateControllerIO {}
 
@@ -477,7 +799,10 @@ async fn create_test_state_controller_tables(pool: &sqlx::PgPool) {
         "CREATE TABLE test_state_controller_queued_objects(
         object_id VARCHAR PRIMARY KEY,
         processed_by TEXT NULL,
-        processing_started_at timestamptz NOT NULL DEFAULT NOW()
+        processing_started_at timestamptz NOT NULL DEFAULT NOW(),
+        priority SMALLINT NOT NULL DEFAULT 0,
+        fairness_key VARCHAR NULL,
+        queued_at timestamptz NOT NULL DEFAULT NOW()
     );",
     )
     .execute(&mut *txn)
@@ -1124,6 +1449,8 @@ async fn test_state_controller_manual_enqueuing(pool: sqlx::PgPool) -> eyre::Res
         vec![QueuedObject {
             object_id: "test-obj-1".to_string(),
             processed_by: None,
+            priority: QueuePriority::Requested,
+            fairness_key: None,
         },]
     );
     txn.commit().await.unwrap();
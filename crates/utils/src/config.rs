@@ -50,3 +50,16 @@ where
 {
     serializer.serialize_str(&format!("{}s", d.as_secs()))
 }
+
+pub fn as_option_std_duration<S>(
+    d: &Option<std::time::Duration>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match d {
+        Some(d) => as_std_duration(d, serializer),
+        None => serializer.serialize_none(),
+    }
+}
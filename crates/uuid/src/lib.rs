@@ -34,6 +34,7 @@ pub mod network_security_group;
 pub mod nvlink;
 pub mod operating_system;
 pub mod power_shelf;
+pub mod prefix_list;
 pub mod rack;
 pub mod switch;
 pub mod typed_uuids;